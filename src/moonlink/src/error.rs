@@ -46,6 +46,18 @@ pub enum Error {
 
     #[error("{0}")]
     OtelExporterBuildError(ErrorStruct),
+
+    #[error("{0}")]
+    WalTruncated(ErrorStruct),
+
+    #[error("{0}")]
+    Encryption(ErrorStruct),
+
+    #[error("{0}")]
+    InvalidTableConfig(ErrorStruct),
+
+    #[error("{0}")]
+    RowNotFound(ErrorStruct),
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -59,6 +71,22 @@ impl Error {
     pub fn delta_generic_error(message: String) -> Self {
         Self::DeltaLakeError(ErrorStruct::new(message, ErrorStatus::Permanent))
     }
+    #[track_caller]
+    pub fn wal_truncated(message: String) -> Self {
+        Self::WalTruncated(ErrorStruct::new(message, ErrorStatus::Permanent))
+    }
+    #[track_caller]
+    pub fn encryption_error(message: String) -> Self {
+        Self::Encryption(ErrorStruct::new(message, ErrorStatus::Permanent))
+    }
+    #[track_caller]
+    pub fn invalid_table_config(message: String) -> Self {
+        Self::InvalidTableConfig(ErrorStruct::new(message, ErrorStatus::Permanent))
+    }
+    #[track_caller]
+    pub fn row_not_found(message: String) -> Self {
+        Self::RowNotFound(ErrorStruct::new(message, ErrorStatus::Permanent))
+    }
 }
 
 impl From<OtelExporterBuildError> for Error {
@@ -225,6 +253,10 @@ impl Error {
             | Error::JoinError(err)
             | Error::PbToMoonlinkRowError(err)
             | Error::OtelExporterBuildError(err)
+            | Error::WalTruncated(err)
+            | Error::Encryption(err)
+            | Error::InvalidTableConfig(err)
+            | Error::RowNotFound(err)
             | Error::Json(err) => err.status,
         }
     }
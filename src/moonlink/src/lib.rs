@@ -1,6 +1,7 @@
 pub mod error;
 pub mod event_sync;
 pub mod lsn_state;
+pub mod memory_accountant;
 pub mod mooncake_table_id;
 mod observability;
 pub mod row;
@@ -13,10 +14,13 @@ mod union_read;
 pub use error::*;
 pub use event_sync::EventSyncSender;
 pub use lsn_state::{CommitState, ReplicationState};
+pub use memory_accountant::{MemoryAccountant, MemoryBudgetConfig};
 pub use mooncake_table_id::MooncakeTableId;
 pub use storage::mooncake_table::batch_id_counter::BatchIdCounter;
 pub use storage::mooncake_table::data_batches::ColumnStoreBuffer;
+pub use storage::mooncake_table::schema_evolution::is_safe_type_promotion;
 pub use storage::parquet_utils::get_default_parquet_properties;
+pub use storage::register_deltalake_storage_handlers;
 pub use storage::storage_utils::create_data_file;
 #[cfg(all(feature = "catalog-glue", feature = "storage-s3"))]
 pub use storage::IcebergGlueCatalogConfig;
@@ -25,18 +29,23 @@ pub use storage::IcebergRestCatalogConfig;
 pub(crate) use storage::NonEvictableHandle;
 pub use storage::{
     AccessorConfig, AwsSecurityConfig, BaseFileSystemAccess, BaseIcebergSnapshotFetcher,
-    CacheTrait, CloudSecurityConfig, DataCompactionConfig, DiskSliceWriterConfig,
-    EventSyncReceiver, FileIndexMergeConfig, FileSystemAccessor, FsChaosConfig, FsRetryConfig,
-    FsThrottleConfig, FsTimeoutConfig, IcebergCatalogConfig, IcebergFileCatalogConfig,
-    IcebergPersistenceConfig, IcebergSnapshotFetcher, IcebergTableConfig, IcebergTableManager,
-    MooncakeTable, MooncakeTableConfig, MoonlinkSecretType, MoonlinkTableConfig,
-    MoonlinkTableSecret, ObjectStorageCache, ObjectStorageCacheConfig, PersistentWalMetadata,
-    SnapshotReadOutput, StorageConfig, TableEventManager, TableManager, TableSnapshotStatus,
-    TableStatusReader, WalConfig, WalManager, WalTransactionState,
+    BaseKeyProvider, CacheTrait, CloudSecurityConfig, ColumnFilter, DataCompactionConfig,
+    DataEncryptionKey, DeltalakeSnapshotFetcher, DeltalakeTableConfig, DiskSliceWriterConfig,
+    EncryptionConfig, EventSyncReceiver, FileIndexMergeConfig, FileSystemAccessor, FsChaosConfig,
+    FsRetryConfig, FsThrottleConfig, FsTimeoutConfig, IcebergCatalogConfig,
+    IcebergFileCatalogConfig, IcebergPersistenceConfig, IcebergSnapshotFetcher, IcebergTableConfig,
+    IcebergTableManager, KeyProviderConfig, LocalFileKeyProvider, MooncakeTable,
+    MooncakeTableConfig, MoonlinkSecretType, MoonlinkTableConfig, MoonlinkTableSecret,
+    ObjectStorageCache, ObjectStorageCacheConfig, ParquetColumnConfig, ParquetCompressionCodec,
+    ParquetWriterConfig, PartitionFieldConfig, PersistentWalMetadata, RowFilter, RowFilterOperator,
+    SnapshotExpirationReport, SnapshotReadOutput, SnapshotRetentionConfig, SortFieldConfig,
+    SourceFilterConfig, StorageConfig, TableEncryption, TableEventManager, TableManager,
+    TableSnapshotStatus, TableStatusReader, WalChange, WalChangeFeed, WalCommittedTransaction,
+    WalConfig, WalManager, WalTransactionState,
 };
 pub use table_handler::TableHandler;
 pub use table_handler_timer::TableHandlerTimer;
-pub use table_notify::TableEvent;
+pub use table_notify::{AlterTableValidationSender, TableEvent};
 pub use union_read::{ReadState, ReadStateFilepathRemap, ReadStateManager};
 
 #[cfg(any(test, feature = "test-utils"))]
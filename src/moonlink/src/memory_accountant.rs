@@ -0,0 +1,380 @@
+/// Memory accountant tracks memory buffered for ingestion across all mooncake tables within one process, including mem slices, mem indices and streaming transaction buffers.
+///
+/// Each mooncake table decides whether to flush by its own config, which doesn't bound total memory usage when there're lots of tables.
+/// - When total usage exceeds soft limit, the largest tables are requested to flush, until enough memory is expected to be released.
+/// - When total usage exceeds hard limit, ingestion sources are expected to reject or pause new writes, until usage drops below hard limit.
+use crate::table_notify::TableEvent;
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{Sender, WeakSender};
+use tokio::sync::watch;
+use tracing::{info, warn};
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct MemoryBudgetConfig {
+    /// Total memory usage in bytes, above which the largest tables are forced to flush.
+    #[serde(default = "MemoryBudgetConfig::default_soft_limit_bytes")]
+    pub soft_limit_bytes: u64,
+    /// Total memory usage in bytes, above which new writes are rejected or paused.
+    #[serde(default = "MemoryBudgetConfig::default_hard_limit_bytes")]
+    pub hard_limit_bytes: u64,
+}
+
+impl MemoryBudgetConfig {
+    /// By default memory usage is unlimited.
+    pub const DEFAULT_SOFT_LIMIT_BYTES: u64 = u64::MAX;
+    pub const DEFAULT_HARD_LIMIT_BYTES: u64 = u64::MAX;
+
+    pub fn default_soft_limit_bytes() -> u64 {
+        Self::DEFAULT_SOFT_LIMIT_BYTES
+    }
+    pub fn default_hard_limit_bytes() -> u64 {
+        Self::DEFAULT_HARD_LIMIT_BYTES
+    }
+
+    /// Return whether the config is valid, soft limit shouldn't exceed hard limit.
+    pub fn is_valid(&self) -> bool {
+        self.soft_limit_bytes <= self.hard_limit_bytes
+    }
+}
+
+impl Default for MemoryBudgetConfig {
+    fn default() -> Self {
+        Self {
+            soft_limit_bytes: Self::DEFAULT_SOFT_LIMIT_BYTES,
+            hard_limit_bytes: Self::DEFAULT_HARD_LIMIT_BYTES,
+        }
+    }
+}
+
+struct TableMemoryEntry {
+    /// Mooncake table id, used to report usage per table.
+    mooncake_table_id: String,
+    /// Latest reported memory usage in bytes.
+    memory_size: u64,
+    /// Weak sender to request flush, which doesn't prevent table handler from shutting down.
+    event_sender: WeakSender<TableEvent>,
+    /// Whether there's a flush request not handled by the table yet.
+    flush_requested: bool,
+}
+
+struct MemoryAccountantState {
+    /// Maps from table id to its memory entry.
+    tables: HashMap<u32, TableMemoryEntry>,
+    /// Sum of memory usage for all tables.
+    total_memory_size: u64,
+}
+
+#[derive(Clone)]
+pub struct MemoryAccountant {
+    config: MemoryBudgetConfig,
+    state: Arc<Mutex<MemoryAccountantState>>,
+    /// Whether total memory usage exceeds hard limit.
+    over_hard_limit_tx: Arc<watch::Sender<bool>>,
+}
+
+impl std::fmt::Debug for MemoryAccountant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MemoryAccountant")
+            .field("config", &self.config)
+            .field("total_memory_size", &self.get_total_memory_size())
+            .finish()
+    }
+}
+
+impl Default for MemoryAccountant {
+    fn default() -> Self {
+        Self::new(MemoryBudgetConfig::default())
+    }
+}
+
+impl MemoryAccountant {
+    pub fn new(config: MemoryBudgetConfig) -> Self {
+        assert!(config.is_valid(), "Invalid memory budget config {config:?}");
+        let (over_hard_limit_tx, _) = watch::channel(false);
+        Self {
+            config,
+            state: Arc::new(Mutex::new(MemoryAccountantState {
+                tables: HashMap::new(),
+                total_memory_size: 0,
+            })),
+            over_hard_limit_tx: Arc::new(over_hard_limit_tx),
+        }
+    }
+
+    pub fn get_config(&self) -> &MemoryBudgetConfig {
+        &self.config
+    }
+
+    /// Register a table, whose memory usage is reported via the returned tracker.
+    pub(crate) fn register_table(
+        &self,
+        table_id: u32,
+        mooncake_table_id: String,
+        event_sender: &Sender<TableEvent>,
+    ) -> TableMemoryTracker {
+        let mut guard = self.state.lock().unwrap();
+        let old_entry = guard.tables.insert(
+            table_id,
+            TableMemoryEntry {
+                mooncake_table_id,
+                memory_size: 0,
+                event_sender: event_sender.downgrade(),
+                flush_requested: false,
+            },
+        );
+        assert!(old_entry.is_none(), "Table {table_id} already registered");
+        TableMemoryTracker {
+            accountant: self.clone(),
+            table_id,
+            last_reported_memory_size: 0,
+        }
+    }
+
+    /// Get total memory usage in bytes for all tables.
+    pub fn get_total_memory_size(&self) -> u64 {
+        self.state.lock().unwrap().total_memory_size
+    }
+
+    /// Get memory usage in bytes for the given table, return [`None`] if the table is not registered.
+    pub fn get_table_memory_size(&self, mooncake_table_id: &str) -> Option<u64> {
+        let guard = self.state.lock().unwrap();
+        guard
+            .tables
+            .values()
+            .filter(|entry| entry.mooncake_table_id == mooncake_table_id)
+            .map(|entry| entry.memory_size)
+            .reduce(|a, b| a + b)
+    }
+
+    /// Return whether total memory usage exceeds hard limit, when new writes should be rejected.
+    pub fn is_over_hard_limit(&self) -> bool {
+        *self.over_hard_limit_tx.borrow()
+    }
+
+    /// Subscribe hard limit state changes, the value indicates whether total memory usage exceeds hard limit.
+    pub fn subscribe_hard_limit(&self) -> watch::Receiver<bool> {
+        self.over_hard_limit_tx.subscribe()
+    }
+
+    /// Block until total memory usage drops below hard limit.
+    pub async fn wait_for_under_hard_limit(&self) {
+        let mut over_hard_limit_rx = self.subscribe_hard_limit();
+        // Sender is owned by [`self`], so it never gets closed while waiting.
+        let _ = over_hard_limit_rx.wait_for(|over| !*over).await;
+    }
+
+    fn unregister_table(&self, table_id: u32) {
+        let total_memory_size = {
+            let mut guard = self.state.lock().unwrap();
+            let entry = guard.tables.remove(&table_id).unwrap();
+            guard.total_memory_size -= entry.memory_size;
+            guard.total_memory_size
+        };
+        self.update_hard_limit_state(total_memory_size);
+    }
+
+    fn update_table_memory_size(&self, table_id: u32, memory_size: u64) {
+        let total_memory_size = {
+            let mut guard = self.state.lock().unwrap();
+            let entry = guard.tables.get_mut(&table_id).unwrap();
+            let old_memory_size = std::mem::replace(&mut entry.memory_size, memory_size);
+            guard.total_memory_size = guard.total_memory_size - old_memory_size + memory_size;
+            if guard.total_memory_size > self.config.soft_limit_bytes {
+                self.request_flush_for_largest_tables(&mut guard);
+            }
+            guard.total_memory_size
+        };
+        self.update_hard_limit_state(total_memory_size);
+    }
+
+    fn complete_flush_request(&self, table_id: u32) {
+        let mut guard = self.state.lock().unwrap();
+        guard.tables.get_mut(&table_id).unwrap().flush_requested = false;
+    }
+
+    /// Request the largest tables to flush, until memory to release brings total usage under soft limit.
+    /// Tables with flush requests on the fly are considered as released.
+    fn request_flush_for_largest_tables(&self, state: &mut MemoryAccountantState) {
+        let mut memory_to_release = state.total_memory_size - self.config.soft_limit_bytes;
+        let mut candidates = vec![];
+        for (table_id, entry) in state.tables.iter() {
+            if entry.flush_requested {
+                memory_to_release = memory_to_release.saturating_sub(entry.memory_size);
+            } else if entry.memory_size > 0 {
+                candidates.push((entry.memory_size, *table_id));
+            }
+        }
+        candidates.sort_unstable_by(|a, b| b.cmp(a));
+
+        for (memory_size, table_id) in candidates {
+            if memory_to_release == 0 {
+                break;
+            }
+            let entry = state.tables.get_mut(&table_id).unwrap();
+            let Some(event_sender) = entry.event_sender.upgrade() else {
+                continue;
+            };
+            // Never block on a full event queue, the table will be requested again at next report.
+            match event_sender.try_send(TableEvent::MemoryPressureFlush) {
+                Ok(()) => {
+                    info!(
+                        mooncake_table_id = %entry.mooncake_table_id,
+                        memory_size,
+                        total_memory_size = state.total_memory_size,
+                        "request flush on memory pressure"
+                    );
+                    entry.flush_requested = true;
+                    memory_to_release = memory_to_release.saturating_sub(memory_size);
+                }
+                Err(e) => {
+                    warn!(
+                        mooncake_table_id = %entry.mooncake_table_id,
+                        error = %e,
+                        "failed to request flush on memory pressure"
+                    );
+                }
+            }
+        }
+    }
+
+    fn update_hard_limit_state(&self, total_memory_size: u64) {
+        let over_hard_limit = total_memory_size > self.config.hard_limit_bytes;
+        self.over_hard_limit_tx.send_if_modified(|cur| {
+            if *cur == over_hard_limit {
+                return false;
+            }
+            if over_hard_limit {
+                warn!(
+                    total_memory_size,
+                    hard_limit_bytes = self.config.hard_limit_bytes,
+                    "memory usage exceeds hard limit, ingestion is paused"
+                );
+            } else {
+                info!(
+                    total_memory_size,
+                    hard_limit_bytes = self.config.hard_limit_bytes,
+                    "memory usage drops below hard limit, ingestion is resumed"
+                );
+            }
+            *cur = over_hard_limit;
+            true
+        });
+    }
+}
+
+/// Per-table handle to report memory usage to accountant, which unregisters the table on drop.
+pub(crate) struct TableMemoryTracker {
+    accountant: MemoryAccountant,
+    table_id: u32,
+    last_reported_memory_size: u64,
+}
+
+impl TableMemoryTracker {
+    /// Report latest memory usage for the table.
+    pub(crate) fn update(&mut self, memory_size: usize) {
+        let memory_size = memory_size as u64;
+        if memory_size == self.last_reported_memory_size {
+            return;
+        }
+        self.last_reported_memory_size = memory_size;
+        self.accountant
+            .update_table_memory_size(self.table_id, memory_size);
+    }
+
+    /// Mark the flush request on memory pressure handled.
+    pub(crate) fn complete_flush_request(&self) {
+        self.accountant.complete_flush_request(self.table_id);
+    }
+}
+
+impl Drop for TableMemoryTracker {
+    fn drop(&mut self) {
+        self.accountant.unregister_table(self.table_id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_request_flush_for_largest_tables() {
+        let accountant = MemoryAccountant::new(MemoryBudgetConfig {
+            soft_limit_bytes: 100,
+            hard_limit_bytes: 200,
+        });
+        let (small_table_tx, mut small_table_rx) = mpsc::channel(10);
+        let (large_table_tx, mut large_table_rx) = mpsc::channel(10);
+        let mut small_table_tracker =
+            accountant.register_table(0, "small_table".to_string(), &small_table_tx);
+        let mut large_table_tracker =
+            accountant.register_table(1, "large_table".to_string(), &large_table_tx);
+
+        // Under soft limit, no flush is requested.
+        small_table_tracker.update(30);
+        large_table_tracker.update(60);
+        assert_eq!(accountant.get_total_memory_size(), 90);
+        assert!(small_table_rx.try_recv().is_err());
+        assert!(large_table_rx.try_recv().is_err());
+
+        // Exceed soft limit, only the largest table is requested to flush.
+        large_table_tracker.update(80);
+        assert!(matches!(
+            large_table_rx.try_recv().unwrap(),
+            TableEvent::MemoryPressureFlush
+        ));
+        assert!(small_table_rx.try_recv().is_err());
+        assert!(!accountant.is_over_hard_limit());
+
+        // Flush request on the fly is not duplicated.
+        small_table_tracker.update(40);
+        assert!(large_table_rx.try_recv().is_err());
+        assert!(small_table_rx.try_recv().is_err());
+
+        // Usage per table is reported.
+        assert_eq!(accountant.get_table_memory_size("small_table"), Some(40));
+        assert_eq!(accountant.get_table_memory_size("large_table"), Some(80));
+        assert_eq!(accountant.get_table_memory_size("unknown_table"), None);
+
+        // Flush completes and releases memory.
+        large_table_tracker.complete_flush_request();
+        large_table_tracker.update(0);
+        assert_eq!(accountant.get_total_memory_size(), 40);
+
+        // Unregister on drop.
+        drop(small_table_tracker);
+        assert_eq!(accountant.get_total_memory_size(), 0);
+        assert_eq!(accountant.get_table_memory_size("small_table"), None);
+    }
+
+    #[tokio::test]
+    async fn test_hard_limit() {
+        let accountant = MemoryAccountant::new(MemoryBudgetConfig {
+            soft_limit_bytes: 100,
+            hard_limit_bytes: 200,
+        });
+        let (event_tx, _event_rx) = mpsc::channel(10);
+        let mut tracker = accountant.register_table(0, "table".to_string(), &event_tx);
+        let mut over_hard_limit_rx = accountant.subscribe_hard_limit();
+
+        tracker.update(250);
+        assert!(accountant.is_over_hard_limit());
+        assert!(over_hard_limit_rx.has_changed().unwrap());
+        assert!(*over_hard_limit_rx.borrow_and_update());
+
+        // Waiter gets notified once usage drops below hard limit.
+        let waiter_accountant = accountant.clone();
+        let waiter = tokio::spawn(async move {
+            waiter_accountant.wait_for_under_hard_limit().await;
+        });
+        tracker.update(150);
+        waiter.await.unwrap();
+        assert!(!accountant.is_over_hard_limit());
+    }
+}
@@ -4,6 +4,7 @@ mod moonlink_row;
 mod moonlink_type;
 mod proto_converter;
 
+pub use arrow_converter::moonlink_rows_to_record_batch;
 pub(crate) use column_array_builder::ColumnArrayBuilder;
 pub use moonlink_row::{IdentityProp, MoonlinkRow};
 pub use moonlink_type::RowValue;
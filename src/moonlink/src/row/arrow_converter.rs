@@ -1,10 +1,13 @@
 use super::moonlink_type::RowValue;
-use crate::row::MoonlinkRow;
+use crate::error::Result;
+use crate::row::{ColumnArrayBuilder, MoonlinkRow};
 use arrow::array::Array;
+use arrow::error::ArrowError;
 use arrow_array::{
-    BinaryArray, BooleanArray, Date32Array, FixedSizeBinaryArray, Float32Array, Float64Array,
-    Int16Array, Int32Array, Int64Array, RecordBatch, StringArray,
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, FixedSizeBinaryArray, Float32Array,
+    Float64Array, Int16Array, Int32Array, Int64Array, RecordBatch, StringArray,
 };
+use arrow_schema::SchemaRef;
 
 /// Convert arrow record batch to moonlink rows.
 pub(super) fn record_batch_to_moonlink_row(batch: &RecordBatch) -> Vec<MoonlinkRow> {
@@ -24,6 +27,38 @@ pub(super) fn record_batch_to_moonlink_row(batch: &RecordBatch) -> Vec<MoonlinkR
     rows
 }
 
+/// Convert moonlink rows to arrow record batch with the given schema.
+/// Rows are expected to be laid out in schema order; null values are only accepted for nullable fields.
+pub fn moonlink_rows_to_record_batch(
+    rows: &[MoonlinkRow],
+    schema: SchemaRef,
+) -> Result<RecordBatch> {
+    let mut builders: Vec<ColumnArrayBuilder> = schema
+        .fields()
+        .iter()
+        .map(|field| ColumnArrayBuilder::new(field.data_type(), rows.len()))
+        .collect();
+    for cur_row in rows.iter() {
+        if cur_row.values.len() != builders.len() {
+            return Err(ArrowError::InvalidArgumentError(format!(
+                "Row has {} values, but schema has {} fields",
+                cur_row.values.len(),
+                builders.len()
+            ))
+            .into());
+        }
+        for (builder, value) in builders.iter_mut().zip(cur_row.values.iter()) {
+            builder.append_value(value)?;
+        }
+    }
+    let columns: Vec<ArrayRef> = builders
+        .into_iter()
+        .zip(schema.fields())
+        .map(|(builder, field)| builder.finish(field.data_type()))
+        .collect();
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
 /// From arrow value to moonlink row value.
 ///
 /// TODO(hjiang): Add composite and array type support.
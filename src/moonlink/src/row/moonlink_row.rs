@@ -1,10 +1,12 @@
 use super::moonlink_type::RowValue;
 use crate::row::arrow_converter;
+use crate::storage::mooncake_table::schema_evolution;
 use ahash::AHasher;
 use arrow::array::Array;
-use arrow::datatypes::Field;
+use arrow::datatypes::{Field, Schema};
 use arrow::record_batch::RecordBatch;
 use more_asserts as ma;
+use parquet::arrow::arrow_reader::ArrowReaderOptions;
 use parquet::arrow::async_reader::ParquetRecordBatchStreamBuilder;
 use parquet::arrow::ProjectionMask;
 use serde::{Deserialize, Serialize};
@@ -21,6 +23,16 @@ impl MoonlinkRow {
         Self { values }
     }
 
+    /// Get estimated memory size in bytes, including heap allocations.
+    pub fn get_estimated_memory_size(&self) -> usize {
+        std::mem::size_of::<MoonlinkRow>()
+            + self
+                .values
+                .iter()
+                .map(|value| value.get_estimated_memory_size())
+                .sum::<usize>()
+    }
+
     /// Convert an arrow RecordBatch into moonlink rows.
     pub fn from_record_batch(batch: &RecordBatch) -> Vec<MoonlinkRow> {
         arrow_converter::record_batch_to_moonlink_row(batch)
@@ -211,15 +223,19 @@ impl MoonlinkRow {
         self.equals_record_batch_at_offset_impl(&indices, offset)
     }
 
-    pub async fn equals_parquet_at_offset(
-        &self,
+    /// Read the `offset`-th row of the given parquet file, with only the projected columns if assigned.
+    /// Projected columns are matched by field id, since columns could have been dropped or renamed after the data file was written.
+    async fn read_parquet_at_offset(
         file_name: &str,
         offset: usize,
-        identity: &IdentityProp,
-    ) -> bool {
-        assert!(self.is_extracted_identity_row(identity));
+        projected_fields: Option<Vec<&Field>>,
+        reader_options: ArrowReaderOptions,
+    ) -> RecordBatch {
         let file = tokio::fs::File::open(file_name).await.unwrap();
-        let stream_builder = ParquetRecordBatchStreamBuilder::new(file).await.unwrap();
+        let stream_builder =
+            ParquetRecordBatchStreamBuilder::new_with_options(file, reader_options)
+                .await
+                .unwrap();
         let row_groups = stream_builder.metadata().row_groups();
         let mut target_row_group = 0;
         let mut row_count: usize = 0;
@@ -230,23 +246,87 @@ impl MoonlinkRow {
             row_count += row_group.num_rows() as usize;
             target_row_group += 1;
         }
-        let proj_mask = ProjectionMask::roots(
-            stream_builder.metadata().file_metadata().schema_descr(),
-            identity.get_key_indices(self.values.len()),
-        );
+        let proj_mask = match projected_fields {
+            Some(fields) => {
+                let file_schema = stream_builder.schema();
+                let indices = fields
+                    .iter()
+                    .map(|field| {
+                        let field_id = schema_evolution::get_field_id(field);
+                        file_schema
+                            .fields()
+                            .iter()
+                            .position(|file_field| match field_id {
+                                Some(_) => schema_evolution::get_field_id(file_field) == field_id,
+                                None => file_field.name() == field.name(),
+                            })
+                            .unwrap_or_else(|| {
+                                panic!(
+                                    "column {} doesn't exist in data file {file_name}",
+                                    field.name()
+                                )
+                            })
+                    })
+                    .collect::<Vec<_>>();
+                ProjectionMask::roots(
+                    stream_builder.metadata().file_metadata().schema_descr(),
+                    indices,
+                )
+            }
+            None => ProjectionMask::all(),
+        };
         let mut reader = stream_builder
             .with_row_groups(vec![target_row_group])
             .with_offset(offset - row_count)
             .with_limit(1)
             .with_batch_size(1)
-            .with_projection(proj_mask.clone())
+            .with_projection(proj_mask)
             .build()
             .unwrap();
         let mut batch_reader = reader.next_row_group().await.unwrap().unwrap();
-        let batch = batch_reader.next().unwrap().unwrap();
+        batch_reader.next().unwrap().unwrap()
+    }
+
+    /// Check whether the `offset`-th row of the given parquet file matches the current identity row.
+    ///
+    /// # Arguments
+    ///
+    /// * schema: current table schema, whose key columns are read from the data file.
+    pub async fn equals_parquet_at_offset(
+        &self,
+        file_name: &str,
+        offset: usize,
+        identity: &IdentityProp,
+        schema: &Schema,
+        reader_options: ArrowReaderOptions,
+    ) -> bool {
+        assert!(self.is_extracted_identity_row(identity));
+        let key_fields = identity
+            .get_key_indices(self.values.len())
+            .into_iter()
+            .map(|idx| schema.field(idx))
+            .collect::<Vec<_>>();
+        let batch =
+            Self::read_parquet_at_offset(file_name, offset, Some(key_fields), reader_options).await;
         self.equals_record_batch_at_offset_impl(&batch, 0)
     }
 
+    /// Read the `offset`-th row of the given parquet file, with all columns.
+    pub async fn from_parquet_at_offset(
+        file_name: &str,
+        offset: usize,
+        reader_options: ArrowReaderOptions,
+    ) -> MoonlinkRow {
+        let batch = Self::read_parquet_at_offset(
+            file_name,
+            offset,
+            /*projection=*/ None,
+            reader_options,
+        )
+        .await;
+        Self::from_record_batch(&batch).pop().unwrap()
+    }
+
     pub fn equals_moonlink_row(&self, other: &Self, identity: &IdentityProp) -> bool {
         match identity {
             IdentityProp::Keys(keys) => {
@@ -594,7 +674,7 @@ mod tests {
             .set_compression(parquet::basic::Compression::UNCOMPRESSED)
             .build();
 
-        let mut writer = AsyncArrowWriter::try_new(file, schema, Some(props)).unwrap();
+        let mut writer = AsyncArrowWriter::try_new(file, schema.clone(), Some(props)).unwrap();
         writer.write(&record_batch).await.unwrap();
         writer.close().await.unwrap();
 
@@ -606,7 +686,9 @@ mod tests {
             row.equals_parquet_at_offset(
                 file_path.to_str().unwrap(),
                 /*offset=*/ 1,
-                &IdentityProp::FullRow
+                &IdentityProp::FullRow,
+                &schema,
+                ArrowReaderOptions::new()
             )
             .await
         );
@@ -614,7 +696,9 @@ mod tests {
             !row.equals_parquet_at_offset(
                 file_path.to_str().unwrap(),
                 /*offset=*/ 0,
-                &IdentityProp::FullRow
+                &IdentityProp::FullRow,
+                &schema,
+                ArrowReaderOptions::new()
             )
             .await
         );
@@ -628,7 +712,9 @@ mod tests {
                 .equals_parquet_at_offset(
                     file_path.to_str().unwrap(),
                     /*offset=*/ 1,
-                    &IdentityProp::Keys(vec![1])
+                    &IdentityProp::Keys(vec![1]),
+                    &schema,
+                    ArrowReaderOptions::new()
                 )
                 .await
         );
@@ -637,7 +723,9 @@ mod tests {
                 .equals_parquet_at_offset(
                     file_path.to_str().unwrap(),
                     /*offset=*/ 0,
-                    &IdentityProp::Keys(vec![1])
+                    &IdentityProp::Keys(vec![1]),
+                    &schema,
+                    ArrowReaderOptions::new()
                 )
                 .await
         );
@@ -33,6 +33,19 @@ impl RowValue {
             }
         }
     }
+
+    /// Get estimated memory size in bytes, including heap allocations.
+    pub fn get_estimated_memory_size(&self) -> usize {
+        let heap_size = match self {
+            RowValue::ByteArray(bytes) => bytes.capacity(),
+            RowValue::Array(values) | RowValue::Struct(values) => values
+                .iter()
+                .map(|value| value.get_estimated_memory_size())
+                .sum(),
+            _ => 0,
+        };
+        std::mem::size_of::<RowValue>() + heap_size
+    }
 }
 
 impl Hash for RowValue {
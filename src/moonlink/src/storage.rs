@@ -1,14 +1,17 @@
 pub(crate) mod async_bitwriter;
 pub(crate) mod cache;
 pub(crate) mod compaction;
+pub(crate) mod encryption;
 pub(crate) mod filesystem;
 pub(crate) mod index;
 pub(crate) mod io_utils;
 pub(crate) mod mooncake_table;
 pub mod mooncake_table_config;
 pub(crate) mod parquet_utils;
+pub(crate) mod partition_utils;
 pub(crate) mod path_utils;
 pub(crate) mod snapshot_options;
+pub(crate) mod sort_utils;
 pub(crate) mod storage_utils;
 pub(crate) mod table;
 pub(super) mod timer;
@@ -20,6 +23,10 @@ pub use cache::object_storage::cache_config::ObjectStorageCacheConfig;
 pub(crate) use cache::object_storage::cache_handle::NonEvictableHandle;
 pub use cache::object_storage::object_storage_cache::ObjectStorageCache;
 pub use compaction::compaction_config::DataCompactionConfig;
+pub use encryption::data_key::DataEncryptionKey;
+pub use encryption::encryption_config::{EncryptionConfig, KeyProviderConfig};
+pub use encryption::key_provider::{BaseKeyProvider, LocalFileKeyProvider};
+pub use encryption::table_encryption::TableEncryption;
 pub use filesystem::accessor::filesystem_accessor::FileSystemAccessor;
 pub use filesystem::accessor_config::{
     AccessorConfig, ChaosConfig as FsChaosConfig, RetryConfig as FsRetryConfig,
@@ -40,7 +47,17 @@ pub(crate) use mooncake_table::SnapshotTableState;
 pub use mooncake_table_config::DiskSliceWriterConfig;
 pub use mooncake_table_config::IcebergPersistenceConfig;
 pub use mooncake_table_config::MooncakeTableConfig;
-pub use table::common::table_manager::TableManager;
+pub use mooncake_table_config::PartitionFieldConfig;
+pub use mooncake_table_config::SnapshotRetentionConfig;
+pub use mooncake_table_config::SortFieldConfig;
+pub use mooncake_table_config::{ColumnFilter, RowFilter, RowFilterOperator, SourceFilterConfig};
+pub use mooncake_table_config::{
+    ParquetColumnConfig, ParquetCompressionCodec, ParquetWriterConfig,
+};
+pub use table::common::table_manager::{SnapshotExpirationReport, TableManager};
+pub use table::deltalake::deltalake_snapshot_fetcher::DeltalakeSnapshotFetcher;
+pub use table::deltalake::deltalake_table_config::DeltalakeTableConfig;
+pub use table::deltalake::utils::register_deltalake_storage_handlers;
 pub use table::iceberg::base_iceberg_snapshot_fetcher::BaseIcebergSnapshotFetcher;
 pub use table::iceberg::cloud_security_config::{AwsSecurityConfig, CloudSecurityConfig};
 pub use table::iceberg::iceberg_snapshot_fetcher::IcebergSnapshotFetcher;
@@ -51,6 +68,7 @@ pub use table::iceberg::iceberg_table_config::GlueCatalogConfig as IcebergGlueCa
 pub use table::iceberg::iceberg_table_config::RestCatalogConfig as IcebergRestCatalogConfig;
 pub use table::iceberg::iceberg_table_config::{IcebergCatalogConfig, IcebergTableConfig};
 pub use table::iceberg::iceberg_table_manager::IcebergTableManager;
+pub use wal::change_feed::{WalChange, WalChangeFeed, WalCommittedTransaction};
 pub use wal::{PersistentWalMetadata, WalConfig, WalManager, WalTransactionState};
 
 pub use filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
@@ -191,14 +191,16 @@ impl<W: AsyncWrite + Unpin + Send + Sync, E: Endianness> BitWriter<W, E> {
     /// Close the current bit writer, several steps involved:
     /// - Pads the current bit queue with zeros until aligned to the next byte boundary.
     /// - Flush both active buffer and inactive one.
-    pub async fn close(mut self) -> io::Result<()> {
+    ///
+    /// Return the internal writer.
+    pub async fn close(mut self) -> io::Result<W> {
         if self.byte_align() {
             // If active buffer full, the flush here clear the active buffer and do a switch with inactive one.
             self.flush().await?;
         }
         // Flush whatever left in the active buffer.
         self.flush().await?;
-        Ok(())
+        Ok(self.writer)
     }
 }
 
@@ -23,6 +23,12 @@ pub struct DataCompactionConfig {
     /// - 100 means only compact when all rows deleted.
     #[serde(default = "DataCompactionConfig::default_data_file_deletion_percentage")]
     pub data_file_deletion_percentage: u32,
+
+    /// Number of other data files a data file's sort key range overlaps with, to rewrite it in a re-cluster compaction regardless of its size.
+    /// Only takes effect for tables with sort order.
+    /// - 0 means re-cluster is disabled.
+    #[serde(default = "DataCompactionConfig::default_recluster_overlap_threshold")]
+    pub recluster_overlap_threshold: u32,
 }
 
 impl DataCompactionConfig {
@@ -34,6 +40,8 @@ impl DataCompactionConfig {
     pub const DEFAULT_DATA_FILE_FINAL_SIZE: u64 = u64::MAX;
     #[cfg(test)]
     pub const DEFAULT_DATA_FILE_DELETION_PERCENTAGE: u32 = 0;
+    #[cfg(test)]
+    pub const DEFAULT_RECLUSTER_OVERLAP_THRESHOLD: u32 = 0;
 
     #[cfg(all(not(test), debug_assertions))]
     pub const DEFAULT_MIN_DATA_FILE_TO_COMPACT: u32 = 4;
@@ -43,6 +51,8 @@ impl DataCompactionConfig {
     pub const DEFAULT_DATA_FILE_FINAL_SIZE: u64 = 1 << 10; // 1KiB
     #[cfg(all(not(test), debug_assertions))]
     pub const DEFAULT_DATA_FILE_DELETION_PERCENTAGE: u32 = 50;
+    #[cfg(all(not(test), debug_assertions))]
+    pub const DEFAULT_RECLUSTER_OVERLAP_THRESHOLD: u32 = 4;
 
     #[cfg(all(not(test), not(debug_assertions)))]
     pub const DEFAULT_MIN_DATA_FILE_TO_COMPACT: u32 = 16;
@@ -52,6 +62,8 @@ impl DataCompactionConfig {
     pub const DEFAULT_DATA_FILE_FINAL_SIZE: u64 = 1 << 29; // 512MiB
     #[cfg(all(not(test), not(debug_assertions)))]
     pub const DEFAULT_DATA_FILE_DELETION_PERCENTAGE: u32 = 50;
+    #[cfg(all(not(test), not(debug_assertions)))]
+    pub const DEFAULT_RECLUSTER_OVERLAP_THRESHOLD: u32 = 8;
 
     pub fn default_min_data_file_to_compact() -> u32 {
         Self::DEFAULT_MIN_DATA_FILE_TO_COMPACT
@@ -65,6 +77,9 @@ impl DataCompactionConfig {
     pub fn default_data_file_deletion_percentage() -> u32 {
         Self::DEFAULT_DATA_FILE_DELETION_PERCENTAGE
     }
+    pub fn default_recluster_overlap_threshold() -> u32 {
+        Self::DEFAULT_RECLUSTER_OVERLAP_THRESHOLD
+    }
 
     pub fn validate(&self) {
         ma::assert_le!(self.min_data_file_to_compact, self.max_data_file_to_compact);
@@ -80,6 +95,7 @@ impl Default for DataCompactionConfig {
             max_data_file_to_compact: Self::DEFAULT_MAX_DATA_FILE_TO_COMPACT,
             data_file_final_size: Self::DEFAULT_DATA_FILE_FINAL_SIZE,
             data_file_deletion_percentage: Self::DEFAULT_DATA_FILE_DELETION_PERCENTAGE,
+            recluster_overlap_threshold: Self::DEFAULT_RECLUSTER_OVERLAP_THRESHOLD,
         }
     }
 }
@@ -97,6 +113,7 @@ impl DataCompactionConfig {
             max_data_file_to_compact: u32::MAX,
             data_file_final_size: u64::MAX,
             data_file_deletion_percentage: 0,
+            recluster_overlap_threshold: 0,
         }
     }
 }
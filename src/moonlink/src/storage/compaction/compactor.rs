@@ -3,23 +3,32 @@
 
 use std::collections::{HashMap, HashSet};
 
+use arrow::compute::concat_batches;
 use arrow_array::RecordBatch;
 use arrow_schema::SchemaRef;
 use futures::TryStreamExt;
 use more_asserts as ma;
 use parquet::arrow::async_reader::ParquetRecordBatchStreamBuilder;
 use parquet::arrow::AsyncArrowWriter;
+use parquet::format::SortingColumn;
 
 use crate::storage::cache::object_storage::base_cache::InlineEvictedFiles;
 use crate::storage::compaction::table_compaction::{
     CompactedDataEntry, DataCompactionPayload, DataCompactionResult, RemappedRecordLocation,
     SingleFileToCompact,
 };
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::index::persisted_bucket_hash_map::GlobalIndexBuilder;
 use crate::storage::index::FileIndex;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
+use crate::storage::mooncake_table_config::{
+    ParquetWriterConfig, PartitionFieldConfig, SortFieldConfig,
+};
+use crate::storage::partition_utils::{PartitionKey, PartitionKeyGenerator, PartitionSourceValues};
+use crate::storage::sort_utils::{SortKeyGenerator, SortKeyRange, SORTED_WRITE_BATCH_SIZE};
 use crate::storage::storage_utils::{
-    get_random_file_name_in_dir, get_unique_file_id_for_flush, MooncakeDataFileRef,
+    create_data_file_with_partition, get_random_file_name_in_dir, get_unique_file_id_for_flush,
+    MooncakeDataFileRef,
 };
 use crate::storage::storage_utils::{FileId, RecordLocation};
 use crate::storage::table::iceberg::puffin_utils;
@@ -35,6 +44,33 @@ pub(crate) struct CompactionFileParams {
     pub(crate) table_auto_incr_ids: std::ops::Range<u32>,
     /// Final size for compacted data files.
     pub(crate) data_file_final_size: u64,
+    /// Partition spec, compacted data files only contain rows within one partition.
+    pub(crate) partition_spec: Vec<PartitionFieldConfig>,
+    /// Parquet writer options for compacted data files.
+    pub(crate) parquet_writer_config: ParquetWriterConfig,
+    /// Data key to encrypt compacted data files and file indices with, if the table is encrypted.
+    pub(crate) encryption_key: Option<DataEncryptionKey>,
+    /// Sort order, rows within each compacted data file are sorted by it.
+    pub(crate) sort_order: Vec<SortFieldConfig>,
+}
+
+/// Rows to compact for one partition, which are buffered to sort before write.
+#[derive(Default)]
+struct PartitionRowsToSort {
+    /// Record batches with deletion vector applied.
+    record_batches: Vec<RecordBatch>,
+    /// Record location before compaction for each row.
+    old_record_locations: Vec<RecordLocation>,
+}
+
+/// Ongoing compacted data file for one partition.
+struct CompactedFileWriter {
+    /// Async arrow writer for the new compacted data file.
+    arrow_writer: AsyncArrowWriter<tokio::fs::File>,
+    /// New compacted data file.
+    new_data_file: MooncakeDataFileRef,
+    /// Current row number for the new compacted data file.
+    row_num: usize,
 }
 
 pub(crate) struct CompactionBuilder {
@@ -46,14 +82,17 @@ pub(crate) struct CompactionBuilder {
     file_params: CompactionFileParams,
     /// New data files after compaction.
     new_data_files: Vec<(MooncakeDataFileRef, CompactedDataEntry)>,
+    /// Used to compute partition key for data files to compact, `None` for unpartitioned tables.
+    partition_key_generator: Option<PartitionKeyGenerator>,
+    /// Used to sort rows to compact, `None` for unsorted tables.
+    sort_key_generator: Option<SortKeyGenerator>,
     /// ===== Current ongoing compaction operation =====
     ///
-    /// Current active async arrow writer, which is initialized in a lazy style.
-    cur_arrow_writer: Option<AsyncArrowWriter<tokio::fs::File>>,
-    /// Current new data file.
-    cur_new_data_file: Option<MooncakeDataFileRef>,
-    /// Current row number for the new compaction file.
-    cur_row_num: usize,
+    /// Current active writers for each partition, which are initialized in a lazy style.
+    /// Unpartitioned tables use `None` as the key.
+    cur_writers: HashMap<Option<PartitionKey>, CompactedFileWriter>,
+    /// Rows to sort for each partition, only used for tables with sort order.
+    rows_to_sort: HashMap<Option<PartitionKey>, PartitionRowsToSort>,
     /// Current compacted file count, including new compacted data files and index block files.
     compacted_file_count: u64,
 }
@@ -90,10 +129,11 @@ impl CompactionBuilder {
             schema,
             file_params,
             new_data_files: Vec::new(),
+            partition_key_generator: None,
+            sort_key_generator: None,
             // Current ongoing compaction operation
-            cur_arrow_writer: None,
-            cur_new_data_file: None,
-            cur_row_num: 0,
+            cur_writers: HashMap::new(),
+            rows_to_sort: HashMap::new(),
             compacted_file_count: 0,
         }
     }
@@ -121,56 +161,99 @@ impl CompactionBuilder {
         compacted_file_idx as usize
     }
 
-    /// Util function to create a new data file.
-    fn create_new_data_file(&self) -> MooncakeDataFileRef {
-        assert!(self.cur_new_data_file.is_none());
+    /// Util function to create a new data file, file ids are assigned in the order of creation.
+    fn create_new_data_file(
+        &mut self,
+        partition_values: Option<PartitionSourceValues>,
+    ) -> MooncakeDataFileRef {
         let next_file_id = self.get_next_file_id();
+        self.compacted_file_count += 1;
         let file_path = get_random_file_name_in_dir(self.file_params.dir_path.as_path());
-        create_data_file(next_file_id, file_path)
+        create_data_file_with_partition(next_file_id, file_path, partition_values)
     }
 
-    /// Initialize arrow writer for once.
-    async fn initialize_arrow_writer_if_not(&mut self) -> Result<()> {
+    /// Initialize arrow writer for the given partition for once.
+    /// [`record_batch`] contains rows to write for the partition, which is used to get partition source values.
+    async fn initialize_arrow_writer_if_not(
+        &mut self,
+        partition_key: &Option<PartitionKey>,
+        record_batch: &RecordBatch,
+        sorting_columns: Option<Vec<SortingColumn>>,
+    ) -> Result<()> {
         // If we create multiple data files during compaction, simply increment file id and recreate a new one.
-        if self.cur_arrow_writer.is_some() {
-            assert!(self.cur_new_data_file.is_some());
+        if self.cur_writers.contains_key(partition_key) {
             return Ok(());
         }
 
-        self.cur_new_data_file = Some(self.create_new_data_file());
-        let write_file =
-            tokio::fs::File::create(self.cur_new_data_file.as_ref().unwrap().file_path()).await?;
-        let properties = parquet_utils::get_compaction_parquet_properties();
-        let writer: AsyncArrowWriter<tokio::fs::File> =
+        let partition_values = match self.partition_key_generator.as_ref() {
+            Some(generator) => Some(generator.get_partition_source_values(record_batch)?),
+            None => None,
+        };
+        let new_data_file = self.create_new_data_file(partition_values);
+        let write_file = tokio::fs::File::create(new_data_file.file_path()).await?;
+        let properties = parquet_utils::get_compaction_parquet_properties(
+            &self.file_params.parquet_writer_config,
+            self.file_params.encryption_key.as_ref(),
+            sorting_columns,
+        )?;
+        let arrow_writer: AsyncArrowWriter<tokio::fs::File> =
             AsyncArrowWriter::try_new(write_file, self.schema.clone(), Some(properties))?;
-        self.cur_arrow_writer = Some(writer);
+        self.cur_writers.insert(
+            partition_key.clone(),
+            CompactedFileWriter {
+                arrow_writer,
+                new_data_file,
+                row_num: 0,
+            },
+        );
 
         Ok(())
     }
 
-    /// Util function to flush current arrow write and re-initialize related states.
-    async fn flush_arrow_writer(&mut self) -> Result<()> {
-        self.cur_arrow_writer.as_mut().unwrap().finish().await?;
-        let file_size = self.cur_arrow_writer.as_ref().unwrap().bytes_written();
+    /// Util function to flush arrow writer for the given partition.
+    async fn flush_arrow_writer(
+        &mut self,
+        partition_key: &Option<PartitionKey>,
+        sort_key_range: Option<SortKeyRange>,
+    ) -> Result<()> {
+        let mut writer = self.cur_writers.remove(partition_key).unwrap();
+        writer.arrow_writer.finish().await?;
+        let file_size = writer.arrow_writer.bytes_written();
         ma::assert_gt!(file_size, 0);
-        ma::assert_gt!(self.cur_row_num, 0);
+        ma::assert_gt!(writer.row_num, 0);
         let compacted_data_entry = CompactedDataEntry {
-            num_rows: self.cur_row_num,
+            num_rows: writer.row_num,
             file_size,
+            sort_key_range,
         };
-        let new_data_file = std::mem::take(&mut self.cur_new_data_file).unwrap();
         self.new_data_files
-            .push((new_data_file, compacted_data_entry));
+            .push((writer.new_data_file, compacted_data_entry));
 
-        // Reinitialize states related to current new compacted data file.
-        self.cur_arrow_writer = None;
-        self.cur_new_data_file = None;
-        self.cur_row_num = 0;
-        self.compacted_file_count += 1;
+        Ok(())
+    }
 
+    /// Util function to flush all ongoing arrow writers.
+    async fn flush_all_arrow_writers(&mut self) -> Result<()> {
+        let partition_keys = self.cur_writers.keys().cloned().collect::<Vec<_>>();
+        for cur_partition_key in partition_keys.iter() {
+            self.flush_arrow_writer(cur_partition_key, /*sort_key_range=*/ None)
+                .await?;
+        }
+        // Writers for different partitions are flushed out of order.
+        self.new_data_files
+            .sort_by_key(|(new_data_file, _)| new_data_file.file_id());
         Ok(())
     }
 
+    /// Get partition key for rows in the given record batch.
+    /// Precondition: all rows in one data file belong to the same partition.
+    fn get_partition_key(&mut self, record_batch: &RecordBatch) -> Result<Option<PartitionKey>> {
+        match self.partition_key_generator.as_mut() {
+            Some(generator) => Ok(Some(generator.get_partition_key(record_batch)?)),
+            None => Ok(None),
+        }
+    }
+
     /// Util function to read the given parquet file, apply the corresponding deletion vector, and write it to the given arrow writer.
     /// Return the data file mapping, and cache evicted data files to delete.
     ///
@@ -211,7 +294,11 @@ impl CompactionBuilder {
         };
 
         let file = tokio::fs::File::open(filepath).await?;
-        let builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+        let builder = ParquetRecordBatchStreamBuilder::new_with_options(
+            file,
+            parquet_utils::get_parquet_reader_options(self.file_params.encryption_key.as_ref()),
+        )
+        .await?;
         let total_num_rows: usize = builder
             .metadata()
             .row_groups()
@@ -222,7 +309,11 @@ impl CompactionBuilder {
 
         let batch_deletion_vector =
             if let Some(puffin_blob_ref) = &data_file_to_compact.deletion_vector {
-                puffin_utils::load_deletion_vector_from_blob(puffin_blob_ref).await?
+                puffin_utils::load_deletion_vector_from_blob(
+                    puffin_blob_ref,
+                    self.file_params.encryption_key.as_ref(),
+                )
+                .await?
             } else {
                 BatchDeletionVector::new(/*max_rows=*/ 0)
             };
@@ -239,6 +330,8 @@ impl CompactionBuilder {
 
         let mut old_start_row_idx = 0;
         let mut old_to_new_remap = HashMap::new();
+        // Number of rows buffered to sort, whose record locations are remapped after all data files read.
+        let mut num_rows_to_sort = 0;
         while let Some(cur_record_batch) = reader.try_next().await? {
             // If all rows have been deleted for the old data file, do nothing.
             let cur_num_rows = cur_record_batch.num_rows();
@@ -249,10 +342,34 @@ impl CompactionBuilder {
                 continue;
             }
 
-            self.initialize_arrow_writer_if_not().await?;
-            self.cur_arrow_writer
-                .as_mut()
-                .unwrap()
+            let partition_key = self.get_partition_key(&filtered_record_batch)?;
+            if self.sort_key_generator.is_some() {
+                num_rows_to_sort += filtered_record_batch.num_rows();
+                let rows_to_sort = self.rows_to_sort.entry(partition_key).or_default();
+                rows_to_sort.record_batches.push(filtered_record_batch);
+                rows_to_sort.old_record_locations.extend(
+                    (old_start_row_idx..(old_start_row_idx + cur_num_rows))
+                        .filter(|old_row_idx| !batch_deletion_vector.is_deleted(*old_row_idx))
+                        .map(|old_row_idx| {
+                            RecordLocation::DiskFile(
+                                data_file_to_compact.file_id.file_id,
+                                old_row_idx,
+                            )
+                        }),
+                );
+                old_start_row_idx += cur_num_rows;
+                continue;
+            }
+
+            self.initialize_arrow_writer_if_not(
+                &partition_key,
+                &filtered_record_batch,
+                /*sorting_columns=*/ None,
+            )
+            .await?;
+            let cur_writer = self.cur_writers.get_mut(&partition_key).unwrap();
+            cur_writer
+                .arrow_writer
                 .write(&filtered_record_batch)
                 .await?;
 
@@ -266,34 +383,40 @@ impl CompactionBuilder {
                 let old_record_location =
                     RecordLocation::DiskFile(data_file_to_compact.file_id.file_id, old_row_idx);
                 let new_record_location = RecordLocation::DiskFile(
-                    self.cur_new_data_file.as_ref().unwrap().file_id(),
-                    self.cur_row_num,
+                    cur_writer.new_data_file.file_id(),
+                    cur_writer.row_num,
                 );
                 // Precondition: data files are compacted before file indices, so [`self.compacted_file_count`] indicates the index of already compacted data files.
                 let remapped_record_location = RemappedRecordLocation {
                     record_location: new_record_location,
-                    new_data_file: self.cur_new_data_file.as_ref().unwrap().clone(),
+                    new_data_file: cur_writer.new_data_file.clone(),
                 };
                 let old_entry =
                     old_to_new_remap.insert(old_record_location, remapped_record_location);
                 assert!(old_entry.is_none());
-                self.cur_row_num += 1;
+                cur_writer.row_num += 1;
             }
 
             old_start_row_idx += cur_num_rows;
         }
 
         // Bytes to write already reached target compacted data file size, flush and close.
-        if self.cur_arrow_writer.is_some()
-            && self.cur_arrow_writer.as_ref().unwrap().memory_size()
-                >= self.file_params.data_file_final_size as usize
-        {
-            self.flush_arrow_writer().await?;
+        let partition_keys_to_flush = self
+            .cur_writers
+            .iter()
+            .filter(|(_, writer)| {
+                writer.arrow_writer.memory_size() >= self.file_params.data_file_final_size as usize
+            })
+            .map(|(partition_key, _)| partition_key.clone())
+            .collect::<Vec<_>>();
+        for cur_partition_key in partition_keys_to_flush.iter() {
+            self.flush_arrow_writer(cur_partition_key, /*sort_key_range=*/ None)
+                .await?;
         }
 
         // Sanity check on compaction result.
         let expected_compacted_num_rows = total_num_rows - deleted_rows_num;
-        let actual_compacted_num_rows = old_to_new_remap.len();
+        let actual_compacted_num_rows = old_to_new_remap.len() + num_rows_to_sort;
         assert_eq!(expected_compacted_num_rows, actual_compacted_num_rows);
 
         let data_file_compaction_result = DataFileCompactionResult {
@@ -308,6 +431,10 @@ impl CompactionBuilder {
     /// Util function to compact the given data files, with their corresponding deletion vector applied.
     #[tracing::instrument(name = "compact_data_files", skip_all)]
     async fn compact_data_files(&mut self) -> Result<DataFileCompactionResult> {
+        self.partition_key_generator =
+            PartitionKeyGenerator::try_new(&self.schema, &self.file_params.partition_spec)?;
+        self.sort_key_generator =
+            SortKeyGenerator::try_new(&self.schema, &self.file_params.sort_order)?;
         let mut old_to_new_remap = HashMap::new();
 
         let disk_files = std::mem::take(&mut self.compaction_payload.disk_files);
@@ -321,6 +448,7 @@ impl CompactionBuilder {
             old_to_new_remap.extend(data_file_compaction_result.data_file_remap);
             files_compacted.extend(data_file_compaction_result.files_compacted);
         }
+        old_to_new_remap.extend(self.write_sorted_rows().await?);
 
         let data_file_compaction_result = DataFileCompactionResult {
             data_file_remap: old_to_new_remap,
@@ -330,6 +458,80 @@ impl CompactionBuilder {
         Ok(data_file_compaction_result)
     }
 
+    /// Sort buffered rows for each partition, and write them to compacted data files.
+    /// Return the mapping from record locations before compaction to ones after compaction for sorted rows.
+    #[tracing::instrument(name = "write_sorted_rows", skip_all)]
+    async fn write_sorted_rows(&mut self) -> Result<DataFileRemap> {
+        let mut old_to_new_remap = HashMap::new();
+        let Some(sort_key_generator) = self.sort_key_generator.take() else {
+            return Ok(old_to_new_remap);
+        };
+
+        let rows_to_sort = std::mem::take(&mut self.rows_to_sort);
+        for (partition_key, cur_rows_to_sort) in rows_to_sort.into_iter() {
+            let sorted = sort_key_generator.sort(&concat_batches(
+                &self.schema,
+                &cur_rows_to_sort.record_batches,
+            )?)?;
+            let num_rows = sorted.record_batch.num_rows();
+            // Sorted row index for the first row in the current compacted data file.
+            let mut file_start_row = 0;
+            for chunk_start_row in (0..num_rows).step_by(SORTED_WRITE_BATCH_SIZE) {
+                let chunk_num_rows = SORTED_WRITE_BATCH_SIZE.min(num_rows - chunk_start_row);
+                if !self.cur_writers.contains_key(&partition_key) {
+                    file_start_row = chunk_start_row;
+                    self.initialize_arrow_writer_if_not(
+                        &partition_key,
+                        &sorted.record_batch.slice(chunk_start_row, chunk_num_rows),
+                        Some(sort_key_generator.get_sorting_columns()),
+                    )
+                    .await?;
+                }
+                let cur_writer = self.cur_writers.get_mut(&partition_key).unwrap();
+                cur_writer
+                    .arrow_writer
+                    .write(&sorted.record_batch.slice(chunk_start_row, chunk_num_rows))
+                    .await?;
+                for sorted_row_idx in chunk_start_row..(chunk_start_row + chunk_num_rows) {
+                    let old_record_location = cur_rows_to_sort.old_record_locations
+                        [sorted.original_row_indices[sorted_row_idx]]
+                        .clone();
+                    let remapped_record_location = RemappedRecordLocation {
+                        record_location: RecordLocation::DiskFile(
+                            cur_writer.new_data_file.file_id(),
+                            cur_writer.row_num,
+                        ),
+                        new_data_file: cur_writer.new_data_file.clone(),
+                    };
+                    assert!(old_to_new_remap
+                        .insert(old_record_location, remapped_record_location)
+                        .is_none());
+                    cur_writer.row_num += 1;
+                }
+
+                // Bytes to write already reached target compacted data file size, flush and close.
+                if cur_writer.arrow_writer.memory_size()
+                    >= self.file_params.data_file_final_size as usize
+                {
+                    let sort_key_range = sorted.get_key_range(
+                        &partition_key,
+                        file_start_row,
+                        chunk_start_row + chunk_num_rows,
+                    );
+                    self.flush_arrow_writer(&partition_key, Some(sort_key_range))
+                        .await?;
+                }
+            }
+            if self.cur_writers.contains_key(&partition_key) {
+                let sort_key_range = sorted.get_key_range(&partition_key, file_start_row, num_rows);
+                self.flush_arrow_writer(&partition_key, Some(sort_key_range))
+                    .await?;
+            }
+        }
+
+        Ok(old_to_new_remap)
+    }
+
     /// Util function to get new compacted data files **IN ORDER**.
     fn get_new_compacted_data_files(&self) -> Vec<MooncakeDataFileRef> {
         let mut prev_file_id: u64 = 0;
@@ -368,6 +570,7 @@ impl CompactionBuilder {
 
         let mut global_index_builder = GlobalIndexBuilder::new();
         global_index_builder.set_directory(self.file_params.dir_path.clone());
+        global_index_builder.set_encryption_key(self.file_params.encryption_key.clone());
         global_index_builder
             .build_from_merge_for_compaction(
                 /*num_rows=*/ old_to_new_remap.len() as u32,
@@ -429,10 +632,8 @@ impl CompactionBuilder {
             });
         }
 
-        // Flush and close the compacted data file.
-        if self.cur_arrow_writer.is_some() {
-            self.flush_arrow_writer().await?;
-        }
+        // Flush and close the compacted data files.
+        self.flush_all_arrow_writers().await?;
 
         // Perform compaction on file indices
         let mut new_file_indices = vec![];
@@ -1,5 +1,6 @@
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
 use crate::storage::index::FileIndex;
+use crate::storage::sort_utils::SortKeyRange;
 use crate::storage::storage_utils::FileId;
 use crate::storage::storage_utils::MooncakeDataFileRef;
 use crate::storage::storage_utils::RecordLocation;
@@ -145,6 +146,8 @@ pub(crate) struct CompactedDataEntry {
     pub(crate) num_rows: usize,
     /// Compacted file size.
     pub(crate) file_size: usize,
+    /// Sort key range of rows, only assigned for tables with sort order.
+    pub(crate) sort_key_range: Option<SortKeyRange>,
 }
 
 /// Remapped record location after compaction.
@@ -202,3 +205,34 @@ impl std::fmt::Debug for DataCompactionResult {
             .finish()
     }
 }
+
+/// Get data files to rewrite in a re-cluster compaction, whose sort key range overlaps with at least [`overlap_threshold`] other data files.
+pub(crate) fn get_data_files_to_recluster<'a>(
+    key_ranges: impl IntoIterator<Item = (FileId, &'a SortKeyRange)>,
+    overlap_threshold: usize,
+) -> HashSet<FileId> {
+    if overlap_threshold == 0 {
+        return HashSet::new();
+    }
+    let mut key_ranges = key_ranges.into_iter().collect::<Vec<_>>();
+    // Sort by partition and min key, so overlapping ranges for one key range are all placed after it until the first non-overlapping one.
+    key_ranges.sort_by(|(_, lhs), (_, rhs)| {
+        (&lhs.partition_key, &lhs.min).cmp(&(&rhs.partition_key, &rhs.min))
+    });
+    let mut overlap_counts = vec![0; key_ranges.len()];
+    for cur_idx in 0..key_ranges.len() {
+        for next_idx in (cur_idx + 1)..key_ranges.len() {
+            if !key_ranges[cur_idx].1.overlaps(key_ranges[next_idx].1) {
+                break;
+            }
+            overlap_counts[cur_idx] += 1;
+            overlap_counts[next_idx] += 1;
+        }
+    }
+    key_ranges
+        .into_iter()
+        .zip(overlap_counts)
+        .filter(|(_, overlap_count)| *overlap_count >= overlap_threshold)
+        .map(|((file_id, _), _)| file_id)
+        .collect()
+}
@@ -1,17 +1,28 @@
 use crate::storage::compaction::compactor::{CompactionBuilder, CompactionFileParams};
-use crate::storage::compaction::table_compaction::{DataCompactionPayload, SingleFileToCompact};
+use crate::storage::compaction::table_compaction::{
+    get_data_files_to_recluster, DataCompactionPayload, SingleFileToCompact,
+};
 use crate::storage::compaction::test_utils;
 use crate::storage::compaction::test_utils::get_record_location_mapping;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
 use crate::storage::mooncake_table::table_creation_test_utils::*;
+use crate::storage::mooncake_table_config::{
+    ParquetWriterConfig, PartitionFieldConfig, SortFieldConfig,
+};
+use crate::storage::sort_utils::SortKeyGenerator;
 use crate::storage::storage_utils::{
     self, get_unique_file_id_for_flush, MooncakeDataFileRef, TableId, TableUniqueFileId,
 };
 use crate::storage::storage_utils::{FileId, RecordLocation};
+use crate::storage::table::iceberg::test_utils::load_arrow_batch;
 use crate::storage::PuffinBlobRef;
 use crate::{create_data_file, FileSystemAccessor, ObjectStorageCache};
 
-use std::collections::HashMap;
+use arrow_array::{Array, Int32Array};
+use iceberg::io::FileIOBuilder;
+use iceberg::spec::{NullOrder, SortDirection, Transform};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 /// Single compacted file size.
 const SINGLE_COMPACTED_DATA_FILE_SIZE: u64 = u64::MAX;
@@ -80,6 +91,10 @@ async fn test_data_file_compaction_1() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -165,6 +180,10 @@ async fn test_data_file_compaction_2() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -253,6 +272,10 @@ async fn test_data_file_compaction_3() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Check compaction results.
@@ -334,6 +357,10 @@ async fn test_data_file_compaction_4() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -450,6 +477,10 @@ async fn test_data_file_compaction_5() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -573,6 +604,10 @@ async fn test_data_file_compaction_6() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Check compaction results.
@@ -663,6 +698,10 @@ async fn test_data_file_compaction_7() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -735,6 +774,10 @@ async fn test_data_file_compaction_8() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -830,6 +873,10 @@ async fn test_data_file_compaction_9() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -926,6 +973,10 @@ async fn test_data_file_compaction_10() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
         data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -1046,6 +1097,10 @@ async fn test_multiple_compacted_data_files_1() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 4),
         data_file_final_size: MULTI_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -1182,6 +1237,10 @@ async fn test_multiple_compacted_data_files_2() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 4),
         data_file_final_size: MULTI_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -1244,6 +1303,10 @@ async fn test_large_number_of_data_files() {
         dir_path: std::path::PathBuf::from(temp_dir.path()),
         table_auto_incr_ids: start_table_auto_incr_id..end_table_auto_incr_id,
         data_file_final_size: 1, // Dump each data file into its own file.
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
     };
 
     // Perform compaction.
@@ -1267,3 +1330,251 @@ async fn test_large_number_of_data_files() {
     );
     assert_eq!(compaction_result.new_file_indices.len(), 1);
 }
+
+/// ============================
+/// Compact partitioned table
+/// ============================
+///
+/// Testing scenario: data files in different partitions are not merged together.
+#[tokio::test]
+async fn test_partitioned_data_file_compaction() {
+    // Create data files and file indices, the first data file falls into partition age=0, the second one into partition age=40.
+    let temp_dir = tempfile::tempdir().unwrap();
+    let data_file_1 = temp_dir.path().join("test-1.parquet");
+    let data_file_2 = temp_dir.path().join("test-2.parquet");
+
+    let data_file_1 = create_data_file(
+        /*file_id=*/ 0,
+        data_file_1.to_str().unwrap().to_string(),
+    );
+    let data_file_2 = create_data_file(
+        /*file_id=*/ 1,
+        data_file_2.to_str().unwrap().to_string(),
+    );
+    let record_batch_1 = test_utils::create_test_batch_1();
+    let record_batch_2 = test_utils::create_test_batch_2();
+    test_utils::dump_arrow_record_batches(vec![record_batch_1], data_file_1.clone()).await;
+    test_utils::dump_arrow_record_batches(vec![record_batch_2], data_file_2.clone()).await;
+
+    let file_index_1 = test_utils::create_file_index_1(
+        temp_dir.path().to_path_buf(),
+        data_file_1.clone(),
+        /*start_file_id=*/ 2,
+    )
+    .await;
+    let file_index_2 = test_utils::create_file_index_2(
+        temp_dir.path().to_path_buf(),
+        data_file_2.clone(),
+        /*start_file_id=*/ 3,
+    )
+    .await;
+
+    // Prepare compaction payload.
+    let payload = DataCompactionPayload {
+        uuid: uuid::Uuid::new_v4(),
+        object_storage_cache: create_test_object_storage_cache(&temp_dir),
+        filesystem_accessor: FileSystemAccessor::default_for_test(&temp_dir),
+        disk_files: vec![
+            get_single_file_to_compact(&data_file_1, /*deletion_vector=*/ None),
+            get_single_file_to_compact(&data_file_2, /*deletion_vector=*/ None),
+        ],
+        file_indices: vec![file_index_1.unwrap(), file_index_2.unwrap()],
+    };
+    let table_auto_incr_id: u64 = 4;
+    let file_params = CompactionFileParams {
+        dir_path: std::path::PathBuf::from(temp_dir.path()),
+        table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
+        data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![PartitionFieldConfig::new("age", Transform::Truncate(40))],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![],
+    };
+
+    // Perform compaction.
+    let builder = CompactionBuilder::new(payload, create_test_arrow_schema(), file_params);
+    let compaction_result = builder.build().await.unwrap();
+
+    // Check compaction results, each partition gets its own compacted data file.
+    let compacted_file_id_1 = FileId(get_unique_file_id_for_flush(
+        table_auto_incr_id,
+        /*file_idx=*/ 0,
+    ));
+    let compacted_file_id_2 = FileId(get_unique_file_id_for_flush(
+        table_auto_incr_id,
+        /*file_idx=*/ 1,
+    ));
+    let new_file_ids = compaction_result
+        .new_data_files
+        .iter()
+        .map(|(data_file, _)| data_file.file_id())
+        .collect::<Vec<_>>();
+    assert_eq!(new_file_ids, vec![compacted_file_id_1, compacted_file_id_2]);
+
+    // Check file indices compaction.
+    let mut expected_record_locations = vec![];
+    for compacted_file_id in [compacted_file_id_1, compacted_file_id_2] {
+        for row_idx in 0..3 {
+            expected_record_locations.push((compacted_file_id, row_idx));
+        }
+    }
+    test_utils::check_file_indices_compaction_for_multiple_compacted_files(
+        compaction_result.new_file_indices.as_slice(),
+        expected_record_locations,
+        /*old_row_indices=*/ (0..6).collect(),
+    )
+    .await;
+}
+
+/// ============================
+/// Compact sorted table
+/// ============================
+///
+/// Testing scenario: rows from all data files are sorted by table sort order in the compacted data file.
+#[tokio::test]
+async fn test_sorted_data_file_compaction() {
+    // Create data files and file indices.
+    let temp_dir = tempfile::tempdir().unwrap();
+    let data_file_1 = temp_dir.path().join("test-1.parquet");
+    let data_file_2 = temp_dir.path().join("test-2.parquet");
+
+    let data_file_1 = create_data_file(
+        /*file_id=*/ 0,
+        data_file_1.to_str().unwrap().to_string(),
+    );
+    let data_file_2 = create_data_file(
+        /*file_id=*/ 1,
+        data_file_2.to_str().unwrap().to_string(),
+    );
+    let record_batch_1 = test_utils::create_test_batch_1();
+    let record_batch_2 = test_utils::create_test_batch_2();
+    test_utils::dump_arrow_record_batches(vec![record_batch_1], data_file_1.clone()).await;
+    test_utils::dump_arrow_record_batches(vec![record_batch_2], data_file_2.clone()).await;
+
+    let file_index_1 = test_utils::create_file_index_1(
+        temp_dir.path().to_path_buf(),
+        data_file_1.clone(),
+        /*start_file_id=*/ 2,
+    )
+    .await;
+    let file_index_2 = test_utils::create_file_index_2(
+        temp_dir.path().to_path_buf(),
+        data_file_2.clone(),
+        /*start_file_id=*/ 3,
+    )
+    .await;
+
+    // Prepare compaction payload.
+    let payload = DataCompactionPayload {
+        uuid: uuid::Uuid::new_v4(),
+        object_storage_cache: create_test_object_storage_cache(&temp_dir),
+        filesystem_accessor: FileSystemAccessor::default_for_test(&temp_dir),
+        disk_files: vec![
+            get_single_file_to_compact(&data_file_1, /*deletion_vector=*/ None),
+            get_single_file_to_compact(&data_file_2, /*deletion_vector=*/ None),
+        ],
+        file_indices: vec![file_index_1.unwrap(), file_index_2.unwrap()],
+    };
+    let table_auto_incr_id: u64 = 4;
+    let file_params = CompactionFileParams {
+        dir_path: std::path::PathBuf::from(temp_dir.path()),
+        table_auto_incr_ids: (table_auto_incr_id as u32)..(table_auto_incr_id as u32 + 1),
+        data_file_final_size: SINGLE_COMPACTED_DATA_FILE_SIZE,
+        partition_spec: vec![],
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_key: None,
+        sort_order: vec![SortFieldConfig::new(
+            "id",
+            SortDirection::Descending,
+            NullOrder::Last,
+        )],
+    };
+
+    // Perform compaction.
+    let builder = CompactionBuilder::new(payload, create_test_arrow_schema(), file_params);
+    let compaction_result = builder.build().await.unwrap();
+
+    // Check rows in the compacted data file are sorted.
+    assert_eq!(compaction_result.new_data_files.len(), 1);
+    let (compacted_data_file, compacted_data_entry) = &compaction_result.new_data_files[0];
+    assert!(compacted_data_entry.sort_key_range.is_some());
+    let file_io = FileIOBuilder::new_fs_io().build().unwrap();
+    let record_batch = load_arrow_batch(&file_io, compacted_data_file.file_path())
+        .await
+        .unwrap();
+    let ids = record_batch
+        .column(0)
+        .as_any()
+        .downcast_ref::<Int32Array>()
+        .unwrap();
+    assert_eq!(ids.values().to_vec(), vec![6, 5, 4, 3, 2, 1]);
+
+    // Check remap results follow sorted row order.
+    let compacted_file_id = compacted_data_file.file_id();
+    let mut expected_remap = HashMap::new();
+    for (old_file_id, old_row_idx, new_row_idx) in [
+        (0, 0, 5),
+        (0, 1, 4),
+        (0, 2, 3),
+        (1, 0, 2),
+        (1, 1, 1),
+        (1, 2, 0),
+    ] {
+        expected_remap.insert(
+            RecordLocation::DiskFile(FileId(old_file_id), old_row_idx),
+            RecordLocation::DiskFile(compacted_file_id, new_row_idx),
+        );
+    }
+    let actual_remap = get_record_location_mapping(&compaction_result.remapped_data_files);
+    assert_eq!(actual_remap, expected_remap);
+}
+
+/// Testing scenario: data files whose sort key ranges overlap with too many others are picked for re-cluster.
+#[test]
+fn test_get_data_files_to_recluster() {
+    let sort_order = vec![SortFieldConfig::new(
+        "id",
+        SortDirection::Ascending,
+        NullOrder::First,
+    )];
+    let schema = create_test_arrow_schema();
+    let generator = SortKeyGenerator::try_new(&schema, &sort_order)
+        .unwrap()
+        .unwrap();
+    let record_batch = arrow_array::RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int32Array::from((0..10).collect::<Vec<_>>())),
+            Arc::new(arrow_array::StringArray::from(vec!["a"; 10])),
+            Arc::new(Int32Array::from(vec![0; 10])),
+        ],
+    )
+    .unwrap();
+    let sorted = generator.sort(&record_batch).unwrap();
+
+    // Key range for file-0 covers all others, file-1 and file-2 overlap with each other, file-3 only overlaps with file-0.
+    let key_ranges = [
+        sorted.get_key_range(&None, /*start=*/ 0, /*end=*/ 10),
+        sorted.get_key_range(&None, /*start=*/ 0, /*end=*/ 4),
+        sorted.get_key_range(&None, /*start=*/ 3, /*end=*/ 6),
+        sorted.get_key_range(&None, /*start=*/ 8, /*end=*/ 10),
+    ];
+    let files_with_key_ranges = || {
+        key_ranges
+            .iter()
+            .enumerate()
+            .map(|(idx, key_range)| (FileId(idx as u64), key_range))
+    };
+    assert_eq!(
+        get_data_files_to_recluster(files_with_key_ranges(), /*overlap_threshold=*/ 2),
+        HashSet::from([FileId(0), FileId(1), FileId(2)])
+    );
+    assert_eq!(
+        get_data_files_to_recluster(files_with_key_ranges(), /*overlap_threshold=*/ 3),
+        HashSet::from([FileId(0)])
+    );
+    // Re-cluster is disabled.
+    assert!(
+        get_data_files_to_recluster(files_with_key_ranges(), /*overlap_threshold=*/ 0).is_empty()
+    );
+}
@@ -0,0 +1,9 @@
+pub(crate) mod aead_stream;
+pub(crate) mod data_key;
+pub(crate) mod encrypted_filesystem_accessor;
+pub mod encryption_config;
+pub(crate) mod key_provider;
+pub(crate) mod table_encryption;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
@@ -0,0 +1,289 @@
+/// This module implements a segmented AEAD stream format, used to encrypt WAL and index files.
+///
+/// Layout: | 4-byte magic | 1-byte version | 7-byte nonce prefix | segment | segment | ... |
+/// Each segment holds at most [`SEGMENT_SIZE`] bytes of plaintext followed by a 16-byte AES-256-GCM tag.
+/// Segment nonce is made up of nonce prefix, 4-byte big-endian segment index and a 1-byte last segment flag,
+/// so segments cannot be reordered, and truncation at segment boundary is detected.
+use crate::{Error, Result};
+
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Magic bytes for AEAD stream.
+const MAGIC: &[u8; 4] = b"MLAE";
+/// Current format version.
+const VERSION: u8 = 1;
+/// Length of the random nonce prefix.
+const NONCE_PREFIX_LEN: usize = 7;
+/// Length of the stream header.
+pub(crate) const HEADER_LEN: usize = MAGIC.len() + 1 + NONCE_PREFIX_LEN;
+/// Max plaintext bytes for each segment.
+pub(crate) const SEGMENT_SIZE: usize = 64 * 1024;
+/// Length of authentication tag for each segment.
+const TAG_LEN: usize = 16;
+/// Length of encryption key.
+pub(crate) const KEY_LEN: usize = 32;
+
+fn create_key(key: &[u8]) -> Result<LessSafeKey> {
+    let unbound_key = UnboundKey::new(&AES_256_GCM, key)
+        .map_err(|_| Error::encryption_error("Invalid AEAD stream key length".to_string()))?;
+    Ok(LessSafeKey::new(unbound_key))
+}
+
+fn get_segment_nonce(nonce_prefix: &[u8], segment_idx: u32, last: bool) -> Nonce {
+    let mut nonce = [0u8; NONCE_LEN];
+    nonce[..NONCE_PREFIX_LEN].copy_from_slice(nonce_prefix);
+    nonce[NONCE_PREFIX_LEN..NONCE_LEN - 1].copy_from_slice(&segment_idx.to_be_bytes());
+    nonce[NONCE_LEN - 1] = last as u8;
+    Nonce::assume_unique_for_key(nonce)
+}
+
+/// Encryptor which takes plaintext in arbitrary chunks, and emits ciphertext in whole segments.
+pub(crate) struct AeadStreamEncryptor {
+    key: LessSafeKey,
+    header: [u8; HEADER_LEN],
+    /// Whether header has been emitted.
+    header_emitted: bool,
+    /// Index for the next segment.
+    segment_idx: u32,
+    /// Buffered plaintext, which hasn't been sealed.
+    buffer: Vec<u8>,
+}
+
+impl AeadStreamEncryptor {
+    pub(crate) fn new(key: &[u8]) -> Result<Self> {
+        let mut header = [0u8; HEADER_LEN];
+        header[..MAGIC.len()].copy_from_slice(MAGIC);
+        header[MAGIC.len()] = VERSION;
+        SystemRandom::new()
+            .fill(&mut header[MAGIC.len() + 1..])
+            .map_err(|_| Error::encryption_error("Failed to generate nonce prefix".to_string()))?;
+        Ok(Self {
+            key: create_key(key)?,
+            header,
+            header_emitted: false,
+            segment_idx: 0,
+            buffer: Vec::with_capacity(SEGMENT_SIZE),
+        })
+    }
+
+    fn seal_segment(
+        &mut self,
+        mut segment: Vec<u8>,
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        let nonce = get_segment_nonce(&self.header[MAGIC.len() + 1..], self.segment_idx, last);
+        self.key
+            .seal_in_place_append_tag(nonce, Aad::from(&self.header), &mut segment)
+            .map_err(|_| Error::encryption_error("Failed to seal AEAD segment".to_string()))?;
+        self.segment_idx = self.segment_idx.checked_add(1).ok_or_else(|| {
+            Error::encryption_error("Too many segments for AEAD stream".to_string())
+        })?;
+        output.extend_from_slice(&segment);
+        Ok(())
+    }
+
+    fn take_header(&mut self, output: &mut Vec<u8>) {
+        if !self.header_emitted {
+            output.extend_from_slice(&self.header);
+            self.header_emitted = true;
+        }
+    }
+
+    /// Feed plaintext, and return ciphertext which is ready to write.
+    /// A full segment is only sealed when more plaintext follows it, since the last segment is sealed differently.
+    pub(crate) fn update(&mut self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        self.take_header(&mut output);
+        self.buffer.extend_from_slice(plaintext);
+        while self.buffer.len() > SEGMENT_SIZE {
+            let remaining = self.buffer.split_off(SEGMENT_SIZE);
+            let segment = std::mem::replace(&mut self.buffer, remaining);
+            self.seal_segment(segment, /*last=*/ false, &mut output)?;
+        }
+        Ok(output)
+    }
+
+    /// Seal the last segment, and return remaining ciphertext.
+    pub(crate) fn finalize(mut self) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        self.take_header(&mut output);
+        let segment = std::mem::take(&mut self.buffer);
+        self.seal_segment(segment, /*last=*/ true, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// Decryptor which takes ciphertext in arbitrary chunks, and emits authenticated plaintext.
+pub(crate) struct AeadStreamDecryptor {
+    key: LessSafeKey,
+    header: Option<[u8; HEADER_LEN]>,
+    /// Index for the next segment.
+    segment_idx: u32,
+    /// Buffered ciphertext, which hasn't been opened.
+    buffer: Vec<u8>,
+}
+
+impl AeadStreamDecryptor {
+    pub(crate) fn new(key: &[u8]) -> Result<Self> {
+        Ok(Self {
+            key: create_key(key)?,
+            header: None,
+            segment_idx: 0,
+            buffer: Vec::new(),
+        })
+    }
+
+    fn open_segment(
+        &mut self,
+        mut segment: Vec<u8>,
+        last: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<()> {
+        let header = self.header.as_ref().unwrap();
+        let nonce = get_segment_nonce(&header[MAGIC.len() + 1..], self.segment_idx, last);
+        let plaintext = self
+            .key
+            .open_in_place(nonce, Aad::from(header), &mut segment)
+            .map_err(|_| {
+                Error::encryption_error(format!(
+                    "Failed to authenticate AEAD segment {}",
+                    self.segment_idx
+                ))
+            })?;
+        output.extend_from_slice(plaintext);
+        self.segment_idx += 1;
+        Ok(())
+    }
+
+    fn parse_header(&mut self) -> Result<()> {
+        if self.header.is_some() || self.buffer.len() < HEADER_LEN {
+            return Ok(());
+        }
+        let remaining = self.buffer.split_off(HEADER_LEN);
+        let header: [u8; HEADER_LEN] = std::mem::replace(&mut self.buffer, remaining)
+            .try_into()
+            .unwrap();
+        if &header[..MAGIC.len()] != MAGIC || header[MAGIC.len()] != VERSION {
+            return Err(Error::encryption_error(
+                "Content is not a supported AEAD stream".to_string(),
+            ));
+        }
+        self.header = Some(header);
+        Ok(())
+    }
+
+    /// Feed ciphertext, and return plaintext authenticated so far.
+    /// A full segment is only opened when more ciphertext follows it, since the last segment is sealed differently.
+    pub(crate) fn update(&mut self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        self.buffer.extend_from_slice(ciphertext);
+        self.parse_header()?;
+        if self.header.is_none() {
+            return Ok(output);
+        }
+        while self.buffer.len() > SEGMENT_SIZE + TAG_LEN {
+            let remaining = self.buffer.split_off(SEGMENT_SIZE + TAG_LEN);
+            let segment = std::mem::replace(&mut self.buffer, remaining);
+            self.open_segment(segment, /*last=*/ false, &mut output)?;
+        }
+        Ok(output)
+    }
+
+    /// Open the last segment, and return remaining plaintext.
+    pub(crate) fn finalize(mut self) -> Result<Vec<u8>> {
+        let mut output = Vec::new();
+        if self.header.is_none() || self.buffer.len() < TAG_LEN {
+            return Err(Error::encryption_error(
+                "AEAD stream is truncated".to_string(),
+            ));
+        }
+        let segment = std::mem::take(&mut self.buffer);
+        self.open_segment(segment, /*last=*/ true, &mut output)?;
+        Ok(output)
+    }
+}
+
+/// Encrypt the whole content in one shot.
+pub(crate) fn encrypt(key: &[u8], plaintext: &[u8]) -> Result<Vec<u8>> {
+    let mut encryptor = AeadStreamEncryptor::new(key)?;
+    let mut ciphertext = encryptor.update(plaintext)?;
+    ciphertext.extend(encryptor.finalize()?);
+    Ok(ciphertext)
+}
+
+/// Decrypt the whole content in one shot.
+pub(crate) fn decrypt(key: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>> {
+    let mut decryptor = AeadStreamDecryptor::new(key)?;
+    let mut plaintext = decryptor.update(ciphertext)?;
+    plaintext.extend(decryptor.finalize()?);
+    Ok(plaintext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rstest::rstest;
+
+    const TEST_KEY: [u8; KEY_LEN] = [7u8; KEY_LEN];
+
+    fn create_plaintext(len: usize) -> Vec<u8> {
+        (0..len).map(|idx| (idx % 251) as u8).collect()
+    }
+
+    #[rstest]
+    #[case(0)]
+    #[case(1)]
+    #[case(SEGMENT_SIZE)]
+    #[case(SEGMENT_SIZE + 1)]
+    #[case(3 * SEGMENT_SIZE + 17)]
+    fn test_aead_stream_roundtrip(#[case] len: usize) {
+        let plaintext = create_plaintext(len);
+        let ciphertext = encrypt(&TEST_KEY, &plaintext).unwrap();
+        assert_eq!(
+            ciphertext.len(),
+            HEADER_LEN + len + len.div_ceil(SEGMENT_SIZE).max(1) * TAG_LEN
+        );
+        assert_eq!(decrypt(&TEST_KEY, &ciphertext).unwrap(), plaintext);
+    }
+
+    /// Testing scenario: plaintext and ciphertext are fed in chunks not aligned with segments.
+    #[test]
+    fn test_aead_stream_chunked_update() {
+        let plaintext = create_plaintext(2 * SEGMENT_SIZE + 100);
+        let mut encryptor = AeadStreamEncryptor::new(&TEST_KEY).unwrap();
+        let mut ciphertext = vec![];
+        for chunk in plaintext.chunks(1000) {
+            ciphertext.extend(encryptor.update(chunk).unwrap());
+        }
+        ciphertext.extend(encryptor.finalize().unwrap());
+
+        let mut decryptor = AeadStreamDecryptor::new(&TEST_KEY).unwrap();
+        let mut actual_plaintext = vec![];
+        for chunk in ciphertext.chunks(333) {
+            actual_plaintext.extend(decryptor.update(chunk).unwrap());
+        }
+        actual_plaintext.extend(decryptor.finalize().unwrap());
+        assert_eq!(actual_plaintext, plaintext);
+    }
+
+    /// Testing scenario: tampered, truncated content, or wrong key fails decryption.
+    #[test]
+    fn test_aead_stream_authentication() {
+        let plaintext = create_plaintext(2 * SEGMENT_SIZE + 100);
+        let ciphertext = encrypt(&TEST_KEY, &plaintext).unwrap();
+
+        let mut tampered = ciphertext.clone();
+        tampered[HEADER_LEN + 10] ^= 1;
+        assert!(decrypt(&TEST_KEY, &tampered).is_err());
+
+        // Truncate at segment boundary.
+        let truncated = &ciphertext[..HEADER_LEN + 2 * (SEGMENT_SIZE + TAG_LEN)];
+        assert!(decrypt(&TEST_KEY, truncated).is_err());
+
+        assert!(decrypt(&[8u8; KEY_LEN], &ciphertext).is_err());
+        assert!(decrypt(&TEST_KEY, &plaintext).is_err());
+    }
+}
@@ -0,0 +1,146 @@
+/// This module defines the per-table data key, from which keys for each file format are derived.
+use crate::storage::encryption::aead_stream;
+use crate::{Error, Result};
+
+use parquet::encryption::decrypt::FileDecryptionProperties;
+use parquet::encryption::encrypt::FileEncryptionProperties;
+use ring::hkdf::{self, KeyType, Salt, HKDF_SHA256};
+use ring::rand::{SecureRandom, SystemRandom};
+
+/// Length of data key.
+pub(crate) const DATA_KEY_LEN: usize = 32;
+/// Length of parquet footer key, parquet modular encryption takes AES-128 keys.
+const PARQUET_KEY_LEN: usize = 16;
+
+/// HKDF info for parquet footer key derivation.
+const PARQUET_KEY_INFO: &[u8] = b"moonlink-parquet-footer-key";
+/// HKDF info for AEAD stream key derivation.
+const AEAD_STREAM_KEY_INFO: &[u8] = b"moonlink-aead-stream-key";
+
+struct DerivedKeyLen(usize);
+
+impl KeyType for DerivedKeyLen {
+    fn len(&self) -> usize {
+        self.0
+    }
+}
+
+/// Per-table data key, which is persisted only in its wrapped form.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DataEncryptionKey {
+    key: [u8; DATA_KEY_LEN],
+}
+
+impl std::fmt::Debug for DataEncryptionKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataEncryptionKey")
+            .field("key", &"xxxxx")
+            .finish()
+    }
+}
+
+impl DataEncryptionKey {
+    /// Generate a random data key.
+    pub(crate) fn generate() -> Result<Self> {
+        let mut key = [0u8; DATA_KEY_LEN];
+        SystemRandom::new()
+            .fill(&mut key)
+            .map_err(|_| Error::encryption_error("Failed to generate data key".to_string()))?;
+        Ok(Self { key })
+    }
+
+    pub(crate) fn try_from_bytes(bytes: &[u8]) -> Result<Self> {
+        let key = bytes.try_into().map_err(|_| {
+            Error::encryption_error(format!(
+                "Data key should be {DATA_KEY_LEN} bytes, but got {} bytes",
+                bytes.len()
+            ))
+        })?;
+        Ok(Self { key })
+    }
+
+    pub(crate) fn as_bytes(&self) -> &[u8] {
+        &self.key
+    }
+
+    fn derive_key(&self, info: &[u8], len: usize) -> Vec<u8> {
+        let prk = Salt::new(HKDF_SHA256, &[]).extract(&self.key);
+        let mut derived_key = vec![0u8; len];
+        // Output length is far below HKDF limit, so derivation doesn't expect failure.
+        prk.expand(&[info], DerivedKeyLen(len))
+            .unwrap()
+            .fill(&mut derived_key)
+            .unwrap();
+        derived_key
+    }
+
+    /// Get the key used to encrypt parquet files.
+    pub fn get_parquet_key(&self) -> Vec<u8> {
+        self.derive_key(PARQUET_KEY_INFO, PARQUET_KEY_LEN)
+    }
+
+    /// Get the key used to encrypt WAL, index block files and puffin blobs with AEAD stream.
+    pub(crate) fn get_aead_stream_key(&self) -> Vec<u8> {
+        self.derive_key(AEAD_STREAM_KEY_INFO, aead_stream::KEY_LEN)
+    }
+
+    /// Encrypt the given content as one AEAD stream.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Result<Vec<u8>> {
+        aead_stream::encrypt(&self.get_aead_stream_key(), plaintext)
+    }
+
+    /// Decrypt content encrypted by [`encrypt`], for example, index block files and puffin blobs.
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<Vec<u8>> {
+        aead_stream::decrypt(&self.get_aead_stream_key(), ciphertext)
+    }
+
+    /// Get parquet modular encryption properties, all columns are encrypted with the footer key.
+    /// Footer is kept in plaintext (but signed), so file metadata could be loaded without keys, for example, when importing into iceberg.
+    pub(crate) fn get_file_encryption_properties(&self) -> FileEncryptionProperties {
+        FileEncryptionProperties::builder(self.get_parquet_key())
+            .with_plaintext_footer(true)
+            .build()
+            .unwrap()
+    }
+
+    /// Get parquet modular decryption properties for files encrypted by [`get_file_encryption_properties`].
+    pub fn get_file_decryption_properties(&self) -> FileDecryptionProperties {
+        FileDecryptionProperties::builder(self.get_parquet_key())
+            .build()
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derived_keys() {
+        let data_key = DataEncryptionKey::generate().unwrap();
+        assert_eq!(data_key.get_parquet_key().len(), PARQUET_KEY_LEN);
+        assert_eq!(data_key.get_aead_stream_key().len(), aead_stream::KEY_LEN);
+        // Derivation is deterministic, and separated by usage.
+        assert_eq!(data_key.get_parquet_key(), data_key.get_parquet_key());
+        assert_ne!(
+            data_key.get_parquet_key(),
+            data_key.get_aead_stream_key()[..PARQUET_KEY_LEN]
+        );
+        // Keys are never exposed in debug output.
+        assert!(!format!("{data_key:?}").contains(&format!("{:?}", data_key.as_bytes())));
+
+        // One-shot AEAD stream roundtrip, which fails with other keys.
+        let content = b"index block".repeat(1000);
+        let ciphertext = data_key.encrypt(&content).unwrap();
+        assert_ne!(ciphertext, content);
+        assert_eq!(data_key.decrypt(&ciphertext).unwrap(), content);
+        assert!(DataEncryptionKey::generate()
+            .unwrap()
+            .decrypt(&ciphertext)
+            .is_err());
+
+        let reloaded_key = DataEncryptionKey::try_from_bytes(data_key.as_bytes()).unwrap();
+        assert_eq!(reloaded_key, data_key);
+        assert!(DataEncryptionKey::try_from_bytes(&[0u8; 16]).is_err());
+    }
+}
@@ -0,0 +1,286 @@
+/// A filesystem accessor wrapper, which encrypts every object with AEAD stream on write and decrypts it on read.
+///
+/// It's used for WAL files, which are only accessed through the accessor.
+/// Table files are encrypted by their writers instead (parquet modular encryption for data files, AEAD stream for index
+/// blocks and puffin blobs), so they're copied and cached as-is through plain accessors.
+use crate::storage::encryption::aead_stream::{
+    self, AeadStreamDecryptor, AeadStreamEncryptor, SEGMENT_SIZE,
+};
+use crate::storage::encryption::data_key::DataEncryptionKey;
+use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
+use crate::storage::filesystem::accessor::base_unbuffered_stream_writer::BaseUnbufferedStreamWriter;
+use crate::storage::filesystem::accessor::metadata::ObjectMetadata;
+use crate::Result;
+
+use async_stream::try_stream;
+use async_trait::async_trait;
+use futures::{Stream, StreamExt};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+use std::ops::Range;
+use std::pin::Pin;
+use std::sync::Arc;
+
+pub(crate) struct EncryptedFileSystemAccessor {
+    inner: Arc<dyn BaseFileSystemAccess>,
+    /// Key for AEAD stream.
+    key: Vec<u8>,
+}
+
+impl std::fmt::Debug for EncryptedFileSystemAccessor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedFileSystemAccessor")
+            .field("inner", &self.inner)
+            .field("key", &"xxxxx")
+            .finish()
+    }
+}
+
+impl EncryptedFileSystemAccessor {
+    pub(crate) fn new(inner: Arc<dyn BaseFileSystemAccess>, data_key: &DataEncryptionKey) -> Self {
+        Self {
+            inner,
+            key: data_key.get_aead_stream_key(),
+        }
+    }
+}
+
+/// Stream writer which encrypts appended content before handing it over to the inner writer.
+struct EncryptedUnbufferedStreamWriter {
+    inner: Box<dyn BaseUnbufferedStreamWriter>,
+    encryptor: AeadStreamEncryptor,
+}
+
+#[async_trait]
+impl BaseUnbufferedStreamWriter for EncryptedUnbufferedStreamWriter {
+    async fn append_non_blocking(&mut self, data: Vec<u8>) -> Result<()> {
+        let ciphertext = self.encryptor.update(&data)?;
+        if ciphertext.is_empty() {
+            return Ok(());
+        }
+        self.inner.append_non_blocking(ciphertext).await
+    }
+
+    async fn finalize(self: Box<Self>) -> Result<()> {
+        let Self {
+            mut inner,
+            encryptor,
+        } = *self;
+        inner.append_non_blocking(encryptor.finalize()?).await?;
+        inner.finalize().await
+    }
+}
+
+#[async_trait]
+impl BaseFileSystemAccess for EncryptedFileSystemAccessor {
+    async fn list_direct_subdirectories(&self, folder: &str) -> Result<Vec<String>> {
+        self.inner.list_direct_subdirectories(folder).await
+    }
+
+    async fn list_objects_recursively(&self, folder: &str) -> Result<Vec<String>> {
+        self.inner.list_objects_recursively(folder).await
+    }
+
+    async fn remove_directory(&self, directory: &str) -> Result<()> {
+        self.inner.remove_directory(directory).await
+    }
+
+    async fn object_exists(&self, object: &str) -> Result<bool> {
+        self.inner.object_exists(object).await
+    }
+
+    /// Notice, returned size is the encrypted size.
+    async fn stats_object(&self, object: &str) -> Result<opendal::Metadata> {
+        self.inner.stats_object(object).await
+    }
+
+    async fn read_object(&self, object: &str) -> Result<Vec<u8>> {
+        let content = self.inner.read_object(object).await?;
+        aead_stream::decrypt(&self.key, &content)
+    }
+
+    async fn read_object_as_string(&self, object: &str) -> Result<String> {
+        let bytes = self.read_object(object).await?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    /// Notice, the whole object is read and decrypted, since AEAD stream segments are authenticated as a whole.
+    async fn read_object_range(&self, object: &str, range: Range<u64>) -> Result<Vec<u8>> {
+        let content = self.read_object(object).await?;
+        let end = (range.end as usize).min(content.len());
+        let start = (range.start as usize).min(end);
+        Ok(content[start..end].to_vec())
+    }
+
+    async fn stream_read(
+        &self,
+        object: &str,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<Vec<u8>>> + Send>>> {
+        let mut inner_stream = self.inner.stream_read(object).await?;
+        let mut decryptor = AeadStreamDecryptor::new(&self.key)?;
+        let stream = try_stream! {
+            while let Some(chunk) = inner_stream.next().await {
+                let plaintext = decryptor.update(&chunk?)?;
+                if !plaintext.is_empty() {
+                    yield plaintext;
+                }
+            }
+            yield decryptor.finalize()?;
+        };
+        Ok(Box::pin(stream))
+    }
+
+    async fn write_object(&self, object: &str, content: Vec<u8>) -> Result<opendal::Metadata> {
+        let content = aead_stream::encrypt(&self.key, &content)?;
+        self.inner.write_object(object, content).await
+    }
+
+    async fn conditional_write_object(
+        &self,
+        object: &str,
+        content: Vec<u8>,
+        etag: Option<String>,
+    ) -> Result<opendal::Metadata> {
+        let content = aead_stream::encrypt(&self.key, &content)?;
+        self.inner
+            .conditional_write_object(object, content, etag)
+            .await
+    }
+
+    async fn create_unbuffered_stream_writer(
+        &self,
+        object: &str,
+    ) -> Result<Box<dyn BaseUnbufferedStreamWriter>> {
+        let inner = self.inner.create_unbuffered_stream_writer(object).await?;
+        Ok(Box::new(EncryptedUnbufferedStreamWriter {
+            inner,
+            encryptor: AeadStreamEncryptor::new(&self.key)?,
+        }))
+    }
+
+    async fn delete_object(&self, object: &str) -> Result<()> {
+        self.inner.delete_object(object).await
+    }
+
+    /// Local file is plaintext, which gets encrypted on upload.
+    async fn copy_from_local_to_remote(&self, src: &str, dst: &str) -> Result<ObjectMetadata> {
+        let mut file = tokio::fs::File::open(src).await?;
+        let mut writer = self.create_unbuffered_stream_writer(dst).await?;
+        let mut buffer = vec![0u8; SEGMENT_SIZE];
+        let mut total_size = 0u64;
+        loop {
+            let n = file.read(&mut buffer).await?;
+            if n == 0 {
+                break;
+            }
+            writer.append_non_blocking(buffer[..n].to_vec()).await?;
+            total_size += n as u64;
+        }
+        writer.finalize().await?;
+        Ok(ObjectMetadata { size: total_size })
+    }
+
+    /// Counterpart of [`copy_from_local_to_remote`], returned size is the plaintext size.
+    async fn copy_from_remote_to_local(&self, src: &str, dst: &str) -> Result<ObjectMetadata> {
+        let mut stream = self.stream_read(src).await?;
+        let mut file = tokio::fs::File::create(dst).await?;
+        let mut total_size = 0u64;
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk?;
+            file.write_all(&chunk).await?;
+            total_size += chunk.len() as u64;
+        }
+        file.flush().await?;
+        Ok(ObjectMetadata { size: total_size })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::FileSystemAccessor;
+
+    fn create_encrypted_filesystem_accessor(
+        temp_dir: &tempfile::TempDir,
+    ) -> EncryptedFileSystemAccessor {
+        EncryptedFileSystemAccessor::new(
+            FileSystemAccessor::default_for_test(temp_dir),
+            &DataEncryptionKey::generate().unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_object_read_write() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let filesystem_accessor = create_encrypted_filesystem_accessor(&temp_dir);
+        let content = b"wal content".repeat(10000);
+
+        // Objects are encrypted at rest, and decrypted transparently.
+        filesystem_accessor
+            .write_object("wal/wal_0.json", content.clone())
+            .await
+            .unwrap();
+        let raw_content = tokio::fs::read(temp_dir.path().join("wal/wal_0.json"))
+            .await
+            .unwrap();
+        assert!(!raw_content
+            .windows(b"wal content".len())
+            .any(|window| window == b"wal content"));
+        assert_eq!(
+            filesystem_accessor
+                .read_object("wal/wal_0.json")
+                .await
+                .unwrap(),
+            content
+        );
+        let mut stream = filesystem_accessor
+            .stream_read("wal/wal_0.json")
+            .await
+            .unwrap();
+        let mut streamed_content = vec![];
+        while let Some(chunk) = stream.next().await {
+            streamed_content.extend(chunk.unwrap());
+        }
+        assert_eq!(streamed_content, content);
+
+        // Objects are encrypted regardless of their names.
+        filesystem_accessor
+            .write_object("data.parquet", content.clone())
+            .await
+            .unwrap();
+        let raw_content = tokio::fs::read(temp_dir.path().join("data.parquet"))
+            .await
+            .unwrap();
+        assert_ne!(raw_content, content);
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_copy_between_local_and_remote() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let filesystem_accessor = create_encrypted_filesystem_accessor(&temp_dir);
+        let content = (0..3 * SEGMENT_SIZE + 5)
+            .map(|idx| (idx % 256) as u8)
+            .collect::<Vec<_>>();
+        let local_filepath = temp_dir.path().join("wal_local.json");
+        tokio::fs::write(&local_filepath, &content).await.unwrap();
+
+        let object_metadata = filesystem_accessor
+            .copy_from_local_to_remote(local_filepath.to_str().unwrap(), "wal_remote.json")
+            .await
+            .unwrap();
+        assert_eq!(object_metadata.size, content.len() as u64);
+        let raw_content = tokio::fs::read(temp_dir.path().join("wal_remote.json"))
+            .await
+            .unwrap();
+        assert_ne!(raw_content, content);
+
+        // Copy back decrypts to plaintext.
+        let cache_filepath = temp_dir.path().join("wal_copy.json");
+        let object_metadata = filesystem_accessor
+            .copy_from_remote_to_local("wal_remote.json", cache_filepath.to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(object_metadata.size, content.len() as u64);
+        assert_eq!(tokio::fs::read(&cache_filepath).await.unwrap(), content);
+    }
+}
@@ -0,0 +1,48 @@
+/// Configuration for client-side table encryption.
+use serde::{Deserialize, Serialize};
+
+/// Where master keys live.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub enum KeyProviderConfig {
+    /// Master keys are stored as files under the given local directory, named after key id.
+    #[serde(rename = "local_file")]
+    LocalFile { directory: String },
+}
+
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct EncryptionConfig {
+    /// Master key used to wrap data key for newly created tables.
+    /// Existing tables keep using the master key recorded along with their wrapped data key, until rotated.
+    pub master_key_id: String,
+    /// Key provider which holds master keys.
+    pub key_provider: KeyProviderConfig,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde_json::json;
+
+    #[test]
+    fn test_deserialize_encryption_config() {
+        let input = json!({
+            "master_key_id": "key-1",
+            "key_provider": {
+                "local_file": {
+                    "directory": "/tmp/moonlink_keys"
+                }
+            }
+        });
+        let config: EncryptionConfig = serde_json::from_value(input).unwrap();
+        assert_eq!(
+            config,
+            EncryptionConfig {
+                master_key_id: "key-1".to_string(),
+                key_provider: KeyProviderConfig::LocalFile {
+                    directory: "/tmp/moonlink_keys".to_string(),
+                },
+            }
+        );
+    }
+}
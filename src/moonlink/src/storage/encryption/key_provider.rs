@@ -0,0 +1,164 @@
+/// This module defines the interface for master key providers, which wrap and unwrap per-table data keys.
+use crate::storage::encryption::encryption_config::KeyProviderConfig;
+use crate::{Error, Result};
+
+use async_trait::async_trait;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use ring::rand::{SecureRandom, SystemRandom};
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+/// Master keys never leave the key provider, callers only get data keys wrapped or unwrapped.
+#[async_trait]
+pub trait BaseKeyProvider: std::fmt::Debug + Send + Sync {
+    /// Wrap the given data key with the master key identified by [`master_key_id`].
+    async fn wrap_data_key(&self, master_key_id: &str, data_key: &[u8]) -> Result<Vec<u8>>;
+
+    /// Unwrap the given wrapped data key with the master key identified by [`master_key_id`].
+    async fn unwrap_data_key(&self, master_key_id: &str, wrapped_key: &[u8]) -> Result<Vec<u8>>;
+}
+
+/// A factory function to create a key provider based on the given [`config`].
+pub(crate) fn create_key_provider(config: &KeyProviderConfig) -> Arc<dyn BaseKeyProvider> {
+    match config {
+        KeyProviderConfig::LocalFile { directory } => {
+            Arc::new(LocalFileKeyProvider::new(directory.clone()))
+        }
+    }
+}
+
+/// Key provider which reads master keys from local files, where each file holds a raw 32-byte key and is named after its key id.
+/// It's made for testing and single-node deployment; production deployment should keep master keys in a KMS.
+#[derive(Debug)]
+pub struct LocalFileKeyProvider {
+    directory: PathBuf,
+}
+
+impl LocalFileKeyProvider {
+    pub fn new(directory: String) -> Self {
+        Self {
+            directory: PathBuf::from(directory),
+        }
+    }
+
+    async fn load_master_key(&self, master_key_id: &str) -> Result<LessSafeKey> {
+        // Key id is used as filename, reject anything which could escape the key directory.
+        if master_key_id.is_empty()
+            || master_key_id.contains(std::path::is_separator)
+            || master_key_id.starts_with('.')
+        {
+            return Err(Error::encryption_error(format!(
+                "Invalid master key id {master_key_id}"
+            )));
+        }
+        let master_key = tokio::fs::read(self.directory.join(master_key_id)).await?;
+        let unbound_key = UnboundKey::new(&AES_256_GCM, &master_key).map_err(|_| {
+            Error::encryption_error(format!(
+                "Master key {master_key_id} should be 32 bytes, but got {} bytes",
+                master_key.len()
+            ))
+        })?;
+        Ok(LessSafeKey::new(unbound_key))
+    }
+
+    /// Generate a new random master key with the given id.
+    pub async fn create_master_key(&self, master_key_id: &str) -> Result<()> {
+        let mut master_key = [0u8; 32];
+        SystemRandom::new()
+            .fill(&mut master_key)
+            .map_err(|_| Error::encryption_error("Failed to generate master key".to_string()))?;
+        tokio::fs::create_dir_all(&self.directory).await?;
+        tokio::fs::write(self.directory.join(master_key_id), master_key).await?;
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl BaseKeyProvider for LocalFileKeyProvider {
+    /// Wrapped key is laid out as | 12-byte nonce | encrypted data key | 16-byte tag |, with master key id as associated data.
+    async fn wrap_data_key(&self, master_key_id: &str, data_key: &[u8]) -> Result<Vec<u8>> {
+        let master_key = self.load_master_key(master_key_id).await?;
+        let mut nonce = [0u8; NONCE_LEN];
+        SystemRandom::new()
+            .fill(&mut nonce)
+            .map_err(|_| Error::encryption_error("Failed to generate nonce".to_string()))?;
+        let mut wrapped_key = data_key.to_vec();
+        master_key
+            .seal_in_place_append_tag(
+                Nonce::assume_unique_for_key(nonce),
+                Aad::from(master_key_id.as_bytes()),
+                &mut wrapped_key,
+            )
+            .map_err(|_| Error::encryption_error("Failed to wrap data key".to_string()))?;
+        let mut output = nonce.to_vec();
+        output.extend(wrapped_key);
+        Ok(output)
+    }
+
+    async fn unwrap_data_key(&self, master_key_id: &str, wrapped_key: &[u8]) -> Result<Vec<u8>> {
+        let master_key = self.load_master_key(master_key_id).await?;
+        if wrapped_key.len() < NONCE_LEN {
+            return Err(Error::encryption_error(
+                "Wrapped data key is truncated".to_string(),
+            ));
+        }
+        let (nonce, wrapped_key) = wrapped_key.split_at(NONCE_LEN);
+        let mut data_key = wrapped_key.to_vec();
+        let data_key_len = master_key
+            .open_in_place(
+                Nonce::try_assume_unique_for_key(nonce).unwrap(),
+                Aad::from(master_key_id.as_bytes()),
+                &mut data_key,
+            )
+            .map_err(|_| {
+                Error::encryption_error(format!(
+                    "Failed to unwrap data key with master key {master_key_id}"
+                ))
+            })?
+            .len();
+        data_key.truncate(data_key_len);
+        Ok(data_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_local_file_key_provider() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let key_provider = LocalFileKeyProvider::new(temp_dir.path().to_str().unwrap().to_string());
+        key_provider.create_master_key("key-1").await.unwrap();
+        key_provider.create_master_key("key-2").await.unwrap();
+
+        let data_key = vec![3u8; 32];
+        let wrapped_key = key_provider
+            .wrap_data_key("key-1", &data_key)
+            .await
+            .unwrap();
+        assert_ne!(wrapped_key[NONCE_LEN..NONCE_LEN + 32], data_key[..]);
+        assert_eq!(
+            key_provider
+                .unwrap_data_key("key-1", &wrapped_key)
+                .await
+                .unwrap(),
+            data_key
+        );
+
+        // Wrong master key, unknown master key, or invalid key id fails.
+        assert!(key_provider
+            .unwrap_data_key("key-2", &wrapped_key)
+            .await
+            .is_err());
+        assert!(key_provider
+            .unwrap_data_key("key-3", &wrapped_key)
+            .await
+            .is_err());
+        assert!(key_provider
+            .wrap_data_key("../key-1", &data_key)
+            .await
+            .is_err());
+    }
+}
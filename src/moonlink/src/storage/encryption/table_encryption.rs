@@ -0,0 +1,308 @@
+/// This module manages the envelope-encrypted data key for a mooncake table.
+///
+/// Each table owns a random data key, which is persisted next to table data only in wrapped form, with the id of master key wrapping it.
+/// Rotating master key re-wraps the data key, so data files, index files and WAL don't need to be rewritten.
+use crate::storage::encryption::data_key::DataEncryptionKey;
+use crate::storage::encryption::encrypted_filesystem_accessor::EncryptedFileSystemAccessor;
+use crate::storage::encryption::encryption_config::{EncryptionConfig, KeyProviderConfig};
+use crate::storage::encryption::key_provider::{create_key_provider, BaseKeyProvider};
+use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
+use crate::{Error, Result};
+
+use moonlink_table_metadata::DataKeyEnvelope;
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::info;
+
+use std::sync::{Arc, RwLock};
+
+/// Folder for wrapped data keys, relative to storage root.
+const DEFAULT_ENCRYPTION_FOLDER: &str = "encryption";
+
+/// Persisted wrapped data key.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct WrappedDataKey {
+    /// Id of the master key which wraps the data key.
+    master_key_id: String,
+    /// Data key wrapped by key provider.
+    wrapped_key: Vec<u8>,
+}
+
+/// Encryption state of one table, shared by table writers, readers and key rotation.
+pub struct TableEncryption {
+    /// Key provider which holds master keys.
+    key_provider: Arc<dyn BaseKeyProvider>,
+    /// Plain accessor to persist wrapped data key.
+    filesystem_accessor: Arc<dyn BaseFileSystemAccess>,
+    /// Mooncake table id.
+    mooncake_table_id: String,
+    /// Unwrapped data key.
+    data_key: DataEncryptionKey,
+    /// Current wrapped data key, updated at master key rotation.
+    wrapped_data_key: RwLock<WrappedDataKey>,
+    /// Serializes master key rotations.
+    rotation_lock: Mutex<()>,
+}
+
+impl std::fmt::Debug for TableEncryption {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TableEncryption")
+            .field("mooncake_table_id", &self.mooncake_table_id)
+            .field("data_key", &self.data_key)
+            .finish()
+    }
+}
+
+/// Table encryption is identified by its data key, compared when table configs are compared.
+impl PartialEq for TableEncryption {
+    fn eq(&self, other: &Self) -> bool {
+        self.data_key == other.data_key
+    }
+}
+
+impl TableEncryption {
+    pub fn get_data_key_filepath(mooncake_table_id: &str) -> String {
+        format!("{DEFAULT_ENCRYPTION_FOLDER}/{mooncake_table_id}/data_key.json")
+    }
+
+    async fn load_wrapped_data_key(
+        filesystem_accessor: &dyn BaseFileSystemAccess,
+        data_key_filepath: &str,
+    ) -> Result<Option<WrappedDataKey>> {
+        if !filesystem_accessor.object_exists(data_key_filepath).await? {
+            return Ok(None);
+        }
+        let content = filesystem_accessor.read_object(data_key_filepath).await?;
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+
+    async fn unwrap_data_key(
+        key_provider: &dyn BaseKeyProvider,
+        master_key_id: &str,
+        wrapped_key: &[u8],
+    ) -> Result<DataEncryptionKey> {
+        let data_key = key_provider
+            .unwrap_data_key(master_key_id, wrapped_key)
+            .await?;
+        DataEncryptionKey::try_from_bytes(&data_key)
+    }
+
+    /// Load data key for the given table, or create one if the table doesn't have one yet.
+    ///
+    /// # Arguments
+    ///
+    /// * filesystem_accessor: plain accessor to persist wrapped data key, which shouldn't be encrypted by itself.
+    pub async fn load_or_create(
+        config: &EncryptionConfig,
+        filesystem_accessor: Arc<dyn BaseFileSystemAccess>,
+        mooncake_table_id: &str,
+    ) -> Result<Arc<Self>> {
+        let key_provider = create_key_provider(&config.key_provider);
+        let data_key_filepath = Self::get_data_key_filepath(mooncake_table_id);
+        let (data_key, wrapped_data_key) =
+            match Self::load_wrapped_data_key(filesystem_accessor.as_ref(), &data_key_filepath)
+                .await?
+            {
+                Some(wrapped_data_key) => {
+                    let data_key = Self::unwrap_data_key(
+                        key_provider.as_ref(),
+                        &wrapped_data_key.master_key_id,
+                        &wrapped_data_key.wrapped_key,
+                    )
+                    .await?;
+                    (data_key, wrapped_data_key)
+                }
+                None => {
+                    let data_key = DataEncryptionKey::generate()?;
+                    let wrapped_data_key = WrappedDataKey {
+                        master_key_id: config.master_key_id.clone(),
+                        wrapped_key: key_provider
+                            .wrap_data_key(&config.master_key_id, data_key.as_bytes())
+                            .await?,
+                    };
+                    // Put-if-absent, so concurrent creation never overwrites a data key which might have been used.
+                    filesystem_accessor
+                        .conditional_write_object(
+                            &data_key_filepath,
+                            serde_json::to_vec(&wrapped_data_key)?,
+                            /*etag=*/ None,
+                        )
+                        .await?;
+                    (data_key, wrapped_data_key)
+                }
+            };
+        Ok(Arc::new(Self {
+            key_provider,
+            filesystem_accessor,
+            mooncake_table_id: mooncake_table_id.to_string(),
+            data_key,
+            wrapped_data_key: RwLock::new(wrapped_data_key),
+            rotation_lock: Mutex::new(()),
+        }))
+    }
+
+    /// Unwrap the data key handed over by read state, with the reader's own key provider.
+    pub async fn unwrap_data_key_envelope(
+        key_provider_config: &KeyProviderConfig,
+        data_key_envelope: &DataKeyEnvelope,
+    ) -> Result<DataEncryptionKey> {
+        let key_provider = create_key_provider(key_provider_config);
+        Self::unwrap_data_key(
+            key_provider.as_ref(),
+            &data_key_envelope.master_key_id,
+            &data_key_envelope.wrapped_key,
+        )
+        .await
+    }
+
+    /// Re-wrap data key with a new master key.
+    /// Persisted data stays untouched, since data key keeps unchanged; the old master key could be retired once readers
+    /// holding read states wrapped by it are done.
+    pub async fn rotate_master_key(&self, new_master_key_id: &str) -> Result<()> {
+        let _guard = self.rotation_lock.lock().await;
+        let old_master_key_id = self.wrapped_data_key.read().unwrap().master_key_id.clone();
+        if old_master_key_id == new_master_key_id {
+            return Err(Error::encryption_error(format!(
+                "Table {} data key is already wrapped by master key {new_master_key_id}",
+                self.mooncake_table_id
+            )));
+        }
+        let new_wrapped_data_key = WrappedDataKey {
+            master_key_id: new_master_key_id.to_string(),
+            wrapped_key: self
+                .key_provider
+                .wrap_data_key(new_master_key_id, self.data_key.as_bytes())
+                .await?,
+        };
+        self.filesystem_accessor
+            .write_object(
+                &Self::get_data_key_filepath(&self.mooncake_table_id),
+                serde_json::to_vec(&new_wrapped_data_key)?,
+            )
+            .await?;
+        *self.wrapped_data_key.write().unwrap() = new_wrapped_data_key;
+        info!(
+            mooncake_table_id = %self.mooncake_table_id,
+            %old_master_key_id,
+            new_master_key_id,
+            "rotated master key for table data key"
+        );
+        Ok(())
+    }
+
+    pub fn get_data_key(&self) -> &DataEncryptionKey {
+        &self.data_key
+    }
+
+    /// Get the currently wrapped data key, which is handed over to readers instead of the data key itself.
+    pub fn get_data_key_envelope(&self) -> DataKeyEnvelope {
+        let wrapped_data_key = self.wrapped_data_key.read().unwrap();
+        DataKeyEnvelope {
+            master_key_id: wrapped_data_key.master_key_id.clone(),
+            wrapped_key: wrapped_data_key.wrapped_key.clone(),
+        }
+    }
+
+    /// Wrap the given WAL accessor, so every WAL file gets encrypted on write and decrypted on read.
+    pub fn wrap_wal_filesystem_accessor(
+        &self,
+        filesystem_accessor: Arc<dyn BaseFileSystemAccess>,
+    ) -> Arc<dyn BaseFileSystemAccess> {
+        Arc::new(EncryptedFileSystemAccessor::new(
+            filesystem_accessor,
+            &self.data_key,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::encryption::key_provider::LocalFileKeyProvider;
+    use crate::storage::encryption::test_utils::*;
+    use crate::FileSystemAccessor;
+
+    #[tokio::test]
+    async fn test_load_create_and_rotate_data_key() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let config = create_test_encryption_config(&temp_dir).await;
+        let KeyProviderConfig::LocalFile { directory } = config.key_provider.clone();
+        LocalFileKeyProvider::new(directory)
+            .create_master_key("key-2")
+            .await
+            .unwrap();
+        let filesystem_accessor = FileSystemAccessor::default_for_test(&temp_dir);
+
+        // Data key is created on first load, and kept for later loads.
+        let table_encryption =
+            TableEncryption::load_or_create(&config, filesystem_accessor.clone(), "table")
+                .await
+                .unwrap();
+        let reloaded_table_encryption =
+            TableEncryption::load_or_create(&config, filesystem_accessor.clone(), "table")
+                .await
+                .unwrap();
+        assert_eq!(
+            table_encryption.get_data_key(),
+            reloaded_table_encryption.get_data_key()
+        );
+        let other_table_encryption =
+            TableEncryption::load_or_create(&config, filesystem_accessor.clone(), "other_table")
+                .await
+                .unwrap();
+        assert_ne!(
+            table_encryption.get_data_key(),
+            other_table_encryption.get_data_key()
+        );
+
+        // Readers get the wrapped data key only, which unwraps to the same data key.
+        let data_key_envelope = table_encryption.get_data_key_envelope();
+        assert_eq!(data_key_envelope.master_key_id, TEST_MASTER_KEY_ID);
+        assert!(!data_key_envelope
+            .wrapped_key
+            .windows(table_encryption.get_data_key().as_bytes().len())
+            .any(|window| window == table_encryption.get_data_key().as_bytes()));
+        assert_eq!(
+            &TableEncryption::unwrap_data_key_envelope(&config.key_provider, &data_key_envelope)
+                .await
+                .unwrap(),
+            table_encryption.get_data_key()
+        );
+
+        // Rotation re-wraps the same data key, which is readable after the old master key is gone.
+        table_encryption.rotate_master_key("key-2").await.unwrap();
+        assert!(table_encryption.rotate_master_key("key-2").await.is_err());
+        assert_eq!(
+            table_encryption.get_data_key_envelope().master_key_id,
+            "key-2"
+        );
+        tokio::fs::remove_file(temp_dir.path().join("keys").join(TEST_MASTER_KEY_ID))
+            .await
+            .unwrap();
+        let rotated_table_encryption =
+            TableEncryption::load_or_create(&config, filesystem_accessor.clone(), "table")
+                .await
+                .unwrap();
+        assert_eq!(
+            table_encryption.get_data_key(),
+            rotated_table_encryption.get_data_key()
+        );
+        assert!(TableEncryption::unwrap_data_key_envelope(
+            &config.key_provider,
+            &table_encryption.get_data_key_envelope()
+        )
+        .await
+        .is_ok());
+        assert!(TableEncryption::unwrap_data_key_envelope(
+            &config.key_provider,
+            &data_key_envelope
+        )
+        .await
+        .is_err());
+        assert!(
+            TableEncryption::load_or_create(&config, filesystem_accessor, "other_table")
+                .await
+                .is_err()
+        );
+    }
+}
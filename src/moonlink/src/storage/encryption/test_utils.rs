@@ -0,0 +1,41 @@
+/// Test utils for table encryption.
+use crate::storage::encryption::encryption_config::{EncryptionConfig, KeyProviderConfig};
+use crate::storage::encryption::key_provider::LocalFileKeyProvider;
+use crate::storage::encryption::table_encryption::TableEncryption;
+use crate::FileSystemAccessor;
+
+use std::sync::Arc;
+
+/// Test master key id.
+pub(crate) const TEST_MASTER_KEY_ID: &str = "key-1";
+
+/// Create encryption config with a local file key provider under the given directory, which holds [`TEST_MASTER_KEY_ID`].
+pub(crate) async fn create_test_encryption_config(
+    temp_dir: &tempfile::TempDir,
+) -> EncryptionConfig {
+    let key_directory = temp_dir.path().join("keys").to_str().unwrap().to_string();
+    LocalFileKeyProvider::new(key_directory.clone())
+        .create_master_key(TEST_MASTER_KEY_ID)
+        .await
+        .unwrap();
+    EncryptionConfig {
+        master_key_id: TEST_MASTER_KEY_ID.to_string(),
+        key_provider: KeyProviderConfig::LocalFile {
+            directory: key_directory,
+        },
+    }
+}
+
+/// Create table encryption, with its wrapped data key persisted under the given directory.
+pub(crate) async fn create_test_table_encryption(
+    temp_dir: &tempfile::TempDir,
+) -> Arc<TableEncryption> {
+    let config = create_test_encryption_config(temp_dir).await;
+    TableEncryption::load_or_create(
+        &config,
+        FileSystemAccessor::default_for_test(temp_dir),
+        "test_table",
+    )
+    .await
+    .unwrap()
+}
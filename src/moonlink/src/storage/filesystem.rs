@@ -1,5 +1,7 @@
 pub(crate) mod accessor;
 pub mod accessor_config;
+#[cfg(feature = "storage-azure")]
+pub(crate) mod azure;
 #[cfg(feature = "storage-gcs")]
 pub(crate) mod gcs;
 #[cfg(feature = "storage-s3")]
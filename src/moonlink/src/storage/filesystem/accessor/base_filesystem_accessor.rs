@@ -6,6 +6,7 @@ use crate::Result;
 use async_trait::async_trait;
 use futures::Stream;
 
+use std::ops::Range;
 use std::pin::Pin;
 
 #[cfg(test)]
@@ -24,6 +25,11 @@ pub trait BaseFileSystemAccess: std::fmt::Debug + Send + Sync {
     /// For example, we have directory "a", "a/b", "a/b/c", listing direct subdirectories for "a" will return "a/b".
     async fn list_direct_subdirectories(&self, folder: &str) -> Result<Vec<String>>;
 
+    /// List all objects under the given directory recursively, returned paths are prefixed with the given directory.
+    ///
+    /// For example, we have object "a/b/c.parquet", listing objects for "a" will return "a/b/c.parquet".
+    async fn list_objects_recursively(&self, folder: &str) -> Result<Vec<String>>;
+
     /// Remove the whole directory recursively.
     async fn remove_directory(&self, directory: &str) -> Result<()>;
 
@@ -42,6 +48,8 @@ pub trait BaseFileSystemAccess: std::fmt::Debug + Send + Sync {
     async fn read_object(&self, object: &str) -> Result<Vec<u8>>;
     /// Similar to [`read_object`], but return content in string format.
     async fn read_object_as_string(&self, object: &str) -> Result<String>;
+    /// Read the given byte range for the object, i.e. parquet footer.
+    async fn read_object_range(&self, object: &str, range: Range<u64>) -> Result<Vec<u8>>;
 
     /// Stream read the content for the given object.
     /// It's suitable for large objects.
@@ -78,7 +86,7 @@ pub trait BaseFileSystemAccess: std::fmt::Debug + Send + Sync {
         object_filepath: &str,
     ) -> Result<Box<dyn BaseUnbufferedStreamWriter>>;
 
-    /// Delete the given object.
+    /// Delete the given object, which succeeds if the object doesn't exist.
     async fn delete_object(&self, object_filepath: &str) -> Result<()>;
 
     /// Copy from local file [`src`] to remote file [`dst`].
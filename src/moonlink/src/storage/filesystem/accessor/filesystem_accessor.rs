@@ -24,6 +24,7 @@ use crate::storage::filesystem::accessor_config::AccessorConfig;
 use crate::storage::filesystem::storage_config::StorageConfig;
 use crate::Result;
 
+use std::ops::Range;
 use std::pin::Pin;
 
 /// IO block size for parallel read and write.
@@ -177,6 +178,33 @@ impl BaseFileSystemAccess for FileSystemAccessor {
         Ok(dirs)
     }
 
+    async fn list_objects_recursively(&self, folder: &str) -> Result<Vec<String>> {
+        let folder = folder.trim_end_matches('/');
+        let sanitized_folder = self.sanitize_path(folder);
+        let prefix = format!("{sanitized_folder}/");
+        let mut objects = Vec::new();
+        let entries = self
+            .get_operator()
+            .await?
+            .list_with(&prefix)
+            .recursive(true)
+            .await?;
+        // Listed paths are relative to operator root, which don't carry leading slash.
+        let listed_prefix = prefix.trim_start_matches('/');
+        for cur_entry in entries.iter() {
+            if cur_entry.metadata().is_dir() {
+                continue;
+            }
+            let relative_path = cur_entry
+                .path()
+                .strip_prefix(listed_prefix)
+                .unwrap_or(cur_entry.path());
+            objects.push(format!("{folder}/{relative_path}"));
+        }
+
+        Ok(objects)
+    }
+
     // TODO(hjiang): Remove this test function once fake-gcs fix the sending empty body will be error issue.
     #[cfg(all(feature = "storage-gcs", test))]
     async fn remove_directory(&self, directory: &str) -> Result<()> {
@@ -250,6 +278,16 @@ impl BaseFileSystemAccess for FileSystemAccessor {
         let bytes = self.read_object(object).await?;
         Ok(String::from_utf8(bytes)?)
     }
+    async fn read_object_range(&self, object: &str, range: Range<u64>) -> Result<Vec<u8>> {
+        let sanitized_object = self.sanitize_path(object);
+        let content = self
+            .get_operator()
+            .await?
+            .read_with(sanitized_object)
+            .range(range)
+            .await?;
+        Ok(content.to_vec())
+    }
 
     async fn stream_read(
         &self,
@@ -344,8 +382,12 @@ impl BaseFileSystemAccess for FileSystemAccessor {
     async fn delete_object(&self, object: &str) -> Result<()> {
         let sanitized_object = self.sanitize_path(object);
         let operator = self.get_operator().await?;
-        operator.delete(sanitized_object).await?;
-        Ok(())
+        // Deleting an already deleted object is a no-op, so retried deletion is idempotent.
+        match operator.delete(sanitized_object).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == opendal::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
     }
 
     async fn copy_from_local_to_remote(&self, src: &str, dst: &str) -> Result<ObjectMetadata> {
@@ -527,6 +569,76 @@ mod tests {
         assert_eq!(actual_content, random_content.as_bytes().to_vec());
     }
 
+    #[tokio::test]
+    async fn test_delete_object() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root_directory = temp_dir.path().to_str().unwrap().to_string();
+        let storage_config = StorageConfig::FileSystem {
+            root_directory: root_directory.clone(),
+            atomic_write_dir: None,
+        };
+        let filesystem_accessor = create_filesystem_accessor(
+            AccessorConfig::new_with_storage_config(storage_config.clone()),
+        );
+
+        const DST_FILENAME: &str = "target";
+        filesystem_accessor
+            .write_object(DST_FILENAME, create_random_string(10).as_bytes().to_vec())
+            .await
+            .unwrap();
+        filesystem_accessor
+            .delete_object(DST_FILENAME)
+            .await
+            .unwrap();
+        assert!(!filesystem_accessor
+            .object_exists(DST_FILENAME)
+            .await
+            .unwrap());
+
+        // Deleting a non-existent object succeeds.
+        filesystem_accessor
+            .delete_object(DST_FILENAME)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_objects_recursively() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let root_directory = temp_dir.path().to_str().unwrap().to_string();
+        let storage_config = StorageConfig::FileSystem {
+            root_directory: root_directory.clone(),
+            atomic_write_dir: None,
+        };
+        let filesystem_accessor = create_filesystem_accessor(
+            AccessorConfig::new_with_storage_config(storage_config.clone()),
+        );
+
+        for filepath in ["table/data/a.parquet", "table/metadata/b.avro", "other/c"] {
+            filesystem_accessor
+                .write_object(
+                    &format!("{root_directory}/{filepath}"),
+                    create_random_string(10).as_bytes().to_vec(),
+                )
+                .await
+                .unwrap();
+        }
+
+        // Listed objects carry the same prefix as the given directory.
+        let mut objects = filesystem_accessor
+            .list_objects_recursively(&format!("{root_directory}/table"))
+            .await
+            .unwrap();
+        objects.sort();
+        assert_eq!(
+            objects,
+            vec![
+                format!("{root_directory}/table/data/a.parquet"),
+                format!("{root_directory}/table/metadata/b.avro"),
+            ]
+        );
+    }
+
     // Local filesystem doesn't support conditional write, which should behave the same as [`write_object`].
     #[tokio::test]
     async fn test_conditional_write() {
@@ -3,6 +3,8 @@ use crate::storage::filesystem::accessor_config::AccessorConfig;
 use crate::storage::filesystem::accessor_config::RetryConfig;
 use crate::storage::filesystem::accessor_config::ThrottleConfig;
 use crate::storage::filesystem::accessor_config::TimeoutConfig;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::storage_config::get_default_azblob_endpoint;
 use crate::storage::filesystem::storage_config::StorageConfig;
 use crate::Result;
 
@@ -78,6 +80,29 @@ fn create_opendal_operator_impl(storage_config: &StorageConfig) -> Result<Operat
             }
             Ok(Operator::new(builder)?.finish())
         }
+        #[cfg(feature = "storage-azure")]
+        StorageConfig::Azblob {
+            account_name,
+            container,
+            account_key,
+            sas_token,
+            endpoint,
+        } => {
+            let endpoint = endpoint
+                .clone()
+                .unwrap_or_else(|| get_default_azblob_endpoint(account_name));
+            let mut builder = services::Azblob::default()
+                .root("/")
+                .container(container)
+                .account_name(account_name)
+                .endpoint(&endpoint);
+            if let Some(account_key) = account_key {
+                builder = builder.account_key(account_key);
+            } else if let Some(sas_token) = sas_token {
+                builder = builder.sas_token(sas_token);
+            }
+            Ok(Operator::new(builder)?.finish())
+        }
     }
 }
 
@@ -0,0 +1,9 @@
+#[cfg(feature = "storage-azure")]
+#[cfg(test)]
+pub(crate) mod azure_test_utils;
+#[cfg(feature = "storage-azure")]
+#[cfg(test)]
+pub(crate) mod test_guard;
+#[cfg(feature = "storage-azure")]
+#[cfg(test)]
+pub(crate) mod tests;
@@ -0,0 +1,155 @@
+use crate::storage::filesystem::accessor_config::AccessorConfig;
+use crate::storage::filesystem::storage_config::StorageConfig;
+use crate::storage::filesystem::test_utils::object_storage_test_utils::*;
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use backon::{ExponentialBuilder, Retryable};
+use base64::engine::general_purpose::STANDARD as base64;
+use base64::Engine;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use iceberg::{Error as IcebergError, Result as IcebergResult};
+use reqwest::{Method, StatusCode};
+use sha2::Sha256;
+use tokio::time::sleep;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Azurite related constants.
+///
+/// Azurite only serves the well-known development account, whose key is public.
+pub(crate) static AZURE_TEST_CONTAINER_PREFIX: &str = "test-azure-warehouse-";
+pub(crate) static AZURE_TEST_WAREHOUSE_URI_PREFIX: &str = "abfss://test-azure-warehouse-";
+pub(crate) static AZURE_TEST_ACCOUNT_NAME: &str = "devstoreaccount1";
+pub(crate) static AZURE_TEST_ACCOUNT_KEY: &str =
+    "Eby8vdM02xNOcqFlqUwJPLlmEtlCDXJ1OUzFT50uSRZ6IFsuFq2UVErCz4I6tq/K1SZFPTOtr/KBHBeksoGMGw==";
+pub(crate) static AZURE_TEST_ENDPOINT: &str = "http://azurite.local:10000/devstoreaccount1";
+/// REST API version used to manage containers.
+static AZURE_TEST_API_VERSION: &str = "2021-08-06";
+
+/// Create an Azure storage config, which communicates with local Azurite server.
+pub(crate) fn create_azure_storage_config(warehouse_uri: &str) -> AccessorConfig {
+    let container = get_bucket_from_warehouse_uri(warehouse_uri);
+    let storage_config = StorageConfig::Azblob {
+        account_name: AZURE_TEST_ACCOUNT_NAME.to_string(),
+        container,
+        account_key: Some(AZURE_TEST_ACCOUNT_KEY.to_string()),
+        sas_token: None,
+        endpoint: Some(AZURE_TEST_ENDPOINT.to_string()),
+    };
+    AccessorConfig::new_with_storage_config(storage_config)
+}
+
+pub(crate) fn get_test_azure_container_and_warehouse() -> (String, String) {
+    let (container, warehouse_uri) =
+        get_bucket_and_warehouse(AZURE_TEST_CONTAINER_PREFIX, AZURE_TEST_WAREHOUSE_URI_PREFIX);
+    (
+        container,
+        format!("{warehouse_uri}@{AZURE_TEST_ACCOUNT_NAME}.dfs.core.windows.net"),
+    )
+}
+
+/// Send a container-level request to Azurite, authorized with shared key.
+async fn send_azure_container_request(
+    method: Method,
+    container: &str,
+) -> IcebergResult<StatusCode> {
+    let date = Utc::now().format("%a, %d %b %Y %T GMT").to_string();
+    // Azurite takes path-style URLs, so account name appears twice in the canonicalized resource.
+    let string_to_sign = format!(
+        "{method}\n\n\n\n\n\n\n\n\n\n\n\nx-ms-date:{date}\nx-ms-version:{AZURE_TEST_API_VERSION}\n/{AZURE_TEST_ACCOUNT_NAME}/{AZURE_TEST_ACCOUNT_NAME}/{container}\nrestype:container"
+    );
+
+    let account_key = base64.decode(AZURE_TEST_ACCOUNT_KEY).unwrap();
+    let mut mac = HmacSha256::new_from_slice(&account_key).unwrap();
+    mac.update(string_to_sign.as_bytes());
+    let signature = base64.encode(mac.finalize().into_bytes());
+
+    let auth_header = format!("SharedKey {AZURE_TEST_ACCOUNT_NAME}:{signature}");
+    let url = format!("{AZURE_TEST_ENDPOINT}/{container}?restype=container");
+    let client = reqwest::Client::new();
+
+    let res = client
+        .request(method.clone(), &url)
+        .header("Authorization", auth_header)
+        .header("x-ms-date", date)
+        .header("x-ms-version", AZURE_TEST_API_VERSION)
+        .header("Content-Length", "0")
+        .send()
+        .await
+        .map_err(|e| {
+            IcebergError::new(
+                iceberg::ErrorKind::Unexpected,
+                format!("Failed to {method} container {container} in azurite with url {url}: {e}"),
+            )
+        })?;
+    Ok(res.status())
+}
+
+async fn create_test_azure_container_impl(container: Arc<String>) -> IcebergResult<()> {
+    let status = send_azure_container_request(Method::PUT, &container).await?;
+    if status != StatusCode::CREATED {
+        return Err(IcebergError::new(
+            iceberg::ErrorKind::Unexpected,
+            format!("Failed to create container {container} in azurite: HTTP {status}"),
+        ));
+    }
+    Ok(())
+}
+
+async fn delete_test_azure_container_impl(container: Arc<String>) -> IcebergResult<()> {
+    // Unlike buckets in minio or fake GCS server, containers could be deleted along with their blobs.
+    let status = send_azure_container_request(Method::DELETE, &container).await?;
+    if status != StatusCode::ACCEPTED {
+        return Err(IcebergError::new(
+            iceberg::ErrorKind::Unexpected,
+            format!("Failed to delete container {container} in azurite: HTTP {status}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Creates the provided container with exponential backoff retry; this function assumes the container doesn't exist, otherwise it will return error.
+pub(crate) async fn create_test_azure_container(container: String) -> IcebergResult<()> {
+    let container = Arc::new(container);
+    let backoff = ExponentialBuilder::default()
+        .with_min_delay(Duration::from_millis(TEST_RETRY_INIT_MILLISEC))
+        .with_max_times(TEST_RETRY_COUNT);
+
+    (move || {
+        let container = Arc::clone(&container);
+        async move { create_test_azure_container_impl(container).await }
+    })
+    .retry(backoff)
+    .sleep(sleep)
+    .when(|e: &IcebergError| {
+        matches!(
+            e.kind(),
+            iceberg::ErrorKind::Unexpected | iceberg::ErrorKind::CatalogCommitConflicts
+        )
+    })
+    .await
+}
+
+pub(crate) async fn delete_test_azure_container(container: String) {
+    let container = Arc::new(container);
+    let backoff = ExponentialBuilder::default()
+        .with_min_delay(Duration::from_millis(TEST_RETRY_INIT_MILLISEC))
+        .with_max_times(TEST_RETRY_COUNT);
+
+    let _ = (move || {
+        let container = Arc::clone(&container);
+        async move { delete_test_azure_container_impl(container).await }
+    })
+    .retry(backoff)
+    .sleep(sleep)
+    .when(|e: &IcebergError| {
+        matches!(
+            e.kind(),
+            iceberg::ErrorKind::Unexpected | iceberg::ErrorKind::CatalogCommitConflicts
+        )
+    })
+    .await;
+}
@@ -0,0 +1,27 @@
+/// A RAII-style test guard, which creates container at construction, and deletes at destruction.
+use crate::storage::filesystem::azure::azure_test_utils;
+
+pub(crate) struct TestGuard {
+    /// Container name.
+    container: String,
+}
+
+impl TestGuard {
+    pub(crate) async fn new(container: String) -> Self {
+        azure_test_utils::create_test_azure_container(container.clone())
+            .await
+            .unwrap();
+        Self { container }
+    }
+}
+
+impl Drop for TestGuard {
+    fn drop(&mut self) {
+        let container = std::mem::take(&mut self.container);
+        tokio::task::block_in_place(|| {
+            tokio::runtime::Handle::current().block_on(async move {
+                azure_test_utils::delete_test_azure_container(container).await;
+            });
+        });
+    }
+}
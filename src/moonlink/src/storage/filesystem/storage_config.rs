@@ -1,4 +1,8 @@
-#[cfg(any(feature = "storage-gcs", feature = "storage-s3"))]
+#[cfg(any(
+    feature = "storage-gcs",
+    feature = "storage-s3",
+    feature = "storage-azure"
+))]
 use crate::MoonlinkSecretType;
 use crate::MoonlinkTableSecret;
 use serde::{Deserialize, Serialize};
@@ -11,6 +15,31 @@ pub struct WriteOption {
     pub multipart_upload_threshold: Option<usize>,
 }
 
+/// Get default blob service endpoint for the given Azure storage account.
+#[cfg(feature = "storage-azure")]
+pub(crate) fn get_default_azblob_endpoint(account_name: &str) -> String {
+    format!("https://{account_name}.blob.core.windows.net")
+}
+
+/// Get ADLS Gen2 host placed in abfss paths for the given Azure storage account.
+/// Endpoint suffix follows the blob endpoint if it's account-scoped, i.e. "https://account.blob.core.chinacloudapi.cn" for sovereign clouds.
+/// Emulator endpoints take path-style URLs, which keep the public suffix since they're only used to override service endpoint.
+#[cfg(feature = "storage-azure")]
+pub(crate) fn get_azblob_dfs_host(account_name: &str, endpoint: Option<&str>) -> String {
+    let endpoint_suffix = endpoint
+        .and_then(|endpoint| url::Url::parse(endpoint).ok())
+        .and_then(|url| {
+            let host = url.host_str()?;
+            let service_host = host.strip_prefix(account_name)?.strip_prefix('.')?;
+            let endpoint_suffix = service_host
+                .strip_prefix("blob.")
+                .or_else(|| service_host.strip_prefix("dfs."))?;
+            Some(endpoint_suffix.to_string())
+        })
+        .unwrap_or_else(|| "core.windows.net".to_string());
+    format!("{account_name}.dfs.{endpoint_suffix}")
+}
+
 /// StorageConfig contains configuration for multiple storage backends.
 #[derive(Clone, Deserialize, PartialEq, Serialize)]
 pub enum StorageConfig {
@@ -57,6 +86,23 @@ pub enum StorageConfig {
         #[serde(default)]
         write_option: Option<WriteOption>,
     },
+    #[cfg(feature = "storage-azure")]
+    #[serde(rename = "azblob")]
+    Azblob {
+        /// Azure storage account name.
+        account_name: String,
+        /// Blob container, which is also the ADLS Gen2 filesystem.
+        container: String,
+        /// Shared key of the storage account, takes precedence over SAS token if both specified.
+        #[serde(default)]
+        account_key: Option<String>,
+        /// Shared access signature token, used when account key is not available.
+        #[serde(default)]
+        sas_token: Option<String>,
+        /// Blob service endpoint, used for Azurite emulator; default to the public Azure endpoint for the account.
+        #[serde(default)]
+        endpoint: Option<String>,
+    },
 }
 
 impl std::fmt::Debug for StorageConfig {
@@ -109,6 +155,22 @@ impl std::fmt::Debug for StorageConfig {
                 .field("access key id", &"xxxxx")
                 .field("secret access key", &"xxxxx")
                 .finish(),
+
+            #[cfg(feature = "storage-azure")]
+            StorageConfig::Azblob {
+                account_name,
+                container,
+                endpoint,
+                account_key: _,
+                sas_token: _,
+            } => f
+                .debug_struct("Azblob")
+                .field("account_name", account_name)
+                .field("container", container)
+                .field("endpoint", endpoint)
+                .field("account key", &"xxxxx")
+                .field("sas token", &"xxxxx")
+                .finish(),
         }
     }
 }
@@ -123,6 +185,16 @@ impl StorageConfig {
             StorageConfig::Gcs { bucket, .. } => format!("gs://{bucket}"),
             #[cfg(feature = "storage-s3")]
             StorageConfig::S3 { bucket, .. } => format!("s3://{bucket}"),
+            #[cfg(feature = "storage-azure")]
+            StorageConfig::Azblob {
+                account_name,
+                container,
+                endpoint,
+                ..
+            } => format!(
+                "abfss://{container}@{}",
+                get_azblob_dfs_host(account_name, endpoint.as_deref())
+            ),
         }
     }
 
@@ -135,6 +207,8 @@ impl StorageConfig {
             StorageConfig::Gcs { region, .. } => Some(region.clone()),
             #[cfg(feature = "storage-s3")]
             StorageConfig::S3 { region, .. } => Some(region.clone()),
+            #[cfg(feature = "storage-azure")]
+            StorageConfig::Azblob { .. } => None,
         }
     }
 
@@ -147,6 +221,8 @@ impl StorageConfig {
             StorageConfig::Gcs { access_key_id, .. } => Some(access_key_id.clone()),
             #[cfg(feature = "storage-s3")]
             StorageConfig::S3 { access_key_id, .. } => Some(access_key_id.clone()),
+            #[cfg(feature = "storage-azure")]
+            StorageConfig::Azblob { account_name, .. } => Some(account_name.clone()),
         }
     }
 
@@ -163,6 +239,8 @@ impl StorageConfig {
             StorageConfig::S3 {
                 secret_access_key, ..
             } => Some(secret_access_key.clone()),
+            #[cfg(feature = "storage-azure")]
+            StorageConfig::Azblob { account_key, .. } => account_key.clone(),
         }
     }
 
@@ -202,16 +280,40 @@ impl StorageConfig {
                 endpoint: endpoint.clone(),
                 region: Some(region.clone()),
             }),
+            // Secret type tells whether the secret is account key or SAS token, anonymous access doesn't need a secret.
+            #[cfg(feature = "storage-azure")]
+            StorageConfig::Azblob {
+                account_name,
+                account_key,
+                sas_token,
+                endpoint,
+                ..
+            } => {
+                let (secret_type, secret) = match (account_key, sas_token) {
+                    (Some(account_key), _) => (MoonlinkSecretType::Azure, account_key),
+                    (None, Some(sas_token)) => (MoonlinkSecretType::AzureSas, sas_token),
+                    (None, None) => return None,
+                };
+                Some(MoonlinkTableSecret {
+                    secret_type,
+                    key_id: account_name.to_string(),
+                    secret: secret.to_string(),
+                    project: None,
+                    endpoint: endpoint.clone(),
+                    region: None,
+                })
+            }
         }
     }
 }
 
-#[cfg(all(test, feature = "storage-gcs"))]
+#[cfg(all(test, any(feature = "storage-gcs", feature = "storage-azure")))]
 mod tests {
     use crate::StorageConfig;
 
     /// Testing scenario: deserialize storage config with partial GCS field populated.
     #[test]
+    #[cfg(feature = "storage-gcs")]
     fn test_deserialize_storage_config_with_only_necessary() {
         let json = r#"
         {
@@ -240,4 +342,91 @@ mod tests {
             }
         );
     }
+
+    /// Testing scenario: deserialize storage config with partial Azure field populated, and extract secret from it.
+    #[test]
+    #[cfg(feature = "storage-azure")]
+    fn test_deserialize_azblob_storage_config() {
+        let json = r#"
+        {
+            "azblob": {
+                "account_name": "testaccount",
+                "container": "test-container",
+                "sas_token": "sv=2022-11-02&sig=fake"
+            }
+        }
+        "#;
+
+        let parsed_config: StorageConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            parsed_config,
+            StorageConfig::Azblob {
+                account_name: "testaccount".to_string(),
+                container: "test-container".to_string(),
+                account_key: None,
+                sas_token: Some("sv=2022-11-02&sig=fake".to_string()),
+                endpoint: None,
+            }
+        );
+        assert_eq!(
+            parsed_config.get_root_path(),
+            "abfss://test-container@testaccount.dfs.core.windows.net"
+        );
+        assert!(!format!("{parsed_config:?}").contains("sig=fake"));
+
+        let secret_entry = parsed_config.extract_security_metadata_entry().unwrap();
+        assert_eq!(
+            secret_entry.secret_type,
+            crate::MoonlinkSecretType::AzureSas
+        );
+        assert_eq!(secret_entry.key_id, "testaccount");
+        assert_eq!(secret_entry.secret, "sv=2022-11-02&sig=fake");
+
+        // Account key takes precedence over SAS token.
+        let StorageConfig::Azblob {
+            account_name,
+            container,
+            sas_token,
+            ..
+        } = parsed_config
+        else {
+            unreachable!()
+        };
+        let storage_config = StorageConfig::Azblob {
+            account_name,
+            container,
+            account_key: Some("fake-account-key".to_string()),
+            sas_token,
+            endpoint: None,
+        };
+        let secret_entry = storage_config.extract_security_metadata_entry().unwrap();
+        assert_eq!(secret_entry.secret_type, crate::MoonlinkSecretType::Azure);
+        assert_eq!(secret_entry.secret, "fake-account-key");
+    }
+
+    /// Testing scenario: abfss root path follows account-scoped endpoint, while emulator endpoints keep the public suffix.
+    #[test]
+    #[cfg(feature = "storage-azure")]
+    fn test_azblob_root_path_with_endpoint() {
+        let create_storage_config = |endpoint: &str| StorageConfig::Azblob {
+            account_name: "testaccount".to_string(),
+            container: "test-container".to_string(),
+            account_key: None,
+            sas_token: None,
+            endpoint: Some(endpoint.to_string()),
+        };
+        assert_eq!(
+            create_storage_config("https://testaccount.blob.core.chinacloudapi.cn").get_root_path(),
+            "abfss://test-container@testaccount.dfs.core.chinacloudapi.cn"
+        );
+        assert_eq!(
+            create_storage_config("https://testaccount.dfs.core.usgovcloudapi.net/")
+                .get_root_path(),
+            "abfss://test-container@testaccount.dfs.core.usgovcloudapi.net"
+        );
+        assert_eq!(
+            create_storage_config("http://127.0.0.1:10000/testaccount").get_root_path(),
+            "abfss://test-container@testaccount.dfs.core.windows.net"
+        );
+    }
 }
@@ -1,16 +1,32 @@
 /// Testing utils for object storage.
-#[cfg(any(feature = "storage-gcs", feature = "storage-s3"))]
+#[cfg(any(
+    feature = "storage-gcs",
+    feature = "storage-s3",
+    feature = "storage-azure"
+))]
 pub(crate) const TEST_RETRY_COUNT: usize = 2;
-#[cfg(any(feature = "storage-gcs", feature = "storage-s3"))]
+#[cfg(any(
+    feature = "storage-gcs",
+    feature = "storage-s3",
+    feature = "storage-azure"
+))]
 pub(crate) const TEST_RETRY_INIT_MILLISEC: u64 = 100;
 
-#[cfg(any(feature = "storage-gcs", feature = "storage-s3"))]
+#[cfg(any(
+    feature = "storage-gcs",
+    feature = "storage-s3",
+    feature = "storage-azure"
+))]
 use rand::Rng;
 
 /// Get object storage bucket name from warehouse uri.
 pub(crate) fn get_bucket_from_warehouse_uri(warehouse_uri: &str) -> String {
     // Try to parse with url::Url
     if let Ok(url) = url::Url::parse(warehouse_uri) {
+        // Azure container is placed at user info, i.e. "abfss://container@account.dfs.core.windows.net/dir".
+        if url.scheme() == "abfss" {
+            return url.username().to_string();
+        }
         if let Some(bucket) = url.host_str() {
             return bucket.to_string();
         }
@@ -25,7 +41,11 @@ pub(crate) fn get_bucket_from_warehouse_uri(warehouse_uri: &str) -> String {
         .to_string()
 }
 
-#[cfg(any(feature = "storage-gcs", feature = "storage-s3"))]
+#[cfg(any(
+    feature = "storage-gcs",
+    feature = "storage-s3",
+    feature = "storage-azure"
+))]
 pub(crate) fn get_bucket_and_warehouse(
     bucket_prefix: &str,
     warehouse_uri_prefix: &str,
@@ -59,5 +79,11 @@ mod tests {
             get_bucket_from_warehouse_uri("gs://another-bucket/folder/file"),
             "another-bucket"
         );
+        assert_eq!(
+            get_bucket_from_warehouse_uri(
+                "abfss://my-container@devstoreaccount1.dfs.core.windows.net/folder/file"
+            ),
+            "my-container"
+        );
     }
 }
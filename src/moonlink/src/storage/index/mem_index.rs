@@ -132,6 +132,24 @@ impl MemIndex {
         }
     }
 
+    /// Get estimated memory size in bytes.
+    /// Heap allocations of identity rows are not accounted, since they're bounded by the rows in mem slice.
+    pub fn get_estimated_memory_size(&self) -> usize {
+        match self {
+            MemIndex::SinglePrimitive(map) => {
+                map.capacity() * std::mem::size_of::<SinglePrimitiveKey>()
+            }
+            MemIndex::Key(map) => map.capacity() * std::mem::size_of::<KeyWithIdentity>(),
+            MemIndex::FullRow(map) => {
+                map.len()
+                    * (std::mem::size_of::<PrimaryKey>()
+                        + std::mem::size_of::<Vec<RecordLocation>>()
+                        + std::mem::size_of::<RecordLocation>())
+            }
+            MemIndex::None => 0,
+        }
+    }
+
     pub fn is_empty(&self) -> bool {
         match self {
             MemIndex::SinglePrimitive(map) => map.is_empty(),
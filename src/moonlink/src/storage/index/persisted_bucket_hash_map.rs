@@ -1,6 +1,7 @@
 use crate::create_data_file;
 use crate::error::Result;
 use crate::storage::async_bitwriter::BitWriter as AsyncBitWriter;
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::storage_utils::{MooncakeDataFileRef, RecordLocation};
 use crate::NonEvictableHandle;
 use bitstream_io::{BigEndian, BitRead, BitReader};
@@ -11,9 +12,12 @@ use std::hash::{Hash, Hasher};
 use std::io::Cursor;
 use std::io::SeekFrom;
 use std::path::PathBuf;
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{Context, Poll};
 use std::{fmt, vec};
 use tokio::fs::File as AsyncFile;
+use tokio::io::AsyncWrite;
 use tokio_bitstream_io::BigEndian as AsyncBigEndian;
 
 // Constants
@@ -78,13 +82,30 @@ pub(crate) struct IndexBlock {
     pub(crate) index_file: MooncakeDataFileRef,
     /// File size for the index block file, used to decide whether to trigger merge index blocks merge.
     pub(crate) file_size: u64,
-    /// Mmapped-data.
-    /// Synchronous IO is not needed because here we use mmap.
-    data: Arc<Option<Mmap>>,
+    /// Index block content.
+    data: Arc<Option<IndexBlockData>>,
     /// Cache handle within object storage cache.
     pub(crate) cache_handle: Option<NonEvictableHandle>,
 }
 
+/// Content of an index block.
+enum IndexBlockData {
+    /// Mmapped-data for plaintext index block files.
+    /// Synchronous IO is not needed because here we use mmap.
+    Mmap(Mmap),
+    /// Encrypted index block files cannot be mmapped, so they're decrypted into memory.
+    Decrypted(Vec<u8>),
+}
+
+impl AsRef<[u8]> for IndexBlockData {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            IndexBlockData::Mmap(mmap) => mmap.as_ref(),
+            IndexBlockData::Decrypted(data) => data.as_slice(),
+        }
+    }
+}
+
 struct BucketEntry {
     upper_hash: u64,
     entry_start: u32,
@@ -92,25 +113,37 @@ struct BucketEntry {
 }
 
 impl IndexBlock {
+    /// Load index block from the given local index file, which is encrypted if [`encryption_key`] is assigned.
     pub(crate) async fn new(
         bucket_start_idx: u32,
         bucket_end_idx: u32,
         bucket_start_offset: u64,
         index_file: MooncakeDataFileRef,
-    ) -> Self {
-        let file = tokio::fs::File::open(index_file.file_path()).await.unwrap();
-        let file_metadata = file.metadata().await.unwrap();
-        let file = file.into_std().await;
-        let data = unsafe { Mmap::map(&file).unwrap() };
-        Self {
+        encryption_key: Option<&DataEncryptionKey>,
+    ) -> Result<Self> {
+        let (data, file_size) = match encryption_key {
+            Some(encryption_key) => {
+                let content = tokio::fs::read(index_file.file_path()).await?;
+                let data = IndexBlockData::Decrypted(encryption_key.decrypt(&content)?);
+                (data, content.len() as u64)
+            }
+            None => {
+                let file = tokio::fs::File::open(index_file.file_path()).await?;
+                let file_metadata = file.metadata().await?;
+                let file = file.into_std().await;
+                let data = IndexBlockData::Mmap(unsafe { Mmap::map(&file)? });
+                (data, file_metadata.len())
+            }
+        };
+        Ok(Self {
             bucket_start_idx,
             bucket_end_idx,
             bucket_start_offset,
             index_file,
-            file_size: file_metadata.len(),
+            file_size,
             data: Arc::new(Some(data)),
             cache_handle: None,
-        }
+        })
     }
 
     fn create_iterator<'a>(
@@ -317,14 +350,50 @@ impl GlobalIndex {
 // ================================
 // Builders
 // ================================
+/// Destination for index block content, which is buffered in memory for encrypted index blocks, and encrypted as a whole
+/// at build so plaintext never reaches disk.
+enum IndexBlockSink {
+    File(AsyncFile),
+    Buffer(Vec<u8>),
+}
+
+impl AsyncWrite for IndexBlockSink {
+    fn poll_write(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<std::io::Result<usize>> {
+        match self.get_mut() {
+            IndexBlockSink::File(file) => Pin::new(file).poll_write(cx, buf),
+            IndexBlockSink::Buffer(buffer) => Pin::new(buffer).poll_write(cx, buf),
+        }
+    }
+
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            IndexBlockSink::File(file) => Pin::new(file).poll_flush(cx),
+            IndexBlockSink::Buffer(buffer) => Pin::new(buffer).poll_flush(cx),
+        }
+    }
+
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<std::io::Result<()>> {
+        match self.get_mut() {
+            IndexBlockSink::File(file) => Pin::new(file).poll_shutdown(cx),
+            IndexBlockSink::Buffer(buffer) => Pin::new(buffer).poll_shutdown(cx),
+        }
+    }
+}
+
 struct IndexBlockBuilder {
     bucket_start_idx: u32,
     bucket_end_idx: u32,
     buckets: Vec<u32>,
     index_file: MooncakeDataFileRef,
-    entry_writer: AsyncBitWriter<AsyncFile, AsyncBigEndian>,
+    entry_writer: AsyncBitWriter<IndexBlockSink, AsyncBigEndian>,
     current_bucket: u32,
     current_entry: u32,
+    /// Data key to encrypt index block file with, if the table is encrypted.
+    encryption_key: Option<DataEncryptionKey>,
 }
 
 impl IndexBlockBuilder {
@@ -333,12 +402,17 @@ impl IndexBlockBuilder {
         bucket_end_idx: u32,
         file_id: u64,
         directory: PathBuf,
+        encryption_key: Option<DataEncryptionKey>,
     ) -> Result<Self> {
         let file_name = format!("index_block_{}.bin", uuid::Uuid::now_v7());
         let file_path = directory.join(&file_name);
 
-        let file = AsyncFile::create(&file_path).await?;
-        let entry_writer = AsyncBitWriter::endian(file, AsyncBigEndian);
+        let sink = if encryption_key.is_some() {
+            IndexBlockSink::Buffer(vec![])
+        } else {
+            IndexBlockSink::File(AsyncFile::create(&file_path).await?)
+        };
+        let entry_writer = AsyncBitWriter::endian(sink, AsyncBigEndian);
         let index_file = create_data_file(file_id, file_path.to_str().unwrap().to_string());
 
         Ok(Self {
@@ -349,6 +423,7 @@ impl IndexBlockBuilder {
             entry_writer,
             current_bucket: bucket_start_idx,
             current_entry: 0,
+            encryption_key,
         })
     }
 
@@ -398,15 +473,33 @@ impl IndexBlockBuilder {
                 self.entry_writer.flush().await?;
             }
         }
-        self.entry_writer.close().await?;
+        let sink = self.entry_writer.close().await?;
+
+        // Encrypted index block keeps its plaintext content in memory, instead of decrypting the file just written.
+        if let (IndexBlockSink::Buffer(content), Some(encryption_key)) =
+            (sink, self.encryption_key.as_ref())
+        {
+            let encrypted_content = encryption_key.encrypt(&content)?;
+            tokio::fs::write(self.index_file.file_path(), &encrypted_content).await?;
+            return Ok(IndexBlock {
+                bucket_start_idx: self.bucket_start_idx,
+                bucket_end_idx: self.bucket_end_idx,
+                bucket_start_offset,
+                index_file: self.index_file,
+                file_size: encrypted_content.len() as u64,
+                data: Arc::new(Some(IndexBlockData::Decrypted(content))),
+                cache_handle: None,
+            });
+        }
 
-        Ok(IndexBlock::new(
+        IndexBlock::new(
             self.bucket_start_idx,
             self.bucket_end_idx,
             bucket_start_offset,
             self.index_file,
+            /*encryption_key=*/ None,
         )
-        .await)
+        .await
     }
 }
 
@@ -414,6 +507,8 @@ pub struct GlobalIndexBuilder {
     num_rows: u32,
     files: Vec<MooncakeDataFileRef>,
     directory: PathBuf,
+    /// Data key to encrypt index block files with, if the table is encrypted.
+    encryption_key: Option<DataEncryptionKey>,
 }
 
 impl Default for GlobalIndexBuilder {
@@ -428,6 +523,7 @@ impl GlobalIndexBuilder {
             num_rows: 0,
             files: vec![],
             directory: PathBuf::new(),
+            encryption_key: None,
         }
     }
 
@@ -436,6 +532,11 @@ impl GlobalIndexBuilder {
         self
     }
 
+    pub fn set_encryption_key(&mut self, encryption_key: Option<DataEncryptionKey>) -> &mut Self {
+        self.encryption_key = encryption_key;
+        self
+    }
+
     pub fn set_files(&mut self, files: Vec<MooncakeDataFileRef>) -> &mut Self {
         self.files = files;
         self
@@ -504,8 +605,14 @@ impl GlobalIndexBuilder {
     ) -> Result<GlobalIndex> {
         let (num_buckets, mut global_index) = self.create_global_index();
         let mut index_blocks = Vec::new();
-        let mut index_block_builder =
-            IndexBlockBuilder::new(0, num_buckets + 1, file_id, self.directory.clone()).await?;
+        let mut index_block_builder = IndexBlockBuilder::new(
+            0,
+            num_buckets + 1,
+            file_id,
+            self.directory.clone(),
+            self.encryption_key.clone(),
+        )
+        .await?;
         for entry in iter {
             let to_flush =
                 index_block_builder.write_entry(entry.0, entry.1, entry.2, &global_index);
@@ -547,8 +654,14 @@ impl GlobalIndexBuilder {
         file_id: u64,
     ) -> Result<GlobalIndex> {
         let (num_buckets, mut global_index) = self.create_global_index();
-        let mut index_block_builder =
-            IndexBlockBuilder::new(0, num_buckets + 1, file_id, self.directory.clone()).await?;
+        let mut index_block_builder = IndexBlockBuilder::new(
+            0,
+            num_buckets + 1,
+            file_id,
+            self.directory.clone(),
+            self.encryption_key.clone(),
+        )
+        .await?;
         while let Some(entry) = iter.next() {
             let to_flush =
                 index_block_builder.write_entry(entry.0, entry.1, entry.2, &global_index);
@@ -622,8 +735,14 @@ impl GlobalIndexBuilder {
         GetSegIdx: FnMut(RecordLocation) -> usize, /*seg_idx*/
     {
         let (num_buckets, mut global_index) = self.create_global_index();
-        let mut index_block_builder =
-            IndexBlockBuilder::new(0, num_buckets + 1, file_id, self.directory.clone()).await?;
+        let mut index_block_builder = IndexBlockBuilder::new(
+            0,
+            num_buckets + 1,
+            file_id,
+            self.directory.clone(),
+            self.encryption_key.clone(),
+        )
+        .await?;
 
         while let Some((hash, old_seg_idx, old_row_idx)) = iter.next() {
             let old_record_location =
@@ -960,6 +1079,58 @@ mod tests {
         assert_eq!(hash_entry_num, hash_entries.len());
     }
 
+    #[tokio::test]
+    async fn test_encrypted_index_block() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let table_encryption =
+            crate::storage::encryption::test_utils::create_test_table_encryption(&temp_dir).await;
+        let encryption_key = table_encryption.get_data_key();
+        let data_file = create_data_file(/*file_id=*/ 0, "a.parquet".to_string());
+        let hash_entries = (0..100).map(|i| (i as u64, 0, i)).collect::<Vec<_>>();
+        let mut builder = GlobalIndexBuilder::new();
+        builder
+            .set_files(vec![data_file.clone()])
+            .set_directory(temp_dir.path().to_path_buf())
+            .set_encryption_key(Some(encryption_key.clone()));
+        let index = builder
+            .build_from_flush(hash_entries.clone(), /*file_id=*/ 1)
+            .await
+            .unwrap();
+        for (hash, _, row_idx) in hash_entries.iter() {
+            assert_eq!(
+                index
+                    .search_values(&test_get_hashes_for_index(&[*hash]))
+                    .await,
+                vec![(
+                    *hash,
+                    RecordLocation::DiskFile(data_file.file_id(), *row_idx)
+                )]
+            );
+        }
+
+        // Index block file only contains ciphertext, which is loaded back with the data key.
+        let index_block = &index.index_blocks[0];
+        let plaintext = index_block.data.as_ref().as_ref().unwrap().as_ref();
+        let content = tokio::fs::read(index_block.index_file.file_path())
+            .await
+            .unwrap();
+        assert_eq!(index_block.file_size, content.len() as u64);
+        assert_ne!(content.as_slice(), plaintext);
+        let loaded_index_block = IndexBlock::new(
+            index_block.bucket_start_idx,
+            index_block.bucket_end_idx,
+            index_block.bucket_start_offset,
+            index_block.index_file.clone(),
+            Some(encryption_key),
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            loaded_index_block.data.as_ref().as_ref().unwrap().as_ref(),
+            plaintext
+        );
+    }
+
     #[tokio::test]
     async fn test_merge() {
         let files = vec![
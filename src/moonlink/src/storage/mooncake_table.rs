@@ -7,6 +7,7 @@ mod mem_slice;
 mod persisted_records;
 mod persistence_buffer;
 pub(crate) mod replay;
+pub mod schema_evolution;
 mod shared_array;
 pub(crate) mod snapshot;
 mod snapshot_cache_utils;
@@ -14,6 +15,7 @@ mod snapshot_maintenance;
 mod snapshot_persistence;
 mod snapshot_read;
 pub mod snapshot_read_output;
+mod snapshot_truncation;
 mod snapshot_validation;
 pub mod table_config;
 pub mod table_event_manager;
@@ -25,7 +27,7 @@ mod transaction_stream;
 
 use super::index::{FileIndex, MemIndex, MooncakeIndex};
 use super::storage_utils::{MooncakeDataFileRef, RawDeletionRecord, RecordLocation};
-use crate::error::Result;
+use crate::error::{Error, Result};
 use crate::observability::latency_exporter::BaseLatencyExporter;
 use crate::observability::snapshot_creation::SnapshotCreationStats;
 use crate::row::{IdentityProp, MoonlinkRow};
@@ -50,12 +52,19 @@ pub(crate) use crate::storage::mooncake_table::table_snapshot::{
     take_file_indices_to_remove, FileIndiceMergePayload, FileIndiceMergeResult,
     PersistenceSnapshotDataCompactionResult, PersistenceSnapshotImportPayload,
     PersistenceSnapshotIndexMergePayload, PersistenceSnapshotPayload, PersistenceSnapshotResult,
+    PersistenceSnapshotTruncationPayload, SnapshotExpirationResult,
 };
 use crate::storage::mooncake_table_config::MooncakeTableConfig;
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
 use crate::storage::snapshot_options::MaintenanceOption;
 use crate::storage::snapshot_options::SnapshotOption;
-use crate::storage::storage_utils::{FileId, TableId};
-use crate::storage::table::common::table_manager::{PersistenceFileParams, TableManager};
+use crate::storage::sort_utils::{self, SortKeyGenerator, SortKeyRange};
+use crate::storage::storage_utils::{FileId, TableAutoIncrIdAllocator, TableId};
+use crate::storage::table::common::table_manager::{
+    PersistenceFileParams, SnapshotExpirationReport, TableManager,
+};
+use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
+use crate::storage::table::deltalake::deltalake_table_manager::DeltalakeTableManager;
 use crate::storage::table::iceberg::iceberg_table_config::IcebergTableConfig;
 use crate::storage::table::iceberg::iceberg_table_manager::IcebergTableManager;
 use crate::storage::table::iceberg::puffin_utils::PuffinBlobRef;
@@ -66,13 +75,19 @@ use arrow::record_batch::RecordBatch;
 use arrow_schema::Schema;
 use delete_vector::BatchDeletionVector;
 pub(crate) use disk_slice::DiskSliceWriter;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use lru::LruCache;
 use mem_slice::MemSlice;
 use more_asserts as ma;
 pub(crate) use snapshot::SnapshotTableState;
 use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::path::PathBuf;
 use std::sync::Arc;
-use table_snapshot::{PersistenceSnapshotImportResult, PersistenceSnapshotIndexMergeResult};
+use table_snapshot::{
+    PersistenceSnapshotImportResult, PersistenceSnapshotIndexMergeResult,
+    PersistenceSnapshotTruncationResult,
+};
 #[cfg(test)]
 use tokio::sync::mpsc::Receiver;
 use tokio::sync::mpsc::{self, Sender};
@@ -81,6 +96,12 @@ use tracing::info_span;
 use tracing::Instrument;
 use transaction_stream::{TransactionStreamOutput, TransactionStreamState};
 
+/// Max number of data files to read concurrently when recomputing sort key ranges at recovery.
+const MAX_CONCURRENT_SORT_KEY_RANGE_COMPUTATION: usize = 16;
+
+/// Max number of rows written by updates to cache, which are looked up by later updates to the same rows.
+const UPDATED_ROW_CACHE_CAPACITY: NonZeroUsize = NonZeroUsize::new(1024).unwrap();
+
 #[derive(Debug)]
 pub struct TableMetadata {
     /// unique table id
@@ -96,37 +117,170 @@ pub struct TableMetadata {
     pub(crate) path: PathBuf,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq, Default)]
 pub struct AlterTableRequest {
+    /// Columns to append, field ids are assigned at alteration.
     pub(crate) new_columns: Vec<arrow_schema::FieldRef>,
     pub(crate) dropped_columns: Vec<String>,
+    /// Columns to rename, in the format of (old name, new name).
+    pub(crate) renamed_columns: Vec<(String, String)>,
+    /// Columns to promote to a wider type, keyed by the name before renaming.
+    pub(crate) promoted_columns: Vec<(String, arrow_schema::DataType)>,
+    /// Columns to relax to nullable, keyed by the name before renaming.
+    pub(crate) nullable_columns: Vec<String>,
 }
 
 impl TableMetadata {
     pub fn new_for_alter_table(
         previous_metadata: Arc<TableMetadata>,
         alter_table_request: AlterTableRequest,
-    ) -> Self {
+    ) -> Result<Self> {
         let mut new_columns = vec![];
         for field in previous_metadata.schema.fields.iter() {
-            if !alter_table_request.dropped_columns.contains(field.name()) {
-                new_columns.push(field.clone());
+            if alter_table_request.dropped_columns.contains(field.name()) {
+                continue;
+            }
+            // Altered columns keep their field ids, so they're still the same columns in iceberg.
+            let mut new_field = field.as_ref().clone();
+            if let Some((_, new_type)) = alter_table_request
+                .promoted_columns
+                .iter()
+                .find(|(name, _)| name == field.name())
+            {
+                if !schema_evolution::is_safe_type_promotion(field.data_type(), new_type) {
+                    return Err(Error::invalid_table_config(format!(
+                        "Cannot promote column {} from {:?} to {:?}",
+                        field.name(),
+                        field.data_type(),
+                        new_type
+                    )));
+                }
+                new_field = new_field.with_data_type(new_type.clone());
             }
+            if alter_table_request.nullable_columns.contains(field.name()) {
+                new_field = new_field.with_nullable(true);
+            }
+            if let Some((_, new_name)) = alter_table_request
+                .renamed_columns
+                .iter()
+                .find(|(old_name, _)| old_name == field.name())
+            {
+                new_field = new_field.with_name(new_name);
+            }
+            new_columns.push(Arc::new(new_field));
+        }
+        let mut next_field_id =
+            schema_evolution::get_highest_field_id(&previous_metadata.schema) + 1;
+        for field in alter_table_request.new_columns.iter() {
+            new_columns.push(Arc::new(schema_evolution::assign_field_ids(
+                field,
+                &mut next_field_id,
+            )));
         }
-        new_columns.extend(alter_table_request.new_columns);
         let new_schema =
             Schema::new_with_metadata(new_columns, previous_metadata.schema.metadata.clone());
-        Self {
+
+        // Partition fields refer to source columns by name, which follow column renames.
+        let mut config = previous_metadata.config.clone();
+        for cur_partition_field in config.partition_spec.iter_mut() {
+            if alter_table_request
+                .dropped_columns
+                .contains(&cur_partition_field.source_column)
+            {
+                return Err(Error::invalid_table_config(format!(
+                    "Cannot drop partition source column {}",
+                    cur_partition_field.source_column
+                )));
+            }
+            if let Some((_, new_name)) = alter_table_request
+                .renamed_columns
+                .iter()
+                .find(|(old_name, _)| *old_name == cur_partition_field.source_column)
+            {
+                cur_partition_field.source_column = new_name.clone();
+            }
+        }
+        // Row identity refers to column positions, which shift when earlier columns are dropped.
+        let remap_key_index = |idx: usize| -> Result<usize> {
+            let key_column = previous_metadata.schema.field(idx).name();
+            if alter_table_request.dropped_columns.contains(key_column) {
+                return Err(Error::invalid_table_config(format!(
+                    "Cannot drop row identity column {key_column}"
+                )));
+            }
+            let dropped_before = previous_metadata.schema.fields()[..idx]
+                .iter()
+                .filter(|field| alter_table_request.dropped_columns.contains(field.name()))
+                .count();
+            Ok(idx - dropped_before)
+        };
+        config.row_identity = match &config.row_identity {
+            IdentityProp::SinglePrimitiveKey(idx) => {
+                IdentityProp::SinglePrimitiveKey(remap_key_index(*idx)?)
+            }
+            IdentityProp::Keys(keys) => IdentityProp::Keys(
+                keys.iter()
+                    .map(|idx| remap_key_index(*idx))
+                    .collect::<Result<Vec<_>>>()?,
+            ),
+            IdentityProp::FullRow => IdentityProp::FullRow,
+            IdentityProp::None => IdentityProp::None,
+        };
+        // Same for sort fields, and dropped columns are dropped from sort order as well.
+        config.sort_order.retain(|cur_sort_field| {
+            !alter_table_request
+                .dropped_columns
+                .contains(&cur_sort_field.source_column)
+        });
+        for cur_sort_field in config.sort_order.iter_mut() {
+            if let Some((_, new_name)) = alter_table_request
+                .renamed_columns
+                .iter()
+                .find(|(old_name, _)| *old_name == cur_sort_field.source_column)
+            {
+                cur_sort_field.source_column = new_name.clone();
+            }
+        }
+        // Parquet column configs are keyed by column path, whose top-level column follows drops and renames.
+        let column_configs = std::mem::take(&mut config.parquet_writer_config.column_configs);
+        for (column, column_config) in column_configs.into_iter() {
+            let (top_level_column, nested_path) = match column.split_once('.') {
+                Some((top_level_column, nested_path)) => (top_level_column, Some(nested_path)),
+                None => (column.as_str(), None),
+            };
+            if alter_table_request
+                .dropped_columns
+                .iter()
+                .any(|dropped_column| dropped_column == top_level_column)
+            {
+                continue;
+            }
+            let top_level_column = alter_table_request
+                .renamed_columns
+                .iter()
+                .find(|(old_name, _)| old_name == top_level_column)
+                .map_or(top_level_column, |(_, new_name)| new_name.as_str());
+            let column = match nested_path {
+                Some(nested_path) => format!("{top_level_column}.{nested_path}"),
+                None => top_level_column.to_string(),
+            };
+            config
+                .parquet_writer_config
+                .column_configs
+                .insert(column, column_config);
+        }
+
+        Ok(Self {
             mooncake_table_id: previous_metadata.mooncake_table_id.clone(),
             table_id: previous_metadata.table_id,
             schema: Arc::new(new_schema),
-            config: previous_metadata.config.clone(),
+            config,
             path: previous_metadata.path.clone(),
-        }
+        })
     }
 
     /// Validate metadata invariants.
-    pub fn validate(&self) {
+    pub fn validate(&self) -> Result<()> {
         // Validate identity property.
         if self.config.row_identity == IdentityProp::None {
             assert!(self.config.append_only);
@@ -135,7 +289,15 @@ impl TableMetadata {
             assert_eq!(self.config.row_identity, IdentityProp::None);
         }
         // Validate table config.
-        self.config.validate();
+        self.config.validate()?;
+        // Validate sort columns against schema, otherwise every flush fails to sort rows.
+        SortKeyGenerator::try_new(&self.schema, &self.config.sort_order)
+            .map_err(|e| Error::invalid_table_config(format!("Invalid sort order: {e}")))?;
+        // Validate parquet column configs against schema, otherwise they never apply.
+        self.config
+            .parquet_writer_config
+            .validate_column_configs(&self.schema)?;
+        Ok(())
     }
 }
 #[derive(Clone, Debug)]
@@ -150,6 +312,9 @@ pub(crate) struct DiskFileEntry {
     pub(crate) committed_deletion_vector: BatchDeletionVector,
     /// Persisted iceberg deletion vector puffin blob.
     pub(crate) puffin_deletion_blob: Option<PuffinBlobRef>,
+    /// Sort key range of rows, only assigned for data files written with table sort order.
+    /// Data files loaded from persisted table don't record it.
+    pub(crate) sort_key_range: Option<SortKeyRange>,
 }
 
 /// Snapshot contains state of the table at a given time.
@@ -224,6 +389,15 @@ impl std::fmt::Debug for Snapshot {
     }
 }
 
+/// Table truncation, which deletes all rows committed before it.
+#[derive(Clone, Debug)]
+pub(crate) struct TableTruncation {
+    /// Truncation LSN, data files flushed at or before it are truncated.
+    lsn: u64,
+    /// Mem slice position at truncation, in-memory rows before which are truncated.
+    mem_slice_position: RecordLocation,
+}
+
 struct RecordBatchWithDeletionVector {
     batch_id: u64,
     record_batch: Arc<RecordBatch>,
@@ -267,6 +441,9 @@ pub struct SnapshotTask {
     /// Schema change, or force snapshot.
     force_empty_persistence_payload: bool,
 
+    /// Committed table truncation.
+    new_truncation: Option<TableTruncation>,
+
     /// Committed deletion records, which have been persisted into iceberg, and should be pruned from mooncake snapshot.
     committed_deletion_logs: HashSet<(FileId, usize /*row idx*/)>,
 
@@ -306,6 +483,7 @@ impl SnapshotTask {
             new_commit_point: None,
             new_streaming_xact: Vec::new(),
             force_empty_persistence_payload: false,
+            new_truncation: None,
             // Committed deletion logs which have been persisted, and should be pruned from mooncake snapshot.
             committed_deletion_logs: HashSet::new(),
             // Index merge related fields.
@@ -335,6 +513,9 @@ impl SnapshotTask {
         if !self.new_streaming_xact.is_empty() {
             return false;
         }
+        if self.new_truncation.is_some() {
+            return false;
+        }
         if !self.index_merge_result.is_empty() {
             return false;
         }
@@ -362,6 +543,7 @@ impl SnapshotTask {
             || !self.persisted_records.import_result.is_empty()
             || !self.persisted_records.index_merge_result.is_empty()
             || !self.persisted_records.data_compaction_result.is_empty()
+            || !self.persisted_records.truncation_result.is_empty()
     }
 
     /// Get newly created data files, including both batch write ones and stream write ones.
@@ -410,6 +592,14 @@ impl SnapshotTask {
         new_file_indices
     }
 
+    /// Set committed table truncation, a later truncation subsumes earlier ones.
+    pub(crate) fn set_truncation(&mut self, truncation: TableTruncation) {
+        if let Some(old_truncation) = &self.new_truncation {
+            ma::assert_le!(old_truncation.lsn, truncation.lsn);
+        }
+        self.new_truncation = Some(truncation);
+    }
+
     /// Attempt to set largest flush LSN.
     pub(crate) fn try_set_largest_flush_lsn(&mut self, flush_lsn: u64) {
         if self.new_largest_flush_lsn.is_some() && self.new_largest_flush_lsn.unwrap() >= flush_lsn
@@ -425,6 +615,7 @@ impl SnapshotTask {
 struct BackgroundTaskStatus {
     mooncake_snapshot_ongoing: bool,
     persistence_snapshot_ongoing: bool,
+    snapshot_expiration_ongoing: bool,
     index_merge_ongoing: bool,
     data_compaction_ongoing: bool,
 }
@@ -468,7 +659,7 @@ pub struct MooncakeTable {
 
     /// Auto increment id for generating unique file ids.
     /// Note, these ids is only used locally, and not persisted.
-    next_file_id: u32,
+    next_file_id: Arc<TableAutoIncrIdAllocator>,
 
     /// Batch ID counters for the two-counter allocation strategy
     non_streaming_batch_id_counter: Arc<BatchIdCounter>,
@@ -504,6 +695,16 @@ pub struct MooncakeTable {
 
     /// Table replay sender.
     event_replay_tx: Option<mpsc::UnboundedSender<MooncakeTableEvent>>,
+
+    /// Truncation within the ongoing non-streaming transaction, which takes effect at commit.
+    pending_truncation: Option<TableTruncation>,
+
+    /// Completion signal for the latest mooncake snapshot, which is set to true once snapshot state gets updated.
+    mooncake_snapshot_completion_rx: Option<watch::Receiver<bool>>,
+
+    /// Latest version of rows written by updates, keyed by lookup key.
+    /// Columns unchanged by an update are usually unchanged by later updates as well (i.e. TOAST values), so later updates to the same rows don't have to wait for mooncake snapshot and read data files.
+    updated_row_cache: LruCache<u64, MoonlinkRow>,
 }
 
 impl MooncakeTable {
@@ -551,16 +752,63 @@ impl MooncakeTable {
         .await
     }
 
+    /// Similar to [`MooncakeTable::new`], but the table is persisted into a delta table.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new_with_deltalake_table(
+        schema: Schema,
+        mooncake_table_id: String,
+        table_id: u32,
+        base_path: PathBuf,
+        deltalake_table_config: DeltalakeTableConfig,
+        table_config: MooncakeTableConfig,
+        wal_manager: WalManager,
+        object_storage_cache: Arc<dyn CacheTrait>,
+        table_filesystem_accessor: Arc<dyn BaseFileSystemAccess>,
+    ) -> Result<Self> {
+        let metadata = Arc::new(TableMetadata {
+            mooncake_table_id,
+            table_id,
+            schema: Arc::new(schema),
+            config: table_config,
+            path: base_path,
+        });
+        let deltalake_table_manager = Box::new(
+            DeltalakeTableManager::new(
+                metadata.clone(),
+                object_storage_cache.clone(),
+                table_filesystem_accessor.clone(),
+                deltalake_table_config,
+            )
+            .await?,
+        );
+
+        Self::new_with_table_manager(
+            metadata,
+            deltalake_table_manager,
+            object_storage_cache,
+            table_filesystem_accessor,
+            wal_manager,
+        )
+        .await
+    }
+
     pub(crate) async fn new_with_table_manager(
         table_metadata: Arc<TableMetadata>,
         mut table_manager: Box<dyn TableManager>,
         object_storage_cache: Arc<dyn CacheTrait>,
         table_filesystem_accessor: Arc<dyn BaseFileSystemAccess>,
-        wal_manager: WalManager,
+        mut wal_manager: WalManager,
     ) -> Result<Self> {
-        table_metadata.validate();
+        table_metadata.validate()?;
+        wal_manager.set_table_schema(table_metadata.schema.clone());
         let (table_snapshot_watch_sender, table_snapshot_watch_receiver) = watch::channel(u64::MAX);
-        let (next_file_id, current_snapshot) = table_manager.load_snapshot_from_table().await?;
+        let (next_file_id, mut current_snapshot) = table_manager.load_snapshot_from_table().await?;
+        Self::recompute_sort_key_ranges(
+            &table_metadata,
+            table_filesystem_accessor.as_ref(),
+            &mut current_snapshot,
+        )
+        .await?;
         let last_persistence_snapshot_lsn = current_snapshot.flush_lsn;
         if let Some(last_persistence_snapshot_lsn) = last_persistence_snapshot_lsn {
             // We should NOT send the wal_highest_completion_lsn, because those events are not applied at this point yet.
@@ -598,7 +846,7 @@ impl MooncakeTable {
             transaction_stream_states: HashMap::new(),
             table_snapshot_watch_sender,
             table_snapshot_watch_receiver,
-            next_file_id,
+            next_file_id: Arc::new(TableAutoIncrIdAllocator::new(next_file_id)),
             non_streaming_batch_id_counter,
             streaming_batch_id_counter,
             iceberg_table_manager: Some(table_manager),
@@ -610,13 +858,71 @@ impl MooncakeTable {
             completed_unrecorded_flush_lsns: BTreeSet::new(),
             event_replay_tx: None,
             snapshot_stats: Arc::new(SnapshotCreationStats::new(mooncake_table_id)),
+            pending_truncation: None,
+            mooncake_snapshot_completion_rx: None,
+            updated_row_cache: LruCache::new(UPDATED_ROW_CACHE_CAPACITY),
         })
     }
 
+    /// Sort key ranges are not persisted, so recompute them for data files loaded from persisted table, which are used to pick data files to recluster.
+    async fn recompute_sort_key_ranges(
+        table_metadata: &TableMetadata,
+        filesystem_accessor: &dyn BaseFileSystemAccess,
+        snapshot: &mut Snapshot,
+    ) -> Result<()> {
+        if table_metadata.config.sort_order.is_empty() {
+            return Ok(());
+        }
+        let data_files = snapshot
+            .disk_files
+            .iter()
+            .filter(|(_, disk_file_entry)| disk_file_entry.sort_key_range.is_none())
+            .map(|(cur_data_file, _)| cur_data_file.clone())
+            .collect::<Vec<_>>();
+        let file_decryption_properties = table_metadata
+            .config
+            .get_encryption_key()
+            .map(|encryption_key| encryption_key.get_file_decryption_properties());
+        let file_decryption_properties = file_decryption_properties.as_ref();
+        let sort_key_ranges =
+            stream::iter(data_files.into_iter().map(|cur_data_file| async move {
+                let sort_key_range = sort_utils::compute_sort_key_range(
+                    filesystem_accessor,
+                    cur_data_file.file_path(),
+                    table_metadata.schema.as_ref(),
+                    &table_metadata.config.partition_spec,
+                    &table_metadata.config.sort_order,
+                    file_decryption_properties,
+                )
+                .await?;
+                Ok::<_, Error>((cur_data_file, sort_key_range))
+            }))
+            .buffer_unordered(MAX_CONCURRENT_SORT_KEY_RANGE_COMPUTATION)
+            .try_collect::<Vec<_>>()
+            .await?;
+        for (cur_data_file, sort_key_range) in sort_key_ranges.into_iter() {
+            snapshot
+                .disk_files
+                .get_mut(&cur_data_file)
+                .unwrap()
+                .sort_key_range = sort_key_range;
+        }
+        Ok(())
+    }
+
+    /// Validate the alter table request against current table metadata, before blocking the table for alteration.
+    pub(crate) fn validate_alter_table(
+        &self,
+        alter_table_request: &AlterTableRequest,
+    ) -> Result<()> {
+        TableMetadata::new_for_alter_table(self.metadata.clone(), alter_table_request.clone())?;
+        Ok(())
+    }
+
     pub(crate) fn alter_table(
         &mut self,
         alter_table_request: AlterTableRequest,
-    ) -> Arc<TableMetadata> {
+    ) -> Result<Arc<TableMetadata>> {
         assert!(
             self.mem_slice.is_empty(),
             "Cannot alter table with non-empty mem slice"
@@ -630,7 +936,7 @@ impl MooncakeTable {
         let new_metadata = Arc::new(TableMetadata::new_for_alter_table(
             self.metadata.clone(),
             alter_table_request,
-        ));
+        )?);
 
         // Follow the same initialization order as mooncake table, which is used to decide batch id assignment.
         self.mem_slice = MemSlice::new(
@@ -641,13 +947,12 @@ impl MooncakeTable {
         );
         let mut guard = self.snapshot.try_write().unwrap();
         guard.reset_for_alter(new_metadata.clone());
-        assert!(
-            self.metadata.schema.fields.len() != new_metadata.schema.fields.len(),
-            "Only support alter table with add/drop fields"
-        );
 
         self.metadata = new_metadata.clone();
-        new_metadata
+        // Rows are recorded in WAL with the new schema afterwards.
+        self.wal_manager
+            .set_table_schema(new_metadata.schema.clone());
+        Ok(new_metadata)
     }
 
     /// Register event completion notifier.
@@ -752,6 +1057,14 @@ impl MooncakeTable {
         self.next_snapshot_task
             .persisted_records
             .data_compaction_result = persistence_snapshot_res.data_compaction_result;
+
+        assert!(self
+            .next_snapshot_task
+            .persisted_records
+            .truncation_result
+            .is_empty());
+        self.next_snapshot_task.persisted_records.truncation_result =
+            persistence_snapshot_res.truncation_result;
     }
 
     /// Set file indices merge result, which will be sync-ed to mooncake and iceberg snapshot in the next periodic snapshot iteration.
@@ -859,6 +1172,24 @@ impl MooncakeTable {
         self.mem_slice.get_num_rows() >= self.metadata.config.mem_slice_size
     }
 
+    pub fn get_table_id(&self) -> u32 {
+        self.metadata.table_id
+    }
+
+    pub(crate) fn get_mooncake_table_id(&self) -> &str {
+        &self.metadata.mooncake_table_id
+    }
+
+    /// Get estimated memory size in bytes for rows buffered in memory, including mem slice, mem index and streaming transactions.
+    pub fn get_estimated_memory_size(&self) -> usize {
+        self.mem_slice.get_estimated_memory_size()
+            + self
+                .transaction_stream_states
+                .values()
+                .map(|stream_state| stream_state.get_estimated_memory_size())
+                .sum::<usize>()
+    }
+
     /// Drains the current mem slice and prepares a disk slice for flushing.
     /// Adds current mem slice batches and indices to `next_snapshot_task`.
     fn prepare_disk_slice(&mut self, lsn: u64) -> Result<DiskSliceWriter> {
@@ -888,17 +1219,19 @@ impl MooncakeTable {
         self.next_snapshot_task.new_mem_indices.push(index.clone());
 
         let path = self.metadata.path.clone();
-        let next_file_id = self.next_file_id;
-        self.next_file_id += 1;
 
         let disk_slice = DiskSliceWriter::new(
             self.metadata.schema.clone(),
             path,
             batches,
             Some(lsn),
-            next_file_id,
+            self.next_file_id.clone(),
             index,
             self.metadata.config.disk_slice_writer_config.clone(),
+            self.metadata.config.partition_spec.clone(),
+            self.metadata.config.parquet_writer_config.clone(),
+            self.metadata.config.get_encryption_key().cloned(),
+            self.metadata.config.sort_order.clone(),
         );
 
         Ok(disk_slice)
@@ -1084,12 +1417,20 @@ impl MooncakeTable {
 
         let table_notify = self.table_notify.as_ref().unwrap().clone();
         let snapshot_stats = self.snapshot_stats.clone();
+        let (completion_tx, completion_rx) = watch::channel(false);
+        self.mooncake_snapshot_completion_rx = Some(completion_rx);
         // Create a detached task, whose completion will be notified separately.
         tokio::task::spawn(async move {
             let _latency_guard = snapshot_stats.start();
-            Self::create_snapshot_async(cur_snapshot, next_snapshot_task, opt, table_notify)
-                .instrument(info_span!("create_snapshot_async"))
-                .await;
+            Self::create_snapshot_async(
+                cur_snapshot,
+                next_snapshot_task,
+                opt,
+                table_notify,
+                completion_tx,
+            )
+            .instrument(info_span!("create_snapshot_async"))
+            .await;
         });
     }
 
@@ -1221,6 +1562,7 @@ impl MooncakeTable {
 
         // Perform append operation.
         let lookup_key = self.metadata.config.row_identity.get_lookup_key(&row);
+        self.updated_row_cache.pop(&lookup_key);
         let identity_for_key = self
             .metadata
             .config
@@ -1287,6 +1629,122 @@ impl MooncakeTable {
         self.delete_impl(row, lsn, /*delete_if_exists=*/ true).await;
     }
 
+    /// Fill columns unchanged by an update with their values in the current version of the row.
+    ///
+    /// Postgres doesn't send unchanged TOAST values in update events, which arrive as null in `new_row`. The current row is looked up by `old_row`, first in rows cached for earlier updates, then in the ongoing streaming transaction if any, then in the mem slice, and finally in the latest snapshot.
+    /// Return error if the current row cannot be found, since unchanged columns cannot be recovered.
+    pub async fn fill_unchanged_columns(
+        &mut self,
+        old_row: &MoonlinkRow,
+        mut new_row: MoonlinkRow,
+        unchanged_columns: &[usize],
+        xact_id: Option<u32>,
+    ) -> Result<MoonlinkRow> {
+        let identity = &self.metadata.config.row_identity;
+        let lookup_key = identity.get_lookup_key(old_row);
+        let mut current_row = self
+            .updated_row_cache
+            .get(&lookup_key)
+            .filter(|cached_row| Self::has_same_identity(identity, cached_row, old_row))
+            .cloned();
+        if current_row.is_none() {
+            current_row = self.find_current_row(old_row, xact_id).await;
+        }
+
+        let Some(current_row) = current_row else {
+            return Err(Error::row_not_found(format!(
+                "current row not found for update with unchanged columns {unchanged_columns:?}"
+            )));
+        };
+        for idx in unchanged_columns.iter().copied() {
+            if let (Some(new_value), Some(current_value)) =
+                (new_row.values.get_mut(idx), current_row.values.get(idx))
+            {
+                *new_value = current_value.clone();
+            }
+        }
+        Ok(new_row)
+    }
+
+    /// Cache the row written by an update, which is looked up by later updates to the same row.
+    pub(crate) fn cache_updated_row(&mut self, row: MoonlinkRow) {
+        let lookup_key = self.metadata.config.row_identity.get_lookup_key(&row);
+        self.updated_row_cache.put(lookup_key, row);
+    }
+
+    /// Return whether the two rows share the same identity.
+    fn has_same_identity(identity: &IdentityProp, lhs: &MoonlinkRow, rhs: &MoonlinkRow) -> bool {
+        match identity {
+            IdentityProp::SinglePrimitiveKey(key) => lhs.values.get(*key) == rhs.values.get(*key),
+            IdentityProp::Keys(keys) => keys
+                .iter()
+                .all(|key| lhs.values.get(*key) == rhs.values.get(*key)),
+            IdentityProp::FullRow => lhs.values == rhs.values,
+            IdentityProp::None => false,
+        }
+    }
+
+    /// Look up the current version of the row identified by `old_row`.
+    async fn find_current_row(
+        &self,
+        old_row: &MoonlinkRow,
+        xact_id: Option<u32>,
+    ) -> Option<MoonlinkRow> {
+        let identity = &self.metadata.config.row_identity;
+        let record = RawDeletionRecord {
+            lookup_key: identity.get_lookup_key(old_row),
+            lsn: 0, // Unused for lookup.
+            pos: None,
+            row_identity: identity.extract_identity_columns(old_row.clone()),
+            delete_if_exists: false,
+        };
+
+        let mut current_row = None;
+        if let Some(stream_state) =
+            xact_id.and_then(|xact_id| self.transaction_stream_states.get(&xact_id))
+        {
+            current_row = stream_state.find_non_deleted_row(&record, identity).await;
+        }
+        if current_row.is_none() {
+            current_row = self.mem_slice.find_non_deleted_row(&record, identity).await;
+        }
+        if current_row.is_none() {
+            // Pending batches and deletions are moved out of [`next_snapshot_task`] when a mooncake snapshot starts, wait until they land in snapshot state.
+            if let Some(completion_rx) = &self.mooncake_snapshot_completion_rx {
+                let _ = completion_rx.clone().wait_for(|completed| *completed).await;
+            }
+            current_row = self
+                .snapshot
+                .read()
+                .await
+                .find_non_deleted_row(&record, &self.next_snapshot_task)
+                .await;
+        }
+        current_row
+    }
+
+    /// Truncate all rows committed before the current transaction, which takes effect at commit.
+    /// Rows appended earlier in the current transaction are deleted as well.
+    pub fn truncate(&mut self, lsn: u64) {
+        // Record events for truncation.
+        if let Some(event_replay_tx) = &self.event_replay_tx {
+            let table_event = replay_events::create_truncate_event(
+                /*lsn=*/ Some(lsn),
+                /*xact_id=*/ None,
+            );
+            event_replay_tx
+                .send(MooncakeTableEvent::Truncate(table_event))
+                .unwrap();
+        }
+
+        self.mem_slice.delete_all_rows();
+        self.updated_row_cache.clear();
+        self.pending_truncation = Some(TableTruncation {
+            lsn,
+            mem_slice_position: self.mem_slice.get_commit_check_point(),
+        });
+    }
+
     pub fn commit(&mut self, lsn: u64) {
         // Record events for commit.
         if let Some(event_replay_tx) = &self.event_replay_tx {
@@ -1301,6 +1759,10 @@ impl MooncakeTable {
         ma::assert_ge!(lsn, self.next_snapshot_task.commit_lsn_baseline);
         self.next_snapshot_task.commit_lsn_baseline = lsn;
         self.next_snapshot_task.new_commit_point = Some(self.mem_slice.get_commit_check_point());
+        if let Some(truncation) = self.pending_truncation.take() {
+            ma::assert_lt!(truncation.lsn, lsn);
+            self.next_snapshot_task.set_truncation(truncation);
+        }
         assert!(
             self.next_snapshot_task.new_deletions.is_empty()
                 || self.next_snapshot_task.new_deletions.last().unwrap().lsn >= transaction_stream::LSN_START_FOR_STREAMING_XACT
@@ -1391,9 +1853,9 @@ impl MooncakeTable {
         }
 
         // Perform index merge operation.
-        let cur_file_id = self.next_file_id as u64;
-        self.next_file_id += 1;
+        let cur_file_id = self.next_file_id.allocate(1) as u64;
         let table_directory = std::path::PathBuf::from(self.metadata.path.to_str().unwrap());
+        let encryption_key = self.metadata.config.get_encryption_key().cloned();
         let table_notify_tx_copy = self.table_notify.as_ref().unwrap().clone();
 
         // Create a detached task, whose completion will be notified separately.
@@ -1401,6 +1863,7 @@ impl MooncakeTable {
             let result: Result<()> = async move {
                 let mut builder = GlobalIndexBuilder::new();
                 builder.set_directory(table_directory);
+                builder.set_encryption_key(encryption_key);
                 let merged = builder
                     .build_from_merge(file_indice_merge_payload.file_indices.clone(), cur_file_id)
                     .await;
@@ -1465,9 +1928,9 @@ impl MooncakeTable {
         // Perform data compaction operation.
         let data_compaction_new_file_ids =
             compaction_payload.get_new_compacted_data_file_ids_number();
+        let start_table_auto_incr_id = self.next_file_id.allocate(data_compaction_new_file_ids);
         let table_auto_incr_ids =
-            self.next_file_id..(self.next_file_id + data_compaction_new_file_ids);
-        self.next_file_id += data_compaction_new_file_ids;
+            start_table_auto_incr_id..(start_table_auto_incr_id + data_compaction_new_file_ids);
         let file_params = CompactionFileParams {
             dir_path: self.metadata.path.clone(),
             table_auto_incr_ids,
@@ -1476,6 +1939,10 @@ impl MooncakeTable {
                 .config
                 .data_compaction_config
                 .data_file_final_size,
+            partition_spec: self.metadata.config.partition_spec.clone(),
+            parquet_writer_config: self.metadata.config.parquet_writer_config.clone(),
+            encryption_key: self.metadata.config.get_encryption_key().cloned(),
+            sort_order: self.metadata.config.sort_order.clone(),
         };
         let schema_ref = self.metadata.schema.clone();
         let table_notify_tx_copy = self.table_notify.as_ref().unwrap().clone();
@@ -1516,12 +1983,15 @@ impl MooncakeTable {
         next_snapshot_task: SnapshotTask,
         opt: SnapshotOption,
         table_notify: Sender<TableEvent>,
+        completion_tx: watch::Sender<bool>,
     ) {
         let mooncake_snapshot_result = snapshot
             .write()
             .await
             .update_snapshot(next_snapshot_task, opt)
             .await;
+        // Receiver could be dropped if a newer snapshot has been initiated, or the table has been dropped.
+        let _ = completion_tx.send(true);
 
         // Send back completion notification to table handler.
         table_notify
@@ -1577,6 +2047,14 @@ impl MooncakeTable {
             .data_compaction_payload
             .data_file_records_remap
             .clone();
+        let old_data_files_to_remove_by_truncation = snapshot_payload
+            .truncation_payload
+            .old_data_files_to_remove
+            .clone();
+        let old_file_indices_to_remove_by_truncation = snapshot_payload
+            .truncation_payload
+            .old_file_indices_to_remove
+            .clone();
 
         let persistence_file_params = PersistenceFileParams {
             table_auto_incr_ids,
@@ -1652,6 +2130,10 @@ impl MooncakeTable {
                 old_file_indices_removed: old_file_indices_to_remove_by_compaction,
                 data_file_records_remap: data_file_record_remap_by_compaction,
             },
+            truncation_result: PersistenceSnapshotTruncationResult {
+                old_data_files_removed: old_data_files_to_remove_by_truncation,
+                old_file_indices_removed: old_file_indices_to_remove_by_truncation,
+            },
             evicted_files_to_delete: iceberg_persistence_res.evicted_files_to_delete,
         };
 
@@ -1681,8 +2163,9 @@ impl MooncakeTable {
 
         // Create a detached task, whose completion will be notified separately.
         let new_file_ids_to_create = snapshot_payload.get_new_file_ids_num();
-        let table_auto_incr_ids = self.next_file_id..(self.next_file_id + new_file_ids_to_create);
-        self.next_file_id += new_file_ids_to_create;
+        let start_table_auto_incr_id = self.next_file_id.allocate(new_file_ids_to_create);
+        let table_auto_incr_ids =
+            start_table_auto_incr_id..(start_table_auto_incr_id + new_file_ids_to_create);
         let table_event_id = snapshot_payload.uuid;
 
         // Record index merge event initiation.
@@ -1709,6 +2192,72 @@ impl MooncakeTable {
             .instrument(info_span!("persist_iceberg_snapshot")),
         );
     }
+
+    /// Expire iceberg snapshots.
+    async fn expire_snapshots_impl(
+        mut iceberg_table_manager: Box<dyn TableManager>,
+        retention_config: SnapshotRetentionConfig,
+        table_notify: Sender<TableEvent>,
+    ) {
+        let report = iceberg_table_manager
+            .expire_snapshots(retention_config)
+            .await;
+        table_notify
+            .send(TableEvent::SnapshotExpirationResult {
+                snapshot_expiration_result: SnapshotExpirationResult {
+                    table_manager: Some(iceberg_table_manager),
+                    report,
+                },
+            })
+            .await
+            .unwrap();
+    }
+
+    /// Expire iceberg snapshots based on table retention config in a background task, if `dry_run` is true, only report what would be removed.
+    /// Snapshot expiration takes table manager, so it cannot run concurrently with iceberg snapshot.
+    pub(crate) fn perform_snapshot_expiration(&mut self, dry_run: bool) {
+        let iceberg_table_manager = self.iceberg_table_manager.take().unwrap();
+        assert!(
+            !self
+                .background_task_status_for_validation
+                .snapshot_expiration_ongoing
+        );
+        self.background_task_status_for_validation
+            .snapshot_expiration_ongoing = true;
+
+        let mut retention_config = self.metadata.config.snapshot_retention_config.clone();
+        retention_config.dry_run |= dry_run;
+        tokio::task::spawn(
+            Self::expire_snapshots_impl(
+                iceberg_table_manager,
+                retention_config,
+                self.table_notify.as_ref().unwrap().clone(),
+            )
+            .instrument(info_span!("expire_snapshots")),
+        );
+    }
+
+    /// Get snapshot retention config for the table.
+    pub(crate) fn get_snapshot_retention_config(&self) -> &SnapshotRetentionConfig {
+        &self.metadata.config.snapshot_retention_config
+    }
+
+    /// Set snapshot expiration result, which returns table manager back to the mooncake table.
+    pub(crate) fn set_snapshot_expiration_res(
+        &mut self,
+        snapshot_expiration_res: SnapshotExpirationResult,
+    ) -> Result<SnapshotExpirationReport> {
+        assert!(
+            self.background_task_status_for_validation
+                .snapshot_expiration_ongoing
+        );
+        self.background_task_status_for_validation
+            .snapshot_expiration_ongoing = false;
+
+        assert!(self.iceberg_table_manager.is_none());
+        self.iceberg_table_manager = Some(snapshot_expiration_res.table_manager.unwrap());
+        snapshot_expiration_res.report
+    }
 }
 
 #[cfg(test)]
@@ -1735,6 +2284,7 @@ mod mooncake_tests {
             },
             index_merge_result: PersistenceSnapshotIndexMergeResult::default(),
             data_compaction_result: PersistenceSnapshotDataCompactionResult::default(),
+            truncation_result: PersistenceSnapshotTruncationResult::default(),
             evicted_files_to_delete: Vec::new(),
         };
         // Valid snapshot result.
@@ -1789,10 +2339,6 @@ mod mooncake_tests {
 
 #[cfg(test)]
 impl MooncakeTable {
-    pub fn get_table_id(&self) -> u32 {
-        self.metadata.table_id
-    }
-
     pub(crate) fn get_snapshot_watch_sender(&self) -> watch::Sender<u64> {
         self.table_snapshot_watch_sender.clone()
     }
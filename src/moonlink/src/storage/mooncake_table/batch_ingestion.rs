@@ -27,7 +27,11 @@ async fn ensure_parquet_files_local_filesystem(
         // Already at local filesystem, skip.
         #[cfg(feature = "storage-fs")]
         StorageConfig::FileSystem { .. } => Ok(parquet_file),
-        #[cfg(any(feature = "storage-gcs", feature = "storage-s3"))]
+        #[cfg(any(
+            feature = "storage-gcs",
+            feature = "storage-s3",
+            feature = "storage-azure"
+        ))]
         _ => {
             let filename_without_suffix = std::path::Path::new(&parquet_file)
                 .file_stem()
@@ -54,7 +58,8 @@ async fn ensure_parquet_files_local_filesystem(
         #[cfg(all(
             not(feature = "storage-fs"),
             not(feature = "storage-gcs"),
-            not(feature = "storage-s3")
+            not(feature = "storage-s3"),
+            not(feature = "storage-azure")
         ))]
         _ => {
             panic!("Unknown storage config {:?}", storage_config);
@@ -73,9 +78,21 @@ impl MooncakeTable {
         parquet_files: Vec<String>,
         storage_config: StorageConfig,
         lsn: u64,
-    ) {
-        let start_id = self.next_file_id;
-        self.next_file_id += parquet_files.len() as u32;
+    ) -> Result<()> {
+        // External parquet files are imported as-is, which don't guarantee one partition per data file.
+        if !self.metadata.config.partition_spec.is_empty() {
+            return Err(Error::invalid_table_config(
+                "Batch ingestion is not supported for partitioned tables".to_string(),
+            ));
+        }
+        // External parquet files are plaintext, which cannot be read with table data key.
+        if self.metadata.config.table_encryption.is_some() {
+            return Err(Error::invalid_table_config(
+                "Batch ingestion is not supported for encrypted tables".to_string(),
+            ));
+        }
+
+        let start_id = self.next_file_id.allocate(parquet_files.len() as u32);
 
         // Create filesystem accessor to download remote parquet files to local filesystem.
         let accessor_config = AccessorConfig::new_with_storage_config(storage_config.clone());
@@ -122,6 +139,7 @@ impl MooncakeTable {
                         file_size,
                         committed_deletion_vector: BatchDeletionVector::new(num_rows),
                         puffin_deletion_blob: None,
+                        sort_key_range: None,
                     };
 
                     (mooncake_data_file, disk_file_entry)
@@ -141,7 +159,7 @@ impl MooncakeTable {
             let files = commit.get_flushed_data_files();
             let identity = self.metadata.config.row_identity.clone();
             let table_dir: PathBuf = self.metadata.path.clone();
-            let index_file_id = self.next_file_id as u64;
+            let index_file_id = self.next_file_id.allocate(1) as u64;
 
             if let Ok(file_index) =
                 Self::build_index_for_files(files, identity, table_dir, index_file_id).await
@@ -160,6 +178,8 @@ impl MooncakeTable {
         self.next_snapshot_task.new_flush_lsn = Some(lsn);
         self.next_snapshot_task.new_largest_flush_lsn = Some(lsn);
         self.next_snapshot_task.commit_lsn_baseline = lsn;
+
+        Ok(())
     }
 
     /// Build a single GlobalIndex spanning `files` by scanning Parquet with identity projection.
@@ -269,7 +289,8 @@ mod tests {
                 storage_config,
                 lsn,
             )
-            .await;
+            .await
+            .unwrap();
 
         // Verify next_snapshot_task updated
         assert_eq!(table.next_snapshot_task.new_streaming_xact.len(), 1);
@@ -320,7 +341,8 @@ mod tests {
                 storage_config,
                 lsn,
             )
-            .await;
+            .await
+            .unwrap();
 
         // Commit enqueued and index attached
         let (mut flushed_files, mut indices) = match &table.next_snapshot_task.new_streaming_xact[0]
@@ -381,7 +403,8 @@ mod tests {
                 storage_config,
                 500,
             )
-            .await;
+            .await
+            .unwrap();
 
         let (mut files, mut indices) = match &table.next_snapshot_task.new_streaming_xact[0] {
             TransactionStreamOutput::Commit(commit) => {
@@ -433,7 +456,8 @@ mod tests {
                 storage_config,
                 600,
             )
-            .await;
+            .await
+            .unwrap();
 
         let (files, mut indices) = match &table.next_snapshot_task.new_streaming_xact[0] {
             TransactionStreamOutput::Commit(commit) => {
@@ -485,7 +509,8 @@ mod tests {
                 storage_config,
                 300,
             )
-            .await;
+            .await
+            .unwrap();
 
         match &table.next_snapshot_task.new_streaming_xact[0] {
             TransactionStreamOutput::Abort(_) => panic!("unexpected abort"),
@@ -511,7 +536,10 @@ mod tests {
             root_directory: context.path().to_str().unwrap().to_string(),
             atomic_write_dir: None,
         };
-        table.batch_ingest(vec![], storage_config, 400).await;
+        table
+            .batch_ingest(vec![], storage_config, 400)
+            .await
+            .unwrap();
 
         match &table.next_snapshot_task.new_streaming_xact[0] {
             TransactionStreamOutput::Abort(_) => panic!("unexpected abort"),
@@ -544,7 +572,10 @@ mod tests {
             root_directory: context.path().to_str().unwrap().to_string(),
             atomic_write_dir: None,
         };
-        table.batch_ingest(vec![], storage_config, 700).await;
+        table
+            .batch_ingest(vec![], storage_config, 700)
+            .await
+            .unwrap();
 
         match &table.next_snapshot_task.new_streaming_xact[0] {
             TransactionStreamOutput::Commit(commit) => {
@@ -569,7 +600,10 @@ mod tests {
             root_directory: context.path().to_str().unwrap().to_string(),
             atomic_write_dir: None,
         };
-        table.batch_ingest(vec![], storage_config, 800).await;
+        table
+            .batch_ingest(vec![], storage_config, 800)
+            .await
+            .unwrap();
 
         match &table.next_snapshot_task.new_streaming_xact[0] {
             TransactionStreamOutput::Commit(commit) => {
@@ -619,6 +653,26 @@ mod tests {
                 storage_config,
                 900,
             )
-            .await;
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_batch_ingest_partitioned_table_rejected() {
+        let context = TestContext::new("batch_ingest_partitioned");
+        let mut table = crate::storage::mooncake_table::test_utils::test_partitioned_table(
+            &context,
+            "t_partitioned",
+            "age",
+        )
+        .await;
+
+        let storage_config = crate::StorageConfig::FileSystem {
+            root_directory: context.path().to_str().unwrap().to_string(),
+            atomic_write_dir: None,
+        };
+        let res = table.batch_ingest(vec![], storage_config, 1000).await;
+        assert!(res.is_err());
+        assert!(table.next_snapshot_task.new_streaming_xact.is_empty());
     }
 }
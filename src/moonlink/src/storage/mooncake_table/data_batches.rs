@@ -45,6 +45,12 @@ impl InMemoryBatch {
         Ok(Some(batch))
     }
 
+    /// Get the row at the given offset, only available after the batch is finalized.
+    pub fn get_row(&self, row_offset: usize) -> Option<MoonlinkRow> {
+        let batch = self.data.as_ref()?;
+        MoonlinkRow::from_record_batch(&batch.slice(row_offset, 1)).pop()
+    }
+
     pub fn get_filtered_batch_with_limit(&self, row_limit: usize) -> Result<Option<RecordBatch>> {
         assert!(self.data.is_some());
         let batch = self
@@ -74,6 +80,10 @@ pub struct ColumnStoreBuffer {
     current_rows: SharedRowBuffer,
     /// Current row count in the current buffer
     current_row_count: usize,
+    /// Estimated memory size for finalized record batches.
+    finalized_batches_memory_size: usize,
+    /// Estimated memory size for rows in the current buffer.
+    current_rows_memory_size: usize,
     /// Batch ID allocator counter
     batch_id_counter: Arc<BatchIdCounter>,
 }
@@ -107,6 +117,8 @@ impl ColumnStoreBuffer {
             current_batch_builder,
             current_rows: SharedRowBuffer::new(max_rows_per_buffer),
             current_row_count: 0,
+            finalized_batches_memory_size: 0,
+            current_rows_memory_size: 0,
             batch_id_counter,
         }
     }
@@ -131,6 +143,10 @@ impl ColumnStoreBuffer {
             assert!(_res.is_ok());
         });
         self.current_row_count += 1;
+        self.current_rows_memory_size += values
+            .iter()
+            .map(|value| value.get_estimated_memory_size())
+            .sum::<usize>();
 
         Ok((
             self.in_memory_batches.last().unwrap().id,
@@ -157,6 +173,8 @@ impl ColumnStoreBuffer {
             self.current_batch_builder[idx].append_value(cell)?;
         }
         self.current_row_count += 1;
+        // Row is buffered both in array builders and in shared row buffer.
+        self.current_rows_memory_size += 2 * row.get_estimated_memory_size();
         self.current_rows.push(row);
 
         Ok((
@@ -198,6 +216,8 @@ impl ColumnStoreBuffer {
         // Reset the current batch
         self.current_row_count = 0;
         self.current_rows = SharedRowBuffer::new(self.max_rows_per_buffer);
+        self.current_rows_memory_size = 0;
+        self.finalized_batches_memory_size += batch.get_array_memory_size();
 
         Ok(Some((next_batch_id - 1, batch)))
     }
@@ -229,6 +249,18 @@ impl ColumnStoreBuffer {
         }
     }
 
+    /// Get the row at the given position, which could be either in a finalized batch or the current one.
+    pub(super) fn get_row(&self, batch_id: u64, row_offset: usize) -> MoonlinkRow {
+        let idx = self
+            .in_memory_batches
+            .binary_search_by_key(&batch_id, |x| x.id)
+            .unwrap();
+        match self.in_memory_batches[idx].batch.get_row(row_offset) {
+            Some(row) => row,
+            None => self.current_rows.get_row(row_offset).clone(),
+        }
+    }
+
     pub fn find_valid_row_by_record(
         &self,
         record: &RawDeletionRecord,
@@ -289,11 +321,29 @@ impl ColumnStoreBuffer {
         None
     }
 
+    /// Mark all rows in the buffer as deleted.
+    pub(super) fn delete_all_rows(&mut self) {
+        let current_row_count = self.current_row_count;
+        let last_idx = self.in_memory_batches.len() - 1;
+        for (idx, entry) in self.in_memory_batches.iter_mut().enumerate() {
+            let num_rows = if idx == last_idx {
+                current_row_count
+            } else {
+                entry.batch.get_raw_record_number() as usize
+            };
+            for row_idx in 0..num_rows {
+                // Rows already deleted are skipped.
+                let _ = entry.batch.deletions.delete_row(row_idx);
+            }
+        }
+    }
+
     pub(super) fn drain(&mut self) -> Vec<BatchEntry> {
         assert!(self.current_row_count == 0);
         let last = self.in_memory_batches.pop();
         let current_batch = std::mem::take(&mut self.in_memory_batches);
         self.in_memory_batches.push(last.unwrap());
+        self.finalized_batches_memory_size = 0;
         current_batch
     }
 
@@ -301,6 +351,11 @@ impl ColumnStoreBuffer {
         (self.in_memory_batches.len() - 1) * self.max_rows_per_buffer + self.current_row_count
     }
 
+    /// Get estimated memory size in bytes for all buffered rows.
+    pub(super) fn get_estimated_memory_size(&self) -> usize {
+        self.finalized_batches_memory_size + self.current_rows_memory_size
+    }
+
     pub(super) fn get_commit_check_point(&self) -> RecordLocation {
         RecordLocation::MemoryBatch(
             self.in_memory_batches.last().unwrap().id,
@@ -63,6 +63,22 @@ impl BatchDeletionVector {
         exist
     }
 
+    /// Mark all rows before [`num_rows`] as deleted, rows already deleted are left untouched.
+    pub(crate) fn delete_rows_before(&mut self, num_rows: usize) {
+        ma::assert_le!(num_rows, self.max_rows);
+        if num_rows == 0 {
+            return;
+        }
+
+        self.initialize_vector_for_once();
+        let bitmap = self.deletion_vector.as_mut().unwrap();
+        let full_bytes = num_rows / 8;
+        bitmap[..full_bytes].fill(0);
+        for row_idx in full_bytes * 8..num_rows {
+            bit_util::unset_bit(bitmap, row_idx);
+        }
+    }
+
     /// Merge with another batch deletion vector.
     pub(crate) fn merge_with(&mut self, rhs: &BatchDeletionVector) {
         assert_eq!(
@@ -243,6 +259,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_delete_rows_before() {
+        let mut batch_deletion_vector = BatchDeletionVector::new(/*max_rows=*/ 20);
+        assert!(batch_deletion_vector.delete_row(3));
+        assert!(batch_deletion_vector.delete_row(15));
+
+        // Deleting no rows doesn't change the deletion vector.
+        batch_deletion_vector.delete_rows_before(/*num_rows=*/ 0);
+        assert_eq!(batch_deletion_vector.collect_deleted_rows(), vec![3, 15]);
+
+        // Rows before the boundary are deleted, including the ones already deleted.
+        batch_deletion_vector.delete_rows_before(/*num_rows=*/ 11);
+        assert_eq!(
+            batch_deletion_vector.collect_deleted_rows(),
+            (0..11).chain(std::iter::once(15)).collect::<Vec<u64>>()
+        );
+        assert!(!batch_deletion_vector.delete_row(10));
+        assert!(batch_deletion_vector.delete_row(11));
+
+        // All rows are deleted.
+        batch_deletion_vector.delete_rows_before(/*num_rows=*/ 20);
+        assert_eq!(batch_deletion_vector.get_num_rows_deleted(), 20);
+        assert!(batch_deletion_vector.collect_active_rows(20).is_empty());
+    }
+
     #[test]
     fn test_apply_filter_with_slice() {
         // Create deletion vector.
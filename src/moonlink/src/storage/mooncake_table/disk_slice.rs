@@ -1,19 +1,27 @@
 use super::data_batches::BatchEntry;
 use crate::error::{Error, Result};
 use crate::storage::cache::object_storage::base_cache::CacheTrait;
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::filesystem::accessor::chaos_generator::ChaosGenerator;
 use crate::storage::index::persisted_bucket_hash_map::GlobalIndexBuilder;
 use crate::storage::index::{cache_utils as index_cache_utils, FileIndex, MemIndex};
-use crate::storage::mooncake_table_config::DiskSliceWriterConfig;
+use crate::storage::mooncake_table_config::{
+    DiskSliceWriterConfig, ParquetWriterConfig, PartitionFieldConfig, SortFieldConfig,
+};
 use crate::storage::parquet_utils;
+use crate::storage::partition_utils::{PartitionKey, PartitionKeyGenerator, PartitionSourceValues};
+use crate::storage::sort_utils::{SortKeyGenerator, SortKeyRange, SORTED_WRITE_BATCH_SIZE};
 use crate::storage::storage_utils::{
-    create_data_file, get_random_file_name_in_dir, get_unique_file_id_for_flush,
-    MooncakeDataFileRef, ProcessedDeletionRecord, RecordLocation, TableId,
+    create_data_file_with_partition, get_random_file_name_in_dir, get_unique_file_id_for_flush,
+    MooncakeDataFileRef, ProcessedDeletionRecord, RecordLocation, TableAutoIncrIdAllocator,
+    TableId, NUM_FILES_PER_FLUSH,
 };
 
+use arrow::compute::concat_batches;
 use arrow_array::RecordBatch;
 use arrow_schema::Schema;
 use parquet::arrow::AsyncArrowWriter;
+use parquet::format::SortingColumn;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -23,6 +31,8 @@ use std::sync::Arc;
 pub(crate) struct DiskFileAttrs {
     pub(crate) file_size: usize,
     pub(crate) row_num: usize,
+    /// Sort key range of rows, only assigned for tables with sort order.
+    pub(crate) sort_key_range: Option<SortKeyRange>,
 }
 
 #[derive(Clone)]
@@ -40,11 +50,27 @@ pub struct DiskSliceWriter {
 
     old_index: Arc<MemIndex>,
 
-    pub table_auto_incr_id: u32,
+    /// Table auto increment ids allocated for the flush, each of which covers [`NUM_FILES_PER_FLUSH`] file ids.
+    table_auto_incr_ids: Vec<u32>,
+
+    /// Allocates more table auto increment ids, when data files and file index exceed the allocated ones.
+    table_auto_incr_id_allocator: Arc<TableAutoIncrIdAllocator>,
 
     /// Write config.
     disk_slice_writer_config: DiskSliceWriterConfig,
 
+    /// Partition spec, rows in different partitions are written to different data files.
+    partition_spec: Vec<PartitionFieldConfig>,
+
+    /// Parquet writer options for data files.
+    parquet_writer_config: ParquetWriterConfig,
+
+    /// Data key to encrypt data files and file indices with, if the table is encrypted.
+    encryption_key: Option<DataEncryptionKey>,
+
+    /// Sort order, rows within each data file are sorted by it.
+    sort_order: Vec<SortFieldConfig>,
+
     // a mapping of old record locations to new record locations
     // this is used to remap deletions on the disk slice
     batch_id_to_idx: HashMap<u64, usize>,
@@ -74,15 +100,47 @@ impl std::fmt::Debug for DiskSliceWriter {
     }
 }
 
+/// Ongoing parquet file write for one partition.
+struct PartitionFileWriter {
+    /// Index of the data file within the disk slice.
+    file_idx: usize,
+    data_file: MooncakeDataFileRef,
+    writer: AsyncArrowWriter<tokio::fs::File>,
+    row_num: usize,
+}
+
+impl PartitionFileWriter {
+    async fn finish(
+        mut self,
+        sort_key_range: Option<SortKeyRange>,
+    ) -> Result<(MooncakeDataFileRef, DiskFileAttrs)> {
+        self.writer.finish().await?;
+        let file_size = self.writer.bytes_written();
+        Ok((
+            self.data_file,
+            DiskFileAttrs {
+                file_size,
+                row_num: self.row_num,
+                sort_key_range,
+            },
+        ))
+    }
+}
+
 impl DiskSliceWriter {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         schema: Arc<Schema>,
         dir_path: PathBuf,
         batches: Vec<BatchEntry>,
         writer_lsn: Option<u64>,
-        table_auto_incr_id: u32,
+        table_auto_incr_id_allocator: Arc<TableAutoIncrIdAllocator>,
         old_index: Arc<MemIndex>,
         disk_slice_writer_config: DiskSliceWriterConfig,
+        partition_spec: Vec<PartitionFieldConfig>,
+        parquet_writer_config: ParquetWriterConfig,
+        encryption_key: Option<DataEncryptionKey>,
+        sort_order: Vec<SortFieldConfig>,
     ) -> Self {
         Self {
             schema,
@@ -91,11 +149,16 @@ impl DiskSliceWriter {
             files: vec![],
             batch_id_to_idx: HashMap::new(),
             writer_lsn,
-            table_auto_incr_id,
+            table_auto_incr_ids: vec![table_auto_incr_id_allocator.allocate(1)],
+            table_auto_incr_id_allocator,
             row_offset_mapping: vec![],
             old_index,
             new_index: None,
             disk_slice_writer_config,
+            partition_spec,
+            parquet_writer_config,
+            encryption_key,
+            sort_order,
         }
     }
 
@@ -127,7 +190,13 @@ impl DiskSliceWriter {
                 id += 1;
             }
         }
-        self.write_batch_to_parquet(&filtered_batches).await?;
+        match SortKeyGenerator::try_new(&self.schema, &self.sort_order)? {
+            Some(sort_key_generator) => {
+                self.write_sorted_batch_to_parquet(&filtered_batches, &sort_key_generator)
+                    .await?
+            }
+            None => self.write_batch_to_parquet(&filtered_batches).await?,
+        }
         self.remap_index().await?;
         Ok(())
     }
@@ -177,79 +246,227 @@ impl DiskSliceWriter {
         &self.old_index
     }
 
+    /// Get unique file id for the given file index within the flush, data files and the file index share unique file ids.
+    /// Allocate a new table auto increment id if the allocated ones are used up.
+    fn get_unique_file_id(&mut self, file_idx: usize) -> u64 {
+        let auto_incr_id_offset = file_idx / NUM_FILES_PER_FLUSH as usize;
+        while self.table_auto_incr_ids.len() <= auto_incr_id_offset {
+            self.table_auto_incr_ids
+                .push(self.table_auto_incr_id_allocator.allocate(1));
+        }
+        get_unique_file_id_for_flush(
+            self.table_auto_incr_ids[auto_incr_id_offset] as u64,
+            file_idx as u64 % NUM_FILES_PER_FLUSH,
+        )
+    }
+
+    /// Create a new data file and its parquet writer.
+    async fn create_partition_file_writer(
+        &mut self,
+        file_idx: usize,
+        partition_values: Option<PartitionSourceValues>,
+        sorting_columns: Option<Vec<SortingColumn>>,
+    ) -> Result<PartitionFileWriter> {
+        let file_id = self.get_unique_file_id(file_idx);
+        let file_path = get_random_file_name_in_dir(&self.dir_path);
+        let data_file = create_data_file_with_partition(file_id, file_path, partition_values);
+        let file = tokio::fs::File::create(self.dir_path.join(data_file.file_path()))
+            .await
+            .map_err(Into::<Error>::into)?;
+        let properties = parquet_utils::get_disk_slice_parquet_properties(
+            &self.parquet_writer_config,
+            self.encryption_key.as_ref(),
+            sorting_columns,
+        )?;
+        let writer = AsyncArrowWriter::try_new(file, self.schema.clone(), Some(properties))?;
+        Ok(PartitionFileWriter {
+            file_idx,
+            data_file,
+            writer,
+            row_num: 0,
+        })
+    }
+
     /// Write record batches to parquet files in synchronous mode.
+    /// For partitioned tables, each data file only contains rows within one partition.
     /// TODO(hjiang): Parallelize the parquet file write operations.
     #[tracing::instrument(name = "write_parquet_batches", skip_all)]
     async fn write_batch_to_parquet(
         &mut self,
         record_batches: &Vec<(usize, RecordBatch, Vec<usize>)>,
     ) -> Result<()> {
-        let mut files = Vec::new();
-        let mut writer = None;
-        let mut out_file_idx = 0;
-        let mut out_row_idx = 0;
-        let dir_path = &self.dir_path;
-        let mut data_file = None;
+        let mut partition_key_generator =
+            PartitionKeyGenerator::try_new(&self.schema, &self.partition_spec)?;
+        // Data files indexed by file index, which are assigned on creation and populated on finish.
+        let mut files: Vec<Option<(MooncakeDataFileRef, DiskFileAttrs)>> = Vec::new();
+        // Maps from partition key to its ongoing writer, unpartitioned tables use `None` as key.
+        let mut writers: HashMap<Option<PartitionKey>, PartitionFileWriter> = HashMap::new();
         for (batch_id, batch, row_indices) in record_batches {
-            if writer.is_none() {
-                // Generate a unique file name
-                // Create the file
-                out_file_idx = files.len();
-                let file_id = get_unique_file_id_for_flush(
-                    self.table_auto_incr_id as u64,
-                    out_file_idx as u64,
-                );
-                let file_path = get_random_file_name_in_dir(dir_path);
-                data_file = Some(create_data_file(file_id, file_path));
-                let file =
-                    tokio::fs::File::create(dir_path.join(data_file.as_ref().unwrap().file_path()))
-                        .await
-                        .map_err(Into::<Error>::into)?;
-                let properties = parquet_utils::get_default_parquet_properties();
-                writer = Some(AsyncArrowWriter::try_new(
-                    file,
-                    self.schema.clone(),
-                    Some(properties),
-                )?);
-                out_row_idx = 0;
-            }
-            for row_idx in row_indices {
-                self.row_offset_mapping[*batch_id][*row_idx] = Some((out_file_idx, out_row_idx));
-                out_row_idx += 1;
+            let partitioned_batches = match partition_key_generator.as_mut() {
+                Some(generator) => generator
+                    .split_by_partition(batch)?
+                    .into_iter()
+                    .map(
+                        |(partition_key, partitioned_batch, partitioned_row_indices)| {
+                            // Map row indices within the filtered batch to the ones in the original batch.
+                            let original_row_indices = partitioned_row_indices
+                                .into_iter()
+                                .map(|idx| row_indices[idx])
+                                .collect::<Vec<_>>();
+                            (Some(partition_key), partitioned_batch, original_row_indices)
+                        },
+                    )
+                    .collect::<Vec<_>>(),
+                None => vec![(None, batch.clone(), row_indices.clone())],
+            };
+
+            for (partition_key, partitioned_batch, partitioned_row_indices) in partitioned_batches {
+                if !writers.contains_key(&partition_key) {
+                    let partition_values = match partition_key_generator.as_ref() {
+                        Some(generator) => {
+                            Some(generator.get_partition_source_values(&partitioned_batch)?)
+                        }
+                        None => None,
+                    };
+                    let writer = self
+                        .create_partition_file_writer(
+                            files.len(),
+                            partition_values,
+                            /*sorting_columns=*/ None,
+                        )
+                        .await?;
+                    files.push(None);
+                    writers.insert(partition_key.clone(), writer);
+                }
+                let writer = writers.get_mut(&partition_key).unwrap();
+                for row_idx in partitioned_row_indices {
+                    self.row_offset_mapping[*batch_id][row_idx] =
+                        Some((writer.file_idx, writer.row_num));
+                    writer.row_num += 1;
+                }
+                // Write the batch
+                writer.writer.write(&partitioned_batch).await?;
+                let estimated_total_size = writer.writer.memory_size();
+                if estimated_total_size > self.disk_slice_writer_config.parquet_file_size {
+                    // Finalize the writer
+                    let writer = writers.remove(&partition_key).unwrap();
+                    let file_idx = writer.file_idx;
+                    files[file_idx] = Some(writer.finish(/*sort_key_range=*/ None).await?);
+                }
             }
-            // Write the batch
-            writer.as_mut().unwrap().write(batch).await?;
-            let estimated_total_size = {
-                let cur_writer = writer.as_ref().unwrap();
-                cur_writer.memory_size()
+        }
+        for (_, writer) in writers.into_iter() {
+            let file_idx = writer.file_idx;
+            files[file_idx] = Some(writer.finish(/*sort_key_range=*/ None).await?);
+        }
+        self.files = files.into_iter().map(|file| file.unwrap()).collect();
+        Ok(())
+    }
+
+    /// Write record batches to parquet files sorted by table sort order.
+    /// Rows are sorted within each partition, so all rows to flush for one partition are buffered in memory.
+    #[tracing::instrument(name = "write_sorted_parquet_batches", skip_all)]
+    async fn write_sorted_batch_to_parquet(
+        &mut self,
+        record_batches: &Vec<(usize, RecordBatch, Vec<usize>)>,
+        sort_key_generator: &SortKeyGenerator,
+    ) -> Result<()> {
+        let mut partition_key_generator =
+            PartitionKeyGenerator::try_new(&self.schema, &self.partition_spec)?;
+        // Rows for each partition ordered by first appearance, along with (batch id, row index) of each row.
+        let mut partition_idx_by_key: HashMap<Option<PartitionKey>, usize> = HashMap::new();
+        let mut partitions: Vec<(Option<PartitionKey>, Vec<RecordBatch>, Vec<(usize, usize)>)> =
+            Vec::new();
+        for (batch_id, batch, row_indices) in record_batches {
+            let partitioned_batches = match partition_key_generator.as_mut() {
+                Some(generator) => generator
+                    .split_by_partition(batch)?
+                    .into_iter()
+                    .map(
+                        |(partition_key, partitioned_batch, partitioned_row_indices)| {
+                            let original_row_indices = partitioned_row_indices
+                                .into_iter()
+                                .map(|idx| row_indices[idx])
+                                .collect::<Vec<_>>();
+                            (Some(partition_key), partitioned_batch, original_row_indices)
+                        },
+                    )
+                    .collect::<Vec<_>>(),
+                None => vec![(None, batch.clone(), row_indices.clone())],
             };
-            if estimated_total_size > self.disk_slice_writer_config.parquet_file_size {
-                // Finalize the writer
-                writer.as_mut().unwrap().finish().await?;
-                let file_size = writer.as_ref().unwrap().bytes_written();
-                writer = None;
-                files.push((
-                    data_file.unwrap(),
-                    DiskFileAttrs {
-                        file_size,
-                        row_num: out_row_idx,
-                    },
-                ));
-                data_file = None;
+            for (partition_key, partitioned_batch, partitioned_row_indices) in partitioned_batches {
+                let partition_idx = *partition_idx_by_key
+                    .entry(partition_key.clone())
+                    .or_insert_with(|| {
+                        partitions.push((partition_key, vec![], vec![]));
+                        partitions.len() - 1
+                    });
+                let (_, batches, row_locations) = &mut partitions[partition_idx];
+                batches.push(partitioned_batch);
+                row_locations.extend(
+                    partitioned_row_indices
+                        .into_iter()
+                        .map(|row_idx| (*batch_id, row_idx)),
+                );
             }
         }
-        if let Some(mut writer) = writer {
-            writer.finish().await?;
-            let file_size = writer.bytes_written();
-            files.push((
-                data_file.unwrap(),
-                DiskFileAttrs {
-                    file_size,
-                    row_num: out_row_idx,
-                },
-            ));
+
+        let mut files: Vec<Option<(MooncakeDataFileRef, DiskFileAttrs)>> = Vec::new();
+        for (partition_key, batches, row_locations) in partitions.into_iter() {
+            let partition_values = match partition_key_generator.as_ref() {
+                Some(generator) => Some(generator.get_partition_source_values(&batches[0])?),
+                None => None,
+            };
+            let sorted = sort_key_generator.sort(&concat_batches(&self.schema, &batches)?)?;
+            let num_rows = sorted.record_batch.num_rows();
+            let mut writer: Option<PartitionFileWriter> = None;
+            // Sorted row index for the first row in the current data file.
+            let mut file_start_row = 0;
+            for chunk_start_row in (0..num_rows).step_by(SORTED_WRITE_BATCH_SIZE) {
+                let chunk_num_rows = SORTED_WRITE_BATCH_SIZE.min(num_rows - chunk_start_row);
+                if writer.is_none() {
+                    writer = Some(
+                        self.create_partition_file_writer(
+                            files.len(),
+                            partition_values.clone(),
+                            Some(sort_key_generator.get_sorting_columns()),
+                        )
+                        .await?,
+                    );
+                    files.push(None);
+                    file_start_row = chunk_start_row;
+                }
+                let cur_writer = writer.as_mut().unwrap();
+                for sorted_row_idx in chunk_start_row..(chunk_start_row + chunk_num_rows) {
+                    let (batch_id, row_idx) =
+                        row_locations[sorted.original_row_indices[sorted_row_idx]];
+                    self.row_offset_mapping[batch_id][row_idx] =
+                        Some((cur_writer.file_idx, cur_writer.row_num));
+                    cur_writer.row_num += 1;
+                }
+                cur_writer
+                    .writer
+                    .write(&sorted.record_batch.slice(chunk_start_row, chunk_num_rows))
+                    .await?;
+                if cur_writer.writer.memory_size() > self.disk_slice_writer_config.parquet_file_size
+                {
+                    let cur_writer = writer.take().unwrap();
+                    let file_idx = cur_writer.file_idx;
+                    let sort_key_range = sorted.get_key_range(
+                        &partition_key,
+                        file_start_row,
+                        chunk_start_row + chunk_num_rows,
+                    );
+                    files[file_idx] = Some(cur_writer.finish(Some(sort_key_range)).await?);
+                }
+            }
+            if let Some(cur_writer) = writer.take() {
+                let file_idx = cur_writer.file_idx;
+                let sort_key_range = sorted.get_key_range(&partition_key, file_start_row, num_rows);
+                files[file_idx] = Some(cur_writer.finish(Some(sort_key_range)).await?);
+            }
         }
-        self.files = files;
+        self.files = files.into_iter().map(|file| file.unwrap()).collect();
         Ok(())
     }
 
@@ -272,11 +489,11 @@ impl DiskSliceWriter {
                 .map(|(_, attrs)| attrs.row_num)
                 .sum::<usize>()
         );
-        let file_id =
-            get_unique_file_id_for_flush(self.table_auto_incr_id as u64, self.files.len() as u64);
+        let file_id = self.get_unique_file_id(self.files.len());
         let mut index_builder = GlobalIndexBuilder::new();
         index_builder.set_files(self.files.iter().map(|(file, _)| file.clone()).collect());
         index_builder.set_directory(self.dir_path.clone());
+        index_builder.set_encryption_key(self.encryption_key.clone());
         self.new_index = Some(index_builder.build_from_flush(list, file_id).await?);
         Ok(())
     }
@@ -320,6 +537,7 @@ mod tests {
     use arrow_array::{Int32Array, StringArray};
     use arrow_schema::Schema;
     use parquet::arrow::async_reader::ParquetRecordBatchStreamBuilder;
+    use std::collections::HashSet;
     use tempfile::tempdir;
 
     /// Util function to create test schema.
@@ -371,9 +589,13 @@ mod tests {
             temp_dir.path().to_path_buf(),
             entries,
             Some(1),
-            /*table_auto_incr_id=*/ 0,
+            Arc::new(TableAutoIncrIdAllocator::new(/*next_id=*/ 0)),
             Arc::new(old_index),
             DiskSliceWriterConfig::default(),
+            /*partition_spec=*/ vec![],
+            ParquetWriterConfig::default(),
+            /*encryption_key=*/ None,
+            /*sort_order=*/ vec![],
         );
         disk_slice.write().await?;
 
@@ -491,9 +713,13 @@ mod tests {
             temp_dir.path().to_path_buf(),
             entries,
             Some(1),
-            0,
+            Arc::new(TableAutoIncrIdAllocator::new(/*next_id=*/ 0)),
             Arc::new(index),
             DiskSliceWriterConfig::default(),
+            /*partition_spec=*/ vec![],
+            ParquetWriterConfig::default(),
+            /*encryption_key=*/ None,
+            /*sort_order=*/ vec![],
         );
 
         // Write the disk slice
@@ -539,4 +765,264 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_partitioned_disk_slice_write() -> Result<()> {
+        let temp_dir = tempdir().map_err(Into::<Error>::into)?;
+        let schema = get_test_schema();
+        let identity = IdentityProp::SinglePrimitiveKey(0);
+        let mut mem_slice = MemSlice::new(
+            schema.clone(),
+            100,
+            identity,
+            Arc::new(BatchIdCounter::new(false)),
+        );
+        for (id, name) in [
+            (1, "Alice"),
+            (2, "Bob"),
+            (3, "Alice"),
+            (4, "Bob"),
+            (5, "Eve"),
+        ] {
+            let row = MoonlinkRow::new(vec![
+                RowValue::Int32(id),
+                RowValue::ByteArray(name.as_bytes().to_vec()),
+            ]);
+            mem_slice.append(id as u64, row, None)?;
+        }
+        let (_new_batch, entries, index) = mem_slice.drain().unwrap();
+
+        let mut disk_slice = DiskSliceWriter::new(
+            schema.clone(),
+            temp_dir.path().to_path_buf(),
+            entries,
+            Some(1),
+            Arc::new(TableAutoIncrIdAllocator::new(/*next_id=*/ 0)),
+            Arc::new(index),
+            DiskSliceWriterConfig::default(),
+            vec![PartitionFieldConfig::new(
+                "name",
+                iceberg::spec::Transform::Identity,
+            )],
+            ParquetWriterConfig::default(),
+            /*encryption_key=*/ None,
+            /*sort_order=*/ vec![],
+        );
+        disk_slice.write().await?;
+
+        // One data file is created for each partition, in the order of first appearance.
+        let mut actual_names = vec![];
+        for (file, attrs) in disk_slice.output_files() {
+            let partition_values = file.partition_values().cloned();
+            let file = tokio::fs::File::open(file.file_path()).await?;
+            let mut reader = ParquetRecordBatchStreamBuilder::new(file)
+                .await?
+                .build()
+                .unwrap();
+            let mut ids = vec![];
+            let mut names = HashSet::new();
+            while let Some(row_group_reader) = reader.next_row_group().await? {
+                for record_batch in row_group_reader {
+                    let record_batch = record_batch.unwrap();
+                    let id_column = record_batch
+                        .column(0)
+                        .as_any()
+                        .downcast_ref::<Int32Array>()
+                        .unwrap();
+                    let name_column = record_batch
+                        .column(1)
+                        .as_any()
+                        .downcast_ref::<StringArray>()
+                        .unwrap();
+                    ids.extend(id_column.values().iter().copied());
+                    names.extend(name_column.iter().map(|name| name.unwrap().to_string()));
+                }
+            }
+            assert_eq!(ids.len(), attrs.row_num);
+            assert_eq!(names.len(), 1);
+            // Partition source values are recorded for the data file.
+            let name = names.into_iter().next().unwrap();
+            assert_eq!(
+                partition_values,
+                Some(vec![RowValue::ByteArray(name.as_bytes().to_vec())])
+            );
+            actual_names.push((name, ids));
+        }
+        assert_eq!(
+            actual_names,
+            vec![
+                ("Alice".to_string(), vec![1, 3]),
+                ("Bob".to_string(), vec![2, 4]),
+                ("Eve".to_string(), vec![5]),
+            ]
+        );
+
+        // Remapped index points to the data file of the corresponding partition.
+        let new_index = disk_slice.take_index().unwrap();
+        let results = new_index
+            .search_values(&test_get_hashes_for_index(&[4]))
+            .await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].1,
+            RecordLocation::DiskFile(disk_slice.output_files()[1].0.file_id(), 1)
+        );
+
+        temp_dir.close().map_err(Into::<Error>::into)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_partitioned_disk_slice_write_exceeding_files_per_flush() -> Result<()> {
+        let temp_dir = tempdir().map_err(Into::<Error>::into)?;
+        let schema = get_test_schema();
+        let identity = IdentityProp::SinglePrimitiveKey(0);
+        let mut mem_slice = MemSlice::new(
+            schema.clone(),
+            1000,
+            identity,
+            Arc::new(BatchIdCounter::new(false)),
+        );
+        // Each row lives in its own partition, so data files and the file index exceed file ids allocated for one flush.
+        let num_partitions = NUM_FILES_PER_FLUSH as i32 + 50;
+        for id in 0..num_partitions {
+            let row = MoonlinkRow::new(vec![
+                RowValue::Int32(id),
+                RowValue::ByteArray(format!("name-{id}").into_bytes()),
+            ]);
+            mem_slice.append(id as u64, row, None)?;
+        }
+        let (_new_batch, entries, index) = mem_slice.drain().unwrap();
+
+        let table_auto_incr_id_allocator =
+            Arc::new(TableAutoIncrIdAllocator::new(/*next_id=*/ 0));
+        let mut disk_slice = DiskSliceWriter::new(
+            schema.clone(),
+            temp_dir.path().to_path_buf(),
+            entries,
+            Some(1),
+            table_auto_incr_id_allocator.clone(),
+            Arc::new(index),
+            DiskSliceWriterConfig::default(),
+            vec![PartitionFieldConfig::new(
+                "name",
+                iceberg::spec::Transform::Identity,
+            )],
+            ParquetWriterConfig::default(),
+            /*encryption_key=*/ None,
+            /*sort_order=*/ vec![],
+        );
+        disk_slice.write().await?;
+
+        // All data files and the file index get unique file ids.
+        assert_eq!(disk_slice.output_files().len(), num_partitions as usize);
+        let mut file_ids = disk_slice
+            .output_files()
+            .iter()
+            .map(|(file, _)| file.file_id().0)
+            .collect::<HashSet<_>>();
+        let new_index = disk_slice.take_index().unwrap();
+        for index_block in new_index.index_blocks.iter() {
+            assert!(file_ids.insert(index_block.index_file.file_id().0));
+        }
+        // Two table auto increment ids have been allocated for the flush.
+        assert_eq!(table_auto_incr_id_allocator.allocate(1), 2);
+
+        // Remapped index points to the data file of the corresponding partition.
+        let results = new_index
+            .search_values(&test_get_hashes_for_index(&[120]))
+            .await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].1,
+            RecordLocation::DiskFile(disk_slice.output_files()[120].0.file_id(), 0)
+        );
+
+        temp_dir.close().map_err(Into::<Error>::into)?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_sorted_disk_slice_write() -> Result<()> {
+        let temp_dir = tempdir().map_err(Into::<Error>::into)?;
+        let schema = get_test_schema();
+        let identity = IdentityProp::SinglePrimitiveKey(0);
+        let mut mem_slice = MemSlice::new(
+            schema.clone(),
+            100,
+            identity,
+            Arc::new(BatchIdCounter::new(false)),
+        );
+        for (id, name) in [(1, "Eve"), (2, "Alice"), (3, "Bob"), (4, "Alice")] {
+            let row = MoonlinkRow::new(vec![
+                RowValue::Int32(id),
+                RowValue::ByteArray(name.as_bytes().to_vec()),
+            ]);
+            mem_slice.append(id as u64, row, None)?;
+        }
+        let (_new_batch, entries, index) = mem_slice.drain().unwrap();
+
+        let mut disk_slice = DiskSliceWriter::new(
+            schema.clone(),
+            temp_dir.path().to_path_buf(),
+            entries,
+            Some(1),
+            Arc::new(TableAutoIncrIdAllocator::new(/*next_id=*/ 0)),
+            Arc::new(index),
+            DiskSliceWriterConfig::default(),
+            /*partition_spec=*/ vec![],
+            ParquetWriterConfig::default(),
+            /*encryption_key=*/ None,
+            vec![
+                SortFieldConfig::new(
+                    "name",
+                    iceberg::spec::SortDirection::Ascending,
+                    iceberg::spec::NullOrder::First,
+                ),
+                SortFieldConfig::new(
+                    "id",
+                    iceberg::spec::SortDirection::Descending,
+                    iceberg::spec::NullOrder::First,
+                ),
+            ],
+        );
+        disk_slice.write().await?;
+
+        // Rows are sorted within the data file, and sorting columns are recorded in parquet metadata.
+        assert_eq!(disk_slice.output_files().len(), 1);
+        let (data_file, attrs) = disk_slice.output_files()[0].clone();
+        assert!(attrs.sort_key_range.is_some());
+        let file = tokio::fs::File::open(data_file.file_path()).await?;
+        let builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+        let sorting_columns = builder.metadata().row_group(0).sorting_columns().unwrap();
+        assert_eq!(sorting_columns.len(), 2);
+        assert_eq!(sorting_columns[0].column_idx, 1);
+        assert!(sorting_columns[1].descending);
+        let mut reader = builder.build().unwrap();
+        let mut record_batch_reader = reader.next_row_group().await.unwrap().unwrap();
+        let record_batch = record_batch_reader.next().unwrap().unwrap();
+        let expected_record_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![4, 2, 3, 1])),
+                Arc::new(StringArray::from(vec!["Alice", "Alice", "Bob", "Eve"])),
+            ],
+        )
+        .unwrap();
+        assert_eq!(record_batch, expected_record_batch);
+
+        // Remapped index points to the sorted row offset.
+        let new_index = disk_slice.take_index().unwrap();
+        let results = new_index
+            .search_values(&test_get_hashes_for_index(&[3]))
+            .await;
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].1,
+            RecordLocation::DiskFile(data_file.file_id(), 2)
+        );
+
+        temp_dir.close().map_err(Into::<Error>::into)?;
+        Ok(())
+    }
 }
@@ -92,11 +92,28 @@ impl MemSlice {
         None
     }
 
+    /// Find the first non-deleted row for a given lookup key
+    pub(super) async fn find_non_deleted_row(
+        &self,
+        record: &RawDeletionRecord,
+        identity: &IdentityProp,
+    ) -> Option<MoonlinkRow> {
+        let (batch_id, row_offset) = self.find_non_deleted_position(record, identity).await?;
+        Some(self.column_store.get_row(batch_id, row_offset))
+    }
+
     #[must_use]
     pub fn try_delete_at_pos(&mut self, pos: (u64, usize)) -> bool {
         self.column_store.try_delete_at_pos(pos)
     }
 
+    /// Delete all rows in the mem slice.
+    /// Deleted rows are never looked up again, so mem index is reset as well.
+    pub(super) fn delete_all_rows(&mut self) {
+        self.column_store.delete_all_rows();
+        self.mem_index = MemIndex::new_like(&self.mem_index);
+    }
+
     /// Append the given row into column store buffer and mem index.
     /// Return the finalized record batch if the current one's full.
     pub(super) fn append(
@@ -120,6 +137,11 @@ impl MemSlice {
         self.column_store.get_num_rows()
     }
 
+    /// Get estimated memory size in bytes for buffered rows and mem index.
+    pub(super) fn get_estimated_memory_size(&self) -> usize {
+        self.column_store.get_estimated_memory_size() + self.mem_index.get_estimated_memory_size()
+    }
+
     #[allow(clippy::type_complexity)]
     pub(super) fn drain(
         &mut self,
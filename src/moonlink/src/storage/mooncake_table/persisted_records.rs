@@ -2,6 +2,7 @@ use crate::storage::index::FileIndex;
 use crate::storage::mooncake_table::table_snapshot::PersistenceSnapshotDataCompactionResult;
 use crate::storage::mooncake_table::table_snapshot::{
     PersistenceSnapshotImportResult, PersistenceSnapshotIndexMergeResult,
+    PersistenceSnapshotTruncationResult,
 };
 use crate::storage::storage_utils::FileId;
 use crate::storage::storage_utils::MooncakeDataFileRef;
@@ -19,6 +20,8 @@ pub(crate) struct PersistedRecords {
     pub(crate) index_merge_result: PersistenceSnapshotIndexMergeResult,
     /// Data compaction persistence result.
     pub(crate) data_compaction_result: PersistenceSnapshotDataCompactionResult,
+    /// Table truncation persistence result.
+    pub(crate) truncation_result: PersistenceSnapshotTruncationResult,
 }
 
 impl PersistedRecords {
@@ -29,7 +32,7 @@ impl PersistedRecords {
             assert!(self.import_result.is_empty());
             assert!(self.index_merge_result.is_empty());
             assert!(self.data_compaction_result.is_empty());
-            assert!(self.data_compaction_result.is_empty());
+            assert!(self.truncation_result.is_empty());
             return true;
         }
 
@@ -4,9 +4,7 @@ use crate::storage::compaction::table_compaction::RemappedRecordLocation;
 use crate::storage::index::persisted_bucket_hash_map::GlobalIndex;
 use crate::storage::mooncake_table::SnapshotTask;
 use crate::storage::mooncake_table_config::MooncakeTableConfig;
-use crate::storage::storage_utils::{MooncakeDataFileRef, RecordLocation};
-
-use more_asserts as ma;
+use crate::storage::storage_utils::{FileId, MooncakeDataFileRef, RecordLocation};
 
 /// Struct which stores unpersisted content for mooncake table.
 #[derive(Clone, Debug, Default)]
@@ -51,6 +49,17 @@ pub(crate) struct UnpersistedRecords {
     /// For a committed deletion record, it could appear in two places: committed deletion log, or iceberg deletion vector puffin blob.
     /// For later, iceberg table manager should handle it, with the knowledge of remapping information.
     compacted_data_file_remap: HashMap<RecordLocation, RemappedRecordLocation>,
+
+    /// ===========================================
+    /// Records generated by table truncation
+    /// ===========================================
+    ///
+    /// LSN of the latest table truncation, truncated files are only removed by persistence with a larger flush LSN.
+    truncate_lsn: Option<u64>,
+    /// Unpersisted truncated data files, which should not appear in the later iceberg snapshots.
+    truncated_data_files_to_remove: Vec<MooncakeDataFileRef>,
+    /// Unpersisted truncated file indices, which should not appear in the later iceberg snapshots.
+    truncated_file_indices_to_remove: Vec<GlobalIndex>,
 }
 
 impl UnpersistedRecords {
@@ -97,6 +106,23 @@ impl UnpersistedRecords {
         self.compacted_data_file_remap.clone()
     }
 
+    /// Table truncation, only returned when persistence flush LSN covers the truncation.
+    pub(crate) fn get_truncated_data_files_to_remove(
+        &self,
+        flush_lsn: u64,
+    ) -> Vec<MooncakeDataFileRef> {
+        if !self.is_truncation_persistable(flush_lsn) {
+            return vec![];
+        }
+        self.truncated_data_files_to_remove.clone()
+    }
+    pub(crate) fn get_truncated_file_indices_to_remove(&self, flush_lsn: u64) -> Vec<GlobalIndex> {
+        if !self.is_truncation_persistable(flush_lsn) {
+            return vec![];
+        }
+        self.truncated_file_indices_to_remove.clone()
+    }
+
     /// Get unpersisted data files as hash set for lookup.
     pub(crate) fn get_unpersisted_data_files_set(&self) -> HashSet<MooncakeDataFileRef> {
         let expected_len = self.new_data_files.len() + self.compacted_data_files_to_add.len();
@@ -111,7 +137,7 @@ impl UnpersistedRecords {
     pub(crate) fn get_unpersisted_file_indices_set(&self) -> HashSet<GlobalIndex> {
         let expected_len = self.new_file_indices.len()
             + self.merged_file_indices_to_add.len()
-            + self.compacted_data_files_to_add.len();
+            + self.compacted_file_indices_to_add.len();
         let mut unpersisted_file_indices = HashSet::new();
         unpersisted_file_indices.extend(self.new_file_indices.iter().cloned());
         unpersisted_file_indices.extend(self.merged_file_indices_to_add.iter().cloned());
@@ -180,85 +206,98 @@ impl UnpersistedRecords {
     /// ==================================
     ///
     /// Update unpersisted data files from successful iceberg snapshot operation.
+    ///
+    /// Buffered records are pruned by identity instead of by count, since table truncation could discard records in the middle.
     fn prune_persisted_data_files(&mut self, task: &SnapshotTask) {
-        let persisted_new_data_files = &task.persisted_records.import_result.new_data_files;
-        ma::assert_ge!(self.new_data_files.len(), persisted_new_data_files.len());
-        self.new_data_files.drain(0..persisted_new_data_files.len());
+        let persisted_new_data_files =
+            get_file_ids(&task.persisted_records.import_result.new_data_files);
+        self.new_data_files
+            .retain(|cur_file| !persisted_new_data_files.contains(&cur_file.file_id()));
     }
 
     /// Update unpersisted file indices from successful iceberg snapshot operation.
+    #[allow(clippy::mutable_key_type)]
     fn prune_persisted_file_indices(&mut self, task: &SnapshotTask) {
-        let persisted_new_file_indices = &task.persisted_records.import_result.new_file_indices;
-        ma::assert_ge!(
-            self.new_file_indices.len(),
-            persisted_new_file_indices.len()
-        );
+        let persisted_new_file_indices =
+            get_file_indices_set(&task.persisted_records.import_result.new_file_indices);
         self.new_file_indices
-            .drain(0..persisted_new_file_indices.len());
+            .retain(|cur_index| !persisted_new_file_indices.contains(cur_index));
     }
 
+    #[allow(clippy::mutable_key_type)]
     fn prune_persisted_merged_indices(&mut self, task: &SnapshotTask) {
-        let old_merged_file_indices = &task
-            .persisted_records
-            .index_merge_result
-            .old_file_indices_removed;
-        ma::assert_ge!(
-            self.merged_file_indices_to_remove.len(),
-            old_merged_file_indices.len()
-        );
+        let index_merge_result = &task.persisted_records.index_merge_result;
+        let old_merged_file_indices =
+            get_file_indices_set(&index_merge_result.old_file_indices_removed);
         self.merged_file_indices_to_remove
-            .drain(0..old_merged_file_indices.len());
-
-        let new_merged_file_indices = &task
-            .persisted_records
-            .index_merge_result
-            .new_file_indices_imported;
-        ma::assert_ge!(
-            self.merged_file_indices_to_add.len(),
-            new_merged_file_indices.len()
-        );
+            .retain(|cur_index| !old_merged_file_indices.contains(cur_index));
+
+        let new_merged_file_indices =
+            get_file_indices_set(&index_merge_result.new_file_indices_imported);
         self.merged_file_indices_to_add
-            .drain(0..new_merged_file_indices.len());
+            .retain(|cur_index| !new_merged_file_indices.contains(cur_index));
     }
 
+    #[allow(clippy::mutable_key_type)]
     fn prune_persisted_compacted_data(&mut self, task: &SnapshotTask) {
         let persisted_compaction_res = &task.persisted_records.data_compaction_result;
 
-        ma::assert_ge!(
-            self.compacted_data_files_to_add.len(),
-            persisted_compaction_res.new_data_files_imported.len()
-        );
+        let new_compacted_data_files =
+            get_file_ids(&persisted_compaction_res.new_data_files_imported);
         self.compacted_data_files_to_add
-            .drain(0..persisted_compaction_res.new_data_files_imported.len());
+            .retain(|cur_file| !new_compacted_data_files.contains(&cur_file.file_id()));
 
-        ma::assert_ge!(
-            self.compacted_data_files_to_remove.len(),
-            persisted_compaction_res.old_data_files_removed.len()
-        );
+        let old_compacted_data_files =
+            get_file_ids(&persisted_compaction_res.old_data_files_removed);
         self.compacted_data_files_to_remove
-            .drain(0..persisted_compaction_res.old_data_files_removed.len());
+            .retain(|cur_file| !old_compacted_data_files.contains(&cur_file.file_id()));
 
-        ma::assert_ge!(
-            self.compacted_file_indices_to_add.len(),
-            persisted_compaction_res.new_file_indices_imported.len()
-        );
+        let new_compacted_file_indices =
+            get_file_indices_set(&persisted_compaction_res.new_file_indices_imported);
         self.compacted_file_indices_to_add
-            .drain(0..persisted_compaction_res.new_file_indices_imported.len());
+            .retain(|cur_index| !new_compacted_file_indices.contains(cur_index));
 
-        ma::assert_ge!(
-            self.compacted_file_indices_to_remove.len(),
-            persisted_compaction_res.old_file_indices_removed.len()
-        );
+        let old_compacted_file_indices =
+            get_file_indices_set(&persisted_compaction_res.old_file_indices_removed);
         self.compacted_file_indices_to_remove
-            .drain(0..persisted_compaction_res.old_file_indices_removed.len());
+            .retain(|cur_index| !old_compacted_file_indices.contains(cur_index));
 
-        if !persisted_compaction_res.data_file_records_remap.is_empty() {
-            assert_eq!(
-                persisted_compaction_res.data_file_records_remap.len(),
-                self.compacted_data_file_remap.len()
-            );
-            self.compacted_data_file_remap.clear();
-        }
+        self.compacted_data_file_remap.retain(|old_location, _| {
+            !persisted_compaction_res
+                .data_file_records_remap
+                .contains_key(old_location)
+        });
+    }
+
+    /// Truncated records could be removed by any persisted removal, for example, maintenance results converted by truncation while they're being persisted.
+    #[allow(clippy::mutable_key_type)]
+    fn prune_persisted_truncation(&mut self, task: &SnapshotTask) {
+        let persisted_records = &task.persisted_records;
+
+        let mut removed_data_files =
+            get_file_ids(&persisted_records.truncation_result.old_data_files_removed);
+        removed_data_files.extend(get_file_ids(
+            &persisted_records
+                .data_compaction_result
+                .old_data_files_removed,
+        ));
+        self.truncated_data_files_to_remove
+            .retain(|cur_file| !removed_data_files.contains(&cur_file.file_id()));
+
+        let mut removed_file_indices =
+            get_file_indices_set(&persisted_records.truncation_result.old_file_indices_removed);
+        removed_file_indices.extend(get_file_indices_set(
+            &persisted_records
+                .index_merge_result
+                .old_file_indices_removed,
+        ));
+        removed_file_indices.extend(get_file_indices_set(
+            &persisted_records
+                .data_compaction_result
+                .old_file_indices_removed,
+        ));
+        self.truncated_file_indices_to_remove
+            .retain(|cur_index| !removed_file_indices.contains(cur_index));
     }
 
     /// Prune persisted records.
@@ -267,6 +306,103 @@ impl UnpersistedRecords {
         self.prune_persisted_file_indices(task);
         self.prune_persisted_merged_indices(task);
         self.prune_persisted_compacted_data(task);
+        self.prune_persisted_truncation(task);
+    }
+
+    /// ==================================
+    /// Truncation utils
+    /// ==================================
+    ///
+    /// Discard unpersisted records for truncated data files and file indices.
+    /// Persisted ones are recorded to remove in later iceberg snapshots; pending maintenance results are turned into removals as well, since their new files are truncated.
+    ///
+    /// # Arguments
+    ///
+    /// * truncated_data_files: all data files dropped by the truncation, including unpersisted ones.
+    /// * persisted_data_files / persisted_file_indices: dropped data files and file indices which have been persisted.
+    pub(crate) fn truncate(
+        &mut self,
+        truncate_lsn: u64,
+        truncated_data_files: &HashSet<FileId>,
+        persisted_data_files: Vec<MooncakeDataFileRef>,
+        persisted_file_indices: Vec<GlobalIndex>,
+    ) {
+        if self.truncate_lsn.is_none() || self.truncate_lsn.unwrap() < truncate_lsn {
+            self.truncate_lsn = Some(truncate_lsn);
+        }
+
+        let is_truncated_index = |index: &GlobalIndex| {
+            index
+                .files
+                .iter()
+                .any(|cur_file| truncated_data_files.contains(&cur_file.file_id()))
+        };
+
+        // New writes.
+        self.new_data_files
+            .retain(|cur_file| !truncated_data_files.contains(&cur_file.file_id()));
+        self.new_file_indices
+            .retain(|cur_index| !is_truncated_index(cur_index));
+
+        // Index merge, merged file indices always reference persisted data files, which are truncated together.
+        if self
+            .merged_file_indices_to_add
+            .iter()
+            .any(is_truncated_index)
+        {
+            assert!(self
+                .merged_file_indices_to_add
+                .iter()
+                .all(is_truncated_index));
+            self.merged_file_indices_to_add.clear();
+            self.truncated_file_indices_to_remove
+                .append(&mut self.merged_file_indices_to_remove);
+        }
+
+        // Data compaction, compacted data files are never newer than the truncation, so they're truncated together.
+        if self
+            .compacted_data_files_to_add
+            .iter()
+            .any(|cur_file| truncated_data_files.contains(&cur_file.file_id()))
+        {
+            assert!(self
+                .compacted_data_files_to_add
+                .iter()
+                .all(|cur_file| truncated_data_files.contains(&cur_file.file_id())));
+            self.compacted_data_files_to_add.clear();
+            self.compacted_file_indices_to_add.clear();
+            self.compacted_data_file_remap.clear();
+            self.truncated_data_files_to_remove
+                .append(&mut self.compacted_data_files_to_remove);
+            self.truncated_file_indices_to_remove
+                .append(&mut self.compacted_file_indices_to_remove);
+        }
+
+        // Persisted data files and file indices.
+        self.truncated_data_files_to_remove
+            .extend(persisted_data_files);
+        self.truncated_file_indices_to_remove
+            .extend(persisted_file_indices);
+    }
+
+    /// Record truncated data files and file indices which get persisted after truncation, for example, by an ongoing persistence operation.
+    pub(crate) fn add_truncated_persisted_records(
+        &mut self,
+        persisted_data_files: Vec<MooncakeDataFileRef>,
+        persisted_file_indices: Vec<GlobalIndex>,
+    ) {
+        self.truncated_data_files_to_remove
+            .extend(persisted_data_files);
+        self.truncated_file_indices_to_remove
+            .extend(persisted_file_indices);
+    }
+
+    /// Whether truncated records could be included in a persistence with the given flush LSN.
+    fn is_truncation_persistable(&self, flush_lsn: u64) -> bool {
+        match self.truncate_lsn {
+            Some(truncate_lsn) => truncate_lsn < flush_lsn,
+            None => false,
+        }
     }
 
     /// ==================================
@@ -314,6 +450,13 @@ impl UnpersistedRecords {
         self.new_data_files.len() >= data_file_snapshot_threshold
     }
 
+    /// Util function to decide whether to create iceberg snapshot by table truncation, which is always persisted as soon as possible.
+    pub(crate) fn if_persist_by_truncation(&self, flush_lsn: u64) -> bool {
+        self.is_truncation_persistable(flush_lsn)
+            && (!self.truncated_data_files_to_remove.is_empty()
+                || !self.truncated_file_indices_to_remove.is_empty())
+    }
+
     /// Util function to decide whether to flush by new data files or maintenance task.
     pub(crate) fn if_persist_by_new_files_or_maintenance(&self, force_create: bool) -> bool {
         if self.if_persist_by_data_files(force_create) {
@@ -350,6 +493,13 @@ impl UnpersistedRecords {
         let old_merged_file_indices_empty = self.merged_file_indices_to_remove.is_empty();
         assert_eq!(new_merged_file_indices_empty, old_merged_file_indices_empty);
 
+        // Validate truncation buffer.
+        if !self.truncated_data_files_to_remove.is_empty()
+            || !self.truncated_file_indices_to_remove.is_empty()
+        {
+            assert!(self.truncate_lsn.is_some());
+        }
+
         // Validate data compaction buffer.
         let old_compacted_data_files_empty = self.compacted_data_files_to_remove.is_empty();
         let new_compacted_data_files_empty = self.compacted_data_files_to_add.is_empty();
@@ -364,3 +514,17 @@ impl UnpersistedRecords {
         }
     }
 }
+
+/// Get file ids for the given data files.
+fn get_file_ids(data_files: &[MooncakeDataFileRef]) -> HashSet<FileId> {
+    data_files
+        .iter()
+        .map(|cur_file| cur_file.file_id())
+        .collect()
+}
+
+/// Get the given file indices as hash set for lookup.
+#[allow(clippy::mutable_key_type)]
+fn get_file_indices_set(file_indices: &[GlobalIndex]) -> HashSet<GlobalIndex> {
+    file_indices.iter().cloned().collect()
+}
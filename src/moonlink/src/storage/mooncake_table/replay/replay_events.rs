@@ -51,6 +51,14 @@ pub struct AbortEvent {
     pub xact_id: u32,
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TruncateEvent {
+    /// Truncation LSN, only assigned on non-streaming ones.
+    pub lsn: Option<u64>,
+    /// Transaction id, only assigned on streaming ones.
+    pub xact_id: Option<u32>,
+}
+
 /// =====================
 /// Flush operation
 /// =====================
@@ -233,6 +241,8 @@ pub enum MooncakeTableEvent {
     Commit(CommitEvent),
     /// Abort operation.
     Abort(AbortEvent),
+    /// Truncate operation.
+    Truncate(TruncateEvent),
     /// =====================
     /// Background operations
     /// =====================
@@ -274,6 +284,10 @@ pub fn create_commit_event(lsn: u64, xact_id: Option<u32>) -> CommitEvent {
 pub fn create_abort_event(xact_id: u32) -> AbortEvent {
     AbortEvent { xact_id }
 }
+/// Create truncate event.
+pub fn create_truncate_event(lsn: Option<u64>, xact_id: Option<u32>) -> TruncateEvent {
+    TruncateEvent { lsn, xact_id }
+}
 /// Create flush events.
 pub fn create_flush_event_initiation(
     uuid: uuid::Uuid,
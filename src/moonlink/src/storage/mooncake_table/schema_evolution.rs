@@ -0,0 +1,219 @@
+//! Schema evolution utils for mooncake table.
+//!
+//! Field ids are kept in arrow field metadata, and mapped to iceberg field ids on persistence.
+//! Renamed and promoted columns keep their field ids, new columns are assigned with ids after the highest one.
+
+use arrow_schema::{DataType, Field, Fields, Schema};
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Arrow field metadata key for field id.
+const PARQUET_FIELD_ID_KEY: &str = "PARQUET:field_id";
+
+/// Return whether values of type `from` could be read as type `to` without loss, which is allowed by iceberg schema evolution.
+/// Reference: https://iceberg.apache.org/spec/#schema-evolution
+pub fn is_safe_type_promotion(from: &DataType, to: &DataType) -> bool {
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (DataType::Int16, DataType::Int32 | DataType::Int64) => true,
+        (DataType::Int32, DataType::Int64) => true,
+        (DataType::Float32, DataType::Float64) => true,
+        (
+            DataType::Decimal128(from_precision, from_scale),
+            DataType::Decimal128(to_precision, to_scale),
+        ) => from_scale == to_scale && from_precision <= to_precision,
+        _ => false,
+    }
+}
+
+/// Get field id from arrow field metadata.
+pub(crate) fn get_field_id(field: &Field) -> Option<i32> {
+    field
+        .metadata()
+        .get(PARQUET_FIELD_ID_KEY)
+        .and_then(|id| id.parse::<i32>().ok())
+}
+
+/// Get the highest field id for the given field, including nested ones.
+fn get_highest_field_id_for_field(field: &Field) -> i32 {
+    let mut highest_field_id = get_field_id(field).unwrap_or(-1);
+    match field.data_type() {
+        DataType::List(item) | DataType::LargeList(item) | DataType::FixedSizeList(item, _) => {
+            highest_field_id = highest_field_id.max(get_highest_field_id_for_field(item));
+        }
+        DataType::Struct(fields) => {
+            for cur_field in fields.iter() {
+                highest_field_id = highest_field_id.max(get_highest_field_id_for_field(cur_field));
+            }
+        }
+        DataType::Map(entries, _) => {
+            highest_field_id = highest_field_id.max(get_highest_field_id_for_field(entries));
+        }
+        _ => {}
+    }
+    highest_field_id
+}
+
+/// Get the highest field id for the given schema, return -1 if there's no field id assigned.
+pub(crate) fn get_highest_field_id(schema: &Schema) -> i32 {
+    schema
+        .fields()
+        .iter()
+        .map(|field| get_highest_field_id_for_field(field))
+        .max()
+        .unwrap_or(-1)
+}
+
+/// Assign field ids to the given field and all nested fields, starting from `next_field_id`.
+/// Nested fields are assigned before their parent, which follows the same order as table creation.
+pub(crate) fn assign_field_ids(field: &Field, next_field_id: &mut i32) -> Field {
+    let data_type = match field.data_type() {
+        DataType::List(item) => DataType::List(Arc::new(assign_field_ids(item, next_field_id))),
+        DataType::LargeList(item) => {
+            DataType::LargeList(Arc::new(assign_field_ids(item, next_field_id)))
+        }
+        DataType::FixedSizeList(item, size) => {
+            DataType::FixedSizeList(Arc::new(assign_field_ids(item, next_field_id)), *size)
+        }
+        DataType::Struct(fields) => DataType::Struct(Fields::from(
+            fields
+                .iter()
+                .map(|cur_field| assign_field_ids(cur_field, next_field_id))
+                .collect::<Vec<_>>(),
+        )),
+        // Map entries struct doesn't carry a field id, only its key and value fields do.
+        DataType::Map(entries, sorted) => {
+            let DataType::Struct(entry_fields) = entries.data_type() else {
+                unreachable!(
+                    "map entries should be struct, but get {:?}",
+                    entries.data_type()
+                );
+            };
+            let entry_fields = entry_fields
+                .iter()
+                .map(|cur_field| assign_field_ids(cur_field, next_field_id))
+                .collect::<Vec<_>>();
+            DataType::Map(
+                Arc::new(
+                    entries
+                        .as_ref()
+                        .clone()
+                        .with_data_type(DataType::Struct(Fields::from(entry_fields))),
+                ),
+                *sorted,
+            )
+        }
+        other => other.clone(),
+    };
+    let mut metadata: HashMap<String, String> = field.metadata().clone();
+    metadata.insert(PARQUET_FIELD_ID_KEY.to_string(), next_field_id.to_string());
+    *next_field_id += 1;
+    field
+        .clone()
+        .with_data_type(data_type)
+        .with_metadata(metadata)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field_with_id(name: &str, data_type: DataType, field_id: i32) -> Field {
+        Field::new(name, data_type, /*nullable=*/ true).with_metadata(HashMap::from([(
+            PARQUET_FIELD_ID_KEY.to_string(),
+            field_id.to_string(),
+        )]))
+    }
+
+    #[test]
+    fn test_type_promotion() {
+        assert!(is_safe_type_promotion(&DataType::Int16, &DataType::Int32));
+        assert!(is_safe_type_promotion(&DataType::Int16, &DataType::Int64));
+        assert!(is_safe_type_promotion(&DataType::Int32, &DataType::Int64));
+        assert!(is_safe_type_promotion(
+            &DataType::Float32,
+            &DataType::Float64
+        ));
+        assert!(is_safe_type_promotion(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal128(12, 2)
+        ));
+        assert!(is_safe_type_promotion(&DataType::Utf8, &DataType::Utf8));
+
+        assert!(!is_safe_type_promotion(&DataType::Int64, &DataType::Int32));
+        assert!(!is_safe_type_promotion(
+            &DataType::Int32,
+            &DataType::Float64
+        ));
+        assert!(!is_safe_type_promotion(&DataType::Int32, &DataType::Utf8));
+        assert!(!is_safe_type_promotion(
+            &DataType::Decimal128(10, 2),
+            &DataType::Decimal128(12, 3)
+        ));
+        assert!(!is_safe_type_promotion(
+            &DataType::Decimal128(12, 2),
+            &DataType::Decimal128(10, 2)
+        ));
+    }
+
+    #[test]
+    fn test_assign_field_ids() {
+        let schema = Schema::new(vec![
+            field_with_id("id", DataType::Int32, 0),
+            field_with_id(
+                "tags",
+                DataType::List(Arc::new(field_with_id("item", DataType::Utf8, 1))),
+                2,
+            ),
+        ]);
+        let mut next_field_id = get_highest_field_id(&schema) + 1;
+        assert_eq!(next_field_id, 3);
+
+        let new_field = Field::new_struct(
+            "address",
+            vec![
+                Field::new("city", DataType::Utf8, true),
+                Field::new("zip", DataType::Int32, true),
+            ],
+            /*nullable=*/ true,
+        );
+        let new_field = assign_field_ids(&new_field, &mut next_field_id);
+        assert_eq!(next_field_id, 6);
+        assert_eq!(get_field_id(&new_field), Some(5));
+        let DataType::Struct(children) = new_field.data_type() else {
+            panic!("expected struct type");
+        };
+        assert_eq!(get_field_id(&children[0]), Some(3));
+        assert_eq!(get_field_id(&children[1]), Some(4));
+
+        // Map key and value fields are assigned with ids, entries struct is not.
+        let entries = Field::new(
+            "key_value",
+            DataType::Struct(Fields::from(vec![
+                Field::new("key", DataType::Utf8, false),
+                Field::new("value", DataType::Int64, true),
+            ])),
+            /*nullable=*/ false,
+        );
+        let new_field = Field::new(
+            "attrs",
+            DataType::Map(Arc::new(entries), /*sorted=*/ false),
+            /*nullable=*/ true,
+        );
+        let new_field = assign_field_ids(&new_field, &mut next_field_id);
+        assert_eq!(get_field_id(&new_field), Some(8));
+        let DataType::Map(entries, _) = new_field.data_type() else {
+            panic!("expected map type");
+        };
+        assert_eq!(get_field_id(entries), None);
+        assert_eq!(get_highest_field_id_for_field(&new_field), 8);
+        let DataType::Struct(children) = entries.data_type() else {
+            panic!("expected struct type");
+        };
+        assert_eq!(get_field_id(&children[0]), Some(6));
+        assert_eq!(get_field_id(&children[1]), Some(7));
+    }
+}
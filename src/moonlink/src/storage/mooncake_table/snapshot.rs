@@ -16,7 +16,9 @@ use crate::storage::mooncake_table::shared_array::SharedRowBufferSnapshot;
 use crate::storage::mooncake_table::BatchIdCounter;
 use crate::storage::mooncake_table::MoonlinkRow;
 use crate::storage::mooncake_table::SnapshotOption;
+use crate::storage::parquet_utils::get_parquet_reader_options;
 use crate::storage::snapshot_options::IcebergSnapshotOption;
+use crate::storage::sort_utils;
 use crate::storage::storage_utils::{FileId, TableId, TableUniqueFileId};
 use crate::storage::storage_utils::{
     MooncakeDataFileRef, ProcessedDeletionRecord, RawDeletionRecord, RecordLocation,
@@ -88,6 +90,13 @@ pub(crate) struct SnapshotTableState {
 
     /// Batch ID counter for non-streaming operations
     pub(super) non_streaming_batch_id_counter: Arc<BatchIdCounter>,
+
+    /// LSN of the latest table truncation, data files flushed at or before which are truncated when integrated.
+    pub(super) last_truncate_lsn: Option<u64>,
+    /// Unpersisted data files and file indices dropped by table truncation, which could still be read by an ongoing persistence operation.
+    /// Their cache handles are released when the next persistence result gets reflected.
+    pub(super) truncated_unpersisted_data_files: HashMap<MooncakeDataFileRef, DiskFileEntry>,
+    pub(super) truncated_unpersisted_file_indices: Vec<FileIndex>,
 }
 
 #[derive(Clone)]
@@ -104,6 +113,8 @@ pub struct MooncakeSnapshotOutput {
     pub(crate) data_compaction_payload: DataCompactionMaintenanceStatus,
     /// Evicted local data cache files to delete.
     pub(crate) evicted_data_files_to_delete: Vec<String>,
+    /// Whether table maintenance result is discarded, since its input files have been truncated.
+    pub(crate) maintenance_result_discarded: bool,
     /// Optional mooncake snapshot dump.
     pub(crate) current_snapshot: Option<Snapshot>,
 }
@@ -126,6 +137,10 @@ impl std::fmt::Debug for MooncakeSnapshotOutput {
                 "evicted data files count",
                 &self.evicted_data_files_to_delete.len(),
             )
+            .field(
+                "maintenance_result_discarded",
+                &self.maintenance_result_discarded,
+            )
             .field("current_snapshot", &self.current_snapshot)
             .finish()
     }
@@ -181,6 +196,9 @@ impl SnapshotTableState {
             compacting_data_files: HashSet::new(),
             unpersisted_records: UnpersistedRecords::new(table_config),
             non_streaming_batch_id_counter,
+            last_truncate_lsn: None,
+            truncated_unpersisted_data_files: HashMap::new(),
+            truncated_unpersisted_file_indices: Vec::new(),
         })
     }
 
@@ -197,6 +215,16 @@ impl SnapshotTableState {
             .is_none());
         self.rows = None;
         self.last_commit = RecordLocation::MemoryBatch(initial_batch_id, 0);
+        // Sort key ranges are encoded with sort columns and partition source columns, which are not comparable after they're dropped or promoted.
+        if sort_utils::get_sort_key_column_types(
+            &self.mooncake_table_metadata.schema,
+            &self.mooncake_table_metadata.config,
+        ) != sort_utils::get_sort_key_column_types(&new_metadata.schema, &new_metadata.config)
+        {
+            for disk_file_entry in self.current_snapshot.disk_files.values_mut() {
+                disk_file_entry.sort_key_range = None;
+            }
+        }
         self.current_snapshot.metadata = new_metadata.clone();
         self.mooncake_table_metadata = new_metadata;
     }
@@ -391,6 +419,7 @@ impl SnapshotTableState {
                         /*max_rows=*/ cur_entry.num_rows,
                     ),
                     puffin_deletion_blob: None,
+                    sort_key_range: cur_entry.sort_key_range.clone(),
                 },
             );
         }
@@ -492,17 +521,22 @@ impl SnapshotTableState {
         task.persisted_records
             .validate_imported_files_remote(&self.iceberg_warehouse_location);
 
+        // All evicted data files by the object storage cache.
+        let mut evicted_data_files_to_delete = vec![];
+
+        // Table maintenance results are discarded if their input files have been truncated.
+        let (discarded_files_to_delete, maintenance_result_discarded) =
+            self.discard_truncated_maintenance_results(&mut task);
+        evicted_data_files_to_delete.extend(discarded_files_to_delete);
+
         // Calculate the expected disk files number after current snapshot update.
-        let expected_disk_files_count = self.get_expected_disk_files_count(&task);
+        let mut expected_disk_files_count = self.get_expected_disk_files_count(&task);
         // Calculate the expected file indices number after current snapshot update.
-        let expected_file_indices_count = self.get_expected_file_indices_count(&task);
+        let mut expected_file_indices_count = self.get_expected_file_indices_count(&task);
 
         // Update compacting data files, so their committed deletion logs won't get deleted.
         self.compacting_data_files = std::mem::take(&mut task.compacting_data_files);
 
-        // All evicted data files by the object storage cache.
-        let mut evicted_data_files_to_delete = vec![];
-
         // Reflect iceberg snapshot to mooncake snapshot.
         let persistence_evicted_files = self.update_snapshot_by_iceberg_snapshot(&task).await;
         evicted_data_files_to_delete.extend(persistence_evicted_files);
@@ -554,6 +588,16 @@ impl SnapshotTableState {
         self.rows = take(&mut task.new_rows);
         self.process_deletion_log(&mut task).await;
 
+        // Truncation drops whole data files and file indices, which are excluded from the expected count.
+        let disk_files_count_before_truncation = self.current_snapshot.disk_files.len();
+        let file_indices_count_before_truncation = self.current_snapshot.indices.file_indices.len();
+        let truncation_evicted_files = self.apply_truncation(&mut task).await;
+        evicted_data_files_to_delete.extend(truncation_evicted_files);
+        expected_disk_files_count -=
+            disk_files_count_before_truncation - self.current_snapshot.disk_files.len();
+        expected_file_indices_count -=
+            file_indices_count_before_truncation - self.current_snapshot.indices.file_indices.len();
+
         // Assert and update flush LSN.
         if let Some(new_flush_lsn) = task.new_flush_lsn {
             // Assert flush LSN doesn't regress, if not force snapshot.
@@ -587,11 +631,14 @@ impl SnapshotTableState {
         // or (2) accumulated unflushed deletion vector exceeds threshold
         // or (3) there're unpersisted table maintenance results
         // or (4) there's pending table schema update
+        // or (5) there're truncated data files to remove
         let mut persistence_snapshot_payload: Option<PersistenceSnapshotPayload> = None;
+        let flush_lsn = self.current_snapshot.flush_lsn.unwrap_or(0);
         let flush_by_deletion = self.create_iceberg_snapshot_by_committed_logs(opt.force_create);
         let flush_by_new_files_or_maintenance = self
             .unpersisted_records
-            .if_persist_by_new_files_or_maintenance(opt.force_create);
+            .if_persist_by_new_files_or_maintenance(opt.force_create)
+            || self.unpersisted_records.if_persist_by_truncation(flush_lsn);
         let force_empty_persistence_payload = task.force_empty_persistence_payload;
 
         // Decide whether to perform a data compaction.
@@ -612,7 +659,6 @@ impl SnapshotTableState {
             && (flush_by_new_files_or_maintenance || flush_by_deletion);
 
         // TODO(hjiang): When there's only schema evolution, we should also flush even no flush.
-        let flush_lsn = self.current_snapshot.flush_lsn.unwrap_or(0);
         let largest_flush_lsn = self.current_snapshot.largest_flush_lsn.unwrap_or(0);
         if opt.iceberg_snapshot_option != IcebergSnapshotOption::Skip
             && (force_empty_persistence_payload || flush_by_table_write)
@@ -657,6 +703,7 @@ impl SnapshotTableState {
             data_compaction_payload,
             file_indices_merge_payload,
             evicted_data_files_to_delete,
+            maintenance_result_discarded,
             current_snapshot: opt.dump_snapshot.then(|| self.current_snapshot.clone()),
         }
     }
@@ -760,6 +807,7 @@ impl SnapshotTableState {
                             cache_handle: Some(cache_handle),
                             committed_deletion_vector: BatchDeletionVector::new(file_attrs.row_num),
                             puffin_deletion_blob: None,
+                            sort_key_range: file_attrs.sort_key_range.clone(),
                         },
                     )
                     .is_none());
@@ -962,12 +1010,137 @@ impl SnapshotTableState {
                         file.file_path(),
                         *row_id,
                         &self.current_snapshot.metadata.config.row_identity,
+                        &self.current_snapshot.metadata.schema,
+                        get_parquet_reader_options(
+                            self.current_snapshot.metadata.config.get_encryption_key(),
+                        ),
                     )
                     .await
             }
         }
     }
 
+    /// Compare record locations by how recent the row is, rows in memory batches are later than rows in data files.
+    fn compare_recency(lhs: &RecordLocation, rhs: &RecordLocation) -> Ordering {
+        match (lhs, rhs) {
+            (
+                RecordLocation::MemoryBatch(lhs_batch_id, lhs_row_id),
+                RecordLocation::MemoryBatch(rhs_batch_id, rhs_row_id),
+            ) => (lhs_batch_id, lhs_row_id).cmp(&(rhs_batch_id, rhs_row_id)),
+            (
+                RecordLocation::DiskFile(lhs_file_id, lhs_row_id),
+                RecordLocation::DiskFile(rhs_file_id, rhs_row_id),
+            ) => (lhs_file_id.0, lhs_row_id).cmp(&(rhs_file_id.0, rhs_row_id)),
+            (RecordLocation::MemoryBatch(_, _), RecordLocation::DiskFile(_, _)) => {
+                Ordering::Greater
+            }
+            (RecordLocation::DiskFile(_, _), RecordLocation::MemoryBatch(_, _)) => Ordering::Less,
+        }
+    }
+
+    /// Find the latest non-deleted row which matches the given record, among the current snapshot and in-memory batches pending in the given snapshot task.
+    pub(super) async fn find_non_deleted_row(
+        &self,
+        record: &RawDeletionRecord,
+        task: &SnapshotTask,
+    ) -> Option<MoonlinkRow> {
+        let identity = &self.current_snapshot.metadata.config.row_identity;
+        // Deletions pending in the snapshot task without position target rows already in snapshot, so only rows appended later in the task could match.
+        let deleted_in_snapshot = task.new_deletions.iter().any(|deletion| {
+            deletion.pos.is_none()
+                && deletion.lookup_key == record.lookup_key
+                && deletion.row_identity == record.row_identity
+        });
+        let deleted_positions = task
+            .new_deletions
+            .iter()
+            .filter_map(|deletion| deletion.pos.map(RecordLocation::from))
+            .chain(
+                self.uncommitted_deletion_log
+                    .iter()
+                    .flatten()
+                    .map(|deletion| deletion.pos.clone()),
+            )
+            .collect::<HashSet<_>>();
+
+        let mut candidates = Vec::new();
+        for index in task.new_mem_indices.iter() {
+            candidates.extend(index.find_record(record));
+        }
+        candidates.extend(self.current_snapshot.indices.find_record(record).await);
+        candidates.sort_unstable_by(|lhs, rhs| Self::compare_recency(rhs, lhs));
+        candidates.dedup();
+
+        for loc in candidates.into_iter() {
+            if deleted_positions.contains(&loc) {
+                continue;
+            }
+            let row = match &loc {
+                RecordLocation::MemoryBatch(batch_id, row_id) => {
+                    if let Some(batch) = task
+                        .new_record_batches
+                        .iter()
+                        .find(|batch| batch.batch_id == *batch_id)
+                    {
+                        if batch
+                            .deletion_vector
+                            .as_ref()
+                            .is_some_and(|deletion_vector| deletion_vector.is_deleted(*row_id))
+                        {
+                            continue;
+                        }
+                        MoonlinkRow::from_record_batch(&batch.record_batch.slice(*row_id, 1)).pop()
+                    } else if deleted_in_snapshot {
+                        continue;
+                    } else if let Some(batch) = self.batches.get(batch_id) {
+                        if batch.deletions.is_deleted(*row_id) {
+                            continue;
+                        }
+                        batch.get_row(*row_id)
+                    } else {
+                        None
+                    }
+                }
+                RecordLocation::DiskFile(file_id, row_id) => {
+                    if deleted_in_snapshot {
+                        continue;
+                    }
+                    let Some((file, disk_file_entry)) =
+                        self.current_snapshot.disk_files.get_key_value(file_id)
+                    else {
+                        continue;
+                    };
+                    if disk_file_entry
+                        .committed_deletion_vector
+                        .is_deleted(*row_id)
+                    {
+                        continue;
+                    }
+                    Some(
+                        MoonlinkRow::from_parquet_at_offset(
+                            file.file_path(),
+                            *row_id,
+                            get_parquet_reader_options(
+                                self.current_snapshot.metadata.config.get_encryption_key(),
+                            ),
+                        )
+                        .await,
+                    )
+                }
+            };
+            let Some(row) = row else {
+                continue;
+            };
+            if let Some(row_identity) = &record.row_identity {
+                if !row_identity.equals_moonlink_row(&row, identity) {
+                    continue;
+                }
+            }
+            return Some(row);
+        }
+        None
+    }
+
     /// Commit a row deletion record.
     fn commit_deletion(&mut self, deletion: ProcessedDeletionRecord) {
         match &deletion.pos {
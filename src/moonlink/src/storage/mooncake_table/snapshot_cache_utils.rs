@@ -43,6 +43,10 @@ impl SnapshotTableState {
             }
         }
 
+        // Unreference and delete data files and file indices dropped by truncation.
+        let cur_evicted_files = self.release_truncated_unpersisted_records().await;
+        evicted_files_to_delete.extend(cur_evicted_files);
+
         evicted_files_to_delete
     }
 
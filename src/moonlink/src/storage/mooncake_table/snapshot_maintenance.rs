@@ -1,7 +1,9 @@
 use std::collections::HashSet;
 
 /// This file contains maintenance related features for mooncake snapshot.
-use crate::storage::compaction::table_compaction::SingleFileToCompact;
+use crate::storage::compaction::table_compaction::{
+    get_data_files_to_recluster, SingleFileToCompact,
+};
 use crate::storage::mooncake_table::snapshot::SnapshotTableState;
 use crate::storage::mooncake_table::{
     DataCompactionPayload, FileIndiceMergePayload, MaintenanceOption, SnapshotTask,
@@ -54,17 +56,19 @@ impl SnapshotTableState {
             max_data_compaction_file_num_threshold,
             data_file_deletion_percentage_threshold,
             data_compaction_file_size_threshold,
+            recluster_overlap_threshold,
         ) = match data_compaction_option {
-            MaintenanceOption::Skip => (None, usize::MAX, usize::MAX, 100, 0),
+            MaintenanceOption::Skip => (None, usize::MAX, usize::MAX, 100, 0, 0),
             MaintenanceOption::ForceRegular(event_id) => (
                 Some(event_id),
                 2,
                 config.max_data_file_to_compact as usize,
                 config.data_file_deletion_percentage as usize,
                 config.data_file_final_size as usize,
+                config.recluster_overlap_threshold as usize,
             ),
             MaintenanceOption::ForceFull(event_id) => {
-                (Some(event_id), 2, usize::MAX, 1, usize::MAX)
+                (Some(event_id), 2, usize::MAX, 1, usize::MAX, 0)
             }
             MaintenanceOption::BestEffort(event_id) => (
                 Some(event_id),
@@ -72,6 +76,7 @@ impl SnapshotTableState {
                 config.max_data_file_to_compact as usize,
                 config.data_file_deletion_percentage as usize,
                 config.data_file_final_size as usize,
+                config.recluster_overlap_threshold as usize,
             ),
         };
 
@@ -88,6 +93,20 @@ impl SnapshotTableState {
         // Number of data files rejected to merge due to unpersistence.
         let mut reject_by_unpersistence = 0;
 
+        // Re-cluster mode: persisted data files whose sort key ranges overlap too much are rewritten regardless of their size.
+        let files_to_recluster = get_data_files_to_recluster(
+            all_disk_files
+                .iter()
+                .filter(|(cur_data_file, _)| !unpersisted_data_files.contains(*cur_data_file))
+                .filter_map(|(cur_data_file, disk_file_entry)| {
+                    disk_file_entry
+                        .sort_key_range
+                        .as_ref()
+                        .map(|sort_key_range| (cur_data_file.file_id(), sort_key_range))
+                }),
+            recluster_overlap_threshold,
+        );
+
         // TODO(hjiang): We should be able to early exit, if left items are not enough to reach the compaction threshold.
         for (cur_data_file, disk_file_entry) in all_disk_files.iter() {
             // Doesn't compact those unpersisted files.
@@ -96,8 +115,10 @@ impl SnapshotTableState {
                 continue;
             }
 
-            // Skip compaction if the file size exceeds threshold, AND deleted rows are below config thresholds.
-            if disk_file_entry.file_size >= data_compaction_file_size_threshold {
+            // Skip compaction if the file size exceeds threshold, AND deleted rows are below config thresholds, AND it doesn't need re-cluster.
+            if disk_file_entry.file_size >= data_compaction_file_size_threshold
+                && !files_to_recluster.contains(&cur_data_file.file_id())
+            {
                 // Compaction by deletion is skipped.
                 if data_file_deletion_percentage_threshold == 0 {
                     continue;
@@ -6,6 +6,7 @@ use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::mooncake_table::SnapshotTask;
 use crate::storage::mooncake_table::{
     PersistenceSnapshotImportPayload, PersistenceSnapshotIndexMergePayload,
+    PersistenceSnapshotTruncationPayload,
 };
 use crate::storage::snapshot_options::IcebergSnapshotOption;
 use crate::storage::storage_utils::FileId;
@@ -67,6 +68,14 @@ impl SnapshotTableState {
                     .get_compacted_file_indices_to_remove(),
                 data_file_records_remap: self.unpersisted_records.get_compacted_data_file_remap(),
             },
+            truncation_payload: PersistenceSnapshotTruncationPayload {
+                old_data_files_to_remove: self
+                    .unpersisted_records
+                    .get_truncated_data_files_to_remove(flush_lsn),
+                old_file_indices_to_remove: self
+                    .unpersisted_records
+                    .get_truncated_file_indices_to_remove(flush_lsn),
+            },
         }
     }
 
@@ -249,6 +258,12 @@ impl SnapshotTableState {
             .persisted_records
             .get_file_indices_to_reflect_persistence();
 
+        // Data files and file indices truncated during persistence are not reflected, but removed at the next persistence.
+        let (persisted_data_files, persisted_file_indices, cur_evicted_files) = self
+            .take_truncated_persisted_records(persisted_data_files, persisted_file_indices)
+            .await;
+        evicted_files_to_delete.extend(cur_evicted_files);
+
         // Record data files number and file indices number for persistence reflection, which is not supposed to change.
         let old_data_files_count = self.current_snapshot.disk_files.len();
         let old_file_indices_count = self.current_snapshot.indices.file_indices.len();
@@ -279,6 +294,12 @@ impl SnapshotTableState {
         assert_eq!(old_data_files_count, new_data_files_count);
         assert_eq!(old_file_indices_count, new_file_indices_count);
 
+        // Remaining truncated records are not accessed by any persistence operation after completion.
+        if task.persisted_records.flush_lsn.is_some() {
+            let cur_evicted_files = self.release_truncated_unpersisted_records().await;
+            evicted_files_to_delete.extend(cur_evicted_files);
+        }
+
         evicted_files_to_delete
     }
 }
@@ -5,13 +5,13 @@ use crate::storage::mooncake_table::snapshot_read_output::{
     DataFileForRead, ReadOutput as SnapshotReadOutput,
 };
 use crate::storage::mooncake_table::table_status::TableSnapshotStatus;
+use crate::storage::mooncake_table_config::MooncakeTableConfig;
+use crate::storage::parquet_utils;
 use crate::storage::storage_utils::RecordLocation;
 use crate::NonEvictableHandle;
 use arrow_schema::Schema;
-use moonlink_table_metadata::{DeletionVector, PositionDelete};
+use moonlink_table_metadata::{DataKeyEnvelope, DeletionVector, PositionDelete};
 use parquet::arrow::AsyncArrowWriter;
-use parquet::basic::{Compression, Encoding};
-use parquet::file::properties::WriterProperties;
 use std::sync::Arc;
 
 impl SnapshotTableState {
@@ -23,6 +23,19 @@ impl SnapshotTableState {
         Ok(self.mooncake_table_metadata.schema.clone())
     }
 
+    pub(crate) fn get_table_config(&self) -> MooncakeTableConfig {
+        self.mooncake_table_metadata.config.clone()
+    }
+
+    /// Get the wrapped data key for readers to decrypt data files and deletion vectors with, if the table is encrypted.
+    fn get_data_key_envelope(&self) -> Option<DataKeyEnvelope> {
+        self.mooncake_table_metadata
+            .config
+            .table_encryption
+            .as_ref()
+            .map(|table_encryption| table_encryption.get_data_key_envelope())
+    }
+
     /// =======================
     /// Read snapshot states
     /// =======================
@@ -172,6 +185,7 @@ impl SnapshotTableState {
                 object_storage_cache: Some(self.object_storage_cache.clone()),
                 filesystem_accessor: Some(self.filesystem_accessor.clone()),
                 table_notifier: Some(self.table_notify.as_ref().unwrap().clone()),
+                data_key_envelope: self.get_data_key_envelope(),
             });
         }
 
@@ -212,11 +226,10 @@ impl SnapshotTableState {
             if !filtered_batches.is_empty() {
                 // Build a parquet file from current record batches
                 let temp_file = tokio::fs::File::create(&file_path).await?;
-                let props = WriterProperties::builder()
-                    .set_compression(Compression::UNCOMPRESSED)
-                    .set_dictionary_enabled(false)
-                    .set_encoding(Encoding::PLAIN)
-                    .build();
+                let props = parquet_utils::get_temp_file_parquet_properties(
+                    &self.mooncake_table_metadata.config.parquet_writer_config,
+                    self.mooncake_table_metadata.config.get_encryption_key(),
+                )?;
                 let mut parquet_writer = AsyncArrowWriter::try_new(temp_file, schema, Some(props))?;
                 for batch in filtered_batches.iter() {
                     parquet_writer.write(batch).await?;
@@ -237,6 +250,7 @@ impl SnapshotTableState {
             object_storage_cache: Some(self.object_storage_cache.clone()),
             filesystem_accessor: Some(self.filesystem_accessor.clone()),
             table_notifier: Some(self.table_notify.as_ref().unwrap().clone()),
+            data_key_envelope: self.get_data_key_envelope(),
         })
     }
 }
@@ -6,7 +6,7 @@ use crate::table_notify::TableEvent;
 use crate::ReadStateFilepathRemap;
 use crate::{NonEvictableHandle, ReadState, Result};
 use futures::{stream, StreamExt};
-use moonlink_table_metadata::{DeletionVector, PositionDelete};
+use moonlink_table_metadata::{DataKeyEnvelope, DeletionVector, PositionDelete};
 
 use std::sync::Arc;
 
@@ -66,6 +66,8 @@ pub struct ReadOutput {
     pub object_storage_cache: Option<Arc<dyn CacheTrait>>,
     /// Filesystem accessor, to access remote storage, could be none for empty read output.
     pub filesystem_accessor: Option<Arc<dyn BaseFileSystemAccess>>,
+    /// Wrapped data key to decrypt data files and deletion vectors with, only assigned for encrypted tables.
+    pub data_key_envelope: Option<DataKeyEnvelope>,
 }
 
 impl ReadOutput {
@@ -206,6 +208,7 @@ impl ReadOutput {
             self.associated_files,
             cache_handles,
             read_state_filepath_remap,
+            self.data_key_envelope,
         )))
     }
 
@@ -296,6 +299,7 @@ mod tests {
             table_notifier: Some(tx),
             object_storage_cache: Some(Arc::new(mock_cache)),
             filesystem_accessor: Some(Arc::new(filesystem_accessor)),
+            data_key_envelope: None,
         };
 
         // Invoke and expect success.
@@ -396,6 +400,7 @@ mod tests {
             table_notifier: Some(tx),
             object_storage_cache: Some(Arc::new(mock_cache)),
             filesystem_accessor: Some(Arc::new(filesystem_accessor)),
+            data_key_envelope: None,
         };
 
         // Invoke and expect error; previously pinned handle must be unpinned.
@@ -481,6 +486,7 @@ mod tests {
             table_notifier: Some(tx),
             object_storage_cache: Some(Arc::new(mock_cache)),
             filesystem_accessor: Some(Arc::new(filesystem_accessor)),
+            data_key_envelope: None,
         };
 
         let res = read_output
@@ -547,6 +553,7 @@ mod tests {
             table_notifier: Some(tx),
             object_storage_cache: Some(Arc::new(real_cache.clone())),
             filesystem_accessor: Some(Arc::new(filesystem_accessor)),
+            data_key_envelope: None,
         };
 
         let _read_state = read_output
@@ -616,6 +623,7 @@ mod tests {
             table_notifier: Some(tx),
             object_storage_cache: Some(Arc::new(real_cache.clone())),
             filesystem_accessor: Some(Arc::new(filesystem_accessor)),
+            data_key_envelope: None,
         };
 
         let _read_state_error = read_output
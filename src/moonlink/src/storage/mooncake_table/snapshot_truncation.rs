@@ -0,0 +1,320 @@
+/// This file contains table truncation related features for mooncake snapshot.
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::mem::take;
+
+use crate::storage::index::{cache_utils as index_cache_utils, FileIndex};
+use crate::storage::mooncake_table::snapshot::SnapshotTableState;
+use crate::storage::mooncake_table::SnapshotTask;
+use crate::storage::storage_utils::{FileId, MooncakeDataFileRef, RecordLocation};
+
+impl SnapshotTableState {
+    /// Apply committed table truncation, which drops all in-memory rows and data files committed before it; data files newly integrated but flushed before the latest truncation are dropped as well.
+    /// Dropped persisted data files and file indices are removed from the iceberg table at the next persistence.
+    /// Return evicted files to delete.
+    #[allow(clippy::mutable_key_type)]
+    pub(super) async fn apply_truncation(&mut self, task: &mut SnapshotTask) -> Vec<String> {
+        let truncation = task.new_truncation.take();
+        if let Some(truncation) = &truncation {
+            self.last_truncate_lsn = Some(truncation.lsn);
+        }
+        let Some(truncate_lsn) = self.last_truncate_lsn else {
+            return vec![];
+        };
+
+        // In-memory batches committed before truncation, maps from batch id to the number of truncated rows.
+        let mut truncated_batch_rows = HashMap::new();
+        if let Some(truncation) = &truncation {
+            let RecordLocation::MemoryBatch(position_batch_id, position_row_idx) =
+                truncation.mem_slice_position
+            else {
+                panic!(
+                    "Truncation position should be in memory, but got {:?}",
+                    truncation.mem_slice_position
+                );
+            };
+            for (batch_id, batch) in self.batches.iter_mut() {
+                let num_rows = if *batch_id >= (1u64 << 63) {
+                    // Streaming batches committed after truncation are kept.
+                    match task.flushing_batch_lsn_map.get(batch_id) {
+                        Some(commit_lsn) if *commit_lsn > truncate_lsn => 0,
+                        _ => batch.get_raw_record_number() as usize,
+                    }
+                } else {
+                    match batch_id.cmp(&position_batch_id) {
+                        Ordering::Less => batch.get_raw_record_number() as usize,
+                        Ordering::Equal => position_row_idx,
+                        Ordering::Greater => 0,
+                    }
+                };
+                if num_rows > 0 {
+                    batch.deletions.delete_rows_before(num_rows);
+                    truncated_batch_rows.insert(*batch_id, num_rows);
+                }
+            }
+        }
+
+        // Data files are truncated if they're flushed before truncation; files already in snapshot are truncated by the current truncation.
+        let truncated_data_files = self
+            .current_snapshot
+            .disk_files
+            .keys()
+            .filter(
+                |cur_file| match task.new_disk_file_lsn_map.get(&cur_file.file_id()) {
+                    Some(flush_lsn) => *flush_lsn <= truncate_lsn,
+                    None => truncation.is_some(),
+                },
+            )
+            .cloned()
+            .collect::<Vec<_>>();
+        if truncated_batch_rows.is_empty() && truncated_data_files.is_empty() {
+            return vec![];
+        }
+        let truncated_file_ids = truncated_data_files
+            .iter()
+            .map(|cur_file| cur_file.file_id())
+            .collect::<HashSet<FileId>>();
+
+        // Deletion records pointing to truncated rows are no longer needed.
+        let is_truncated = |pos: &RecordLocation| match pos {
+            RecordLocation::MemoryBatch(batch_id, row_idx) => truncated_batch_rows
+                .get(batch_id)
+                .is_some_and(|num_rows| row_idx < num_rows),
+            RecordLocation::DiskFile(file_id, _) => truncated_file_ids.contains(file_id),
+        };
+        self.committed_deletion_log
+            .retain(|cur_deletion| !is_truncated(&cur_deletion.pos));
+        self.uncommitted_deletion_log
+            .retain(|cur_deletion| !is_truncated(&cur_deletion.as_ref().unwrap().pos));
+
+        // Aggregate evicted files to delete.
+        let mut evicted_files_to_delete = vec![];
+
+        // Drop truncated data files.
+        let unpersisted_data_files = self.unpersisted_records.get_unpersisted_data_files_set();
+        let mut persisted_data_files = vec![];
+        for cur_data_file in truncated_data_files.into_iter() {
+            let disk_file_entry = self
+                .current_snapshot
+                .disk_files
+                .remove(&cur_data_file)
+                .unwrap();
+
+            // Unpersisted data files could be read by ongoing persistence, so they're kept in cache until the next persistence completion.
+            if unpersisted_data_files.contains(&cur_data_file) {
+                assert!(disk_file_entry.puffin_deletion_blob.is_none());
+                assert!(self
+                    .truncated_unpersisted_data_files
+                    .insert(cur_data_file, disk_file_entry)
+                    .is_none());
+                continue;
+            }
+
+            if let Some(cache_handle) = disk_file_entry.cache_handle {
+                let cur_evicted_files = cache_handle.unreference_and_delete().await;
+                evicted_files_to_delete.extend(cur_evicted_files);
+            }
+            // Even if there's no pinned cache handle within current snapshot (since it's persisted), still try to delete it from cache if exists.
+            else {
+                let unique_file_id = self.get_table_unique_file_id(cur_data_file.file_id());
+                let cur_evicted_files = self
+                    .object_storage_cache
+                    .try_delete_cache_entry(unique_file_id)
+                    .await;
+                evicted_files_to_delete.extend(cur_evicted_files);
+            }
+            if let Some(puffin_deletion_blob) = disk_file_entry.puffin_deletion_blob {
+                let cur_evicted_files = puffin_deletion_blob
+                    .puffin_file_cache_handle
+                    .unreference_and_delete()
+                    .await;
+                evicted_files_to_delete.extend(cur_evicted_files);
+            }
+            persisted_data_files.push(cur_data_file);
+        }
+
+        // Drop file indices for truncated data files; a file index never references both truncated and remaining data files.
+        let unpersisted_file_indices = self.unpersisted_records.get_unpersisted_file_indices_set();
+        let (truncated_file_indices, remaining_file_indices): (Vec<_>, Vec<_>) =
+            take(&mut self.current_snapshot.indices.file_indices)
+                .into_iter()
+                .partition(|cur_file_index| {
+                    cur_file_index
+                        .files
+                        .iter()
+                        .any(|cur_file| truncated_file_ids.contains(&cur_file.file_id()))
+                });
+        self.current_snapshot.indices.file_indices = remaining_file_indices;
+        let mut persisted_file_indices = vec![];
+        for cur_file_index in truncated_file_indices.into_iter() {
+            assert!(cur_file_index
+                .files
+                .iter()
+                .all(|cur_file| truncated_file_ids.contains(&cur_file.file_id())));
+            if unpersisted_file_indices.contains(&cur_file_index) {
+                self.truncated_unpersisted_file_indices.push(cur_file_index);
+                continue;
+            }
+
+            let mut file_index_copy = cur_file_index.clone();
+            let cur_evicted_files =
+                index_cache_utils::unreference_and_delete_file_index_from_cache(
+                    &mut file_index_copy,
+                )
+                .await;
+            evicted_files_to_delete.extend(cur_evicted_files);
+            persisted_file_indices.push(cur_file_index);
+        }
+
+        self.unpersisted_records.truncate(
+            truncate_lsn,
+            &truncated_file_ids,
+            persisted_data_files,
+            persisted_file_indices,
+        );
+
+        evicted_files_to_delete
+    }
+
+    /// Split out persisted data files and file indices which have been dropped by table truncation during persistence, they're recorded to remove at the next persistence.
+    /// Return the remaining persisted data files and file indices to reflect, and evicted files to delete.
+    pub(super) async fn take_truncated_persisted_records(
+        &mut self,
+        persisted_data_files: Vec<MooncakeDataFileRef>,
+        persisted_file_indices: Vec<FileIndex>,
+    ) -> (Vec<MooncakeDataFileRef>, Vec<FileIndex>, Vec<String>) {
+        let mut evicted_files_to_delete = vec![];
+
+        let (truncated_data_files, persisted_data_files): (Vec<_>, Vec<_>) = persisted_data_files
+            .into_iter()
+            .partition(|cur_file| !self.current_snapshot.disk_files.contains_key(cur_file));
+        for cur_data_file in truncated_data_files.iter() {
+            let disk_file_entry = self
+                .truncated_unpersisted_data_files
+                .remove(cur_data_file)
+                .unwrap();
+            if let Some(cache_handle) = disk_file_entry.cache_handle {
+                let cur_evicted_files = cache_handle.unreference_and_delete().await;
+                evicted_files_to_delete.extend(cur_evicted_files);
+            }
+        }
+
+        let (truncated_file_indices, persisted_file_indices): (Vec<_>, Vec<_>) =
+            persisted_file_indices
+                .into_iter()
+                .partition(|cur_file_index| {
+                    cur_file_index
+                        .files
+                        .iter()
+                        .any(|cur_file| !self.current_snapshot.disk_files.contains_key(cur_file))
+                });
+        for cur_file_index in truncated_file_indices.iter() {
+            // Persisted file index shares the same pinned cache handles with the truncated one, so only unreference once.
+            let held_index = self
+                .truncated_unpersisted_file_indices
+                .iter()
+                .position(|cur_held_index| cur_held_index == cur_file_index)
+                .unwrap();
+            self.truncated_unpersisted_file_indices
+                .swap_remove(held_index);
+
+            let mut file_index_copy = cur_file_index.clone();
+            let cur_evicted_files =
+                index_cache_utils::unreference_and_delete_file_index_from_cache(
+                    &mut file_index_copy,
+                )
+                .await;
+            evicted_files_to_delete.extend(cur_evicted_files);
+        }
+
+        self.unpersisted_records
+            .add_truncated_persisted_records(truncated_data_files, truncated_file_indices);
+
+        (
+            persisted_data_files,
+            persisted_file_indices,
+            evicted_files_to_delete,
+        )
+    }
+
+    /// Discard table maintenance results whose input data files or file indices have been dropped by table truncation.
+    /// Return files to delete which are generated by the discarded results, and whether any result is discarded.
+    pub(super) fn discard_truncated_maintenance_results(
+        &mut self,
+        task: &mut SnapshotTask,
+    ) -> (Vec<String>, bool) {
+        let mut files_to_delete = vec![];
+        let mut discarded = false;
+
+        // Index merge.
+        if task
+            .index_merge_result
+            .old_file_indices
+            .iter()
+            .any(|cur_file_index| {
+                !self
+                    .current_snapshot
+                    .indices
+                    .file_indices
+                    .contains(cur_file_index)
+            })
+        {
+            let index_merge_result = std::mem::take(&mut task.index_merge_result);
+            for cur_file_index in index_merge_result.new_file_indices.iter() {
+                files_to_delete.extend(
+                    cur_file_index
+                        .index_blocks
+                        .iter()
+                        .map(|cur_index_block| cur_index_block.index_file.file_path().clone()),
+                );
+            }
+            discarded = true;
+        }
+
+        // Data compaction.
+        if task
+            .data_compaction_result
+            .old_data_files
+            .iter()
+            .any(|cur_data_file| !self.current_snapshot.disk_files.contains_key(cur_data_file))
+        {
+            let data_compaction_result = std::mem::take(&mut task.data_compaction_result);
+            for (cur_data_file, _) in data_compaction_result.new_data_files.iter() {
+                files_to_delete.push(cur_data_file.file_path().clone());
+            }
+            for cur_file_index in data_compaction_result.new_file_indices.iter() {
+                files_to_delete.extend(
+                    cur_file_index
+                        .index_blocks
+                        .iter()
+                        .map(|cur_index_block| cur_index_block.index_file.file_path().clone()),
+                );
+            }
+            files_to_delete.extend(data_compaction_result.evicted_files_to_delete);
+            discarded = true;
+        }
+
+        (files_to_delete, discarded)
+    }
+
+    /// Release cache handles for unpersisted data files and file indices dropped by table truncation, which are no longer accessed by persistence.
+    /// Return evicted files to delete.
+    pub(crate) async fn release_truncated_unpersisted_records(&mut self) -> Vec<String> {
+        let mut evicted_files_to_delete = vec![];
+        for (_, disk_file_entry) in self.truncated_unpersisted_data_files.drain() {
+            if let Some(cache_handle) = disk_file_entry.cache_handle {
+                let cur_evicted_files = cache_handle.unreference_and_delete().await;
+                evicted_files_to_delete.extend(cur_evicted_files);
+            }
+        }
+        for mut cur_file_index in take(&mut self.truncated_unpersisted_file_indices) {
+            let cur_evicted_files =
+                index_cache_utils::unreference_and_delete_file_index_from_cache(
+                    &mut cur_file_index,
+                )
+                .await;
+            evicted_files_to_delete.extend(cur_evicted_files);
+        }
+        evicted_files_to_delete
+    }
+}
@@ -1,4 +1,5 @@
 use crate::storage::mooncake_table_config::MooncakeTableConfig;
+use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
 use crate::storage::table::iceberg::iceberg_table_config::IcebergTableConfig;
 use crate::WalConfig;
 
@@ -10,6 +11,8 @@ pub struct TableConfig {
     pub mooncake_table_config: MooncakeTableConfig,
     /// Iceberg table config.
     pub iceberg_table_config: IcebergTableConfig,
+    /// Delta table config, if assigned, table is persisted in delta format instead of iceberg.
+    pub deltalake_table_config: Option<DeltalakeTableConfig>,
     /// Wal table config
     pub wal_table_config: WalConfig,
 }
@@ -6,6 +6,8 @@ use crate::storage::compaction::compaction_config::DataCompactionConfig;
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
 use crate::storage::filesystem::accessor::factory::create_filesystem_accessor;
 use crate::storage::filesystem::accessor_config::AccessorConfig;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::azure_test_utils;
 #[cfg(feature = "storage-gcs")]
 use crate::storage::filesystem::gcs::gcs_test_utils;
 #[cfg(feature = "storage-s3")]
@@ -145,6 +147,15 @@ pub(crate) fn create_iceberg_table_config(warehouse_uri: String) -> IcebergTable
         {
             panic!("GCS support not enabled. Enable `storage-gcs` feature.");
         }
+    } else if warehouse_uri.starts_with("abfss://") {
+        #[cfg(feature = "storage-azure")]
+        {
+            azure_test_utils::create_azure_storage_config(&warehouse_uri)
+        }
+        #[cfg(not(feature = "storage-azure"))]
+        {
+            panic!("Azure support not enabled. Enable `storage-azure` feature.");
+        }
     } else {
         let storage_config = StorageConfig::FileSystem {
             root_directory: warehouse_uri.clone(),
@@ -189,6 +200,15 @@ pub(crate) fn create_delta_table_config(warehouse_uri: String) -> DeltalakeTable
         {
             panic!("GCS support not enabled. Enable `storage-gcs` feature.");
         }
+    } else if warehouse_uri.starts_with("abfss://") {
+        #[cfg(feature = "storage-azure")]
+        {
+            azure_test_utils::create_azure_storage_config(&warehouse_uri)
+        }
+        #[cfg(not(feature = "storage-azure"))]
+        {
+            panic!("Azure support not enabled. Enable `storage-azure` feature.");
+        }
     } else {
         let storage_config = StorageConfig::FileSystem {
             root_directory: warehouse_uri.clone(),
@@ -369,6 +389,7 @@ pub(crate) fn create_test_table_metadata_with_data_compaction_disable_flush(
         max_data_file_to_compact: u32::MAX,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     };
     let mut config = MooncakeTableConfig::new(local_table_directory.clone());
     config.disk_slice_writer_config = disk_slice_write_config;
@@ -469,6 +490,7 @@ pub(crate) async fn create_mooncake_table_and_notify_for_compaction(
             min_data_file_to_compact: 2,
             max_data_file_to_compact: u32::MAX,
             data_file_deletion_percentage: 0,
+            recluster_overlap_threshold: 0,
         },
         ..Default::default()
     };
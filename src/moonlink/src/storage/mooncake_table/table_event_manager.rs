@@ -2,6 +2,7 @@
 use tokio::sync::{broadcast, mpsc, oneshot, watch};
 
 use crate::event_sync::EventSyncReceiver;
+use crate::storage::SnapshotExpirationReport;
 use crate::Result;
 use crate::TableEvent;
 
@@ -19,6 +20,8 @@ pub struct TableEventManager {
     force_snapshot_completion_rx: watch::Receiver<Option<Result<u64>>>,
     /// Sender which is used to create notification at latest data compaction completion.
     table_maintenance_completion_tx: broadcast::Sender<Result<()>>,
+    /// Sender which is used to create notification at latest snapshot expiration completion.
+    snapshot_expiration_completion_tx: broadcast::Sender<Result<SnapshotExpirationReport>>,
 }
 
 impl TableEventManager {
@@ -33,6 +36,8 @@ impl TableEventManager {
             wal_flush_lsn_rx: table_event_sync_rx.wal_flush_lsn_rx,
             force_snapshot_completion_rx: table_event_sync_rx.force_snapshot_completion_rx,
             table_maintenance_completion_tx: table_event_sync_rx.table_maintenance_completion_tx,
+            snapshot_expiration_completion_tx: table_event_sync_rx
+                .snapshot_expiration_completion_tx,
         }
     }
 
@@ -118,6 +123,20 @@ impl TableEventManager {
         subscriber
     }
 
+    /// Initiate a snapshot expiration event based on table retention config, return the channel for synchronization.
+    /// If `dry_run` is true, the completion report only contains what would be removed.
+    pub async fn initiate_snapshot_expiration(
+        &mut self,
+        dry_run: bool,
+    ) -> broadcast::Receiver<Result<SnapshotExpirationReport>> {
+        let subscriber = self.snapshot_expiration_completion_tx.subscribe();
+        self.table_event_tx
+            .send(TableEvent::ForceSnapshotExpiration { dry_run })
+            .await
+            .unwrap();
+        subscriber
+    }
+
     /// Drop a mooncake table.
     /// Each table event manager correspond to one mooncake table, so this function should be called at most once.
     pub async fn drop_table(&mut self) -> Result<()> {
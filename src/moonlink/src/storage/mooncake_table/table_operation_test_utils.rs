@@ -722,10 +722,10 @@ pub(crate) async fn alter_table_and_persist_to_iceberg(
 
     if let Some(mut persistence_snapshot_payload) = persistence_snapshot_payload {
         let alter_table_request = AlterTableRequest {
-            new_columns: vec![],
             dropped_columns: vec!["age".to_string()],
+            ..Default::default()
         };
-        let new_table_metadata = table.alter_table(alter_table_request);
+        let new_table_metadata = table.alter_table(alter_table_request).unwrap();
         persistence_snapshot_payload.new_table_schema = Some(new_table_metadata.clone());
         let persistence_snapshot_result =
             create_iceberg_snapshot(table, Some(persistence_snapshot_payload), notify_rx).await;
@@ -742,10 +742,10 @@ pub(crate) fn get_read_state_filepath_remap() -> ReadStateFilepathRemap {
 
 pub(crate) async fn alter_table(table: &mut MooncakeTable) {
     let alter_table_request = AlterTableRequest {
-        new_columns: vec![],
         dropped_columns: vec!["age".to_string()],
+        ..Default::default()
     };
-    table.alter_table(alter_table_request);
+    table.alter_table(alter_table_request).unwrap();
 }
 
 #[cfg(test)]
@@ -9,12 +9,20 @@ pub enum SecretType {
     Gcs,
     #[cfg(feature = "storage-s3")]
     S3,
+    /// Azure shared key, with account name as key id and account key as secret.
+    #[cfg(feature = "storage-azure")]
+    Azure,
+    /// Azure shared access signature, with account name as key id and SAS token as secret.
+    #[cfg(feature = "storage-azure")]
+    AzureSas,
 }
 
 #[derive(Clone, PartialEq)]
 pub struct SecretEntry {
     pub secret_type: SecretType,
+    /// Access key id, or account name for Azure.
     pub key_id: String,
+    /// Secret access key, or account key or SAS token for Azure depending on secret type.
     pub secret: String,
     pub project: Option<String>,
     pub endpoint: Option<String>,
@@ -43,6 +51,10 @@ impl SecretEntry {
             SecretType::Gcs => "gcs".to_string(),
             #[cfg(feature = "storage-s3")]
             SecretType::S3 => "s3".to_string(),
+            #[cfg(feature = "storage-azure")]
+            SecretType::Azure => "azure".to_string(),
+            #[cfg(feature = "storage-azure")]
+            SecretType::AzureSas => "azure_sas".to_string(),
         }
     }
 
@@ -60,6 +72,15 @@ impl SecretEntry {
                 return SecretType::S3;
             }
         }
+        #[cfg(feature = "storage-azure")]
+        {
+            if secret_type == "azure" {
+                return SecretType::Azure;
+            }
+            if secret_type == "azure_sas" {
+                return SecretType::AzureSas;
+            }
+        }
         // Used to suppress compilation warning.
         assert_eq!(secret_type, "filesystem");
         SecretType::FileSystem
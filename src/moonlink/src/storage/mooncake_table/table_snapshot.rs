@@ -7,8 +7,10 @@ use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
 use crate::storage::storage_utils::FileId;
 use crate::storage::storage_utils::MooncakeDataFileRef;
 use crate::storage::storage_utils::RecordLocation;
+use crate::storage::table::common::table_manager::SnapshotExpirationReport;
 use crate::storage::table::iceberg::puffin_utils::PuffinBlobRef;
 use crate::storage::TableManager;
+use crate::Result;
 
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -132,6 +134,37 @@ impl PersistenceSnapshotDataCompactionPayload {
     }
 }
 
+/// Iceberg snapshot payload by table truncation.
+#[derive(Clone, Default)]
+pub struct PersistenceSnapshotTruncationPayload {
+    /// Truncated data files to remove from the iceberg table.
+    pub(crate) old_data_files_to_remove: Vec<MooncakeDataFileRef>,
+    /// Truncated file indices to remove from the iceberg table.
+    pub(crate) old_file_indices_to_remove: Vec<MooncakeFileIndex>,
+}
+
+impl PersistenceSnapshotTruncationPayload {
+    /// Return whether truncation payload is empty.
+    pub fn is_empty(&self) -> bool {
+        self.old_data_files_to_remove.is_empty() && self.old_file_indices_to_remove.is_empty()
+    }
+}
+
+impl std::fmt::Debug for PersistenceSnapshotTruncationPayload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistenceSnapshotTruncationPayload")
+            .field(
+                "old data files to remove count",
+                &self.old_data_files_to_remove.len(),
+            )
+            .field(
+                "old file indices to remove count",
+                &self.old_file_indices_to_remove.len(),
+            )
+            .finish()
+    }
+}
+
 #[derive(Clone)]
 pub struct PersistenceSnapshotPayload {
     /// Background event id.
@@ -148,6 +181,8 @@ pub struct PersistenceSnapshotPayload {
     pub(crate) index_merge_payload: PersistenceSnapshotIndexMergePayload,
     /// Payload by data file compaction operations.
     pub(crate) data_compaction_payload: PersistenceSnapshotDataCompactionPayload,
+    /// Payload by table truncation.
+    pub(crate) truncation_payload: PersistenceSnapshotTruncationPayload,
 }
 
 impl std::fmt::Debug for PersistenceSnapshotPayload {
@@ -162,6 +197,7 @@ impl std::fmt::Debug for PersistenceSnapshotPayload {
             .field("import payload", &self.import_payload)
             .field("index merge payload", &self.index_merge_payload)
             .field("data compaction payload", &self.data_compaction_payload)
+            .field("truncation payload", &self.truncation_payload)
             .finish()
     }
 }
@@ -207,6 +243,7 @@ impl PersistenceSnapshotPayload {
                 .old_data_files_to_remove
                 .clone(),
         );
+        old_data_files.extend(self.truncation_payload.old_data_files_to_remove.clone());
         old_data_files
     }
 
@@ -234,6 +271,7 @@ impl PersistenceSnapshotPayload {
                 .old_file_indices_to_remove
                 .clone(),
         );
+        old_file_indices.extend(self.truncation_payload.old_file_indices_to_remove.clone());
         old_file_indices
     }
 }
@@ -367,6 +405,37 @@ impl std::fmt::Debug for PersistenceSnapshotDataCompactionResult {
     }
 }
 
+/// Iceberg snapshot table truncation result.
+#[derive(Clone, Default)]
+pub struct PersistenceSnapshotTruncationResult {
+    /// Truncated data files which are removed from the iceberg table.
+    pub(crate) old_data_files_removed: Vec<MooncakeDataFileRef>,
+    /// Truncated file indices which are removed from the iceberg table.
+    pub(crate) old_file_indices_removed: Vec<MooncakeFileIndex>,
+}
+
+impl PersistenceSnapshotTruncationResult {
+    /// Return whether truncation result is empty.
+    pub fn is_empty(&self) -> bool {
+        self.old_data_files_removed.is_empty() && self.old_file_indices_removed.is_empty()
+    }
+}
+
+impl std::fmt::Debug for PersistenceSnapshotTruncationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PersistenceSnapshotTruncationResult")
+            .field(
+                "old data files removed count",
+                &self.old_data_files_removed.len(),
+            )
+            .field(
+                "old file indices removed count",
+                &self.old_file_indices_removed.len(),
+            )
+            .finish()
+    }
+}
+
 pub struct PersistenceSnapshotResult {
     /// Background event id.
     pub(crate) uuid: uuid::Uuid,
@@ -384,6 +453,8 @@ pub struct PersistenceSnapshotResult {
     pub(crate) index_merge_result: PersistenceSnapshotIndexMergeResult,
     /// Iceberg data file compaction result.
     pub(crate) data_compaction_result: PersistenceSnapshotDataCompactionResult,
+    /// Iceberg table truncation result.
+    pub(crate) truncation_result: PersistenceSnapshotTruncationResult,
     /// Evicted files to delete by object storage cache.
     pub(crate) evicted_files_to_delete: Vec<String>,
 }
@@ -399,6 +470,7 @@ impl Clone for PersistenceSnapshotResult {
             import_result: self.import_result.clone(),
             index_merge_result: self.index_merge_result.clone(),
             data_compaction_result: self.data_compaction_result.clone(),
+            truncation_result: self.truncation_result.clone(),
             evicted_files_to_delete: self.evicted_files_to_delete.clone(),
         }
     }
@@ -429,6 +501,7 @@ impl std::fmt::Debug for PersistenceSnapshotResult {
             .field("import_result", &self.import_result)
             .field("index_merge_result", &self.index_merge_result)
             .field("data_compaction_result", &self.data_compaction_result)
+            .field("truncation_result", &self.truncation_result)
             .field(
                 "evicted files to delete count",
                 &self.evicted_files_to_delete.len(),
@@ -437,6 +510,34 @@ impl std::fmt::Debug for PersistenceSnapshotResult {
     }
 }
 
+////////////////////////////
+/// Snapshot expiration
+////////////////////////////
+///
+pub struct SnapshotExpirationResult {
+    /// Table manager is moved into snapshot expiration task, same as iceberg snapshot.
+    pub(crate) table_manager: Option<Box<dyn TableManager>>,
+    /// Snapshot expiration report.
+    pub(crate) report: Result<SnapshotExpirationReport>,
+}
+
+impl Clone for SnapshotExpirationResult {
+    fn clone(&self) -> Self {
+        SnapshotExpirationResult {
+            table_manager: None,
+            report: self.report.clone(),
+        }
+    }
+}
+
+impl std::fmt::Debug for SnapshotExpirationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SnapshotExpirationResult")
+            .field("report", &self.report)
+            .finish()
+    }
+}
+
 ////////////////////////////
 /// Index merge
 ////////////////////////////
@@ -506,11 +607,15 @@ pub fn take_data_files_to_import(
 pub fn take_data_files_to_remove(
     snapshot_payload: &mut PersistenceSnapshotPayload,
 ) -> Vec<MooncakeDataFileRef> {
-    std::mem::take(
+    let mut old_data_files = std::mem::take(
         &mut snapshot_payload
             .data_compaction_payload
             .old_data_files_to_remove,
-    )
+    );
+    old_data_files.extend(std::mem::take(
+        &mut snapshot_payload.truncation_payload.old_data_files_to_remove,
+    ));
+    old_data_files
 }
 
 /// Util functions to take all file indices to import.
@@ -545,5 +650,10 @@ pub fn take_file_indices_to_remove(
             .data_compaction_payload
             .old_file_indices_to_remove,
     ));
+    old_file_indices.extend(std::mem::take(
+        &mut snapshot_payload
+            .truncation_payload
+            .old_file_indices_to_remove,
+    ));
     old_file_indices
 }
@@ -2,6 +2,7 @@
 use std::sync::Arc;
 
 use crate::storage::mooncake_table::table_status::TableSnapshotStatus;
+use crate::storage::mooncake_table_config::MooncakeTableConfig;
 use crate::storage::IcebergTableConfig;
 use crate::storage::MooncakeTable;
 use crate::storage::SnapshotTableState;
@@ -50,6 +51,12 @@ impl TableStatusReader {
         };
         Ok(table_schema)
     }
+
+    /// Get current table config, which reflects column renames after schema changes.
+    pub async fn get_current_table_config(&self) -> MooncakeTableConfig {
+        let snapshot_guard = self.table_snapshot.read().await;
+        snapshot_guard.get_table_config()
+    }
 }
 
 #[cfg(test)]
@@ -119,6 +119,71 @@ pub async fn test_append_only_table(context: &TestContext, table_name: &str) ->
     .unwrap()
 }
 
+/// Create a test table partitioned by identity transform on the given column.
+pub async fn test_partitioned_table(
+    context: &TestContext,
+    table_name: &str,
+    partition_column: &str,
+) -> MooncakeTable {
+    let iceberg_table_config = test_iceberg_table_config(context, table_name);
+    let mut table_config = test_mooncake_table_config(context);
+    table_config.batch_size = 2;
+    table_config.row_identity = IdentityProp::Keys(vec![0]);
+    table_config.partition_spec = vec![crate::PartitionFieldConfig::new(
+        partition_column,
+        iceberg::spec::Transform::Identity,
+    )];
+    let wal_config = WalConfig::default_wal_config_local(WAL_TEST_TABLE_ID, &context.path());
+    let wal_manager = WalManager::new(&wal_config);
+    MooncakeTable::new(
+        (*create_test_arrow_schema()).clone(),
+        table_name.to_string(),
+        1,
+        context.path(),
+        iceberg_table_config.clone(),
+        table_config,
+        wal_manager,
+        create_test_object_storage_cache(&context.temp_dir),
+        create_test_filesystem_accessor(&iceberg_table_config),
+    )
+    .await
+    .unwrap()
+}
+
+pub async fn test_sorted_table(
+    context: &TestContext,
+    table_name: &str,
+    sort_columns: &[&str],
+) -> Result<MooncakeTable> {
+    let iceberg_table_config = test_iceberg_table_config(context, table_name);
+    let mut table_config = test_mooncake_table_config(context);
+    table_config.row_identity = IdentityProp::Keys(vec![0]);
+    table_config.sort_order = sort_columns
+        .iter()
+        .map(|column| {
+            crate::SortFieldConfig::new(
+                column,
+                iceberg::spec::SortDirection::Ascending,
+                iceberg::spec::NullOrder::First,
+            )
+        })
+        .collect();
+    let wal_config = WalConfig::default_wal_config_local(WAL_TEST_TABLE_ID, &context.path());
+    let wal_manager = WalManager::new(&wal_config);
+    MooncakeTable::new(
+        (*create_test_arrow_schema()).clone(),
+        table_name.to_string(),
+        1,
+        context.path(),
+        iceberg_table_config.clone(),
+        table_config,
+        wal_manager,
+        create_test_object_storage_cache(&context.temp_dir),
+        create_test_filesystem_accessor(&iceberg_table_config),
+    )
+    .await
+}
+
 // TODO(hjiang): Support object storage.
 pub fn read_ids_from_parquet(file_path: &String) -> Vec<Option<i32>> {
     let file = File::open(file_path).unwrap();
@@ -244,6 +309,7 @@ pub async fn verify_files_and_deletions(
             puffin_file_paths
                 .get(cur_blob.puffin_file_number as usize)
                 .unwrap(),
+            /*encryption_key=*/ None,
         );
         load_blob_futures.push(get_blob_future);
     }
@@ -4,6 +4,7 @@ use crate::storage::mooncake_table::table_creation_test_utils::*;
 use crate::storage::mooncake_table::table_operation_test_utils::*;
 use crate::storage::mooncake_table::test_utils::{append_rows, test_row, test_table, TestContext};
 use crate::storage::mooncake_table::Snapshot as MooncakeSnapshot;
+use crate::storage::mooncake_table_config::ParquetColumnConfig;
 use crate::storage::snapshot_options::IcebergSnapshotOption;
 use crate::storage::snapshot_options::MaintenanceOption;
 use crate::storage::snapshot_options::SnapshotOption;
@@ -258,6 +259,76 @@ async fn test_update_rows(#[case] identity: IdentityProp) -> Result<()> {
     Ok(())
 }
 
+#[apply(shared_cases)]
+#[tokio::test]
+async fn test_fill_unchanged_columns(#[case] identity: IdentityProp) -> Result<()> {
+    let old_row = test_row(1, "Row 1", 31);
+    // Name column is unchanged by the update, so it's not carried by the new row.
+    let mut new_row = test_row(1, "", 41);
+    new_row.values[1] = crate::row::RowValue::Null;
+    let expected_row = test_row(1, "Row 1", 41);
+
+    let context = TestContext::new("fill_unchanged_columns");
+    let mut table = test_table(&context, "update_table", identity).await;
+    let (event_completion_tx, mut event_completion_rx) = mpsc::channel(100);
+    table.register_table_notify(event_completion_tx).await;
+
+    // Current row lives in mem slice.
+    table.append(old_row.clone())?;
+    table.commit(/*lsn=*/ 100);
+    let filled_row = table
+        .fill_unchanged_columns(&old_row, new_row.clone(), &[1], /*xact_id=*/ None)
+        .await?;
+    assert_eq!(filled_row, expected_row);
+
+    // Current row lives in data file.
+    flush_table_and_sync(&mut table, &mut event_completion_rx, /*lsn=*/ 100).await?;
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+    let filled_row = table
+        .fill_unchanged_columns(&old_row, new_row.clone(), &[1], /*xact_id=*/ None)
+        .await?;
+    assert_eq!(filled_row, expected_row);
+
+    // Deleted row is not taken as current row, both before and after snapshot.
+    table.delete(old_row.clone(), /*lsn=*/ 200).await;
+    table.commit(/*lsn=*/ 300);
+    assert!(table
+        .fill_unchanged_columns(&old_row, new_row.clone(), &[1], /*xact_id=*/ None)
+        .await
+        .is_err());
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+    assert!(table
+        .fill_unchanged_columns(&old_row, new_row.clone(), &[1], /*xact_id=*/ None)
+        .await
+        .is_err());
+
+    // Rows written by updates are cached for later updates.
+    table.append(expected_row.clone())?;
+    table.cache_updated_row(expected_row.clone());
+    table.commit(/*lsn=*/ 400);
+    let filled_row = table
+        .fill_unchanged_columns(&expected_row, new_row.clone(), &[1], /*xact_id=*/ None)
+        .await?;
+    assert_eq!(filled_row, expected_row);
+
+    // Cached row is invalidated by later appends to the same row.
+    table.delete(expected_row.clone(), /*lsn=*/ 500).await;
+    let reinserted_row = test_row(1, "Row 2", 41);
+    table.append(reinserted_row.clone())?;
+    table.commit(/*lsn=*/ 600);
+    let filled_row = table
+        .fill_unchanged_columns(
+            &reinserted_row,
+            new_row.clone(),
+            &[1],
+            /*xact_id=*/ None,
+        )
+        .await?;
+    assert_eq!(filled_row, reinserted_row);
+
+    Ok(())
+}
+
 #[tokio::test]
 async fn test_snapshot_initialization() -> Result<()> {
     let schema = create_test_arrow_schema();
@@ -641,6 +712,156 @@ async fn test_snapshot_store_failure() {
     assert!(persistence_snapshot_result.is_err());
 }
 
+#[tokio::test]
+async fn test_alter_table_drop_partition_column_rejected() {
+    let context = TestContext::new("alter_table_partition_column");
+    let mut table = test_partitioned_table(&context, "alter_table_partition_column", "age").await;
+
+    // Dropping partition source column is rejected, and table metadata is left unchanged.
+    let alter_table_request = AlterTableRequest {
+        dropped_columns: vec!["age".to_string()],
+        ..Default::default()
+    };
+    assert!(table.validate_alter_table(&alter_table_request).is_err());
+    let old_metadata = table.metadata.clone();
+    assert!(table.alter_table(alter_table_request).is_err());
+    assert!(Arc::ptr_eq(&old_metadata, &table.metadata));
+}
+
+#[tokio::test]
+async fn test_invalid_sort_order_rejected() {
+    let context = TestContext::new("invalid_sort_order");
+    assert!(
+        test_sorted_table(&context, "invalid_sort_order", &["non_existent"])
+            .await
+            .is_err()
+    );
+}
+
+#[tokio::test]
+async fn test_alter_table_drop_sort_column() {
+    let context = TestContext::new("alter_table_sort_column");
+    let mut table = test_sorted_table(&context, "alter_table_sort_column", &["age", "id"])
+        .await
+        .unwrap();
+
+    // Dropped column is removed from sort order, and renamed sort column is tracked by its new name.
+    let alter_table_request = AlterTableRequest {
+        dropped_columns: vec!["age".to_string()],
+        renamed_columns: vec![("id".to_string(), "new_id".to_string())],
+        ..Default::default()
+    };
+    let new_metadata = table.alter_table(alter_table_request).unwrap();
+    let sort_columns = new_metadata
+        .config
+        .sort_order
+        .iter()
+        .map(|cur_sort_field| cur_sort_field.source_column.clone())
+        .collect::<Vec<_>>();
+    assert_eq!(sort_columns, vec!["new_id".to_string()]);
+}
+
+#[tokio::test]
+async fn test_alter_table_parquet_column_configs() {
+    let context = TestContext::new("alter_table_parquet_column_configs");
+    let create_table = |table_name: &str, column_configs: &[&str]| {
+        let iceberg_table_config = test_iceberg_table_config(&context, table_name);
+        let mut table_config = test_mooncake_table_config(&context);
+        table_config.row_identity = IdentityProp::Keys(vec![0]);
+        for column in column_configs.iter() {
+            table_config
+                .parquet_writer_config
+                .column_configs
+                .insert(column.to_string(), ParquetColumnConfig::default());
+        }
+        let wal_config = WalConfig::default_wal_config_local(WAL_TEST_TABLE_ID, &context.path());
+        MooncakeTable::new(
+            (*create_test_arrow_schema()).clone(),
+            table_name.to_string(),
+            /*table_id=*/ 1,
+            context.path(),
+            iceberg_table_config.clone(),
+            table_config,
+            WalManager::new(&wal_config),
+            create_test_object_storage_cache(&context.temp_dir),
+            create_test_filesystem_accessor(&iceberg_table_config),
+        )
+    };
+
+    // Column configs on unknown columns are rejected at table creation.
+    assert!(create_table("invalid_column_configs", &["non_existent"])
+        .await
+        .is_err());
+
+    // Column configs on dropped columns are removed, and renamed columns are tracked by their new names.
+    let mut table = create_table("alter_table_parquet_column_configs", &["age", "name"])
+        .await
+        .unwrap();
+    let alter_table_request = AlterTableRequest {
+        dropped_columns: vec!["age".to_string()],
+        renamed_columns: vec![("name".to_string(), "new_name".to_string())],
+        ..Default::default()
+    };
+    let new_metadata = table.alter_table(alter_table_request).unwrap();
+    let columns = new_metadata
+        .config
+        .parquet_writer_config
+        .column_configs
+        .keys()
+        .cloned()
+        .collect::<Vec<_>>();
+    assert_eq!(columns, vec!["new_name".to_string()]);
+}
+
+#[tokio::test]
+async fn test_alter_table_drop_column_with_key_identity() {
+    let context = TestContext::new("alter_table_key_identity");
+    let mut table = test_table(
+        &context,
+        "alter_table_key_identity",
+        IdentityProp::Keys(vec![0, 2]),
+    )
+    .await;
+    let (event_completion_tx, mut event_completion_rx) = mpsc::channel(100);
+    table.register_table_notify(event_completion_tx).await;
+
+    table.append(test_row(1, "A", 20)).unwrap();
+    table.append(test_row(2, "B", 21)).unwrap();
+    table.commit(1);
+    flush_table_and_sync(&mut table, &mut event_completion_rx, 1)
+        .await
+        .unwrap();
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+
+    // Dropping row identity column is rejected.
+    let alter_table_request = AlterTableRequest {
+        dropped_columns: vec!["age".to_string()],
+        ..Default::default()
+    };
+    assert!(table.validate_alter_table(&alter_table_request).is_err());
+
+    // Key indices are remapped after dropping a preceding column.
+    let alter_table_request = AlterTableRequest {
+        dropped_columns: vec!["name".to_string()],
+        ..Default::default()
+    };
+    let new_metadata = table.alter_table(alter_table_request).unwrap();
+    assert_eq!(
+        new_metadata.config.row_identity,
+        IdentityProp::Keys(vec![0, 1])
+    );
+
+    // Rows in data files written before the alter table are still matched by key columns.
+    let row = MoonlinkRow::new(vec![
+        crate::row::RowValue::Int32(1),
+        crate::row::RowValue::Int32(20),
+    ]);
+    table.delete(row, /*lsn=*/ 2).await;
+    table.commit(2);
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+    check_mooncake_table_snapshot(&mut table, /*target_lsn=*/ None, &[2]).await;
+}
+
 #[tokio::test]
 async fn test_alter_table_with_operations() {
     let context = TestContext::new("alter_table");
@@ -3004,3 +3225,75 @@ async fn test_check_mooncake_table_snapshot_function(#[case] identity: IdentityP
 
     Ok(())
 }
+
+#[apply(shared_cases)]
+#[tokio::test]
+async fn test_truncate_table(#[case] identity: IdentityProp) -> Result<()> {
+    let context = TestContext::new("truncate_table");
+    let mut table = test_table(&context, "truncate_table", identity).await;
+    let (event_completion_tx, mut event_completion_rx) = mpsc::channel(100);
+    table.register_table_notify(event_completion_tx).await;
+
+    // Rows persisted in data file.
+    append_commit_flush_create_mooncake_snapshot_for_test(
+        &mut table,
+        &mut event_completion_rx,
+        batch_rows(1, 3),
+        1,
+    )
+    .await?;
+    // Rows committed in memory.
+    append_rows(&mut table, vec![test_row(4, "Row 4", 34)])?;
+    table.commit(2);
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+
+    // Truncate and re-insert an existing key within the same transaction, only rows after truncation are visible.
+    append_rows(&mut table, vec![test_row(5, "Row 5", 35)])?;
+    table.truncate(3);
+    append_rows(&mut table, vec![test_row(1, "Row 1", 31)])?;
+    table.commit(4);
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+
+    {
+        let mut snapshot = table.snapshot.write().await;
+        let SnapshotReadOutput {
+            data_file_paths,
+            puffin_cache_handles,
+            position_deletes,
+            deletion_vectors,
+            ..
+        } = snapshot.request_read().await?;
+        verify_files_and_deletions(
+            get_data_files_for_read(&data_file_paths).as_slice(),
+            get_deletion_puffin_files_for_read(&puffin_cache_handles).as_slice(),
+            position_deletes,
+            deletion_vectors,
+            &[1],
+        )
+        .await;
+    }
+
+    // Rows after truncation could be deleted as usual.
+    table.delete(test_row(1, "Row 1", 31), 5).await;
+    append_rows(&mut table, vec![test_row(6, "Row 6", 36)])?;
+    table.commit(6);
+    create_mooncake_snapshot_for_test(&mut table, &mut event_completion_rx).await;
+
+    let mut snapshot = table.snapshot.write().await;
+    let SnapshotReadOutput {
+        data_file_paths,
+        puffin_cache_handles,
+        position_deletes,
+        deletion_vectors,
+        ..
+    } = snapshot.request_read().await?;
+    verify_files_and_deletions(
+        get_data_files_for_read(&data_file_paths).as_slice(),
+        get_deletion_puffin_files_for_read(&puffin_cache_handles).as_slice(),
+        position_deletes,
+        deletion_vectors,
+        &[6],
+    )
+    .await;
+    Ok(())
+}
@@ -3,6 +3,7 @@ use crate::storage::cache::object_storage::base_cache::{CacheEntry, CacheTrait,
 use crate::storage::index::cache_utils as index_cache_utils;
 use crate::storage::mooncake_table::data_batches::InMemoryBatch;
 use crate::storage::mooncake_table::DiskFileEntry;
+use crate::storage::parquet_utils::get_parquet_reader_options;
 use crate::storage::storage_utils::{ProcessedDeletionRecord, TableUniqueFileId};
 use fastbloom::BloomFilter;
 use more_asserts as ma;
@@ -29,6 +30,8 @@ pub(crate) struct TransactionStreamState {
     ongoing_flush_count: u32,
     /// Commit LSN for this transaction, set when transaction is committed.
     pub(crate) commit_lsn: Option<u64>,
+    /// Whether the transaction truncates the table, which applies to main table at commit time.
+    truncated: bool,
 }
 
 /// Determines the state of a transaction stream.
@@ -130,8 +133,34 @@ impl TransactionStreamState {
             status: TransactionStreamStatus::Pending,
             ongoing_flush_count: 0,
             commit_lsn: None,
+            truncated: false,
         }
     }
+
+    /// Find the non-deleted row for the given record in the stream mem slice.
+    pub(super) async fn find_non_deleted_row(
+        &self,
+        record: &RawDeletionRecord,
+        identity: &IdentityProp,
+    ) -> Option<MoonlinkRow> {
+        if !self.index_bloom_filter.contains(&record.lookup_key) {
+            return None;
+        }
+        self.mem_slice.find_non_deleted_row(record, identity).await
+    }
+
+    /// Get estimated memory size in bytes, including rows not flushed yet, record batches under flush and bloom filter.
+    pub(crate) fn get_estimated_memory_size(&self) -> usize {
+        let record_batches_size = self
+            .new_record_batches
+            .values()
+            .filter_map(|batch| batch.data.as_ref())
+            .map(|batch| batch.get_array_memory_size())
+            .sum::<usize>();
+        self.mem_slice.get_estimated_memory_size()
+            + record_batches_size
+            + self.index_bloom_filter.num_bits() / 8
+    }
 }
 
 pub(crate) const LSN_START_FOR_STREAMING_XACT: u64 = 0xFFFF_FFFF_0000_0000;
@@ -168,6 +197,18 @@ impl MooncakeTable {
             >= self.metadata.config.mem_slice_size
     }
 
+    /// Get pending transaction streams which have unflushed rows.
+    pub fn get_transaction_streams_to_flush(&self) -> Vec<u32> {
+        self.transaction_stream_states
+            .iter()
+            .filter(|(_, stream_state)| {
+                stream_state.status == TransactionStreamStatus::Pending
+                    && !stream_state.mem_slice.is_empty()
+            })
+            .map(|(xact_id, _)| *xact_id)
+            .collect()
+    }
+
     pub fn append_in_stream_batch(&mut self, row: MoonlinkRow, xact_id: u32) -> Result<()> {
         // Record events for replay.
         if let Some(event_replay_tx) = &self.event_replay_tx {
@@ -179,6 +220,7 @@ impl MooncakeTable {
 
         // Perform append operation.
         let lookup_key = self.metadata.config.row_identity.get_lookup_key(&row);
+        self.updated_row_cache.pop(&lookup_key);
         let identity_for_key = self
             .metadata
             .config
@@ -296,6 +338,10 @@ impl MooncakeTable {
                                         file.file_path(),
                                         row_id,
                                         &row_identity,
+                                        &self.metadata.schema,
+                                        get_parquet_reader_options(
+                                            self.metadata.config.get_encryption_key(),
+                                        ),
                                     )
                                     .await
                             {
@@ -331,6 +377,50 @@ impl MooncakeTable {
         }
     }
 
+    /// Truncate the table within a streaming transaction.
+    /// Rows written by the transaction so far are deleted immediately, while the main table is truncated at commit time.
+    pub fn truncate_in_stream_batch(&mut self, xact_id: u32) {
+        // Record events for replay.
+        if let Some(event_replay_tx) = &self.event_replay_tx {
+            let table_event =
+                replay_events::create_truncate_event(/*lsn=*/ None, Some(xact_id));
+            event_replay_tx
+                .send(MooncakeTableEvent::Truncate(table_event))
+                .unwrap();
+        }
+
+        self.updated_row_cache.clear();
+        let stream_state = self.get_or_create_stream_state(xact_id);
+        stream_state.mem_slice.delete_all_rows();
+
+        // Delete rows which have been drained from stream mem slice.
+        let lsn = get_lsn_for_pending_xact(xact_id);
+        for (batch_id, batch) in stream_state.new_record_batches.iter_mut() {
+            for row_idx in 0..batch.get_raw_record_number() as usize {
+                if batch.deletions.delete_row(row_idx) {
+                    stream_state.local_deletions.push(ProcessedDeletionRecord {
+                        pos: RecordLocation::MemoryBatch(*batch_id, row_idx),
+                        lsn,
+                    });
+                }
+            }
+        }
+        for (file, disk_file_entry) in stream_state.flushed_files.iter_mut() {
+            for row_idx in 0..disk_file_entry.num_rows {
+                if disk_file_entry
+                    .committed_deletion_vector
+                    .delete_row(row_idx)
+                {
+                    stream_state.local_deletions.push(ProcessedDeletionRecord {
+                        pos: RecordLocation::DiskFile(file.file_id(), row_idx),
+                        lsn,
+                    });
+                }
+            }
+        }
+        stream_state.truncated = true;
+    }
+
     pub fn abort_in_stream_batch(&mut self, xact_id: u32) {
         // Record events for replay.
         if let Some(event_replay_tx) = &self.event_replay_tx {
@@ -340,6 +430,9 @@ impl MooncakeTable {
                 .unwrap();
         }
 
+        // Rows written by the aborted transaction could have been cached.
+        self.updated_row_cache.clear();
+
         // Record abortion in snapshot task so we can remove any uncommitted deletions
         let stream_state = self
             .transaction_stream_states
@@ -366,9 +459,6 @@ impl MooncakeTable {
         stream_state: &mut TransactionStreamState,
         lsn: Option<u64>,
     ) -> Result<DiskSliceWriter> {
-        let next_file_id = self.next_file_id;
-        self.next_file_id += 1;
-
         // Add filtered record batches to stream state
         // We filter here since in the stream case we delete from the current mem slice directly instead of adding to `new_deletions`
         let (_, mut batches, index) = stream_state.mem_slice.drain()?;
@@ -395,9 +485,13 @@ impl MooncakeTable {
             path,
             batches,
             lsn,
-            next_file_id,
+            self.next_file_id.clone(),
             index,
             self.metadata.config.disk_slice_writer_config.clone(),
+            self.metadata.config.partition_spec.clone(),
+            self.metadata.config.parquet_writer_config.clone(),
+            self.metadata.config.get_encryption_key().cloned(),
+            self.metadata.config.sort_order.clone(),
         );
 
         Ok(disk_slice)
@@ -478,6 +572,7 @@ impl MooncakeTable {
                 cache_handle: None,
                 committed_deletion_vector: BatchDeletionVector::new(file_attrs.row_num),
                 puffin_deletion_blob: None,
+                sort_key_range: file_attrs.sort_key_range.clone(),
             };
             // Add now flushed files to stream state
             assert!(stream_state
@@ -649,6 +744,15 @@ impl MooncakeTable {
             deletion.lsn = lsn - 1;
         }
 
+        // Truncation within the transaction applies to all data committed before it.
+        if stream_state.truncated {
+            self.mem_slice.delete_all_rows();
+            self.next_snapshot_task.set_truncation(TableTruncation {
+                lsn: lsn - 1,
+                mem_slice_position: self.mem_slice.get_commit_check_point(),
+            });
+        }
+
         // Set largest flush LSN.
         if !stream_state.flushed_files.is_empty() {
             self.next_snapshot_task.try_set_largest_flush_lsn(lsn);
@@ -42,6 +42,7 @@ pub(crate) async fn check_deletion_vector_consistency(disk_file_entry: &DiskFile
             .unwrap()
             .puffin_file_cache_handle
             .get_cache_filepath(),
+        /*encryption_key=*/ None,
     )
     .await
     .unwrap();
@@ -1,9 +1,20 @@
+use crate::error::{Error, Result};
 use crate::row::IdentityProp;
 use crate::storage::compaction::compaction_config::DataCompactionConfig;
+use crate::storage::encryption::data_key::DataEncryptionKey;
+use crate::storage::encryption::encryption_config::EncryptionConfig;
+use crate::storage::encryption::table_encryption::TableEncryption;
 use crate::storage::filesystem::accessor_config::ChaosConfig;
 use crate::storage::index::index_merge_config::FileIndexMergeConfig;
+use crate::storage::parquet_utils;
 
+use arrow_schema::Schema;
+use iceberg::spec::{NullOrder, SortDirection, Transform};
+use parquet::arrow::ArrowSchemaConverter;
+use parquet::basic::{BrotliLevel, Compression, GzipLevel, ZstdLevel};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct DiskSliceWriterConfig {
@@ -42,6 +53,113 @@ impl Default for DiskSliceWriterConfig {
     }
 }
 
+/// Partition field for the table, which is mapped to an iceberg partition field.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct PartitionFieldConfig {
+    /// Name of the column to derive partition value from.
+    pub source_column: String,
+
+    /// Partition transform, serialized in iceberg format, for example `identity`, `bucket[16]`, `truncate[4]` and `day`.
+    pub transform: Transform,
+
+    /// Name of the partition field, derived from source column and transform if unassigned.
+    #[serde(default)]
+    pub name: Option<String>,
+}
+
+impl PartitionFieldConfig {
+    pub fn new(source_column: &str, transform: Transform) -> Self {
+        Self {
+            source_column: source_column.to_string(),
+            transform,
+            name: None,
+        }
+    }
+
+    /// Get partition field name, which follows iceberg naming convention by default.
+    pub fn partition_field_name(&self) -> String {
+        if let Some(name) = &self.name {
+            return name.clone();
+        }
+        match self.transform {
+            Transform::Identity => self.source_column.clone(),
+            Transform::Bucket(_) => format!("{}_bucket", self.source_column),
+            Transform::Truncate(_) => format!("{}_trunc", self.source_column),
+            Transform::Year => format!("{}_year", self.source_column),
+            Transform::Month => format!("{}_month", self.source_column),
+            Transform::Day => format!("{}_day", self.source_column),
+            Transform::Hour => format!("{}_hour", self.source_column),
+            _ => format!("{}_{}", self.source_column, self.transform),
+        }
+    }
+
+    /// Partition spec is user provided at table creation, so invalid ones are reported as error.
+    pub fn validate(&self) -> Result<()> {
+        if self.source_column.is_empty() {
+            return Err(Error::invalid_table_config(
+                "Partition source column is empty".to_string(),
+            ));
+        }
+        if !matches!(
+            self.transform,
+            Transform::Identity
+                | Transform::Bucket(_)
+                | Transform::Truncate(_)
+                | Transform::Year
+                | Transform::Month
+                | Transform::Day
+                | Transform::Hour
+        ) {
+            return Err(Error::invalid_table_config(format!(
+                "Unsupported partition transform {} for column {}",
+                self.transform, self.source_column
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Sort field for the table, which is mapped to an iceberg sort field with identity transform.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct SortFieldConfig {
+    /// Name of the column to sort rows by.
+    pub source_column: String,
+
+    /// Sort direction, serialized in iceberg format, `asc` or `desc`.
+    #[serde(default = "SortFieldConfig::default_direction")]
+    pub direction: SortDirection,
+
+    /// Nulls order, serialized in iceberg format, `nulls-first` or `nulls-last`.
+    #[serde(default = "SortFieldConfig::default_null_order")]
+    pub null_order: NullOrder,
+}
+
+impl SortFieldConfig {
+    pub fn new(source_column: &str, direction: SortDirection, null_order: NullOrder) -> Self {
+        Self {
+            source_column: source_column.to_string(),
+            direction,
+            null_order,
+        }
+    }
+
+    pub fn default_direction() -> SortDirection {
+        SortDirection::Ascending
+    }
+    pub fn default_null_order() -> NullOrder {
+        NullOrder::First
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.source_column.is_empty() {
+            return Err(Error::invalid_table_config(
+                "Sort source column is empty".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct IcebergPersistenceConfig {
     /// Number of new data files to trigger an iceberg snapshot.
@@ -117,6 +235,327 @@ impl Default for IcebergPersistenceConfig {
     }
 }
 
+/// Retention policy for iceberg snapshots.
+/// Snapshots beyond the retention are expired, with files only reachable from them deleted; the current snapshot is always retained.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct SnapshotRetentionConfig {
+    /// Max number of latest snapshots to retain, unlimited if unassigned.
+    #[serde(default)]
+    pub max_snapshots_to_keep: Option<usize>,
+
+    /// Max age in milliseconds for snapshots to retain, unlimited if unassigned.
+    #[serde(default)]
+    pub max_snapshot_age_ms: Option<u64>,
+
+    /// Min age in milliseconds for files under table location, which are referenced by no snapshot, to get deleted as orphan files.
+    /// Orphan files are left by failed persistence and file deletion; they're not cleaned up if unassigned.
+    /// Only applies to iceberg tables, since delta lake vacuum already deletes unreferenced files.
+    #[serde(default)]
+    pub orphan_file_grace_period_ms: Option<u64>,
+
+    /// Whether to only report expired snapshots and unreachable files, without updating table metadata or deleting files.
+    #[serde(default)]
+    pub dry_run: bool,
+}
+
+impl SnapshotRetentionConfig {
+    /// Return whether any retention limit or orphan file cleanup is assigned; snapshots are never expired otherwise.
+    pub fn is_enabled(&self) -> bool {
+        self.max_snapshots_to_keep.is_some()
+            || self.max_snapshot_age_ms.is_some()
+            || self.orphan_file_grace_period_ms.is_some()
+    }
+
+    /// Return whether the config is valid, used to reject user inputs before table creation.
+    pub fn is_valid(&self) -> bool {
+        // At least one snapshot should be retained.
+        if self.max_snapshots_to_keep == Some(0) {
+            return false;
+        }
+        // Files being written by ongoing persistence are not referenced yet, which are protected by the grace period.
+        if self.orphan_file_grace_period_ms == Some(0) {
+            return false;
+        }
+        true
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if !self.is_valid() {
+            return Err(Error::invalid_table_config(format!(
+                "Invalid snapshot retention config {self:?}"
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Compression codec for parquet files, named after iceberg `write.parquet.compression-codec` values.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ParquetCompressionCodec {
+    Uncompressed,
+    Snappy,
+    Gzip,
+    Lz4,
+    Zstd,
+    Brotli,
+}
+
+impl ParquetCompressionCodec {
+    /// Get codec name used in iceberg table properties.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ParquetCompressionCodec::Uncompressed => "uncompressed",
+            ParquetCompressionCodec::Snappy => "snappy",
+            ParquetCompressionCodec::Gzip => "gzip",
+            ParquetCompressionCodec::Lz4 => "lz4",
+            ParquetCompressionCodec::Zstd => "zstd",
+            ParquetCompressionCodec::Brotli => "brotli",
+        }
+    }
+
+    /// Get parquet compression with the given level, return None if the level is invalid for the codec.
+    /// Level only applies to gzip, zstd and brotli; the codec default level is used if unassigned.
+    pub(crate) fn to_parquet_compression(&self, level: Option<i32>) -> Option<Compression> {
+        match (self, level) {
+            (ParquetCompressionCodec::Uncompressed, None) => Some(Compression::UNCOMPRESSED),
+            (ParquetCompressionCodec::Snappy, None) => Some(Compression::SNAPPY),
+            (ParquetCompressionCodec::Lz4, None) => Some(Compression::LZ4_RAW),
+            (ParquetCompressionCodec::Gzip, None) => Some(Compression::GZIP(GzipLevel::default())),
+            (ParquetCompressionCodec::Gzip, Some(level)) => {
+                let level = GzipLevel::try_new(u32::try_from(level).ok()?).ok()?;
+                Some(Compression::GZIP(level))
+            }
+            (ParquetCompressionCodec::Zstd, None) => Some(Compression::ZSTD(ZstdLevel::default())),
+            (ParquetCompressionCodec::Zstd, Some(level)) => {
+                Some(Compression::ZSTD(ZstdLevel::try_new(level).ok()?))
+            }
+            (ParquetCompressionCodec::Brotli, None) => {
+                Some(Compression::BROTLI(BrotliLevel::default()))
+            }
+            (ParquetCompressionCodec::Brotli, Some(level)) => {
+                let level = BrotliLevel::try_new(u32::try_from(level).ok()?).ok()?;
+                Some(Compression::BROTLI(level))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Column-level parquet writer overrides, unassigned options fall back to table-level config.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ParquetColumnConfig {
+    /// Compression codec for the column.
+    #[serde(default)]
+    pub compression: Option<ParquetCompressionCodec>,
+
+    /// Compression level for the column, only applies to gzip, zstd and brotli.
+    #[serde(default)]
+    pub compression_level: Option<i32>,
+
+    /// Whether to enable dictionary encoding for the column.
+    #[serde(default)]
+    pub dictionary_enabled: Option<bool>,
+
+    /// Whether to write bloom filter for the column.
+    #[serde(default)]
+    pub bloom_filter_enabled: Option<bool>,
+
+    /// False positive probability for the column bloom filter, within (0, 1).
+    #[serde(default)]
+    pub bloom_filter_fpp: Option<f64>,
+
+    /// Number of distinct values to size the column bloom filter.
+    #[serde(default)]
+    pub bloom_filter_ndv: Option<u64>,
+}
+
+impl ParquetColumnConfig {
+    fn is_valid(&self, table_compression: Option<ParquetCompressionCodec>) -> bool {
+        if self.compression.is_some() || self.compression_level.is_some() {
+            // Column-level compression level applies to table-level codec if codec not overridden.
+            let Some(codec) = self.compression.or(table_compression) else {
+                return false;
+            };
+            if codec
+                .to_parquet_compression(self.compression_level)
+                .is_none()
+            {
+                return false;
+            }
+        }
+        if let Some(fpp) = self.bloom_filter_fpp {
+            if !(fpp > 0.0 && fpp < 1.0) {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Parquet writer options for data files written by flush, compaction and union read temporary files.
+/// Unassigned options keep the defaults for each write path, for example, snappy for flush and zstd for compaction.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct ParquetWriterConfig {
+    /// Compression codec for all columns.
+    #[serde(default)]
+    pub compression: Option<ParquetCompressionCodec>,
+
+    /// Compression level, only applies to gzip, zstd and brotli.
+    #[serde(default)]
+    pub compression_level: Option<i32>,
+
+    /// Max number of rows in a row group.
+    #[serde(default)]
+    pub max_row_group_size: Option<usize>,
+
+    /// Best-effort max size of a data page in bytes.
+    #[serde(default)]
+    pub data_page_size: Option<usize>,
+
+    /// Whether to enable dictionary encoding for all columns.
+    #[serde(default)]
+    pub dictionary_enabled: Option<bool>,
+
+    /// Column-level overrides, keyed by column name; nested columns are separated by `.`, for example, `address.city`.
+    #[serde(default)]
+    pub column_configs: BTreeMap<String, ParquetColumnConfig>,
+}
+
+impl ParquetWriterConfig {
+    /// Return whether the config is valid, used to reject user inputs before table creation.
+    pub fn is_valid(&self) -> bool {
+        if let Some(codec) = self.compression {
+            if codec
+                .to_parquet_compression(self.compression_level)
+                .is_none()
+            {
+                return false;
+            }
+        } else if self.compression_level.is_some() {
+            return false;
+        }
+        if self.max_row_group_size == Some(0) || self.data_page_size == Some(0) {
+            return false;
+        }
+        self.column_configs.iter().all(|(column, column_config)| {
+            !column.is_empty() && column_config.is_valid(self.compression)
+        })
+    }
+
+    pub fn validate(&self) {
+        assert!(self.is_valid(), "Invalid parquet writer config {self:?}");
+    }
+
+    /// Validate column-level overrides refer to leaf columns in the given schema, otherwise they're silently ignored by parquet writer.
+    pub(crate) fn validate_column_configs(&self, schema: &Schema) -> Result<()> {
+        if self.column_configs.is_empty() {
+            return Ok(());
+        }
+        let parquet_schema = ArrowSchemaConverter::new().convert(schema)?;
+        for column in self.column_configs.keys() {
+            let column_path = parquet_utils::get_column_path(column);
+            if !parquet_schema
+                .columns()
+                .iter()
+                .any(|cur_column| *cur_column.path() == column_path)
+            {
+                return Err(Error::invalid_table_config(format!(
+                    "Parquet column config {column} doesn't refer to a leaf column in schema"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Column filter on the source table, which decides columns mirrored into mooncake table.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColumnFilter {
+    /// Only mirror the given columns.
+    Include(Vec<String>),
+    /// Mirror all columns except the given ones.
+    Exclude(Vec<String>),
+}
+
+impl ColumnFilter {
+    /// Return whether the column is mirrored.
+    pub fn contains(&self, column: &str) -> bool {
+        match self {
+            ColumnFilter::Include(columns) => columns.iter().any(|cur| cur == column),
+            ColumnFilter::Exclude(columns) => !columns.iter().any(|cur| cur == column),
+        }
+    }
+
+    fn is_valid(&self) -> bool {
+        match self {
+            ColumnFilter::Include(columns) => {
+                !columns.is_empty() && columns.iter().all(|column| !column.is_empty())
+            }
+            ColumnFilter::Exclude(columns) => columns.iter().all(|column| !column.is_empty()),
+        }
+    }
+}
+
+/// Operator of a row filter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RowFilterOperator {
+    /// Column value equals any of the filter values.
+    In,
+    /// Column value equals none of the filter values.
+    NotIn,
+}
+
+/// Row filter on the source table, only rows whose column value satisfies the filter are mirrored.
+/// Same as SQL, rows with null column value never satisfy the filter.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct RowFilter {
+    /// Name of the column to filter on.
+    pub column: String,
+    /// Filter operator.
+    pub operator: RowFilterOperator,
+    /// Filter values, in postgres text format.
+    pub values: Vec<String>,
+}
+
+impl RowFilter {
+    fn is_valid(&self) -> bool {
+        !self.column.is_empty() && !self.values.is_empty()
+    }
+}
+
+/// Column and row filters applied on the source table.
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize)]
+pub struct SourceFilterConfig {
+    /// Column filter, all columns are mirrored if unassigned.
+    #[serde(default)]
+    pub column_filter: Option<ColumnFilter>,
+    /// Row filters, rows are mirrored only if they satisfy all filters.
+    #[serde(default)]
+    pub row_filters: Vec<RowFilter>,
+}
+
+impl SourceFilterConfig {
+    /// Return whether the config is valid, used to reject user inputs before table creation.
+    pub fn is_valid(&self) -> bool {
+        if let Some(column_filter) = &self.column_filter {
+            if !column_filter.is_valid() {
+                return false;
+            }
+        }
+        self.row_filters
+            .iter()
+            .all(|row_filter| row_filter.is_valid())
+    }
+
+    /// Return whether there's no filter.
+    pub fn is_empty(&self) -> bool {
+        self.column_filter.is_none() && self.row_filters.is_empty()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct MooncakeTableConfig {
     /// Number of batch records which decides when to flush records from MemSlice to disk.
@@ -139,6 +578,33 @@ pub struct MooncakeTableConfig {
     pub append_only: bool,
     /// Identity for table rows.
     pub row_identity: IdentityProp,
+    /// Partition spec for the table, empty for unpartitioned tables.
+    /// Each data file only contains rows within one partition.
+    #[serde(default)]
+    pub partition_spec: Vec<PartitionFieldConfig>,
+    /// Sort order for the table, empty for unsorted tables.
+    /// Rows within each data file written by flush and compaction are sorted by it.
+    #[serde(default)]
+    pub sort_order: Vec<SortFieldConfig>,
+    /// Retention policy for iceberg snapshots.
+    #[serde(default)]
+    pub snapshot_retention_config: SnapshotRetentionConfig,
+    /// Parquet writer options for data files.
+    #[serde(default)]
+    pub parquet_writer_config: ParquetWriterConfig,
+    /// Client-side encryption for data files, index files and WAL, disabled if unassigned.
+    #[serde(default)]
+    pub encryption_config: Option<EncryptionConfig>,
+    /// Table encryption to encrypt data files, index block files and puffin blobs with, resolved at table initialization if encryption is enabled.
+    /// It's never persisted; only the wrapped data key is.
+    #[serde(skip)]
+    pub table_encryption: Option<Arc<TableEncryption>>,
+    /// Whether to alter postgres source table to REPLICA IDENTITY FULL, if it has no usable replica identity key.
+    #[serde(default)]
+    pub replica_identity_full: bool,
+    /// Column and row filters on the source table, which only apply to postgres sources.
+    #[serde(default)]
+    pub source_filter_config: SourceFilterConfig,
 }
 
 impl Default for MooncakeTableConfig {
@@ -176,6 +642,14 @@ impl MooncakeTableConfig {
             file_index_config: FileIndexMergeConfig::default(),
             append_only: false,
             row_identity: IdentityProp::default(),
+            partition_spec: Vec::new(),
+            sort_order: Vec::new(),
+            snapshot_retention_config: SnapshotRetentionConfig::default(),
+            parquet_writer_config: ParquetWriterConfig::default(),
+            encryption_config: None,
+            table_encryption: None,
+            replica_identity_full: false,
+            source_filter_config: SourceFilterConfig::default(),
             temp_files_directory,
         }
     }
@@ -195,13 +669,44 @@ impl MooncakeTableConfig {
     }
 
     // Validation util function.
-    pub fn validate(&self) {
+    pub fn validate(&self) -> Result<()> {
         self.disk_slice_writer_config.validate();
         self.file_index_config.validate();
         self.data_compaction_config.validate();
+        self.snapshot_retention_config.validate()?;
+        self.parquet_writer_config.validate();
+
+        let mut partition_field_names = HashSet::new();
+        for cur_partition_field in self.partition_spec.iter() {
+            cur_partition_field.validate()?;
+            if !partition_field_names.insert(cur_partition_field.partition_field_name()) {
+                return Err(Error::invalid_table_config(format!(
+                    "Duplicate partition field {}",
+                    cur_partition_field.partition_field_name()
+                )));
+            }
+        }
+
+        let mut sort_columns = HashSet::new();
+        for cur_sort_field in self.sort_order.iter() {
+            cur_sort_field.validate()?;
+            if !sort_columns.insert(cur_sort_field.source_column.as_str()) {
+                return Err(Error::invalid_table_config(format!(
+                    "Duplicate sort column {}",
+                    cur_sort_field.source_column
+                )));
+            }
+        }
+        Ok(())
     }
 
     // Accessor functions.
+    /// Get the data key to encrypt table files with, if the table is encrypted.
+    pub(crate) fn get_encryption_key(&self) -> Option<&DataEncryptionKey> {
+        self.table_encryption
+            .as_ref()
+            .map(|table_encryption| table_encryption.get_data_key())
+    }
     pub fn batch_size(&self) -> usize {
         self.batch_size
     }
@@ -1,20 +1,375 @@
 /// This module contains parquet related constants and utils.
-use parquet::basic::{Compression, ZstdLevel};
-use parquet::file::properties::WriterProperties;
+use crate::error::{Error, Result};
+use crate::storage::encryption::data_key::DataEncryptionKey;
+use crate::storage::mooncake_table_config::{ParquetCompressionCodec, ParquetWriterConfig};
+use parquet::arrow::arrow_reader::ArrowReaderOptions;
+use parquet::basic::{Compression, Encoding, ZstdLevel};
+use parquet::file::properties::{WriterProperties, WriterPropertiesBuilder};
+use parquet::format::SortingColumn;
+use parquet::schema::types::ColumnPath;
 
 /// Default compression.
 const DEFAULT_COMPRESSION: Compression = parquet::basic::Compression::SNAPPY;
 
-/// Get the parquet write properties for disk slices.
+/// Default compression level for compacted files.
+const DEFAULT_COMPACTION_ZSTD_LEVEL: i32 = 4;
+
+/// Get parquet column path for the given column name, where nested columns are separated by `.`.
+pub(crate) fn get_column_path(column: &str) -> ColumnPath {
+    ColumnPath::new(column.split('.').map(str::to_string).collect())
+}
+
+/// Get parquet compression for the given codec and level.
+fn get_parquet_compression(
+    codec: ParquetCompressionCodec,
+    compression_level: Option<i32>,
+) -> Result<Compression> {
+    codec
+        .to_parquet_compression(compression_level)
+        .ok_or_else(|| {
+            Error::invalid_table_config(format!(
+                "Invalid compression level {compression_level:?} for parquet codec {codec:?}"
+            ))
+        })
+}
+
+/// Apply user-provided parquet writer config on top of the given builder.
+/// Options unassigned in the config keep builder values.
+fn apply_parquet_writer_config(
+    mut builder: WriterPropertiesBuilder,
+    config: &ParquetWriterConfig,
+    encryption_key: Option<&DataEncryptionKey>,
+) -> Result<WriterPropertiesBuilder> {
+    if let Some(codec) = config.compression {
+        builder =
+            builder.set_compression(get_parquet_compression(codec, config.compression_level)?);
+    }
+    if let Some(max_row_group_size) = config.max_row_group_size {
+        builder = builder.set_max_row_group_size(max_row_group_size);
+    }
+    if let Some(data_page_size) = config.data_page_size {
+        builder = builder.set_data_page_size_limit(data_page_size);
+    }
+    if let Some(dictionary_enabled) = config.dictionary_enabled {
+        builder = builder.set_dictionary_enabled(dictionary_enabled);
+    }
+
+    for (column, column_config) in config.column_configs.iter() {
+        let column_path = get_column_path(column);
+        if column_config.compression.is_some() || column_config.compression_level.is_some() {
+            let Some(codec) = column_config.compression.or(config.compression) else {
+                return Err(Error::invalid_table_config(format!(
+                    "Compression level for parquet column {column} requires a codec"
+                )));
+            };
+            builder = builder.set_column_compression(
+                column_path.clone(),
+                get_parquet_compression(codec, column_config.compression_level)?,
+            );
+        }
+        if let Some(dictionary_enabled) = column_config.dictionary_enabled {
+            builder =
+                builder.set_column_dictionary_enabled(column_path.clone(), dictionary_enabled);
+        }
+        if let Some(bloom_filter_enabled) = column_config.bloom_filter_enabled {
+            builder =
+                builder.set_column_bloom_filter_enabled(column_path.clone(), bloom_filter_enabled);
+        }
+        if let Some(fpp) = column_config.bloom_filter_fpp {
+            builder = builder.set_column_bloom_filter_fpp(column_path.clone(), fpp);
+        }
+        if let Some(ndv) = column_config.bloom_filter_ndv {
+            builder = builder.set_column_bloom_filter_ndv(column_path, ndv);
+        }
+    }
+    if let Some(encryption_key) = encryption_key {
+        builder = builder
+            .with_file_encryption_properties(encryption_key.get_file_encryption_properties());
+    }
+    Ok(builder)
+}
+
+/// Get the parquet reader options for data files, which are decrypted with the given data key if the table is encrypted.
+pub(crate) fn get_parquet_reader_options(
+    encryption_key: Option<&DataEncryptionKey>,
+) -> ArrowReaderOptions {
+    let options = ArrowReaderOptions::new();
+    match encryption_key {
+        Some(encryption_key) => {
+            options.with_file_decryption_properties(encryption_key.get_file_decryption_properties())
+        }
+        None => options,
+    }
+}
+
+/// Get the parquet write properties for disk slices, without table-level config.
 pub fn get_default_parquet_properties() -> WriterProperties {
     WriterProperties::builder()
         .set_compression(DEFAULT_COMPRESSION)
         .build()
 }
 
+/// Get the parquet write properties for disk slices.
+/// [`sorting_columns`] is assigned if rows are sorted by table sort order.
+pub(crate) fn get_disk_slice_parquet_properties(
+    config: &ParquetWriterConfig,
+    encryption_key: Option<&DataEncryptionKey>,
+    sorting_columns: Option<Vec<SortingColumn>>,
+) -> Result<WriterProperties> {
+    let builder = WriterProperties::builder()
+        .set_compression(DEFAULT_COMPRESSION)
+        .set_sorting_columns(sorting_columns);
+    Ok(apply_parquet_writer_config(builder, config, encryption_key)?.build())
+}
+
 /// Get the parquet write properties for compacted files.
-pub fn get_compaction_parquet_properties() -> WriterProperties {
-    WriterProperties::builder()
-        .set_compression(Compression::ZSTD(ZstdLevel::try_new(4).unwrap()))
+/// [`sorting_columns`] is assigned if rows are sorted by table sort order.
+pub(crate) fn get_compaction_parquet_properties(
+    config: &ParquetWriterConfig,
+    encryption_key: Option<&DataEncryptionKey>,
+    sorting_columns: Option<Vec<SortingColumn>>,
+) -> Result<WriterProperties> {
+    let builder = WriterProperties::builder()
+        .set_compression(Compression::ZSTD(
+            ZstdLevel::try_new(DEFAULT_COMPACTION_ZSTD_LEVEL).unwrap(),
+        ))
+        .set_sorting_columns(sorting_columns);
+    Ok(apply_parquet_writer_config(builder, config, encryption_key)?.build())
+}
+
+/// Get the parquet write properties for union read temporary files, which are uncompressed and plain encoded by default for read performance.
+pub(crate) fn get_temp_file_parquet_properties(
+    config: &ParquetWriterConfig,
+    encryption_key: Option<&DataEncryptionKey>,
+) -> Result<WriterProperties> {
+    let builder = WriterProperties::builder()
+        .set_compression(Compression::UNCOMPRESSED)
+        .set_dictionary_enabled(false)
+        .set_encoding(Encoding::PLAIN);
+    Ok(apply_parquet_writer_config(builder, config, encryption_key)?.build())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::mooncake_table_config::{ParquetColumnConfig, ParquetCompressionCodec};
+
+    #[test]
+    fn test_parquet_properties_default() {
+        let config = ParquetWriterConfig::default();
+        let column_path = ColumnPath::from("id");
+
+        let properties = get_disk_slice_parquet_properties(
+            &config, /*encryption_key=*/ None, /*sorting_columns=*/ None,
+        )
+        .unwrap();
+        assert_eq!(properties.compression(&column_path), Compression::SNAPPY);
+        let properties = get_compaction_parquet_properties(
+            &config, /*encryption_key=*/ None, /*sorting_columns=*/ None,
+        )
+        .unwrap();
+        assert_eq!(
+            properties.compression(&column_path),
+            Compression::ZSTD(ZstdLevel::try_new(4).unwrap())
+        );
+        let properties =
+            get_temp_file_parquet_properties(&config, /*encryption_key=*/ None).unwrap();
+        assert_eq!(
+            properties.compression(&column_path),
+            Compression::UNCOMPRESSED
+        );
+        assert!(!properties.dictionary_enabled(&column_path));
+        assert!(properties.bloom_filter_properties(&column_path).is_none());
+    }
+
+    #[test]
+    fn test_parquet_properties_with_config() {
+        let mut config = ParquetWriterConfig {
+            compression: Some(ParquetCompressionCodec::Zstd),
+            compression_level: Some(9),
+            max_row_group_size: Some(1000),
+            data_page_size: Some(4096),
+            dictionary_enabled: Some(false),
+            ..Default::default()
+        };
+        config.column_configs.insert(
+            "id".to_string(),
+            ParquetColumnConfig {
+                compression: Some(ParquetCompressionCodec::Lz4),
+                dictionary_enabled: Some(true),
+                bloom_filter_enabled: Some(true),
+                bloom_filter_fpp: Some(0.01),
+                bloom_filter_ndv: Some(1000),
+                ..Default::default()
+            },
+        );
+        config.validate();
+
+        let id_path = ColumnPath::from("id");
+        let name_path = ColumnPath::from("name");
+        let zstd_9 = Compression::ZSTD(ZstdLevel::try_new(9).unwrap());
+        for properties in [
+            get_disk_slice_parquet_properties(
+                &config, /*encryption_key=*/ None, /*sorting_columns=*/ None,
+            ),
+            get_compaction_parquet_properties(
+                &config, /*encryption_key=*/ None, /*sorting_columns=*/ None,
+            ),
+            get_temp_file_parquet_properties(&config, /*encryption_key=*/ None),
+        ] {
+            let properties = properties.unwrap();
+            assert_eq!(properties.max_row_group_size(), 1000);
+            assert_eq!(properties.data_page_size_limit(), 4096);
+
+            // Table-level options.
+            assert_eq!(properties.compression(&name_path), zstd_9);
+            assert!(!properties.dictionary_enabled(&name_path));
+            assert!(properties.bloom_filter_properties(&name_path).is_none());
+
+            // Column-level overrides.
+            assert_eq!(properties.compression(&id_path), Compression::LZ4_RAW);
+            assert!(properties.dictionary_enabled(&id_path));
+            let bloom_filter_properties = properties.bloom_filter_properties(&id_path).unwrap();
+            assert_eq!(bloom_filter_properties.fpp, 0.01);
+            assert_eq!(bloom_filter_properties.ndv, 1000);
+        }
+    }
+
+    #[test]
+    fn test_parquet_nested_column_config() {
+        let schema = arrow_schema::Schema::new(vec![
+            arrow_schema::Field::new(
+                "id",
+                arrow_schema::DataType::Int32,
+                /*nullable=*/ false,
+            ),
+            arrow_schema::Field::new_struct(
+                "address",
+                vec![arrow_schema::Field::new(
+                    "city",
+                    arrow_schema::DataType::Utf8,
+                    /*nullable=*/ true,
+                )],
+                /*nullable=*/ true,
+            ),
+        ]);
+        let mut config = ParquetWriterConfig::default();
+        config.column_configs.insert(
+            "address.city".to_string(),
+            ParquetColumnConfig {
+                bloom_filter_enabled: Some(true),
+                ..Default::default()
+            },
+        );
+        config.validate_column_configs(&schema).unwrap();
+        let properties = get_disk_slice_parquet_properties(
+            &config, /*encryption_key=*/ None, /*sorting_columns=*/ None,
+        )
+        .unwrap();
+        let city_path = ColumnPath::new(vec!["address".to_string(), "city".to_string()]);
+        assert!(properties.bloom_filter_properties(&city_path).is_some());
+
+        // Column configs should refer to existing leaf columns.
+        for column in ["name", "address", "address.zip"] {
+            let mut config = ParquetWriterConfig::default();
+            config
+                .column_configs
+                .insert(column.to_string(), ParquetColumnConfig::default());
+            assert!(config.validate_column_configs(&schema).is_err());
+        }
+    }
+
+    #[test]
+    fn test_parquet_writer_config_validity() {
+        let mut config = ParquetWriterConfig {
+            compression: Some(ParquetCompressionCodec::Snappy),
+            compression_level: Some(3),
+            ..Default::default()
+        };
+        // Snappy doesn't take compression level.
+        assert!(!config.is_valid());
+        config.compression = Some(ParquetCompressionCodec::Zstd);
+        assert!(config.is_valid());
+        config.compression_level = Some(100);
+        assert!(!config.is_valid());
+        config.compression_level = None;
+
+        // Column-level compression level requires a codec.
+        config.compression = None;
+        config.column_configs.insert(
+            "id".to_string(),
+            ParquetColumnConfig {
+                compression_level: Some(3),
+                ..Default::default()
+            },
+        );
+        assert!(!config.is_valid());
+        config.compression = Some(ParquetCompressionCodec::Gzip);
+        assert!(config.is_valid());
+
+        // Bloom filter false positive probability should be within (0, 1).
+        config
+            .column_configs
+            .get_mut("id")
+            .unwrap()
+            .bloom_filter_fpp = Some(1.0);
+        assert!(!config.is_valid());
+    }
+
+    #[tokio::test]
+    async fn test_parquet_encryption() {
+        use crate::storage::encryption::test_utils::create_test_table_encryption;
+        use arrow_array::{Int32Array, RecordBatch};
+        use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+        use parquet::arrow::ArrowWriter;
+        use std::sync::Arc;
+
+        let schema = Arc::new(arrow_schema::Schema::new(vec![arrow_schema::Field::new(
+            "id",
+            arrow_schema::DataType::Int32,
+            /*nullable=*/ false,
+        )]));
+        let record_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![1, 2, 3]))],
+        )
+        .unwrap();
+        let temp_dir = tempfile::tempdir().unwrap();
+        let table_encryption = create_test_table_encryption(&temp_dir).await;
+        let encryption_key = Some(table_encryption.get_data_key());
+
+        let mut buffer = vec![];
+        let properties = get_disk_slice_parquet_properties(
+            &ParquetWriterConfig::default(),
+            encryption_key,
+            /*sorting_columns=*/ None,
+        )
+        .unwrap();
+        let mut writer = ArrowWriter::try_new(&mut buffer, schema, Some(properties)).unwrap();
+        writer.write(&record_batch).unwrap();
+        writer.close().unwrap();
+        let buffer = bytes::Bytes::from(buffer);
+
+        // Data file is only readable with the data key.
+        let reader = ParquetRecordBatchReaderBuilder::try_new_with_options(
+            buffer.clone(),
+            get_parquet_reader_options(encryption_key),
+        )
+        .unwrap()
         .build()
+        .unwrap();
+        let record_batches = reader.collect::<std::result::Result<Vec<_>, _>>().unwrap();
+        assert_eq!(record_batches, vec![record_batch]);
+        let other_temp_dir = tempfile::tempdir().unwrap();
+        let other_table_encryption = create_test_table_encryption(&other_temp_dir).await;
+        assert!(ParquetRecordBatchReaderBuilder::try_new_with_options(
+            buffer,
+            get_parquet_reader_options(Some(other_table_encryption.get_data_key())),
+        )
+        .and_then(|builder| builder
+            .build()?
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into))
+        .is_err());
+    }
 }
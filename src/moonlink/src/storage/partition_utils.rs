@@ -0,0 +1,204 @@
+/// This module contains util functions to split rows by table partition spec.
+use crate::error::Result;
+use crate::row::{MoonlinkRow, RowValue};
+use crate::storage::mooncake_table_config::PartitionFieldConfig;
+
+use arrow::compute::take_record_batch;
+use arrow::row::{OwnedRow, RowConverter, Rows, SortField};
+use arrow_array::{RecordBatch, UInt32Array};
+use arrow_schema::Schema;
+use iceberg::transform::{create_transform_function, BoxedTransformFunction};
+use iceberg::{Error as IcebergError, ErrorKind};
+use std::collections::HashMap;
+
+/// Partition key for a row, which is the row format of all transformed partition source columns.
+pub(crate) type PartitionKey = OwnedRow;
+
+/// Partition source column values for rows within one partition, ordered by partition spec.
+/// All rows within a partition share the same transformed values, so they're taken from any row.
+pub(crate) type PartitionSourceValues = Vec<RowValue>;
+
+/// Computes partition keys for record batches, with the same transform functions as iceberg.
+pub(crate) struct PartitionKeyGenerator {
+    /// Source column index and transform function for each partition field.
+    partition_fields: Vec<(usize, BoxedTransformFunction)>,
+    /// Row converter for transformed columns, initialized with the first record batch.
+    row_converter: Option<RowConverter>,
+}
+
+impl PartitionKeyGenerator {
+    /// Return `None` if the table is unpartitioned.
+    pub(crate) fn try_new(
+        schema: &Schema,
+        partition_spec: &[PartitionFieldConfig],
+    ) -> Result<Option<Self>> {
+        if partition_spec.is_empty() {
+            return Ok(None);
+        }
+        let mut partition_fields = Vec::with_capacity(partition_spec.len());
+        for cur_partition_field in partition_spec.iter() {
+            let (column_idx, _) = schema
+                .column_with_name(&cur_partition_field.source_column)
+                .ok_or_else(|| {
+                    IcebergError::new(
+                        ErrorKind::DataInvalid,
+                        format!(
+                            "Partition source column {} doesn't exist in schema",
+                            cur_partition_field.source_column
+                        ),
+                    )
+                })?;
+            let transform_function = create_transform_function(&cur_partition_field.transform)?;
+            partition_fields.push((column_idx, transform_function));
+        }
+        Ok(Some(Self {
+            partition_fields,
+            row_converter: None,
+        }))
+    }
+
+    /// Transform partition source columns, and convert them into row format.
+    fn get_partition_rows(&mut self, record_batch: &RecordBatch) -> Result<Rows> {
+        let mut transformed_columns = Vec::with_capacity(self.partition_fields.len());
+        for (column_idx, transform_function) in self.partition_fields.iter() {
+            let transformed_column =
+                transform_function.transform(record_batch.column(*column_idx).clone())?;
+            transformed_columns.push(transformed_column);
+        }
+        if self.row_converter.is_none() {
+            let sort_fields = transformed_columns
+                .iter()
+                .map(|cur_column| SortField::new(cur_column.data_type().clone()))
+                .collect::<Vec<_>>();
+            self.row_converter = Some(RowConverter::new(sort_fields)?);
+        }
+        let rows = self
+            .row_converter
+            .as_ref()
+            .unwrap()
+            .convert_columns(&transformed_columns)?;
+        Ok(rows)
+    }
+
+    /// Get partition key for the first row of the given record batch.
+    /// Precondition: the given record batch is not empty.
+    pub(crate) fn get_partition_key(&mut self, record_batch: &RecordBatch) -> Result<PartitionKey> {
+        assert!(record_batch.num_rows() > 0);
+        let rows = self.get_partition_rows(&record_batch.slice(0, 1))?;
+        Ok(rows.row(0).owned())
+    }
+
+    /// Get partition source column values for the first row of the given record batch, which are recorded for data files written for the partition.
+    /// Precondition: the given record batch is not empty.
+    pub(crate) fn get_partition_source_values(
+        &self,
+        record_batch: &RecordBatch,
+    ) -> Result<PartitionSourceValues> {
+        assert!(record_batch.num_rows() > 0);
+        let column_indices = self
+            .partition_fields
+            .iter()
+            .map(|(column_idx, _)| *column_idx)
+            .collect::<Vec<_>>();
+        let partition_source_batch = record_batch.slice(0, 1).project(&column_indices)?;
+        let mut rows = MoonlinkRow::from_record_batch(&partition_source_batch);
+        Ok(rows.pop().unwrap().values)
+    }
+
+    /// Split the given record batch by partition.
+    /// Return partition key, rows within the partition and their row indices in the given record batch, ordered by first appearance.
+    pub(crate) fn split_by_partition(
+        &mut self,
+        record_batch: &RecordBatch,
+    ) -> Result<Vec<(PartitionKey, RecordBatch, Vec<usize>)>> {
+        let rows = self.get_partition_rows(record_batch)?;
+        let mut partition_idx_by_row = HashMap::new();
+        let mut partitions: Vec<(PartitionKey, Vec<usize>)> = vec![];
+        for row_idx in 0..record_batch.num_rows() {
+            let cur_row = rows.row(row_idx);
+            let partition_idx = *partition_idx_by_row.entry(cur_row).or_insert_with(|| {
+                partitions.push((cur_row.owned(), vec![]));
+                partitions.len() - 1
+            });
+            partitions[partition_idx].1.push(row_idx);
+        }
+
+        let mut partitioned_batches = Vec::with_capacity(partitions.len());
+        for (partition_key, row_indices) in partitions.into_iter() {
+            // Avoid copy if all rows fall into the same partition.
+            let partitioned_batch = if row_indices.len() == record_batch.num_rows() {
+                record_batch.clone()
+            } else {
+                let indices =
+                    UInt32Array::from_iter_values(row_indices.iter().map(|idx| *idx as u32));
+                take_record_batch(record_batch, &indices)?
+            };
+            partitioned_batches.push((partition_key, partitioned_batch, row_indices));
+        }
+        Ok(partitioned_batches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_array::{Array, Int32Array, StringArray};
+    use arrow_schema::{DataType, Field};
+    use iceberg::spec::Transform;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_split_by_partition() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("tenant", DataType::Utf8, true),
+        ]));
+        let record_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    Some("b"),
+                    Some("a"),
+                    None,
+                    Some("b"),
+                ])),
+            ],
+        )
+        .unwrap();
+        let partition_spec = vec![PartitionFieldConfig::new("tenant", Transform::Identity)];
+        let mut generator = PartitionKeyGenerator::try_new(&schema, &partition_spec)
+            .unwrap()
+            .unwrap();
+
+        let partitions = generator.split_by_partition(&record_batch).unwrap();
+        let row_indices = partitions
+            .iter()
+            .map(|(_, _, row_indices)| row_indices.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(row_indices, vec![vec![0, 2], vec![1, 4], vec![3]]);
+        let (_, first_partition_batch, _) = &partitions[0];
+        assert_eq!(
+            first_partition_batch.column(0).as_ref(),
+            &Int32Array::from(vec![1, 3]) as &dyn Array
+        );
+
+        // Partition key for a single row is the same as the one for its partition.
+        let partition_key = generator
+            .get_partition_key(&record_batch.slice(4, 1))
+            .unwrap();
+        assert_eq!(partition_key, partitions[1].0);
+    }
+
+    #[test]
+    fn test_unpartitioned_or_invalid_spec() {
+        let schema = Schema::new(vec![Field::new("id", DataType::Int32, false)]);
+        assert!(PartitionKeyGenerator::try_new(&schema, &[])
+            .unwrap()
+            .is_none());
+        let partition_spec = vec![PartitionFieldConfig::new("ts", Transform::Day)];
+        assert!(PartitionKeyGenerator::try_new(&schema, &partition_spec).is_err());
+    }
+}
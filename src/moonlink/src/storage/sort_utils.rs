@@ -0,0 +1,795 @@
+/// This module contains util functions to sort rows by table sort order, and track sort key ranges for data files.
+use crate::error::Result;
+use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
+use crate::storage::mooncake_table::schema_evolution;
+use crate::storage::mooncake_table_config::{
+    MooncakeTableConfig, PartitionFieldConfig, SortFieldConfig,
+};
+use crate::storage::partition_utils::{PartitionKey, PartitionKeyGenerator};
+
+use arrow::compute::kernels::zip::zip;
+use arrow::compute::{cast, concat, take_record_batch};
+use arrow::row::{OwnedRow, RowConverter, Rows, SortField};
+use arrow_array::{new_null_array, Array, BooleanArray, RecordBatch, UInt32Array};
+use arrow_schema::{DataType, Schema, SchemaRef, SortOptions};
+use bytes::Bytes;
+use futures::future::{BoxFuture, FutureExt};
+use iceberg::spec::{NullOrder, SortDirection};
+use iceberg::{Error as IcebergError, ErrorKind};
+use parquet::arrow::arrow_reader::statistics::StatisticsConverter;
+use parquet::arrow::arrow_reader::{ArrowReaderOptions, ParquetRecordBatchReaderBuilder};
+use parquet::arrow::async_reader::MetadataFetch;
+use parquet::arrow::{parquet_to_arrow_schema, ArrowSchemaConverter, ProjectionMask};
+use parquet::encryption::decrypt::FileDecryptionProperties;
+use parquet::errors::{ParquetError, Result as ParquetResult};
+use parquet::file::metadata::{ParquetMetaData, ParquetMetaDataReader};
+use parquet::format::SortingColumn;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// Number of sorted rows to write into parquet writer at a time, so data files could be rotated at a reasonable granularity.
+pub(crate) const SORTED_WRITE_BATCH_SIZE: usize = 4096;
+
+/// Sort key for a row, which is the row format of all sort columns with their sort options, so keys are byte-wise comparable.
+pub(crate) type SortKey = OwnedRow;
+
+/// Range of sort keys within one data file, both ends inclusive.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct SortKeyRange {
+    /// Partition key for rows in the data file, `None` for unpartitioned tables.
+    pub(crate) partition_key: Option<PartitionKey>,
+    pub(crate) min: SortKey,
+    pub(crate) max: SortKey,
+}
+
+impl SortKeyRange {
+    /// Return whether the two key ranges overlap, key ranges for different partitions never overlap.
+    pub(crate) fn overlaps(&self, other: &SortKeyRange) -> bool {
+        self.partition_key == other.partition_key && self.min <= other.max && other.min <= self.max
+    }
+
+    /// Extend the key range to cover the other one within the same partition.
+    fn merge(self, other: SortKeyRange) -> SortKeyRange {
+        assert_eq!(self.partition_key, other.partition_key);
+        SortKeyRange {
+            partition_key: self.partition_key,
+            min: std::cmp::min(self.min, other.min),
+            max: std::cmp::max(self.max, other.max),
+        }
+    }
+}
+
+/// Get data types for sort columns and partition source columns, which decide how sort key ranges are encoded.
+/// Sort key ranges encoded with different column types are not comparable.
+pub(crate) fn get_sort_key_column_types(
+    schema: &Schema,
+    config: &MooncakeTableConfig,
+) -> Vec<Option<DataType>> {
+    config
+        .sort_order
+        .iter()
+        .map(|cur_sort_field| &cur_sort_field.source_column)
+        .chain(
+            config
+                .partition_spec
+                .iter()
+                .map(|cur_partition_field| &cur_partition_field.source_column),
+        )
+        .map(|column| {
+            schema
+                .field_with_name(column)
+                .ok()
+                .map(|field| field.data_type().clone())
+        })
+        .collect()
+}
+
+/// Get schema for sort columns and partition source columns, in table schema order.
+fn get_key_schema(
+    schema: &Schema,
+    partition_spec: &[PartitionFieldConfig],
+    sort_order: &[SortFieldConfig],
+) -> SchemaRef {
+    Arc::new(Schema::new(
+        schema
+            .fields()
+            .iter()
+            .filter(|field| {
+                sort_order
+                    .iter()
+                    .any(|cur_sort_field| &cur_sort_field.source_column == field.name())
+                    || partition_spec.iter().any(|cur_partition_field| {
+                        &cur_partition_field.source_column == field.name()
+                    })
+            })
+            .cloned()
+            .collect::<Vec<_>>(),
+    ))
+}
+
+/// Get column index in the data file for each key column.
+/// Columns are matched by field id, since they could have been renamed or promoted after the data file was written.
+fn get_file_column_indices(
+    key_schema: &Schema,
+    file_schema: &Schema,
+    filepath: &str,
+) -> Result<Vec<usize>> {
+    let mut file_column_indices = Vec::with_capacity(key_schema.fields().len());
+    for field in key_schema.fields().iter() {
+        let field_id = schema_evolution::get_field_id(field);
+        let file_column_idx = file_schema
+            .fields()
+            .iter()
+            .position(|file_field| match field_id {
+                Some(_) => schema_evolution::get_field_id(file_field) == field_id,
+                None => file_field.name() == field.name(),
+            })
+            .ok_or_else(|| {
+                IcebergError::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Column {} doesn't exist in data file {filepath}",
+                        field.name()
+                    ),
+                )
+            })?;
+        file_column_indices.push(file_column_idx);
+    }
+    Ok(file_column_indices)
+}
+
+/// Fetches byte ranges of a data file via filesystem accessor, so only parquet footer is read.
+struct DataFileRangeFetcher<'a> {
+    filesystem_accessor: &'a dyn BaseFileSystemAccess,
+    filepath: &'a str,
+}
+
+impl MetadataFetch for DataFileRangeFetcher<'_> {
+    fn fetch(&mut self, range: Range<u64>) -> BoxFuture<'_, ParquetResult<Bytes>> {
+        async move {
+            let content = self
+                .filesystem_accessor
+                .read_object_range(self.filepath, range)
+                .await
+                .map_err(|e| ParquetError::External(Box::new(e)))?;
+            Ok(Bytes::from(content))
+        }
+        .boxed()
+    }
+}
+
+/// Get bounds of key columns for each non-empty row group from parquet column statistics.
+/// For the i-th row group, the i-th row holds lower bounds and the (i + num_row_groups)-th row holds upper bounds in sort order, so sort keys of all rows in the data file fall into the key range of returned rows.
+/// Return [`None`] if column statistics are not available.
+fn get_key_bounds_from_statistics(
+    metadata: &ParquetMetaData,
+    file_schema: &Schema,
+    key_schema: &SchemaRef,
+    file_column_indices: &[usize],
+    partition_spec: &[PartitionFieldConfig],
+    sort_order: &[SortFieldConfig],
+) -> Result<Option<RecordBatch>> {
+    let row_groups = metadata
+        .row_groups()
+        .iter()
+        .filter(|row_group| row_group.num_rows() > 0)
+        .collect::<Vec<_>>();
+    let mut columns = Vec::with_capacity(key_schema.fields().len());
+    for (field, file_column_idx) in key_schema.fields().iter().zip(file_column_indices.iter()) {
+        let converter = StatisticsConverter::try_new(
+            file_schema.field(*file_column_idx).name(),
+            file_schema,
+            metadata.file_metadata().schema_descr(),
+        )?;
+        let Some(parquet_column_idx) = converter.parquet_column_index() else {
+            return Ok(None);
+        };
+        let mins = converter.row_group_mins(row_groups.iter().copied())?;
+        let maxes = converter.row_group_maxes(row_groups.iter().copied())?;
+        let null_counts = converter.row_group_null_counts(row_groups.iter().copied())?;
+        // Partition key is computed from the first row, which should be an actual value of the data file.
+        let is_partition_source = partition_spec
+            .iter()
+            .any(|cur_partition_field| &cur_partition_field.source_column == field.name());
+        let mut has_nulls = Vec::with_capacity(row_groups.len());
+        for (row_group_idx, row_group) in row_groups.iter().enumerate() {
+            let Some(statistics) = row_group.column(parquet_column_idx).statistics() else {
+                return Ok(None);
+            };
+            if null_counts.is_null(row_group_idx) {
+                return Ok(None);
+            }
+            let null_count = null_counts.value(row_group_idx);
+            let all_nulls = null_count == row_group.num_rows() as u64;
+            if !all_nulls {
+                if mins.is_null(row_group_idx) || maxes.is_null(row_group_idx) {
+                    return Ok(None);
+                }
+                if is_partition_source && !(statistics.min_is_exact() && statistics.max_is_exact())
+                {
+                    return Ok(None);
+                }
+            }
+            has_nulls.push(null_count > 0);
+        }
+
+        let sort_field = sort_order
+            .iter()
+            .find(|cur_sort_field| &cur_sort_field.source_column == field.name());
+        let (lower_bounds, upper_bounds) = match sort_field {
+            Some(sort_field) if sort_field.direction == SortDirection::Descending => (maxes, mins),
+            Some(_) => (mins, maxes),
+            // Partition source columns not sorted by only decide partition key, which is the same for all rows in the data file.
+            None => (mins.clone(), mins),
+        };
+        let has_nulls = BooleanArray::from(has_nulls);
+        let nulls = new_null_array(lower_bounds.data_type(), row_groups.len());
+        let (lower_bounds, upper_bounds) = match sort_field {
+            Some(sort_field) if sort_field.null_order == NullOrder::First => {
+                (zip(&has_nulls, &nulls, &lower_bounds)?, upper_bounds)
+            }
+            Some(_) => (lower_bounds, zip(&has_nulls, &nulls, &upper_bounds)?),
+            None => (lower_bounds, upper_bounds),
+        };
+        let bounds = concat(&[lower_bounds.as_ref(), upper_bounds.as_ref()])?;
+        columns.push(cast(&bounds, field.data_type())?);
+    }
+    Ok(Some(RecordBatch::try_new(key_schema.clone(), columns)?))
+}
+
+/// Read key columns of the data file, which is used when column statistics are not available.
+async fn read_key_columns(
+    filesystem_accessor: &dyn BaseFileSystemAccess,
+    filepath: &str,
+    key_schema: &SchemaRef,
+    file_column_indices: &[usize],
+    file_decryption_properties: Option<&FileDecryptionProperties>,
+) -> Result<Vec<RecordBatch>> {
+    let mut reader_options = ArrowReaderOptions::new();
+    if let Some(file_decryption_properties) = file_decryption_properties {
+        reader_options =
+            reader_options.with_file_decryption_properties(file_decryption_properties.clone());
+    }
+    let content = filesystem_accessor.read_object(filepath).await?;
+    let builder = ParquetRecordBatchReaderBuilder::try_new_with_options(
+        Bytes::from(content),
+        reader_options,
+    )?;
+    // Projected columns are placed in file column order.
+    let mut projected_column_indices = file_column_indices.to_vec();
+    projected_column_indices.sort_unstable();
+    let projection =
+        ProjectionMask::roots(builder.parquet_schema(), projected_column_indices.clone());
+    let reader = builder.with_projection(projection).build()?;
+
+    let mut key_record_batches = vec![];
+    for record_batch in reader {
+        let record_batch = record_batch?;
+        let mut columns = Vec::with_capacity(key_schema.fields().len());
+        for (field, file_column_idx) in key_schema.fields().iter().zip(file_column_indices.iter()) {
+            let projected_idx = projected_column_indices
+                .binary_search(file_column_idx)
+                .unwrap();
+            columns.push(cast(record_batch.column(projected_idx), field.data_type())?);
+        }
+        key_record_batches.push(RecordBatch::try_new(key_schema.clone(), columns)?);
+    }
+    Ok(key_record_batches)
+}
+
+/// Compute sort key range for a persisted data file, by its sort columns and partition source columns.
+/// Sort key ranges are not persisted, so it's used for data files loaded from persisted table.
+/// Only parquet footer is read if column statistics are available, otherwise key columns are read; range from statistics could be wider than the actual one, which is fine to pick data files to recluster.
+pub(crate) async fn compute_sort_key_range(
+    filesystem_accessor: &dyn BaseFileSystemAccess,
+    filepath: &str,
+    schema: &Schema,
+    partition_spec: &[PartitionFieldConfig],
+    sort_order: &[SortFieldConfig],
+    file_decryption_properties: Option<&FileDecryptionProperties>,
+) -> Result<Option<SortKeyRange>> {
+    let key_schema = get_key_schema(schema, partition_spec, sort_order);
+    let Some(sort_key_generator) = SortKeyGenerator::try_new(&key_schema, sort_order)? else {
+        return Ok(None);
+    };
+    let mut partition_key_generator = PartitionKeyGenerator::try_new(&key_schema, partition_spec)?;
+
+    let file_size = filesystem_accessor
+        .stats_object(filepath)
+        .await?
+        .content_length();
+    let metadata = ParquetMetaDataReader::new()
+        .with_decryption_properties(file_decryption_properties)
+        .load_and_finish(
+            DataFileRangeFetcher {
+                filesystem_accessor,
+                filepath,
+            },
+            file_size,
+        )
+        .await?;
+    let file_schema = parquet_to_arrow_schema(
+        metadata.file_metadata().schema_descr(),
+        metadata.file_metadata().key_value_metadata(),
+    )?;
+    let file_column_indices = get_file_column_indices(&key_schema, &file_schema, filepath)?;
+    let key_record_batches = match get_key_bounds_from_statistics(
+        &metadata,
+        &file_schema,
+        &key_schema,
+        &file_column_indices,
+        partition_spec,
+        sort_order,
+    )? {
+        Some(key_bounds) => vec![key_bounds],
+        None => {
+            read_key_columns(
+                filesystem_accessor,
+                filepath,
+                &key_schema,
+                &file_column_indices,
+                file_decryption_properties,
+            )
+            .await?
+        }
+    };
+
+    let mut sort_key_range: Option<SortKeyRange> = None;
+    for key_record_batch in key_record_batches.iter() {
+        if key_record_batch.num_rows() == 0 {
+            continue;
+        }
+        let partition_key = match partition_key_generator.as_mut() {
+            Some(partition_key_generator) => {
+                Some(partition_key_generator.get_partition_key(key_record_batch)?)
+            }
+            None => None,
+        };
+        let cur_sort_key_range =
+            sort_key_generator.get_key_range(&partition_key, key_record_batch)?;
+        sort_key_range = Some(match sort_key_range {
+            Some(sort_key_range) => sort_key_range.merge(cur_sort_key_range),
+            None => cur_sort_key_range,
+        });
+    }
+    Ok(sort_key_range)
+}
+
+/// Rows sorted by table sort order.
+pub(crate) struct SortedRecordBatch {
+    /// Sorted record batch.
+    pub(crate) record_batch: RecordBatch,
+    /// Row index in the input record batch for each sorted row.
+    pub(crate) original_row_indices: Vec<usize>,
+    /// Sort keys for rows in the input record batch.
+    sort_keys: Rows,
+}
+
+impl SortedRecordBatch {
+    /// Get sort key range for sorted rows within [start, end), which all belong to the given partition.
+    pub(crate) fn get_key_range(
+        &self,
+        partition_key: &Option<PartitionKey>,
+        start: usize,
+        end: usize,
+    ) -> SortKeyRange {
+        assert!(start < end);
+        SortKeyRange {
+            partition_key: partition_key.clone(),
+            min: self.sort_keys.row(self.original_row_indices[start]).owned(),
+            max: self
+                .sort_keys
+                .row(self.original_row_indices[end - 1])
+                .owned(),
+        }
+    }
+}
+
+/// Sorts record batches by table sort order.
+pub(crate) struct SortKeyGenerator {
+    /// Column index for each sort field.
+    sort_columns: Vec<usize>,
+    /// Sorting columns recorded in parquet metadata, which refer to parquet leaf columns.
+    sorting_columns: Vec<SortingColumn>,
+    /// Row converter for sort columns.
+    row_converter: RowConverter,
+}
+
+impl SortKeyGenerator {
+    /// Return `None` if the table is unsorted.
+    pub(crate) fn try_new(schema: &Schema, sort_order: &[SortFieldConfig]) -> Result<Option<Self>> {
+        if sort_order.is_empty() {
+            return Ok(None);
+        }
+        let parquet_schema = ArrowSchemaConverter::new().convert(schema)?;
+        let mut sort_columns = Vec::with_capacity(sort_order.len());
+        let mut sort_fields = Vec::with_capacity(sort_order.len());
+        let mut sorting_columns = Vec::with_capacity(sort_order.len());
+        for cur_sort_field in sort_order.iter() {
+            let (column_idx, field) = schema
+                .column_with_name(&cur_sort_field.source_column)
+                .ok_or_else(|| {
+                    IcebergError::new(
+                        ErrorKind::DataInvalid,
+                        format!(
+                            "Sort column {} doesn't exist in schema",
+                            cur_sort_field.source_column
+                        ),
+                    )
+                })?;
+            // Parquet sorting columns refer to leaf columns, so only primitive columns could be sorted by.
+            let leaf_column_idx = parquet_schema
+                .columns()
+                .iter()
+                .position(|cur_column| {
+                    cur_column.path().parts() == std::slice::from_ref(&cur_sort_field.source_column)
+                })
+                .ok_or_else(|| {
+                    IcebergError::new(
+                        ErrorKind::DataInvalid,
+                        format!(
+                            "Sort column {} is not a primitive column",
+                            cur_sort_field.source_column
+                        ),
+                    )
+                })?;
+            let sort_options = SortOptions {
+                descending: cur_sort_field.direction == SortDirection::Descending,
+                nulls_first: cur_sort_field.null_order == NullOrder::First,
+            };
+            sort_columns.push(column_idx);
+            sort_fields.push(SortField::new_with_options(
+                field.data_type().clone(),
+                sort_options,
+            ));
+            sorting_columns.push(SortingColumn {
+                column_idx: leaf_column_idx as i32,
+                descending: sort_options.descending,
+                nulls_first: sort_options.nulls_first,
+            });
+        }
+        Ok(Some(Self {
+            sort_columns,
+            sorting_columns,
+            row_converter: RowConverter::new(sort_fields)?,
+        }))
+    }
+
+    /// Get sorting columns to record in parquet metadata.
+    pub(crate) fn get_sorting_columns(&self) -> Vec<SortingColumn> {
+        self.sorting_columns.clone()
+    }
+
+    /// Get sort key range for rows in the given record batch, which all belong to the given partition.
+    /// Precondition: the given record batch is not empty.
+    pub(crate) fn get_key_range(
+        &self,
+        partition_key: &Option<PartitionKey>,
+        record_batch: &RecordBatch,
+    ) -> Result<SortKeyRange> {
+        assert!(record_batch.num_rows() > 0);
+        let columns = self
+            .sort_columns
+            .iter()
+            .map(|column_idx| record_batch.column(*column_idx).clone())
+            .collect::<Vec<_>>();
+        let sort_keys = self.row_converter.convert_columns(&columns)?;
+        Ok(SortKeyRange {
+            partition_key: partition_key.clone(),
+            min: sort_keys.iter().min().unwrap().owned(),
+            max: sort_keys.iter().max().unwrap().owned(),
+        })
+    }
+
+    /// Sort rows in the given record batch, rows with the same sort key keep their input order.
+    pub(crate) fn sort(&self, record_batch: &RecordBatch) -> Result<SortedRecordBatch> {
+        let columns = self
+            .sort_columns
+            .iter()
+            .map(|column_idx| record_batch.column(*column_idx).clone())
+            .collect::<Vec<_>>();
+        let sort_keys = self.row_converter.convert_columns(&columns)?;
+        let mut original_row_indices = (0..record_batch.num_rows()).collect::<Vec<_>>();
+        original_row_indices.sort_by(|lhs, rhs| sort_keys.row(*lhs).cmp(&sort_keys.row(*rhs)));
+        let indices =
+            UInt32Array::from_iter_values(original_row_indices.iter().map(|idx| *idx as u32));
+        let sorted_record_batch = take_record_batch(record_batch, &indices)?;
+        Ok(SortedRecordBatch {
+            record_batch: sorted_record_batch,
+            original_row_indices,
+            sort_keys,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::FileSystemAccessor;
+    use arrow_array::{Array, Int32Array, Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Fields};
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::{EnabledStatistics, WriterProperties};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn field_with_id(name: &str, data_type: DataType, field_id: i32) -> Field {
+        Field::new(name, data_type, true).with_metadata(HashMap::from([(
+            "PARQUET:field_id".to_string(),
+            field_id.to_string(),
+        )]))
+    }
+
+    #[test]
+    fn test_sort_record_batch() {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("tenant", DataType::Utf8, true),
+        ]));
+        let record_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5])),
+                Arc::new(StringArray::from(vec![
+                    Some("a"),
+                    None,
+                    Some("c"),
+                    Some("a"),
+                    Some("b"),
+                ])),
+            ],
+        )
+        .unwrap();
+        let sort_order = vec![
+            SortFieldConfig::new("tenant", SortDirection::Descending, NullOrder::Last),
+            SortFieldConfig::new("id", SortDirection::Ascending, NullOrder::First),
+        ];
+        let generator = SortKeyGenerator::try_new(&schema, &sort_order)
+            .unwrap()
+            .unwrap();
+        let sorted = generator.sort(&record_batch).unwrap();
+        assert_eq!(sorted.original_row_indices, vec![2, 4, 0, 3, 1]);
+        let ids = sorted
+            .record_batch
+            .column(0)
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+        assert_eq!(ids.values().to_vec(), vec![3, 5, 1, 4, 2]);
+        assert!(sorted.record_batch.column(1).is_null(4));
+
+        // Key ranges for sorted rows.
+        let first_range = sorted.get_key_range(&None, /*start=*/ 0, /*end=*/ 2);
+        let second_range = sorted.get_key_range(&None, /*start=*/ 1, /*end=*/ 4);
+        let third_range = sorted.get_key_range(&None, /*start=*/ 2, /*end=*/ 5);
+        assert!(first_range.min < first_range.max);
+        assert!(first_range.overlaps(&second_range));
+        assert!(second_range.overlaps(&third_range));
+        assert!(!first_range.overlaps(&third_range));
+
+        // Key ranges for different partitions don't overlap.
+        let partition_key = Some(first_range.min.clone());
+        let partitioned_range =
+            sorted.get_key_range(&partition_key, /*start=*/ 0, /*end=*/ 2);
+        assert!(!first_range.overlaps(&partitioned_range));
+
+        // Sorting columns refer to parquet leaf columns.
+        let sorting_columns = generator.get_sorting_columns();
+        assert_eq!(
+            sorting_columns,
+            vec![
+                SortingColumn {
+                    column_idx: 1,
+                    descending: true,
+                    nulls_first: false,
+                },
+                SortingColumn {
+                    column_idx: 0,
+                    descending: false,
+                    nulls_first: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sort_key_generator_with_nested_columns() {
+        let schema = Schema::new(vec![
+            Field::new(
+                "nested",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("a", DataType::Int32, true),
+                    Field::new("b", DataType::Int32, true),
+                ])),
+                true,
+            ),
+            Field::new("id", DataType::Int32, false),
+        ]);
+
+        // Leaf column index accounts for nested columns ahead.
+        let sort_order = vec![SortFieldConfig::new(
+            "id",
+            SortDirection::Ascending,
+            NullOrder::First,
+        )];
+        let generator = SortKeyGenerator::try_new(&schema, &sort_order)
+            .unwrap()
+            .unwrap();
+        assert_eq!(generator.get_sorting_columns()[0].column_idx, 2);
+
+        // Nested columns cannot be sorted by.
+        let sort_order = vec![SortFieldConfig::new(
+            "nested",
+            SortDirection::Ascending,
+            NullOrder::First,
+        )];
+        assert!(SortKeyGenerator::try_new(&schema, &sort_order).is_err());
+
+        // Unsorted tables don't have a generator.
+        assert!(SortKeyGenerator::try_new(&schema, &[]).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compute_sort_key_range() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let filepath = temp_dir.path().join("data.parquet");
+        let file_schema = Arc::new(Schema::new(vec![
+            field_with_id("id", DataType::Int32, 0),
+            field_with_id("tenant", DataType::Utf8, 1),
+        ]));
+        let record_batch = RecordBatch::try_new(
+            file_schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![3, 1, 2])),
+                Arc::new(StringArray::from(vec!["b", "a", "c"])),
+            ],
+        )
+        .unwrap();
+        let mut writer = ArrowWriter::try_new(
+            std::fs::File::create(&filepath).unwrap(),
+            file_schema.clone(),
+            /*props=*/ None,
+        )
+        .unwrap();
+        writer.write(&record_batch).unwrap();
+        writer.close().unwrap();
+
+        // Sort column has been renamed and promoted after the data file was written.
+        let table_schema = Schema::new(vec![
+            field_with_id("tenant", DataType::Utf8, 1),
+            field_with_id("new_id", DataType::Int64, 0),
+        ]);
+        let sort_order = vec![SortFieldConfig::new(
+            "new_id",
+            SortDirection::Ascending,
+            NullOrder::First,
+        )];
+        let sort_key_range = compute_sort_key_range(
+            FileSystemAccessor::default_for_test(&temp_dir).as_ref(),
+            filepath.to_str().unwrap(),
+            &table_schema,
+            /*partition_spec=*/ &[],
+            &sort_order,
+            /*file_decryption_properties=*/ None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let expected_schema = Arc::new(Schema::new(vec![field_with_id(
+            "new_id",
+            DataType::Int64,
+            0,
+        )]));
+        let expected_record_batch = RecordBatch::try_new(
+            expected_schema.clone(),
+            vec![Arc::new(Int64Array::from(vec![1, 3]))],
+        )
+        .unwrap();
+        let generator = SortKeyGenerator::try_new(&expected_schema, &sort_order)
+            .unwrap()
+            .unwrap();
+        let expected_range = generator
+            .get_key_range(&None, &expected_record_batch)
+            .unwrap();
+        assert!(sort_key_range.partition_key.is_none());
+        assert_eq!(sort_key_range.min, expected_range.min);
+        assert_eq!(sort_key_range.max, expected_range.max);
+
+        // Data files without column statistics fall back to reading sort columns.
+        let filepath_without_statistics = temp_dir.path().join("data_without_statistics.parquet");
+        let mut writer = ArrowWriter::try_new(
+            std::fs::File::create(&filepath_without_statistics).unwrap(),
+            file_schema.clone(),
+            Some(
+                WriterProperties::builder()
+                    .set_statistics_enabled(EnabledStatistics::None)
+                    .build(),
+            ),
+        )
+        .unwrap();
+        writer.write(&record_batch).unwrap();
+        writer.close().unwrap();
+        let sort_key_range_without_statistics = compute_sort_key_range(
+            FileSystemAccessor::default_for_test(&temp_dir).as_ref(),
+            filepath_without_statistics.to_str().unwrap(),
+            &table_schema,
+            /*partition_spec=*/ &[],
+            &sort_order,
+            /*file_decryption_properties=*/ None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(sort_key_range_without_statistics, sort_key_range);
+
+        // Unsorted tables don't have sort key ranges.
+        assert!(compute_sort_key_range(
+            FileSystemAccessor::default_for_test(&temp_dir).as_ref(),
+            filepath.to_str().unwrap(),
+            &table_schema,
+            /*partition_spec=*/ &[],
+            /*sort_order=*/ &[],
+            /*file_decryption_properties=*/ None,
+        )
+        .await
+        .unwrap()
+        .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_compute_sort_key_range_from_statistics() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let filepath = temp_dir.path().join("data.parquet");
+        let schema = Arc::new(Schema::new(vec![field_with_id("id", DataType::Int32, 0)]));
+        let record_batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![
+                Some(5),
+                None,
+                Some(2),
+                Some(9),
+                Some(7),
+            ]))],
+        )
+        .unwrap();
+        // Each row group carries its own column statistics.
+        let mut writer = ArrowWriter::try_new(
+            std::fs::File::create(&filepath).unwrap(),
+            schema.clone(),
+            Some(
+                WriterProperties::builder()
+                    .set_max_row_group_size(3)
+                    .build(),
+            ),
+        )
+        .unwrap();
+        writer.write(&record_batch).unwrap();
+        writer.close().unwrap();
+
+        let sort_order = vec![SortFieldConfig::new(
+            "id",
+            SortDirection::Descending,
+            NullOrder::First,
+        )];
+        let sort_key_range = compute_sort_key_range(
+            FileSystemAccessor::default_for_test(&temp_dir).as_ref(),
+            filepath.to_str().unwrap(),
+            &schema,
+            /*partition_spec=*/ &[],
+            &sort_order,
+            /*file_decryption_properties=*/ None,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        // Nulls are placed first, and the smallest value is placed last.
+        let generator = SortKeyGenerator::try_new(&schema, &sort_order)
+            .unwrap()
+            .unwrap();
+        let expected_range = generator.get_key_range(&None, &record_batch).unwrap();
+        assert_eq!(sort_key_range, expected_range);
+    }
+}
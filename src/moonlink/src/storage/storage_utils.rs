@@ -1,15 +1,19 @@
 use crate::row::MoonlinkRow;
+use crate::storage::partition_utils::PartitionSourceValues;
 use more_asserts as ma;
 use serde::{Deserialize, Serialize};
 use std::borrow::Borrow;
 use std::hash::{Hash, Hasher};
 use std::path::Path;
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct MooncakeDataFile {
     pub(crate) file_id: FileId,
     pub(crate) file_path: String,
+    /// Partition source column values of rows within the data file, only assigned for data files written to partitioned tables.
+    pub(crate) partition_values: Option<PartitionSourceValues>,
 }
 
 impl MooncakeDataFile {
@@ -20,6 +24,10 @@ impl MooncakeDataFile {
     pub fn file_path(&self) -> &String {
         &self.file_path
     }
+
+    pub(crate) fn partition_values(&self) -> Option<&PartitionSourceValues> {
+        self.partition_values.as_ref()
+    }
 }
 
 impl PartialEq for MooncakeDataFile {
@@ -44,6 +52,26 @@ pub fn get_unique_file_id_for_flush(table_auto_incr_id: u64, file_idx: u64) -> u
     LOCAL_FILE_ID_BASE + table_auto_incr_id * NUM_FILES_PER_FLUSH + file_idx
 }
 
+/// Allocates table auto increment ids, which are used to generate unique file ids.
+/// It's shared with disk slice writers, so a flush creating more than [`NUM_FILES_PER_FLUSH`] files could allocate more ids.
+#[derive(Debug)]
+pub(crate) struct TableAutoIncrIdAllocator {
+    next_id: AtomicU32,
+}
+
+impl TableAutoIncrIdAllocator {
+    pub(crate) fn new(next_id: u32) -> Self {
+        Self {
+            next_id: AtomicU32::new(next_id),
+        }
+    }
+
+    /// Allocate the given number of consecutive ids, and return the first one.
+    pub(crate) fn allocate(&self, num_ids: u32) -> u32 {
+        self.next_id.fetch_add(num_ids, Ordering::Relaxed)
+    }
+}
+
 pub fn get_random_file_name_in_dir(dir_path: &Path) -> String {
     dir_path
         .join(format!("data-{}.parquet", uuid::Uuid::now_v7()))
@@ -55,6 +83,20 @@ pub fn create_data_file(file_id: u64, file_path: String) -> MooncakeDataFileRef
     Arc::new(MooncakeDataFile {
         file_id: FileId(file_id),
         file_path,
+        partition_values: None,
+    })
+}
+
+/// Create a data file written by mooncake writers, which records partition source values for partitioned tables.
+pub(crate) fn create_data_file_with_partition(
+    file_id: u64,
+    file_path: String,
+    partition_values: Option<PartitionSourceValues>,
+) -> MooncakeDataFileRef {
+    Arc::new(MooncakeDataFile {
+        file_id: FileId(file_id),
+        file_path,
+        partition_values,
     })
 }
 
@@ -79,7 +121,7 @@ impl Hash for TableUniqueFileId {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum RecordLocation {
     /// Record is in a memory batch
     /// (batch_id, row_offset)
@@ -186,6 +228,7 @@ mod tests {
         let df = Arc::new(MooncakeDataFile {
             file_id: FileId(42),
             file_path: "hello.txt".into(),
+            partition_values: None,
         });
         set.insert(df.clone());
 
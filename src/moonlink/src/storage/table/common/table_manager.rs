@@ -4,6 +4,7 @@ use std::collections::HashMap;
 use crate::storage::index::FileIndex;
 use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::mooncake_table::Snapshot as MooncakeSnapshot;
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
 use crate::storage::storage_utils::FileId;
 use crate::storage::storage_utils::MooncakeDataFileRef;
 use crate::storage::table::iceberg::puffin_utils::PuffinBlobRef;
@@ -35,6 +36,19 @@ pub struct PersistenceResult {
     pub(crate) evicted_files_to_delete: Vec<String>,
 }
 
+/// Snapshot expiration report.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SnapshotExpirationReport {
+    /// Whether it's a dry run, which leaves table metadata and files untouched.
+    pub dry_run: bool,
+    /// Ids for expired snapshots.
+    pub expired_snapshot_ids: Vec<i64>,
+    /// Files only reachable from expired snapshots, including manifest lists, manifest files, data files and puffin files.
+    pub unreachable_files: Vec<String>,
+    /// Files under table location which are referenced by no snapshot and older than the grace period.
+    pub orphan_files: Vec<String>,
+}
+
 #[async_trait]
 #[cfg_attr(test, automock)]
 pub trait TableManager: Send {
@@ -62,6 +76,14 @@ pub trait TableManager: Send {
         &mut self,
     ) -> Result<(u32 /*next file id*/, MooncakeSnapshot)>;
 
+    /// Expire snapshots beyond the given retention policy, and delete files which are only reachable from expired snapshots.
+    /// For dry run, only report what would be removed.
+    #[allow(async_fn_in_trait)]
+    async fn expire_snapshots(
+        &mut self,
+        retention_config: SnapshotRetentionConfig,
+    ) -> Result<SnapshotExpirationReport>;
+
     /// Drop the current iceberg table.
     #[allow(async_fn_in_trait)]
     async fn drop_table(&mut self) -> Result<()>;
@@ -1,3 +1,5 @@
+pub(crate) mod deletion_vector;
+pub(crate) mod deltalake_snapshot_fetcher;
 pub(crate) mod deltalake_table_config;
 pub(crate) mod deltalake_table_loader;
 pub(crate) mod deltalake_table_manager;
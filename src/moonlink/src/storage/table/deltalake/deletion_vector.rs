@@ -0,0 +1,270 @@
+/// This module persists mooncake batch deletion vectors as delta deletion vectors.
+///
+/// Delta deletion vector file starts with a 1-byte format version, followed by the serialized deletion vector, which shares the same layout as iceberg deletion vector blob:
+/// | len for magic and vector | magic | vector | crc32 |
+/// So the deletion vector file could be pinned in object storage cache and read as a puffin blob at query time.
+use std::collections::HashMap;
+
+use deltalake::kernel::{DeletionVectorDescriptor, StorageType};
+use iceberg::puffin::{Blob, DELETION_VECTOR_V1};
+
+use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
+use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
+use crate::storage::table::deltalake::io_utils::get_remote_filepath;
+use crate::storage::table::iceberg::deletion_vector::{
+    DeletionVector, DELETION_VECTOR_CADINALITY, DELETION_VECTOR_REFERENCED_DATA_FILE,
+    MOONCAKE_DELETION_VECTOR_NUM_ROWS,
+};
+use crate::{Error, Result};
+
+/// Format version for delta deletion vector file.
+const DELETION_VECTOR_FILE_FORMAT_VERSION: u8 = 1;
+/// Start offset for the deletion vector within the file, which is right after the format version.
+pub(crate) const DELETION_VECTOR_START_OFFSET: usize = 1;
+/// Size for the length field and checksum field which wrap the magic bytes and serialized vector.
+pub(crate) const DELETION_VECTOR_LENGTH_AND_CHECKSUM_SIZE: usize = 8;
+/// Length of the z85-encoded uuid in uuid-relative deletion vector path.
+const ENCODED_UUID_LEN: usize = 20;
+/// Z85 alphabet, which delta uses to encode deletion vector file uuid.
+const Z85_ALPHABET: &[u8; 85] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// Deletion vector serialized into delta format.
+pub(crate) struct SerializedDeletionVector {
+    /// Content for the deletion vector file.
+    pub(crate) file_content: Vec<u8>,
+    /// Deletion vector descriptor to record in delta log.
+    pub(crate) descriptor: DeletionVectorDescriptor,
+    /// Remote filepath to write the deletion vector file to.
+    pub(crate) remote_filepath: String,
+    /// Size of the serialized deletion vector, including length and checksum fields.
+    pub(crate) blob_size: usize,
+    /// Number of rows deleted.
+    pub(crate) num_rows_deleted: usize,
+}
+
+/// Encode the given bytes in z85, whose length should be multiple of 4.
+fn encode_z85(bytes: &[u8]) -> String {
+    assert_eq!(bytes.len() % 4, 0);
+    let mut encoded = String::with_capacity(bytes.len() / 4 * 5);
+    for chunk in bytes.chunks(4) {
+        let mut value = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+        let mut chars = [0u8; 5];
+        for cur_char in chars.iter_mut().rev() {
+            *cur_char = Z85_ALPHABET[(value % 85) as usize];
+            value /= 85;
+        }
+        encoded.extend(chars.iter().map(|c| *c as char));
+    }
+    encoded
+}
+
+/// Decode the given z85 string, whose length should be multiple of 5.
+fn decode_z85(encoded: &str) -> Result<Vec<u8>> {
+    if encoded.len() % 5 != 0 {
+        return Err(Error::delta_generic_error(format!(
+            "Invalid z85 encoded string {encoded}"
+        )));
+    }
+    let mut bytes = Vec::with_capacity(encoded.len() / 5 * 4);
+    for chunk in encoded.as_bytes().chunks(5) {
+        let mut value: u64 = 0;
+        for cur_char in chunk.iter() {
+            let digit = Z85_ALPHABET
+                .iter()
+                .position(|c| c == cur_char)
+                .ok_or_else(|| {
+                    Error::delta_generic_error(format!("Invalid z85 encoded string {encoded}"))
+                })?;
+            value = value * 85 + digit as u64;
+        }
+        let value = u32::try_from(value).map_err(|_| {
+            Error::delta_generic_error(format!("Invalid z85 encoded string {encoded}"))
+        })?;
+        bytes.extend(value.to_be_bytes());
+    }
+    Ok(bytes)
+}
+
+/// Get deletion vector filepath relative to table location.
+fn get_deletion_vector_relative_path(prefix: &str, uuid: &uuid::Uuid) -> String {
+    if prefix.is_empty() {
+        return format!("deletion_vector_{uuid}.bin");
+    }
+    format!("{prefix}/deletion_vector_{uuid}.bin")
+}
+
+/// Get remote filepath for the deletion vector file referenced by the given descriptor.
+pub(crate) fn get_deletion_vector_filepath(
+    config: &DeltalakeTableConfig,
+    descriptor: &DeletionVectorDescriptor,
+) -> Result<String> {
+    let path_or_inline_dv = &descriptor.path_or_inline_dv;
+    match descriptor.storage_type {
+        StorageType::UuidRelativePath => {
+            if path_or_inline_dv.len() < ENCODED_UUID_LEN {
+                return Err(Error::delta_generic_error(format!(
+                    "Invalid uuid relative deletion vector path {path_or_inline_dv}"
+                )));
+            }
+            let (prefix, encoded_uuid) =
+                path_or_inline_dv.split_at(path_or_inline_dv.len() - ENCODED_UUID_LEN);
+            let uuid = uuid::Uuid::from_slice(&decode_z85(encoded_uuid)?).map_err(|e| {
+                Error::delta_generic_error(format!(
+                    "Invalid uuid relative deletion vector path {path_or_inline_dv}: {e}"
+                ))
+            })?;
+            Ok(get_remote_filepath(
+                config,
+                &get_deletion_vector_relative_path(prefix, &uuid),
+            ))
+        }
+        StorageType::AbsolutePath => Ok(path_or_inline_dv.clone()),
+        StorageType::Inline => Err(Error::delta_generic_error(format!(
+            "Inline deletion vector is not supported: {descriptor:?}"
+        ))),
+    }
+}
+
+/// Serialize the given batch deletion vector, which should have at least one deleted row, into delta deletion vector.
+pub(crate) fn serialize_deletion_vector(
+    config: &DeltalakeTableConfig,
+    referenced_data_file: &str,
+    batch_deletion_vector: &BatchDeletionVector,
+) -> SerializedDeletionVector {
+    let deleted_rows = batch_deletion_vector.collect_deleted_rows();
+    assert!(!deleted_rows.is_empty());
+    let num_rows_deleted = deleted_rows.len();
+    let mut deletion_vector = DeletionVector::new();
+    deletion_vector.mark_rows_deleted(deleted_rows);
+
+    // Delta deletion vector shares the same layout as iceberg blob.
+    let blob_properties = HashMap::from([
+        (
+            DELETION_VECTOR_REFERENCED_DATA_FILE.to_string(),
+            referenced_data_file.to_string(),
+        ),
+        (
+            DELETION_VECTOR_CADINALITY.to_string(),
+            num_rows_deleted.to_string(),
+        ),
+        (
+            MOONCAKE_DELETION_VECTOR_NUM_ROWS.to_string(),
+            batch_deletion_vector.get_max_rows().to_string(),
+        ),
+    ]);
+    let blob = deletion_vector.serialize(blob_properties);
+    let blob_size = blob.data().len();
+    let mut file_content = Vec::with_capacity(DELETION_VECTOR_START_OFFSET + blob_size);
+    file_content.push(DELETION_VECTOR_FILE_FORMAT_VERSION);
+    file_content.extend_from_slice(blob.data());
+
+    let uuid = uuid::Uuid::now_v7();
+    let descriptor = DeletionVectorDescriptor {
+        storage_type: StorageType::UuidRelativePath,
+        path_or_inline_dv: encode_z85(uuid.as_bytes()),
+        offset: Some(DELETION_VECTOR_START_OFFSET as i32),
+        // Size in bytes only covers magic bytes and serialized vector.
+        size_in_bytes: (blob_size - DELETION_VECTOR_LENGTH_AND_CHECKSUM_SIZE) as i32,
+        cardinality: num_rows_deleted as i64,
+    };
+    SerializedDeletionVector {
+        file_content,
+        descriptor,
+        remote_filepath: get_remote_filepath(
+            config,
+            &get_deletion_vector_relative_path(/*prefix=*/ "", &uuid),
+        ),
+        blob_size,
+        num_rows_deleted,
+    }
+}
+
+/// Deserialize delta deletion vector from the given file content into batch deletion vector.
+pub(crate) fn deserialize_deletion_vector(
+    file_content: &[u8],
+    descriptor: &DeletionVectorDescriptor,
+    num_rows: usize,
+) -> Result<BatchDeletionVector> {
+    let start_offset = descriptor
+        .offset
+        .unwrap_or(DELETION_VECTOR_START_OFFSET as i32) as usize;
+    let end_offset =
+        start_offset + descriptor.size_in_bytes as usize + DELETION_VECTOR_LENGTH_AND_CHECKSUM_SIZE;
+    if file_content.len() < end_offset {
+        return Err(Error::delta_generic_error(format!(
+            "Deletion vector file is truncated, expected at least {end_offset} bytes, actual {} bytes",
+            file_content.len()
+        )));
+    }
+
+    let blob = Blob::builder()
+        .r#type(DELETION_VECTOR_V1.to_string())
+        .fields(vec![])
+        .snapshot_id(-1)
+        .sequence_number(-1)
+        .data(file_content[start_offset..end_offset].to_vec())
+        .properties(HashMap::from([(
+            MOONCAKE_DELETION_VECTOR_NUM_ROWS.to_string(),
+            num_rows.to_string(),
+        )]))
+        .build();
+    let deletion_vector = DeletionVector::deserialize(blob)?;
+    if deletion_vector.bitmap.len() != descriptor.cardinality as u64 {
+        return Err(Error::delta_generic_error(format!(
+            "Deletion vector cardinality mismatch, expected {}, actual {}",
+            descriptor.cardinality,
+            deletion_vector.bitmap.len()
+        )));
+    }
+    Ok(deletion_vector.take_as_batch_delete_vector())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::mooncake_table::table_creation_test_utils::get_delta_table_config;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_z85_encoding() {
+        // Test vector from z85 spec.
+        let bytes = [0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B];
+        assert_eq!(encode_z85(&bytes), "HelloWorld");
+        assert_eq!(decode_z85("HelloWorld").unwrap(), bytes);
+        assert!(decode_z85("Hello").is_ok());
+        assert!(decode_z85("Hell").is_err());
+        assert!(decode_z85("Hell\"").is_err());
+    }
+
+    #[test]
+    fn test_deletion_vector_serde() {
+        let temp_dir = TempDir::new().unwrap();
+        let config = get_delta_table_config(&temp_dir);
+        let mut batch_deletion_vector = BatchDeletionVector::new(/*max_rows=*/ 100);
+        assert!(batch_deletion_vector.delete_row(3));
+        assert!(batch_deletion_vector.delete_row(99));
+
+        let serialized =
+            serialize_deletion_vector(&config, "data-file.parquet", &batch_deletion_vector);
+        assert_eq!(serialized.num_rows_deleted, 2);
+        assert_eq!(serialized.descriptor.cardinality, 2);
+        assert_eq!(
+            serialized.file_content.len(),
+            DELETION_VECTOR_START_OFFSET + serialized.blob_size
+        );
+        assert_eq!(
+            get_deletion_vector_filepath(&config, &serialized.descriptor).unwrap(),
+            serialized.remote_filepath
+        );
+
+        let deserialized = deserialize_deletion_vector(
+            &serialized.file_content,
+            &serialized.descriptor,
+            /*num_rows=*/ 100,
+        )
+        .unwrap();
+        assert_eq!(deserialized.collect_deleted_rows(), vec![3, 99]);
+        assert_eq!(deserialized.get_max_rows(), 100);
+    }
+}
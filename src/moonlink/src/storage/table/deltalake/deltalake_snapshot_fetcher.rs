@@ -0,0 +1,43 @@
+use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
+use crate::storage::table::deltalake::utils;
+use crate::storage::table::iceberg::base_iceberg_snapshot_fetcher::BaseIcebergSnapshotFetcher;
+use crate::Result;
+
+use arrow_schema::Schema as ArrowSchema;
+use async_trait::async_trait;
+use deltalake::kernel::engine::arrow_conversion::TryFromKernel;
+
+/// Fetches the latest snapshot status from a delta table, which shares the same interface as iceberg one.
+pub struct DeltalakeSnapshotFetcher {
+    /// Delta table configuration.
+    config: DeltalakeTableConfig,
+}
+
+impl DeltalakeSnapshotFetcher {
+    pub fn new(config: DeltalakeTableConfig) -> Self {
+        Self { config }
+    }
+}
+
+#[async_trait]
+impl BaseIcebergSnapshotFetcher for DeltalakeSnapshotFetcher {
+    async fn fetch_table_schema(&self) -> Result<Option<ArrowSchema>> {
+        let table = utils::get_deltalake_table_if_exists(&self.config).await?;
+        if let Some(table) = table {
+            let delta_schema = table.snapshot()?.schema();
+            let arrow_schema = ArrowSchema::try_from_kernel(&*delta_schema)?;
+            return Ok(Some(arrow_schema));
+        }
+        Ok(None)
+    }
+
+    async fn get_flush_lsn(&self) -> Result<Option<u64>> {
+        let table = utils::get_deltalake_table_if_exists(&self.config).await?;
+        if let Some(table) = table {
+            let commit_app_metadata =
+                utils::get_latest_moonlink_commit_app_metadata(&table).await?;
+            return Ok(commit_app_metadata.map(|(flush_lsn, _)| flush_lsn));
+        }
+        Ok(None)
+    }
+}
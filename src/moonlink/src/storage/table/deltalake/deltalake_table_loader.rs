@@ -1,57 +1,161 @@
 use std::collections::{HashMap, HashSet};
 
-use deltalake::kernel::LogicalFileView;
-use deltalake::DeltaTable;
+use deltalake::kernel::{Add, LogicalFileView};
 use futures::TryStreamExt;
 
-use crate::storage::index::MooncakeIndex;
+use crate::storage::index::{FileIndex as MooncakeFileIndex, MooncakeIndex};
+use crate::storage::io_utils;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
 use crate::storage::mooncake_table::{DiskFileEntry, Snapshot as MooncakeSnapshot};
-use crate::storage::storage_utils::MooncakeDataFileRef;
-use crate::storage::table::common::MOONCAKE_TABLE_FLUSH_LSN;
-use crate::storage::table::deltalake::deltalake_table_manager::DeltalakeTableManager;
+use crate::storage::storage_utils::{FileId, MooncakeDataFileRef, TableId, TableUniqueFileId};
+use crate::storage::table::deltalake::deletion_vector::{
+    deserialize_deletion_vector, get_deletion_vector_filepath,
+    DELETION_VECTOR_LENGTH_AND_CHECKSUM_SIZE, DELETION_VECTOR_START_OFFSET,
+};
+use crate::storage::table::deltalake::deltalake_table_manager::{
+    DataFileEntry, DeltalakeTableManager,
+};
+use crate::storage::table::deltalake::io_utils::get_remote_filepath;
+use crate::storage::table::deltalake::utils;
+use crate::storage::table::iceberg::index::FileIndex as PersistedFileIndex;
+use crate::storage::table::iceberg::puffin_utils::PuffinBlobRef;
 use crate::{create_data_file, Result};
 
 impl DeltalakeTableManager {
-    #[allow(unused)]
-    fn load_data_files(
+    /// Load the deletion vector referenced by the given add action, and pin the deletion vector file in object storage cache.
+    async fn load_deletion_vector(
+        &self,
+        add_action: &Add,
+        num_rows: usize,
+        next_file_id: &mut u64,
+    ) -> Result<(BatchDeletionVector, Option<PuffinBlobRef>)> {
+        let Some(descriptor) = &add_action.deletion_vector else {
+            return Ok((BatchDeletionVector::new(num_rows), None));
+        };
+        let deletion_vector_filepath = get_deletion_vector_filepath(&self.config, descriptor)?;
+        let file_content = self
+            .filesystem_accessor
+            .read_object(&deletion_vector_filepath)
+            .await?;
+        let batch_deletion_vector =
+            deserialize_deletion_vector(&file_content, descriptor, num_rows)?;
+
+        // Load remote deletion vector file to local cache and pin.
+        let cur_file_id = *next_file_id;
+        *next_file_id += 1;
+        let unique_file_id = TableUniqueFileId {
+            table_id: TableId(self.mooncake_table_metadata.table_id),
+            file_id: FileId(cur_file_id),
+        };
+        let (cache_handle, evicted_files_to_delete) = self
+            .object_storage_cache
+            .get_cache_entry(
+                unique_file_id,
+                &deletion_vector_filepath,
+                self.filesystem_accessor.as_ref(),
+            )
+            .await?;
+        io_utils::delete_local_files(&evicted_files_to_delete).await?;
+
+        let start_offset = descriptor
+            .offset
+            .unwrap_or(DELETION_VECTOR_START_OFFSET as i32) as u32;
+        let puffin_blob_ref = PuffinBlobRef {
+            puffin_file_cache_handle: cache_handle.unwrap(),
+            start_offset,
+            // Blob size includes length field and checksum field, which aren't counted in delta deletion vector size.
+            blob_size: (descriptor.size_in_bytes as usize
+                + DELETION_VECTOR_LENGTH_AND_CHECKSUM_SIZE) as u32,
+            num_rows: batch_deletion_vector.get_num_rows_deleted(),
+        };
+        Ok((batch_deletion_vector, Some(puffin_blob_ref)))
+    }
+
+    /// Load data files and their deletion vectors from delta file views, and return the mapping from remote filepath to file id.
+    async fn load_data_files(
+        &mut self,
         adds: Vec<LogicalFileView>,
-        next_file_id: &mut i32,
-    ) -> HashMap<MooncakeDataFileRef, DiskFileEntry> {
+        next_file_id: &mut u64,
+    ) -> Result<(
+        HashMap<MooncakeDataFileRef, DiskFileEntry>,
+        HashMap<String, FileId>,
+    )> {
         let mut disk_files = HashMap::with_capacity(adds.len());
+        let mut remote_data_file_to_file_id = HashMap::with_capacity(adds.len());
         for cur_add in adds.into_iter() {
             let cur_file_id = *next_file_id;
             *next_file_id += 1;
-            let data_file = create_data_file(cur_file_id as u64, cur_add.path().to_string());
+            let add_action = cur_add.add_action();
+            let remote_filepath = get_remote_filepath(&self.config, &add_action.path);
+            let data_file = create_data_file(cur_file_id, remote_filepath.clone());
+            // Row count is recorded in per-file statistics at persistence.
+            let num_rows = cur_add.num_records().unwrap_or(0);
+            let (deletion_vector, puffin_deletion_blob) = self
+                .load_deletion_vector(&add_action, num_rows, next_file_id)
+                .await?;
             let disk_file_entry = DiskFileEntry {
                 cache_handle: None,
-                num_rows: 0, // TODO(hjiang): Record and recover.
-                file_size: cur_add.size() as usize,
-                committed_deletion_vector: BatchDeletionVector::new(/*max_rows=*/ 0),
-                puffin_deletion_blob: None,
+                num_rows,
+                file_size: add_action.size as usize,
+                committed_deletion_vector: deletion_vector.clone(),
+                puffin_deletion_blob,
+                sort_key_range: None,
             };
             assert!(disk_files.insert(data_file, disk_file_entry).is_none());
+            assert!(self
+                .persisted_data_files
+                .insert(
+                    FileId(cur_file_id),
+                    DataFileEntry {
+                        remote_filepath: remote_filepath.clone(),
+                        add_action,
+                        deletion_vector,
+                    },
+                )
+                .is_none());
+            remote_data_file_to_file_id.insert(remote_filepath, FileId(cur_file_id));
         }
-        disk_files
+        Ok((disk_files, remote_data_file_to_file_id))
     }
 
-    #[allow(unused)]
-    async fn get_flush_lsn(table: &DeltaTable) -> Result<u64> {
-        let commit_infos = table.history(/*limit=*/ Some(1)).await?;
-        let latest_commit = commit_infos.last().unwrap();
-        let flush_lsn_json = latest_commit.info.get(MOONCAKE_TABLE_FLUSH_LSN).unwrap();
-        let flush_lsn = flush_lsn_json.as_u64().unwrap();
-        Ok(flush_lsn)
+    /// Load file indices from their serialized remote files.
+    async fn load_file_indices(
+        &mut self,
+        file_index_filepaths: Vec<String>,
+        remote_data_file_to_file_id: &HashMap<String, FileId>,
+        next_file_id: &mut u64,
+    ) -> Result<Vec<MooncakeFileIndex>> {
+        let table_id = TableId(self.mooncake_table_metadata.table_id);
+        let mut file_indices = Vec::with_capacity(file_index_filepaths.len());
+        for cur_filepath in file_index_filepaths.into_iter() {
+            let serialized = self.filesystem_accessor.read_object(&cur_filepath).await?;
+            let mut persisted_file_index: PersistedFileIndex = serde_json::from_slice(&serialized)?;
+            let mooncake_file_index = persisted_file_index
+                .as_mooncake_file_index(
+                    remote_data_file_to_file_id,
+                    self.object_storage_cache.clone(),
+                    self.filesystem_accessor.as_ref(),
+                    table_id,
+                    next_file_id,
+                    /*encryption_key=*/ None,
+                )
+                .await?;
+            assert!(self
+                .persisted_file_indices
+                .insert(mooncake_file_index.clone(), cur_filepath)
+                .is_none());
+            file_indices.push(mooncake_file_index);
+        }
+        Ok(file_indices)
     }
 
-    #[allow(unused)]
     pub(crate) async fn load_snapshot_from_table_impl(
         &mut self,
     ) -> Result<(u32, MooncakeSnapshot)> {
         assert!(!self.snapshot_loaded);
         self.snapshot_loaded = true;
 
-        // Unique file id to assign to every data file.
+        // Unique file id to assign to every data file and index block file.
         let mut next_file_id = 0;
 
         // Handle cases where delta table doesn't exist.
@@ -64,7 +168,7 @@ impl DeltalakeTableManager {
         // TODO(hjiang): Validate schema before operation.
 
         // Handle cases where no snapshot.
-        let table = self.table.as_ref().unwrap();
+        let table = self.table.clone().unwrap();
         let snapshot = table.snapshot();
         if snapshot.is_err() {
             let empty_mooncake_snapshot =
@@ -73,15 +177,30 @@ impl DeltalakeTableManager {
         }
 
         let snapshot = snapshot.unwrap();
-        let flush_lsn = Self::get_flush_lsn(table).await?;
+        // Handle cases where no snapshot has been persisted by moonlink.
+        let Some((flush_lsn, app_metadata)) =
+            utils::get_latest_moonlink_commit_app_metadata(&table).await?
+        else {
+            let empty_mooncake_snapshot =
+                MooncakeSnapshot::new(self.mooncake_table_metadata.clone());
+            return Ok((next_file_id as u32, empty_mooncake_snapshot));
+        };
 
         // Get deltalake file view.
         let log_store = table.log_store();
-        let mut stream = snapshot
+        let stream = snapshot
             .snapshot()
             .file_views(&*log_store, /*predicate=*/ None);
         let adds = stream.try_collect::<Vec<LogicalFileView>>().await?;
-        let disk_files = Self::load_data_files(adds, &mut next_file_id);
+        let (disk_files, remote_data_file_to_file_id) =
+            self.load_data_files(adds, &mut next_file_id).await?;
+        let file_indices = self
+            .load_file_indices(
+                utils::get_file_index_filepaths(&app_metadata)?,
+                &remote_data_file_to_file_id,
+                &mut next_file_id,
+            )
+            .await?;
 
         let mooncake_snapshot = MooncakeSnapshot {
             metadata: self.mooncake_table_metadata.clone(),
@@ -91,7 +210,7 @@ impl DeltalakeTableManager {
             largest_flush_lsn: Some(flush_lsn),
             indices: MooncakeIndex {
                 in_memory_index: HashSet::new(),
-                file_indices: Vec::new(),
+                file_indices,
             },
         };
         Ok((next_file_id as u32, mooncake_snapshot))
@@ -2,29 +2,36 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
-use deltalake::DeltaTable;
+use deltalake::kernel::Add;
+use deltalake::{DeltaOps, DeltaTable};
 
-use crate::error::Result;
+use crate::error::{Error, Result};
+use crate::storage::index::FileIndex as MooncakeFileIndex;
+use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
 use crate::storage::mooncake_table::Snapshot as MooncakeSnapshot;
 use crate::storage::mooncake_table::{
     PersistenceSnapshotPayload, TableMetadata as MooncakeTableMetadata,
 };
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
 use crate::storage::storage_utils::FileId;
 use crate::storage::table::common::table_manager::PersistenceFileParams;
 use crate::storage::table::common::table_manager::PersistenceResult;
+use crate::storage::table::common::table_manager::SnapshotExpirationReport;
 use crate::storage::table::common::table_manager::TableManager;
 use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
 use crate::storage::table::deltalake::utils;
 use crate::{BaseFileSystemAccess, CacheTrait};
 
-#[allow(unused)]
 #[derive(Clone, Debug)]
 pub(crate) struct DataFileEntry {
     /// Remote filepath.
     pub(crate) remote_filepath: String,
+    /// Latest add action for the data file, whose path is relative to table location.
+    pub(crate) add_action: Add,
+    /// Deleted rows which have been persisted for the data file.
+    pub(crate) deletion_vector: BatchDeletionVector,
 }
 
-#[allow(unused)]
 #[derive(Debug)]
 pub struct DeltalakeTableManager {
     /// Mooncake table metadata.
@@ -47,10 +54,12 @@ pub struct DeltalakeTableManager {
 
     /// Maps from file id to file entry.
     pub(crate) persisted_data_files: HashMap<FileId, DataFileEntry>,
+
+    /// Maps from mooncake file index to its serialized remote filepath.
+    pub(crate) persisted_file_indices: HashMap<MooncakeFileIndex, String>,
 }
 
 impl DeltalakeTableManager {
-    #[allow(unused)]
     pub async fn new(
         mooncake_table_metadata: Arc<MooncakeTableMetadata>,
         object_storage_cache: Arc<dyn CacheTrait>,
@@ -65,10 +74,10 @@ impl DeltalakeTableManager {
             object_storage_cache,
             filesystem_accessor,
             persisted_data_files: HashMap::new(),
+            persisted_file_indices: HashMap::new(),
         })
     }
 
-    #[allow(unused)]
     pub(crate) async fn initialize_table_if_exists(&mut self) -> Result<()> {
         assert!(self.table.is_none());
         self.table = utils::get_deltalake_table_if_exists(&self.config).await?;
@@ -78,12 +87,10 @@ impl DeltalakeTableManager {
 
 #[async_trait]
 impl TableManager for DeltalakeTableManager {
-    #[allow(unused)]
     fn get_warehouse_location(&self) -> String {
         self.config.location.clone()
     }
 
-    #[allow(unused)]
     async fn sync_snapshot(
         &mut self,
         snapshot_payload: PersistenceSnapshotPayload,
@@ -95,13 +102,43 @@ impl TableManager for DeltalakeTableManager {
         Ok(persistence_result)
     }
 
-    #[allow(unused)]
     async fn load_snapshot_from_table(&mut self) -> Result<(u32, MooncakeSnapshot)> {
         let snapshot = self.load_snapshot_from_table_impl().await?;
         Ok(snapshot)
     }
 
-    #[allow(unused)]
+    async fn expire_snapshots(
+        &mut self,
+        retention_config: SnapshotRetentionConfig,
+    ) -> Result<SnapshotExpirationReport> {
+        // Delta lake vacuum only supports retention by file age.
+        if retention_config.max_snapshots_to_keep.is_some() {
+            return Err(Error::invalid_table_config(format!(
+                "Delta lake tables only support age based snapshot retention, but got {retention_config:?}"
+            )));
+        }
+        let mut report = SnapshotExpirationReport {
+            dry_run: retention_config.dry_run,
+            ..Default::default()
+        };
+        let (Some(max_snapshot_age_ms), Some(table)) =
+            (retention_config.max_snapshot_age_ms, self.table.as_ref())
+        else {
+            return Ok(report);
+        };
+
+        // File indices are placed under a hidden directory, which is skipped by vacuum.
+        let (new_table, metrics) = DeltaOps(table.clone())
+            .vacuum()
+            .with_retention_period(chrono::Duration::milliseconds(max_snapshot_age_ms as i64))
+            .with_enforce_retention_duration(false)
+            .with_dry_run(retention_config.dry_run)
+            .await?;
+        self.table = Some(new_table);
+        report.unreachable_files = metrics.files_deleted;
+        Ok(report)
+    }
+
     async fn drop_table(&mut self) -> Result<()> {
         let warehouse = self.get_warehouse_location();
         self.filesystem_accessor
@@ -112,6 +149,7 @@ impl TableManager for DeltalakeTableManager {
         self.table = None;
         self.snapshot_loaded = false;
         self.persisted_data_files.clear();
+        self.persisted_file_indices.clear();
 
         Ok(())
     }
@@ -1,34 +1,90 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+use deltalake::kernel::engine::arrow_conversion::TryFromArrow;
 use deltalake::kernel::transaction::CommitBuilder;
-use deltalake::kernel::{Action, Add, Remove};
+use deltalake::kernel::{Action, Add, MetadataExt, Remove, StructType};
 use deltalake::protocol::DeltaOperation;
+use deltalake::DeltaTable;
 use futures::{stream, StreamExt, TryStreamExt};
 use serde_json::Value;
 
 use crate::create_data_file;
 use crate::error::{Error, Result};
+use crate::storage::cache::object_storage::base_cache::InlineEvictedFiles;
+use crate::storage::compaction::table_compaction::RemappedRecordLocation;
+use crate::storage::index::FileIndex as MooncakeFileIndex;
+use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
+use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
 use crate::storage::mooncake_table::{
-    take_data_files_to_import, take_data_files_to_remove, PersistenceSnapshotPayload,
+    take_data_files_to_import, take_data_files_to_remove, take_file_indices_to_import,
+    take_file_indices_to_remove, PersistenceSnapshotPayload,
+};
+use crate::storage::storage_utils::{
+    self, get_unique_file_id_for_flush, FileId, MooncakeDataFileRef, RecordLocation, TableId,
+    TableUniqueFileId,
 };
-use crate::storage::storage_utils::MooncakeDataFileRef;
 use crate::storage::table::common::table_manager::PersistenceFileParams;
 use crate::storage::table::common::table_manager::PersistenceResult;
 use crate::storage::table::common::MOONCAKE_TABLE_FLUSH_LSN;
-use crate::storage::table::deltalake::io_utils::upload_data_file_to_delta;
+use crate::storage::table::deltalake::deletion_vector::{
+    serialize_deletion_vector, DELETION_VECTOR_START_OFFSET,
+};
+use crate::storage::table::deltalake::io_utils::{
+    generate_unique_file_index_filepath, upload_data_file_to_delta, upload_index_file_to_delta,
+};
 use crate::storage::table::deltalake::parquet_utils::collect_parquet_stats;
+use crate::storage::table::deltalake::utils::MOONCAKE_TABLE_FILE_INDICES;
 use crate::storage::table::deltalake::{deltalake_table_manager::*, utils};
+use crate::storage::table::iceberg::index::FileIndex as PersistedFileIndex;
 use crate::storage::table::iceberg::parquet_utils;
+use crate::storage::table::iceberg::puffin_utils::PuffinBlobRef;
 
 /// Max retry attempts count.
 const DEFAULT_MAX_RETRY_COUNT: usize = 5;
 /// Default data file upload concurrency.
 const DEFAULT_DATA_FILE_UPLOAD_CONCURRENCY: usize = 128;
+/// Default file index upload concurrency.
+const DEFAULT_FILE_INDEX_UPLOAD_CONCURRENCY: usize = 128;
+/// Default deletion vector upload concurrency.
+const DEFAULT_DELETION_VECTOR_UPLOAD_CONCURRENCY: usize = 128;
 
 struct UploadedDataFile {
+    local_filepath: String,
     remote_data_file: MooncakeDataFileRef,
-    add_action: Action,
+    num_rows: usize,
+    add_action: Add,
+}
+
+/// Upload result for one deletion vector.
+struct UploadedDeletionVector {
+    /// File id for the data file which the deletion vector refers to.
+    file_id: FileId,
+    /// Data file entry with deletion vector updated.
+    entry: DataFileEntry,
+    /// Reference to the deletion vector file pinned in object storage cache.
+    puffin_blob_ref: PuffinBlobRef,
+    /// Evicted files to delete when pinning the deletion vector file.
+    evicted_files_to_delete: InlineEvictedFiles,
+}
+
+/// Result for persisting deletion vectors.
+struct DeletionVectorsSyncResult {
+    /// Maps from data file id to its persisted deletion vector.
+    puffin_deletion_blobs: HashMap<FileId, PuffinBlobRef>,
+    /// Delta actions to replace data files whose deletion vector gets updated.
+    delta_actions: Vec<Action>,
+    /// Evicted files to delete.
+    evicted_files_to_delete: Vec<String>,
+}
+
+/// Upload result for one mooncake file index.
+struct UploadedFileIndex {
+    /// Maps from local index block file to remote filepath.
+    local_index_file_to_remote: HashMap<String, String>,
+    /// Remote filepath for the serialized file index.
+    file_index_filepath: String,
 }
 
 impl DeltalakeTableManager {
@@ -62,11 +118,300 @@ impl DeltalakeTableManager {
         Ok(())
     }
 
-    #[allow(unused)]
+    /// Get unique file id for the deletion vector file, which is pinned in object storage cache.
+    fn get_unique_table_id_for_deletion_vector(
+        &self,
+        file_params: &PersistenceFileParams,
+        deletion_vector_index: u64,
+    ) -> TableUniqueFileId {
+        let unique_table_auto_incre_id_offset =
+            deletion_vector_index / storage_utils::NUM_FILES_PER_FLUSH;
+        let cur_table_auto_incr_id =
+            file_params.table_auto_incr_ids.start as u64 + unique_table_auto_incre_id_offset;
+        assert!(file_params
+            .table_auto_incr_ids
+            .contains(&(cur_table_auto_incr_id as u32)));
+        let cur_file_idx = deletion_vector_index
+            - storage_utils::NUM_FILES_PER_FLUSH * unique_table_auto_incre_id_offset;
+        TableUniqueFileId {
+            table_id: TableId(self.mooncake_table_metadata.table_id),
+            file_id: FileId(get_unique_file_id_for_flush(
+                cur_table_auto_incr_id,
+                cur_file_idx,
+            )),
+        }
+    }
+
+    /// Get deleted rows in persisted data files, which have been remapped to new data files by data compaction.
+    /// Precondition: new data files have been recorded in persisted data files.
+    fn get_remapped_deletion_records(
+        &self,
+        data_file_records_remap: &HashMap<RecordLocation, RemappedRecordLocation>,
+    ) -> HashMap<MooncakeDataFileRef, BatchDeletionVector> {
+        let mut remapped_deletion_records =
+            HashMap::<MooncakeDataFileRef, BatchDeletionVector>::new();
+        if data_file_records_remap.is_empty() {
+            return remapped_deletion_records;
+        }
+        for (old_file_id, old_data_file_entry) in self.persisted_data_files.iter() {
+            for old_row_idx in old_data_file_entry
+                .deletion_vector
+                .collect_deleted_rows()
+                .into_iter()
+            {
+                let old_record_location =
+                    RecordLocation::DiskFile(*old_file_id, old_row_idx as usize);
+                let Some(new_remapped_record_location) =
+                    data_file_records_remap.get(&old_record_location)
+                else {
+                    continue;
+                };
+                let new_data_file = new_remapped_record_location.new_data_file.clone();
+                let new_row_idx = new_remapped_record_location.record_location.get_row_idx();
+                let new_batch_deletion_vector = remapped_deletion_records
+                    .entry(new_data_file.clone())
+                    .or_insert_with(|| {
+                        let new_data_file_entry = self
+                            .persisted_data_files
+                            .get(&new_data_file.file_id())
+                            // Invariant sanity check: all data files have been imported into in-memory state.
+                            .unwrap();
+                        BatchDeletionVector::new(new_data_file_entry.deletion_vector.get_max_rows())
+                    });
+                assert!(new_batch_deletion_vector.delete_row(new_row_idx));
+            }
+        }
+        remapped_deletion_records
+    }
+
+    /// Merge the given deletion vector with the persisted one, write it as delta deletion vector file, and pin it in object storage cache.
+    async fn upload_deletion_vector(
+        &self,
+        deletion_vector_index: u64,
+        data_file: MooncakeDataFileRef,
+        new_deletion_vector: BatchDeletionVector,
+        file_params: &PersistenceFileParams,
+    ) -> Result<UploadedDeletionVector> {
+        let mut entry = self
+            .persisted_data_files
+            .get(&data_file.file_id())
+            .unwrap()
+            .clone();
+        assert_eq!(
+            entry.deletion_vector.get_max_rows(),
+            new_deletion_vector.get_max_rows()
+        );
+        entry.deletion_vector.merge_with(&new_deletion_vector);
+
+        let serialized_deletion_vector =
+            serialize_deletion_vector(&self.config, &entry.remote_filepath, &entry.deletion_vector);
+        self.filesystem_accessor
+            .write_object(
+                &serialized_deletion_vector.remote_filepath,
+                serialized_deletion_vector.file_content,
+            )
+            .await?;
+        entry.add_action.deletion_vector = Some(serialized_deletion_vector.descriptor);
+
+        let unique_file_id =
+            self.get_unique_table_id_for_deletion_vector(file_params, deletion_vector_index);
+        let (cache_handle, evicted_files_to_delete) = self
+            .object_storage_cache
+            .get_cache_entry(
+                unique_file_id,
+                &serialized_deletion_vector.remote_filepath,
+                self.filesystem_accessor.as_ref(),
+            )
+            .await?;
+        let puffin_blob_ref = PuffinBlobRef {
+            puffin_file_cache_handle: cache_handle.unwrap(),
+            start_offset: DELETION_VECTOR_START_OFFSET as u32,
+            blob_size: serialized_deletion_vector.blob_size as u32,
+            num_rows: serialized_deletion_vector.num_rows_deleted,
+        };
+
+        Ok(UploadedDeletionVector {
+            file_id: data_file.file_id(),
+            entry,
+            puffin_blob_ref,
+            evicted_files_to_delete,
+        })
+    }
+
+    /// Persist the given deletion vectors, each of which is merged with the persisted one for the same data file.
+    ///
+    /// # Arguments:
+    ///
+    /// * new_data_file_ids: data files added in the current commit, whose add actions haven't been recorded in delta log.
+    async fn sync_deletion_vectors(
+        &mut self,
+        new_deletion_vectors: HashMap<MooncakeDataFileRef, BatchDeletionVector>,
+        new_data_file_ids: &HashSet<FileId>,
+        file_params: &PersistenceFileParams,
+        deletion_timestamp: i64,
+    ) -> Result<DeletionVectorsSyncResult> {
+        let uploaded_deletion_vectors = stream::iter(new_deletion_vectors.into_iter().enumerate())
+            .map(
+                |(deletion_vector_index, (data_file, new_deletion_vector))| {
+                    self.upload_deletion_vector(
+                        deletion_vector_index as u64,
+                        data_file,
+                        new_deletion_vector,
+                        file_params,
+                    )
+                },
+            )
+            .buffer_unordered(DEFAULT_DELETION_VECTOR_UPLOAD_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        let mut puffin_deletion_blobs = HashMap::with_capacity(uploaded_deletion_vectors.len());
+        let mut delta_actions = Vec::new();
+        let mut evicted_files_to_delete = Vec::new();
+        for cur_uploaded in uploaded_deletion_vectors.into_iter() {
+            let old_entry = self
+                .persisted_data_files
+                .insert(cur_uploaded.file_id, cur_uploaded.entry.clone())
+                .unwrap();
+            // Deletion vector for an already committed data file is updated by replacing its add action.
+            if !new_data_file_ids.contains(&cur_uploaded.file_id) {
+                delta_actions.push(Action::Remove(Remove {
+                    path: old_entry.add_action.path.clone(),
+                    data_change: true,
+                    deletion_timestamp: Some(deletion_timestamp),
+                    size: Some(old_entry.add_action.size),
+                    deletion_vector: old_entry.add_action.deletion_vector.clone(),
+                    ..Default::default()
+                }));
+                let mut add_action = cur_uploaded.entry.add_action;
+                add_action.data_change = true;
+                delta_actions.push(Action::Add(add_action));
+            }
+            assert!(puffin_deletion_blobs
+                .insert(cur_uploaded.file_id, cur_uploaded.puffin_blob_ref)
+                .is_none());
+            evicted_files_to_delete.extend(cur_uploaded.evicted_files_to_delete);
+        }
+
+        Ok(DeletionVectorsSyncResult {
+            puffin_deletion_blobs,
+            delta_actions,
+            evicted_files_to_delete,
+        })
+    }
+
+    /// Upload index blocks for the given file index, and write the serialized file index next to them.
+    async fn upload_file_index(
+        &self,
+        file_index: &MooncakeFileIndex,
+        local_data_file_to_remote: &HashMap<String, String>,
+    ) -> Result<UploadedFileIndex> {
+        let mut local_index_file_to_remote = HashMap::new();
+        for cur_index_block in file_index.index_blocks.iter() {
+            let remote_index_block = upload_index_file_to_delta(
+                &self.config,
+                cur_index_block.index_file.file_path(),
+                &*self.filesystem_accessor,
+            )
+            .await?;
+            local_index_file_to_remote.insert(
+                cur_index_block.index_file.file_path().to_string(),
+                remote_index_block,
+            );
+        }
+
+        // Delta table doesn't have a place for index files, so file index is serialized in the same format as iceberg puffin blob.
+        let persisted_file_index = PersistedFileIndex::new(
+            file_index,
+            &local_index_file_to_remote,
+            local_data_file_to_remote,
+        );
+        let file_index_filepath = generate_unique_file_index_filepath(&self.config);
+        self.filesystem_accessor
+            .write_object(
+                &file_index_filepath,
+                serde_json::to_vec(&persisted_file_index)?,
+            )
+            .await?;
+
+        Ok(UploadedFileIndex {
+            local_index_file_to_remote,
+            file_index_filepath,
+        })
+    }
+
+    /// Persist new file indices and unreference old ones, return remote file indices in the same order as [`file_indices_to_import`].
+    async fn sync_file_indices(
+        &mut self,
+        file_indices_to_import: &[MooncakeFileIndex],
+        file_indices_to_remove: &[MooncakeFileIndex],
+        local_data_file_to_remote: &HashMap<String, String>,
+    ) -> Result<Vec<MooncakeFileIndex>> {
+        let uploaded_file_indices = stream::iter(file_indices_to_import.iter())
+            .map(|cur_file_index| self.upload_file_index(cur_file_index, local_data_file_to_remote))
+            .buffered(DEFAULT_FILE_INDEX_UPLOAD_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+
+        // Index block files are no longer referenced once removed from the table, which are left for vacuum.
+        for cur_file_index in file_indices_to_remove.iter() {
+            assert!(self.persisted_file_indices.remove(cur_file_index).is_some());
+        }
+
+        let mut remote_file_indices = Vec::with_capacity(file_indices_to_import.len());
+        for (cur_file_index, cur_uploaded) in file_indices_to_import
+            .iter()
+            .zip(uploaded_file_indices.into_iter())
+        {
+            let mut remote_file_index = cur_file_index.clone();
+            for cur_data_file in remote_file_index.files.iter_mut() {
+                if let Some(remote_data_file) =
+                    local_data_file_to_remote.get(cur_data_file.file_path())
+                {
+                    *cur_data_file =
+                        create_data_file(cur_data_file.file_id().0, remote_data_file.clone());
+                }
+            }
+            // Similar to iceberg, index blocks point to remote filepaths while their cache handles still refer to local cache files,
+            // which get reconciled when they're imported into mooncake snapshot.
+            for cur_index_block in remote_file_index.index_blocks.iter_mut() {
+                let remote_index_block = cur_uploaded
+                    .local_index_file_to_remote
+                    .get(cur_index_block.index_file.file_path())
+                    .unwrap();
+                cur_index_block.index_file = create_data_file(
+                    cur_index_block.index_file.file_id().0,
+                    remote_index_block.clone(),
+                );
+            }
+            assert!(self
+                .persisted_file_indices
+                .insert(cur_file_index.clone(), cur_uploaded.file_index_filepath)
+                .is_none());
+            remote_file_indices.push(remote_file_index);
+        }
+
+        Ok(remote_file_indices)
+    }
+
+    /// Get the metadata action to overwrite delta table schema with the given mooncake table schema.
+    fn get_schema_evolution_action(
+        table: &DeltaTable,
+        new_table_schema: &MooncakeTableMetadata,
+    ) -> Result<Action> {
+        let new_schema = StructType::try_from_arrow(new_table_schema.schema.as_ref())?;
+        let new_metadata = table
+            .snapshot()?
+            .metadata()
+            .clone()
+            .with_schema(&new_schema)?;
+        Ok(Action::Metadata(new_metadata))
+    }
+
     pub(crate) async fn sync_snapshot_impl(
         &mut self,
         mut snapshot_payload: PersistenceSnapshotPayload,
-        _file_params: PersistenceFileParams,
+        file_params: PersistenceFileParams,
     ) -> Result<PersistenceResult> {
         let table = utils::get_or_create_deltalake_table(
             self.mooncake_table_metadata.clone(),
@@ -77,9 +422,13 @@ impl DeltalakeTableManager {
         .await?;
         self.table = Some(table.clone());
         let filesystem_accessor = self.filesystem_accessor.clone();
+        let config = Arc::new(self.config.clone());
 
         let new_data_files = take_data_files_to_import(&mut snapshot_payload);
         let old_data_files = take_data_files_to_remove(&mut snapshot_payload);
+        let new_file_indices = take_file_indices_to_import(&mut snapshot_payload);
+        let old_file_indices = take_file_indices_to_remove(&mut snapshot_payload);
+        let new_table_schema = std::mem::take(&mut snapshot_payload.new_table_schema);
 
         // Validate data files to add and remove are valid.
         self.validate_new_data_files(&new_data_files)?;
@@ -87,7 +436,7 @@ impl DeltalakeTableManager {
 
         let uploaded_files = stream::iter(new_data_files.into_iter())
             .map(|cur_local_data_file| {
-                let table = table.clone();
+                let config = config.clone();
                 let fs_accessor = filesystem_accessor.clone();
 
                 async move {
@@ -96,18 +445,15 @@ impl DeltalakeTableManager {
                             .await?;
                     let file_stats = collect_parquet_stats(&parquet_metadata)?;
 
-                    let remote_filepath = upload_data_file_to_delta(
-                        &table,
-                        &cur_local_data_file.file_path,
+                    let (delta_path, remote_filepath) = upload_data_file_to_delta(
+                        &config,
+                        cur_local_data_file.file_path(),
                         &*fs_accessor,
                     )
                     .await?;
 
-                    let data_file =
-                        create_data_file(cur_local_data_file.file_id().0, remote_filepath.clone());
-
                     let add_action = Add {
-                        path: remote_filepath.clone(),
+                        path: delta_path,
                         size: file_size as i64,
                         data_change: true,
                         stats: Some(serde_json::to_string(&file_stats).unwrap()),
@@ -115,11 +461,13 @@ impl DeltalakeTableManager {
                     };
 
                     Ok::<UploadedDataFile, Error>(UploadedDataFile {
+                        local_filepath: cur_local_data_file.file_path().clone(),
                         remote_data_file: create_data_file(
                             cur_local_data_file.file_id().0,
                             remote_filepath,
                         ),
-                        add_action: Action::Add(add_action),
+                        num_rows: parquet_metadata.file_metadata().num_rows() as usize,
+                        add_action,
                     })
                 }
             })
@@ -129,9 +477,11 @@ impl DeltalakeTableManager {
 
         // Aggregate results.
         let mut new_remote_data_files = Vec::new();
+        let mut new_data_file_ids = HashSet::new();
+        let mut local_data_file_to_remote = HashMap::new();
         let mut delta_actions = Vec::new();
 
-        // Reflect add actions.
+        // Reflect add actions, which are recorded after deletion vectors get persisted.
         for cur_file in uploaded_files {
             assert!(self
                 .persisted_data_files
@@ -139,38 +489,121 @@ impl DeltalakeTableManager {
                     cur_file.remote_data_file.file_id,
                     DataFileEntry {
                         remote_filepath: cur_file.remote_data_file.file_path.clone(),
+                        add_action: cur_file.add_action,
+                        deletion_vector: BatchDeletionVector::new(cur_file.num_rows),
                     }
                 )
                 .is_none());
+            local_data_file_to_remote.insert(
+                cur_file.local_filepath,
+                cur_file.remote_data_file.file_path.clone(),
+            );
+            new_data_file_ids.insert(cur_file.remote_data_file.file_id);
             new_remote_data_files.push(cur_file.remote_data_file.clone());
-            delta_actions.push(cur_file.add_action);
         }
 
+        // Remap deleted rows in compacted data files before they're removed.
+        let remapped_deletion_records = self.get_remapped_deletion_records(
+            &snapshot_payload
+                .data_compaction_payload
+                .data_file_records_remap,
+        );
+
         // Reflect remove actions.
         let now_ms = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_millis() as i64;
         for cur_old_data_file in old_data_files.into_iter() {
+            let old_entry = self
+                .persisted_data_files
+                .remove(&cur_old_data_file.file_id())
+                .unwrap();
             let cur_remove_action = Remove {
-                path: cur_old_data_file.file_path().clone(),
+                path: old_entry.add_action.path,
                 data_change: false,
                 deletion_timestamp: Some(now_ms),
+                size: Some(old_entry.add_action.size),
+                deletion_vector: old_entry.add_action.deletion_vector,
                 ..Default::default()
             };
             delta_actions.push(Action::Remove(cur_remove_action));
         }
 
+        // Persist committed deletion logs.
+        let mut new_deletion_vectors =
+            std::mem::take(&mut snapshot_payload.import_payload.new_deletion_vector);
+        for (remapped_data_file, remapped_batch_deletion_vector) in
+            remapped_deletion_records.into_iter()
+        {
+            if let Some(new_batch_deletion_vector) =
+                new_deletion_vectors.get_mut(&remapped_data_file)
+            {
+                new_batch_deletion_vector.merge_with(&remapped_batch_deletion_vector);
+            } else {
+                assert!(new_deletion_vectors
+                    .insert(remapped_data_file, remapped_batch_deletion_vector)
+                    .is_none());
+            }
+        }
+        let deletion_vectors_sync_result = self
+            .sync_deletion_vectors(
+                new_deletion_vectors,
+                &new_data_file_ids,
+                &file_params,
+                now_ms,
+            )
+            .await?;
+        delta_actions.extend(deletion_vectors_sync_result.delta_actions);
+
+        // New data files are added with their latest deletion vectors.
+        for cur_data_file in new_remote_data_files.iter() {
+            let cur_entry = self
+                .persisted_data_files
+                .get(&cur_data_file.file_id())
+                .unwrap();
+            delta_actions.push(Action::Add(cur_entry.add_action.clone()));
+        }
+
+        // Persist file indices, which should refer to remote data files.
+        let remote_file_indices = self
+            .sync_file_indices(
+                &new_file_indices,
+                &old_file_indices,
+                &local_data_file_to_remote,
+            )
+            .await?;
+
+        // Schema change is committed along with the data files.
+        if let Some(new_table_schema) = new_table_schema {
+            delta_actions.push(Self::get_schema_evolution_action(
+                self.table.as_ref().unwrap(),
+                &new_table_schema,
+            )?);
+        }
+
         // Record remote filepath to delta table.
         let write_op = DeltaOperation::Write {
             mode: deltalake::protocol::SaveMode::Append,
             partition_by: None,
             predicate: None,
         };
-        let app_metadata = HashMap::<String, Value>::from([(
-            MOONCAKE_TABLE_FLUSH_LSN.to_string(),
-            serde_json::from_str(&snapshot_payload.flush_lsn.to_string()).unwrap(),
-        )]);
+        let mut file_index_filepaths = self
+            .persisted_file_indices
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        file_index_filepaths.sort();
+        let app_metadata = HashMap::<String, Value>::from([
+            (
+                MOONCAKE_TABLE_FLUSH_LSN.to_string(),
+                serde_json::from_str(&snapshot_payload.flush_lsn.to_string()).unwrap(),
+            ),
+            (
+                MOONCAKE_TABLE_FILE_INDICES.to_string(),
+                serde_json::to_value(file_index_filepaths).unwrap(),
+            ),
+        ]);
 
         CommitBuilder::default()
             .with_actions(delta_actions)
@@ -185,9 +618,9 @@ impl DeltalakeTableManager {
 
         Ok(PersistenceResult {
             remote_data_files: new_remote_data_files,
-            remote_file_indices: Vec::new(),
-            puffin_blob_ref: HashMap::new(),
-            evicted_files_to_delete: Vec::new(),
+            remote_file_indices,
+            puffin_blob_ref: deletion_vectors_sync_result.puffin_deletion_blobs,
+            evicted_files_to_delete: deletion_vectors_sync_result.evicted_files_to_delete,
         })
     }
 }
@@ -1,37 +1,86 @@
-use deltalake::DeltaTable;
+use std::path::Path;
 
+use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
 use crate::{BaseFileSystemAccess, Result};
 
-/// Get a unique filepath for iceberg table data filepath.
-#[allow(unused)]
-fn generate_unique_data_filepath(table: &DeltaTable, local_filepath: &String) -> String {
-    let filename_without_suffix = std::path::Path::new(local_filepath)
+/// Directory under delta table location, which stores mooncake file indices.
+pub(crate) const FILE_INDEX_DIRECTORY: &str = "_moonlink_index";
+
+/// Get a unique filename for delta table data file, which is relative to table location.
+fn generate_unique_data_filename(local_filepath: &str) -> String {
+    let filename_without_suffix = Path::new(local_filepath)
         .file_stem()
         .unwrap()
         .to_str()
         .unwrap()
         .to_string();
-    let table_uri = table.table_uri();
-    let remote_filepath = format!(
-        "{}.{}-{}.parquet",
-        table_uri,
+    format!(
+        "{}-{}.parquet",
         filename_without_suffix,
         uuid::Uuid::now_v7()
-    );
-    remote_filepath
+    )
+}
+
+/// Get the remote filepath for the given path relative to delta table location.
+pub(crate) fn get_remote_filepath(config: &DeltalakeTableConfig, relative_path: &str) -> String {
+    // Delta log could record absolute paths as well.
+    if relative_path.contains("://") || relative_path.starts_with('/') {
+        return relative_path.to_string();
+    }
+    format!(
+        "{}/{}",
+        config.location.trim_end_matches('/'),
+        relative_path
+    )
 }
 
-/// Upload the given data file to delta table.
-#[allow(unused)]
+/// Upload the given data file to delta table, return its path relative to table location and its remote filepath.
 pub(crate) async fn upload_data_file_to_delta(
-    table: &DeltaTable,
-    local_filepath: &String,
+    config: &DeltalakeTableConfig,
+    local_filepath: &str,
     filesystem_accessor: &dyn BaseFileSystemAccess,
-) -> Result<String> {
-    let remote_filepath = generate_unique_data_filepath(table, local_filepath);
+) -> Result<(String, String)> {
+    let relative_path = generate_unique_data_filename(local_filepath);
+    let remote_filepath = get_remote_filepath(config, &relative_path);
     // Import local parquet file to remote.
     filesystem_accessor
         .copy_from_local_to_remote(local_filepath, &remote_filepath)
         .await?;
+    Ok((relative_path, remote_filepath))
+}
+
+/// Copy the given local index block file to delta table, and return its remote filepath.
+pub(crate) async fn upload_index_file_to_delta(
+    config: &DeltalakeTableConfig,
+    local_index_filepath: &str,
+    filesystem_accessor: &dyn BaseFileSystemAccess,
+) -> Result<String> {
+    let filename = Path::new(local_index_filepath)
+        .file_name()
+        .unwrap()
+        .to_str()
+        .unwrap();
+    let remote_filepath = get_remote_filepath(
+        config,
+        &format!(
+            "{FILE_INDEX_DIRECTORY}/{}-{}",
+            uuid::Uuid::now_v7(),
+            filename
+        ),
+    );
+    filesystem_accessor
+        .copy_from_local_to_remote(local_index_filepath, &remote_filepath)
+        .await?;
     Ok(remote_filepath)
 }
+
+/// Get a unique remote filepath to store one serialized mooncake file index.
+pub(crate) fn generate_unique_file_index_filepath(config: &DeltalakeTableConfig) -> String {
+    get_remote_filepath(
+        config,
+        &format!(
+            "{FILE_INDEX_DIRECTORY}/{}-file-index.json",
+            uuid::Uuid::now_v7()
+        ),
+    )
+}
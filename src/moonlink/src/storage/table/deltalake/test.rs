@@ -1,13 +1,22 @@
+use deltalake::kernel::engine::arrow_conversion::TryFromArrow;
+use deltalake::operations::create::CreateBuilder;
+use deltalake::TableProperty;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tempfile::TempDir;
 
 use crate::storage::filesystem::accessor::factory::create_filesystem_accessor;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::azure_test_utils;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::test_guard::TestGuard as AzureTestGuard;
 #[cfg(feature = "storage-s3")]
 use crate::storage::filesystem::s3::s3_test_utils;
 #[cfg(feature = "storage-s3")]
 use crate::storage::filesystem::s3::test_guard::TestGuard as S3TestGuard;
-#[cfg(feature = "storage-s3")]
+use crate::storage::index::FileIndex as MooncakeFileIndex;
+use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
+#[cfg(any(feature = "storage-s3", feature = "storage-azure"))]
 use crate::storage::mooncake_table::table_creation_test_utils::create_delta_table_config;
 use crate::storage::mooncake_table::table_creation_test_utils::{
     create_test_table_metadata, get_delta_table_config,
@@ -16,11 +25,13 @@ use crate::storage::mooncake_table::table_operation_test_utils::create_local_par
 use crate::storage::mooncake_table::{
     PersistenceSnapshotDataCompactionPayload, PersistenceSnapshotImportPayload,
     PersistenceSnapshotIndexMergePayload, PersistenceSnapshotPayload,
+    PersistenceSnapshotTruncationPayload,
 };
 use crate::storage::table::common::table_manager::TableManager;
 use crate::storage::table::common::table_manager::{PersistenceFileParams, PersistenceResult};
 use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
 use crate::storage::table::deltalake::deltalake_table_manager::DeltalakeTableManager;
+use crate::storage::table::deltalake::utils;
 use crate::{create_data_file, ObjectStorageCache};
 
 async fn test_basic_store_and_load_impl(delta_table_config: DeltalakeTableConfig) {
@@ -61,6 +72,7 @@ async fn test_basic_store_and_load_impl(delta_table_config: DeltalakeTableConfig
         },
         index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
         data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persist_result: PersistenceResult = delta_table_manager
@@ -112,6 +124,7 @@ async fn test_basic_store_and_load_impl(delta_table_config: DeltalakeTableConfig
             old_file_indices_to_remove: Vec::new(),
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persist_result: PersistenceResult = delta_table_manager
@@ -156,13 +169,431 @@ async fn test_basic_store_and_load() {
     test_basic_store_and_load_impl(delta_table_config).await;
 }
 
+#[tokio::test]
+async fn test_store_and_load_file_indices() {
+    let temp_dir = TempDir::new().unwrap();
+    let delta_table_config = get_delta_table_config(&temp_dir);
+    let mooncake_table_metadata = create_test_table_metadata(delta_table_config.location.clone());
+    let filesystem_accessor =
+        create_filesystem_accessor(delta_table_config.data_accessor_config.clone());
+    let mut delta_table_manager = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    // Persist one data file along with its file index.
+    let data_file = create_data_file(
+        /*file_id=*/ 0,
+        create_local_parquet_file(&temp_dir).await,
+    );
+    let file_index = MooncakeFileIndex {
+        files: vec![data_file.clone()],
+        num_rows: 3,
+        hash_bits: 0,
+        hash_upper_bits: 0,
+        hash_lower_bits: 0,
+        seg_id_bits: 0,
+        row_id_bits: 0,
+        bucket_bits: 0,
+        index_blocks: vec![],
+    };
+    let persistence_payload = PersistenceSnapshotPayload {
+        uuid: uuid::Uuid::new_v4(),
+        flush_lsn: 10,
+        committed_deletion_logs: HashSet::new(),
+        new_table_schema: None,
+        import_payload: PersistenceSnapshotImportPayload {
+            data_files: vec![data_file.clone()],
+            new_deletion_vector: HashMap::new(),
+            file_indices: vec![file_index],
+        },
+        index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
+        data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
+    };
+    let persist_result = delta_table_manager
+        .sync_snapshot(
+            persistence_payload,
+            PersistenceFileParams {
+                table_auto_incr_ids: 1..2,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Remote file index refers to remote data file.
+    assert_eq!(persist_result.remote_data_files.len(), 1);
+    assert_eq!(persist_result.remote_file_indices.len(), 1);
+    let remote_data_file = persist_result.remote_data_files[0].clone();
+    let remote_file_index = persist_result.remote_file_indices[0].clone();
+    assert_eq!(remote_file_index.files, vec![remote_data_file.clone()]);
+
+    // Load file index and row count from delta table.
+    let mut reload_mgr = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = reload_mgr.load_snapshot_from_table().await.unwrap();
+    let (loaded_data_file, disk_file_entry) = snapshot.disk_files.iter().next().unwrap();
+    assert_eq!(loaded_data_file.file_path(), remote_data_file.file_path());
+    assert_eq!(disk_file_entry.num_rows, 3);
+    assert_eq!(snapshot.indices.file_indices.len(), 1);
+    assert_eq!(
+        snapshot.indices.file_indices[0].files,
+        vec![loaded_data_file.clone()]
+    );
+    assert_eq!(snapshot.indices.file_indices[0].num_rows, 3);
+
+    // Remove data file and its file index, which are no longer loaded.
+    let persistence_payload = PersistenceSnapshotPayload {
+        uuid: uuid::Uuid::new_v4(),
+        flush_lsn: 20,
+        committed_deletion_logs: HashSet::new(),
+        new_table_schema: None,
+        import_payload: PersistenceSnapshotImportPayload::default(),
+        index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
+        data_compaction_payload: PersistenceSnapshotDataCompactionPayload {
+            new_data_files_to_import: Vec::new(),
+            old_data_files_to_remove: vec![remote_data_file],
+            new_file_indices_to_import: Vec::new(),
+            old_file_indices_to_remove: vec![remote_file_index],
+            data_file_records_remap: HashMap::new(),
+        },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
+    };
+    delta_table_manager
+        .sync_snapshot(
+            persistence_payload,
+            PersistenceFileParams {
+                table_auto_incr_ids: 2..3,
+            },
+        )
+        .await
+        .unwrap();
+
+    let mut reload_mgr = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = reload_mgr.load_snapshot_from_table().await.unwrap();
+    assert!(snapshot.disk_files.is_empty());
+    assert!(snapshot.indices.file_indices.is_empty());
+    assert_eq!(snapshot.flush_lsn.unwrap(), 20);
+}
+
+#[tokio::test]
+async fn test_store_and_load_deletion_vectors() {
+    let temp_dir = TempDir::new().unwrap();
+    let delta_table_config = get_delta_table_config(&temp_dir);
+    let mooncake_table_metadata = create_test_table_metadata(delta_table_config.location.clone());
+    let filesystem_accessor =
+        create_filesystem_accessor(delta_table_config.data_accessor_config.clone());
+    let mut delta_table_manager = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    // Persist one data file along with its deletion vector.
+    let data_file = create_data_file(
+        /*file_id=*/ 0,
+        create_local_parquet_file(&temp_dir).await,
+    );
+    let mut deletion_vector = BatchDeletionVector::new(/*max_rows=*/ 3);
+    assert!(deletion_vector.delete_row(0));
+    let persistence_payload = PersistenceSnapshotPayload {
+        uuid: uuid::Uuid::new_v4(),
+        flush_lsn: 10,
+        committed_deletion_logs: HashSet::new(),
+        new_table_schema: None,
+        import_payload: PersistenceSnapshotImportPayload {
+            data_files: vec![data_file.clone()],
+            new_deletion_vector: HashMap::from([(data_file.clone(), deletion_vector)]),
+            file_indices: vec![],
+        },
+        index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
+        data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
+    };
+    let persist_result = delta_table_manager
+        .sync_snapshot(
+            persistence_payload,
+            PersistenceFileParams {
+                table_auto_incr_ids: 1..2,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(persist_result.puffin_blob_ref.len(), 1);
+    assert_eq!(
+        persist_result.puffin_blob_ref[&data_file.file_id()].num_rows,
+        1
+    );
+    let remote_data_file = persist_result.remote_data_files[0].clone();
+
+    // Delete another row of the persisted data file.
+    let mut deletion_vector = BatchDeletionVector::new(/*max_rows=*/ 3);
+    assert!(deletion_vector.delete_row(2));
+    let persistence_payload = PersistenceSnapshotPayload {
+        uuid: uuid::Uuid::new_v4(),
+        flush_lsn: 20,
+        committed_deletion_logs: HashSet::new(),
+        new_table_schema: None,
+        import_payload: PersistenceSnapshotImportPayload {
+            data_files: vec![],
+            new_deletion_vector: HashMap::from([(remote_data_file.clone(), deletion_vector)]),
+            file_indices: vec![],
+        },
+        index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
+        data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
+    };
+    let persist_result = delta_table_manager
+        .sync_snapshot(
+            persistence_payload,
+            PersistenceFileParams {
+                table_auto_incr_ids: 2..3,
+            },
+        )
+        .await
+        .unwrap();
+    // Persisted deletion vector contains all deleted rows for the data file.
+    assert_eq!(
+        persist_result.puffin_blob_ref[&remote_data_file.file_id()].num_rows,
+        2
+    );
+
+    // Load deletion vectors from delta table.
+    let mut reload_mgr = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = reload_mgr.load_snapshot_from_table().await.unwrap();
+    assert_eq!(snapshot.disk_files.len(), 1);
+    let (loaded_data_file, disk_file_entry) = snapshot.disk_files.iter().next().unwrap();
+    assert_eq!(loaded_data_file.file_path(), remote_data_file.file_path());
+    assert_eq!(
+        disk_file_entry
+            .committed_deletion_vector
+            .collect_deleted_rows(),
+        vec![0, 2]
+    );
+    assert_eq!(
+        disk_file_entry
+            .puffin_deletion_blob
+            .as_ref()
+            .unwrap()
+            .num_rows,
+        2
+    );
+    assert_eq!(snapshot.flush_lsn.unwrap(), 20);
+}
+
+/// Testing scenario: deletion vectors are persisted to a pre-existing delta table, which is created without deletion vectors.
+#[tokio::test]
+async fn test_store_deletion_vectors_to_existing_table() {
+    let temp_dir = TempDir::new().unwrap();
+    let delta_table_config = get_delta_table_config(&temp_dir);
+    let mooncake_table_metadata = create_test_table_metadata(delta_table_config.location.clone());
+    let delta_schema =
+        deltalake::kernel::Schema::try_from_arrow(mooncake_table_metadata.schema.as_ref()).unwrap();
+    let existing_table = CreateBuilder::new()
+        .with_location(delta_table_config.location.clone())
+        .with_columns(delta_schema.fields().cloned())
+        .await
+        .unwrap();
+    assert!(!existing_table
+        .snapshot()
+        .unwrap()
+        .metadata()
+        .configuration()
+        .contains_key(TableProperty::EnableDeletionVectors.as_ref()));
+
+    let filesystem_accessor =
+        create_filesystem_accessor(delta_table_config.data_accessor_config.clone());
+    let mut delta_table_manager = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let data_file = create_data_file(
+        /*file_id=*/ 0,
+        create_local_parquet_file(&temp_dir).await,
+    );
+    let mut deletion_vector = BatchDeletionVector::new(/*max_rows=*/ 3);
+    assert!(deletion_vector.delete_row(1));
+    let persistence_payload = PersistenceSnapshotPayload {
+        uuid: uuid::Uuid::new_v4(),
+        flush_lsn: 10,
+        committed_deletion_logs: HashSet::new(),
+        new_table_schema: None,
+        import_payload: PersistenceSnapshotImportPayload {
+            data_files: vec![data_file.clone()],
+            new_deletion_vector: HashMap::from([(data_file.clone(), deletion_vector)]),
+            file_indices: vec![],
+        },
+        index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
+        data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
+    };
+    delta_table_manager
+        .sync_snapshot(
+            persistence_payload,
+            PersistenceFileParams {
+                table_auto_incr_ids: 1..2,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Deletion vectors get enabled on the existing table.
+    let table = utils::get_deltalake_table_if_exists(&delta_table_config)
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        table
+            .snapshot()
+            .unwrap()
+            .metadata()
+            .configuration()
+            .get(TableProperty::EnableDeletionVectors.as_ref())
+            .unwrap(),
+        "true"
+    );
+
+    // Load deletion vectors from delta table.
+    let mut reload_mgr = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = reload_mgr.load_snapshot_from_table().await.unwrap();
+    assert_eq!(snapshot.disk_files.len(), 1);
+    let disk_file_entry = snapshot.disk_files.values().next().unwrap();
+    assert_eq!(
+        disk_file_entry
+            .committed_deletion_vector
+            .collect_deleted_rows(),
+        vec![1]
+    );
+    assert_eq!(snapshot.flush_lsn.unwrap(), 10);
+}
+
+/// Testing scenario: commits made by other writers after moonlink's don't lose flush LSN at recovery.
+#[tokio::test]
+async fn test_load_after_external_commit() {
+    let temp_dir = TempDir::new().unwrap();
+    let delta_table_config = get_delta_table_config(&temp_dir);
+    let mooncake_table_metadata = create_test_table_metadata(delta_table_config.location.clone());
+    let filesystem_accessor =
+        create_filesystem_accessor(delta_table_config.data_accessor_config.clone());
+    let mut delta_table_manager = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let data_file = create_data_file(
+        /*file_id=*/ 0,
+        create_local_parquet_file(&temp_dir).await,
+    );
+    let persistence_payload = PersistenceSnapshotPayload {
+        uuid: uuid::Uuid::new_v4(),
+        flush_lsn: 10,
+        committed_deletion_logs: HashSet::new(),
+        new_table_schema: None,
+        import_payload: PersistenceSnapshotImportPayload {
+            data_files: vec![data_file.clone()],
+            new_deletion_vector: HashMap::new(),
+            file_indices: vec![],
+        },
+        index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
+        data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
+    };
+    delta_table_manager
+        .sync_snapshot(
+            persistence_payload,
+            PersistenceFileParams {
+                table_auto_incr_ids: 1..2,
+            },
+        )
+        .await
+        .unwrap();
+
+    // Commit from another writer, which doesn't carry moonlink app metadata.
+    let table = utils::get_deltalake_table_if_exists(&delta_table_config)
+        .await
+        .unwrap()
+        .unwrap();
+    deltalake::DeltaOps(table)
+        .set_tbl_properties()
+        .with_properties(HashMap::from([(
+            TableProperty::LogRetentionDuration.as_ref().to_string(),
+            "interval 30 days".to_string(),
+        )]))
+        .await
+        .unwrap();
+
+    let mut reload_mgr = DeltalakeTableManager::new(
+        mooncake_table_metadata.clone(),
+        Arc::new(ObjectStorageCache::default_for_test(&temp_dir)),
+        filesystem_accessor.clone(),
+        delta_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = reload_mgr.load_snapshot_from_table().await.unwrap();
+    assert_eq!(snapshot.disk_files.len(), 1);
+    assert_eq!(snapshot.flush_lsn.unwrap(), 10);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[cfg(feature = "storage-s3")]
 async fn test_basic_store_and_load_with_s3() {
-    deltalake::aws::register_handlers(None);
+    utils::register_deltalake_storage_handlers();
     let (bucket, warehouse_uri) = s3_test_utils::get_test_s3_bucket_and_warehouse();
     let _test_guard = S3TestGuard::new(bucket.clone()).await;
     let delta_table_config = create_delta_table_config(warehouse_uri);
 
     test_basic_store_and_load_impl(delta_table_config).await;
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_basic_store_and_load_with_azure() {
+    utils::register_deltalake_storage_handlers();
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let delta_table_config = create_delta_table_config(warehouse_uri);
+
+    test_basic_store_and_load_impl(delta_table_config).await;
+}
@@ -1,22 +1,64 @@
 use deltalake::kernel::engine::arrow_conversion::TryFromArrow;
 use deltalake::open_table_with_storage_options;
-use deltalake::{operations::create::CreateBuilder, DeltaTable};
+use deltalake::{
+    operations::create::CreateBuilder, DeltaOps, DeltaTable, DeltaTableError, TableProperty,
+};
+use serde_json::Value;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, Once};
 use url::Url;
 
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
 use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
+use crate::storage::table::common::MOONCAKE_TABLE_FLUSH_LSN;
 use crate::storage::table::deltalake::deltalake_table_config::DeltalakeTableConfig;
 use crate::storage::StorageConfig as MoonlinkStorgaeConfig;
 use crate::CacheTrait;
-use crate::Result;
+use crate::{Error, Result};
+
+/// Commit app metadata key, which records remote filepaths of all serialized file indices in the table.
+pub(crate) const MOONCAKE_TABLE_FILE_INDICES: &str = "moonlink.table-file-indices";
 
 /// Known schema prefix for deltalake location.
-const KNOWN_SCHEME_PREFIXS: &[&str] = &["file://", "http://", "https://", "s3://", "gs://"];
+const KNOWN_SCHEME_PREFIXS: &[&str] = &[
+    "file://", "http://", "https://", "s3://", "gs://", "abfss://",
+];
+
+/// GCS endpoint compatible with S3 API, which accepts HMAC key.
+#[cfg(feature = "storage-gcs")]
+const GCS_S3_COMPATIBLE_ENDPOINT: &str = "https://storage.googleapis.com";
+
+static REGISTER_STORAGE_HANDLERS: Once = Once::new();
+
+/// Register object store handlers for remote delta table locations, which should be called once at startup.
+pub fn register_deltalake_storage_handlers() {
+    REGISTER_STORAGE_HANDLERS.call_once(|| {
+        #[cfg(any(feature = "storage-s3", feature = "storage-gcs"))]
+        deltalake::aws::register_handlers(/*additional_prefixes=*/ None);
+        #[cfg(feature = "storage-gcs")]
+        deltalake::gcp::register_handlers(/*additional_prefixes=*/ None);
+        #[cfg(feature = "storage-azure")]
+        deltalake::azure::register_handlers(/*additional_prefixes=*/ None);
+    });
+}
+
+/// Get location to access deltalake table with.
+/// GCS object store in deltalake doesn't accept HMAC key, so production GCS tables are accessed via S3 compatible API, same as iceberg.
+fn get_deltalake_table_location(config: &DeltalakeTableConfig) -> String {
+    match &config.data_accessor_config.storage_config {
+        #[cfg(feature = "storage-gcs")]
+        MoonlinkStorgaeConfig::Gcs {
+            disable_auth: false,
+            ..
+        } => match config.location.strip_prefix("gs://") {
+            Some(path) => format!("s3://{path}"),
+            None => config.location.clone(),
+        },
+        _ => config.location.clone(),
+    }
+}
 
 /// Sanitize deltalake table location, to ensure it conforms URL style.
-#[allow(unused)]
 fn sanitize_deltalake_table_location(location: &str) -> String {
     if KNOWN_SCHEME_PREFIXS
         .iter()
@@ -54,6 +96,63 @@ fn get_storage_option(storage_config: &MoonlinkStorgaeConfig) -> HashMap<String,
                 storage_options.insert("AWS_S3_ALLOW_UNSAFE_RENAME".into(), "true".into());
             }
         }
+        #[cfg(feature = "storage-gcs")]
+        MoonlinkStorgaeConfig::Gcs {
+            region,
+            access_key_id,
+            secret_access_key,
+            endpoint,
+            disable_auth,
+            ..
+        } => {
+            if *disable_auth {
+                // Used for fake GCS server, which takes base url from service account key and skips authentication.
+                let service_account_key = serde_json::json!({
+                    "gcs_base_url": endpoint.clone().unwrap_or_default(),
+                    "disable_oauth": true,
+                    "client_email": "",
+                    "private_key": "",
+                    "private_key_id": "",
+                });
+                storage_options.insert(
+                    "GOOGLE_SERVICE_ACCOUNT_KEY".into(),
+                    service_account_key.to_string(),
+                );
+                storage_options.insert("GOOGLE_SKIP_SIGNATURE".into(), "true".into());
+                storage_options.insert("ALLOW_HTTP".into(), "true".into());
+            } else {
+                storage_options.insert("AWS_ACCESS_KEY_ID".into(), access_key_id.clone());
+                storage_options.insert("AWS_SECRET_ACCESS_KEY".into(), secret_access_key.clone());
+                storage_options.insert("AWS_REGION".into(), region.clone());
+                storage_options.insert(
+                    "AWS_ENDPOINT_URL".into(),
+                    GCS_S3_COMPATIBLE_ENDPOINT.to_string(),
+                );
+                // GCS provides strong consistency for conditional writes, but no DynamoDB style locking.
+                storage_options.insert("AWS_S3_ALLOW_UNSAFE_RENAME".into(), "true".into());
+            }
+        }
+        #[cfg(feature = "storage-azure")]
+        MoonlinkStorgaeConfig::Azblob {
+            account_name,
+            container: _,
+            account_key,
+            sas_token,
+            endpoint,
+        } => {
+            storage_options.insert("AZURE_STORAGE_ACCOUNT_NAME".into(), account_name.clone());
+            if let Some(account_key) = account_key {
+                storage_options.insert("AZURE_STORAGE_ACCOUNT_KEY".into(), account_key.clone());
+            } else if let Some(sas_token) = sas_token {
+                storage_options.insert("AZURE_STORAGE_SAS_TOKEN".into(), sas_token.clone());
+            }
+
+            if let Some(endpoint) = endpoint {
+                storage_options.insert("AZURE_STORAGE_ENDPOINT".into(), endpoint.clone());
+                // Used for Azurite emulator.
+                storage_options.insert("AZURE_ALLOW_HTTP".into(), "true".into());
+            }
+        }
         _ => {}
     }
 
@@ -63,9 +162,8 @@ fn get_storage_option(storage_config: &MoonlinkStorgaeConfig) -> HashMap<String,
 /// Get or create a Delta table at the given location.
 ///
 /// - If the table doesn't exist → create a new one using the Arrow schema.
-/// - If it already exists → load and return.
+/// - If it already exists → load, enable deletion vectors if not yet, and return.
 /// - This mirrors the Iceberg `get_or_create_iceberg_table` pattern.
-#[allow(unused)]
 pub(crate) async fn get_or_create_deltalake_table(
     mooncake_table_metadata: Arc<MooncakeTableMetadata>,
     _object_storage_cache: Arc<dyn CacheTrait>,
@@ -73,28 +171,53 @@ pub(crate) async fn get_or_create_deltalake_table(
     config: DeltalakeTableConfig,
 ) -> Result<DeltaTable> {
     let storage_options = get_storage_option(&config.data_accessor_config.storage_config);
-    let table_location = sanitize_deltalake_table_location(&config.location);
-    let table_url = Url::parse(&table_location)?;
+    let location = get_deltalake_table_location(&config);
+    let table_url = Url::parse(&sanitize_deltalake_table_location(&location))?;
     match open_table_with_storage_options(table_url, storage_options.clone()).await {
-        Ok(existing_table) => Ok(existing_table),
-        Err(_) => {
+        Ok(existing_table) => enable_deletion_vectors_if_absent(existing_table).await,
+        // Only create table when it doesn't exist, other failures (i.e. auth, network) are surfaced.
+        Err(DeltaTableError::NotATable(_) | DeltaTableError::InvalidTableLocation(_)) => {
             let arrow_schema = mooncake_table_metadata.schema.as_ref();
             let delta_schema_struct = deltalake::kernel::Schema::try_from_arrow(arrow_schema)?;
             let delta_schema_fields: Vec<deltalake::kernel::StructField> =
                 delta_schema_struct.fields().cloned().collect();
 
             let table = CreateBuilder::new()
-                .with_location(config.location.clone())
+                .with_location(location)
                 .with_columns(delta_schema_fields)
+                // Deletion vectors require the matching reader and writer features on the table protocol.
+                .with_configuration_property(TableProperty::EnableDeletionVectors, Some("true"))
                 .with_save_mode(deltalake::protocol::SaveMode::ErrorIfExists)
                 .with_storage_options(storage_options)
                 .await?;
             Ok(table)
         }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Deletion vectors are persisted at every sync, so tables created elsewhere (or by older versions) need the table
+/// property, which also upgrades table protocol with the required reader and writer features.
+async fn enable_deletion_vectors_if_absent(table: DeltaTable) -> Result<DeltaTable> {
+    let deletion_vectors_enabled = table
+        .snapshot()?
+        .metadata()
+        .configuration()
+        .get(TableProperty::EnableDeletionVectors.as_ref())
+        .is_some_and(|value| value.eq_ignore_ascii_case("true"));
+    if deletion_vectors_enabled {
+        return Ok(table);
     }
+    let table = DeltaOps(table)
+        .set_tbl_properties()
+        .with_properties(HashMap::from([(
+            TableProperty::EnableDeletionVectors.as_ref().to_string(),
+            "true".to_string(),
+        )]))
+        .await?;
+    Ok(table)
 }
 
-#[allow(unused)]
 fn get_deltalake_table_url(location: &str) -> Result<Url> {
     if KNOWN_SCHEME_PREFIXS
         .iter()
@@ -107,14 +230,57 @@ fn get_deltalake_table_url(location: &str) -> Result<Url> {
     Ok(url)
 }
 
-#[allow(unused)]
 pub(crate) async fn get_deltalake_table_if_exists(
     config: &DeltalakeTableConfig,
 ) -> Result<Option<DeltaTable>> {
-    let table_url = get_deltalake_table_url(&config.location)?;
+    let table_url = get_deltalake_table_url(&get_deltalake_table_location(config))?;
     let storage_options = get_storage_option(&config.data_accessor_config.storage_config);
     match open_table_with_storage_options(table_url, storage_options).await {
         Ok(table) => Ok(Some(table)),
-        Err(_) => Ok(None),
+        Err(DeltaTableError::NotATable(_) | DeltaTableError::InvalidTableLocation(_)) => Ok(None),
+        Err(e) => Err(e.into()),
     }
 }
+
+/// Get flush LSN and app metadata of the latest commit written by moonlink, which records them at every snapshot persistence.
+/// Commits from other writers (i.e. OPTIMIZE, VACUUM) don't carry moonlink app metadata, so they're skipped.
+/// Return [`None`] if no commit has been made by moonlink.
+pub(crate) async fn get_latest_moonlink_commit_app_metadata(
+    table: &DeltaTable,
+) -> Result<Option<(u64, HashMap<String, Value>)>> {
+    // Commit history is listed from the latest commit.
+    let commit_infos = table.history(/*limit=*/ None).await?;
+    for commit_info in commit_infos {
+        if let Some(flush_lsn) = get_flush_lsn(&commit_info.info)? {
+            return Ok(Some((flush_lsn, commit_info.info.clone())));
+        }
+    }
+    Ok(None)
+}
+
+/// Get flush LSN from commit app metadata.
+fn get_flush_lsn(app_metadata: &HashMap<String, Value>) -> Result<Option<u64>> {
+    let Some(flush_lsn) = app_metadata.get(MOONCAKE_TABLE_FLUSH_LSN) else {
+        return Ok(None);
+    };
+    let flush_lsn = flush_lsn.as_u64().ok_or_else(|| {
+        Error::delta_generic_error(format!(
+            "Invalid flush LSN {flush_lsn} in delta commit app metadata"
+        ))
+    })?;
+    Ok(Some(flush_lsn))
+}
+
+/// Get remote filepaths of serialized file indices from commit app metadata.
+pub(crate) fn get_file_index_filepaths(
+    app_metadata: &HashMap<String, Value>,
+) -> Result<Vec<String>> {
+    let Some(filepaths) = app_metadata.get(MOONCAKE_TABLE_FILE_INDICES) else {
+        return Ok(vec![]);
+    };
+    serde_json::from_value(filepaths.clone()).map_err(|e| {
+        Error::delta_generic_error(format!(
+            "Invalid file index filepaths {filepaths} in delta commit app metadata: {e}"
+        ))
+    })
+}
@@ -19,6 +19,7 @@ pub(crate) mod moonlink_catalog;
 pub(crate) mod parquet_metadata_utils;
 pub(crate) mod parquet_stats_utils;
 pub(crate) mod parquet_utils;
+pub(crate) mod partition_utils;
 pub(crate) mod puffin_utils;
 pub(crate) mod puffin_writer_proxy;
 mod table_update_proxy;
@@ -30,7 +31,9 @@ pub(crate) mod glue_catalog;
 pub(crate) mod rest_catalog;
 
 mod schema_utils;
+mod snapshot_expiration;
 mod snapshot_utils;
+pub(crate) mod sort_order_utils;
 mod table_commit_proxy;
 pub(crate) mod table_property;
 pub(crate) mod utils;
@@ -44,6 +47,10 @@ mod s3_test_utils;
 #[cfg(test)]
 mod gcs_test_utils;
 
+#[cfg(feature = "storage-azure")]
+#[cfg(test)]
+mod azure_test_utils;
+
 #[cfg(test)]
 mod tests;
 
@@ -0,0 +1,8 @@
+use crate::storage::filesystem::azure::azure_test_utils::*;
+use crate::storage::table::iceberg::file_catalog::FileCatalog;
+use crate::storage::table::iceberg::file_catalog_test_utils::*;
+
+pub(crate) fn create_azure_catalog(warehouse_uri: &str) -> FileCatalog {
+    let storage_config = create_azure_storage_config(warehouse_uri);
+    FileCatalog::new(storage_config, get_test_schema()).unwrap()
+}
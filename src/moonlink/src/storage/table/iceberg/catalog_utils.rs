@@ -112,6 +112,9 @@ pub(crate) fn reflect_table_updates(
             TableUpdate::SetCurrentSchema { schema_id } => {
                 builder = builder.set_current_schema(*schema_id)?;
             }
+            TableUpdate::RemoveSnapshots { snapshot_ids } => {
+                builder = builder.remove_snapshots(snapshot_ids);
+            }
             _ => {
                 unreachable!("Unimplemented table update: {:?}", update);
             }
@@ -202,7 +205,7 @@ pub(crate) async fn update_table_impl(
     let requirements = commit.take_requirements();
 
     let builder = TableMetadataBuilder::new_from_metadata(metadata.clone(), None);
-    let updates = commit.take_updates();
+    let updates = table_update_proxy.rewrite_snapshot_operation(commit.take_updates());
     let builder = reflect_table_updates(builder, updates)?;
     let build_result = builder.build()?;
 
@@ -95,6 +95,7 @@ fn get_data_compaction_config() -> DataCompactionConfig {
         max_data_file_to_compact: u32::MAX,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     }
 }
 
@@ -1,9 +1,9 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use iceberg::io::FileIO;
 use iceberg::spec::{
-    ManifestContentType, ManifestEntry, ManifestFile, ManifestMetadata, ManifestWriter,
+    ManifestContentType, ManifestEntry, ManifestFile, ManifestMetadata, ManifestWriter, Struct,
     TableMetadata,
 };
 use iceberg::Result as IcebergResult;
@@ -89,6 +89,20 @@ impl<'a> DataFileManifestManager<'a> {
         Ok(())
     }
 
+    /// Record partition tuple for all data files in the given manifest entries, keyed by data file path.
+    pub(crate) fn collect_data_file_partitions(
+        manifest_entries: &[Arc<ManifestEntry>],
+        data_file_partitions: &mut HashMap<String, Struct>,
+    ) {
+        for cur_manifest_entry in manifest_entries.iter() {
+            let data_file = cur_manifest_entry.data_file();
+            data_file_partitions.insert(
+                data_file.file_path().to_string(),
+                data_file.partition().clone(),
+            );
+        }
+    }
+
     /// Finalize the current manifest file and return.
     pub(crate) async fn finalize(self) -> IcebergResult<Option<ManifestFile>> {
         if let Some(writer) = self.writer {
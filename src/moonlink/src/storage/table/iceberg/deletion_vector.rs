@@ -1,3 +1,4 @@
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
 /// Iceberg deletion vector is the persistent format of in-memory BatchDeletionVector.
 /// On persistence stage, batch deletion vector is converted to iceberg one, by serializing corresponding roaring bitmap and its properties;
@@ -231,9 +232,17 @@ impl DeletionVector {
     /// Load deletion vector from puffin file blob.
     ///
     /// TODO(hjiang): Add unit test for load blob from local filesystem.
-    pub async fn load_from_dv_blob(file_io: FileIO, puffin_file: &DataFile) -> IcebergResult<Self> {
-        let blob =
-            puffin_utils::load_blob_from_puffin_file(file_io, puffin_file.file_path()).await?;
+    pub async fn load_from_dv_blob(
+        file_io: FileIO,
+        puffin_file: &DataFile,
+        encryption_key: Option<&DataEncryptionKey>,
+    ) -> IcebergResult<Self> {
+        let blob = puffin_utils::load_blob_from_puffin_file(
+            file_io,
+            puffin_file.file_path(),
+            encryption_key,
+        )
+        .await?;
         DeletionVector::deserialize(blob)
     }
 
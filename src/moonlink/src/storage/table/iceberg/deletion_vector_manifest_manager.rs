@@ -17,6 +17,7 @@ use iceberg::spec::{
     ManifestWriter, Struct, TableMetadata,
 };
 use iceberg::Result as IcebergResult;
+use iceberg::{Error as IcebergError, ErrorKind};
 
 pub(crate) struct DeletionVectorManifestManager<'a> {
     table_metadata: &'a TableMetadata,
@@ -87,14 +88,24 @@ impl<'a> DeletionVectorManifestManager<'a> {
         Ok(())
     }
 
+    /// Add new deletion vector puffin blobs.
+    ///
+    /// # Arguments
+    ///
+    /// * data_file_partitions: partition tuple for all data files in the current snapshot, keyed by data file path; deletion vectors share the same partition with their referenced data files.
     pub(crate) fn add_new_puffin_blobs(
         &mut self,
         deletion_vector_blobs_to_add: &HashMap<String, Vec<PuffinBlobMetadataProxy>>,
+        data_file_partitions: &HashMap<String, Struct>,
     ) -> IcebergResult<()> {
         for (puffin_filepath, blob_metadata) in deletion_vector_blobs_to_add.iter() {
             for cur_blob_metadata in blob_metadata.iter() {
-                let (referenced_data_filepath, data_file) =
-                    get_data_file_for_deletion_vector(puffin_filepath, cur_blob_metadata);
+                let (referenced_data_filepath, data_file) = get_data_file_for_deletion_vector(
+                    self.table_metadata,
+                    puffin_filepath,
+                    cur_blob_metadata,
+                    data_file_partitions,
+                )?;
                 self.existing_deletion_vector_entries
                     .remove(&referenced_data_filepath);
                 self.init_writer_for_once()?;
@@ -131,21 +142,32 @@ impl<'a> DeletionVectorManifestManager<'a> {
 
 /// Util function to get `DataFileProxy` for deletion vector puffin blob.
 fn get_data_file_for_deletion_vector(
+    table_metadata: &TableMetadata,
     puffin_filepath: &str,
     blob_metadata: &PuffinBlobMetadataProxy,
-) -> (String /*referenced_data_filepath*/, DataFile) {
+    data_file_partitions: &HashMap<String, Struct>,
+) -> IcebergResult<(String /*referenced_data_filepath*/, DataFile)> {
     assert_eq!(blob_metadata.r#type, DELETION_VECTOR_V1);
     let referenced_data_filepath = blob_metadata
         .properties
         .get(DELETION_VECTOR_REFERENCED_DATA_FILE)
         .unwrap()
         .clone();
+    let partition = data_file_partitions
+        .get(&referenced_data_filepath)
+        .ok_or_else(|| {
+            IcebergError::new(
+                ErrorKind::DataInvalid,
+                format!("Referenced data file {referenced_data_filepath} doesn't exist in the current snapshot"),
+            )
+        })?
+        .clone();
 
     let data_file_proxy = DataFileProxy {
         content: DataContentType::PositionDeletes,
         file_path: puffin_filepath.to_string(),
         file_format: DataFileFormat::Puffin,
-        partition: Struct::empty(),
+        partition,
         record_count: blob_metadata
             .properties
             .get(DELETION_VECTOR_CADINALITY)
@@ -164,11 +186,11 @@ fn get_data_file_for_deletion_vector(
         equality_ids: Vec::new(),
         sort_order_id: None,
         first_row_id: None,
-        partition_spec_id: 0,
+        partition_spec_id: table_metadata.default_partition_spec_id(),
         referenced_data_file: Some(referenced_data_filepath.clone()),
         content_offset: Some(blob_metadata.offset as i64),
         content_size_in_bytes: Some(blob_metadata.length as i64),
     };
     let data_file = unsafe { std::mem::transmute::<DataFileProxy, DataFile>(data_file_proxy) };
-    (referenced_data_filepath, data_file)
+    Ok((referenced_data_filepath, data_file))
 }
@@ -50,7 +50,8 @@ use tokio::sync::Mutex;
 use async_trait::async_trait;
 use iceberg::io::FileIO;
 use iceberg::spec::{
-    Schema as IcebergSchema, TableMetadata, TableMetadataBuildResult, TableMetadataBuilder,
+    Operation, Schema as IcebergSchema, TableMetadata, TableMetadataBuildResult,
+    TableMetadataBuilder,
 };
 use iceberg::table::Table;
 use iceberg::Error as IcebergError;
@@ -257,6 +258,10 @@ impl PuffinWrite for FileCatalog {
             .set_index_puffin_files_to_remove(puffin_filepaths);
     }
 
+    fn set_snapshot_operation(&mut self, operation: Operation) {
+        self.table_update_proxy.set_snapshot_operation(operation);
+    }
+
     fn clear_puffin_metadata(&mut self) {
         self.table_update_proxy.clear();
     }
@@ -599,7 +604,9 @@ impl Catalog for FileCatalog {
         validate_table_requirements(commit.take_requirements(), &metadata)?;
 
         // Construct new metadata with updates.
-        let updates = commit.take_updates();
+        let updates = self
+            .table_update_proxy
+            .rewrite_snapshot_operation(commit.take_updates());
         let builder = reflect_table_updates(builder, updates)?;
         let metadata = builder.build()?.metadata;
 
@@ -1,4 +1,8 @@
 use crate::storage::filesystem::accessor_config::AccessorConfig;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::azure_test_utils;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::test_guard::TestGuard as AzureTestGuard;
 #[cfg(feature = "storage-gcs")]
 use crate::storage::filesystem::gcs::gcs_test_utils;
 #[cfg(feature = "storage-gcs")]
@@ -8,6 +12,8 @@ use crate::storage::filesystem::s3::s3_test_utils;
 #[cfg(feature = "storage-s3")]
 use crate::storage::filesystem::s3::test_guard::TestGuard as S3TestGuard;
 use crate::storage::filesystem::storage_config::StorageConfig;
+#[cfg(feature = "storage-azure")]
+use crate::storage::table::iceberg::azure_test_utils as iceberg_azure_test_utils;
 use crate::storage::table::iceberg::catalog_test_impl::*;
 use crate::storage::table::iceberg::file_catalog::FileCatalog;
 use crate::storage::table::iceberg::file_catalog::NAMESPACE_INDICATOR_OBJECT_NAME;
@@ -102,6 +108,14 @@ async fn create_gcs_catalog() -> (FileCatalog, GcsTestGuard) {
     let file_catalog = iceberg_gcs_test_utils::create_gcs_catalog(&warehouse_uri);
     (file_catalog, test_guard)
 }
+// Create Azure catalog with local azurite deployment and a random container.
+#[cfg(feature = "storage-azure")]
+async fn create_azure_catalog() -> (FileCatalog, AzureTestGuard) {
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let test_guard = AzureTestGuard::new(container).await;
+    let file_catalog = iceberg_azure_test_utils::create_azure_catalog(&warehouse_uri);
+    (file_catalog, test_guard)
+}
 
 /// ==============================
 /// Test with features
@@ -126,6 +140,12 @@ async fn test_catalog_namespace_operations_gcs() {
     let (catalog, _test_guard) = create_gcs_catalog().await;
     test_catalog_namespace_operations_impl(&catalog).await
 }
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_catalog_namespace_operations_azure() {
+    let (catalog, _test_guard) = create_azure_catalog().await;
+    test_catalog_namespace_operations_impl(&catalog).await
+}
 
 /// Table operations test.
 #[tokio::test]
@@ -146,6 +166,12 @@ async fn test_catalog_table_operations_gcs() {
     let (catalog, _test_guard) = create_gcs_catalog().await;
     test_catalog_table_operations_impl(&catalog).await
 }
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_catalog_table_operations_azure() {
+    let (catalog, _test_guard) = create_azure_catalog().await;
+    test_catalog_table_operations_impl(&catalog).await
+}
 
 /// List operation test.
 #[tokio::test]
@@ -166,6 +192,12 @@ async fn test_list_operation_gcs() {
     let (catalog, _test_guard) = create_gcs_catalog().await;
     test_list_operation_impl(&catalog).await
 }
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_list_operation_azure() {
+    let (catalog, _test_guard) = create_azure_catalog().await;
+    test_list_operation_impl(&catalog).await
+}
 
 const NAMESPACE: &str = "default";
 const TABLE: &str = "test_table";
@@ -189,6 +221,12 @@ async fn test_update_table_gcs() {
     let (mut catalog, _test_guard) = create_gcs_catalog().await;
     test_update_table_impl(&mut catalog, NAMESPACE.to_string(), TABLE.to_string()).await;
 }
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_update_table_azure() {
+    let (mut catalog, _test_guard) = create_azure_catalog().await;
+    test_update_table_impl(&mut catalog, NAMESPACE.to_string(), TABLE.to_string()).await;
+}
 
 /// Update schema test.
 #[tokio::test]
@@ -209,6 +247,12 @@ async fn test_update_schema_gcs() {
     let (mut catalog, _test_guard) = create_gcs_catalog().await;
     test_update_schema_impl(&mut catalog, NAMESPACE.to_string(), TABLE.to_string()).await;
 }
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_update_schema_azure() {
+    let (mut catalog, _test_guard) = create_azure_catalog().await;
+    test_update_schema_impl(&mut catalog, NAMESPACE.to_string(), TABLE.to_string()).await;
+}
 
 /// Requirement check failure.
 #[tokio::test]
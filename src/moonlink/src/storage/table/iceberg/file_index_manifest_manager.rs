@@ -3,6 +3,7 @@ use crate::storage::table::iceberg::index::{
 };
 use crate::storage::table::iceberg::manifest_utils;
 use crate::storage::table::iceberg::manifest_utils::ManifestEntryType;
+use crate::storage::table::iceberg::partition_utils;
 use crate::storage::table::iceberg::puffin_writer_proxy::DataFileProxy;
 use crate::storage::table::iceberg::puffin_writer_proxy::PuffinBlobMetadataProxy;
 
@@ -12,7 +13,7 @@ use std::sync::Arc;
 use iceberg::io::FileIO;
 use iceberg::spec::{
     DataContentType, DataFile, DataFileFormat, ManifestEntry, ManifestFile, ManifestMetadata,
-    ManifestWriter, Struct, TableMetadata,
+    ManifestWriter, TableMetadata,
 };
 use iceberg::Result as IcebergResult;
 
@@ -82,7 +83,11 @@ impl<'a> FileIndexManifestManager<'a> {
     ) -> IcebergResult<()> {
         for (puffin_filepath, blob_metadata) in file_index_blobs_to_add.iter() {
             for cur_blob_metadata in blob_metadata.iter() {
-                let data_file = get_data_file_for_file_index(puffin_filepath, cur_blob_metadata);
+                let data_file = get_data_file_for_file_index(
+                    self.table_metadata,
+                    puffin_filepath,
+                    cur_blob_metadata,
+                );
                 self.init_writer_for_once()?;
                 self.writer
                     .as_mut()
@@ -104,7 +109,9 @@ impl<'a> FileIndexManifestManager<'a> {
 }
 
 /// Util function to get `DataFileProxy` for new file index puffin blob.
+/// File indices could cover data files in multiple partitions, so they're assigned with null partition values.
 fn get_data_file_for_file_index(
+    table_metadata: &TableMetadata,
     puffin_filepath: &str,
     blob_metadata: &PuffinBlobMetadataProxy,
 ) -> DataFile {
@@ -113,7 +120,7 @@ fn get_data_file_for_file_index(
         content: DataContentType::Data,
         file_path: puffin_filepath.to_string(),
         file_format: DataFileFormat::Puffin,
-        partition: Struct::empty(),
+        partition: partition_utils::get_null_partition(table_metadata.default_partition_spec()),
         record_count: blob_metadata
             .properties
             .get(MOONCAKE_HASH_INDEX_V1_CARDINALITY)
@@ -132,7 +139,7 @@ fn get_data_file_for_file_index(
         equality_ids: Vec::new(),
         sort_order_id: None,
         first_row_id: None,
-        partition_spec_id: 0,
+        partition_spec_id: table_metadata.default_partition_spec_id(),
         referenced_data_file: None,
         content_offset: None,
         content_size_in_bytes: None,
@@ -9,7 +9,7 @@ use crate::storage::table::iceberg::table_update_proxy::TableUpdateProxy;
 use crate::StorageConfig;
 use async_trait::async_trait;
 use iceberg::io::{FileIO, S3_ACCESS_KEY_ID, S3_ENDPOINT, S3_REGION, S3_SECRET_ACCESS_KEY};
-use iceberg::spec::{Schema as IcebergSchema, TableMetadata};
+use iceberg::spec::{Operation, Schema as IcebergSchema, TableMetadata};
 use iceberg::table::Table;
 use iceberg::CatalogBuilder;
 use iceberg::Error as IcebergError;
@@ -269,6 +269,10 @@ impl PuffinWrite for GlueCatalog {
             .set_index_puffin_files_to_remove(puffin_filepaths);
     }
 
+    fn set_snapshot_operation(&mut self, operation: Operation) {
+        self.table_update_proxy.set_snapshot_operation(operation);
+    }
+
     fn clear_puffin_metadata(&mut self) {
         self.table_update_proxy.clear();
     }
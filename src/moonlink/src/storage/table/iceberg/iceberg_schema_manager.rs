@@ -1,5 +1,6 @@
 use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
 use crate::storage::table::iceberg::iceberg_table_manager::*;
+use crate::storage::table::iceberg::schema_utils;
 use crate::Result;
 
 use iceberg::arrow as IcebergArrow;
@@ -15,7 +16,16 @@ impl IcebergTableManager {
         self.initialize_iceberg_table_for_once().await?;
 
         let table_ident = self.get_table_ident();
+        // Field ids are carried over from arrow field metadata, so renamed and promoted columns remain the same iceberg fields.
         let new_schema = IcebergArrow::arrow_schema_to_schema(&updated_table_metadata.schema)?;
+        schema_utils::validate_schema_evolution(
+            self.iceberg_table
+                .as_ref()
+                .unwrap()
+                .metadata()
+                .current_schema(),
+            &new_schema,
+        )?;
         let updated_table = self
             .catalog
             .update_table_schema(new_schema, table_ident)
@@ -49,8 +49,10 @@ impl IcebergTableManager {
         }
 
         // Load mooncake file indices from iceberg file index blobs.
+        let encryption_key = self.mooncake_table_metadata.config.get_encryption_key();
         let file_index_blob =
-            FileIndexBlob::load_from_index_blob(file_io.clone(), entry.data_file()).await?;
+            FileIndexBlob::load_from_index_blob(file_io.clone(), entry.data_file(), encryption_key)
+                .await?;
         let mut cur_iceberg_file_index = file_index_blob.file_index;
         let table_id = TableId(self.mooncake_table_metadata.table_id);
         let mooncake_file_index = cur_iceberg_file_index
@@ -60,6 +62,7 @@ impl IcebergTableManager {
                 self.filesystem_accessor.as_ref(),
                 table_id,
                 next_file_id,
+                encryption_key,
             )
             .await?;
 
@@ -120,7 +123,12 @@ impl IcebergTableManager {
         let data_file_entry = self.persisted_data_files.get_mut(data_file_id).unwrap();
 
         IcebergValidation::validate_puffin_manifest_entry(entry)?;
-        let deletion_vector = DeletionVector::load_from_dv_blob(file_io.clone(), data_file).await?;
+        let deletion_vector = DeletionVector::load_from_dv_blob(
+            file_io.clone(),
+            data_file,
+            self.mooncake_table_metadata.config.get_encryption_key(),
+        )
+        .await?;
         let num_rows = data_file.record_count();
 
         let batch_deletion_vector = deletion_vector.take_as_batch_delete_vector();
@@ -206,6 +214,7 @@ impl IcebergTableManager {
                     cache_handle: None,
                     puffin_deletion_blob,
                     committed_deletion_vector: data_file_entry.deletion_vector.clone(),
+                    sort_key_range: None,
                 },
             );
         }
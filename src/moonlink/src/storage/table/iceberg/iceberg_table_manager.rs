@@ -8,9 +8,10 @@ use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
 use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::mooncake_table::Snapshot as MooncakeSnapshot;
 use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
 use crate::storage::storage_utils::FileId;
 use crate::storage::table::common::table_manager::{
-    PersistenceFileParams, PersistenceResult, TableManager,
+    PersistenceFileParams, PersistenceResult, SnapshotExpirationReport, TableManager,
 };
 use crate::storage::table::iceberg::catalog_utils;
 use crate::storage::table::iceberg::moonlink_catalog::MoonlinkCatalog;
@@ -165,6 +166,9 @@ impl IcebergTableManager {
                 &self.config.namespace,
                 &self.config.table_name,
                 self.mooncake_table_metadata.schema.as_ref(),
+                &self.mooncake_table_metadata.config.partition_spec,
+                &self.mooncake_table_metadata.config.sort_order,
+                &self.mooncake_table_metadata.config.parquet_writer_config,
             )
             .await?;
             self.iceberg_table = Some(table);
@@ -218,6 +222,14 @@ impl TableManager for IcebergTableManager {
         Ok(snapshot)
     }
 
+    async fn expire_snapshots(
+        &mut self,
+        retention_config: SnapshotRetentionConfig,
+    ) -> Result<SnapshotExpirationReport> {
+        let report = self.expire_snapshots_impl(retention_config).await?;
+        Ok(report)
+    }
+
     async fn drop_table(&mut self) -> Result<()> {
         let table_ident = TableIdent::new(
             NamespaceIdent::from_strs(&self.config.namespace).unwrap(),
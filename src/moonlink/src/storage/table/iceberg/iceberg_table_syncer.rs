@@ -1,6 +1,7 @@
 use crate::observability::latency_exporter::BaseLatencyExporter;
 use crate::storage::cache::object_storage::base_cache::InlineEvictedFiles;
 use crate::storage::compaction::table_compaction::RemappedRecordLocation;
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
 use crate::storage::index::FileIndex as MooncakeFileIndex;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
@@ -40,7 +41,7 @@ use std::sync::Arc;
 use std::vec;
 
 use iceberg::puffin::CompressionCodec;
-use iceberg::spec::DataFile;
+use iceberg::spec::{DataFile, Operation};
 use iceberg::transaction::{ApplyTransactionAction, Transaction};
 use iceberg::{Error as IcebergError, Result as IcebergResult};
 
@@ -121,6 +122,7 @@ async fn import_one_file_index(
     local_data_file_to_remote: &HashMap<String, String>,
     iceberg_table: &Table,
     filesystem_accessor: &dyn BaseFileSystemAccess,
+    encryption_key: Option<&DataEncryptionKey>,
 ) -> IcebergResult<SingleFileIndexImportResult> {
     let mut local_index_file_to_remote = HashMap::new();
 
@@ -148,7 +150,7 @@ async fn import_one_file_index(
         &local_index_file_to_remote,
         local_data_file_to_remote,
     );
-    let puffin_blob = file_index_blob.as_blob()?;
+    let puffin_blob = puffin_utils::encrypt_blob(file_index_blob.as_blob()?, encryption_key)?;
     puffin_writer
         .add(puffin_blob, iceberg::puffin::CompressionCodec::None)
         .await?;
@@ -260,6 +262,7 @@ impl IcebergTableManager {
                     iceberg_io_utils::write_record_batch_to_iceberg(
                         iceberg_table.as_ref().as_ref().unwrap(),
                         local_data_file.file_path(),
+                        local_data_file.partition_values(),
                         iceberg_table.as_ref().unwrap().metadata(),
                         filesystem_accessor.as_ref(),
                     )
@@ -413,7 +416,10 @@ impl IcebergTableManager {
                 entry.deletion_vector.get_max_rows().to_string(),
             ),
         ]);
-        let blob = iceberg_deletion_vector.serialize(blob_properties);
+        let blob = puffin_utils::encrypt_blob(
+            iceberg_deletion_vector.serialize(blob_properties),
+            self.mooncake_table_metadata.config.get_encryption_key(),
+        )?;
         let blob_size = blob.data().len();
         let puffin_filepath = self.get_unique_deletion_vector_filepath();
         let mut puffin_writer = puffin_utils::create_puffin_writer(
@@ -623,6 +629,7 @@ impl IcebergTableManager {
 
         let iceberg_table = self.iceberg_table.as_ref().unwrap();
         let filesystem_accessor = &*self.filesystem_accessor;
+        let encryption_key = self.mooncake_table_metadata.config.get_encryption_key();
         let local_data_file_to_remote_clone = Arc::new(local_data_file_to_remote.clone());
         let file_indices_to_import_clone = file_indices_to_import.to_vec();
 
@@ -641,6 +648,7 @@ impl IcebergTableManager {
                             &local_data_file_to_remote_clone,
                             iceberg_table,
                             filesystem_accessor,
+                            encryption_key,
                         )
                         .await
                     }
@@ -735,6 +743,11 @@ impl IcebergTableManager {
         // Validate schema consistency before persistence operation.
         self.validate_schema_consistency_at_store().await;
 
+        // Truncation deletes rows committed before it, which should be committed as an overwrite snapshot.
+        let is_overwrite = !snapshot_payload
+            .truncation_payload
+            .old_data_files_to_remove
+            .is_empty();
         let new_data_files = take_data_files_to_import(&mut snapshot_payload);
         let old_data_files = take_data_files_to_remove(&mut snapshot_payload);
         let new_file_indices = take_file_indices_to_import(&mut snapshot_payload);
@@ -792,6 +805,11 @@ impl IcebergTableManager {
             snapshot_payload.flush_lsn.to_string(),
         )]);
 
+        // iceberg-rust only commits append snapshots, data files removed are reflected by catalog at commit.
+        if is_overwrite {
+            self.catalog.set_snapshot_operation(Operation::Overwrite);
+        }
+
         let mut txn = Transaction::new(self.iceberg_table.as_ref().unwrap());
         let mut action = txn.fast_append();
 
@@ -2,6 +2,7 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::storage::cache::object_storage::base_cache::CacheTrait;
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
 use crate::storage::index::persisted_bucket_hash_map::IndexBlock as MooncakeIndexBlock;
 /// This module defines the file index struct used for iceberg, which corresponds to in-memory mooncake table file index structs, and supports the serde between mooncake table format and iceberg format.
@@ -112,6 +113,11 @@ impl FileIndex {
     }
 
     /// Transfer the ownership and convert into [storage::index::FileIndex].
+    ///
+    /// # Arguments
+    ///
+    /// * encryption_key: data key to decrypt index block files with, if the table is encrypted.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn as_mooncake_file_index(
         &mut self,
         data_file_to_id: &HashMap<String, FileId>,
@@ -119,6 +125,7 @@ impl FileIndex {
         filesystem_accessor: &dyn BaseFileSystemAccess,
         table_id: TableId,
         next_file_id: &mut u64,
+        encryption_key: Option<&DataEncryptionKey>,
     ) -> IcebergResult<MooncakeFileIndex> {
         // All mooncake index blocks.
         let mut mooncake_index_blocks = Vec::with_capacity(self.index_block_files.len());
@@ -158,8 +165,16 @@ impl FileIndex {
                 cur_index_block.bucket_start_offset,
                 /*index_file=*/
                 create_data_file(cur_file_id, cache_handle.get_cache_filepath().to_string()),
+                encryption_key,
             )
-            .await;
+            .await
+            .map_err(|e| {
+                IcebergError::new(
+                    iceberg::ErrorKind::Unexpected,
+                    format!("Failed to load index block {}", cur_index_block.filepath),
+                )
+                .with_source(e)
+            })?;
             cur_index_block.cache_handle = Some(cache_handle);
             mooncake_index_blocks.push(cur_index_block);
         }
@@ -254,9 +269,14 @@ impl FileIndexBlob {
     pub async fn load_from_index_blob(
         file_io: FileIO,
         puffin_file: &DataFile,
+        encryption_key: Option<&DataEncryptionKey>,
     ) -> IcebergResult<Self> {
-        let blob =
-            puffin_utils::load_blob_from_puffin_file(file_io, puffin_file.file_path()).await?;
+        let blob = puffin_utils::load_blob_from_puffin_file(
+            file_io,
+            puffin_file.file_path(),
+            encryption_key,
+        )
+        .await?;
         FileIndexBlob::from_blob(blob)
     }
 
@@ -327,16 +347,16 @@ mod tests {
             row_id_bits: 3,
             bucket_bits: 5,
             files: vec![local_data_file.clone()],
-            index_blocks: vec![
-                MooncakeIndexBlock::new(
-                    /*bucket_start_idx=*/ 0,
-                    /*bucket_end_idx=*/ 3,
-                    /*bucket_start_offset=*/ 10,
-                    /*index_file=*/
-                    create_data_file(/*file_id=*/ 1, local_index_filepath.clone()),
-                )
-                .await,
-            ],
+            index_blocks: vec![MooncakeIndexBlock::new(
+                /*bucket_start_idx=*/ 0,
+                /*bucket_end_idx=*/ 3,
+                /*bucket_start_offset=*/ 10,
+                /*index_file=*/
+                create_data_file(/*file_id=*/ 1, local_index_filepath.clone()),
+                /*encryption_key=*/ None,
+            )
+            .await
+            .unwrap()],
         };
 
         // Serialization.
@@ -369,6 +389,7 @@ mod tests {
                 filesystem_accessor.as_ref(),
                 table_id,
                 &mut next_file_id,
+                /*encryption_key=*/ None,
             )
             .await
             .unwrap();
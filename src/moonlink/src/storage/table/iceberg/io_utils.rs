@@ -1,6 +1,7 @@
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
 use crate::storage::filesystem::accessor_config::AccessorConfig;
 use crate::storage::filesystem::storage_config::StorageConfig;
+use crate::storage::partition_utils::PartitionSourceValues;
 use crate::storage::table::iceberg::parquet_utils;
 
 use std::path::Path;
@@ -41,6 +42,7 @@ fn generate_unique_data_filepath(
 pub(crate) async fn write_record_batch_to_iceberg(
     table: &IcebergTable,
     local_filepath: &String,
+    partition_values: Option<&PartitionSourceValues>,
     table_metadata: &IcebergTableMetadata,
     filesystem_accessor: &dyn BaseFileSystemAccess,
 ) -> IcebergResult<DataFile> {
@@ -62,6 +64,7 @@ pub(crate) async fn write_record_batch_to_iceberg(
     let data_file = parquet_utils::get_data_file_from_local_parquet_file(
         local_filepath,
         remote_filepath,
+        partition_values,
         table_metadata,
     )
     .await?;
@@ -153,9 +156,56 @@ pub(crate) fn create_file_io(accessor_config: &AccessorConfig) -> IcebergResult<
             }
             file_io_builder.build()
         }
+        #[cfg(feature = "storage-azure")]
+        StorageConfig::Azblob {
+            account_name,
+            account_key,
+            sas_token,
+            endpoint,
+            ..
+        } => {
+            let mut file_io_builder =
+                FileIOBuilder::new("abfss").with_prop(iceberg::io::ADLS_ACCOUNT_NAME, account_name);
+            if let Some(account_key) = account_key {
+                file_io_builder =
+                    file_io_builder.with_prop(iceberg::io::ADLS_ACCOUNT_KEY, account_key);
+            } else if let Some(sas_token) = sas_token {
+                file_io_builder = file_io_builder.with_prop(iceberg::io::ADLS_SAS_TOKEN, sas_token);
+            }
+            // Endpoint could only be overwritten via connection string.
+            if let Some(endpoint) = endpoint {
+                let connection_string = get_azblob_connection_string(
+                    account_name,
+                    account_key.as_deref(),
+                    sas_token.as_deref(),
+                    endpoint,
+                );
+                file_io_builder = file_io_builder
+                    .with_prop(iceberg::io::ADLS_CONNECTION_STRING, connection_string);
+            }
+            file_io_builder.build()
+        }
     }
 }
 
+/// Get Azure storage connection string with the given endpoint, which carries the same credential as storage config.
+#[cfg(feature = "storage-azure")]
+fn get_azblob_connection_string(
+    account_name: &str,
+    account_key: Option<&str>,
+    sas_token: Option<&str>,
+    endpoint: &str,
+) -> String {
+    let credential = match (account_key, sas_token) {
+        (Some(account_key), _) => format!("AccountKey={account_key};"),
+        (None, Some(sas_token)) => format!("SharedAccessSignature={sas_token};"),
+        (None, None) => String::new(),
+    };
+    format!(
+        "AccountName={account_name};{credential}BlobEndpoint={endpoint};DfsEndpoint={endpoint};"
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +265,22 @@ mod tests {
         assert!(iceberg_filepath.ends_with(".parquet"));
         assert_eq!(iceberg_filepath.match_indices("parquet").count(), 1);
     }
+
+    #[test]
+    #[cfg(feature = "storage-azure")]
+    fn test_azblob_connection_string() {
+        const ENDPOINT: &str = "http://127.0.0.1:10000/account";
+        assert_eq!(
+            get_azblob_connection_string("account", Some("key"), Some("sig"), ENDPOINT),
+            format!("AccountName=account;AccountKey=key;BlobEndpoint={ENDPOINT};DfsEndpoint={ENDPOINT};")
+        );
+        assert_eq!(
+            get_azblob_connection_string("account", None, Some("sv=2022-11-02&sig=fake"), ENDPOINT),
+            format!("AccountName=account;SharedAccessSignature=sv=2022-11-02&sig=fake;BlobEndpoint={ENDPOINT};DfsEndpoint={ENDPOINT};")
+        );
+        assert_eq!(
+            get_azblob_connection_string("account", None, None, ENDPOINT),
+            format!("AccountName=account;BlobEndpoint={ENDPOINT};DfsEndpoint={ENDPOINT};")
+        );
+    }
 }
@@ -1,5 +1,5 @@
 use async_trait::async_trait;
-use iceberg::spec::{Schema as IcebergSchema, TableMetadata};
+use iceberg::spec::{Operation, Schema as IcebergSchema, TableMetadata};
 use iceberg::table::Table;
 use iceberg::{Catalog, Result as IcebergResult, TableIdent};
 
@@ -30,6 +30,9 @@ pub trait PuffinWrite {
     /// Set puffin file to remove.
     fn set_index_puffin_files_to_remove(&mut self, puffin_filepaths: HashSet<String>);
 
+    /// Set operation for the snapshot to commit, since iceberg-rust only commits append snapshots.
+    fn set_snapshot_operation(&mut self, operation: Operation);
+
     /// After transaction commits, puffin metadata should be cleared for next puffin write.
     fn clear_puffin_metadata(&mut self);
 }
@@ -21,7 +21,7 @@ use iceberg::arrow::DEFAULT_MAP_FIELD_NAME;
 use iceberg::io::FileIOBuilder;
 use iceberg::spec::{
     visit_schema, DataContentType, DataFile, DataFileBuilder, DataFileFormat, ListType, MapType,
-    NestedFieldRef, PrimitiveType, Schema, SchemaRef, SchemaVisitor, Struct, StructType,
+    NestedFieldRef, PartitionSpec, PrimitiveType, Schema, SchemaRef, SchemaVisitor, StructType,
     TableMetadata,
 };
 use iceberg::Result as IcebergResult;
@@ -29,8 +29,10 @@ use iceberg::{Error as IcebergError, ErrorKind};
 use itertools::Itertools;
 use parquet::file::metadata::ParquetMetaData;
 
+use crate::storage::partition_utils::PartitionSourceValues;
 use crate::storage::table::iceberg::parquet_metadata_utils;
 use crate::storage::table::iceberg::parquet_stats_utils::MinMaxColAggregator;
+use crate::storage::table::iceberg::partition_utils;
 
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -166,9 +168,11 @@ impl SchemaVisitor for IndexByParquetPathName {
 // parquet_to_data_file_builder
 // ================================
 //
-// `ParquetMetadata` to data file builder
+// `ParquetMetadata` to data file builder, partition tuple is computed from partition source values recorded by writers.
 fn parquet_to_data_file_builder(
     schema: SchemaRef,
+    partition_spec: &PartitionSpec,
+    partition_values: Option<&PartitionSourceValues>,
     metadata: Arc<ParquetMetaData>,
     written_size: usize,
     file_path: String,
@@ -184,7 +188,7 @@ fn parquet_to_data_file_builder(
         let mut per_col_size: HashMap<i32, u64> = HashMap::new();
         let mut per_col_val_num: HashMap<i32, u64> = HashMap::new();
         let mut per_col_null_val_num: HashMap<i32, u64> = HashMap::new();
-        let mut min_max_agg = MinMaxColAggregator::new(schema);
+        let mut min_max_agg = MinMaxColAggregator::new(schema.clone());
 
         for row_group in metadata.row_groups() {
             for column_chunk_metadata in row_group.columns() {
@@ -216,12 +220,15 @@ fn parquet_to_data_file_builder(
         )
     };
 
+    let partition =
+        partition_utils::get_data_file_partition(&schema, partition_spec, partition_values)?;
+
     let mut builder = DataFileBuilder::default();
     builder
         .content(DataContentType::Data)
         .file_path(file_path)
         .file_format(DataFileFormat::Parquet)
-        .partition(Struct::empty())
+        .partition(partition)
         .record_count(metadata.file_metadata().num_rows() as u64)
         .file_size_in_bytes(written_size as u64)
         .column_sizes(column_sizes)
@@ -270,18 +277,33 @@ pub(crate) async fn get_parquet_metadata(
 pub(crate) async fn get_data_file_from_local_parquet_file(
     local_parquet_file: &str,
     remote_parquet_file: String,
+    partition_values: Option<&PartitionSourceValues>,
     table_metadata: &TableMetadata,
 ) -> IcebergResult<DataFile> {
     let (parquet_metadata, file_size) = get_parquet_metadata(local_parquet_file).await?;
+    let parquet_metadata = Arc::new(parquet_metadata);
     let mut builder = parquet_to_data_file_builder(
         table_metadata.current_schema().clone(),
-        Arc::new(parquet_metadata),
+        table_metadata.default_partition_spec().as_ref(),
+        partition_values,
+        parquet_metadata.clone(),
         file_size,
         remote_parquet_file,
         // TODO: Implement nan_value_counts here
         HashMap::new(),
     )?;
     builder.partition_spec_id(table_metadata.default_partition_spec_id());
+    // Only data files written with table sort order record sorting columns, which are tagged with the default sort order.
+    let is_sorted = parquet_metadata
+        .row_groups()
+        .iter()
+        .all(|row_group| row_group.sorting_columns().is_some());
+    if is_sorted
+        && parquet_metadata.num_row_groups() > 0
+        && !table_metadata.default_sort_order().is_unsorted()
+    {
+        builder.sort_order_id(Some(table_metadata.default_sort_order_id() as i32));
+    }
 
     builder.build().map_err(|e| {
         IcebergError::new(
@@ -344,6 +366,7 @@ mod tests {
         let iceberg_schema = IcebergArrow::arrow_schema_to_schema(&schema).unwrap();
         let mut data_file_builder = parquet_to_data_file_builder(
             Arc::new(iceberg_schema),
+            &PartitionSpec::unpartition_spec(),
             Arc::new(parquet_metadata),
             file_size,
             remote_filepath.to_str().unwrap().to_string(),
@@ -0,0 +1,248 @@
+/// This module contains util functions to convert mooncake partition spec into iceberg one, and compute partition tuples for iceberg data files.
+use crate::row::RowValue;
+use crate::storage::mooncake_table_config::PartitionFieldConfig;
+use crate::storage::partition_utils::PartitionSourceValues;
+
+use iceberg::spec::{
+    Datum, Literal, PartitionSpec, PrimitiveLiteral, PrimitiveType, Schema, Struct, Type,
+    UnboundPartitionSpec,
+};
+use iceberg::transform::create_transform_function;
+use iceberg::Result as IcebergResult;
+use iceberg::{Error as IcebergError, ErrorKind};
+use uuid::Uuid;
+
+/// Get unbound iceberg partition spec for the given mooncake partition spec, which is used at table creation.
+pub(crate) fn get_unbound_partition_spec(
+    schema: &Schema,
+    partition_spec: &[PartitionFieldConfig],
+) -> IcebergResult<UnboundPartitionSpec> {
+    let mut builder = UnboundPartitionSpec::builder();
+    for cur_partition_field in partition_spec.iter() {
+        let source_field = schema
+            .field_by_name(&cur_partition_field.source_column)
+            .ok_or_else(|| {
+                IcebergError::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Partition source column {} doesn't exist in iceberg schema",
+                        cur_partition_field.source_column
+                    ),
+                )
+            })?;
+        builder = builder.add_partition_field(
+            source_field.id,
+            cur_partition_field.partition_field_name(),
+            cur_partition_field.transform,
+        )?;
+    }
+    Ok(builder.build())
+}
+
+/// Convert partition source column value into iceberg datum, based on the source column type.
+/// Values written before a type promotion are promoted to the current column type.
+fn get_partition_source_datum(
+    primitive_type: &PrimitiveType,
+    value: &RowValue,
+) -> IcebergResult<Option<Datum>> {
+    let datum = match (primitive_type, value) {
+        (_, RowValue::Null) => return Ok(None),
+        (PrimitiveType::Boolean, RowValue::Bool(val)) => Datum::bool(*val),
+        (PrimitiveType::Int, RowValue::Int32(val)) => Datum::int(*val),
+        (PrimitiveType::Date, RowValue::Int32(val)) => Datum::date(*val),
+        (PrimitiveType::Long, RowValue::Int32(val)) => Datum::long(*val as i64),
+        (PrimitiveType::Long, RowValue::Int64(val)) => Datum::long(*val),
+        (PrimitiveType::Time, RowValue::Int64(val)) => Datum::time_micros(*val)?,
+        (PrimitiveType::Timestamp, RowValue::Int64(val)) => Datum::timestamp_micros(*val),
+        (PrimitiveType::Timestamptz, RowValue::Int64(val)) => Datum::timestamptz_micros(*val),
+        (PrimitiveType::Float, RowValue::Float32(val)) => Datum::float(*val),
+        (PrimitiveType::Double, RowValue::Float32(val)) => Datum::double(*val as f64),
+        (PrimitiveType::Double, RowValue::Float64(val)) => Datum::double(*val),
+        (PrimitiveType::Decimal { .. }, RowValue::Decimal(val)) => {
+            Datum::new(primitive_type.clone(), PrimitiveLiteral::Int128(*val))
+        }
+        (PrimitiveType::String, RowValue::ByteArray(val)) => {
+            Datum::string(std::str::from_utf8(val).map_err(|e| {
+                IcebergError::new(
+                    ErrorKind::DataInvalid,
+                    "Partition source value is not valid utf8 string",
+                )
+                .with_source(e)
+            })?)
+        }
+        (PrimitiveType::Binary, RowValue::ByteArray(val)) => Datum::binary(val.clone()),
+        (PrimitiveType::Uuid, RowValue::FixedLenByteArray(val)) => {
+            Datum::uuid(Uuid::from_bytes(*val))
+        }
+        _ => {
+            return Err(IcebergError::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Partition source value {value:?} doesn't match column type {primitive_type:?}"
+                ),
+            ));
+        }
+    };
+    Ok(Some(datum))
+}
+
+/// Get partition tuple for a data file, based on the partition source values recorded by mooncake writers.
+///
+/// Precondition: all rows in the data file belong to the same partition, which is guaranteed by mooncake writers.
+pub(crate) fn get_data_file_partition(
+    schema: &Schema,
+    partition_spec: &PartitionSpec,
+    partition_values: Option<&PartitionSourceValues>,
+) -> IcebergResult<Struct> {
+    if partition_spec.fields().is_empty() {
+        return Ok(Struct::empty());
+    }
+    let partition_values = partition_values.ok_or_else(|| {
+        IcebergError::new(
+            ErrorKind::DataInvalid,
+            "Data file for partitioned table doesn't record partition values",
+        )
+    })?;
+    if partition_values.len() != partition_spec.fields().len() {
+        return Err(IcebergError::new(
+            ErrorKind::DataInvalid,
+            format!(
+                "Data file records {} partition values, but partition spec has {} fields",
+                partition_values.len(),
+                partition_spec.fields().len()
+            ),
+        ));
+    }
+
+    let mut partition_tuple = Vec::with_capacity(partition_spec.fields().len());
+    for (cur_partition_field, cur_value) in partition_spec.fields().iter().zip(partition_values) {
+        let source_field = schema
+            .field_by_id(cur_partition_field.source_id)
+            .ok_or_else(|| {
+                IcebergError::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Partition source field {} doesn't exist in iceberg schema",
+                        cur_partition_field.source_id
+                    ),
+                )
+            })?;
+        let Type::Primitive(primitive_type) = source_field.field_type.as_ref() else {
+            return Err(IcebergError::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Partition source field {} is not primitive type",
+                    source_field.name
+                ),
+            ));
+        };
+        let transform_function = create_transform_function(&cur_partition_field.transform)?;
+        let partition_value = match get_partition_source_datum(primitive_type, cur_value)? {
+            Some(datum) => transform_function.transform_literal(&datum)?,
+            None => None,
+        };
+        partition_tuple
+            .push(partition_value.map(|datum| Literal::Primitive(datum.literal().clone())));
+    }
+    Ok(Struct::from_iter(partition_tuple))
+}
+
+/// Get partition tuple with all partition values null, which is used for files not belonging to any partition (i.e., file indices).
+pub(crate) fn get_null_partition(partition_spec: &PartitionSpec) -> Struct {
+    Struct::from_iter(partition_spec.fields().iter().map(|_| None))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use iceberg::spec::{NestedField, PrimitiveType, Transform, Type};
+    use std::sync::Arc;
+
+    fn create_test_schema() -> Schema {
+        Schema::builder()
+            .with_fields(vec![
+                Arc::new(NestedField::required(
+                    1,
+                    "id",
+                    Type::Primitive(PrimitiveType::Int),
+                )),
+                Arc::new(NestedField::optional(
+                    2,
+                    "name",
+                    Type::Primitive(PrimitiveType::String),
+                )),
+            ])
+            .build()
+            .unwrap()
+    }
+
+    fn create_test_partition_spec(schema: Schema) -> PartitionSpec {
+        let partition_spec = vec![
+            PartitionFieldConfig::new("id", Transform::Truncate(10)),
+            PartitionFieldConfig::new("name", Transform::Identity),
+        ];
+        get_unbound_partition_spec(&schema, &partition_spec)
+            .unwrap()
+            .bind(Arc::new(schema))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_get_unbound_partition_spec() {
+        let schema = create_test_schema();
+        let partition_spec = create_test_partition_spec(schema.clone());
+        let partition_field_names = partition_spec
+            .fields()
+            .iter()
+            .map(|field| field.name.clone())
+            .collect::<Vec<_>>();
+        assert_eq!(partition_field_names, vec!["id_trunc", "name"]);
+
+        let invalid_partition_spec = vec![PartitionFieldConfig::new("age", Transform::Identity)];
+        assert!(get_unbound_partition_spec(&schema, &invalid_partition_spec).is_err());
+    }
+
+    #[test]
+    fn test_get_data_file_partition() {
+        let schema = create_test_schema();
+        let partition_spec = create_test_partition_spec(schema.clone());
+
+        // Rows within one partition.
+        let partition_values = vec![RowValue::Int32(11), RowValue::ByteArray(b"a".to_vec())];
+        let partition =
+            get_data_file_partition(&schema, &partition_spec, Some(&partition_values)).unwrap();
+        assert_eq!(
+            partition,
+            Struct::from_iter([Some(Literal::int(10)), Some(Literal::string("a"))])
+        );
+
+        // Source column with null value.
+        let partition_values = vec![RowValue::Int32(11), RowValue::Null];
+        let partition =
+            get_data_file_partition(&schema, &partition_spec, Some(&partition_values)).unwrap();
+        assert_eq!(partition, Struct::from_iter([Some(Literal::int(10)), None]));
+
+        // Mismatched value type, or missing partition values.
+        let partition_values = vec![RowValue::Int64(11), RowValue::Null];
+        assert!(
+            get_data_file_partition(&schema, &partition_spec, Some(&partition_values)).is_err()
+        );
+        assert!(
+            get_data_file_partition(&schema, &partition_spec, /*partition_values=*/ None).is_err()
+        );
+
+        // Unpartitioned table.
+        let partition = get_data_file_partition(
+            &schema,
+            &PartitionSpec::unpartition_spec(),
+            /*partition_values=*/ None,
+        )
+        .unwrap();
+        assert_eq!(partition, Struct::empty());
+        assert_eq!(
+            get_null_partition(&partition_spec),
+            Struct::from_iter([None, None])
+        );
+    }
+}
@@ -6,6 +6,7 @@ use iceberg::puffin::PuffinWriter;
 use iceberg::puffin::{Blob, PuffinReader};
 use iceberg::{Error as IcebergError, Result as IcebergResult};
 
+use crate::storage::encryption::data_key::DataEncryptionKey;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
 use crate::storage::table::iceberg::deletion_vector::DeletionVector;
 use crate::NonEvictableHandle;
@@ -40,11 +41,58 @@ pub(crate) async fn create_puffin_writer(
     Ok(puffin_writer)
 }
 
-/// Load blob from the given puffin filepath.
+/// Rebuild the given blob with its payload transformed, with blob metadata kept as-is.
+fn transform_blob_data(blob: Blob, data: Vec<u8>) -> Blob {
+    Blob::builder()
+        .r#type(blob.blob_type().to_string())
+        .fields(blob.fields().to_vec())
+        .snapshot_id(blob.snapshot_id())
+        .sequence_number(blob.sequence_number())
+        .data(data)
+        .properties(blob.properties().clone())
+        .build()
+}
+
+/// Encrypt blob payload with the given data key if assigned, so the blob could be added to puffin file.
+/// Puffin footer stays plaintext, so blob offsets and properties are still accessible without the key.
+pub(crate) fn encrypt_blob(
+    blob: Blob,
+    encryption_key: Option<&DataEncryptionKey>,
+) -> IcebergResult<Blob> {
+    let Some(encryption_key) = encryption_key else {
+        return Ok(blob);
+    };
+    let data = encryption_key.encrypt(blob.data()).map_err(|e| {
+        IcebergError::new(
+            iceberg::ErrorKind::Unexpected,
+            "Failed to encrypt puffin blob".to_string(),
+        )
+        .with_source(e)
+    })?;
+    Ok(transform_blob_data(blob, data))
+}
+
+/// Decrypt blob payload with the given data key if assigned.
+fn decrypt_blob(blob: Blob, encryption_key: Option<&DataEncryptionKey>) -> IcebergResult<Blob> {
+    let Some(encryption_key) = encryption_key else {
+        return Ok(blob);
+    };
+    let data = encryption_key.decrypt(blob.data()).map_err(|e| {
+        IcebergError::new(
+            iceberg::ErrorKind::DataInvalid,
+            "Failed to decrypt puffin blob".to_string(),
+        )
+        .with_source(e)
+    })?;
+    Ok(transform_blob_data(blob, data))
+}
+
+/// Load blob from the given puffin filepath, which is decrypted if [`encryption_key`] is assigned.
 /// Note: this function assumes there's only one blob in the puffin file.
 pub(crate) async fn load_blob_from_puffin_file(
     file_io: FileIO,
     file_path: &str,
+    encryption_key: Option<&DataEncryptionKey>,
 ) -> IcebergResult<Blob> {
     let input_file = file_io.new_input(file_path)?;
     let puffin_reader = PuffinReader::new(input_file);
@@ -62,19 +110,21 @@ pub(crate) async fn load_blob_from_puffin_file(
     }
 
     let blob_metadata = &puffin_file_metadata.blobs()[0];
-    puffin_reader.blob(blob_metadata).await
+    let blob = puffin_reader.blob(blob_metadata).await?;
+    decrypt_blob(blob, encryption_key)
 }
 
 /// Util function to load batch deletion vector from puffin blob.
 /// Precondition: there's only one deletion vector blob in the puffin file.
 pub(crate) async fn load_deletion_vector_from_blob(
     puffin_blob_ref: &PuffinBlobRef,
+    encryption_key: Option<&DataEncryptionKey>,
 ) -> IcebergResult<BatchDeletionVector> {
     let cache_filepath = puffin_blob_ref
         .puffin_file_cache_handle
         .get_cache_filepath();
     let file_io = FileIO::from_path(cache_filepath)?.build()?;
-    let puffin_blob = load_blob_from_puffin_file(file_io, cache_filepath).await?;
+    let puffin_blob = load_blob_from_puffin_file(file_io, cache_filepath, encryption_key).await?;
     let deletion_vector = DeletionVector::deserialize(puffin_blob)?;
     Ok(deletion_vector.take_as_batch_delete_vector())
 }
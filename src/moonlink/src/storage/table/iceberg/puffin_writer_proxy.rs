@@ -282,6 +282,8 @@ pub(crate) async fn append_puffin_metadata_and_rewrite(
         DeletionVectorManifestManager::new(table_metadata, file_io, data_files_to_remove);
     let mut file_index_manifest_manager =
         FileIndexManifestManager::new(table_metadata, file_io, index_puffin_blobs_to_remove);
+    // Partition tuples for data files, which new deletion vectors inherit from their referenced data files.
+    let mut data_file_partitions = HashMap::new();
 
     // How to tell different manifest entry types:
     // - Data file: manifest content type `Data`, manifest entry file format `Parquet`
@@ -302,6 +304,14 @@ pub(crate) async fn append_puffin_metadata_and_rewrite(
         // Check for data file entries, see if there're updates.
         let manifest_entry_type =
             manifest_utils::get_manifest_entry_type(&manifest_entries, &manifest_metadata);
+        if manifest_entry_type == ManifestEntryType::DataFile
+            && !deletion_vector_blobs_to_add.is_empty()
+        {
+            DataFileManifestManager::collect_data_file_partitions(
+                &manifest_entries,
+                &mut data_file_partitions,
+            );
+        }
         if manifest_entry_type == ManifestEntryType::DataFile && data_files_to_remove.is_empty() {
             manifest_list_writer.add_manifests([cur_manifest_file.clone()].into_iter())?;
             continue;
@@ -343,7 +353,8 @@ pub(crate) async fn append_puffin_metadata_and_rewrite(
     }
 
     // Append puffin blobs into existing manifest entries.
-    deletion_vector_manifest_manager.add_new_puffin_blobs(deletion_vector_blobs_to_add)?;
+    deletion_vector_manifest_manager
+        .add_new_puffin_blobs(deletion_vector_blobs_to_add, &data_file_partitions)?;
     file_index_manifest_manager.add_new_puffin_blobs(file_index_blobs_to_add)?;
 
     // Attempt to finalize all existing manifest entries.
@@ -8,7 +8,7 @@ use crate::storage::table::iceberg::table_commit_proxy::TableCommitProxy;
 use crate::storage::table::iceberg::table_update_proxy::TableUpdateProxy;
 use async_trait::async_trait;
 use iceberg::io::FileIO;
-use iceberg::spec::{Schema as IcebergSchema, TableMetadata};
+use iceberg::spec::{Operation, Schema as IcebergSchema, TableMetadata};
 use iceberg::table::Table;
 use iceberg::CatalogBuilder;
 use iceberg::Result as IcebergResult;
@@ -208,6 +208,10 @@ impl PuffinWrite for RestCatalog {
             .set_index_puffin_files_to_remove(puffin_filepaths);
     }
 
+    fn set_snapshot_operation(&mut self, operation: Operation) {
+        self.table_update_proxy.set_snapshot_operation(operation);
+    }
+
     fn clear_puffin_metadata(&mut self) {
         self.table_update_proxy.clear();
     }
@@ -1,9 +1,10 @@
 #[cfg(any(test, debug_assertions))]
 use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
-#[cfg(any(test, debug_assertions))]
 use iceberg::spec::Schema as IcebergSchema;
 use iceberg::spec::DEFAULT_SCHEMA_ID;
+use iceberg::spec::{PrimitiveType, Type as IcebergType};
 use iceberg::table::Table as IcebergTable;
+use iceberg::{Error as IcebergError, ErrorKind, Result as IcebergResult};
 
 /// Schema related utils.
 ///
@@ -39,3 +40,68 @@ pub(crate) fn assert_table_schema_id(table: &IcebergTable) {
     let schema_id = table.metadata().current_schema_id();
     assert_ne!(schema_id, DEFAULT_SCHEMA_ID);
 }
+
+/// Return whether iceberg type `from` could be promoted to `to`.
+/// Reference: https://iceberg.apache.org/spec/#schema-evolution
+fn is_valid_type_promotion(from: &IcebergType, to: &IcebergType) -> bool {
+    if from == to {
+        return true;
+    }
+    match (from, to) {
+        (IcebergType::Primitive(from), IcebergType::Primitive(to)) => {
+            matches!(
+                (from, to),
+                (PrimitiveType::Int, PrimitiveType::Long)
+                    | (PrimitiveType::Float, PrimitiveType::Double)
+            ) || matches!(
+                (from, to),
+                (
+                    PrimitiveType::Decimal {
+                        precision: from_precision,
+                        scale: from_scale,
+                    },
+                    PrimitiveType::Decimal {
+                        precision: to_precision,
+                        scale: to_scale,
+                    },
+                ) if from_scale == to_scale && from_precision <= to_precision
+            )
+        }
+        _ => false,
+    }
+}
+
+/// Validate the new schema is a valid evolution of the current one, fields are matched by field ids.
+/// Renamed fields are allowed, while type changes are only allowed for promotion, and nullability could only be relaxed.
+pub(crate) fn validate_schema_evolution(
+    current_schema: &IcebergSchema,
+    new_schema: &IcebergSchema,
+) -> IcebergResult<()> {
+    for new_field in new_schema.as_struct().fields() {
+        let Some(current_field) = current_schema.field_by_id(new_field.id) else {
+            continue;
+        };
+        if !is_valid_type_promotion(&current_field.field_type, &new_field.field_type) {
+            return Err(IcebergError::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Cannot change field {} (id {}) from {} to {}",
+                    current_field.name,
+                    current_field.id,
+                    current_field.field_type,
+                    new_field.field_type
+                ),
+            ));
+        }
+        if !current_field.required && new_field.required {
+            return Err(IcebergError::new(
+                ErrorKind::DataInvalid,
+                format!(
+                    "Cannot change optional field {} (id {}) to required",
+                    current_field.name, current_field.id
+                ),
+            ));
+        }
+    }
+    Ok(())
+}
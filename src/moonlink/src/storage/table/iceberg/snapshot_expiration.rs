@@ -0,0 +1,373 @@
+/// This module contains snapshot expiration implementation for iceberg table manager.
+///
+/// Each iceberg persistence creates a new snapshot, and files replaced by data compaction and index merge are still referenced by old snapshots.
+/// Snapshot expiration removes snapshots beyond retention from table metadata, and deletes files which are only reachable from expired snapshots.
+/// Files referenced by no snapshot under table location are deleted as orphan files, if orphan file cleanup is enabled.
+use crate::storage::encryption::data_key::DataEncryptionKey;
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
+use crate::storage::table::common::table_manager::SnapshotExpirationReport;
+use crate::storage::table::iceberg::iceberg_table_manager::*;
+use crate::storage::table::iceberg::index::FileIndexBlob;
+use crate::storage::table::iceberg::table_commit_proxy::TableCommitProxy;
+use crate::storage::table::iceberg::utils;
+use crate::Result;
+
+use std::collections::{HashMap, HashSet};
+
+use futures::future::try_join_all;
+use iceberg::io::FileIO;
+use iceberg::spec::{SnapshotRef, TableMetadata, MAIN_BRANCH};
+use iceberg::Result as IcebergResult;
+use iceberg::{TableRequirement, TableUpdate};
+
+/// Snapshot attributes used to decide whether to expire.
+#[derive(Clone, Debug)]
+struct SnapshotEntry {
+    snapshot_id: i64,
+    sequence_number: i64,
+    timestamp_ms: i64,
+}
+
+/// Get ids for snapshots to expire, which exceed either max snapshot count or max snapshot age.
+/// The current snapshot is always retained, and counts towards max snapshot count.
+fn get_snapshots_to_expire(
+    mut snapshots: Vec<SnapshotEntry>,
+    current_snapshot_id: Option<i64>,
+    retention_config: &SnapshotRetentionConfig,
+    now_ms: i64,
+) -> Vec<i64> {
+    // Order snapshots from the latest to the oldest.
+    snapshots.sort_by(|a, b| b.sequence_number.cmp(&a.sequence_number));
+
+    let mut snapshots_to_expire = vec![];
+    for (idx, cur_snapshot) in snapshots.iter().enumerate() {
+        if Some(cur_snapshot.snapshot_id) == current_snapshot_id {
+            continue;
+        }
+        let exceeds_count = retention_config
+            .max_snapshots_to_keep
+            .is_some_and(|max_snapshots_to_keep| idx >= max_snapshots_to_keep);
+        let exceeds_age = retention_config
+            .max_snapshot_age_ms
+            .is_some_and(|max_snapshot_age_ms| {
+                now_ms.saturating_sub(cur_snapshot.timestamp_ms) > max_snapshot_age_ms as i64
+            });
+        if exceeds_count || exceeds_age {
+            snapshots_to_expire.push(cur_snapshot.snapshot_id);
+        }
+    }
+    snapshots_to_expire
+}
+
+/// Get all files reachable from the given snapshot, including its manifest list, manifest files, files referenced by manifest entries (data files, deletion vector puffin files and file index puffin files), and index block files referenced by file indices.
+///
+/// Manifest files are usually shared by multiple snapshots, so referenced files are cached by manifest filepath to avoid repeated IO.
+async fn get_snapshot_files(
+    snapshot: &SnapshotRef,
+    table_metadata: &TableMetadata,
+    file_io: &FileIO,
+    encryption_key: Option<&DataEncryptionKey>,
+    manifest_files_cache: &mut HashMap<String, Vec<String>>,
+) -> IcebergResult<HashSet<String>> {
+    let mut snapshot_files = HashSet::new();
+    snapshot_files.insert(snapshot.manifest_list().to_string());
+
+    let manifest_list = snapshot.load_manifest_list(file_io, table_metadata).await?;
+    for manifest_file in manifest_list.entries().iter() {
+        snapshot_files.insert(manifest_file.manifest_path.clone());
+        if !manifest_files_cache.contains_key(&manifest_file.manifest_path) {
+            let manifest = manifest_file.load_manifest(file_io).await?;
+            let mut referenced_files = Vec::with_capacity(manifest.entries().len());
+            for entry in manifest.entries().iter() {
+                referenced_files.push(entry.data_file().file_path().to_string());
+                // Index block files are only referenced by file index blobs.
+                if utils::is_file_index(entry) {
+                    let file_index_blob = FileIndexBlob::load_from_index_blob(
+                        file_io.clone(),
+                        entry.data_file(),
+                        encryption_key,
+                    )
+                    .await?;
+                    referenced_files.extend(
+                        file_index_blob
+                            .file_index
+                            .index_block_files
+                            .into_iter()
+                            .map(|index_block| index_block.filepath),
+                    );
+                }
+            }
+            manifest_files_cache.insert(manifest_file.manifest_path.clone(), referenced_files);
+        }
+        snapshot_files.extend(
+            manifest_files_cache[&manifest_file.manifest_path]
+                .iter()
+                .cloned(),
+        );
+    }
+    Ok(snapshot_files)
+}
+
+/// Return whether the file under table location could be taken as orphan file, which covers data files, puffin files, index block files, manifest files and manifest lists.
+/// Table metadata files are managed by the catalog, which are never taken as orphan files.
+fn is_orphan_file_candidate(filepath: &str, table_location: &str) -> bool {
+    let table_location = table_location.trim_end_matches('/');
+    if filepath.starts_with(&format!("{table_location}/data/")) {
+        return true;
+    }
+    filepath.starts_with(&format!("{table_location}/metadata/")) && filepath.ends_with(".avro")
+}
+
+impl IcebergTableManager {
+    pub(super) async fn expire_snapshots_impl(
+        &mut self,
+        retention_config: SnapshotRetentionConfig,
+    ) -> Result<SnapshotExpirationReport> {
+        let mut report = SnapshotExpirationReport {
+            dry_run: retention_config.dry_run,
+            ..Default::default()
+        };
+        // Iceberg table is created lazily at the first persistence, there's nothing to expire before that.
+        if !retention_config.is_enabled() || self.iceberg_table.is_none() {
+            return Ok(report);
+        }
+
+        self.remove_expired_snapshots(&retention_config, &mut report)
+            .await?;
+        if let Some(grace_period_ms) = retention_config.orphan_file_grace_period_ms {
+            report.orphan_files = self
+                .delete_orphan_files(grace_period_ms, retention_config.dry_run)
+                .await?;
+        }
+        Ok(report)
+    }
+
+    /// Get data key to decrypt file index blobs, if the table is encrypted.
+    fn get_encryption_key(&self) -> Option<&DataEncryptionKey> {
+        self.mooncake_table_metadata.config.get_encryption_key()
+    }
+
+    /// Remove snapshots beyond retention from table metadata, and delete files only reachable from them.
+    async fn remove_expired_snapshots(
+        &mut self,
+        retention_config: &SnapshotRetentionConfig,
+        report: &mut SnapshotExpirationReport,
+    ) -> Result<()> {
+        let iceberg_table = self.iceberg_table.as_ref().unwrap();
+        let table_metadata = iceberg_table.metadata();
+        let current_snapshot_id = table_metadata.current_snapshot_id();
+        let snapshots = table_metadata
+            .snapshots()
+            .map(|snapshot| SnapshotEntry {
+                snapshot_id: snapshot.snapshot_id(),
+                sequence_number: snapshot.sequence_number(),
+                timestamp_ms: snapshot.timestamp_ms(),
+            })
+            .collect::<Vec<_>>();
+        let snapshots_to_expire = get_snapshots_to_expire(
+            snapshots,
+            current_snapshot_id,
+            retention_config,
+            chrono::Utc::now().timestamp_millis(),
+        );
+        if snapshots_to_expire.is_empty() {
+            return Ok(());
+        }
+
+        // A file is unreachable only if no retained snapshots reference it.
+        let expired_snapshot_ids = snapshots_to_expire.iter().copied().collect::<HashSet<_>>();
+        let mut manifest_files_cache = HashMap::new();
+        let mut retained_files = HashSet::new();
+        let mut expired_files = HashSet::new();
+        for cur_snapshot in table_metadata.snapshots() {
+            let snapshot_files = get_snapshot_files(
+                cur_snapshot,
+                table_metadata,
+                iceberg_table.file_io(),
+                self.get_encryption_key(),
+                &mut manifest_files_cache,
+            )
+            .await?;
+            if expired_snapshot_ids.contains(&cur_snapshot.snapshot_id()) {
+                expired_files.extend(snapshot_files);
+            } else {
+                retained_files.extend(snapshot_files);
+            }
+        }
+        let mut unreachable_files = expired_files
+            .difference(&retained_files)
+            .cloned()
+            .collect::<Vec<_>>();
+        unreachable_files.sort();
+
+        report.expired_snapshot_ids = snapshots_to_expire;
+        report.unreachable_files = unreachable_files;
+        if report.dry_run {
+            return Ok(());
+        }
+
+        // Commit metadata change first, so failed file deletion only leaves orphan files.
+        let table_commit = TableCommitProxy {
+            ident: self.get_table_ident(),
+            requirements: vec![TableRequirement::RefSnapshotIdMatch {
+                r#ref: MAIN_BRANCH.to_string(),
+                snapshot_id: current_snapshot_id,
+            }],
+            updates: vec![TableUpdate::RemoveSnapshots {
+                snapshot_ids: report.expired_snapshot_ids.clone(),
+            }],
+        }
+        .take_as_table_commit();
+        let updated_iceberg_table = self.catalog.update_table(table_commit).await?;
+        self.iceberg_table = Some(updated_iceberg_table);
+
+        let delete_futures = report
+            .unreachable_files
+            .iter()
+            .map(|filepath| self.filesystem_accessor.delete_object(filepath));
+        try_join_all(delete_futures).await?;
+
+        Ok(())
+    }
+
+    /// Delete files under table location which are referenced by no snapshot, and older than the given grace period.
+    /// Orphan files are left by failed persistence (i.e. uploaded files not committed) and failed file deletion after snapshot expiration.
+    /// The grace period protects files which are being written but not committed yet.
+    ///
+    /// Return deleted orphan files, or files to delete for dry run.
+    async fn delete_orphan_files(
+        &self,
+        grace_period_ms: u64,
+        dry_run: bool,
+    ) -> Result<Vec<String>> {
+        let iceberg_table = self.iceberg_table.as_ref().unwrap();
+        let table_metadata = iceberg_table.metadata();
+        let mut manifest_files_cache = HashMap::new();
+        let mut referenced_files = HashSet::new();
+        for cur_snapshot in table_metadata.snapshots() {
+            referenced_files.extend(
+                get_snapshot_files(
+                    cur_snapshot,
+                    table_metadata,
+                    iceberg_table.file_io(),
+                    self.get_encryption_key(),
+                    &mut manifest_files_cache,
+                )
+                .await?,
+            );
+        }
+
+        let table_location = table_metadata.location();
+        let listed_files = self
+            .filesystem_accessor
+            .list_objects_recursively(table_location)
+            .await?;
+        let now = chrono::Utc::now();
+        let mut orphan_files = vec![];
+        for cur_file in listed_files.into_iter() {
+            if referenced_files.contains(&cur_file)
+                || !is_orphan_file_candidate(&cur_file, table_location)
+            {
+                continue;
+            }
+            // Files without modification time are kept, since their age is unknown.
+            let object_metadata = self.filesystem_accessor.stats_object(&cur_file).await?;
+            let Some(last_modified) = object_metadata.last_modified() else {
+                continue;
+            };
+            let age_ms = now.signed_duration_since(last_modified).num_milliseconds();
+            if age_ms > grace_period_ms as i64 {
+                orphan_files.push(cur_file);
+            }
+        }
+        orphan_files.sort();
+        if dry_run {
+            return Ok(orphan_files);
+        }
+
+        let delete_futures = orphan_files
+            .iter()
+            .map(|filepath| self.filesystem_accessor.delete_object(filepath));
+        try_join_all(delete_futures).await?;
+        Ok(orphan_files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_snapshot_entries() -> Vec<SnapshotEntry> {
+        // Snapshots are committed every second, with the latest one being the current snapshot.
+        (1..=5)
+            .map(|idx| SnapshotEntry {
+                snapshot_id: idx * 100,
+                sequence_number: idx,
+                timestamp_ms: idx * 1000,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_get_snapshots_to_expire() {
+        let now_ms = 5000;
+        let current_snapshot_id = Some(500);
+
+        // Retention disabled.
+        let retention_config = SnapshotRetentionConfig::default();
+        assert!(get_snapshots_to_expire(
+            create_snapshot_entries(),
+            current_snapshot_id,
+            &retention_config,
+            now_ms,
+        )
+        .is_empty());
+
+        // Keep last two snapshots.
+        let retention_config = SnapshotRetentionConfig {
+            max_snapshots_to_keep: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_snapshots_to_expire(
+                create_snapshot_entries(),
+                current_snapshot_id,
+                &retention_config,
+                now_ms,
+            ),
+            vec![300, 200, 100]
+        );
+
+        // Keep snapshots within two seconds.
+        let retention_config = SnapshotRetentionConfig {
+            max_snapshot_age_ms: Some(2000),
+            ..Default::default()
+        };
+        assert_eq!(
+            get_snapshots_to_expire(
+                create_snapshot_entries(),
+                current_snapshot_id,
+                &retention_config,
+                now_ms,
+            ),
+            vec![200, 100]
+        );
+
+        // Current snapshot is always retained, even if it's too old.
+        let retention_config = SnapshotRetentionConfig {
+            max_snapshots_to_keep: Some(4),
+            max_snapshot_age_ms: Some(0),
+            orphan_file_grace_period_ms: None,
+            dry_run: false,
+        };
+        assert_eq!(
+            get_snapshots_to_expire(
+                create_snapshot_entries(),
+                current_snapshot_id,
+                &retention_config,
+                /*now_ms=*/ 10000,
+            ),
+            vec![400, 300, 200, 100]
+        );
+    }
+}
@@ -0,0 +1,106 @@
+/// This module contains util functions to convert mooncake sort order into iceberg one.
+use crate::storage::mooncake_table_config::SortFieldConfig;
+
+use iceberg::spec::{Schema, SortField, SortOrder, Transform};
+use iceberg::Result as IcebergResult;
+use iceberg::{Error as IcebergError, ErrorKind};
+
+/// Sort order id for the table sort order at table creation, which is the first one after unsorted order.
+const TABLE_SORT_ORDER_ID: i64 = 1;
+
+/// Get iceberg sort order for the given mooncake sort order, which is used at table creation.
+/// Unsorted order is returned for unsorted tables.
+pub(crate) fn get_sort_order(
+    schema: &Schema,
+    sort_order: &[SortFieldConfig],
+) -> IcebergResult<SortOrder> {
+    if sort_order.is_empty() {
+        return Ok(SortOrder::unsorted_order());
+    }
+
+    let mut builder = SortOrder::builder();
+    builder.with_order_id(TABLE_SORT_ORDER_ID);
+    for cur_sort_field in sort_order.iter() {
+        let source_field = schema
+            .field_by_name(&cur_sort_field.source_column)
+            .ok_or_else(|| {
+                IcebergError::new(
+                    ErrorKind::DataInvalid,
+                    format!(
+                        "Sort source column {} doesn't exist in iceberg schema",
+                        cur_sort_field.source_column
+                    ),
+                )
+            })?;
+        builder.with_sort_field(
+            SortField::builder()
+                .source_id(source_field.id)
+                .transform(Transform::Identity)
+                .direction(cur_sort_field.direction)
+                .null_order(cur_sort_field.null_order)
+                .build(),
+        );
+    }
+    builder.build(schema)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use iceberg::spec::{NestedField, NullOrder, PrimitiveType, SortDirection, Type};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_get_sort_order() {
+        let schema = Schema::builder()
+            .with_fields(vec![
+                Arc::new(NestedField::required(
+                    1,
+                    "id",
+                    Type::Primitive(PrimitiveType::Int),
+                )),
+                Arc::new(NestedField::optional(
+                    2,
+                    "name",
+                    Type::Primitive(PrimitiveType::String),
+                )),
+            ])
+            .build()
+            .unwrap();
+
+        // Unsorted table.
+        let sort_order = get_sort_order(&schema, &[]).unwrap();
+        assert!(sort_order.is_unsorted());
+
+        // Sorted table.
+        let sort_order = get_sort_order(
+            &schema,
+            &[
+                SortFieldConfig::new("name", SortDirection::Descending, NullOrder::Last),
+                SortFieldConfig::new("id", SortDirection::Ascending, NullOrder::First),
+            ],
+        )
+        .unwrap();
+        assert_eq!(sort_order.order_id, TABLE_SORT_ORDER_ID);
+        let source_ids = sort_order
+            .fields
+            .iter()
+            .map(|field| field.source_id)
+            .collect::<Vec<_>>();
+        assert_eq!(source_ids, vec![2, 1]);
+        assert_eq!(sort_order.fields[0].direction, SortDirection::Descending);
+        assert_eq!(sort_order.fields[0].null_order, NullOrder::Last);
+
+        // Unknown sort column.
+        assert!(get_sort_order(
+            &schema,
+            &[SortFieldConfig::new(
+                "unknown",
+                SortDirection::Ascending,
+                NullOrder::First
+            )],
+        )
+        .is_err());
+    }
+}
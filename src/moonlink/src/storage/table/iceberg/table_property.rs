@@ -1,10 +1,21 @@
 /// This module defines a few iceberg table property related constants and utils.
 /// Reference: https://iceberg.apache.org/docs/latest/configuration/#table-properties
+use crate::storage::mooncake_table_config::ParquetWriterConfig;
 use std::collections::HashMap;
 
 /// Compression codec for parquet files.
 pub(crate) const PARQUET_COMPRESSION: &str = "write.parquet.compression-codec";
 pub(crate) const PARQUET_COMPRESSION_DEFAULT: &str = "snappy";
+pub(crate) const PARQUET_COMPRESSION_LEVEL: &str = "write.parquet.compression-level";
+
+/// Parquet page size.
+pub(crate) const PARQUET_PAGE_SIZE_BYTES: &str = "write.parquet.page-size-bytes";
+
+/// Column-level parquet bloom filter properties, suffixed with column name.
+pub(crate) const PARQUET_BLOOM_FILTER_ENABLED_COLUMN_PREFIX: &str =
+    "write.parquet.bloom-filter-enabled.column.";
+pub(crate) const PARQUET_BLOOM_FILTER_FPP_COLUMN_PREFIX: &str =
+    "write.parquet.bloom-filter-fpp.column.";
 
 /// Compression codec for metadata.
 pub(crate) const METADATA_COMPRESSION: &str = "write.metadata.compression-codec";
@@ -24,13 +35,25 @@ pub(crate) const TABLE_COMMIT_RETRY_TIMEOUT_MS: &str = "commit.retry.total-timeo
 pub(crate) const TABLE_COMMIT_RETRY_TIMEOUT_MS_DEFAULT: u64 = 120000; // 2 min
 
 // Create iceberg table properties from table config.
-pub(crate) fn create_iceberg_table_properties() -> HashMap<String, String> {
+pub(crate) fn create_iceberg_table_properties(
+    parquet_writer_config: &ParquetWriterConfig,
+) -> HashMap<String, String> {
     let mut props = HashMap::with_capacity(6);
     // Compression properties.
     props.insert(
         PARQUET_COMPRESSION.to_string(),
-        PARQUET_COMPRESSION_DEFAULT.to_string(),
+        parquet_writer_config
+            .compression
+            .map(|codec| codec.as_str())
+            .unwrap_or(PARQUET_COMPRESSION_DEFAULT)
+            .to_string(),
     );
+    if let Some(compression_level) = parquet_writer_config.compression_level {
+        props.insert(
+            PARQUET_COMPRESSION_LEVEL.to_string(),
+            compression_level.to_string(),
+        );
+    }
     props.insert(
         METADATA_COMPRESSION.to_string(),
         METADATA_COMPRESSION_DEFAULT.to_string(),
@@ -52,5 +75,73 @@ pub(crate) fn create_iceberg_table_properties() -> HashMap<String, String> {
         TABLE_COMMIT_RETRY_TIMEOUT_MS.to_string(),
         TABLE_COMMIT_RETRY_TIMEOUT_MS_DEFAULT.to_string(),
     );
+    // Parquet writer properties, iceberg doesn't have equivalents for row group row count, dictionary encoding or column-level compression.
+    if let Some(data_page_size) = parquet_writer_config.data_page_size {
+        props.insert(
+            PARQUET_PAGE_SIZE_BYTES.to_string(),
+            data_page_size.to_string(),
+        );
+    }
+    for (column, column_config) in parquet_writer_config.column_configs.iter() {
+        if let Some(bloom_filter_enabled) = column_config.bloom_filter_enabled {
+            props.insert(
+                format!("{PARQUET_BLOOM_FILTER_ENABLED_COLUMN_PREFIX}{column}"),
+                bloom_filter_enabled.to_string(),
+            );
+        }
+        if let Some(fpp) = column_config.bloom_filter_fpp {
+            props.insert(
+                format!("{PARQUET_BLOOM_FILTER_FPP_COLUMN_PREFIX}{column}"),
+                fpp.to_string(),
+            );
+        }
+    }
     props
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::mooncake_table_config::{ParquetColumnConfig, ParquetCompressionCodec};
+
+    #[test]
+    fn test_create_iceberg_table_properties() {
+        // Default parquet writer config.
+        let props = create_iceberg_table_properties(&ParquetWriterConfig::default());
+        assert_eq!(props.get(PARQUET_COMPRESSION).unwrap(), "snappy");
+        assert!(!props.contains_key(PARQUET_COMPRESSION_LEVEL));
+        assert!(!props.contains_key(PARQUET_PAGE_SIZE_BYTES));
+
+        // Customized parquet writer config.
+        let mut parquet_writer_config = ParquetWriterConfig {
+            compression: Some(ParquetCompressionCodec::Zstd),
+            compression_level: Some(3),
+            data_page_size: Some(8192),
+            ..Default::default()
+        };
+        parquet_writer_config.column_configs.insert(
+            "id".to_string(),
+            ParquetColumnConfig {
+                bloom_filter_enabled: Some(true),
+                bloom_filter_fpp: Some(0.05),
+                ..Default::default()
+            },
+        );
+        let props = create_iceberg_table_properties(&parquet_writer_config);
+        assert_eq!(props.get(PARQUET_COMPRESSION).unwrap(), "zstd");
+        assert_eq!(props.get(PARQUET_COMPRESSION_LEVEL).unwrap(), "3");
+        assert_eq!(props.get(PARQUET_PAGE_SIZE_BYTES).unwrap(), "8192");
+        assert_eq!(
+            props
+                .get("write.parquet.bloom-filter-enabled.column.id")
+                .unwrap(),
+            "true"
+        );
+        assert_eq!(
+            props
+                .get("write.parquet.bloom-filter-fpp.column.id")
+                .unwrap(),
+            "0.05"
+        );
+    }
+}
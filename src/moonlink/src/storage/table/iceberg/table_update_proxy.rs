@@ -1,5 +1,8 @@
 use std::collections::{HashMap, HashSet};
 
+use iceberg::spec::{Operation, Snapshot, Summary};
+use iceberg::TableUpdate;
+
 use crate::storage::table::iceberg::{
     moonlink_catalog::PuffinBlobType, puffin_writer_proxy::PuffinBlobMetadataProxy,
 };
@@ -17,6 +20,8 @@ pub(crate) struct TableUpdateProxy {
     pub(crate) puffin_blobs_to_remove: HashSet<String>,
     /// A set of data files to remove, along with their corresponding deletion vectors and file indices.
     pub(crate) data_files_to_remove: HashSet<String>,
+    /// Operation for the snapshot to add, which overrides the append operation set by iceberg-rust.
+    pub(crate) snapshot_operation: Option<Operation>,
 }
 
 impl TableUpdateProxy {
@@ -30,6 +35,29 @@ impl TableUpdateProxy {
         assert!(self.puffin_blobs_to_remove.is_empty());
         self.puffin_blobs_to_remove = puffin_filepaths;
     }
+    /// Notice: it should be only set once, otherwise panic.
+    pub(crate) fn set_snapshot_operation(&mut self, operation: Operation) {
+        assert!(self.snapshot_operation.is_none());
+        self.snapshot_operation = Some(operation);
+    }
+    /// Rewrite operation for snapshots to add, if assigned.
+    pub(crate) fn rewrite_snapshot_operation(
+        &self,
+        table_updates: Vec<TableUpdate>,
+    ) -> Vec<TableUpdate> {
+        let Some(operation) = &self.snapshot_operation else {
+            return table_updates;
+        };
+        table_updates
+            .into_iter()
+            .map(|update| match update {
+                TableUpdate::AddSnapshot { snapshot } => TableUpdate::AddSnapshot {
+                    snapshot: Self::get_snapshot_with_operation(&snapshot, operation.clone()),
+                },
+                _ => update,
+            })
+            .collect()
+    }
     /// Notice: given puffin filepath should correspond to one metadata, and should be set only once.
     pub(crate) fn record_puffin_metadata(
         &mut self,
@@ -54,5 +82,25 @@ impl TableUpdateProxy {
         self.file_index_blobs_to_add.clear();
         self.puffin_blobs_to_remove.clear();
         self.data_files_to_remove.clear();
+        self.snapshot_operation = None;
+    }
+
+    /// Snapshot is immutable, craft a new one with the given operation.
+    fn get_snapshot_with_operation(snapshot: &Snapshot, operation: Operation) -> Snapshot {
+        let summary = Summary {
+            operation,
+            additional_properties: snapshot.summary().additional_properties.clone(),
+        };
+        let builder = Snapshot::builder()
+            .with_snapshot_id(snapshot.snapshot_id())
+            .with_parent_snapshot_id(snapshot.parent_snapshot_id())
+            .with_sequence_number(snapshot.sequence_number())
+            .with_timestamp_ms(snapshot.timestamp_ms())
+            .with_manifest_list(snapshot.manifest_list())
+            .with_summary(summary);
+        match snapshot.schema_id() {
+            Some(schema_id) => builder.with_schema_id(schema_id).build(),
+            None => builder.build(),
+        }
     }
 }
@@ -2,6 +2,10 @@ use crate::row::IdentityProp;
 /// This module contain tests which are not covered by state-machine based test, including complex operations, object storage based tests, etc.
 use crate::row::MoonlinkRow;
 use crate::row::RowValue;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::azure_test_utils;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::test_guard::TestGuard as AzureTestGuard;
 #[cfg(feature = "storage-gcs")]
 use crate::storage::filesystem::gcs::gcs_test_utils;
 #[cfg(feature = "storage-gcs")]
@@ -15,6 +19,7 @@ use crate::storage::index::persisted_bucket_hash_map::GlobalIndex;
 use crate::storage::index::MooncakeIndex;
 use crate::storage::io_utils;
 use crate::storage::mooncake_table::delete_vector::BatchDeletionVector;
+use crate::storage::mooncake_table::table_accessor_test_utils::get_disk_files_for_table;
 use crate::storage::mooncake_table::table_creation_test_utils::*;
 use crate::storage::mooncake_table::table_operation_test_utils::*;
 use crate::storage::mooncake_table::test_utils_commons::ICEBERG_TEST_NAMESPACE;
@@ -25,11 +30,12 @@ use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::mooncake_table::PersistenceSnapshotResult;
 use crate::storage::mooncake_table::{
     PersistenceSnapshotDataCompactionPayload, PersistenceSnapshotImportPayload,
-    PersistenceSnapshotIndexMergePayload,
+    PersistenceSnapshotIndexMergePayload, PersistenceSnapshotTruncationPayload,
 };
 use crate::storage::mooncake_table_config::DiskSliceWriterConfig;
 use crate::storage::mooncake_table_config::IcebergPersistenceConfig;
 use crate::storage::mooncake_table_config::MooncakeTableConfig;
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
 use crate::storage::snapshot_options::IcebergSnapshotOption;
 use crate::storage::snapshot_options::MaintenanceOption;
 use crate::storage::snapshot_options::SnapshotOption;
@@ -62,9 +68,10 @@ use std::collections::HashSet;
 use std::path::PathBuf;
 use std::sync::Arc;
 
-use arrow::datatypes::Schema as ArrowSchema;
+use arrow::datatypes::{DataType, Field, Schema as ArrowSchema};
 use arrow_array::{Int32Array, RecordBatch, StringArray};
 use iceberg::arrow::arrow_schema_to_schema;
+use iceberg::spec::Operation;
 use iceberg::NamespaceIdent;
 use iceberg::TableIdent;
 use parquet::arrow::AsyncArrowWriter;
@@ -391,6 +398,7 @@ async fn test_manifest_entries_write_with_pagination_impl(
             old_file_indices_to_remove: vec![],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persistence_file_params = PersistenceFileParams {
@@ -423,6 +431,7 @@ async fn test_manifest_entries_write_with_pagination_impl(
             old_file_indices_to_remove: vec![],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
     let persistence_file_params = PersistenceFileParams {
         table_auto_incr_ids: 0..(DEFAULT_MAX_MANIFEST_ENTRY_COUNT + 1) as u32, // unused
@@ -515,6 +524,7 @@ async fn test_store_and_load_snapshot_impl(iceberg_table_config: IcebergTableCon
             old_file_indices_to_remove: vec![],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persistence_file_params = PersistenceFileParams {
@@ -569,6 +579,7 @@ async fn test_store_and_load_snapshot_impl(iceberg_table_config: IcebergTableCon
             old_file_indices_to_remove: vec![],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persistence_file_params = PersistenceFileParams {
@@ -649,6 +660,7 @@ async fn test_store_and_load_snapshot_impl(iceberg_table_config: IcebergTableCon
             old_file_indices_to_remove: vec![],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
     let persistence_file_params = PersistenceFileParams {
         table_auto_incr_ids: 4..5,
@@ -734,6 +746,7 @@ async fn test_store_and_load_snapshot_impl(iceberg_table_config: IcebergTableCon
             old_file_indices_to_remove: vec![merged_file_index.clone()],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
     let persistence_file_params = PersistenceFileParams {
         table_auto_incr_ids: 6..7,
@@ -808,6 +821,7 @@ async fn test_store_and_load_snapshot_impl(iceberg_table_config: IcebergTableCon
             old_file_indices_to_remove: vec![compacted_file_index.clone()],
             data_file_records_remap: HashMap::new(),
         },
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
     let persistence_file_params = PersistenceFileParams {
         table_auto_incr_ids: 7..8,
@@ -879,6 +893,18 @@ async fn test_sync_snapshot_with_gcs() {
     test_store_and_load_snapshot_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_sync_snapshot_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_store_and_load_snapshot_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test drop table
 /// ================================
@@ -963,6 +989,18 @@ async fn test_drop_table_with_gcs() {
     test_drop_table_impl(iceberg_table_config).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_drop_table_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_drop_table_impl(iceberg_table_config).await;
+}
+
 /// ================================
 /// Test empty table load
 /// ================================
@@ -1040,6 +1078,18 @@ async fn test_empty_snapshot_load_with_gcs() {
     test_empty_snapshot_load_impl(iceberg_table_config).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_empty_snapshot_load_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_empty_snapshot_load_impl(iceberg_table_config).await;
+}
+
 /// ================================
 /// Test recover from failed snapshot persistence
 /// ================================
@@ -1091,6 +1141,7 @@ async fn test_recover_from_failed_snapshot_impl(iceberg_table_config: IcebergTab
         },
         index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
         data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persistence_file_params = PersistenceFileParams {
@@ -1306,6 +1357,18 @@ async fn test_index_merge_and_create_snapshot_with_gcs() {
     test_index_merge_and_create_snapshot_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_index_merge_and_create_snapshot_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_index_merge_and_create_snapshot_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test data compaction
 /// ================================
@@ -1319,6 +1382,7 @@ async fn test_data_compaction_and_create_snapshot_impl(iceberg_table_config: Ice
         max_data_file_to_compact: 2,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     };
     let mut config = MooncakeTableConfig::new(table_temp_dir.path().to_str().unwrap().to_string());
     config.data_compaction_config = data_compaction_config;
@@ -1478,6 +1542,299 @@ async fn test_data_compaction_and_create_snapshot_with_gcs() {
     test_data_compaction_and_create_snapshot_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_data_compaction_and_create_snapshot_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_data_compaction_and_create_snapshot_impl(iceberg_table_config.clone()).await;
+}
+
+/// ================================
+/// Test snapshot expiration
+/// ================================
+///
+/// Testing scenario: data files replaced by data compaction are only referenced by old snapshots, which get deleted at snapshot expiration.
+#[tokio::test]
+async fn test_snapshot_expiration_after_data_compaction() {
+    let iceberg_temp_dir = tempdir().unwrap();
+    let iceberg_table_config = get_iceberg_table_config(&iceberg_temp_dir);
+
+    // Local filesystem to store write-through cache.
+    let table_temp_dir = tempdir().unwrap();
+    let data_compaction_config = DataCompactionConfig {
+        min_data_file_to_compact: 2,
+        max_data_file_to_compact: 2,
+        data_file_final_size: u64::MAX,
+        data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
+    };
+    let mut config = MooncakeTableConfig::new(table_temp_dir.path().to_str().unwrap().to_string());
+    config.data_compaction_config = data_compaction_config;
+    let mooncake_table_metadata = create_test_table_metadata_with_config(
+        table_temp_dir.path().to_str().unwrap().to_string(),
+        config,
+    );
+
+    // Local filesystem to store read-through cache.
+    let cache_temp_dir = tempdir().unwrap();
+
+    // Create mooncake table and table event notification receiver.
+    let (mut table, mut notify_rx) = create_mooncake_table_and_notify(
+        mooncake_table_metadata.clone(),
+        iceberg_table_config.clone(),
+        create_test_object_storage_cache(&cache_temp_dir), // Use separate cache for each table.
+    )
+    .await;
+    let filesystem_accessor = create_test_filesystem_accessor(&iceberg_table_config);
+
+    // Append rows and commit/flush for multiple times, so we have multiple iceberg snapshots.
+    for (lsn, cur_row) in [test_row_1(), test_row_2(), test_row_3()]
+        .into_iter()
+        .enumerate()
+    {
+        let lsn = lsn as u64 + 1;
+        table.append(cur_row).unwrap();
+        table.commit(lsn);
+        flush_table_and_sync(&mut table, &mut notify_rx, lsn)
+            .await
+            .unwrap();
+    }
+
+    // Attempt data compaction and flush to iceberg table.
+    create_mooncake_and_persist_for_data_compaction_for_test(
+        &mut table,
+        &mut notify_rx,
+        /*injected_committed_deletion_rows=*/ vec![],
+        /*injected_uncommitted_deletion_rows=*/ vec![],
+    )
+    .await;
+
+    // Create a new iceberg table manager to expire snapshots.
+    let mut iceberg_table_manager = IcebergTableManager::new(
+        mooncake_table_metadata.clone(),
+        create_test_object_storage_cache(&cache_temp_dir), // Use separate cache for each table.
+        filesystem_accessor.clone(),
+        iceberg_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = iceberg_table_manager
+        .load_snapshot_from_table()
+        .await
+        .unwrap();
+    let live_files = snapshot
+        .disk_files
+        .keys()
+        .map(|data_file| data_file.file_path().clone())
+        .collect::<HashSet<_>>();
+
+    // Dry run only reports files to delete.
+    let retention_config = SnapshotRetentionConfig {
+        max_snapshots_to_keep: Some(1),
+        max_snapshot_age_ms: None,
+        orphan_file_grace_period_ms: None,
+        dry_run: true,
+    };
+    let report = iceberg_table_manager
+        .expire_snapshots(retention_config.clone())
+        .await
+        .unwrap();
+    assert!(report.dry_run);
+    assert!(!report.expired_snapshot_ids.is_empty());
+    // Two compacted data files are no longer reachable.
+    let unreachable_data_files = report
+        .unreachable_files
+        .iter()
+        .filter(|filepath| filepath.ends_with(".parquet"))
+        .collect::<Vec<_>>();
+    assert_eq!(unreachable_data_files.len(), 2);
+    for cur_file in report.unreachable_files.iter() {
+        assert!(!live_files.contains(cur_file));
+        assert!(filesystem_accessor.object_exists(cur_file).await.unwrap());
+    }
+
+    // Expire snapshots and delete unreachable files.
+    let report = iceberg_table_manager
+        .expire_snapshots(SnapshotRetentionConfig {
+            dry_run: false,
+            ..retention_config
+        })
+        .await
+        .unwrap();
+    assert!(!report.dry_run);
+    for cur_file in report.unreachable_files.iter() {
+        assert!(!filesystem_accessor.object_exists(cur_file).await.unwrap());
+    }
+
+    // Create a new iceberg table manager and check states.
+    let mut iceberg_table_manager_for_recovery = IcebergTableManager::new(
+        mooncake_table_metadata.clone(),
+        create_test_object_storage_cache(&cache_temp_dir), // Use separate cache for each table.
+        filesystem_accessor.clone(),
+        iceberg_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = iceberg_table_manager_for_recovery
+        .load_snapshot_from_table()
+        .await
+        .unwrap();
+    assert_eq!(snapshot.disk_files.len(), 2);
+    assert_eq!(snapshot.indices.file_indices.len(), 2);
+    assert_eq!(snapshot.flush_lsn.unwrap(), 3);
+    validate_recovered_snapshot(
+        &snapshot,
+        &iceberg_table_config
+            .metadata_accessor_config
+            .get_warehouse_uri(),
+        filesystem_accessor.as_ref(),
+    )
+    .await;
+
+    // Nothing to expire with only the current snapshot left.
+    let report = iceberg_table_manager_for_recovery
+        .expire_snapshots(retention_config)
+        .await
+        .unwrap();
+    assert!(report.expired_snapshot_ids.is_empty());
+    assert!(report.unreachable_files.is_empty());
+}
+
+/// Testing scenario: files under table location referenced by no snapshot are deleted as orphan files, after the grace period.
+#[tokio::test]
+async fn test_orphan_file_cleanup() {
+    let iceberg_temp_dir = tempdir().unwrap();
+    let iceberg_table_config = get_iceberg_table_config(&iceberg_temp_dir);
+
+    // Local filesystem to store write-through cache.
+    let table_temp_dir = tempdir().unwrap();
+    let mooncake_table_metadata =
+        create_test_table_metadata(table_temp_dir.path().to_str().unwrap().to_string());
+
+    // Local filesystem to store read-through cache.
+    let cache_temp_dir = tempdir().unwrap();
+
+    // Create mooncake table and table event notification receiver.
+    let (mut table, mut notify_rx) = create_mooncake_table_and_notify(
+        mooncake_table_metadata.clone(),
+        iceberg_table_config.clone(),
+        create_test_object_storage_cache(&cache_temp_dir), // Use separate cache for each table.
+    )
+    .await;
+    let filesystem_accessor = create_test_filesystem_accessor(&iceberg_table_config);
+
+    table.append(test_row_1()).unwrap();
+    table.commit(/*lsn=*/ 1);
+    flush_table_and_sync(&mut table, &mut notify_rx, /*lsn=*/ 1)
+        .await
+        .unwrap();
+
+    let mut iceberg_table_manager = IcebergTableManager::new(
+        mooncake_table_metadata.clone(),
+        create_test_object_storage_cache(&cache_temp_dir), // Use separate cache for each table.
+        filesystem_accessor.clone(),
+        iceberg_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    iceberg_table_manager
+        .load_snapshot_from_table()
+        .await
+        .unwrap();
+
+    // Place an orphan data file, and a file not managed by snapshots.
+    let table_location = iceberg_table_manager
+        .iceberg_table
+        .as_ref()
+        .unwrap()
+        .metadata()
+        .location()
+        .to_string();
+    let orphan_file = format!("{table_location}/data/orphan.parquet");
+    let unmanaged_file = format!("{table_location}/metadata/orphan.metadata.json");
+    for cur_file in [&orphan_file, &unmanaged_file] {
+        filesystem_accessor
+            .write_object(cur_file, b"orphan".to_vec())
+            .await
+            .unwrap();
+    }
+
+    // Files within the grace period are not deleted.
+    let retention_config = SnapshotRetentionConfig {
+        orphan_file_grace_period_ms: Some(3_600_000),
+        ..Default::default()
+    };
+    let report = iceberg_table_manager
+        .expire_snapshots(retention_config)
+        .await
+        .unwrap();
+    assert!(report.orphan_files.is_empty());
+
+    // Dry run only reports orphan files.
+    tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    let retention_config = SnapshotRetentionConfig {
+        orphan_file_grace_period_ms: Some(1),
+        dry_run: true,
+        ..Default::default()
+    };
+    let report = iceberg_table_manager
+        .expire_snapshots(retention_config.clone())
+        .await
+        .unwrap();
+    assert_eq!(report.orphan_files, vec![orphan_file.clone()]);
+    assert!(filesystem_accessor
+        .object_exists(&orphan_file)
+        .await
+        .unwrap());
+
+    // Delete orphan files, while files referenced by the snapshot are kept.
+    let report = iceberg_table_manager
+        .expire_snapshots(SnapshotRetentionConfig {
+            dry_run: false,
+            ..retention_config
+        })
+        .await
+        .unwrap();
+    assert_eq!(report.orphan_files, vec![orphan_file.clone()]);
+    assert!(!filesystem_accessor
+        .object_exists(&orphan_file)
+        .await
+        .unwrap());
+    assert!(filesystem_accessor
+        .object_exists(&unmanaged_file)
+        .await
+        .unwrap());
+
+    // Create a new iceberg table manager and check states.
+    let mut iceberg_table_manager_for_recovery = IcebergTableManager::new(
+        mooncake_table_metadata.clone(),
+        create_test_object_storage_cache(&cache_temp_dir), // Use separate cache for each table.
+        filesystem_accessor.clone(),
+        iceberg_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = iceberg_table_manager_for_recovery
+        .load_snapshot_from_table()
+        .await
+        .unwrap();
+    assert_eq!(snapshot.disk_files.len(), 1);
+    assert_eq!(snapshot.indices.file_indices.len(), 1);
+    validate_recovered_snapshot(
+        &snapshot,
+        &iceberg_table_config
+            .metadata_accessor_config
+            .get_warehouse_uri(),
+        filesystem_accessor.as_ref(),
+    )
+    .await;
+}
+
 /// ================================
 /// Test data compaction with update
 /// ================================
@@ -1491,6 +1848,7 @@ async fn test_data_compaction_with_update_impl(iceberg_table_config: IcebergTabl
         max_data_file_to_compact: 3,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     };
     let mut config = MooncakeTableConfig::new(table_temp_dir.path().to_str().unwrap().to_string());
     config.data_compaction_config = data_compaction_config;
@@ -1577,6 +1935,82 @@ async fn test_data_compaction_with_update() {
     test_data_compaction_with_update_impl(iceberg_table_config).await;
 }
 
+/// Testing scenario: data files and file indices dropped by table truncation are removed from iceberg table.
+#[tokio::test]
+async fn test_truncate_and_create_snapshot() {
+    // Local filesystem for iceberg.
+    let iceberg_temp_dir = tempdir().unwrap();
+    let iceberg_table_config = get_iceberg_table_config(&iceberg_temp_dir);
+
+    // Local filesystem to store write-through cache.
+    let table_temp_dir = tempdir().unwrap();
+    let mooncake_table_metadata =
+        create_test_table_metadata(table_temp_dir.path().to_str().unwrap().to_string());
+
+    // Local filesystem to store read-through cache.
+    let cache_temp_dir = tempdir().unwrap();
+
+    // Create mooncake table and table event notification receiver.
+    let (mut table, mut notify_rx) = create_mooncake_table_and_notify(
+        mooncake_table_metadata.clone(),
+        iceberg_table_config.clone(),
+        create_test_object_storage_cache(&cache_temp_dir),
+    )
+    .await;
+    let filesystem_accessor = create_test_filesystem_accessor(&iceberg_table_config);
+
+    // Append one row and persist.
+    table.append(test_row_1()).unwrap();
+    table.commit(/*lsn=*/ 1);
+    flush_table_and_sync(&mut table, &mut notify_rx, /*lsn=*/ 1)
+        .await
+        .unwrap();
+    create_mooncake_and_persist_for_test(&mut table, &mut notify_rx).await;
+
+    // Truncate table and append another row.
+    table.truncate(/*lsn=*/ 2);
+    table.append(test_row_2()).unwrap();
+    table.commit(/*lsn=*/ 3);
+    flush_table_and_sync(&mut table, &mut notify_rx, /*lsn=*/ 3)
+        .await
+        .unwrap();
+    create_mooncake_and_persist_for_test(&mut table, &mut notify_rx).await;
+
+    // Check mooncake snapshot only contains data file after truncation.
+    let disk_files = get_disk_files_for_table(&table).await;
+    assert_eq!(disk_files.len(), 1);
+
+    // Create a new iceberg table manager and check states.
+    let mut iceberg_table_manager_for_recovery = IcebergTableManager::new(
+        mooncake_table_metadata.clone(),
+        create_test_object_storage_cache(&cache_temp_dir),
+        filesystem_accessor.clone(),
+        iceberg_table_config.clone(),
+    )
+    .await
+    .unwrap();
+    let (_, snapshot) = iceberg_table_manager_for_recovery
+        .load_snapshot_from_table()
+        .await
+        .unwrap();
+    assert_eq!(snapshot.disk_files.len(), 1);
+    assert_eq!(snapshot.indices.file_indices.len(), 1);
+    assert_eq!(snapshot.flush_lsn.unwrap(), 3);
+    check_deletion_vector_consistency_for_snapshot(&snapshot).await;
+    verify_recovered_mooncake_snapshot(&snapshot, /*expected_ids=*/ &[2]).await;
+
+    // Snapshot which removes truncated data files is committed as an overwrite one.
+    let iceberg_snapshot = iceberg_table_manager_for_recovery
+        .iceberg_table
+        .as_ref()
+        .unwrap()
+        .metadata()
+        .current_snapshot()
+        .unwrap()
+        .clone();
+    assert_eq!(iceberg_snapshot.summary().operation, Operation::Overwrite);
+}
+
 /// ================================
 /// Test delayed data compaction
 /// ================================
@@ -1593,6 +2027,7 @@ async fn test_delayed_compaction_impl(iceberg_table_config: IcebergTableConfig)
         max_data_file_to_compact: 3,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     };
     let mut config = MooncakeTableConfig::new(table_temp_dir.path().to_str().unwrap().to_string());
     config.data_compaction_config = data_compaction_config;
@@ -1735,6 +2170,7 @@ async fn test_data_compaction_append_only_and_create_snapshot_impl(
         max_data_file_to_compact: 2,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     };
     let mut config = MooncakeTableConfig::new(table_temp_dir.path().to_str().unwrap().to_string());
     config.data_compaction_config = data_compaction_config;
@@ -1844,6 +2280,18 @@ async fn test_data_compaction_append_only_and_create_snapshot_with_gcs() {
     test_data_compaction_append_only_and_create_snapshot_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_data_compaction_append_only_and_create_snapshot_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_data_compaction_append_only_and_create_snapshot_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test data compaction by deletion
 /// ================================
@@ -1859,6 +2307,7 @@ async fn test_data_compaction_by_deletion_and_create_snapshot_impl(
         max_data_file_to_compact: 2,
         data_file_final_size: 1,
         data_file_deletion_percentage: 50,
+        recluster_overlap_threshold: 0,
     };
     let mut config = MooncakeTableConfig::new(table_temp_dir.path().to_str().unwrap().to_string());
     config.data_compaction_config = data_compaction_config;
@@ -1982,6 +2431,18 @@ async fn test_data_compaction_by_deletion_and_create_snapshot_with_gcs() {
     test_data_compaction_by_deletion_and_create_snapshot_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_data_compaction_by_deletion_and_create_snapshot_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_data_compaction_by_deletion_and_create_snapshot_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test empty snapshot creation
 /// ================================
@@ -2014,6 +2475,7 @@ async fn test_empty_content_snapshot_creation_impl(iceberg_table_config: Iceberg
         import_payload: PersistenceSnapshotImportPayload::default(),
         index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
         data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persistence_file_params = PersistenceFileParams {
@@ -2077,6 +2539,18 @@ async fn test_empty_content_snapshot_creation_with_gcs() {
     test_empty_content_snapshot_creation_impl(iceberg_table_config).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_empty_content_snapshot_creation_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_empty_content_snapshot_creation_impl(iceberg_table_config).await;
+}
+
 /// ================================
 /// Test duplicate local filename
 /// ================================
@@ -2143,6 +2617,7 @@ async fn test_snapshot_creation_with_duplicate_filename_impl(
         },
         index_merge_payload: PersistenceSnapshotIndexMergePayload::default(),
         data_compaction_payload: PersistenceSnapshotDataCompactionPayload::default(),
+        truncation_payload: PersistenceSnapshotTruncationPayload::default(),
     };
 
     let persistence_file_params = PersistenceFileParams {
@@ -2206,6 +2681,18 @@ async fn test_snapshot_creation_with_duplicate_filename_with_gcs() {
     test_snapshot_creation_with_duplicate_filename_impl(iceberg_table_config).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_snapshot_creation_with_duplicate_filename_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_snapshot_creation_with_duplicate_filename_impl(iceberg_table_config).await;
+}
+
 /// Test scenario: small batch size and large parquet file, which means:
 /// 1. all rows live within their own record batch, and potentially their own batch deletion vector.
 /// 2. when flushed to on-disk parquet files, they're grouped into one file but different arrow batch records.
@@ -2629,6 +3116,18 @@ async fn test_async_iceberg_snapshot_with_gcs() {
     test_async_iceberg_snapshot_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_async_iceberg_snapshot_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_async_iceberg_snapshot_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test mooncake snapshot
 /// ================================
@@ -2995,6 +3494,18 @@ async fn test_object_storage_sync_snapshots_with_gcs() {
     mooncake_table_snapshot_persist_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_object_storage_sync_snapshots_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    mooncake_table_snapshot_persist_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test table creation
 /// ================================
@@ -3084,6 +3595,18 @@ async fn test_schema_for_table_creation_with_gcs() {
     test_schema_for_table_creation_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_schema_for_table_creation_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_schema_for_table_creation_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test update schema with update
 /// ================================
@@ -3216,6 +3739,18 @@ async fn test_schema_update_with_no_table_write_with_gcs() {
     test_schema_update_with_no_table_write_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_schema_update_with_no_table_write_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_schema_update_with_no_table_write_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test update schema
 /// ================================
@@ -3342,6 +3877,18 @@ async fn test_schema_update_with_gcs() {
     test_schema_update_impl(iceberg_table_config.clone()).await;
 }
 
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[cfg(feature = "storage-azure")]
+async fn test_schema_update_with_azure() {
+    // Remote object storage for iceberg.
+    let (container, warehouse_uri) = azure_test_utils::get_test_azure_container_and_warehouse();
+    let _test_guard = AzureTestGuard::new(container.clone()).await;
+    let iceberg_table_config = create_iceberg_table_config(warehouse_uri);
+
+    // Common testing logic.
+    test_schema_update_impl(iceberg_table_config.clone()).await;
+}
+
 /// ================================
 /// Test deletion record remap for compaction
 /// ================================
@@ -3365,6 +3912,7 @@ async fn test_persisted_deletion_record_remap() {
         max_data_file_to_compact: u32::MAX,
         data_file_final_size: u64::MAX,
         data_file_deletion_percentage: 0,
+        recluster_overlap_threshold: 0,
     };
     let iceberg_persistence_config = IcebergPersistenceConfig {
         new_data_file_count: 1,
@@ -3934,3 +4482,36 @@ async fn test_partial_completed_streaming_flush() {
     let iceberg_table_config = get_iceberg_table_config(&iceberg_temp_dir);
     test_partial_completed_streaming_flush_impl(iceberg_table_config).await;
 }
+
+/// Testing scenario: schema evolution validation matches fields by field ids, which allows rename, type promotion and nullability relaxation.
+#[test]
+fn test_validate_schema_evolution() {
+    let current_arrow_schema = create_test_arrow_schema();
+    let current_schema = arrow_schema_to_schema(current_arrow_schema.as_ref()).unwrap();
+    let alter_field = |field_idx: usize, name: &str, data_type: DataType, nullable: bool| {
+        let mut fields: Vec<Field> = current_arrow_schema
+            .fields()
+            .iter()
+            .map(|field| field.as_ref().clone())
+            .collect();
+        fields[field_idx] = fields[field_idx]
+            .clone()
+            .with_name(name)
+            .with_data_type(data_type)
+            .with_nullable(nullable);
+        arrow_schema_to_schema(&ArrowSchema::new(fields)).unwrap()
+    };
+
+    // Rename column.
+    let new_schema = alter_field(1, "full_name", DataType::Utf8, /*nullable=*/ true);
+    validate_schema_evolution(&current_schema, &new_schema).unwrap();
+    // Promote column type and relax nullability.
+    let new_schema = alter_field(2, "age", DataType::Int64, /*nullable=*/ true);
+    validate_schema_evolution(&current_schema, &new_schema).unwrap();
+    // Incompatible type change.
+    let new_schema = alter_field(2, "age", DataType::Utf8, /*nullable=*/ false);
+    assert!(validate_schema_evolution(&current_schema, &new_schema).is_err());
+    // Tighten nullability.
+    let new_schema = alter_field(1, "name", DataType::Utf8, /*nullable=*/ false);
+    assert!(validate_schema_evolution(&current_schema, &new_schema).is_err());
+}
@@ -1,4 +1,9 @@
+use crate::storage::mooncake_table_config::{
+    ParquetWriterConfig, PartitionFieldConfig, SortFieldConfig,
+};
 use crate::storage::table::iceberg::moonlink_catalog::MoonlinkCatalog;
+use crate::storage::table::iceberg::partition_utils;
+use crate::storage::table::iceberg::sort_order_utils;
 use crate::storage::table::iceberg::table_property;
 
 use std::collections::HashMap;
@@ -61,6 +66,9 @@ async fn create_iceberg_table<C: MoonlinkCatalog + ?Sized>(
     table_name: &str,
     namespace_ident: NamespaceIdent,
     arrow_schema: &ArrowSchema,
+    partition_spec: &[PartitionFieldConfig],
+    sort_order: &[SortFieldConfig],
+    parquet_writer_config: &ParquetWriterConfig,
 ) -> IcebergResult<IcebergTable> {
     let namespace_already_exists = catalog.namespace_exists(&namespace_ident).await?;
     if !namespace_already_exists {
@@ -70,6 +78,9 @@ async fn create_iceberg_table<C: MoonlinkCatalog + ?Sized>(
     }
 
     let iceberg_schema = IcebergArrow::arrow_schema_to_schema(arrow_schema)?;
+    let unbound_partition_spec =
+        partition_utils::get_unbound_partition_spec(&iceberg_schema, partition_spec)?;
+    let sort_order = sort_order_utils::get_sort_order(&iceberg_schema, sort_order)?;
     let tbl_creation = TableCreation::builder()
         .name(table_name.to_string())
         .location(format!(
@@ -79,7 +90,11 @@ async fn create_iceberg_table<C: MoonlinkCatalog + ?Sized>(
             table_name
         ))
         .schema(iceberg_schema)
-        .properties(table_property::create_iceberg_table_properties())
+        .partition_spec(unbound_partition_spec)
+        .sort_order(sort_order)
+        .properties(table_property::create_iceberg_table_properties(
+            parquet_writer_config,
+        ))
         .build();
     let table = catalog.create_table(&namespace_ident, tbl_creation).await?;
     Ok(table)
@@ -97,6 +112,9 @@ pub(crate) async fn get_or_create_iceberg_table<C: MoonlinkCatalog + ?Sized>(
     namespace: &Vec<String>,
     table_name: &str,
     arrow_schema: &ArrowSchema,
+    partition_spec: &[PartitionFieldConfig],
+    sort_order: &[SortFieldConfig],
+    parquet_writer_config: &ParquetWriterConfig,
 ) -> IcebergResult<IcebergTable> {
     let namespace_ident = NamespaceIdent::from_strs(namespace).unwrap();
     let table_ident = TableIdent::new(namespace_ident.clone(), table_name.to_string());
@@ -114,6 +132,9 @@ pub(crate) async fn get_or_create_iceberg_table<C: MoonlinkCatalog + ?Sized>(
             table_name,
             namespace_ident,
             arrow_schema,
+            partition_spec,
+            sort_order,
+            parquet_writer_config,
         )
         .await
     } else {
@@ -5,6 +5,7 @@ use crate::storage::filesystem::accessor_config::AccessorConfig;
 use crate::storage::filesystem::storage_config::StorageConfig;
 use crate::table_notify::TableEvent;
 use crate::Result;
+use arrow_schema::{Schema, SchemaRef};
 use futures::stream::{self, Stream};
 use futures::{future, StreamExt};
 use more_asserts as ma;
@@ -15,6 +16,8 @@ use std::pin::Pin;
 use std::sync::Arc;
 use tokio::sync::mpsc::Sender;
 
+pub(crate) mod change_feed;
+
 pub const DEFAULT_WAL_FOLDER: &str = "_wal";
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -90,6 +93,10 @@ pub enum WalEvent {
         xact_id: Option<u32>,
         delete_if_exists: bool,
     },
+    Truncate {
+        lsn: u64,
+        xact_id: Option<u32>,
+    },
     Commit {
         lsn: u64,
         xact_id: Option<u32>,
@@ -100,6 +107,11 @@ pub enum WalEvent {
     StreamFlush {
         xact_id: u32,
     },
+    /// Table schema which applies to the events afterwards, recorded at the start of every WAL file and at schema changes.
+    /// It's only consumed by change feed, which decodes rows with it.
+    Schema {
+        schema: Schema,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -150,6 +162,10 @@ impl WalEvent {
                 xact_id: *xact_id,
                 delete_if_exists: *delete_if_exists,
             },
+            TableEvent::Truncate { lsn, xact_id, .. } => WalEvent::Truncate {
+                lsn: *lsn,
+                xact_id: *xact_id,
+            },
             TableEvent::Commit { lsn, xact_id, .. } => WalEvent::Commit {
                 lsn: *lsn,
                 xact_id: *xact_id,
@@ -167,8 +183,9 @@ impl WalEvent {
         }
     }
 
-    pub fn into_table_event(self) -> TableEvent {
-        match self {
+    /// Convert into table event to replay, return [`None`] for events which don't change table data.
+    pub fn into_table_event(self) -> Option<TableEvent> {
+        let table_event = match self {
             WalEvent::Append { row, xact_id, lsn } => TableEvent::Append {
                 row,
                 xact_id,
@@ -187,6 +204,11 @@ impl WalEvent {
                 delete_if_exists,
                 is_recovery: false,
             },
+            WalEvent::Truncate { lsn, xact_id } => TableEvent::Truncate {
+                lsn,
+                xact_id,
+                is_recovery: false,
+            },
             WalEvent::Commit { lsn, xact_id } => TableEvent::Commit {
                 lsn,
                 xact_id,
@@ -201,7 +223,9 @@ impl WalEvent {
                 xact_id,
                 is_recovery: false,
             },
-        }
+            WalEvent::Schema { .. } => return None,
+        };
+        Some(table_event)
     }
 }
 
@@ -439,6 +463,9 @@ pub struct WalManager {
     /// This is in ascending order of completion LSN.
     main_transaction_tracker: Vec<WalTransactionState>,
 
+    /// Current table schema, recorded at the start of every WAL file.
+    table_schema: Option<SchemaRef>,
+
     file_system_accessor: Arc<dyn BaseFileSystemAccess>,
     wal_config: WalConfig,
 }
@@ -447,6 +474,15 @@ impl WalManager {
     pub fn new(config: &WalConfig) -> Self {
         // TODO(Paul): Add a more robust constructor when implementing recovery
         let accessor_config = config.get_accessor_config().clone();
+        // TODO(Paul): Implement object storage
+        Self::new_with_file_system_accessor(config, create_filesystem_accessor(accessor_config))
+    }
+
+    /// Create a WAL manager which persists WAL files through the given accessor, for example, an encrypted one.
+    pub fn new_with_file_system_accessor(
+        config: &WalConfig,
+        file_system_accessor: Arc<dyn BaseFileSystemAccess>,
+    ) -> Self {
         Self {
             in_mem_buf: Vec::new(),
             highest_completion_lsn: 0,
@@ -454,8 +490,8 @@ impl WalManager {
             curr_file_number: 0,
             active_transactions: HashMap::new(),
             main_transaction_tracker: Vec::new(),
-            // TODO(Paul): Implement object storage
-            file_system_accessor: create_filesystem_accessor(accessor_config),
+            table_schema: None,
+            file_system_accessor,
             wal_config: config.clone(),
         }
     }
@@ -566,6 +602,7 @@ impl WalManager {
         match table_event {
             TableEvent::Append { .. }
             | TableEvent::Delete { .. }
+            | TableEvent::Truncate { .. }
             | TableEvent::StreamFlush { .. } => WalTransactionState::Open {
                 start_file: xact_state.get_start_file(),
             },
@@ -593,6 +630,7 @@ impl WalManager {
         let xact_id = match table_event {
             TableEvent::Append { xact_id, .. } => *xact_id,
             TableEvent::Delete { xact_id, .. } => *xact_id,
+            TableEvent::Truncate { xact_id, .. } => *xact_id,
             TableEvent::Commit { xact_id, .. } => *xact_id,
             TableEvent::StreamAbort { xact_id, .. } => Some(*xact_id),
             _ => None, // Other events don't have xact_id
@@ -667,6 +705,29 @@ impl WalManager {
         };
     }
 
+    /// Set table schema, which applies to events pushed afterwards.
+    pub(crate) fn set_table_schema(&mut self, table_schema: SchemaRef) {
+        // Otherwise the new schema is recorded at the start of the next WAL file.
+        if !self.in_mem_buf.is_empty() {
+            self.in_mem_buf.push(WalEvent::Schema {
+                schema: table_schema.as_ref().clone(),
+            });
+        }
+        self.table_schema = Some(table_schema);
+    }
+
+    fn push_wal_event(&mut self, wal_event: WalEvent) {
+        // Every WAL file starts with table schema, so changes could be decoded from any live WAL file.
+        if self.in_mem_buf.is_empty() {
+            if let Some(table_schema) = &self.table_schema {
+                self.in_mem_buf.push(WalEvent::Schema {
+                    schema: table_schema.as_ref().clone(),
+                });
+            }
+        }
+        self.in_mem_buf.push(wal_event);
+    }
+
     pub fn push(&mut self, table_event: &TableEvent) {
         assert!(
             !table_event.is_recovery(),
@@ -674,7 +735,7 @@ impl WalManager {
         );
         // add to in_mem_buf
         let wal_event = WalEvent::new(table_event);
-        self.in_mem_buf.push(wal_event);
+        self.push_wal_event(wal_event);
 
         // Update highest_lsn if this event has a higher LSN
         if let TableEvent::Commit { lsn, .. } | TableEvent::CommitFlush { lsn, .. } = table_event {
@@ -1156,6 +1217,7 @@ impl WalManager {
             curr_file_number: persistent_wal_metadata.curr_file_number,
             active_transactions: persistent_wal_metadata.active_transactions,
             main_transaction_tracker: persistent_wal_metadata.main_transaction_tracker,
+            table_schema: None,
             file_system_accessor,
             wal_config,
         }
@@ -1196,7 +1258,7 @@ impl WalManager {
                         };
                         let table_events = wal_events
                             .into_iter()
-                            .map(|wal| wal.into_table_event())
+                            .filter_map(|wal| wal.into_table_event())
                             .collect();
                         Some((Ok(table_events), file_number + 1))
                     }
@@ -1245,6 +1307,7 @@ impl WalManager {
             // for everything, check if already in iceberg snapshot
             TableEvent::Append { lsn, xact_id, .. }
             | TableEvent::Delete { lsn, xact_id, .. }
+            | TableEvent::Truncate { lsn, xact_id, .. }
             | TableEvent::Commit { lsn, xact_id, .. } => {
                 // Streaming xacts
                 if let Some(xact_id) = xact_id {
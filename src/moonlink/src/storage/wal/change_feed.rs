@@ -0,0 +1,257 @@
+/// This module serves committed changes of a mooncake table from its persisted WAL, which allows subscribers to resume
+/// from any LSN as long as the WAL hasn't been truncated past it.
+use crate::row::MoonlinkRow;
+use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
+use crate::storage::wal::{PersistentWalMetadata, WalConfig, WalEvent, WalManager};
+use crate::{Error, Result};
+use arrow_schema::SchemaRef;
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// A row-level change recorded in the WAL.
+#[derive(Clone, Debug, PartialEq)]
+pub enum WalChange {
+    Insert(MoonlinkRow),
+    /// Deleted row, which only carries identity columns if the source doesn't log full rows.
+    Delete(MoonlinkRow),
+    /// All existing rows are removed.
+    Truncate,
+}
+
+/// Changes of one committed transaction, in the order they're applied.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WalCommittedTransaction {
+    pub commit_lsn: u64,
+    pub changes: Vec<WalChange>,
+    /// Table schema at commit, which rows are encoded with; unassigned if the WAL doesn't record schema.
+    pub schema: Option<SchemaRef>,
+}
+
+/// Change feed for one mooncake table, backed by its persisted WAL files.
+/// Changes become visible to subscribers after the WAL file containing their commit gets persisted.
+#[derive(Clone)]
+pub struct WalChangeFeed {
+    file_system_accessor: Arc<dyn BaseFileSystemAccess>,
+    wal_config: WalConfig,
+    /// Notified after each WAL persistence.
+    wal_flush_lsn_rx: watch::Receiver<u64>,
+}
+
+impl WalChangeFeed {
+    pub fn new(
+        file_system_accessor: Arc<dyn BaseFileSystemAccess>,
+        wal_config: WalConfig,
+        wal_flush_lsn_rx: watch::Receiver<u64>,
+    ) -> Self {
+        Self {
+            file_system_accessor,
+            wal_config,
+            wal_flush_lsn_rx,
+        }
+    }
+
+    /// Subscribe to transactions committed at or after [`start_lsn`].
+    /// Return [`Error::WalTruncated`] if the requested position is no longer retained in the WAL; the same error
+    /// terminates the stream if a slow subscriber falls behind WAL truncation.
+    /// The stream ends when the table is dropped.
+    pub async fn subscribe(
+        &self,
+        start_lsn: u64,
+    ) -> Result<Pin<Box<dyn Stream<Item = Result<WalCommittedTransaction>> + Send>>> {
+        let mut wal_flush_lsn_rx = self.wal_flush_lsn_rx.clone();
+        wal_flush_lsn_rx.borrow_and_update();
+        let mut state = ChangeFeedState {
+            file_system_accessor: self.file_system_accessor.clone(),
+            mooncake_table_id: self.wal_config.get_mooncake_table_id().to_string(),
+            wal_flush_lsn_rx,
+            next_file_number: 0,
+            end_file_number: 0,
+            lowest_pending_lsn: start_lsn,
+            main_changes: Vec::new(),
+            stream_changes: HashMap::new(),
+            table_schema: None,
+        };
+        state.refresh().await?;
+
+        let transactions = stream::unfold(Some(state), |state| async move {
+            let mut state = state?;
+            match state.next_transactions().await {
+                Some(Ok(transactions)) => Some((Ok(transactions), Some(state))),
+                // Stop at the first failure.
+                Some(Err(e)) => Some((Err(e), None)),
+                None => None,
+            }
+        })
+        .flat_map(|result| match result {
+            Ok(transactions) => stream::iter(transactions.into_iter().map(Ok).collect::<Vec<_>>()),
+            Err(e) => stream::iter(vec![Err(e)]),
+        })
+        .boxed();
+        Ok(transactions)
+    }
+}
+
+struct ChangeFeedState {
+    file_system_accessor: Arc<dyn BaseFileSystemAccess>,
+    mooncake_table_id: String,
+    wal_flush_lsn_rx: watch::Receiver<u64>,
+    /// Number of the next WAL file to read.
+    next_file_number: u64,
+    /// Number of the next WAL file to be persisted, as of the last loaded metadata.
+    end_file_number: u64,
+    /// Lowest commit LSN which hasn't been read yet and should be delivered.
+    lowest_pending_lsn: u64,
+    /// Buffered changes for the main transaction.
+    main_changes: Vec<WalChange>,
+    /// Buffered changes for streaming transactions, keyed by transaction id.
+    stream_changes: HashMap<u32, Vec<WalChange>>,
+    /// Table schema of the events being read.
+    table_schema: Option<SchemaRef>,
+}
+
+impl ChangeFeedState {
+    async fn load_metadata(&self) -> Result<Option<PersistentWalMetadata>> {
+        let metadata_file_name =
+            WalManager::get_metadata_file_path_for_mooncake_table(&self.mooncake_table_id);
+        if !self
+            .file_system_accessor
+            .object_exists(&metadata_file_name)
+            .await?
+        {
+            return Ok(None);
+        }
+        let metadata_bytes = self
+            .file_system_accessor
+            .read_object(&metadata_file_name)
+            .await?;
+        Ok(Some(serde_json::from_slice(&metadata_bytes)?))
+    }
+
+    /// Reload WAL metadata, and skip over WAL files which have been truncated.
+    async fn refresh(&mut self) -> Result<()> {
+        let Some(metadata) = self.load_metadata().await? else {
+            return Ok(());
+        };
+        let lowest_live_file_number = metadata
+            .live_wal_files_tracker
+            .first()
+            .map(|file_info| file_info.file_number)
+            .unwrap_or(metadata.curr_file_number);
+        if self.next_file_number < lowest_live_file_number {
+            // Truncated files only contain transactions completed at or before persistence snapshot LSN.
+            if let Some(persistence_snapshot_lsn) = metadata.persistence_snapshot_lsn {
+                if persistence_snapshot_lsn >= self.lowest_pending_lsn {
+                    return Err(Error::wal_truncated(format!(
+                        "WAL for table {} has been truncated up to LSN {persistence_snapshot_lsn}, which is past requested LSN {}",
+                        self.mooncake_table_id, self.lowest_pending_lsn
+                    )));
+                }
+            }
+            // Every transaction touching truncated files has been completed, so buffered changes are stale.
+            self.main_changes.clear();
+            self.stream_changes.clear();
+            self.next_file_number = lowest_live_file_number;
+        }
+        self.end_file_number = metadata.curr_file_number;
+        Ok(())
+    }
+
+    /// Read the next persisted WAL file, return `None` if all persisted files have been read.
+    async fn read_next_file(&mut self) -> Result<Option<Vec<WalEvent>>> {
+        loop {
+            if self.next_file_number >= self.end_file_number {
+                return Ok(None);
+            }
+            let file_name = WalManager::get_wal_file_path_for_mooncake_table(
+                self.next_file_number,
+                &self.mooncake_table_id,
+            );
+            match self.file_system_accessor.read_object(&file_name).await {
+                Ok(bytes) => {
+                    self.next_file_number += 1;
+                    return Ok(Some(serde_json::from_slice(&bytes)?));
+                }
+                Err(e) => {
+                    // WAL file could be truncated after metadata is loaded, reload to check whether it's still needed.
+                    if self.file_system_accessor.object_exists(&file_name).await? {
+                        return Err(e);
+                    }
+                    let next_file_number = self.next_file_number;
+                    self.refresh().await?;
+                    if self.next_file_number == next_file_number {
+                        return Err(e);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Apply WAL events to buffered changes, and return transactions committed within them.
+    fn apply_wal_events(&mut self, wal_events: Vec<WalEvent>) -> Vec<WalCommittedTransaction> {
+        let mut transactions = Vec::new();
+        for cur_event in wal_events.into_iter() {
+            let (xact_id, change) = match cur_event {
+                WalEvent::Append { row, xact_id, .. } => (xact_id, WalChange::Insert(row)),
+                WalEvent::Delete { row, xact_id, .. } => (xact_id, WalChange::Delete(row)),
+                WalEvent::Truncate { xact_id, .. } => (xact_id, WalChange::Truncate),
+                WalEvent::Commit { lsn, xact_id } => {
+                    let changes = match xact_id {
+                        Some(xact_id) => self.stream_changes.remove(&xact_id).unwrap_or_default(),
+                        None => std::mem::take(&mut self.main_changes),
+                    };
+                    // Transactions committed before the requested LSN have been delivered, or were partially truncated.
+                    if lsn >= self.lowest_pending_lsn {
+                        self.lowest_pending_lsn = lsn + 1;
+                        if !changes.is_empty() {
+                            transactions.push(WalCommittedTransaction {
+                                commit_lsn: lsn,
+                                changes,
+                                schema: self.table_schema.clone(),
+                            });
+                        }
+                    }
+                    continue;
+                }
+                WalEvent::StreamAbort { xact_id } => {
+                    self.stream_changes.remove(&xact_id);
+                    continue;
+                }
+                WalEvent::StreamFlush { .. } => continue,
+                WalEvent::Schema { schema } => {
+                    self.table_schema = Some(Arc::new(schema));
+                    continue;
+                }
+            };
+            match xact_id {
+                Some(xact_id) => self.stream_changes.entry(xact_id).or_default().push(change),
+                None => self.main_changes.push(change),
+            }
+        }
+        transactions
+    }
+
+    /// Get transactions committed in the next WAL file, which waits for WAL persistence if all persisted files have been read.
+    /// Return `None` if the table has been dropped.
+    async fn next_transactions(&mut self) -> Option<Result<Vec<WalCommittedTransaction>>> {
+        loop {
+            match self.read_next_file().await {
+                Ok(Some(wal_events)) => {
+                    let transactions = self.apply_wal_events(wal_events);
+                    if !transactions.is_empty() {
+                        return Some(Ok(transactions));
+                    }
+                }
+                Ok(None) => {
+                    self.wal_flush_lsn_rx.changed().await.ok()?;
+                    if let Err(e) = self.refresh().await {
+                        return Some(Err(e));
+                    }
+                }
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
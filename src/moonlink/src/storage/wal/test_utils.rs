@@ -1,4 +1,8 @@
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::azure_test_utils;
+#[cfg(feature = "storage-azure")]
+use crate::storage::filesystem::azure::test_guard::TestGuard as AzureTestGuard;
 #[cfg(feature = "storage-gcs")]
 use crate::storage::filesystem::gcs::gcs_test_utils;
 #[cfg(feature = "storage-gcs")]
@@ -70,6 +74,8 @@ pub enum WalTestEnv {
     Gcs((WalConfig, GcsTestGuard)),
     #[cfg(feature = "storage-s3")]
     S3((WalConfig, S3TestGuard)),
+    #[cfg(feature = "storage-azure")]
+    Azure((WalConfig, AzureTestGuard)),
 }
 
 impl WalTestEnv {
@@ -92,6 +98,17 @@ impl WalTestEnv {
             return WalTestEnv::S3((wal_config, test_guard));
         }
 
+        #[cfg(feature = "storage-azure")]
+        if path_or_obj_store_indicator == "azure" {
+            let (container, warehouse_uri) =
+                azure_test_utils::get_test_azure_container_and_warehouse();
+            let test_guard = AzureTestGuard::new(container.clone()).await;
+            let azure_storage_config =
+                azure_test_utils::create_azure_storage_config(&warehouse_uri);
+            let wal_config = WalConfig::new(azure_storage_config, WAL_TEST_TABLE_ID);
+            return WalTestEnv::Azure((wal_config, test_guard));
+        }
+
         let test_context = TestContext::new(path_or_obj_store_indicator);
         WalTestEnv::Local(
             WalConfig::default_wal_config_local(
@@ -109,6 +126,8 @@ impl WalTestEnv {
             WalTestEnv::Gcs((wal_config, _)) => wal_config.clone(),
             #[cfg(feature = "storage-s3")]
             WalTestEnv::S3((wal_config, _)) => wal_config.clone(),
+            #[cfg(feature = "storage-azure")]
+            WalTestEnv::Azure((wal_config, _)) => wal_config.clone(),
         }
     }
 }
@@ -1,16 +1,21 @@
+use arrow_schema::{DataType, Field, Schema};
 use rstest::rstest;
+use std::sync::Arc;
 
+use crate::storage::mooncake_table::test_utils::test_row;
 use crate::storage::wal::test_utils::WalTestEnv;
 use crate::storage::wal::test_utils::*;
 use crate::storage::wal::WalManager;
 use crate::storage::wal::{PersistentWalMetadata, WalTransactionState};
-use crate::TableEvent;
 use crate::{assert_wal_file_does_not_exist, assert_wal_file_exists, assert_wal_logs_equal};
+use crate::{Error, TableEvent, WalChange, WalChangeFeed, WalCommittedTransaction};
+use futures::StreamExt;
 
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_persist")]
 async fn test_wal_insert_persist_files(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -36,6 +41,7 @@ async fn test_wal_insert_persist_files(#[case] path_or_obj_store_indicator: &str
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_empty_persist")]
 async fn test_wal_empty_persist(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -53,6 +59,7 @@ async fn test_wal_empty_persist(#[case] path_or_obj_store_indicator: &str) {
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_file_numbering")]
 async fn test_wal_file_numbering_sequence(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -84,6 +91,7 @@ async fn test_wal_file_numbering_sequence(#[case] path_or_obj_store_indicator: &
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_truncation")]
 async fn test_wal_truncation_deletes_files(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -134,6 +142,7 @@ async fn test_wal_truncation_deletes_files(#[case] path_or_obj_store_indicator:
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_truncation_no_files")]
 async fn test_wal_truncation_with_no_files(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -150,6 +159,7 @@ async fn test_wal_truncation_with_no_files(#[case] path_or_obj_store_indicator:
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_truncation_delete_all")]
 async fn test_wal_truncation_deletes_all_files(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -181,6 +191,7 @@ async fn test_wal_truncation_deletes_all_files(#[case] path_or_obj_store_indicat
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_persist_truncate")]
 async fn test_wal_truncate_incomplete_main_xact(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -212,6 +223,7 @@ async fn test_wal_truncate_incomplete_main_xact(#[case] path_or_obj_store_indica
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_persist_truncate")]
 async fn test_wal_truncate_unfinished_main_xact_multiple_commits(
     #[case] path_or_obj_store_indicator: &str,
@@ -260,6 +272,7 @@ async fn test_wal_truncate_unfinished_main_xact_multiple_commits(
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_truncate_main_and_streaming_xact_interleave")]
 async fn test_wal_truncate_main_and_streaming_xact_interleave(
     #[case] path_or_obj_store_indicator: &str,
@@ -312,6 +325,7 @@ async fn test_wal_truncate_main_and_streaming_xact_interleave(
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_multiple_interleaved_truncations")]
 async fn test_wal_multiple_interleaved_truncations(#[case] path_or_obj_store_indicator: &str) {
     // multiple truncations should behave
@@ -380,6 +394,7 @@ async fn test_wal_multiple_interleaved_truncations(#[case] path_or_obj_store_ind
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_stream_abort")]
 async fn test_wal_stream_abort(#[case] path_or_obj_store_indicator: &str) {
     // Testing case: streaming xact is not finished and prevents file cleanup
@@ -430,6 +445,7 @@ async fn test_wal_stream_abort(#[case] path_or_obj_store_indicator: &str) {
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_recovery_basic")]
 async fn test_wal_recovery_basic(#[case] path_or_obj_store_indicator: &str) {
     let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
@@ -456,6 +472,7 @@ async fn test_wal_recovery_basic(#[case] path_or_obj_store_indicator: &str) {
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_main_tracker_merge_subset")]
 async fn test_main_tracker_merges_multiple_subset_commits_in_same_file(
     #[case] path_or_obj_store_indicator: &str,
@@ -505,6 +522,7 @@ async fn test_main_tracker_merges_multiple_subset_commits_in_same_file(
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_main_tracker_spanning_and_subset")]
 async fn test_main_tracker_allows_one_spanning_and_one_subset_per_file(
     #[case] path_or_obj_store_indicator: &str,
@@ -564,6 +582,7 @@ async fn test_main_tracker_allows_one_spanning_and_one_subset_per_file(
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_main_tracker_highest_subset")]
 async fn test_main_tracker_keeps_highest_subset_commit_per_file(
     #[case] path_or_obj_store_indicator: &str,
@@ -633,6 +652,7 @@ async fn test_main_tracker_keeps_highest_subset_commit_per_file(
 #[rstest]
 #[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
 #[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
 #[case::local("wal_recovery_metadata_source_of_truth")]
 async fn test_recovery_uses_metadata_as_source_of_truth_when_file_persisted_but_metadata_not(
     #[case] path_or_obj_store_indicator: &str,
@@ -717,3 +737,142 @@ async fn test_recovery_uses_metadata_as_source_of_truth_when_file_persisted_but_
     // Ensure only metadata-tracked events were replayed (i.e., exactly file 0's events)
     assert_ingestion_events_vectors_equal(&replayed_events, &expected_events_file0);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[rstest]
+#[cfg_attr(feature = "storage-gcs", case::gcs("gcs"))]
+#[cfg_attr(feature = "storage-s3", case::s3("s3"))]
+#[cfg_attr(feature = "storage-azure", case::azure("azure"))]
+#[case::local("wal_change_feed")]
+async fn test_wal_change_feed(#[case] path_or_obj_store_indicator: &str) {
+    let wal_test_env = WalTestEnv::new_from_string(path_or_obj_store_indicator).await;
+    let wal_config = wal_test_env.get_wal_config();
+    let mut wal = WalManager::new(&wal_config);
+    let mut expected_events = Vec::new();
+    let row = test_row(1, "Alice", 30);
+
+    // Main transaction committed at LSN 3, streaming transaction 7 committed at LSN 5, and aborted streaming transaction 8.
+    add_new_example_append_event(1, None, &mut wal, &mut expected_events);
+    add_new_example_delete_event(2, None, &mut wal, &mut expected_events);
+    add_new_example_append_event(3, Some(7), &mut wal, &mut expected_events);
+    add_new_example_append_event(3, Some(8), &mut wal, &mut expected_events);
+    add_new_example_commit_event(3, None, &mut wal, &mut expected_events);
+    add_new_example_stream_abort_event(8, &mut wal, &mut expected_events);
+    add_new_example_commit_event(5, Some(7), &mut wal, &mut expected_events);
+    wal.do_wal_persistence_update_for_test(None).await.unwrap();
+
+    let (wal_flush_lsn_tx, wal_flush_lsn_rx) = tokio::sync::watch::channel(5);
+    let change_feed = WalChangeFeed::new(
+        wal.get_file_system_accessor(),
+        wal_config.clone(),
+        wal_flush_lsn_rx,
+    );
+    let mut changes = change_feed.subscribe(/*start_lsn=*/ 0).await.unwrap();
+    assert_eq!(
+        changes.next().await.unwrap().unwrap(),
+        WalCommittedTransaction {
+            commit_lsn: 3,
+            changes: vec![
+                WalChange::Insert(row.clone()),
+                WalChange::Delete(row.clone())
+            ],
+            schema: None,
+        }
+    );
+    assert_eq!(
+        changes.next().await.unwrap().unwrap(),
+        WalCommittedTransaction {
+            commit_lsn: 5,
+            changes: vec![WalChange::Insert(row.clone())],
+            schema: None,
+        }
+    );
+
+    // Subscriber resumes from the requested LSN.
+    let mut resumed_changes = change_feed.subscribe(/*start_lsn=*/ 4).await.unwrap();
+    assert_eq!(resumed_changes.next().await.unwrap().unwrap().commit_lsn, 5);
+
+    // Changes persisted afterwards are delivered to existing subscribers.
+    add_new_example_append_event(6, None, &mut wal, &mut expected_events);
+    add_new_example_commit_event(7, None, &mut wal, &mut expected_events);
+    wal.do_wal_persistence_update_for_test(None).await.unwrap();
+    wal_flush_lsn_tx.send(7).unwrap();
+    assert_eq!(
+        changes.next().await.unwrap().unwrap(),
+        WalCommittedTransaction {
+            commit_lsn: 7,
+            changes: vec![WalChange::Insert(row.clone())],
+            schema: None,
+        }
+    );
+
+    // Positions captured by iceberg snapshot are no longer available after WAL truncation.
+    wal.do_wal_persistence_update_for_test(Some(7))
+        .await
+        .unwrap();
+    assert!(matches!(
+        change_feed.subscribe(/*start_lsn=*/ 4).await,
+        Err(Error::WalTruncated(_))
+    ));
+    let mut latest_changes = change_feed.subscribe(/*start_lsn=*/ 8).await.unwrap();
+
+    // Change feed ends when the table is dropped.
+    drop(wal_flush_lsn_tx);
+    assert!(latest_changes.next().await.is_none());
+}
+
+/// Testing scenario: change feed decodes every transaction with the table schema at its commit.
+#[tokio::test]
+async fn test_wal_change_feed_with_schema_change() {
+    let wal_test_env = WalTestEnv::new_from_string("wal_change_feed_schema_change").await;
+    let wal_config = wal_test_env.get_wal_config();
+    let mut wal = WalManager::new(&wal_config);
+    let mut expected_events = Vec::new();
+    let old_schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+    let new_schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, true),
+    ]));
+
+    // Schema changes in the middle of a WAL file.
+    wal.set_table_schema(old_schema.clone());
+    add_new_example_append_event(1, None, &mut wal, &mut expected_events);
+    add_new_example_commit_event(1, None, &mut wal, &mut expected_events);
+    wal.set_table_schema(new_schema.clone());
+    add_new_example_append_event(2, None, &mut wal, &mut expected_events);
+    add_new_example_commit_event(2, None, &mut wal, &mut expected_events);
+    wal.do_wal_persistence_update_for_test(None).await.unwrap();
+    // The next WAL file starts with the latest schema.
+    add_new_example_append_event(3, None, &mut wal, &mut expected_events);
+    add_new_example_commit_event(3, None, &mut wal, &mut expected_events);
+    wal.do_wal_persistence_update_for_test(None).await.unwrap();
+
+    let (_wal_flush_lsn_tx, wal_flush_lsn_rx) = tokio::sync::watch::channel(3);
+    let change_feed = WalChangeFeed::new(
+        wal.get_file_system_accessor(),
+        wal_config.clone(),
+        wal_flush_lsn_rx,
+    );
+    let mut changes = change_feed.subscribe(/*start_lsn=*/ 0).await.unwrap();
+    for (commit_lsn, schema) in [(1, &old_schema), (2, &new_schema), (3, &new_schema)] {
+        let transaction = changes.next().await.unwrap().unwrap();
+        assert_eq!(transaction.commit_lsn, commit_lsn);
+        assert_eq!(transaction.schema.as_ref(), Some(schema));
+    }
+
+    // Schema records are not replayed at recovery.
+    let metadata = WalManager::recover_from_persistent_wal_metadata(
+        wal.get_file_system_accessor(),
+        wal_config.clone(),
+    )
+    .await
+    .unwrap();
+    let replayed_events =
+        WalManager::recover_flushed_wals_flat(wal.get_file_system_accessor(), &metadata)
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .map(|event| event.unwrap())
+            .collect::<Vec<_>>();
+    assert_ingestion_events_vectors_equal(&replayed_events, &expected_events);
+}
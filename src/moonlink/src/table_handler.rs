@@ -9,8 +9,10 @@
 /// - persisted table LSN: the largest LSN where all updates have been persisted into iceberg
 ///   Suppose we have two tables, table-A has persisted all updated into iceberg; with table-B taking new updates. persisted table LSN for table-A grows with table-B.
 use crate::event_sync::EventSyncSender;
+use crate::memory_accountant::{MemoryAccountant, TableMemoryTracker};
 use crate::storage::mooncake_table::replay::replay_events::MooncakeTableEvent;
 use crate::storage::mooncake_table::AlterTableRequest;
+use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::snapshot_options::IcebergSnapshotOption;
 use crate::storage::snapshot_options::MaintenanceOption;
 use crate::storage::snapshot_options::SnapshotOption;
@@ -21,7 +23,7 @@ use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::watch;
 use tokio::task::JoinHandle;
 use tracing::Instrument;
-use tracing::{debug, error, info_span};
+use tracing::{debug, error, info, info_span};
 pub(crate) mod table_handler_state;
 use table_handler_state::{
     MaintenanceProcessStatus, MaintenanceRequestStatus, SpecialTableState, TableHandlerState,
@@ -50,10 +52,18 @@ impl TableHandler {
         replication_lsn_rx: watch::Receiver<u64>,
         handler_event_replay_tx: Option<mpsc::UnboundedSender<TableEvent>>,
         table_event_replay_tx: Option<mpsc::UnboundedSender<MooncakeTableEvent>>,
+        memory_accountant: MemoryAccountant,
     ) -> Self {
         // Create channel for events
         let (event_sender, event_receiver) = mpsc::channel(MAX_BUFFERED_TABLE_EVENTS);
 
+        // Register table to memory accountant, so it could be force flushed on memory pressure.
+        let memory_tracker = memory_accountant.register_table(
+            table.get_table_id(),
+            table.get_mooncake_table_id().to_string(),
+            &event_sender,
+        );
+
         // Register channel for internal control events.
         table.register_table_notify(event_sender.clone()).await;
         // Register channel for mooncake table events replay.
@@ -64,6 +74,7 @@ impl TableHandler {
         let event_sender_for_periodical_snapshot = event_sender.clone();
         let event_sender_for_periodical_force_snapshot = event_sender.clone();
         let event_sender_for_periodical_wal = event_sender.clone();
+        let event_sender_for_periodical_snapshot_expiration = event_sender.clone();
         let periodic_event_handle = tokio::spawn(async move {
             loop {
                 tokio::select! {
@@ -83,6 +94,11 @@ impl TableHandler {
                             return;
                         }
                     }
+                    _ = table_handler_timer.snapshot_expiration_timer.tick() => {
+                        if event_sender_for_periodical_snapshot_expiration.send(TableEvent::PeriodicalSnapshotExpiration(uuid::Uuid::new_v4())).await.is_err() {
+                            return;
+                        }
+                    }
                     else => {
                         break;
                     }
@@ -99,6 +115,7 @@ impl TableHandler {
                     event_receiver,
                     replication_lsn_rx,
                     handler_event_replay_tx,
+                    memory_tracker,
                     table,
                 )
                 .await;
@@ -127,6 +144,7 @@ impl TableHandler {
         mut event_receiver: Receiver<TableEvent>,
         replication_lsn_rx: watch::Receiver<u64>,
         handler_event_replay_tx: Option<mpsc::UnboundedSender<TableEvent>>,
+        mut memory_tracker: TableMemoryTracker,
         mut table: MooncakeTable,
     ) {
         let persistence_snapshot_lsn = table.get_persistence_snapshot_lsn();
@@ -229,7 +247,9 @@ impl TableHandler {
                         storage_config,
                         lsn,
                     } => {
-                        table.batch_ingest(files, storage_config, lsn).await;
+                        if let Err(e) = table.batch_ingest(files, storage_config, lsn).await {
+                            error!(error = %e, "failed to load files");
+                        }
                     }
 
                     // ==============================
@@ -329,6 +349,15 @@ impl TableHandler {
                         table_handler_state.data_compaction_request_status =
                             MaintenanceRequestStatus::ForceFull;
                     }
+                    // Branch to trigger a force snapshot expiration request, which starts when table manager is not in use.
+                    TableEvent::ForceSnapshotExpiration { dry_run } => {
+                        table_handler_state.request_snapshot_expiration(dry_run);
+                        if let Some(dry_run) =
+                            table_handler_state.take_snapshot_expiration_request()
+                        {
+                            table.perform_snapshot_expiration(dry_run);
+                        }
+                    }
                     // Branch to drop the iceberg table and clear pinned data files from the global object storage cache, only used when the whole table requested to drop.
                     // So we block wait for asynchronous request completion.
                     TableEvent::DropTable => {
@@ -341,13 +370,8 @@ impl TableHandler {
                         // Otherwise, leave a drop marker to clean up states later.
                         table_handler_state.mark_drop_table();
                     }
-                    TableEvent::AlterTable { columns_to_drop } => {
-                        debug!("altering table, dropping columns: {:?}", columns_to_drop);
-                        let alter_table_request = AlterTableRequest {
-                            new_columns: vec![],
-                            dropped_columns: columns_to_drop,
-                        };
-                        table_handler_state.start_alter_table(alter_table_request);
+                    event @ TableEvent::AlterTable { .. } => {
+                        Self::process_alter_table_event(event, &table, &mut table_handler_state);
                     }
                     TableEvent::StartInitialCopy => {
                         debug!("starting initial copy");
@@ -389,7 +413,7 @@ impl TableHandler {
                         }
 
                         if table_handler_state.has_pending_force_snapshot_request()
-                            && !table_handler_state.persistence_snapshot_ongoing
+                            && !table_handler_state.is_table_manager_in_use()
                         {
                             // flush if needed
                             if let Some(commit_lsn) = table_handler_state.table_consistent_view_lsn
@@ -435,37 +459,26 @@ impl TableHandler {
                             );
                     }
                     TableEvent::RegularIcebergSnapshot {
-                        mut persistence_snapshot_payload,
+                        persistence_snapshot_payload,
                     } => {
-                        // Update table maintenance status.
-                        if persistence_snapshot_payload.contains_table_maintenance_payload()
-                            && table_handler_state.table_maintenance_process_status
-                                == MaintenanceProcessStatus::ReadyToPersist
-                        {
-                            table_handler_state.table_maintenance_process_status =
-                                MaintenanceProcessStatus::InPersist;
-                        }
-                        table_handler_state.persistence_snapshot_ongoing = true;
-                        if table_handler_state
-                            .should_complete_alter_table(persistence_snapshot_payload.flush_lsn)
-                        {
-                            if let SpecialTableState::AlterTable {
-                                ref mut alter_table_request,
-                                ..
-                            } = table_handler_state.special_table_state
-                            {
-                                let new_table_metadata =
-                                    table.alter_table(alter_table_request.take().unwrap());
-                                persistence_snapshot_payload.new_table_schema =
-                                    Some(new_table_metadata);
-                            } else {
-                                unreachable!("alter table request is not set");
-                            }
-                            table_handler_state.finish_alter_table();
-                            Self::process_blocked_events(&mut table, &mut table_handler_state)
-                                .await;
+                        // Snapshot expiration could start after the payload gets queued, buffer and persist it after expiration completes.
+                        if table_handler_state.snapshot_expiration_ongoing {
+                            debug!(
+                                "buffer iceberg snapshot because of ongoing snapshot expiration"
+                            );
+                            assert!(table_handler_state
+                                .buffered_persistence_snapshot_payload
+                                .is_none());
+                            table_handler_state.buffered_persistence_snapshot_payload =
+                                Some(persistence_snapshot_payload);
+                            continue;
                         }
-                        table.persist_iceberg_snapshot(persistence_snapshot_payload);
+                        Self::start_persistence_snapshot(
+                            &mut table,
+                            &mut table_handler_state,
+                            persistence_snapshot_payload,
+                        )
+                        .await;
                     }
                     TableEvent::MooncakeTableSnapshotResult {
                         mooncake_snapshot_result,
@@ -483,6 +496,19 @@ impl TableHandler {
                         table.mark_mooncake_snapshot_completed();
                         table_handler_state.mooncake_snapshot_ongoing = false;
 
+                        // Table maintenance result could be discarded by table truncation, which has nothing to persist.
+                        if mooncake_snapshot_result.maintenance_result_discarded
+                            && table_handler_state.table_maintenance_process_status
+                                == MaintenanceProcessStatus::ReadyToPersist
+                        {
+                            table_handler_state.table_maintenance_process_status =
+                                MaintenanceProcessStatus::Unrequested;
+                            // Table maintenance could come from table internal events, which doesn't have notification receiver.
+                            let _ = table_handler_state
+                                .table_maintenance_completion_tx
+                                .send(Ok(()));
+                        }
+
                         // Drop table if requested, and table at a clean state.
                         if table_handler_state.special_table_state == SpecialTableState::DropTable
                             && table_handler_state.can_drop_table_now(table.has_ongoing_flush())
@@ -508,7 +534,7 @@ impl TableHandler {
                             mooncake_snapshot_result.commit_lsn,
                             min_pending_flush_lsn,
                             table_handler_state.persistence_snapshot_result_consumed,
-                            table_handler_state.persistence_snapshot_ongoing,
+                            table_handler_state.is_table_manager_in_use(),
                         ) {
                             if let Some(persistence_snapshot_payload) =
                                 mooncake_snapshot_result.persistence_snapshot_payload
@@ -645,6 +671,13 @@ impl TableHandler {
                                 table.set_persistence_snapshot_res(snapshot_res);
                                 table_handler_state.persistence_snapshot_result_consumed = false;
 
+                                // Start pending snapshot expiration, now that table manager is available.
+                                if let Some(dry_run) =
+                                    table_handler_state.take_snapshot_expiration_request()
+                                {
+                                    table.perform_snapshot_expiration(dry_run);
+                                }
+
                                 // Notify all waiters with LSN satisfied.
                                 let replication_lsn = *replication_lsn_rx.borrow();
                                 table_handler_state.update_iceberg_persisted_lsn(
@@ -732,9 +765,76 @@ impl TableHandler {
                             return;
                         }
                     }
+                    TableEvent::PeriodicalSnapshotExpiration(_) => {
+                        if !table.get_snapshot_retention_config().is_enabled() {
+                            continue;
+                        }
+                        table_handler_state.request_snapshot_expiration(/*dry_run=*/ false);
+                        if let Some(dry_run) =
+                            table_handler_state.take_snapshot_expiration_request()
+                        {
+                            table.perform_snapshot_expiration(dry_run);
+                        }
+                    }
+                    TableEvent::SnapshotExpirationResult {
+                        snapshot_expiration_result,
+                    } => {
+                        table_handler_state.snapshot_expiration_ongoing = false;
+                        let report = table.set_snapshot_expiration_res(snapshot_expiration_result);
+                        match &report {
+                            Ok(report) => {
+                                info!(
+                                    dry_run = report.dry_run,
+                                    expired_snapshot_ids = ?report.expired_snapshot_ids,
+                                    unreachable_files = ?report.unreachable_files,
+                                    orphan_files = ?report.orphan_files,
+                                    "snapshot expiration completed"
+                                );
+                            }
+                            Err(err) => {
+                                error!(error = ?err, "failed to expire snapshots");
+                            }
+                        }
+                        // Snapshot expiration could come from periodical events, which doesn't have notification receiver.
+                        let _ = event_sync_sender
+                            .snapshot_expiration_completion_tx
+                            .send(report);
+
+                        // Check whether need to drop table.
+                        if table_handler_state.special_table_state == SpecialTableState::DropTable
+                            && table_handler_state.can_drop_table_now(table.has_ongoing_flush())
+                        {
+                            drop_table(&mut table, event_sync_sender).await;
+                            return;
+                        }
+
+                        // Persist iceberg snapshot buffered during the ongoing expiration, which takes precedence over pending expiration requests.
+                        if let Some(persistence_snapshot_payload) = table_handler_state
+                            .buffered_persistence_snapshot_payload
+                            .take()
+                        {
+                            Self::start_persistence_snapshot(
+                                &mut table,
+                                &mut table_handler_state,
+                                persistence_snapshot_payload,
+                            )
+                            .await;
+                        }
+
+                        // Start snapshot expiration requested during the ongoing one.
+                        if let Some(dry_run) =
+                            table_handler_state.take_snapshot_expiration_request()
+                        {
+                            table.perform_snapshot_expiration(dry_run);
+                        }
+                    }
                     TableEvent::EvictedFilesToDelete { evicted_files } => {
                         start_task_to_delete_evicted(evicted_files.files);
                     }
+                    TableEvent::MemoryPressureFlush => {
+                        memory_tracker.complete_flush_request();
+                        Self::flush_on_memory_pressure(&mut table, &mut table_handler_state);
+                    }
                     TableEvent::PeriodicalPersistenceUpdateWal(uuid) => {
                         if !table_handler_state.wal_persist_ongoing {
                             table_handler_state.wal_persist_ongoing = true;
@@ -817,6 +917,9 @@ impl TableHandler {
                     }
                 }
             }
+
+            // Report memory usage once per batch, rather than per event.
+            memory_tracker.update(table.get_estimated_memory_size());
         }
         // If all senders have been dropped, exit the loop
         if let Err(e) = table.shutdown().await {
@@ -840,6 +943,67 @@ impl TableHandler {
             table_handler_state.initial_copy_buffered_events.push(event);
             return;
         }
+        // Tables requiring resync don't apply row events, but transactions are still committed so replication keeps making progress.
+        if table_handler_state.requires_resync
+            && matches!(
+                event,
+                TableEvent::Append { .. }
+                    | TableEvent::Delete { .. }
+                    | TableEvent::Update { .. }
+                    | TableEvent::Truncate { .. }
+            )
+        {
+            return;
+        }
+        // Update events are applied as delete and append, after unchanged columns are filled from the current row.
+        if let TableEvent::Update {
+            old_row,
+            new_row,
+            unchanged_columns,
+            lsn,
+            xact_id,
+            is_recovery,
+        } = event
+        {
+            let new_row = match table
+                .fill_unchanged_columns(&old_row, new_row, &unchanged_columns, xact_id)
+                .await
+            {
+                Ok(new_row) => new_row,
+                Err(e) => {
+                    error!(error = %e, "failed to apply update, table stops applying row events and needs resync from source");
+                    table_handler_state.requires_resync = true;
+                    return;
+                }
+            };
+            let delete_event = TableEvent::Delete {
+                row: old_row,
+                lsn,
+                xact_id,
+                delete_if_exists: false,
+                is_recovery,
+            };
+            let append_event = TableEvent::Append {
+                row: new_row.clone(),
+                xact_id,
+                lsn,
+                is_recovery,
+            };
+            Box::pin(Self::process_cdc_table_event(
+                delete_event,
+                table,
+                table_handler_state,
+            ))
+            .await;
+            Box::pin(Self::process_cdc_table_event(
+                append_event,
+                table,
+                table_handler_state,
+            ))
+            .await;
+            table.cache_updated_row(new_row);
+            return;
+        }
         // Don't update the lsn if the event is not processed yet.
         table_handler_state.update_table_lsns(&event);
 
@@ -895,6 +1059,10 @@ impl TableHandler {
                     }
                 };
             }
+            TableEvent::Truncate { lsn, xact_id, .. } => match xact_id {
+                Some(xact_id) => table.truncate_in_stream_batch(xact_id),
+                None => table.truncate(lsn),
+            },
             TableEvent::Commit { lsn, xact_id, .. } => {
                 Self::commit_and_attempt_flush(
                     lsn,
@@ -937,6 +1105,41 @@ impl TableHandler {
         }
     }
 
+    /// Start iceberg persistence for the given payload, and complete ongoing alter table if applicable.
+    async fn start_persistence_snapshot(
+        table: &mut MooncakeTable,
+        table_handler_state: &mut TableHandlerState,
+        mut persistence_snapshot_payload: PersistenceSnapshotPayload,
+    ) {
+        // Update table maintenance status.
+        if persistence_snapshot_payload.contains_table_maintenance_payload()
+            && table_handler_state.table_maintenance_process_status
+                == MaintenanceProcessStatus::ReadyToPersist
+        {
+            table_handler_state.table_maintenance_process_status =
+                MaintenanceProcessStatus::InPersist;
+        }
+        table_handler_state.persistence_snapshot_ongoing = true;
+        if table_handler_state.should_complete_alter_table(persistence_snapshot_payload.flush_lsn) {
+            if let SpecialTableState::AlterTable {
+                ref mut alter_table_request,
+                ..
+            } = table_handler_state.special_table_state
+            {
+                // Alter table request has been validated before blocking the table.
+                let new_table_metadata = table
+                    .alter_table(alter_table_request.take().unwrap())
+                    .expect("alter table request should be validated");
+                persistence_snapshot_payload.new_table_schema = Some(new_table_metadata);
+            } else {
+                unreachable!("alter table request is not set");
+            }
+            table_handler_state.finish_alter_table();
+            Self::process_blocked_events(table, table_handler_state).await;
+        }
+        table.persist_iceberg_snapshot(persistence_snapshot_payload);
+    }
+
     async fn process_blocked_events(
         table: &mut MooncakeTable,
         table_handler_state: &mut TableHandlerState,
@@ -946,10 +1149,98 @@ impl TableHandler {
             .drain(..)
             .collect::<Vec<_>>();
         for event in buffered_events {
+            if let TableEvent::AlterTable { .. } = event {
+                Self::process_alter_table_event(event, table, table_handler_state);
+                continue;
+            }
             Self::process_cdc_table_event(event, table, table_handler_state).await;
         }
     }
 
+    /// Start an alter table operation, or buffer it if the table is blocked (i.e., a previous alter table is ongoing), so alter table requests are applied in order.
+    /// Invalid requests (i.e., dropping a partition source column) are rejected without blocking the table.
+    fn process_alter_table_event(
+        event: TableEvent,
+        table: &MooncakeTable,
+        table_handler_state: &mut TableHandlerState,
+    ) {
+        if table_handler_state.is_in_blocking_state() {
+            table_handler_state.initial_copy_buffered_events.push(event);
+            return;
+        }
+        let TableEvent::AlterTable {
+            columns_to_drop,
+            columns_to_add,
+            columns_to_rename,
+            columns_to_promote,
+            columns_to_relax_nullability,
+            validation_result_tx,
+        } = event
+        else {
+            unreachable!("unexpected event: {:?}", event);
+        };
+        debug!(
+            "altering table, dropping columns: {:?}, adding columns: {:?}, renaming columns: {:?}, promoting columns: {:?}, relaxing nullability: {:?}",
+            columns_to_drop,
+            columns_to_add,
+            columns_to_rename,
+            columns_to_promote,
+            columns_to_relax_nullability
+        );
+        let alter_table_request = AlterTableRequest {
+            new_columns: columns_to_add,
+            dropped_columns: columns_to_drop,
+            renamed_columns: columns_to_rename,
+            promoted_columns: columns_to_promote,
+            nullable_columns: columns_to_relax_nullability,
+        };
+        let validation_result = table.validate_alter_table(&alter_table_request);
+        if let Some(validation_result_tx) = validation_result_tx {
+            validation_result_tx.send(validation_result.clone());
+        }
+        if let Err(e) = validation_result {
+            error!(error = %e, "rejected invalid alter table request");
+            return;
+        }
+        table_handler_state.start_alter_table(alter_table_request);
+    }
+
+    /// Flush in-memory writes on memory pressure, including committed non-streaming writes and all ongoing streaming transactions.
+    fn flush_on_memory_pressure(
+        table: &mut MooncakeTable,
+        table_handler_state: &mut TableHandlerState,
+    ) {
+        // Writes are buffered elsewhere under blocking state, which is resolved soon; flush at next commit instead.
+        if table_handler_state.is_in_blocking_state() {
+            table_handler_state.memory_pressure_flush_requested = true;
+            return;
+        }
+
+        for xact_id in table.get_transaction_streams_to_flush() {
+            let event_id = uuid::Uuid::new_v4();
+            if let Err(e) = table.flush_stream(xact_id, /*lsn=*/ None, event_id) {
+                error!(error = %e, "failed to flush stream on memory pressure");
+            }
+        }
+
+        // Flush drains the whole mem slice, so it's only safe when there're no uncommitted non-streaming writes; otherwise flush at next commit.
+        match (
+            table_handler_state.last_unflushed_commit_lsn,
+            table_handler_state.table_consistent_view_lsn,
+        ) {
+            (Some(last_unflushed_commit_lsn), Some(_)) => {
+                table_handler_state.last_unflushed_commit_lsn = None;
+                let event_id = uuid::Uuid::new_v4();
+                if let Err(e) = table.flush(last_unflushed_commit_lsn, event_id) {
+                    error!(error = %e, "flush failed on memory pressure");
+                }
+            }
+            _ => {
+                table_handler_state.memory_pressure_flush_requested = true;
+            }
+        }
+    }
+
     async fn commit_and_attempt_flush(
         lsn: u64,
         xact_id: Option<u32>,
@@ -976,6 +1267,7 @@ impl TableHandler {
                         error!(error = %e, "flush non-streaming writes failed in LSN {lsn}");
                     }
                     table_handler_state.last_unflushed_commit_lsn = None;
+                    table_handler_state.memory_pressure_flush_requested = false;
                 }
 
                 // For streaming writers, whose commit LSN is only finalized at commit phase, delay decision whether to discard now.
@@ -993,8 +1285,13 @@ impl TableHandler {
             }
             None => {
                 table.commit(lsn);
-                if table.should_flush() || should_force_flush || force_flush_requested {
+                if table.should_flush()
+                    || should_force_flush
+                    || force_flush_requested
+                    || table_handler_state.memory_pressure_flush_requested
+                {
                     table_handler_state.last_unflushed_commit_lsn = None;
+                    table_handler_state.memory_pressure_flush_requested = false;
                     let event_id = uuid::Uuid::new_v4();
                     if let Err(e) = table.flush(lsn, event_id) {
                         error!(error = %e, "flush failed in commit");
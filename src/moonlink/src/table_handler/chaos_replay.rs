@@ -417,6 +417,8 @@ pub(crate) async fn replay(replay_filepath: &str) {
     let mut uncommitted_appended_ids = HashSet::new();
     // Ids for the current ongoing transaction to delete, which could be aborted.
     let mut uncommitted_deleted_ids = HashSet::new();
+    // Whether the current ongoing transaction truncates the table, which could be aborted.
+    let mut uncommitted_truncation = false;
 
     while let Some(serialized_event) = lines.next_line().await.unwrap() {
         let replay_table_event: MooncakeTableEvent =
@@ -467,6 +469,9 @@ pub(crate) async fn replay(replay_filepath: &str) {
                 // Update in-memory state.
                 let appended = std::mem::take(&mut uncommitted_appended_ids);
                 let deleted = std::mem::take(&mut uncommitted_deleted_ids);
+                if std::mem::take(&mut uncommitted_truncation) {
+                    committed_ids.clear();
+                }
                 {
                     // Consider update case,
                     // - It's possible to add an existing id.
@@ -499,10 +504,24 @@ pub(crate) async fn replay(replay_filepath: &str) {
                 // Update in-memory state.
                 uncommitted_appended_ids.clear();
                 uncommitted_deleted_ids.clear();
+                uncommitted_truncation = false;
 
                 // Apply update to mooncake table.
                 table.abort_in_stream_batch(abort_event.xact_id);
             }
+            MooncakeTableEvent::Truncate(truncate_event) => {
+                // Update in-memory state, rows appended earlier in the transaction are truncated as well.
+                uncommitted_appended_ids.clear();
+                uncommitted_deleted_ids.clear();
+                uncommitted_truncation = true;
+
+                // Apply update to mooncake table.
+                if let Some(xact_id) = truncate_event.xact_id {
+                    table.truncate_in_stream_batch(xact_id);
+                } else {
+                    table.truncate(truncate_event.lsn.unwrap());
+                }
+            }
             // =====================
             // Flush operation
             // =====================
@@ -6,6 +6,7 @@
 /// - Rows to delete comes from committed appended ones
 /// - LSN always increases
 use crate::event_sync::create_table_event_syncer;
+use crate::memory_accountant::MemoryAccountant;
 use crate::row::{MoonlinkRow, RowValue};
 #[cfg(feature = "storage-gcs")]
 use crate::storage::filesystem::gcs::gcs_test_utils::*;
@@ -876,6 +877,7 @@ impl TestEnvironment {
             replication_lsn_rx.clone(),
             Some(handler_event_replay_tx),
             Some(table_event_replay_tx),
+            MemoryAccountant::default(),
         )
         .await;
         let wal_flush_lsn_rx = table_event_sync_receiver.wal_flush_lsn_rx.clone();
@@ -1,4 +1,5 @@
 use crate::event_sync::create_table_event_syncer;
+use crate::memory_accountant::MemoryAccountant;
 use crate::row::{IdentityProp, MoonlinkRow, RowValue};
 use crate::storage::mooncake_table::table_event_manager::TableEventManager;
 use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
@@ -371,6 +372,7 @@ impl TestEnvironment {
             replication_lsn_rx.clone(),
             /*handler_event_replay_tx=*/ None,
             /*table_event_replay_tx=*/ None,
+            MemoryAccountant::default(),
         )
         .await;
         let wal_flush_lsn_rx = table_event_sync_receiver.wal_flush_lsn_rx.clone();
@@ -494,6 +496,7 @@ fn create_test_table_metadata_for_profile(
         max_data_file_to_compact: 32,
         data_file_final_size: 1 << 29, // 512MiB
         data_file_deletion_percentage: 50,
+        recluster_overlap_threshold: 0,
     };
     config.file_index_config = FileIndexMergeConfig {
         min_file_indices_to_merge: 16,
@@ -1,6 +1,7 @@
 /// Table handler state manages table event process states.
 use crate::storage::mooncake_table::AlterTableRequest;
 use crate::storage::mooncake_table::DataCompactionResult;
+use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::snapshot_options::IcebergSnapshotOption;
 use crate::storage::snapshot_options::MaintenanceOption;
 use crate::storage::snapshot_options::SnapshotOption;
@@ -84,6 +85,8 @@ pub(crate) struct TableHandlerState {
     // Last unflushed commit LSN for non-streaming transaction.
     // Used to flush non-streaming writes at streaming transaction commit.
     pub(crate) last_unflushed_commit_lsn: Option<u64>,
+    // Whether to flush at next non-streaming commit, because memory pressure flush request comes with uncommitted writes.
+    pub(crate) memory_pressure_flush_requested: bool,
 
     // ================================================
     // Table management and event handling states
@@ -108,6 +111,8 @@ pub(crate) struct TableHandlerState {
     pub(crate) special_table_state: SpecialTableState,
     // Buffered events during blocking operations: initial copy, alter table, drop table, etc.
     pub(crate) initial_copy_buffered_events: Vec<TableEvent>,
+    // Whether the table stops applying row events and needs resync from source, because an update cannot be applied without losing data.
+    pub(crate) requires_resync: bool,
 
     // ================================================
     // Table maintenance status
@@ -124,6 +129,19 @@ pub(crate) struct TableHandlerState {
     /// Notify when data compaction completes.
     pub(crate) table_maintenance_completion_tx: broadcast::Sender<Result<()>>,
 
+    // ================================================
+    // Snapshot expiration status
+    // ================================================
+    //
+    // Snapshot expiration takes table manager, so it never runs concurrently with iceberg persistence.
+    //
+    // Pending snapshot expiration request, which records whether it's a dry run.
+    pub(crate) pending_snapshot_expiration: Option<bool>,
+    // Whether there's an ongoing snapshot expiration operation.
+    pub(crate) snapshot_expiration_ongoing: bool,
+    // Persistence payload received during ongoing snapshot expiration, which is persisted after expiration completes.
+    pub(crate) buffered_persistence_snapshot_payload: Option<PersistenceSnapshotPayload>,
+
     // ================================================
     // Write-ahead log (WAL)
     // ================================================
@@ -144,6 +162,7 @@ impl TableHandlerState {
             mooncake_snapshot_ongoing: false,
             initial_persistence_lsn,
             last_unflushed_commit_lsn: None,
+            memory_pressure_flush_requested: false,
             latest_commit_lsn: None,
             special_table_state: SpecialTableState::Normal,
             // Force snapshot fields.
@@ -157,6 +176,11 @@ impl TableHandlerState {
             table_maintenance_completion_tx,
             // Initial copy fields.
             initial_copy_buffered_events: Vec::new(),
+            requires_resync: false,
+            // Snapshot expiration fields.
+            pending_snapshot_expiration: None,
+            snapshot_expiration_ongoing: false,
+            buffered_persistence_snapshot_payload: None,
             wal_persist_ongoing: false,
         }
     }
@@ -180,6 +204,8 @@ impl TableHandlerState {
                 // Unset for table write operations.
                 TableEvent::Append { .. }
                 | TableEvent::Delete { .. }
+                | TableEvent::Update { .. }
+                | TableEvent::Truncate { .. }
                 | TableEvent::StreamAbort { .. } => {
                     self.table_consistent_view_lsn = None;
                 }
@@ -324,6 +350,9 @@ impl TableHandlerState {
         if self.persistence_snapshot_ongoing {
             return false;
         }
+        if self.snapshot_expiration_ongoing {
+            return false;
+        }
         if self.wal_persist_ongoing {
             return false;
         }
@@ -431,6 +460,11 @@ impl TableHandlerState {
             && flush_lsn < min_ongoing_flush_lsn
     }
 
+    /// Return whether table manager is taken by an ongoing iceberg persistence or snapshot expiration.
+    pub(crate) fn is_table_manager_in_use(&self) -> bool {
+        self.persistence_snapshot_ongoing || self.snapshot_expiration_ongoing
+    }
+
     pub(crate) fn reset_iceberg_state_at_mooncake_snapshot(&mut self) {
         // Validate iceberg snapshot state before mooncake snapshot creation.
         //
@@ -469,7 +503,7 @@ impl TableHandlerState {
         }
     }
     fn get_iceberg_snapshot_option(&self) -> IcebergSnapshotOption {
-        if self.persistence_snapshot_ongoing {
+        if self.is_table_manager_in_use() {
             IcebergSnapshotOption::Skip
         } else {
             IcebergSnapshotOption::BestEffort(uuid::Uuid::new_v4())
@@ -527,4 +561,30 @@ impl TableHandlerState {
         }
         true
     }
+
+    /// ============================
+    /// Snapshot expiration
+    /// ============================
+    ///
+    /// Record a snapshot expiration request, which will be started when table manager is available.
+    /// Pending requests are merged into one, which is a dry run only if all requests are.
+    pub(crate) fn request_snapshot_expiration(&mut self, dry_run: bool) {
+        self.pending_snapshot_expiration = Some(match self.pending_snapshot_expiration {
+            None => dry_run,
+            Some(pending_dry_run) => pending_dry_run && dry_run,
+        });
+    }
+
+    /// Take the pending snapshot expiration request if it could be started now, and return whether it's a dry run.
+    pub(crate) fn take_snapshot_expiration_request(&mut self) -> Option<bool> {
+        if self.is_table_manager_in_use() {
+            return None;
+        }
+        if self.special_table_state != SpecialTableState::Normal {
+            return None;
+        }
+        let dry_run = self.pending_snapshot_expiration.take()?;
+        self.snapshot_expiration_ongoing = true;
+        Some(dry_run)
+    }
 }
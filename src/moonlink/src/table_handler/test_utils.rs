@@ -1,4 +1,5 @@
 use crate::event_sync::create_table_event_syncer;
+use crate::memory_accountant::MemoryAccountant;
 use crate::row::IdentityProp;
 use crate::row::{MoonlinkRow, RowValue};
 use crate::storage::filesystem::accessor::base_filesystem_accessor::BaseFileSystemAccess;
@@ -98,6 +99,20 @@ impl TestEnvironment {
 
     /// Create a new test environment with the given mooncake table.
     pub(crate) async fn new_with_mooncake_table(temp_dir: TempDir, table: MooncakeTable) -> Self {
+        Self::new_with_mooncake_table_and_memory_accountant(
+            temp_dir,
+            table,
+            MemoryAccountant::default(),
+        )
+        .await
+    }
+
+    /// Create a new test environment with the given mooncake table, which reports memory usage to the given accountant.
+    async fn new_with_mooncake_table_and_memory_accountant(
+        temp_dir: TempDir,
+        table: MooncakeTable,
+        memory_accountant: MemoryAccountant,
+    ) -> Self {
         let (replication_tx, replication_rx) = watch::channel(0u64);
         let (last_commit_tx, last_commit_rx) = watch::channel(0u64);
         let snapshot_lsn_tx = table.get_snapshot_watch_sender().clone();
@@ -127,6 +142,7 @@ impl TestEnvironment {
             replication_rx.clone(),
             /*event_replay_tx=*/ None,
             /*table_event_replay_tx=*/ None,
+            memory_accountant,
         )
         .await;
         let table_event_manager =
@@ -151,6 +167,20 @@ impl TestEnvironment {
 
     /// Creates a new test environment with default settings.
     pub async fn new(temp_dir: TempDir, mooncake_table_config: MooncakeTableConfig) -> Self {
+        Self::new_with_memory_accountant(
+            temp_dir,
+            mooncake_table_config,
+            MemoryAccountant::default(),
+        )
+        .await
+    }
+
+    /// Creates a new test environment, whose table reports memory usage to the given accountant.
+    pub async fn new_with_memory_accountant(
+        temp_dir: TempDir,
+        mooncake_table_config: MooncakeTableConfig,
+        memory_accountant: MemoryAccountant,
+    ) -> Self {
         let path = temp_dir.path().to_path_buf();
         let table_name = "table_name";
         let iceberg_table_config =
@@ -172,7 +202,12 @@ impl TestEnvironment {
         .await
         .unwrap();
 
-        Self::new_with_mooncake_table(temp_dir, mooncake_table).await
+        Self::new_with_mooncake_table_and_memory_accountant(
+            temp_dir,
+            mooncake_table,
+            memory_accountant,
+        )
+        .await
     }
 
     /// Create iceberg table manager.
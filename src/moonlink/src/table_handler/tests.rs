@@ -1,4 +1,5 @@
 use arrow_array::{Int32Array, RecordBatch, StringArray};
+use arrow_schema::{DataType, Field};
 use more_asserts as ma;
 use tempfile::tempdir;
 use tokio::sync::broadcast;
@@ -6,6 +7,7 @@ use tokio::sync::watch;
 
 use super::test_utils::*;
 use super::TableEvent;
+use crate::memory_accountant::{MemoryAccountant, MemoryBudgetConfig};
 use crate::row::IdentityProp;
 use crate::row::{MoonlinkRow, RowValue};
 use crate::storage::compaction::compaction_config::DataCompactionConfig;
@@ -18,6 +20,9 @@ use crate::storage::mooncake_table::TableMetadata as MooncakeTableMetadata;
 use crate::storage::mooncake_table_config::DiskSliceWriterConfig;
 use crate::storage::mooncake_table_config::IcebergPersistenceConfig;
 use crate::storage::mooncake_table_config::MooncakeTableConfig;
+use crate::storage::mooncake_table_config::ParquetWriterConfig;
+use crate::storage::mooncake_table_config::SnapshotRetentionConfig;
+use crate::storage::mooncake_table_config::SourceFilterConfig;
 use crate::storage::wal::test_utils::WAL_TEST_TABLE_ID;
 use crate::storage::wal::WalManager;
 use crate::storage::MockTableManager;
@@ -89,6 +94,14 @@ async fn test_append_with_small_disk_slice() {
             old_compacted_data_file_count: 1,
             old_merged_file_indices_count: 1,
         },
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let env = TestEnvironment::new(temp_dir, mooncake_table_config.clone()).await;
 
@@ -647,6 +660,14 @@ async fn test_iceberg_snapshot_creation_for_batch_write() {
             old_compacted_data_file_count: 1,
             old_merged_file_indices_count: 1,
         },
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let mut env = TestEnvironment::new(temp_dir, mooncake_table_config.clone()).await;
 
@@ -857,6 +878,14 @@ async fn test_iceberg_snapshot_creation_for_streaming_write() {
             old_compacted_data_file_count: 1,
             old_merged_file_indices_count: 1,
         },
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let mut env = TestEnvironment::new(temp_dir, mooncake_table_config.clone()).await;
 
@@ -1098,6 +1127,14 @@ async fn test_multiple_snapshot_requests() {
             old_compacted_data_file_count: 1,
             old_merged_file_indices_count: 1,
         },
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let mut env = TestEnvironment::new(temp_dir, mooncake_table_config.clone()).await;
 
@@ -1283,6 +1320,64 @@ async fn test_flush_lsn_consistency_across_snapshots() {
     env.shutdown().await;
 }
 
+/// Test iceberg persistence interleaved with snapshot expiration, persistence payloads received during expiration shouldn't be lost.
+#[tokio::test]
+async fn test_persistence_with_concurrent_snapshot_expiration() {
+    let temp_dir = tempdir().unwrap();
+    let mut mooncake_table_config = MooncakeTableConfig::default();
+    mooncake_table_config.snapshot_retention_config = SnapshotRetentionConfig {
+        max_snapshots_to_keep: Some(1),
+        ..Default::default()
+    };
+    let mut env = TestEnvironment::new(temp_dir, mooncake_table_config.clone()).await;
+
+    let test_lsns = vec![10, 20, 30, 40, 50];
+    let mut expiration_rxs = vec![];
+    for lsn in test_lsns.iter().copied() {
+        env.append_row(
+            lsn as i32,
+            &format!("User{lsn}"),
+            25,
+            /*lsn=*/ lsn - 5,
+            None,
+        )
+        .await;
+        env.commit(lsn).await;
+
+        // Request snapshot expiration without waiting, so it runs concurrently with the following persistence.
+        expiration_rxs.push(
+            env.table_event_manager
+                .initiate_snapshot_expiration(/*dry_run=*/ false)
+                .await,
+        );
+        let rx = env.table_event_manager.initiate_snapshot(lsn).await;
+        TableEventManager::synchronize_force_snapshot_request(rx, /*requested_lsn=*/ lsn)
+            .await
+            .unwrap();
+    }
+    for mut cur_rx in expiration_rxs.into_iter() {
+        cur_rx.recv().await.unwrap().unwrap();
+    }
+
+    // All committed rows are persisted.
+    let mut iceberg_table_manager = env
+        .create_iceberg_table_manager(mooncake_table_config)
+        .await;
+    let (_, snapshot) = iceberg_table_manager
+        .load_snapshot_from_table()
+        .await
+        .unwrap();
+    assert_eq!(snapshot.flush_lsn, Some(50));
+    let num_rows = snapshot
+        .disk_files
+        .values()
+        .map(|entry| entry.num_rows)
+        .sum::<usize>();
+    assert_eq!(num_rows, test_lsns.len());
+
+    env.shutdown().await;
+}
+
 #[tokio::test]
 async fn test_initial_copy_basic() {
     let mut env = TestEnvironment::default().await;
@@ -1561,6 +1656,7 @@ async fn test_full_maintenance_with_sufficient_data_files() {
             max_data_file_to_compact: u32::MAX,
             data_file_final_size: u64::MAX,
             data_file_deletion_percentage: 0,
+            recluster_overlap_threshold: 0,
         },
         file_index_config: FileIndexMergeConfig {
             min_file_indices_to_merge: u32::MAX,
@@ -2172,6 +2268,14 @@ async fn test_append_only_table_full_pipeline() {
             old_compacted_data_file_count: 1,
             old_merged_file_indices_count: 1,
         },
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
 
     let mut env = TestEnvironment::new(temp_dir, mooncake_table_config.clone()).await;
@@ -2278,6 +2382,14 @@ async fn test_append_only_table_high_volume() {
         data_compaction_config: DataCompactionConfig::default(),
         file_index_config: FileIndexMergeConfig::default(),
         persistence_config: IcebergPersistenceConfig::default(),
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
 
     let mut env = TestEnvironment::new(temp_dir, mooncake_table_config).await;
@@ -2332,6 +2444,14 @@ async fn test_append_only_table_basic() {
         data_compaction_config: DataCompactionConfig::default(),
         file_index_config: FileIndexMergeConfig::default(),
         persistence_config: IcebergPersistenceConfig::default(),
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
 
     let mut env = TestEnvironment::new(temp_dir, mooncake_table_config).await;
@@ -2395,6 +2515,14 @@ async fn test_batch_ingestion() {
         data_compaction_config: DataCompactionConfig::default(),
         file_index_config: FileIndexMergeConfig::default(),
         persistence_config: IcebergPersistenceConfig::default(),
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let env = TestEnvironment::new(temp_dir, mooncake_table_config).await;
 
@@ -2441,6 +2569,14 @@ async fn test_batch_ingestion_with_gcs() {
         data_compaction_config: DataCompactionConfig::default(),
         file_index_config: FileIndexMergeConfig::default(),
         persistence_config: IcebergPersistenceConfig::default(),
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let env = TestEnvironment::new(temp_dir, mooncake_table_config).await;
 
@@ -2478,6 +2614,14 @@ async fn test_batch_ingestion_with_s3() {
         data_compaction_config: DataCompactionConfig::default(),
         file_index_config: FileIndexMergeConfig::default(),
         persistence_config: IcebergPersistenceConfig::default(),
+        partition_spec: vec![],
+        snapshot_retention_config: SnapshotRetentionConfig::default(),
+        parquet_writer_config: ParquetWriterConfig::default(),
+        encryption_config: None,
+        table_encryption: None,
+        replica_identity_full: false,
+        source_filter_config: SourceFilterConfig::default(),
+        sort_order: vec![],
     };
     let env = TestEnvironment::new(temp_dir, mooncake_table_config).await;
 
@@ -2500,6 +2644,11 @@ async fn test_alter_table() {
 
     env.send_event(TableEvent::AlterTable {
         columns_to_drop: vec!["name".to_string()],
+        columns_to_add: vec![],
+        columns_to_rename: vec![],
+        columns_to_promote: vec![],
+        columns_to_relax_nullability: vec![],
+        validation_result_tx: None,
     })
     .await;
 
@@ -2518,6 +2667,141 @@ async fn test_alter_table() {
     env.shutdown().await;
 }
 
+#[tokio::test]
+async fn test_alter_table_rename_and_promote_columns() {
+    let mut env = TestEnvironment::default().await;
+
+    env.append_row(1, "Alice", 25, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    env.commit(1).await;
+
+    env.send_event(TableEvent::AlterTable {
+        columns_to_drop: vec![],
+        columns_to_add: vec![],
+        columns_to_rename: vec![("name".to_string(), "full_name".to_string())],
+        columns_to_promote: vec![("age".to_string(), DataType::Int64)],
+        columns_to_relax_nullability: vec!["age".to_string()],
+        validation_result_tx: None,
+    })
+    .await;
+
+    env.send_event(TableEvent::Append {
+        row: MoonlinkRow::new(vec![
+            RowValue::Int32(2),
+            RowValue::ByteArray("Bob".as_bytes().to_vec()),
+            RowValue::Int64(30),
+        ]),
+        lsn: 1,
+        xact_id: None,
+        is_recovery: false,
+    })
+    .await;
+    env.commit(2).await;
+
+    env.set_readable_lsn(2);
+    env.verify_snapshot(2, &[1, 2]).await;
+
+    env.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_alter_table_validation_result() {
+    let mut env = TestEnvironment::default().await;
+
+    env.append_row(1, "Alice", 25, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    env.commit(1).await;
+
+    // Invalid request is rejected without altering the table.
+    let (validation_result_tx, validation_result_rx) = crate::AlterTableValidationSender::new();
+    env.send_event(TableEvent::AlterTable {
+        columns_to_drop: vec![],
+        columns_to_add: vec![],
+        columns_to_rename: vec![],
+        columns_to_promote: vec![("name".to_string(), DataType::Int64)],
+        columns_to_relax_nullability: vec![],
+        validation_result_tx: Some(validation_result_tx),
+    })
+    .await;
+    assert!(validation_result_rx.await.unwrap().is_err());
+
+    // Valid request is notified before the table gets altered.
+    let (validation_result_tx, validation_result_rx) = crate::AlterTableValidationSender::new();
+    env.send_event(TableEvent::AlterTable {
+        columns_to_drop: vec!["name".to_string()],
+        columns_to_add: vec![],
+        columns_to_rename: vec![],
+        columns_to_promote: vec![],
+        columns_to_relax_nullability: vec![],
+        validation_result_tx: Some(validation_result_tx),
+    })
+    .await;
+    validation_result_rx.await.unwrap().unwrap();
+
+    env.send_event(TableEvent::Append {
+        row: MoonlinkRow::new(vec![RowValue::Int32(2), RowValue::Int32(30)]),
+        lsn: 1,
+        xact_id: None,
+        is_recovery: false,
+    })
+    .await;
+    env.commit(2).await;
+
+    env.set_readable_lsn(2);
+    env.verify_snapshot(2, &[1, 2]).await;
+
+    env.shutdown().await;
+}
+
+#[tokio::test]
+async fn test_consecutive_alter_tables() {
+    let mut env = TestEnvironment::default().await;
+
+    env.append_row(1, "Alice", 25, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    env.commit(1).await;
+
+    // The second alter table arrives before the first one completes, which gets buffered and applied in order.
+    env.send_event(TableEvent::AlterTable {
+        columns_to_drop: vec![],
+        columns_to_add: vec![Arc::new(Field::new("score", DataType::Int32, true))],
+        columns_to_rename: vec![],
+        columns_to_promote: vec![],
+        columns_to_relax_nullability: vec![],
+        validation_result_tx: None,
+    })
+    .await;
+    env.commit(2).await;
+    env.send_event(TableEvent::AlterTable {
+        columns_to_drop: vec![],
+        columns_to_add: vec![],
+        columns_to_rename: vec![],
+        columns_to_promote: vec![("score".to_string(), DataType::Int64)],
+        columns_to_relax_nullability: vec![],
+        validation_result_tx: None,
+    })
+    .await;
+
+    env.send_event(TableEvent::Append {
+        row: MoonlinkRow::new(vec![
+            RowValue::Int32(2),
+            RowValue::ByteArray("Bob".as_bytes().to_vec()),
+            RowValue::Int32(30),
+            RowValue::Int64(100),
+        ]),
+        lsn: 2,
+        xact_id: None,
+        is_recovery: false,
+    })
+    .await;
+    env.commit(3).await;
+
+    env.set_readable_lsn(3);
+    env.verify_snapshot(3, &[1, 2]).await;
+
+    env.shutdown().await;
+}
+
 #[tokio::test]
 async fn test_initial_copy_iceberg_ack_without_wal() {
     let mut env = TestEnvironment::default().await;
@@ -2777,3 +3061,98 @@ async fn test_initial_copy_with_buffered_cdc_no_dup_no_drop() {
 
     env.shutdown().await;
 }
+
+/// Test util function to block wait until memory usage reported to accountant satisfies the given condition.
+async fn wait_for_memory_size(
+    memory_accountant: &MemoryAccountant,
+    condition: impl Fn(u64) -> bool,
+) {
+    while !condition(memory_accountant.get_total_memory_size()) {
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+}
+
+/// Testing scenario: table is flushed when total memory usage exceeds soft limit, even if mem slice is not full.
+#[tokio::test]
+async fn test_flush_on_memory_pressure() {
+    let temp_dir = tempdir().unwrap();
+    let mut mooncake_table_config =
+        MooncakeTableConfig::new(temp_dir.path().to_str().unwrap().to_string());
+    mooncake_table_config.row_identity = IdentityProp::Keys(vec![0]);
+    let memory_accountant = MemoryAccountant::new(MemoryBudgetConfig {
+        soft_limit_bytes: 1,
+        hard_limit_bytes: u64::MAX,
+    });
+    let mut env = TestEnvironment::new_with_memory_accountant(
+        temp_dir,
+        mooncake_table_config,
+        memory_accountant.clone(),
+    )
+    .await;
+
+    // Uncommitted writes stay in memory.
+    env.append_row(1, "Alice", 25, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    env.append_row(2, "Bob", 30, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    wait_for_memory_size(&memory_accountant, |memory_size| memory_size > 0).await;
+    assert!(
+        memory_accountant
+            .get_table_memory_size("table_name")
+            .unwrap()
+            > 0
+    );
+
+    // Committed writes get flushed.
+    env.commit(1).await;
+    wait_for_memory_size(&memory_accountant, |memory_size| memory_size == 0).await;
+
+    env.set_readable_lsn(1);
+    env.verify_snapshot(1, &[1, 2]).await;
+
+    env.shutdown().await;
+}
+
+/// Testing scenario: memory usage exceeds hard limit in the middle of a non-streaming transaction, whose writes are
+/// flushed at commit and bring memory usage back under hard limit.
+#[tokio::test]
+async fn test_hard_limit_exceeded_mid_transaction() {
+    let temp_dir = tempdir().unwrap();
+    let mut mooncake_table_config =
+        MooncakeTableConfig::new(temp_dir.path().to_str().unwrap().to_string());
+    mooncake_table_config.row_identity = IdentityProp::Keys(vec![0]);
+    let memory_accountant = MemoryAccountant::new(MemoryBudgetConfig {
+        soft_limit_bytes: 1,
+        hard_limit_bytes: 1,
+    });
+    let mut env = TestEnvironment::new_with_memory_accountant(
+        temp_dir,
+        mooncake_table_config,
+        memory_accountant.clone(),
+    )
+    .await;
+
+    // Flush requested on memory pressure cannot drain uncommitted writes.
+    env.append_row(1, "Alice", 25, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    wait_for_memory_size(&memory_accountant, |memory_size| memory_size > 1).await;
+    assert!(memory_accountant.is_over_hard_limit());
+    env.append_row(2, "Bob", 30, /*lsn=*/ 0, /*xact_id=*/ None)
+        .await;
+    tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    assert!(memory_accountant.is_over_hard_limit());
+
+    // Commit of the open transaction flushes the pending memory pressure request.
+    env.commit(1).await;
+    tokio::time::timeout(
+        std::time::Duration::from_secs(30),
+        memory_accountant.wait_for_under_hard_limit(),
+    )
+    .await
+    .unwrap();
+
+    env.set_readable_lsn(1);
+    env.verify_snapshot(1, &[1, 2]).await;
+
+    env.shutdown().await;
+}
@@ -11,6 +11,8 @@ pub struct TableHandlerTimer {
     pub force_snapshot_timer: Box<dyn Ticker>,
     /// Timer for periodical WAL operations.
     pub wal_snapshot_timer: Box<dyn Ticker>,
+    /// Timer for periodical snapshot expiration.
+    pub snapshot_expiration_timer: Box<dyn Ticker>,
 }
 
 /// Util function to create table handler timers, with default config.
@@ -19,5 +21,6 @@ pub fn create_table_handler_timers() -> TableHandlerTimer {
         mooncake_snapshot_timer: Box::new(TokioTicker::new(Duration::from_millis(500))),
         force_snapshot_timer: Box::new(TokioTicker::new(Duration::from_secs(300))),
         wal_snapshot_timer: Box::new(TokioTicker::new(Duration::from_millis(500))),
+        snapshot_expiration_timer: Box::new(TokioTicker::new(Duration::from_secs(600))),
     }
 }
@@ -7,10 +7,14 @@ use crate::storage::mooncake_table::FileIndiceMergePayload;
 use crate::storage::mooncake_table::FileIndiceMergeResult;
 use crate::storage::mooncake_table::PersistenceSnapshotPayload;
 use crate::storage::mooncake_table::PersistenceSnapshotResult;
+use crate::storage::mooncake_table::SnapshotExpirationResult;
 use crate::storage::wal::WalPersistenceUpdateResult;
 use crate::Result;
 use crate::StorageConfig;
 
+use std::sync::{Arc, Mutex};
+use tokio::sync::oneshot;
+
 /// Table maintenance status.
 #[derive(Clone, Debug)]
 pub enum TableMaintenanceStatus<T> {
@@ -60,6 +64,32 @@ impl std::fmt::Debug for EvictedFiles {
     }
 }
 
+/// Sender to notify validation result of an alter table request, which is notified at most once.
+/// Wrapped in a mutex, so table events are still cloneable.
+#[derive(Clone, Debug)]
+pub struct AlterTableValidationSender {
+    tx: Arc<Mutex<Option<oneshot::Sender<Result<()>>>>>,
+}
+
+impl AlterTableValidationSender {
+    pub fn new() -> (Self, oneshot::Receiver<Result<()>>) {
+        let (tx, rx) = oneshot::channel();
+        (
+            Self {
+                tx: Arc::new(Mutex::new(Some(tx))),
+            },
+            rx,
+        )
+    }
+
+    /// Notify validation result, which is a no-op if already notified or the receiver is gone.
+    pub(crate) fn send(&self, result: Result<()>) {
+        if let Some(tx) = self.tx.lock().unwrap().take() {
+            let _ = tx.send(result);
+        }
+    }
+}
+
 /// Completion notifications for mooncake table, including snapshot creation and compaction, etc.
 ///
 /// TODO(hjiang): Revisit whether we need to place the payload into box.
@@ -86,6 +116,23 @@ pub enum TableEvent {
         delete_if_exists: bool,
         is_recovery: bool,
     },
+    /// Update a row in the table, which is applied as a delete of `old_row` followed by an append of `new_row`.
+    /// Columns in `unchanged_columns` are not carried by `new_row`, and are filled from the current row before it gets deleted.
+    Update {
+        old_row: MoonlinkRow,
+        new_row: MoonlinkRow,
+        unchanged_columns: Vec<usize>,
+        lsn: u64,
+        xact_id: Option<u32>,
+        is_recovery: bool,
+    },
+    /// Truncate the table, all rows committed before the given LSN are deleted.
+    /// Rows appended later in the same transaction are kept.
+    Truncate {
+        lsn: u64,
+        xact_id: Option<u32>,
+        is_recovery: bool,
+    },
     /// Commit all pending operations with a given LSN and xact_id
     Commit {
         lsn: u64,
@@ -156,11 +203,26 @@ pub enum TableEvent {
     ForceRegularDataCompaction,
     /// Force a full table maintenance operation.
     ForceFullMaintenance,
+    /// Force a snapshot expiration operation based on table snapshot retention config.
+    ForceSnapshotExpiration {
+        /// Whether to only report expired snapshots and unreachable files, without removing them.
+        dry_run: bool,
+    },
     /// Drop table.
     DropTable,
     /// Alter table,
     AlterTable {
         columns_to_drop: Vec<String>,
+        /// Columns to append at the end of the table.
+        columns_to_add: Vec<arrow_schema::FieldRef>,
+        /// Columns to rename, in the format of (old name, new name).
+        columns_to_rename: Vec<(String, String)>,
+        /// Columns to promote to a wider type, keyed by the name before renaming.
+        columns_to_promote: Vec<(String, arrow_schema::DataType)>,
+        /// Columns to relax to nullable, keyed by the name before renaming.
+        columns_to_relax_nullability: Vec<String>,
+        /// Notified when the request is validated against the table schema, which happens after previous alter tables complete.
+        validation_result_tx: Option<AlterTableValidationSender>,
     },
     /// Start initial table copy.
     /// `start_lsn` is the `pg_current_wal_lsn` when the initial copy starts.
@@ -202,11 +264,20 @@ pub enum TableEvent {
         /// Result for data compaction.
         data_compaction_result: Result<DataCompactionResult>,
     },
+    /// Periodical snapshot expiration.
+    PeriodicalSnapshotExpiration(uuid::Uuid),
+    /// Snapshot expiration completes.
+    SnapshotExpirationResult {
+        /// Result for snapshot expiration.
+        snapshot_expiration_result: SnapshotExpirationResult,
+    },
     /// Evicted files to delete.
     EvictedFilesToDelete {
         /// Evicted data files by object storage cache.
         evicted_files: EvictedFiles,
     },
+    /// Flush requested by memory accountant, when total memory usage exceeds soft limit.
+    MemoryPressureFlush,
 
     /// ================================================
     /// WAL events
@@ -231,6 +302,8 @@ impl TableEvent {
                 self,
                 TableEvent::Append { .. }
                     | TableEvent::Delete { .. }
+                    | TableEvent::Update { .. }
+                    | TableEvent::Truncate { .. }
                     | TableEvent::Commit { .. }
                     | TableEvent::StreamAbort { .. }
                     | TableEvent::CommitFlush { .. }
@@ -243,6 +316,8 @@ impl TableEvent {
                 self,
                 TableEvent::Append { .. }
                     | TableEvent::Delete { .. }
+                    | TableEvent::Update { .. }
+                    | TableEvent::Truncate { .. }
                     | TableEvent::Commit { .. }
                     | TableEvent::StreamAbort { .. }
             )
@@ -254,6 +329,8 @@ impl TableEvent {
         match &self {
             TableEvent::Append { xact_id, .. } => xact_id.is_some(),
             TableEvent::Delete { xact_id, .. } => xact_id.is_some(),
+            TableEvent::Update { xact_id, .. } => xact_id.is_some(),
+            TableEvent::Truncate { xact_id, .. } => xact_id.is_some(),
             TableEvent::StreamAbort { .. } => true,
             TableEvent::Commit { xact_id, .. } => xact_id.is_some(),
             TableEvent::CommitFlush { xact_id, .. } => xact_id.is_some(),
@@ -266,6 +343,8 @@ impl TableEvent {
         match self {
             TableEvent::Append { lsn, .. } => Some(*lsn),
             TableEvent::Delete { lsn, .. } => Some(*lsn),
+            TableEvent::Update { lsn, .. } => Some(*lsn),
+            TableEvent::Truncate { lsn, .. } => Some(*lsn),
             TableEvent::Commit { lsn, .. } => Some(*lsn),
             TableEvent::StreamAbort { .. } => None,
             TableEvent::CommitFlush { lsn, .. } => Some(*lsn),
@@ -277,6 +356,8 @@ impl TableEvent {
         match self {
             TableEvent::Append { is_recovery, .. }
             | TableEvent::Delete { is_recovery, .. }
+            | TableEvent::Update { is_recovery, .. }
+            | TableEvent::Truncate { is_recovery, .. }
             | TableEvent::Commit { is_recovery, .. }
             | TableEvent::StreamAbort { is_recovery, .. }
             | TableEvent::CommitFlush { is_recovery, .. }
@@ -292,6 +373,8 @@ impl TableEvent {
         match self {
             TableEvent::Append { is_recovery, .. }
             | TableEvent::Delete { is_recovery, .. }
+            | TableEvent::Update { is_recovery, .. }
+            | TableEvent::Truncate { is_recovery, .. }
             | TableEvent::Commit { is_recovery, .. }
             | TableEvent::StreamAbort { is_recovery, .. }
             | TableEvent::CommitFlush { is_recovery, .. }
@@ -5,7 +5,9 @@
 
 use crate::storage::io_utils;
 use crate::NonEvictableHandle;
-use moonlink_table_metadata::{DeletionVector, MooncakeTableMetadata, PositionDelete};
+use moonlink_table_metadata::{
+    DataKeyEnvelope, DeletionVector, MooncakeTableMetadata, PositionDelete,
+};
 
 use bincode::config;
 use tracing::Instrument;
@@ -79,6 +81,7 @@ impl ReadState {
         associated_files: Vec<String>,
         mut cache_handles: Vec<NonEvictableHandle>, // Cache handles for data files.
         read_state_filepath_remap: ReadStateFilepathRemap, // Used to remap local filepath to
+        data_key_envelope: Option<DataKeyEnvelope>, // Used to decrypt data files and deletion vectors for encrypted tables.
     ) -> Self {
         deletion_vectors_at_read.sort_by(|dv_1, dv_2| {
             dv_1.data_file_number
@@ -109,6 +112,7 @@ impl ReadState {
             puffin_files: remapped_puffin_files,
             deletion_vectors: deletion_vectors_at_read,
             position_deletes,
+            data_key_envelope,
         };
         let data = bincode::encode_to_vec(metadata, BINCODE_CONFIG).unwrap(); // TODO
 
@@ -178,6 +182,7 @@ mod tests {
             /*associated_files=*/ vec![],
             /*cache_handles=*/ vec![],
             read_state_filepath_remap,
+            /*data_key_envelope=*/ None,
         );
         let (
             deserialized_data_files,
@@ -46,6 +46,7 @@ impl ReadStateManager {
                 /*associated_files=*/ Vec::new(),
                 /*cache_handles=*/ Vec::new(),
                 read_state_filepath_remap.clone(), // Unused
+                /*data_key_envelope=*/ None,
             ))),
             table_snapshot,
             table_snapshot_watch_receiver,
@@ -0,0 +1,232 @@
+//! Change feed which serves committed inserts and deletes of a table from its WAL.
+//!
+//! Rows are encoded as JSON objects with table schema at each commit, which is recorded in WAL; deleted rows could only carry identity columns, so all columns are treated as nullable.
+use crate::error::Result;
+
+use arrow_json::ArrayWriter;
+use arrow_schema::{Field, Schema, SchemaRef};
+use futures::stream::{BoxStream, Stream, StreamExt};
+use moonlink::row::{moonlink_rows_to_record_batch, MoonlinkRow};
+use moonlink::{WalChange, WalCommittedTransaction};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::sync::Arc;
+
+/// Operation of a row-level change.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOperation {
+    Insert,
+    Delete,
+    Truncate,
+}
+
+impl ChangeOperation {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ChangeOperation::Insert => "insert",
+            ChangeOperation::Delete => "delete",
+            ChangeOperation::Truncate => "truncate",
+        }
+    }
+}
+
+/// A row-level change, row is unassigned for truncation.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub struct TableChange {
+    pub operation: ChangeOperation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub row: Option<Map<String, Value>>,
+}
+
+/// Changes of one committed transaction, in the order they're applied.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CommittedChanges {
+    pub commit_lsn: u64,
+    pub changes: Vec<TableChange>,
+}
+
+/// Stream of committed transactions, in commit order.
+pub type ChangeStream = BoxStream<'static, Result<CommittedChanges>>;
+
+/// Encode rows into JSON objects, with all fields relaxed to nullable.
+fn encode_rows(rows: &[MoonlinkRow], schema: &SchemaRef) -> Result<Vec<Map<String, Value>>> {
+    if rows.is_empty() {
+        return Ok(vec![]);
+    }
+    let record_batch = moonlink_rows_to_record_batch(rows, schema.clone())?;
+    let mut writer = ArrayWriter::new(Vec::new());
+    writer.write(&record_batch)?;
+    writer.finish()?;
+    Ok(serde_json::from_slice(&writer.into_inner())?)
+}
+
+fn encode_transaction(
+    transaction: WalCommittedTransaction,
+    schema: &SchemaRef,
+) -> Result<CommittedChanges> {
+    let mut operations = Vec::with_capacity(transaction.changes.len());
+    let mut rows = Vec::with_capacity(transaction.changes.len());
+    for cur_change in transaction.changes.into_iter() {
+        match cur_change {
+            WalChange::Insert(row) => {
+                operations.push(ChangeOperation::Insert);
+                rows.push(row);
+            }
+            WalChange::Delete(row) => {
+                operations.push(ChangeOperation::Delete);
+                rows.push(row);
+            }
+            WalChange::Truncate => operations.push(ChangeOperation::Truncate),
+        }
+    }
+
+    let mut encoded_rows = encode_rows(&rows, schema)?.into_iter();
+    let changes = operations
+        .into_iter()
+        .map(|operation| TableChange {
+            operation,
+            row: match operation {
+                ChangeOperation::Truncate => None,
+                ChangeOperation::Insert | ChangeOperation::Delete => encoded_rows.next(),
+            },
+        })
+        .collect();
+    Ok(CommittedChanges {
+        commit_lsn: transaction.commit_lsn,
+        changes,
+    })
+}
+
+/// Relax all fields of the given schema to nullable.
+fn get_nullable_schema(table_schema: &Schema) -> SchemaRef {
+    let fields = table_schema
+        .fields()
+        .iter()
+        .map(|field| Arc::new(field.as_ref().clone().with_nullable(true)))
+        .collect::<Vec<_>>();
+    Arc::new(Schema::new_with_metadata(
+        fields,
+        table_schema.metadata().clone(),
+    ))
+}
+
+/// Encode committed transactions read from WAL, each with the table schema at its commit.
+/// The given table schema is only used for transactions whose schema is not recorded in WAL.
+pub(crate) fn encode_change_stream<S>(transactions: S, table_schema: &Schema) -> ChangeStream
+where
+    S: Stream<Item = moonlink::Result<WalCommittedTransaction>> + Send + 'static,
+{
+    let default_schema = get_nullable_schema(table_schema);
+    // Schema only changes at alter table, so cache the relaxed one for the latest seen schema.
+    let mut cached_schema: Option<(SchemaRef, SchemaRef)> = None;
+    transactions
+        .map(move |transaction| {
+            let transaction = transaction?;
+            let schema = match &transaction.schema {
+                Some(transaction_schema) => match &cached_schema {
+                    Some((cached_source, cached_relaxed))
+                        if Arc::ptr_eq(cached_source, transaction_schema) =>
+                    {
+                        cached_relaxed.clone()
+                    }
+                    _ => {
+                        let relaxed = get_nullable_schema(transaction_schema);
+                        cached_schema = Some((transaction_schema.clone(), relaxed.clone()));
+                        relaxed
+                    }
+                },
+                None => default_schema.clone(),
+            };
+            encode_transaction(transaction, &schema)
+        })
+        // Stop at the first failure, since changes afterwards cannot be applied without it.
+        .scan(false, |failed, committed_changes| {
+            if *failed {
+                return std::future::ready(None);
+            }
+            *failed = committed_changes.is_err();
+            std::future::ready(Some(committed_changes))
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_schema::DataType;
+    use futures::stream;
+    use moonlink::row::RowValue;
+
+    #[tokio::test]
+    async fn test_encode_change_stream() {
+        let table_schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]);
+        let transactions = vec![Ok(WalCommittedTransaction {
+            commit_lsn: 10,
+            changes: vec![
+                WalChange::Insert(MoonlinkRow::new(vec![
+                    RowValue::Int32(1),
+                    RowValue::ByteArray(b"Alice".to_vec()),
+                ])),
+                WalChange::Truncate,
+                // Deleted row only carries identity column.
+                WalChange::Delete(MoonlinkRow::new(vec![RowValue::Int32(2), RowValue::Null])),
+            ],
+            schema: None,
+        })];
+        let mut changes = encode_change_stream(stream::iter(transactions), &table_schema);
+        let committed_changes = changes.next().await.unwrap().unwrap();
+        assert_eq!(committed_changes.commit_lsn, 10);
+        assert_eq!(
+            serde_json::to_value(&committed_changes.changes).unwrap(),
+            serde_json::json!([
+                {"operation": "insert", "row": {"id": 1, "name": "Alice"}},
+                {"operation": "truncate"},
+                {"operation": "delete", "row": {"id": 2}},
+            ])
+        );
+        assert!(changes.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_encode_change_stream_with_schema_change() {
+        let old_schema = Arc::new(Schema::new(vec![Field::new("id", DataType::Int32, false)]));
+        let new_schema = Arc::new(Schema::new(vec![
+            Field::new("uid", DataType::Int64, false),
+            Field::new("name", DataType::Utf8, true),
+        ]));
+        let transactions = vec![
+            Ok(WalCommittedTransaction {
+                commit_lsn: 10,
+                changes: vec![WalChange::Insert(MoonlinkRow::new(vec![RowValue::Int32(
+                    1,
+                )]))],
+                schema: Some(old_schema),
+            }),
+            // Column renamed and promoted, with a new column added.
+            Ok(WalCommittedTransaction {
+                commit_lsn: 20,
+                changes: vec![WalChange::Insert(MoonlinkRow::new(vec![
+                    RowValue::Int64(2),
+                    RowValue::ByteArray(b"Bob".to_vec()),
+                ]))],
+                schema: Some(new_schema.clone()),
+            }),
+        ];
+        // Current table schema is not used, since schema is recorded for every transaction.
+        let mut changes = encode_change_stream(stream::iter(transactions), &new_schema);
+        assert_eq!(
+            serde_json::to_value(&changes.next().await.unwrap().unwrap().changes).unwrap(),
+            serde_json::json!([{"operation": "insert", "row": {"id": 1}}])
+        );
+        assert_eq!(
+            serde_json::to_value(&changes.next().await.unwrap().unwrap().changes).unwrap(),
+            serde_json::json!([{"operation": "insert", "row": {"uid": 2, "name": "Bob"}}])
+        );
+        assert!(changes.next().await.is_none());
+    }
+}
@@ -1,9 +1,11 @@
+use arrow_schema::ArrowError;
 use moonlink::Error as MoonlinkError;
 use moonlink_connectors::Error as MoonlinkConnectorError;
 use moonlink_connectors::PostgresSourceError;
 use moonlink_error::io_error_utils;
 use moonlink_error::{ErrorStatus, ErrorStruct};
 use moonlink_metadata_store::error::Error as MoonlinkMetadataStoreError;
+use parquet::errors::ParquetError;
 use serde::{Deserialize, Serialize};
 use std::num::ParseIntError;
 use std::panic::Location;
@@ -52,6 +54,18 @@ pub enum Error {
 
     #[error("{0}")]
     InsufficientDiskSpace(ErrorStruct),
+
+    #[error("{0}")]
+    Arrow(ErrorStruct),
+
+    #[error("{0}")]
+    Parquet(ErrorStruct),
+
+    #[error("{0}")]
+    WalTruncated(ErrorStruct),
+
+    #[error("{0}")]
+    MemoryLimitExceeded(ErrorStruct),
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -80,6 +94,13 @@ impl Error {
         );
         Self::InsufficientDiskSpace(ErrorStruct::new(message, ErrorStatus::Permanent))
     }
+    #[track_caller]
+    pub fn memory_limit_exceeded(memory_usage: u64, hard_limit: u64) -> Self {
+        let message = format!(
+            "Moonlink memory usage {memory_usage} bytes exceeds hard limit {hard_limit} bytes, retry after tables flush."
+        );
+        Self::MemoryLimitExceeded(ErrorStruct::new(message, ErrorStatus::Temporary))
+    }
 
     pub fn get_status(&self) -> ErrorStatus {
         match self {
@@ -96,6 +117,10 @@ impl Error {
             Error::DataCorruptionError(es) => es.status,
             Error::InvalidConfig(es) => es.status,
             Error::InsufficientDiskSpace(es) => es.status,
+            Error::Arrow(es) => es.status,
+            Error::Parquet(es) => es.status,
+            Error::WalTruncated(es) => es.status,
+            Error::MemoryLimitExceeded(es) => es.status,
         }
     }
 }
@@ -128,6 +153,10 @@ impl From<ParseIntError> for Error {
 impl From<MoonlinkConnectorError> for Error {
     #[track_caller]
     fn from(source: MoonlinkConnectorError) -> Self {
+        // Invalid schema change comes from user requests.
+        if let MoonlinkConnectorError::RestInvalidSchemaChange(es) = source {
+            return Error::InvalidArgumentError(es);
+        }
         let status = match &source {
             MoonlinkConnectorError::PostgresSourceError(es)
             | MoonlinkConnectorError::TokioPostgres(es)
@@ -153,6 +182,10 @@ impl From<MoonlinkConnectorError> for Error {
 impl From<MoonlinkError> for Error {
     #[track_caller]
     fn from(source: MoonlinkError) -> Self {
+        // Surface WAL truncation directly, so callers could tell the requested position is gone.
+        if let MoonlinkError::WalTruncated(es) = source {
+            return Error::WalTruncated(es);
+        }
         Error::MoonlinkError(ErrorStruct {
             message: "Moonlink source error".to_string(),
             status: source.get_status(),
@@ -226,3 +259,31 @@ impl<T: Send + Sync + 'static> From<tokio::sync::mpsc::error::SendError<T>> for
         })
     }
 }
+
+impl From<ArrowError> for Error {
+    #[track_caller]
+    fn from(source: ArrowError) -> Self {
+        let status = match source {
+            ArrowError::IoError(_, _) => ErrorStatus::Temporary,
+            _ => ErrorStatus::Permanent,
+        };
+        Error::Arrow(ErrorStruct {
+            message: "Arrow error".to_string(),
+            status,
+            source: Some(Arc::new(source.into())),
+            location: Some(Location::caller().to_string()),
+        })
+    }
+}
+
+impl From<ParquetError> for Error {
+    #[track_caller]
+    fn from(source: ParquetError) -> Self {
+        Error::Parquet(ErrorStruct {
+            message: "Parquet error".to_string(),
+            status: ErrorStatus::Permanent,
+            source: Some(Arc::new(source.into())),
+            location: Some(Location::caller().to_string()),
+        })
+    }
+}
@@ -1,31 +1,35 @@
+pub mod change_feed;
 mod config_utils;
 mod error;
 pub mod file_utils;
 mod parquet_utils;
 mod recovery_utils;
 pub mod table_config;
+pub mod table_scan;
 pub mod table_status;
 
 use apache_avro::schema::Schema as AvroSchema;
-use arrow_schema::Schema;
+use arrow_schema::{FieldRef, Schema};
 pub use error::{Error, Result};
 use futures::{stream, StreamExt, TryStreamExt};
-pub use moonlink::ReadState;
-use moonlink::{MooncakeTableId, MoonlinkTableConfig};
+use moonlink::{MemoryAccountant, MooncakeTableId, MoonlinkTableConfig};
+pub use moonlink::{MemoryBudgetConfig, ReadState, SnapshotExpirationReport};
 use moonlink::{ReadStateFilepathRemap, TableEventManager};
 pub use moonlink_connectors::rest_ingest::event_request::{
-    EventRequest, FileEventOperation, FileEventRequest, FlushRequest, IngestRequestPayload,
-    RowEventOperation, RowEventRequest, SnapshotRequest,
+    BatchRowEventRequest, BatchRowOperation, EventRequest, FileEventOperation, FileEventRequest,
+    FlushRequest, IngestRequestPayload, RowEventOperation, RowEventRequest, SnapshotRequest,
 };
 pub use moonlink_connectors::rest_ingest::rest_event::RestEvent;
 pub use moonlink_connectors::rest_ingest::rest_source::RestSource;
 use moonlink_connectors::ReplicationManager;
-pub use moonlink_connectors::REST_API_URI;
+pub use moonlink_connectors::{KAFKA_URI_PREFIX, REST_API_URI};
 use moonlink_metadata_store::base_metadata_store::MetadataStoreTrait;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+use crate::change_feed::ChangeStream;
 use crate::recovery_utils::BackendAttributes;
+use crate::table_scan::{ScanOptions, TableScan};
 use crate::table_status::TableStatus;
 
 /// Type alias for filepath remap function, which remaps http URI to local filepath if possible.
@@ -57,6 +61,29 @@ impl MoonlinkBackend {
         data_server_uri: Option<String>,
         metadata_store_accessor: Box<dyn MetadataStoreTrait>,
     ) -> Result<Self> {
+        Self::new_with_memory_budget(
+            base_path,
+            data_server_uri,
+            metadata_store_accessor,
+            MemoryBudgetConfig::default(),
+        )
+        .await
+    }
+
+    /// Create moonlink backend with the given memory budget shared by all tables.
+    /// When memory usage exceeds soft limit, largest tables are flushed; when it exceeds hard limit, ingestion is paused or rejected.
+    pub async fn new_with_memory_budget(
+        base_path: String,
+        data_server_uri: Option<String>,
+        metadata_store_accessor: Box<dyn MetadataStoreTrait>,
+        memory_budget_config: MemoryBudgetConfig,
+    ) -> Result<Self> {
+        if !memory_budget_config.is_valid() {
+            return Err(Error::invalid_config(format!(
+                "Memory soft limit should be no larger than hard limit, but got {memory_budget_config:?}"
+            )));
+        }
+
         // Create local filepath remap logic, so IO requests could be routed to data server.
         let base_path_arc = Arc::new(base_path.clone());
         let data_server_uri_arc = Arc::new(data_server_uri.clone());
@@ -112,10 +139,17 @@ impl MoonlinkBackend {
         file_utils::recreate_directory(temp_files_dir.to_str().unwrap()).unwrap();
         file_utils::recreate_directory(read_cache_files_dir.to_str().unwrap()).unwrap();
 
+        // Remote delta tables are accessed via registered object store handlers, which have to be ready before recovery.
+        moonlink::register_deltalake_storage_handlers();
+
         let object_storage_cache =
             file_utils::create_default_object_storage_cache(read_cache_files_dir)?;
-        let mut replication_manager =
-            ReplicationManager::new(base_path_str.to_string(), object_storage_cache);
+        let memory_accountant = MemoryAccountant::new(memory_budget_config);
+        let mut replication_manager = ReplicationManager::new(
+            base_path_str.to_string(),
+            object_storage_cache,
+            memory_accountant,
+        );
 
         let backend_attributes = BackendAttributes {
             temp_files_dir: temp_files_dir.to_str().unwrap().to_string(),
@@ -204,6 +238,28 @@ impl MoonlinkBackend {
                     )
                     .await?;
                 Ok(cur_moonlink_table_config)
+            } else if src_uri.starts_with(KAFKA_URI_PREFIX) {
+                let arrow_schema = input_schema.ok_or_else(|| {
+                    Error::invalid_argument("arrow schema is required for Kafka source".to_string())
+                })?;
+                let cur_moonlink_table_config = config_utils::parse_event_table_config(
+                    &table_config,
+                    &mooncake_table_id,
+                    &self.base_path,
+                    &self.temp_files_dir,
+                )?;
+                manager
+                    .add_kafka_table(
+                        &src_uri,
+                        mooncake_table_id,
+                        &src_table_name,
+                        arrow_schema,
+                        cur_moonlink_table_config.clone(),
+                        self.read_state_filepath_remap.clone(),
+                        /*flush_lsn=*/ None,
+                    )
+                    .await?;
+                Ok(cur_moonlink_table_config)
             } else {
                 let mut cur_moonlink_table_config = config_utils::parse_replication_table_config(
                     &table_config,
@@ -244,19 +300,97 @@ impl MoonlinkBackend {
     ///
     /// # Arguments
     ///
+    /// * database, table: mooncake table id, under which the Avro schema is persisted for recovery
     /// * src_table_name: Source table name (typically matches the table name used in create_table)
+    /// * schema_id: Schema id in confluent wire format, if the schema comes from a schema registry
     /// * avro_schema: Avro schema for parsing data
+    ///
+    /// Schemas which append new columns alter the table schema, other versions are only used to resolve records written with them.
     pub async fn set_avro_schema(
         &self,
+        database: String,
+        table: String,
         src_table_name: String,
+        schema_id: Option<u32>,
         avro_schema: AvroSchema,
     ) -> Result<()> {
+        validate_not_empty(&database, "database")?;
+        validate_not_empty(&table, "table")?;
         validate_not_empty(&src_table_name, "src_table_name")?;
+        let serialized_avro_schema = serde_json::to_string(&avro_schema)?;
 
         let mut manager = self.replication_manager.write().await;
         // Set Avro schema on the existing REST table
-        manager.set_avro_schema(src_table_name, avro_schema).await?;
+        manager
+            .set_avro_schema(src_table_name, schema_id, avro_schema)
+            .await?;
+
+        // Persist Avro schema, which is set again at recovery.
+        self.metadata_store_accessor
+            .store_avro_schema(&database, &table, schema_id, &serialized_avro_schema)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Set Avro schema for a table which ingests from Kafka
+    ///
+    /// # Arguments
+    ///
+    /// * src_uri: Kafka URI used to create the table
+    /// * topic: Kafka topic, which matches the source table name used in create_table
+    /// * schema_id: Schema id in confluent wire format, if the schema comes from a schema registry
+    /// * avro_schema: Avro schema for parsing message values
+    pub async fn set_kafka_avro_schema(
+        &self,
+        src_uri: String,
+        topic: String,
+        schema_id: Option<u32>,
+        avro_schema: AvroSchema,
+    ) -> Result<()> {
+        validate_not_empty(&topic, "topic")?;
+
+        let mut manager = self.replication_manager.write().await;
+        manager
+            .set_kafka_avro_schema(&src_uri, topic, schema_id, avro_schema)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Alter schema for a table created via REST API.
+    ///
+    /// # Arguments
+    ///
+    /// * src_table_name: source table name used for REST ingestion, which must match the table if assigned
+    /// * columns_to_add: columns to append at the end of the table, which must be nullable
+    /// * columns_to_drop: columns to drop, which cannot be referenced by row identity, partition spec or sort order
+    /// * columns_to_rename: columns to rename, in the format of (old name, new name)
+    ///
+    /// Schema change applies to all requests received afterwards.
+    pub async fn alter_table(
+        &self,
+        database: String,
+        table: String,
+        src_table_name: Option<String>,
+        columns_to_add: Vec<FieldRef>,
+        columns_to_drop: Vec<String>,
+        columns_to_rename: Vec<(String, String)>,
+    ) -> Result<()> {
+        validate_not_empty(&database, "database")?;
+        validate_not_empty(&table, "table")?;
 
+        let mooncake_table_id = MooncakeTableId { database, table };
+        let manager = self.replication_manager.read().await;
+        manager
+            .alter_table(
+                &mooncake_table_id,
+                src_table_name.as_deref(),
+                columns_to_add,
+                columns_to_drop,
+                columns_to_rename,
+            )
+            .await?;
         Ok(())
     }
 
@@ -324,9 +458,13 @@ impl MoonlinkBackend {
     pub async fn list_tables(&self) -> Result<Vec<TableStatus>> {
         let mut table_statuses = vec![];
         let manager = self.replication_manager.read().await;
+        let memory_accountant = manager.get_memory_accountant();
         let table_state_readers = manager.get_table_status_readers();
         for (mooncake_table_id, cur_reader) in table_state_readers.into_iter() {
             let table_snapshot_status = cur_reader.get_current_table_state().await?;
+            let memory_usage_bytes = memory_accountant
+                .get_table_memory_size(&mooncake_table_id.to_string())
+                .unwrap_or(0);
             let table_status = TableStatus {
                 database: mooncake_table_id.database.clone(),
                 table: mooncake_table_id.table.clone(),
@@ -334,6 +472,7 @@ impl MoonlinkBackend {
                 flush_lsn: table_snapshot_status.flush_lsn,
                 cardinality: table_snapshot_status.cardinality,
                 iceberg_warehouse_location: table_snapshot_status.iceberg_warehouse_location,
+                memory_usage_bytes,
             };
             table_statuses.push(table_status);
         }
@@ -382,6 +521,57 @@ impl MoonlinkBackend {
         Ok(())
     }
 
+    /// Expire iceberg snapshots based on the table's snapshot retention config.
+    /// If `dry_run` is true, only report snapshots and files to remove without any deletion.
+    pub async fn expire_snapshots(
+        &self,
+        database: String,
+        table: String,
+        dry_run: bool,
+    ) -> Result<SnapshotExpirationReport> {
+        validate_not_empty(&database, "database")?;
+        validate_not_empty(&table, "table")?;
+
+        let mut rx = {
+            let mut manager = self.replication_manager.write().await;
+            let mooncake_table_id = MooncakeTableId { database, table };
+            let writer = manager.get_table_event_manager(&mooncake_table_id)?;
+            writer.initiate_snapshot_expiration(dry_run).await
+        };
+
+        let report = rx.recv().await.unwrap()?;
+        Ok(report)
+    }
+
+    /// Re-wrap the table data key with the given master key, which should already exist at the table's key provider.
+    /// Persisted files stay untouched, since data key keeps unchanged.
+    /// If the table is not encrypted, return [`InvalidArgumentError`].
+    pub async fn rotate_master_key(
+        &self,
+        database: String,
+        table: String,
+        new_master_key_id: String,
+    ) -> Result<()> {
+        validate_not_empty(&database, "database")?;
+        validate_not_empty(&table, "table")?;
+        validate_not_empty(&new_master_key_id, "new_master_key_id")?;
+
+        let mooncake_table_id = MooncakeTableId { database, table };
+        let table_encryption = {
+            let manager = self.replication_manager.read().await;
+            manager.get_table_encryption(&mooncake_table_id)?
+        };
+        let Some(table_encryption) = table_encryption else {
+            return Err(Error::invalid_argument(format!(
+                "Table {mooncake_table_id} is not encrypted"
+            )));
+        };
+        table_encryption
+            .rotate_master_key(&new_master_key_id)
+            .await?;
+        Ok(())
+    }
+
     /// If the requested database or table doesn't exist, return [`TableNotFound`] error.
     pub async fn scan_table(
         &self,
@@ -402,6 +592,59 @@ impl MoonlinkBackend {
         Ok(read_state.clone())
     }
 
+    /// Scan rows visible at the requested LSN, with deletion vectors and position deletes applied.
+    /// Data files are read lazily when the returned stream is polled, and they're kept alive until the stream is dropped.
+    pub async fn scan_table_rows(
+        &self,
+        database: String,
+        table: String,
+        lsn: Option<u64>,
+        options: ScanOptions,
+    ) -> Result<TableScan> {
+        let table_schema = self
+            .get_table_schema(database.clone(), table.clone())
+            .await?;
+        let table_encryption = {
+            let manager = self.replication_manager.read().await;
+            let mooncake_table_id = MooncakeTableId {
+                database: database.clone(),
+                table: table.clone(),
+            };
+            manager.get_table_encryption(&mooncake_table_id)?
+        };
+        let read_state = self.scan_table(database, table, lsn).await?;
+        table_scan::scan_read_state(
+            read_state,
+            table_schema,
+            options,
+            self.http_filepath_remap.clone(),
+            table_encryption,
+        )
+    }
+
+    /// Subscribe to committed changes of the table, starting from transactions committed at or after [`start_lsn`].
+    /// Changes are read from persisted WAL, so subscribers could resume after restart, unless WAL has been truncated past the requested LSN, in which case [`Error::WalTruncated`] is returned.
+    pub async fn subscribe_table_changes(
+        &self,
+        database: String,
+        table: String,
+        start_lsn: u64,
+    ) -> Result<ChangeStream> {
+        let table_schema = self
+            .get_table_schema(database.clone(), table.clone())
+            .await?;
+        let table_change_feed = {
+            let manager = self.replication_manager.read().await;
+            let mooncake_table_id = MooncakeTableId { database, table };
+            manager.get_table_change_feed(&mooncake_table_id)?.clone()
+        };
+        let transactions = table_change_feed.subscribe(start_lsn).await?;
+        Ok(change_feed::encode_change_stream(
+            transactions,
+            &table_schema,
+        ))
+    }
+
     /// Wait for the WAL flush LSN to reach the requested LSN. Note that WAL flush LSN will update
     /// up till the latest commit that has been persisted in to the WAL.
     pub async fn wait_for_wal_flush(
@@ -447,7 +690,22 @@ impl MoonlinkBackend {
         Ok(())
     }
 
+    /// Row ingestion requests are rejected with [`MemoryLimitExceeded`] error if memory usage exceeds hard limit, callers are expected to retry after tables flush.
+    /// Snapshot and flush requests are always accepted, since they help release memory.
     pub async fn send_event_request(&self, request: EventRequest) -> Result<()> {
+        if matches!(
+            request,
+            EventRequest::RowRequest(_) | EventRequest::BatchRowRequest(_)
+        ) {
+            let manager = self.replication_manager.read().await;
+            let memory_accountant = manager.get_memory_accountant();
+            if memory_accountant.is_over_hard_limit() {
+                return Err(Error::memory_limit_exceeded(
+                    memory_accountant.get_total_memory_size(),
+                    memory_accountant.get_config().hard_limit_bytes,
+                ));
+            }
+        }
         self.event_api_sender
             .as_ref()
             .expect("event api sender not initialized")
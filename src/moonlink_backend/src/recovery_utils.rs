@@ -1,8 +1,14 @@
-use crate::error::Result;
+use crate::error::{Error, Result};
+use apache_avro::schema::Schema as AvroSchema;
+use arrow_schema::Schema as ArrowSchema;
 use moonlink::MooncakeTableId;
 use moonlink::ReadStateFilepathRemap;
-use moonlink::{BaseIcebergSnapshotFetcher, IcebergSnapshotFetcher};
-use moonlink_connectors::{ReplicationManager, REST_API_URI};
+use moonlink::{
+    BaseIcebergSnapshotFetcher, DeltalakeSnapshotFetcher, IcebergSnapshotFetcher,
+    MoonlinkTableConfig,
+};
+use moonlink_connectors::rest_ingest::rest_source::get_avro_reader_schema;
+use moonlink_connectors::{ReplicationManager, KAFKA_URI_PREFIX, REST_API_URI};
 use moonlink_metadata_store::base_metadata_store::{MetadataStoreTrait, TableMetadataEntry};
 
 use std::collections::HashSet;
@@ -16,22 +22,82 @@ pub(crate) struct BackendAttributes {
     pub(crate) base_path: String,
 }
 
+/// Fetch table schema and flush LSN of the latest persisted snapshot, from delta table if configured, otherwise from iceberg table.
+async fn fetch_persisted_snapshot(
+    moonlink_table_config: &MoonlinkTableConfig,
+) -> Result<(Option<ArrowSchema>, Option<u64>)> {
+    if let Some(deltalake_table_config) = &moonlink_table_config.deltalake_table_config {
+        let deltalake_snapshot_fetcher =
+            DeltalakeSnapshotFetcher::new(deltalake_table_config.clone());
+        let arrow_schema = deltalake_snapshot_fetcher.fetch_table_schema().await?;
+        let flush_lsn = deltalake_snapshot_fetcher.get_flush_lsn().await?;
+        return Ok((arrow_schema, flush_lsn));
+    }
+
+    let iceberg_snapshot_fetcher =
+        IcebergSnapshotFetcher::new(moonlink_table_config.iceberg_table_config.clone()).await?;
+    let arrow_schema = iceberg_snapshot_fetcher.fetch_table_schema().await?;
+    let flush_lsn = iceberg_snapshot_fetcher.get_flush_lsn().await?;
+    Ok((arrow_schema, flush_lsn))
+}
+
+/// Set persisted Avro schemas for the recovered REST table.
+/// Reader schema is set first, which appends columns not yet persisted to the table; then all schema versions are registered again.
+async fn recover_avro_schemas(
+    mooncake_table_id: &MooncakeTableId,
+    src_table_name: &str,
+    metadata_store_accessor: &dyn MetadataStoreTrait,
+    replication_manager: &mut ReplicationManager,
+) -> Result<()> {
+    let avro_schema_entries = metadata_store_accessor
+        .get_avro_schemas(&mooncake_table_id.database, &mooncake_table_id.table)
+        .await?;
+    let mut avro_schemas = Vec::with_capacity(avro_schema_entries.len());
+    for entry in avro_schema_entries.into_iter() {
+        let avro_schema = AvroSchema::parse_str(&entry.avro_schema).map_err(|e| {
+            Error::data_corruption(format!(
+                "Invalid persisted Avro schema for table {mooncake_table_id}: {e}"
+            ))
+        })?;
+        avro_schemas.push((entry.schema_id, avro_schema));
+    }
+
+    let schemas = avro_schemas
+        .iter()
+        .map(|(_, avro_schema)| avro_schema.clone())
+        .collect::<Vec<_>>();
+    let Some(reader_schema) = get_avro_reader_schema(&schemas)? else {
+        return Ok(());
+    };
+    replication_manager
+        .set_avro_schema(
+            src_table_name.to_string(),
+            /*schema_id=*/ None,
+            reader_schema,
+        )
+        .await?;
+    for (schema_id, avro_schema) in avro_schemas.into_iter() {
+        if schema_id.is_some() {
+            replication_manager
+                .set_avro_schema(src_table_name.to_string(), schema_id, avro_schema)
+                .await?;
+        }
+    }
+    Ok(())
+}
+
 /// Recover REST ingestion table.
 async fn recover_rest_table(
     backend_attributes: BackendAttributes,
     metadata_entry: TableMetadataEntry,
+    metadata_store_accessor: &dyn MetadataStoreTrait,
     replication_manager: &mut ReplicationManager,
     read_state_filepath_remap: ReadStateFilepathRemap,
 ) -> Result<()> {
     assert_eq!(metadata_entry.src_table_uri, REST_API_URI);
 
-    let iceberg_table_config = metadata_entry
-        .moonlink_table_config
-        .iceberg_table_config
-        .clone();
-    let iceberg_snapshot_fetcher = IcebergSnapshotFetcher::new(iceberg_table_config).await?;
-    let arrow_schema = iceberg_snapshot_fetcher.fetch_table_schema().await?;
-    let flush_lsn = iceberg_snapshot_fetcher.get_flush_lsn().await?;
+    let (arrow_schema, flush_lsn) =
+        fetch_persisted_snapshot(&metadata_entry.moonlink_table_config).await?;
 
     // Only perform recovery when there's valid iceberg snapshot.
     if arrow_schema.is_none() {
@@ -51,6 +117,50 @@ async fn recover_rest_table(
         .await?;
     replication_manager
         .add_rest_table(
+            &metadata_entry.src_table_uri,
+            mooncake_table_id.clone(),
+            &metadata_entry.src_table_name,
+            arrow_schema.unwrap(),
+            metadata_entry.moonlink_table_config,
+            read_state_filepath_remap,
+            flush_lsn,
+        )
+        .await?;
+    recover_avro_schemas(
+        &mooncake_table_id,
+        &metadata_entry.src_table_name,
+        metadata_store_accessor,
+        replication_manager,
+    )
+    .await?;
+    Ok(())
+}
+
+/// Recover Kafka ingestion table, consumption resumes right after the latest iceberg snapshot.
+async fn recover_kafka_table(
+    metadata_entry: TableMetadataEntry,
+    replication_manager: &mut ReplicationManager,
+    read_state_filepath_remap: ReadStateFilepathRemap,
+) -> Result<()> {
+    assert!(metadata_entry.src_table_uri.starts_with(KAFKA_URI_PREFIX));
+
+    let (arrow_schema, flush_lsn) =
+        fetch_persisted_snapshot(&metadata_entry.moonlink_table_config).await?;
+
+    // Only perform recovery when there's valid iceberg snapshot.
+    if arrow_schema.is_none() {
+        return Ok(());
+    }
+    if flush_lsn.is_none() {
+        return Ok(());
+    }
+
+    let mooncake_table_id = MooncakeTableId {
+        database: metadata_entry.database,
+        table: metadata_entry.table,
+    };
+    replication_manager
+        .add_kafka_table(
             &metadata_entry.src_table_uri,
             mooncake_table_id,
             &metadata_entry.src_table_name,
@@ -91,6 +201,7 @@ async fn recover_non_rest_table(
 async fn recover_table(
     backend_attributes: BackendAttributes,
     metadata_entry: TableMetadataEntry,
+    metadata_store_accessor: &dyn MetadataStoreTrait,
     replication_manager: &mut ReplicationManager,
     read_state_filepath_remap: ReadStateFilepathRemap,
 ) -> Result<()> {
@@ -98,6 +209,15 @@ async fn recover_table(
     if metadata_entry.src_table_uri == REST_API_URI {
         return recover_rest_table(
             backend_attributes,
+            metadata_entry,
+            metadata_store_accessor,
+            replication_manager,
+            read_state_filepath_remap,
+        )
+        .await;
+    }
+    if metadata_entry.src_table_uri.starts_with(KAFKA_URI_PREFIX) {
+        return recover_kafka_table(
             metadata_entry,
             replication_manager,
             read_state_filepath_remap,
@@ -149,6 +269,7 @@ pub(super) async fn recover_all_tables(
         recover_table(
             backend_attributes.clone(),
             cur_metadata_entry,
+            metadata_store_accessor,
             replication_manager,
             read_state_filepath_remap.clone(),
         )
@@ -4,8 +4,10 @@ use crate::{Error, Result};
 use moonlink::row::IdentityProp;
 use moonlink::MooncakeTableId;
 use moonlink::{
-    AccessorConfig, DataCompactionConfig, FileIndexMergeConfig, IcebergTableConfig,
-    MooncakeTableConfig, MoonlinkTableConfig, StorageConfig, WalConfig,
+    AccessorConfig, DataCompactionConfig, DeltalakeTableConfig, EncryptionConfig,
+    FileIndexMergeConfig, IcebergTableConfig, MooncakeTableConfig, MoonlinkTableConfig,
+    ParquetWriterConfig, PartitionFieldConfig, SnapshotRetentionConfig, SortFieldConfig,
+    SourceFilterConfig, StorageConfig, WalConfig,
 };
 /// Configuration on table creation.
 use serde::{Deserialize, Serialize};
@@ -25,6 +27,27 @@ pub struct MooncakeConfig {
     /// Row identity of the table.
     #[serde(default)]
     pub row_identity: Option<IdentityProp>,
+    /// Partition spec of the table, empty for unpartitioned tables.
+    #[serde(default)]
+    pub partition_spec: Vec<PartitionFieldConfig>,
+    /// Sort order of the table, rows within each data file are sorted by it, empty for unsorted tables.
+    #[serde(default)]
+    pub sort_order: Vec<SortFieldConfig>,
+    /// Retention policy for iceberg snapshots, snapshots are never expired by default.
+    #[serde(default)]
+    pub snapshot_retention: SnapshotRetentionConfig,
+    /// Parquet writer options for data files, which keep moonlink defaults if unassigned.
+    #[serde(default)]
+    pub parquet_writer: ParquetWriterConfig,
+    /// Client-side encryption for data files, index files, deletion vectors and WAL, disabled if unassigned.
+    #[serde(default)]
+    pub encryption: Option<EncryptionConfig>,
+    /// Whether to alter the postgres source table to REPLICA IDENTITY FULL, if it has neither primary key nor replica identity index.
+    #[serde(default)]
+    pub replica_identity_full: bool,
+    /// Column and row filters on the postgres source table, everything is mirrored if unassigned.
+    #[serde(default)]
+    pub source_filter: SourceFilterConfig,
 }
 
 impl MooncakeConfig {
@@ -41,6 +64,15 @@ impl MooncakeConfig {
         {
             return false;
         }
+        if !self.parquet_writer.is_valid() {
+            return false;
+        }
+        if !self.snapshot_retention.is_valid() {
+            return false;
+        }
+        if !self.source_filter.is_valid() {
+            return false;
+        }
         true
     }
 
@@ -72,10 +104,28 @@ impl MooncakeConfig {
         mooncake_table_config.data_compaction_config = data_compaction_config;
         mooncake_table_config.append_only = self.append_only.unwrap();
         mooncake_table_config.row_identity = self.row_identity.unwrap();
+        mooncake_table_config.partition_spec = self.partition_spec;
+        mooncake_table_config.sort_order = self.sort_order;
+        mooncake_table_config.snapshot_retention_config = self.snapshot_retention;
+        mooncake_table_config.parquet_writer_config = self.parquet_writer;
+        mooncake_table_config.encryption_config = self.encryption;
+        mooncake_table_config.replica_identity_full = self.replica_identity_full;
+        mooncake_table_config.source_filter_config = self.source_filter;
+        // Reject invalid partition spec at table creation, instead of failing at table initialization.
+        mooncake_table_config.validate()?;
         Ok(mooncake_table_config)
     }
 }
 
+/// Open table format which the table is persisted in.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum TableFormat {
+    #[default]
+    Iceberg,
+    Delta,
+}
+
 /// Mooncake table configuration specified at creation.
 #[derive(Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub struct TableConfig {
@@ -84,7 +134,11 @@ pub struct TableConfig {
     #[serde(default)]
     pub mooncake_config: MooncakeConfig,
 
-    /// Iceberg storage config.
+    /// Table format to persist in, iceberg by default.
+    #[serde(default)]
+    pub format: TableFormat,
+
+    /// Table storage config, which applies to both iceberg and delta tables.
     #[serde(rename = "iceberg")]
     #[serde(default)]
     pub iceberg_config: Option<AccessorConfig>,
@@ -101,6 +155,20 @@ impl TableConfig {
         if !self.mooncake_config.is_valid() {
             return false;
         }
+        // Delta lake vacuum only supports retention by file age.
+        if self.format == TableFormat::Delta
+            && self
+                .mooncake_config
+                .snapshot_retention
+                .max_snapshots_to_keep
+                .is_some()
+        {
+            return false;
+        }
+        // Delta lake tables are read by external engines, which don't share moonlink data keys.
+        if self.format == TableFormat::Delta && self.mooncake_config.encryption.is_some() {
+            return false;
+        }
         true
     }
 
@@ -137,6 +205,24 @@ impl TableConfig {
             )));
         }
 
+        // Delta table is placed at the same location as iceberg table with file catalog.
+        let deltalake_table_config = match self.format {
+            TableFormat::Iceberg => None,
+            TableFormat::Delta => {
+                let accessor_config = self.iceberg_config.clone().unwrap();
+                Some(DeltalakeTableConfig {
+                    table_name: mooncake_table_id.table.clone(),
+                    location: format!(
+                        "{}/{}/{}",
+                        accessor_config.get_root_path().trim_end_matches('/'),
+                        mooncake_table_id.database,
+                        mooncake_table_id.table
+                    ),
+                    data_accessor_config: accessor_config,
+                })
+            }
+        };
+
         let config = MoonlinkTableConfig {
             mooncake_table_config: self
                 .mooncake_config
@@ -149,6 +235,7 @@ impl TableConfig {
                     accessor_config: self.iceberg_config.clone().unwrap(),
                 },
             },
+            deltalake_table_config,
             wal_table_config: WalConfig::new(
                 self.wal_config.unwrap(),
                 &mooncake_table_id.to_string(),
@@ -161,6 +248,8 @@ impl TableConfig {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use iceberg::spec::{NullOrder, SortDirection};
+    use moonlink::{ColumnFilter, RowFilter, RowFilterOperator};
 
     #[test]
     fn test_table_config_from_empty_json() {
@@ -173,7 +262,15 @@ mod tests {
                 skip_data_compaction: false,
                 append_only: None,
                 row_identity: None,
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: false,
+                source_filter: SourceFilterConfig::default(),
             },
+            format: TableFormat::Iceberg,
             iceberg_config: Some(AccessorConfig::new_with_storage_config(
                 moonlink::StorageConfig::FileSystem {
                     root_directory: "/tmp/path".to_string(),
@@ -226,7 +323,15 @@ mod tests {
                 skip_data_compaction: false,
                 append_only: None,
                 row_identity: None,
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: false,
+                source_filter: SourceFilterConfig::default(),
             },
+            format: TableFormat::Iceberg,
             iceberg_config: Some(AccessorConfig::new_with_storage_config(
                 moonlink::StorageConfig::FileSystem {
                     root_directory: "/tmp".to_string(),
@@ -288,7 +393,15 @@ mod tests {
                 skip_data_compaction: false,
                 append_only: None,
                 row_identity: None,
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: false,
+                source_filter: SourceFilterConfig::default(),
             },
+            format: TableFormat::Iceberg,
             iceberg_config: Some(AccessorConfig::new_with_storage_config(
                 moonlink::StorageConfig::Gcs {
                     project: "gcs-proj".to_string(),
@@ -360,7 +473,15 @@ mod tests {
                 skip_data_compaction: false,
                 append_only: None,
                 row_identity: None,
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: false,
+                source_filter: SourceFilterConfig::default(),
             },
+            format: TableFormat::Iceberg,
             iceberg_config: Some(AccessorConfig::new_with_storage_config(
                 moonlink::StorageConfig::S3 {
                     region: "us-west1".to_string(),
@@ -383,6 +504,78 @@ mod tests {
         assert_eq!(expected_table_config, actual_table_config);
     }
 
+    #[test]
+    #[cfg(feature = "storage-azure")]
+    fn test_table_config_from_valid_json_with_azure() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "skip_index_merge": true
+                },
+                "iceberg": {
+                    "storage_config": {
+                        "azblob": {
+                            "account_name": "moonlinkaccount",
+                            "container": "moonlink",
+                            "account_key": "account-key"
+                        }
+                    }
+                },
+                "wal": {
+                    "storage_config": {
+                        "azblob": {
+                            "account_name": "moonlinkaccount",
+                            "container": "moonlink-wal",
+                            "sas_token": "sas-token-wal"
+                        }
+                    }
+                }
+            }
+        "#;
+
+        // Deserialize and check.
+        let actual_table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        let expected_table_config = TableConfig {
+            mooncake_config: MooncakeConfig {
+                skip_index_merge: true,
+                skip_data_compaction: false,
+                append_only: None,
+                row_identity: None,
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: false,
+                source_filter: SourceFilterConfig::default(),
+            },
+            format: TableFormat::Iceberg,
+            iceberg_config: Some(AccessorConfig::new_with_storage_config(
+                moonlink::StorageConfig::Azblob {
+                    account_name: "moonlinkaccount".to_string(),
+                    container: "moonlink".to_string(),
+                    account_key: Some("account-key".to_string()),
+                    sas_token: None,
+                    endpoint: None,
+                },
+            )),
+            wal_config: Some(AccessorConfig::new_with_storage_config(
+                moonlink::StorageConfig::Azblob {
+                    account_name: "moonlinkaccount".to_string(),
+                    container: "moonlink-wal".to_string(),
+                    account_key: None,
+                    sas_token: Some("sas-token-wal".to_string()),
+                    endpoint: None,
+                },
+            )),
+        };
+        assert_eq!(expected_table_config, actual_table_config);
+    }
+
     #[test]
     fn test_table_config_with_append_only() {
         let serialized = r#"
@@ -408,7 +601,15 @@ mod tests {
                 skip_data_compaction: true,
                 append_only: Some(true),
                 row_identity: Some(IdentityProp::None),
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: false,
+                source_filter: SourceFilterConfig::default(),
             },
+            format: TableFormat::Iceberg,
             iceberg_config: Some(AccessorConfig::new_with_storage_config(
                 moonlink::StorageConfig::FileSystem {
                     root_directory: "/tmp/path".to_string(),
@@ -424,4 +625,381 @@ mod tests {
         };
         assert_eq!(expected_table_config, actual_table_config);
     }
+
+    #[test]
+    fn test_table_config_with_partition_spec() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "partition_spec": [
+                        { "source_column": "created_at", "transform": "day" },
+                        { "source_column": "tenant_id", "transform": "bucket[16]" },
+                        { "source_column": "region", "transform": "identity", "name": "region_part" }
+                    ]
+                }
+            }
+        "#;
+
+        // Deserialize and check.
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        let partition_field_names = table_config
+            .mooncake_config
+            .partition_spec
+            .iter()
+            .map(|field| field.partition_field_name())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            partition_field_names,
+            vec!["created_at_day", "tenant_id_bucket", "region_part"]
+        );
+
+        // Partition spec is carried over to mooncake table config.
+        let expected_partition_spec = table_config.mooncake_config.partition_spec.clone();
+        let mooncake_table_config = table_config
+            .mooncake_config
+            .take_as_mooncake_table_config(/*temp_files_dir=*/ "/tmp/path".to_string())
+            .unwrap();
+        assert_eq!(
+            mooncake_table_config.partition_spec,
+            expected_partition_spec
+        );
+    }
+
+    #[test]
+    fn test_table_config_with_replica_identity_full() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": false,
+                    "row_identity": "FullRow",
+                    "replica_identity_full": true
+                }
+            }
+        "#;
+
+        // Deserialize and check.
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert!(table_config.mooncake_config.replica_identity_full);
+
+        // Replica identity fallback is carried over to mooncake table config.
+        let mooncake_table_config = table_config
+            .mooncake_config
+            .take_as_mooncake_table_config(/*temp_files_dir=*/ "/tmp/path".to_string())
+            .unwrap();
+        assert!(mooncake_table_config.replica_identity_full);
+    }
+
+    #[test]
+    fn test_table_config_with_source_filter() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": false,
+                    "row_identity": "FullRow",
+                    "source_filter": {
+                        "column_filter": { "exclude": ["email", "ssn"] },
+                        "row_filters": [
+                            { "column": "tenant_id", "operator": "not_in", "values": ["1", "2"] }
+                        ]
+                    }
+                }
+            }
+        "#;
+
+        // Deserialize and check.
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        let expected_source_filter = SourceFilterConfig {
+            column_filter: Some(ColumnFilter::Exclude(vec![
+                "email".to_string(),
+                "ssn".to_string(),
+            ])),
+            row_filters: vec![RowFilter {
+                column: "tenant_id".to_string(),
+                operator: RowFilterOperator::NotIn,
+                values: vec!["1".to_string(), "2".to_string()],
+            }],
+        };
+        assert_eq!(
+            table_config.mooncake_config.source_filter,
+            expected_source_filter
+        );
+
+        // Source filter is carried over to mooncake table config.
+        let mooncake_table_config = table_config
+            .mooncake_config
+            .clone()
+            .take_as_mooncake_table_config(/*temp_files_dir=*/ "/tmp/path".to_string())
+            .unwrap();
+        assert_eq!(
+            mooncake_table_config.source_filter_config,
+            expected_source_filter
+        );
+
+        // Row filter without values is rejected.
+        let mut mooncake_config = table_config.mooncake_config;
+        mooncake_config.source_filter.row_filters[0].values.clear();
+        assert!(!mooncake_config.is_valid());
+    }
+
+    #[test]
+    fn test_table_config_with_snapshot_retention() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "snapshot_retention": {
+                        "max_snapshots_to_keep": 5,
+                        "orphan_file_grace_period_ms": 86400000
+                    }
+                }
+            }
+        "#;
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert!(table_config.is_valid());
+        assert_eq!(
+            table_config
+                .mooncake_config
+                .snapshot_retention
+                .orphan_file_grace_period_ms,
+            Some(86400000)
+        );
+
+        // At least one snapshot should be retained.
+        let mut mooncake_config = table_config.mooncake_config.clone();
+        mooncake_config.snapshot_retention.max_snapshots_to_keep = Some(0);
+        assert!(!mooncake_config.is_valid());
+
+        // Orphan files need a grace period, so files being written are not deleted.
+        let mut mooncake_config = table_config.mooncake_config;
+        mooncake_config
+            .snapshot_retention
+            .orphan_file_grace_period_ms = Some(0);
+        assert!(!mooncake_config.is_valid());
+    }
+
+    #[test]
+    fn test_table_config_with_sort_order() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "sort_order": [
+                        { "source_column": "tenant_id" },
+                        { "source_column": "created_at", "direction": "desc", "null_order": "nulls-last" }
+                    ]
+                }
+            }
+        "#;
+
+        // Deserialize and check, direction and nulls order default to ascending and nulls first.
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert_eq!(
+            table_config.mooncake_config.sort_order,
+            vec![
+                SortFieldConfig::new("tenant_id", SortDirection::Ascending, NullOrder::First),
+                SortFieldConfig::new("created_at", SortDirection::Descending, NullOrder::Last),
+            ]
+        );
+
+        // Sort order is carried over to mooncake table config.
+        let expected_sort_order = table_config.mooncake_config.sort_order.clone();
+        let mooncake_table_config = table_config
+            .mooncake_config
+            .take_as_mooncake_table_config(/*temp_files_dir=*/ "/tmp/path".to_string())
+            .unwrap();
+        assert_eq!(mooncake_table_config.sort_order, expected_sort_order);
+    }
+
+    #[test]
+    fn test_table_config_with_parquet_writer() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "parquet_writer": {
+                        "compression": "zstd",
+                        "compression_level": 3,
+                        "max_row_group_size": 8192,
+                        "column_configs": {
+                            "id": { "bloom_filter_enabled": true, "bloom_filter_fpp": 0.01 }
+                        }
+                    }
+                }
+            }
+        "#;
+
+        // Deserialize and check.
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert!(table_config.is_valid());
+        let parquet_writer_config = &table_config.mooncake_config.parquet_writer;
+        assert_eq!(
+            parquet_writer_config.compression,
+            Some(moonlink::ParquetCompressionCodec::Zstd)
+        );
+        assert_eq!(parquet_writer_config.compression_level, Some(3));
+        assert_eq!(parquet_writer_config.max_row_group_size, Some(8192));
+        let id_column_config = parquet_writer_config.column_configs.get("id").unwrap();
+        assert_eq!(id_column_config.bloom_filter_enabled, Some(true));
+        assert_eq!(id_column_config.bloom_filter_fpp, Some(0.01));
+
+        // Parquet writer config is carried over to mooncake table config.
+        let expected_parquet_writer_config = parquet_writer_config.clone();
+        let mooncake_table_config = table_config
+            .mooncake_config
+            .take_as_mooncake_table_config(/*temp_files_dir=*/ "/tmp/path".to_string())
+            .unwrap();
+        assert_eq!(
+            mooncake_table_config.parquet_writer_config,
+            expected_parquet_writer_config
+        );
+    }
+
+    #[test]
+    fn test_table_config_with_invalid_parquet_writer() {
+        // Snappy doesn't take compression level.
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "parquet_writer": {
+                        "compression": "snappy",
+                        "compression_level": 3
+                    }
+                }
+            }
+        "#;
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert!(!table_config.is_valid());
+        assert!(table_config
+            .mooncake_config
+            .take_as_mooncake_table_config(/*temp_files_dir=*/ "/tmp/path".to_string())
+            .is_err());
+    }
+
+    #[test]
+    fn test_table_config_with_delta_format() {
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None"
+                },
+                "format": "delta"
+            }
+        "#;
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert_eq!(table_config.format, TableFormat::Delta);
+
+        // Delta table shares storage config with iceberg table.
+        let mooncake_table_id = MooncakeTableId {
+            database: "db".to_string(),
+            table: "tbl".to_string(),
+        };
+        let moonlink_table_config = table_config
+            .take_as_moonlink_config(
+                /*temp_files_dir=*/ "/tmp/path".to_string(),
+                &mooncake_table_id,
+            )
+            .unwrap();
+        assert_eq!(
+            moonlink_table_config.deltalake_table_config.unwrap(),
+            DeltalakeTableConfig {
+                table_name: "tbl".to_string(),
+                location: "/tmp/path/db/tbl".to_string(),
+                data_accessor_config: moonlink_table_config
+                    .iceberg_table_config
+                    .data_accessor_config
+                    .clone(),
+            }
+        );
+
+        // Count based snapshot retention is not supported by delta tables.
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "snapshot_retention": {
+                        "max_snapshots_to_keep": 1
+                    }
+                },
+                "format": "delta"
+            }
+        "#;
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert!(!table_config.is_valid());
+
+        // Encryption is not supported by delta tables.
+        let serialized = r#"
+            {
+                "mooncake": {
+                    "append_only": true,
+                    "row_identity": "None",
+                    "encryption": {
+                        "master_key_id": "key-1",
+                        "key_provider": {
+                            "local_file": {
+                                "directory": "/tmp/moonlink_keys"
+                            }
+                        }
+                    }
+                },
+                "format": "delta"
+            }
+        "#;
+        let table_config = TableConfig::from_json_or_default(
+            serialized,
+            /*default_table_directory=*/ "/tmp/path",
+        )
+        .unwrap();
+        assert!(!table_config.is_valid());
+
+        // Iceberg is the default format.
+        let table_config =
+            TableConfig::from_json_or_default("{}", /*default_table_directory=*/ "/tmp/path")
+                .unwrap();
+        assert_eq!(table_config.format, TableFormat::Iceberg);
+    }
 }
@@ -0,0 +1,530 @@
+//! Table scan which reads rows visible at a [`ReadState`], with deletion vectors and position deletes applied.
+//!
+//! Data files could be written with older table schemas, so columns are matched with current table schema by field id (or by name if field id is unassigned); missing columns are filled with nulls.
+use crate::error::{Error, Result};
+use crate::HttpFilepathRemap;
+
+use arrow::compute::kernels::cmp;
+use arrow::compute::{and, cast, filter_record_batch};
+use arrow_array::{
+    new_null_array, Array, ArrayRef, BooleanArray, RecordBatch, RecordBatchOptions, Scalar,
+    StringArray,
+};
+use arrow_schema::{Field, Schema, SchemaRef};
+use futures::stream::{self, BoxStream, StreamExt, TryStreamExt};
+use moonlink::{DataEncryptionKey, ReadState, TableEncryption};
+use moonlink_table_metadata::{DeletionVector, MooncakeTableMetadata, PositionDelete};
+use parquet::arrow::arrow_reader::{ArrowReaderOptions, RowSelection, RowSelector};
+use parquet::arrow::ParquetRecordBatchStreamBuilder;
+use roaring::RoaringTreemap;
+use std::io::SeekFrom;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+/// Arrow field metadata key for field id.
+const PARQUET_FIELD_ID_KEY: &str = "PARQUET:field_id";
+
+/// Comparison operators supported in scan predicates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComparisonOperator {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl ComparisonOperator {
+    /// Operator symbols, two-character ones are placed ahead so they're matched first.
+    const SYMBOLS: [(&'static str, ComparisonOperator); 6] = [
+        ("!=", ComparisonOperator::NotEq),
+        ("<=", ComparisonOperator::LtEq),
+        (">=", ComparisonOperator::GtEq),
+        ("=", ComparisonOperator::Eq),
+        ("<", ComparisonOperator::Lt),
+        (">", ComparisonOperator::Gt),
+    ];
+}
+
+/// Predicate which compares a column with a literal, literal is parsed based on the column type.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ScanPredicate {
+    pub column: String,
+    pub op: ComparisonOperator,
+    pub value: String,
+}
+
+impl FromStr for ScanPredicate {
+    type Err = Error;
+
+    /// Parse predicate in the form of `<column><op><value>`, for example, `id>=10`.
+    fn from_str(predicate: &str) -> Result<Self> {
+        let op_idx = predicate
+            .find(['!', '=', '<', '>'])
+            .ok_or_else(|| Error::invalid_argument(format!("Invalid predicate {predicate}")))?;
+        let (op_str, op) = ComparisonOperator::SYMBOLS
+            .iter()
+            .find(|(symbol, _)| predicate[op_idx..].starts_with(symbol))
+            .ok_or_else(|| Error::invalid_argument(format!("Invalid predicate {predicate}")))?;
+        let column = predicate[..op_idx].trim();
+        if column.is_empty() {
+            return Err(Error::invalid_argument(format!(
+                "Missing column in predicate {predicate}"
+            )));
+        }
+        Ok(Self {
+            column: column.to_string(),
+            op: *op,
+            value: predicate[op_idx + op_str.len()..].trim().to_string(),
+        })
+    }
+}
+
+/// Options for table scan.
+#[derive(Clone, Debug, Default)]
+pub struct ScanOptions {
+    /// Columns to return in the given order, all columns are returned if unassigned.
+    pub projection: Option<Vec<String>>,
+    /// Predicates which are combined with AND, rows which evaluate to null are filtered out.
+    pub predicates: Vec<ScanPredicate>,
+}
+
+/// Result for table scan.
+pub struct TableScan {
+    /// Schema for returned record batches, with projection applied.
+    pub schema: SchemaRef,
+    /// Record batches for visible rows, read state is kept alive until the stream is dropped.
+    pub stream: BoxStream<'static, Result<RecordBatch>>,
+}
+
+/// Predicate with literal casted to column type.
+struct CompiledPredicate {
+    column_idx: usize,
+    op: ComparisonOperator,
+    literal: Scalar<ArrayRef>,
+}
+
+impl CompiledPredicate {
+    fn try_new(schema: &Schema, predicate: &ScanPredicate) -> Result<Self> {
+        let (column_idx, field) = schema.column_with_name(&predicate.column).ok_or_else(|| {
+            Error::invalid_argument(format!(
+                "Predicate column {} doesn't exist in table",
+                predicate.column
+            ))
+        })?;
+        if field.data_type().is_nested() {
+            return Err(Error::invalid_argument(format!(
+                "Predicate column {} is not a primitive column",
+                predicate.column
+            )));
+        }
+        let literal = cast(
+            &StringArray::from(vec![predicate.value.as_str()]),
+            field.data_type(),
+        )
+        .map_err(|e| {
+            Error::invalid_argument(format!(
+                "Invalid literal {} for column {}: {e}",
+                predicate.value, predicate.column
+            ))
+        })?;
+        // Unparsable strings are casted to null.
+        if literal.is_null(0) {
+            return Err(Error::invalid_argument(format!(
+                "Invalid literal {} for column {} of type {}",
+                predicate.value,
+                predicate.column,
+                field.data_type()
+            )));
+        }
+        Ok(Self {
+            column_idx,
+            op: predicate.op,
+            literal: Scalar::new(literal),
+        })
+    }
+
+    fn evaluate(&self, record_batch: &RecordBatch) -> Result<BooleanArray> {
+        let column = record_batch.column(self.column_idx);
+        let result = match self.op {
+            ComparisonOperator::Eq => cmp::eq(column, &self.literal)?,
+            ComparisonOperator::NotEq => cmp::neq(column, &self.literal)?,
+            ComparisonOperator::Lt => cmp::lt(column, &self.literal)?,
+            ComparisonOperator::LtEq => cmp::lt_eq(column, &self.literal)?,
+            ComparisonOperator::Gt => cmp::gt(column, &self.literal)?,
+            ComparisonOperator::GtEq => cmp::gt_eq(column, &self.literal)?,
+        };
+        Ok(result)
+    }
+}
+
+/// Data file to read, along with its deleted rows.
+struct DataFileScan {
+    data_file: String,
+    deletion_vector: Option<(String, DeletionVector)>,
+    position_deletes: RoaringTreemap,
+}
+
+/// States shared by all data file reads within one scan.
+struct ScanContext {
+    /// Keep read state alive until scan completion, so data files are not deleted.
+    _read_state: Arc<ReadState>,
+    table_schema: SchemaRef,
+    projection: Vec<usize>,
+    predicates: Vec<CompiledPredicate>,
+    filepath_remap: HttpFilepathRemap,
+    /// Options to open data files with, which carry decryption properties for encrypted tables.
+    reader_options: ArrowReaderOptions,
+    /// Data key to decrypt deletion vector blobs with, if the table is encrypted.
+    data_key: Option<DataEncryptionKey>,
+}
+
+/// Get field id from arrow field metadata.
+fn get_field_id(field: &Field) -> Option<&String> {
+    field.metadata().get(PARQUET_FIELD_ID_KEY)
+}
+
+/// Load deleted rows from the deletion vector puffin blob, which is decrypted as a whole if [`data_key`] is assigned.
+async fn load_deletion_vector(
+    puffin_file: &str,
+    deletion_vector: &DeletionVector,
+    data_key: Option<&DataEncryptionKey>,
+) -> Result<RoaringTreemap> {
+    let mut file = tokio::fs::File::open(puffin_file).await.map_err(|e| {
+        Error::io(format!(
+            "Failed to open file {puffin_file} with error {e:?}"
+        ))
+    })?;
+    file.seek(SeekFrom::Start(deletion_vector.offset as u64))
+        .await?;
+    let mut blob = vec![0u8; deletion_vector.size as usize];
+    file.read_exact(&mut blob).await?;
+    if let Some(data_key) = data_key {
+        blob = data_key.decrypt(&blob)?;
+    }
+    // | 4-byte length | 4-byte magic | buffer | 4-byte CRC-32 |
+    if blob.len() < 12 {
+        return Err(Error::data_corruption(format!(
+            "Deletion vector blob in {puffin_file} has invalid size {}",
+            blob.len()
+        )));
+    }
+    Ok(RoaringTreemap::deserialize_from(&blob[8..blob.len() - 4])?)
+}
+
+/// Get row selection which skips deleted rows.
+fn get_row_selection(deleted_rows: &RoaringTreemap, num_rows: u64) -> RowSelection {
+    let mut selectors = vec![];
+    let mut next_row = 0;
+    for deleted_row in deleted_rows.iter() {
+        if deleted_row >= num_rows {
+            break;
+        }
+        if deleted_row > next_row {
+            selectors.push(RowSelector::select((deleted_row - next_row) as usize));
+        }
+        selectors.push(RowSelector::skip(1));
+        next_row = deleted_row + 1;
+    }
+    if next_row < num_rows {
+        selectors.push(RowSelector::select((num_rows - next_row) as usize));
+    }
+    RowSelection::from(selectors)
+}
+
+impl ScanContext {
+    /// Align record batch read from data file with the current table schema.
+    fn align_with_table_schema(&self, record_batch: RecordBatch) -> Result<RecordBatch> {
+        let file_schema = record_batch.schema();
+        let mut columns = Vec::with_capacity(self.table_schema.fields().len());
+        for table_field in self.table_schema.fields().iter() {
+            let file_column_idx = match get_field_id(table_field) {
+                Some(field_id) => file_schema
+                    .fields()
+                    .iter()
+                    .position(|file_field| get_field_id(file_field) == Some(field_id)),
+                None => file_schema.index_of(table_field.name()).ok(),
+            };
+            let column = match file_column_idx {
+                Some(idx) if record_batch.column(idx).data_type() == table_field.data_type() => {
+                    record_batch.column(idx).clone()
+                }
+                Some(idx) => cast(record_batch.column(idx), table_field.data_type())?,
+                None => new_null_array(table_field.data_type(), record_batch.num_rows()),
+            };
+            columns.push(column);
+        }
+        let options = RecordBatchOptions::new().with_row_count(Some(record_batch.num_rows()));
+        Ok(RecordBatch::try_new_with_options(
+            self.table_schema.clone(),
+            columns,
+            &options,
+        )?)
+    }
+
+    /// Apply predicates and projection to the record batch.
+    fn process_record_batch(&self, record_batch: RecordBatch) -> Result<RecordBatch> {
+        let mut record_batch = self.align_with_table_schema(record_batch)?;
+        let mut selection: Option<BooleanArray> = None;
+        for cur_predicate in self.predicates.iter() {
+            let cur_selection = cur_predicate.evaluate(&record_batch)?;
+            selection = Some(match selection {
+                Some(prev_selection) => and(&prev_selection, &cur_selection)?,
+                None => cur_selection,
+            });
+        }
+        if let Some(selection) = selection {
+            record_batch = filter_record_batch(&record_batch, &selection)?;
+        }
+        Ok(record_batch.project(&self.projection)?)
+    }
+
+    /// Read the given data file, with deleted rows skipped.
+    async fn read_data_file(
+        self: Arc<Self>,
+        data_file_scan: DataFileScan,
+    ) -> Result<BoxStream<'static, Result<RecordBatch>>> {
+        let DataFileScan {
+            data_file,
+            deletion_vector,
+            mut position_deletes,
+        } = data_file_scan;
+        if let Some((puffin_file, deletion_vector)) = deletion_vector {
+            let puffin_file = (self.filepath_remap)(puffin_file);
+            position_deletes |=
+                load_deletion_vector(&puffin_file, &deletion_vector, self.data_key.as_ref())
+                    .await?;
+        }
+
+        let data_file = (self.filepath_remap)(data_file);
+        let file = tokio::fs::File::open(&data_file)
+            .await
+            .map_err(|e| Error::io(format!("Failed to open file {data_file} with error {e:?}")))?;
+        let mut builder =
+            ParquetRecordBatchStreamBuilder::new_with_options(file, self.reader_options.clone())
+                .await?;
+        if !position_deletes.is_empty() {
+            let num_rows = builder.metadata().file_metadata().num_rows() as u64;
+            builder = builder.with_row_selection(get_row_selection(&position_deletes, num_rows));
+        }
+        let stream = builder
+            .build()?
+            .map(move |record_batch| self.process_record_batch(record_batch?))
+            // Skip record batches with all rows filtered out.
+            .try_filter(|record_batch| std::future::ready(record_batch.num_rows() > 0))
+            .boxed();
+        Ok(stream)
+    }
+}
+
+/// Get data files to read from the serialized read state.
+fn get_data_file_scans(read_state: &ReadState) -> Result<Vec<DataFileScan>> {
+    let (metadata, _): (MooncakeTableMetadata, usize) =
+        bincode::decode_from_slice(&read_state.data, bincode::config::standard()).map_err(|e| {
+            Error::data_corruption(format!("Failed to decode read state with error {e:?}"))
+        })?;
+    let MooncakeTableMetadata {
+        data_files,
+        puffin_files,
+        deletion_vectors,
+        position_deletes,
+        // Scan happens in-process, so data key is taken from table encryption instead of unwrapping it again.
+        data_key_envelope: _,
+    } = metadata;
+    let mut data_file_scans = data_files
+        .into_iter()
+        .map(|data_file| DataFileScan {
+            data_file,
+            deletion_vector: None,
+            position_deletes: RoaringTreemap::new(),
+        })
+        .collect::<Vec<_>>();
+    for deletion_vector in deletion_vectors.into_iter() {
+        let puffin_file = puffin_files[deletion_vector.puffin_file_number as usize].clone();
+        data_file_scans[deletion_vector.data_file_number as usize].deletion_vector =
+            Some((puffin_file, deletion_vector));
+    }
+    for PositionDelete {
+        data_file_number,
+        data_file_row_number,
+    } in position_deletes.into_iter()
+    {
+        data_file_scans[data_file_number as usize]
+            .position_deletes
+            .insert(data_file_row_number as u64);
+    }
+    Ok(data_file_scans)
+}
+
+/// Scan rows visible at the given read state, data files are read lazily when the result stream is polled.
+pub(crate) fn scan_read_state(
+    read_state: Arc<ReadState>,
+    table_schema: SchemaRef,
+    options: ScanOptions,
+    filepath_remap: HttpFilepathRemap,
+    table_encryption: Option<Arc<TableEncryption>>,
+) -> Result<TableScan> {
+    let projection = match &options.projection {
+        Some(columns) => columns
+            .iter()
+            .map(|column| {
+                table_schema.index_of(column).map_err(|_| {
+                    Error::invalid_argument(format!("Column {column} doesn't exist in table"))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?,
+        None => (0..table_schema.fields().len()).collect(),
+    };
+    let predicates = options
+        .predicates
+        .iter()
+        .map(|predicate| CompiledPredicate::try_new(&table_schema, predicate))
+        .collect::<Result<Vec<_>>>()?;
+    let schema = Arc::new(table_schema.project(&projection)?);
+    let data_file_scans = get_data_file_scans(&read_state)?;
+    let data_key = table_encryption.map(|table_encryption| table_encryption.get_data_key().clone());
+    let mut reader_options = ArrowReaderOptions::new();
+    if let Some(data_key) = &data_key {
+        reader_options = reader_options
+            .with_file_decryption_properties(data_key.get_file_decryption_properties());
+    }
+
+    let context = Arc::new(ScanContext {
+        _read_state: read_state,
+        table_schema,
+        projection,
+        predicates,
+        filepath_remap,
+        reader_options,
+        data_key,
+    });
+    let stream = stream::iter(data_file_scans)
+        .then(move |data_file_scan| context.clone().read_data_file(data_file_scan))
+        .try_flatten()
+        .boxed();
+    Ok(TableScan { schema, stream })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_scan_predicate() {
+        let predicate = ScanPredicate::from_str("id>=10").unwrap();
+        assert_eq!(
+            predicate,
+            ScanPredicate {
+                column: "id".to_string(),
+                op: ComparisonOperator::GtEq,
+                value: "10".to_string(),
+            }
+        );
+        let predicate = ScanPredicate::from_str(" name != alice ").unwrap();
+        assert_eq!(predicate.column, "name");
+        assert_eq!(predicate.op, ComparisonOperator::NotEq);
+        assert_eq!(predicate.value, "alice");
+        // Only the first operator is parsed, the rest belongs to the literal.
+        let predicate = ScanPredicate::from_str("expr=a<b").unwrap();
+        assert_eq!(predicate.op, ComparisonOperator::Eq);
+        assert_eq!(predicate.value, "a<b");
+
+        assert!(ScanPredicate::from_str("id").is_err());
+        assert!(ScanPredicate::from_str(">=10").is_err());
+        assert!(ScanPredicate::from_str("id!10").is_err());
+    }
+
+    #[test]
+    fn test_get_row_selection() {
+        let deleted_rows = RoaringTreemap::from_iter([0, 3, 4, 9]);
+        let selection = get_row_selection(&deleted_rows, /*num_rows=*/ 8);
+        assert_eq!(
+            Vec::<RowSelector>::from(selection),
+            vec![
+                RowSelector::skip(1),
+                RowSelector::select(2),
+                RowSelector::skip(2),
+                RowSelector::select(3),
+            ]
+        );
+    }
+
+    /// Dropping read state spawns cleanup task, so tokio runtime is required.
+    #[tokio::test]
+    async fn test_align_with_table_schema() {
+        use arrow_array::{Int32Array, Int64Array};
+        use std::collections::HashMap;
+
+        let field_with_id = |name: &str, data_type, field_id: i32| {
+            Field::new(name, data_type, /*nullable=*/ true).with_metadata(HashMap::from([(
+                PARQUET_FIELD_ID_KEY.to_string(),
+                field_id.to_string(),
+            )]))
+        };
+        // Column "id" is promoted to int64, "value" is renamed to "amount", and "note" is added.
+        let file_schema = Arc::new(Schema::new(vec![
+            field_with_id("id", arrow_schema::DataType::Int32, 0),
+            field_with_id("value", arrow_schema::DataType::Int32, 1),
+        ]));
+        let table_schema = Arc::new(Schema::new(vec![
+            field_with_id("amount", arrow_schema::DataType::Int32, 1),
+            field_with_id("id", arrow_schema::DataType::Int64, 0),
+            field_with_id("note", arrow_schema::DataType::Utf8, 2),
+        ]));
+        let context = ScanContext {
+            _read_state: Arc::new(ReadState::new(
+                /*data_files=*/ vec![],
+                /*puffin_cache_handles=*/ vec![],
+                /*deletion_vectors_at_read=*/ vec![],
+                /*position_deletes=*/ vec![],
+                /*associated_files=*/ vec![],
+                /*cache_handles=*/ vec![],
+                Arc::new(|path: String| path),
+                /*data_key_envelope=*/ None,
+            )),
+            table_schema: table_schema.clone(),
+            projection: vec![1, 0],
+            predicates: vec![CompiledPredicate::try_new(
+                &table_schema,
+                &ScanPredicate::from_str("amount>15").unwrap(),
+            )
+            .unwrap()],
+            filepath_remap: Arc::new(|path: String| path),
+            reader_options: ArrowReaderOptions::new(),
+            data_key: None,
+        };
+        let record_batch = RecordBatch::try_new(
+            file_schema,
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Int32Array::from(vec![10, 20, 30])),
+            ],
+        )
+        .unwrap();
+        let aligned = context
+            .align_with_table_schema(record_batch.clone())
+            .unwrap();
+        assert_eq!(aligned.schema(), table_schema);
+        assert_eq!(aligned.column(2).null_count(), 3);
+
+        let processed = context.process_record_batch(record_batch).unwrap();
+        assert_eq!(processed.num_columns(), 2);
+        assert_eq!(
+            processed.column(0).as_ref(),
+            &Int64Array::from(vec![2, 3]) as &dyn Array
+        );
+        assert_eq!(
+            processed.column(1).as_ref(),
+            &Int32Array::from(vec![20, 30]) as &dyn Array
+        );
+
+        // Invalid literal for column type.
+        assert!(CompiledPredicate::try_new(
+            &table_schema,
+            &ScanPredicate::from_str("id=abc").unwrap()
+        )
+        .is_err());
+    }
+}
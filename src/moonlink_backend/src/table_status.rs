@@ -15,4 +15,6 @@ pub struct TableStatus {
     pub cardinality: u64,
     /// Iceberg warehouse location.
     pub iceberg_warehouse_location: String,
+    /// Estimated memory usage in bytes for rows buffered in memory.
+    pub memory_usage_bytes: u64,
 }
@@ -1,6 +1,6 @@
 use arrow_array::Int64Array;
 use moonlink::row::IdentityProp;
-use moonlink_backend::table_config::{MooncakeConfig, TableConfig};
+use moonlink_backend::table_config::{MooncakeConfig, TableConfig, TableFormat};
 use moonlink_metadata_store::SqliteMetadataStore;
 use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
 use std::collections::HashMap;
@@ -11,7 +11,10 @@ use tokio_postgres::{connect, types::PgLsn, Client};
 
 use std::{collections::HashSet, fs::File};
 
-use moonlink::{decode_read_state_for_testing, AccessorConfig, StorageConfig};
+use moonlink::{
+    decode_read_state_for_testing, AccessorConfig, ParquetWriterConfig, SnapshotRetentionConfig,
+    SourceFilterConfig, StorageConfig,
+};
 use moonlink_backend::file_utils::{recreate_directory, DEFAULT_MOONLINK_TEMP_FILE_PATH};
 use moonlink_backend::{MoonlinkBackend, ReadState};
 use moonlink_table_metadata::PositionDelete;
@@ -78,7 +81,15 @@ impl TestGuard {
                 skip_data_compaction: true,
                 append_only: Some(false),
                 row_identity: Some(IdentityProp::FullRow),
+                partition_spec: vec![],
+                sort_order: vec![],
+                snapshot_retention: SnapshotRetentionConfig::default(),
+                parquet_writer: ParquetWriterConfig::default(),
+                encryption: None,
+                replica_identity_full: true,
+                source_filter: SourceFilterConfig::default(),
             },
+            format: TableFormat::Iceberg,
             iceberg_config: Some(AccessorConfig::new_with_storage_config(
                 StorageConfig::FileSystem {
                     root_directory: root_directory.clone(),
@@ -448,7 +459,15 @@ pub fn get_serialized_table_config(tmp_dir: &TempDir) -> String {
             skip_data_compaction: true,
             append_only: Some(false),
             row_identity: Some(IdentityProp::FullRow),
+            partition_spec: vec![],
+            sort_order: vec![],
+            snapshot_retention: SnapshotRetentionConfig::default(),
+            parquet_writer: ParquetWriterConfig::default(),
+            encryption: None,
+            replica_identity_full: true,
+            source_filter: SourceFilterConfig::default(),
         },
+        format: TableFormat::Iceberg,
         iceberg_config: Some(AccessorConfig::new_with_storage_config(
             StorageConfig::FileSystem {
                 root_directory: root_directory.clone(),
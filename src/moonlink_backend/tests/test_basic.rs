@@ -8,9 +8,23 @@ mod tests {
         current_wal_lsn, get_database_uri, smoke_create_and_insert, TestGuard, DATABASE, TABLE,
     };
     use moonlink_backend::table_status::TableStatus;
+    use moonlink_backend::MoonlinkBackend;
 
     use serial_test::serial;
     use std::collections::HashSet;
+    use std::time::Duration;
+
+    /// Block wait until the table memory usage reported to backend satisfies the given condition.
+    /// Memory usage is reported by table handler asynchronously.
+    async fn wait_for_memory_usage(backend: &MoonlinkBackend, condition: impl Fn(u64) -> bool) {
+        tokio::time::timeout(Duration::from_secs(30), async {
+            while !condition(backend.list_tables().await.unwrap()[0].memory_usage_bytes) {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timeout waiting for table memory usage")
+    }
 
     /// Validate `create_table` and `drop_table` across successive uses.
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
@@ -159,12 +173,18 @@ mod tests {
             .await
             .unwrap();
 
+        // Committed rows stay in memory until flushed.
+        wait_for_memory_usage(backend, |memory_usage_bytes| memory_usage_bytes > 0).await;
+
         // After all changes reflected at mooncake snapshot, trigger an iceberg snapshot.
         backend
             .create_snapshot(DATABASE.to_string(), TABLE.to_string(), lsn)
             .await
             .unwrap();
 
+        // Iceberg snapshot flushes all rows to disk, which releases the memory.
+        wait_for_memory_usage(backend, |memory_usage_bytes| memory_usage_bytes == 0).await;
+
         // Look for any file in the Iceberg metadata dir.
         let meta_dir = guard
             .tmp()
@@ -185,6 +205,7 @@ mod tests {
             flush_lsn: Some(lsn),
             cardinality: 1,
             iceberg_warehouse_location: guard.tmp().unwrap().path().to_str().unwrap().to_string(),
+            memory_usage_bytes: 0,
         };
         assert_eq!(table_statuses, vec![expected_table_status]);
     }
@@ -42,7 +42,9 @@ mod tests {
     async fn test_run_control_query_reconnects_after_terminate() {
         let uri = get_database_uri();
         // Build a dedicated PostgresConnection (control-plane)
-        let mut conn = PostgresConnection::new(uri.clone()).await.unwrap();
+        let mut conn = PostgresConnection::new(uri.clone(), moonlink::MemoryAccountant::default())
+            .await
+            .unwrap();
 
         // Baseline: SELECT 1 works
         let _ = conn.run_control_query("SELECT 1;").await.unwrap();
@@ -77,7 +79,9 @@ mod tests {
             .unwrap();
 
         // Build a dedicated PostgresConnection
-        let mut conn = PostgresConnection::new(uri.clone()).await.unwrap();
+        let mut conn = PostgresConnection::new(uri.clone(), moonlink::MemoryAccountant::default())
+            .await
+            .unwrap();
 
         // Kill control-plane backend
         let pid = get_control_pid(&mut conn).await;
@@ -97,7 +101,9 @@ mod tests {
     #[serial]
     async fn test_drop_replication_slot_after_terminate() {
         let uri = get_database_uri();
-        let mut conn = PostgresConnection::new(uri.clone()).await.unwrap();
+        let mut conn = PostgresConnection::new(uri.clone(), moonlink::MemoryAccountant::default())
+            .await
+            .unwrap();
 
         // Kill control-plane backend to force reconnect on next call
         let pid = get_control_pid(&mut conn).await;
@@ -116,7 +122,9 @@ mod tests {
     #[serial]
     async fn test_run_control_query_non_transport_error() {
         let uri = get_database_uri();
-        let mut conn = PostgresConnection::new(uri).await.unwrap();
+        let mut conn = PostgresConnection::new(uri, moonlink::MemoryAccountant::default())
+            .await
+            .unwrap();
         let err = conn.run_control_query("THIS IS NOT SQL").await.err();
         assert!(err.is_some());
     }
@@ -140,7 +148,9 @@ mod tests {
             .unwrap();
 
         // Create a PostgresConnection (will set lock_timeout too)
-        let mut conn = PostgresConnection::new(uri.clone()).await.unwrap();
+        let mut conn = PostgresConnection::new(uri.clone(), moonlink::MemoryAccountant::default())
+            .await
+            .unwrap();
 
         // Kill control-plane backend to force a transport error on the first simple_query
         let pid = get_control_pid(&mut conn).await;
@@ -175,7 +185,9 @@ mod tests {
     async fn test_run_control_query_backoff_virtual_time() {
         let uri = get_database_uri();
         // Build a control-plane connection
-        let mut conn = PostgresConnection::new(uri.clone()).await.unwrap();
+        let mut conn = PostgresConnection::new(uri.clone(), moonlink::MemoryAccountant::default())
+            .await
+            .unwrap();
         let _ = conn.run_control_query("SELECT 1;").await.unwrap();
 
         // Induce transport error for the first attempt
@@ -13,6 +13,7 @@ mod tests {
     };
     use moonlink_connectors::pg_replicate::postgres_source::PostgresSource;
     use moonlink_connectors::pg_replicate::table::TableName;
+    use moonlink_connectors::pg_replicate::table_filter::TableFilter;
     use serial_test::serial;
     use std::time::{SystemTime, UNIX_EPOCH};
     use tempfile::tempdir;
@@ -271,6 +272,7 @@ mod tests {
                 uri.clone(),
                 snapshot_id.clone(),
                 schema.clone(),
+                TableFilter::default(),
                 "id % 2 = 0".to_string(),
                 tx,
                 1024,
@@ -352,9 +354,15 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
 
         // Run initial copy directly
-        let _progress = copy_table_stream(schema.clone(), &tx, base_path, ic_cfg)
-            .await
-            .expect("copy_table_stream");
+        let _progress = copy_table_stream(
+            schema.clone(),
+            TableFilter::default(),
+            &tx,
+            base_path,
+            ic_cfg,
+        )
+        .await
+        .expect("copy_table_stream");
 
         // Expect LoadFiles event and verify root_directory
         if let Some(TableEvent::LoadFiles {
@@ -445,9 +453,15 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
 
         // Run initial copy and expect failure due to writer error
-        let err = copy_table_stream(schema.clone(), &tx, base_path.to_str().unwrap(), ic_cfg)
-            .await
-            .expect_err("expected failure");
+        let err = copy_table_stream(
+            schema.clone(),
+            TableFilter::default(),
+            &tx,
+            base_path.to_str().unwrap(),
+            ic_cfg,
+        )
+        .await
+        .expect_err("expected failure");
         let s = err.to_string().to_lowercase();
         assert!(
             s.contains("io") || s.contains("parquet") || s.contains("create") || s.contains("file")
@@ -512,9 +526,15 @@ mod tests {
         };
 
         let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
-        let _progress = copy_table_stream(schema.clone(), &tx, base_path, ic_cfg)
-            .await
-            .expect("copy_table_stream");
+        let _progress = copy_table_stream(
+            schema.clone(),
+            TableFilter::default(),
+            &tx,
+            base_path,
+            ic_cfg,
+        )
+        .await
+        .expect("copy_table_stream");
 
         if let Some(TableEvent::LoadFiles { files, .. }) = rx.recv().await {
             assert!(
@@ -588,9 +608,15 @@ mod tests {
         };
 
         let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
-        let progress = copy_table_stream(schema.clone(), &tx, base_path, ic_cfg)
-            .await
-            .expect("copy_table_stream");
+        let progress = copy_table_stream(
+            schema.clone(),
+            TableFilter::default(),
+            &tx,
+            base_path,
+            ic_cfg,
+        )
+        .await
+        .expect("copy_table_stream");
 
         // Rows copied equals baseline
         assert_eq!(progress.rows_copied, baseline as u64);
@@ -690,6 +716,7 @@ mod tests {
                 uri.clone(),
                 snapshot_id.clone(),
                 schema.clone(),
+                TableFilter::default(),
                 poison_pred,
                 tx,
                 64,
@@ -827,6 +854,7 @@ mod tests {
                 uri.clone(),
                 snapshot_id.clone(),
                 schema.clone(),
+                TableFilter::default(),
                 preds,
                 tx,
                 8,
@@ -918,9 +946,15 @@ mod tests {
         };
 
         let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
-        let progress = copy_table_stream(schema.clone(), &tx, base_path, ic_cfg)
-            .await
-            .expect("copy_table_stream");
+        let progress = copy_table_stream(
+            schema.clone(),
+            TableFilter::default(),
+            &tx,
+            base_path,
+            ic_cfg,
+        )
+        .await
+        .expect("copy_table_stream");
 
         // Rows copied should match baseline
         assert_eq!(progress.rows_copied, baseline as u64);
@@ -998,7 +1032,14 @@ mod tests {
         let tx_clone = tx.clone();
         let base_path_string = base_path.to_string();
         let copy_handle = tokio::spawn(async move {
-            copy_table_stream(schema_clone, &tx_clone, &base_path_string, ic_cfg).await
+            copy_table_stream(
+                schema_clone,
+                TableFilter::default(),
+                &tx_clone,
+                &base_path_string,
+                ic_cfg,
+            )
+            .await
         });
 
         // Give readers a moment to start streaming
@@ -1075,9 +1116,12 @@ mod tests {
         assert!(pid_before.is_some() && pid_before.unwrap() > 0);
 
         // Construct PostgresConnection::new which should terminate the active backend
-        let _conn = moonlink_connectors::pg_replicate::PostgresConnection::new(uri.clone())
-            .await
-            .unwrap();
+        let _conn = moonlink_connectors::pg_replicate::PostgresConnection::new(
+            uri.clone(),
+            moonlink::MemoryAccountant::default(),
+        )
+        .await
+        .unwrap();
 
         // Poll until pid clears
         let mut pid_after = active_pid_for_slot(slot_name).await;
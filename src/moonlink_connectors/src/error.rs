@@ -63,6 +63,10 @@ pub enum Error {
     #[error("{0}")]
     RestPayloadConversion(ErrorStruct),
 
+    // REST source error: requested schema change is invalid for the table.
+    #[error("{0}")]
+    RestInvalidSchemaChange(ErrorStruct),
+
     // Parquet parse error.
     #[error("{0}")]
     ParquetError(ErrorStruct),
@@ -74,6 +78,10 @@ pub enum Error {
     // Schema building error
     #[error("{0}")]
     SchemaBuildError(ErrorStruct),
+
+    // Kafka source error.
+    #[error("{0}")]
+    Kafka(ErrorStruct),
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -119,6 +127,26 @@ impl Error {
         })
     }
 
+    #[track_caller]
+    pub fn kafka(err_msg: String, err: Option<Arc<anyhow::Error>>) -> Self {
+        Error::Kafka(ErrorStruct {
+            message: format!("Kafka source error: {err_msg}"),
+            status: ErrorStatus::Permanent,
+            source: err,
+            location: Some(Location::caller().to_string()),
+        })
+    }
+
+    #[track_caller]
+    pub fn rest_invalid_schema_change(err_msg: String) -> Self {
+        Error::RestInvalidSchemaChange(ErrorStruct {
+            message: format!("REST source error: invalid schema change: {err_msg}"),
+            status: ErrorStatus::Permanent,
+            source: None,
+            location: Some(Location::caller().to_string()),
+        })
+    }
+
     #[track_caller]
     pub fn rest_non_existent_table(id: SrcTableId) -> Self {
         Error::RestNonExistentTable(ErrorStruct {
@@ -231,6 +259,9 @@ impl<T: Send + Sync + 'static> From<tokio::sync::mpsc::error::SendError<T>> for
 impl From<RestSourceError> for Error {
     #[track_caller]
     fn from(source: RestSourceError) -> Self {
+        if let RestSourceError::InvalidSchemaChange(err_msg) = source {
+            return Error::rest_invalid_schema_change(err_msg);
+        }
         Error::RestSource(ErrorStruct {
             message: "rest source error".to_string(),
             status: ErrorStatus::Permanent,
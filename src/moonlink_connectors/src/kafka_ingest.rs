@@ -0,0 +1,711 @@
+pub mod in_memory_broker;
+pub mod kafka_consumer;
+pub mod kafka_source;
+#[cfg(feature = "connector-kafka")]
+pub mod rdkafka_consumer;
+
+use crate::kafka_ingest::kafka_consumer::KafkaConsumer;
+use crate::kafka_ingest::kafka_source::{lsn_to_next_offset, KafkaDecodeErrorPolicy, KafkaSource};
+use crate::rest_ingest::moonlink_rest_sink::{RestSink, TableStatus};
+use crate::rest_ingest::SrcTableId;
+use crate::{Error, Result};
+use apache_avro::schema::Schema as AvroSchema;
+use arrow_schema::Schema;
+use moonlink::CommitState;
+use moonlink::MemoryAccountant;
+use moonlink::ReplicationState;
+use moonlink::TableEvent;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, watch};
+use tracing::{debug, error, warn};
+
+/// URI prefix for kafka source, for example `kafka://broker1:9092,broker2:9092?group_id=moonlink&value_format=json&on_decode_error=skip`.
+pub const KAFKA_URI_PREFIX: &str = "kafka://";
+/// Default consumer group id, if not specified in URI.
+const DEFAULT_GROUP_ID: &str = "moonlink";
+/// Max number of messages to ingest for one poll.
+const MAX_MESSAGES_PER_POLL: usize = 1024;
+/// Interval to commit persisted offsets to consumer group.
+const OFFSET_COMMIT_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Encoding for kafka message values.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KafkaValueFormat {
+    #[default]
+    Json,
+    /// Raw avro datum written with table schema.
+    Avro,
+    /// Avro datum prefixed with confluent schema registry header.
+    ConfluentAvro,
+}
+
+/// Kafka connection config parsed from source URI.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KafkaConnectionConfig {
+    /// Comma separated list of brokers.
+    pub bootstrap_servers: String,
+    /// Consumer group which tracks committed offsets.
+    pub group_id: String,
+    pub value_format: KafkaValueFormat,
+    pub decode_error_policy: KafkaDecodeErrorPolicy,
+}
+
+impl KafkaConnectionConfig {
+    pub fn from_uri(uri: &str) -> Result<Self> {
+        let invalid_uri = |reason: &str| Error::kafka(format!("invalid uri {uri}: {reason}"), None);
+        let remaining = uri
+            .strip_prefix(KAFKA_URI_PREFIX)
+            .ok_or_else(|| invalid_uri("missing kafka:// prefix"))?;
+        let (bootstrap_servers, query) = match remaining.split_once('?') {
+            Some((bootstrap_servers, query)) => (bootstrap_servers, query),
+            None => (remaining, ""),
+        };
+        if bootstrap_servers.is_empty() {
+            return Err(invalid_uri("missing bootstrap servers"));
+        }
+
+        let mut config = Self {
+            bootstrap_servers: bootstrap_servers.to_string(),
+            group_id: DEFAULT_GROUP_ID.to_string(),
+            value_format: KafkaValueFormat::default(),
+            decode_error_policy: KafkaDecodeErrorPolicy::default(),
+        };
+        for param in query.split('&').filter(|param| !param.is_empty()) {
+            let (key, value) = param
+                .split_once('=')
+                .ok_or_else(|| invalid_uri("query parameter should be key=value"))?;
+            match key {
+                "group_id" => config.group_id = value.to_string(),
+                "value_format" => {
+                    config.value_format = match value {
+                        "json" => KafkaValueFormat::Json,
+                        "avro" => KafkaValueFormat::Avro,
+                        "confluent_avro" => KafkaValueFormat::ConfluentAvro,
+                        _ => return Err(invalid_uri(&format!("unknown value format {value}"))),
+                    }
+                }
+                "on_decode_error" => {
+                    config.decode_error_policy = match value {
+                        "stall" => KafkaDecodeErrorPolicy::Stall,
+                        "skip" => KafkaDecodeErrorPolicy::Skip,
+                        _ => {
+                            return Err(invalid_uri(&format!(
+                                "unknown decode error policy {value}"
+                            )))
+                        }
+                    }
+                }
+                _ => return Err(invalid_uri(&format!("unknown query parameter {key}"))),
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// Create a consumer which connects to kafka cluster.
+#[cfg(feature = "connector-kafka")]
+fn create_kafka_consumer(config: &KafkaConnectionConfig) -> Result<Box<dyn KafkaConsumer>> {
+    Ok(Box::new(rdkafka_consumer::RdKafkaConsumer::new(config)?))
+}
+
+#[cfg(not(feature = "connector-kafka"))]
+fn create_kafka_consumer(_config: &KafkaConnectionConfig) -> Result<Box<dyn KafkaConsumer>> {
+    Err(Error::kafka(
+        "kafka consumer requires feature connector-kafka".to_string(),
+        None,
+    ))
+}
+
+/// Commands for the kafka event loop.
+#[derive(Debug)]
+pub enum KafkaCommand {
+    AddTable {
+        topic: String,
+        src_table_id: SrcTableId,
+        schema: Arc<Schema>,
+        event_sender: mpsc::Sender<TableEvent>,
+        commit_lsn_tx: Arc<CommitState>,
+        flush_lsn_rx: watch::Receiver<u64>,
+        wal_flush_lsn_rx: watch::Receiver<u64>,
+        /// Replication state owned by the topic, since LSNs are derived from per-topic offsets.
+        replication_state: Arc<ReplicationState>,
+        /// Persist LSN, only assigned for tables to recovery; consumption resumes right after it.
+        persist_lsn: Option<u64>,
+        /// Used to notify whether the topic is valid and consumption has started.
+        result_tx: oneshot::Sender<Result<()>>,
+    },
+    SetAvroSchema {
+        topic: String,
+        /// Schema id in confluent wire format, if the schema comes from a schema registry.
+        schema_id: Option<u32>,
+        avro_schema: AvroSchema,
+        /// Used to notify whether the avro schema is valid and has been applied.
+        result_tx: oneshot::Sender<Result<()>>,
+    },
+    DropTable {
+        topic: String,
+        src_table_id: SrcTableId,
+    },
+    Shutdown,
+}
+
+/// Kafka connection, which consumes one topic for each table.
+pub struct KafkaConnection {
+    value_format: KafkaValueFormat,
+    decode_error_policy: KafkaDecodeErrorPolicy,
+    consumer: Option<Box<dyn KafkaConsumer>>,
+    cmd_tx: mpsc::Sender<KafkaCommand>,
+    cmd_rx: Option<mpsc::Receiver<KafkaCommand>>,
+    next_src_table_id_generator: AtomicU32,
+    /// Consumption pauses when memory usage exceeds hard limit.
+    memory_accountant: MemoryAccountant,
+}
+
+impl KafkaConnection {
+    pub async fn new(uri: &str, memory_accountant: MemoryAccountant) -> Result<Self> {
+        let config = KafkaConnectionConfig::from_uri(uri)?;
+        let consumer = create_kafka_consumer(&config)?;
+        Ok(Self::with_consumer(
+            config.value_format,
+            config.decode_error_policy,
+            consumer,
+            memory_accountant,
+        ))
+    }
+
+    /// Create a kafka connection with the given consumer, which is useful to run against in-process broker.
+    pub fn with_consumer(
+        value_format: KafkaValueFormat,
+        decode_error_policy: KafkaDecodeErrorPolicy,
+        consumer: Box<dyn KafkaConsumer>,
+        memory_accountant: MemoryAccountant,
+    ) -> Self {
+        let (cmd_tx, cmd_rx) = mpsc::channel(8);
+        Self {
+            value_format,
+            decode_error_policy,
+            consumer: Some(consumer),
+            cmd_tx,
+            cmd_rx: Some(cmd_rx),
+            next_src_table_id_generator: AtomicU32::new(1),
+            memory_accountant,
+        }
+    }
+
+    pub fn next_src_table_id(&self) -> SrcTableId {
+        self.next_src_table_id_generator
+            .fetch_add(1, Ordering::SeqCst)
+    }
+
+    async fn send_command(&self, command: KafkaCommand) -> Result<()> {
+        self.cmd_tx.send(command).await.map_err(|e| {
+            Error::kafka(
+                format!("Failed to send command: {e}"),
+                Some(Arc::new(e.into())),
+            )
+        })
+    }
+
+    /// Send command to event loop, and block wait until it gets processed.
+    async fn send_command_and_wait(
+        &self,
+        command: KafkaCommand,
+        result_rx: oneshot::Receiver<Result<()>>,
+    ) -> Result<()> {
+        self.send_command(command).await?;
+        result_rx.await.map_err(|e| {
+            Error::kafka(
+                format!("Failed to receive command result: {e}"),
+                Some(Arc::new(e.into())),
+            )
+        })?
+    }
+
+    /// Add a table which ingests from the given topic, and block wait until consumption starts.
+    /// Single-partition topics resume right after persisted LSN at recovery; multi-partition topics resume from consumer group
+    /// offsets, which are committed after persistence, so messages persisted after the last offset commit are ingested again.
+    ///
+    /// # Arguments
+    ///
+    /// * replication_state: replication state owned by the topic, which is also used to build the table.
+    /// * persist_lsn: only assigned at recovery, used to resume consumption and update replication LSN.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_table(
+        &self,
+        topic: String,
+        src_table_id: SrcTableId,
+        schema: Arc<Schema>,
+        event_sender: mpsc::Sender<TableEvent>,
+        commit_lsn_tx: Arc<CommitState>,
+        flush_lsn_rx: watch::Receiver<u64>,
+        wal_flush_lsn_rx: watch::Receiver<u64>,
+        replication_state: Arc<ReplicationState>,
+        persist_lsn: Option<u64>,
+    ) -> Result<()> {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.send_command_and_wait(
+            KafkaCommand::AddTable {
+                topic,
+                src_table_id,
+                schema,
+                event_sender,
+                commit_lsn_tx,
+                flush_lsn_rx,
+                wal_flush_lsn_rx,
+                replication_state,
+                persist_lsn,
+                result_tx,
+            },
+            result_rx,
+        )
+        .await
+    }
+
+    /// Set Avro schema for an existing topic, and block wait until the schema gets applied.
+    pub async fn set_avro_schema(
+        &self,
+        topic: String,
+        schema_id: Option<u32>,
+        avro_schema: AvroSchema,
+    ) -> Result<()> {
+        let (result_tx, result_rx) = oneshot::channel();
+        self.send_command_and_wait(
+            KafkaCommand::SetAvroSchema {
+                topic,
+                schema_id,
+                avro_schema,
+                result_tx,
+            },
+            result_rx,
+        )
+        .await
+    }
+
+    /// Stop ingesting from the given topic (sends command to event loop)
+    pub async fn drop_table(&self, src_table_id: SrcTableId, topic: &str) -> Result<()> {
+        self.send_command(KafkaCommand::DropTable {
+            topic: topic.to_string(),
+            src_table_id,
+        })
+        .await
+    }
+
+    pub async fn shutdown_replication(&mut self) -> Result<()> {
+        self.send_command(KafkaCommand::Shutdown).await
+    }
+
+    /// Spawn kafka event loop task.
+    pub async fn spawn_kafka_task(&mut self) -> tokio::task::JoinHandle<Result<()>> {
+        let sink = RestSink::new();
+        let source = KafkaSource::new(self.value_format, self.decode_error_policy);
+        let consumer = self.consumer.take().unwrap();
+        let cmd_rx = self.cmd_rx.take().unwrap();
+        let over_hard_limit_rx = self.memory_accountant.subscribe_hard_limit();
+
+        tokio::spawn(async move {
+            run_kafka_event_loop(consumer, source, sink, cmd_rx, over_hard_limit_rx).await
+        })
+    }
+}
+
+/// Start consuming the given topic, and add it to kafka source and sink.
+#[allow(clippy::too_many_arguments)]
+async fn add_topic(
+    consumer: &mut dyn KafkaConsumer,
+    source: &mut KafkaSource,
+    sink: &mut RestSink,
+    topic: String,
+    src_table_id: SrcTableId,
+    schema: Arc<Schema>,
+    table_status: TableStatus,
+    flush_lsn_rx: watch::Receiver<u64>,
+    persist_lsn: Option<u64>,
+) -> Result<()> {
+    let partition_count = consumer.partition_count(&topic).await?;
+    let committed_offsets = consumer.committed_offsets(&topic, partition_count).await?;
+    let (start_offsets, lsn_base) = match persist_lsn {
+        // Offsets of a single-partition topic map to LSNs one-to-one, so consumption resumes right after the last iceberg persistence.
+        Some(persist_lsn) if partition_count == 1 => {
+            (BTreeMap::from([(0, lsn_to_next_offset(persist_lsn))]), 0)
+        }
+        // Otherwise start from consumer group offsets, and keep LSNs of re-consumed messages after persisted LSN.
+        _ => {
+            let start_offsets: BTreeMap<i32, u64> = (0..partition_count as i32)
+                .map(|partition| {
+                    let offset = committed_offsets.get(&partition).copied().unwrap_or(0);
+                    (partition, offset)
+                })
+                .collect();
+            let total_start_offset: u64 = start_offsets.values().sum();
+            let lsn_base = persist_lsn.map_or(0, |persist_lsn| {
+                persist_lsn.saturating_sub(total_start_offset)
+            });
+            (start_offsets, lsn_base)
+        }
+    };
+
+    sink.add_table(src_table_id, table_status, persist_lsn)?;
+    if let Err(e) = source.add_table(
+        topic.clone(),
+        src_table_id,
+        schema,
+        flush_lsn_rx,
+        start_offsets.clone(),
+        lsn_base,
+    ) {
+        sink.drop_table(src_table_id)?;
+        return Err(e);
+    }
+    if let Err(e) = consumer.assign(&topic, &start_offsets).await {
+        sink.drop_table(src_table_id)?;
+        source.remove_table(&topic)?;
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Kafka event loop, which ingests consumed messages and commits persisted offsets to consumer group.
+/// Failures to apply consumed messages to tables stop the event loop, since consumed offsets cannot be rewound.
+#[tracing::instrument(name = "kafka_event_loop", skip_all)]
+pub async fn run_kafka_event_loop(
+    mut consumer: Box<dyn KafkaConsumer>,
+    mut source: KafkaSource,
+    mut sink: RestSink,
+    mut cmd_rx: mpsc::Receiver<KafkaCommand>,
+    mut over_hard_limit_rx: watch::Receiver<bool>,
+) -> Result<()> {
+    let mut offset_commit_interval = tokio::time::interval(OFFSET_COMMIT_INTERVAL);
+
+    // For command and kafka client errors happen inside of the eventloop, we simply log and proceed.
+    loop {
+        tokio::select! {
+            cmd = cmd_rx.recv() => match cmd {
+                Some(KafkaCommand::AddTable { topic, src_table_id, schema, event_sender, commit_lsn_tx, flush_lsn_rx, wal_flush_lsn_rx, replication_state, persist_lsn, result_tx }) => {
+                    debug!("Adding kafka topic '{}' with src_table_id {}", topic, src_table_id);
+                    let table_status = TableStatus {
+                        _wal_flush_lsn_rx: wal_flush_lsn_rx,
+                        _flush_lsn_rx: flush_lsn_rx.clone(),
+                        event_sender,
+                        commit_lsn_tx,
+                        replication_state,
+                    };
+                    let result = add_topic(consumer.as_mut(), &mut source, &mut sink, topic.clone(), src_table_id, schema, table_status, flush_lsn_rx, persist_lsn).await;
+                    if let Err(e) = &result {
+                        error!("Add topic {topic} with id {src_table_id} failed: {e}");
+                    }
+                    // Requester could be gone, so no guarantee send success.
+                    let _ = result_tx.send(result);
+                }
+                Some(KafkaCommand::SetAvroSchema { topic, schema_id, avro_schema, result_tx }) => {
+                    debug!("Setting Avro schema for topic '{}'", topic);
+                    let result = match source.set_avro_schema(&topic, schema_id, avro_schema) {
+                        // Resume consumption from the message which failed to decode.
+                        Ok(Some(resume_offsets)) => consumer.assign(&topic, &resume_offsets).await,
+                        Ok(None) => Ok(()),
+                        Err(e) => Err(e),
+                    };
+                    if let Err(e) = &result {
+                        error!("Set avro schema for topic {topic} failed: {e}");
+                    }
+                    // Requester could be gone, so no guarantee send success.
+                    let _ = result_tx.send(result);
+                }
+                Some(KafkaCommand::DropTable { topic, src_table_id }) => {
+                    debug!("Dropping kafka topic '{}' with src_table_id {}", topic, src_table_id);
+                    if let Err(e) = consumer.unassign(&topic).await {
+                        error!("Unassign topic {topic} failed: {e}");
+                    }
+                    if let Err(e) = sink.drop_table(src_table_id) {
+                        error!("Drop topic {topic} with id {src_table_id} failed: {e}");
+                        continue;
+                    }
+                    if let Err(e) = source.remove_table(&topic) {
+                        error!("Remove topic {topic} failed: {e}");
+                        continue;
+                    }
+                }
+                Some(KafkaCommand::Shutdown) | None => {
+                    debug!("received shutdown command");
+                    break;
+                }
+            },
+            // Stop polling when memory usage exceeds hard limit, and resume after tables flush.
+            messages = consumer.poll(MAX_MESSAGES_PER_POLL), if source.has_tables() && !*over_hard_limit_rx.borrow() => {
+                match messages {
+                    Ok(messages) => {
+                        for rest_event in source.process_messages(messages) {
+                            if let Err(e) = sink.process_rest_event(rest_event).await {
+                                error!(error = ?e, "failed to process kafka message");
+                                return Err(e);
+                            }
+                        }
+                        // Stop fetching for stalled topics, whose offsets are still committed after persistence.
+                        for topic in source.take_stalled_topics() {
+                            if let Err(e) = consumer.unassign(&topic).await {
+                                warn!(error = ?e, %topic, "failed to unassign stalled kafka topic");
+                            }
+                        }
+                    }
+                    Err(e) => {
+                        warn!(error = ?e, "failed to poll kafka messages");
+                    }
+                }
+            },
+            Ok(()) = over_hard_limit_rx.changed() => {
+                debug!(paused = *over_hard_limit_rx.borrow(), "kafka consumption pause state changed");
+            },
+            _ = offset_commit_interval.tick() => {
+                for (topic, offsets) in source.get_offsets_to_commit() {
+                    if let Err(e) = consumer.commit_offsets(&topic, &offsets).await {
+                        warn!(error = ?e, %topic, ?offsets, "failed to commit kafka offsets");
+                    }
+                }
+            },
+        }
+    }
+
+    debug!("kafka event loop stopped");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::kafka_ingest::in_memory_broker::InMemoryKafkaBroker;
+    use arrow_schema::{DataType, Field};
+    use tokio::task::JoinHandle;
+
+    const TOPIC: &str = "users";
+    const GROUP_ID: &str = "group";
+
+    struct TestTable {
+        event_rx: mpsc::Receiver<TableEvent>,
+        flush_lsn_tx: watch::Sender<u64>,
+        replication_state: Arc<ReplicationState>,
+        cmd_tx: mpsc::Sender<KafkaCommand>,
+        handle: JoinHandle<Result<()>>,
+    }
+
+    /// Spawn a kafka event loop against the in-memory broker, which ingests from one topic.
+    async fn start_table(broker: &InMemoryKafkaBroker, persist_lsn: Option<u64>) -> TestTable {
+        let mut connection = KafkaConnection::with_consumer(
+            KafkaValueFormat::Json,
+            KafkaDecodeErrorPolicy::Stall,
+            Box::new(broker.create_consumer(GROUP_ID)),
+            MemoryAccountant::default(),
+        );
+        let handle = connection.spawn_kafka_task().await;
+
+        let (event_tx, event_rx) = mpsc::channel(100);
+        let (flush_lsn_tx, flush_lsn_rx) = watch::channel(persist_lsn.unwrap_or(0));
+        let (_wal_flush_lsn_tx, wal_flush_lsn_rx) = watch::channel(0);
+        let replication_state = ReplicationState::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]));
+        connection
+            .add_table(
+                TOPIC.to_string(),
+                connection.next_src_table_id(),
+                schema,
+                event_tx,
+                CommitState::new(),
+                flush_lsn_rx,
+                wal_flush_lsn_rx,
+                replication_state.clone(),
+                persist_lsn,
+            )
+            .await
+            .unwrap();
+
+        TestTable {
+            event_rx,
+            flush_lsn_tx,
+            replication_state,
+            cmd_tx: connection.cmd_tx.clone(),
+            handle,
+        }
+    }
+
+    fn produce_users(broker: &InMemoryKafkaBroker, ids: std::ops::Range<i32>) {
+        for id in ids {
+            let payload = format!(r#"{{"id": {id}, "name": "user-{id}"}}"#);
+            broker.produce(TOPIC, Some(payload.into_bytes()));
+        }
+    }
+
+    /// Receive table events until the given commit LSN, and return LSNs for appended rows.
+    async fn receive_until_commit(event_rx: &mut mpsc::Receiver<TableEvent>, lsn: u64) -> Vec<u64> {
+        let mut appended_lsns = vec![];
+        loop {
+            match event_rx.recv().await.unwrap() {
+                TableEvent::Append { lsn, .. } => appended_lsns.push(lsn),
+                TableEvent::Commit {
+                    lsn: commit_lsn, ..
+                } if commit_lsn == lsn => return appended_lsns,
+                TableEvent::Commit { .. } => {}
+                event => panic!("unexpected table event {event:?}"),
+            }
+        }
+    }
+
+    async fn wait_for_committed_offset(broker: &InMemoryKafkaBroker, partition: i32, offset: u64) {
+        for _ in 0..50 {
+            if broker.committed_offset(GROUP_ID, TOPIC, partition) == Some(offset) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+        panic!("offset {offset} of partition {partition} not committed to consumer group");
+    }
+
+    async fn shutdown(table: TestTable) {
+        table.cmd_tx.send(KafkaCommand::Shutdown).await.unwrap();
+        table.handle.await.unwrap().unwrap();
+    }
+
+    #[test]
+    fn test_parse_kafka_uri() {
+        let config = KafkaConnectionConfig::from_uri(
+            "kafka://broker1:9092,broker2:9092?group_id=g1&value_format=confluent_avro",
+        )
+        .unwrap();
+        assert_eq!(
+            config,
+            KafkaConnectionConfig {
+                bootstrap_servers: "broker1:9092,broker2:9092".to_string(),
+                group_id: "g1".to_string(),
+                value_format: KafkaValueFormat::ConfluentAvro,
+                decode_error_policy: KafkaDecodeErrorPolicy::Stall,
+            }
+        );
+
+        let config = KafkaConnectionConfig::from_uri("kafka://localhost:9092").unwrap();
+        assert_eq!(config.group_id, DEFAULT_GROUP_ID);
+        assert_eq!(config.value_format, KafkaValueFormat::Json);
+
+        let config =
+            KafkaConnectionConfig::from_uri("kafka://localhost:9092?on_decode_error=skip").unwrap();
+        assert_eq!(config.decode_error_policy, KafkaDecodeErrorPolicy::Skip);
+
+        assert!(KafkaConnectionConfig::from_uri("kafka://").is_err());
+        assert!(KafkaConnectionConfig::from_uri("kafka://localhost:9092?format=json").is_err());
+        assert!(
+            KafkaConnectionConfig::from_uri("kafka://localhost:9092?value_format=xml").is_err()
+        );
+        assert!(
+            KafkaConnectionConfig::from_uri("kafka://localhost:9092?on_decode_error=retry")
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_kafka_offsets_map_to_commit_lsns() {
+        let broker = InMemoryKafkaBroker::new();
+        produce_users(&broker, 0..3);
+
+        let mut table = start_table(&broker, /*persist_lsn=*/ None).await;
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 3).await,
+            vec![1, 2, 3]
+        );
+        assert_eq!(table.replication_state.now(), 3);
+
+        // Offsets are committed to consumer group only after iceberg persistence.
+        produce_users(&broker, 3..5);
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 5).await,
+            vec![4, 5]
+        );
+        assert!(broker
+            .committed_offset(GROUP_ID, TOPIC, /*partition=*/ 0)
+            .is_none());
+        table.flush_lsn_tx.send(3).unwrap();
+        wait_for_committed_offset(&broker, /*partition=*/ 0, /*offset=*/ 3).await;
+        shutdown(table).await;
+    }
+
+    #[tokio::test]
+    async fn test_kafka_resume_after_restart() {
+        let broker = InMemoryKafkaBroker::new();
+        produce_users(&broker, 0..5);
+
+        let mut table = start_table(&broker, /*persist_lsn=*/ None).await;
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 5).await,
+            vec![1, 2, 3, 4, 5]
+        );
+        table.flush_lsn_tx.send(2).unwrap();
+        wait_for_committed_offset(&broker, /*partition=*/ 0, /*offset=*/ 2).await;
+        shutdown(table).await;
+
+        // Recover from iceberg persistence, unpersisted messages get consumed again.
+        let mut table = start_table(&broker, /*persist_lsn=*/ Some(4)).await;
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 5).await,
+            vec![5]
+        );
+        shutdown(table).await;
+
+        // Fresh table resumes from consumer group offset.
+        let mut table = start_table(&broker, /*persist_lsn=*/ None).await;
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 5).await,
+            vec![3, 4, 5]
+        );
+        shutdown(table).await;
+    }
+
+    #[tokio::test]
+    async fn test_kafka_multi_partition_topic() {
+        let broker = InMemoryKafkaBroker::new();
+        broker.set_partition_count(TOPIC, /*partition_count=*/ 2);
+        let produce_user = |partition: i32, id: i32| {
+            let payload = format!(r#"{{"id": {id}, "name": "user-{id}"}}"#);
+            broker.produce_to_partition(TOPIC, partition, Some(payload.into_bytes()));
+        };
+        produce_user(/*partition=*/ 0, /*id=*/ 0);
+        produce_user(/*partition=*/ 1, /*id=*/ 1);
+        produce_user(/*partition=*/ 1, /*id=*/ 2);
+
+        let mut table = start_table(&broker, /*persist_lsn=*/ None).await;
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 3).await,
+            vec![1, 2, 3]
+        );
+
+        // Persisted offsets are committed for each partition.
+        table.flush_lsn_tx.send(3).unwrap();
+        wait_for_committed_offset(&broker, /*partition=*/ 0, /*offset=*/ 1).await;
+        wait_for_committed_offset(&broker, /*partition=*/ 1, /*offset=*/ 2).await;
+        shutdown(table).await;
+
+        // Recovery resumes from consumer group offsets, with LSNs after persisted one.
+        produce_user(/*partition=*/ 0, /*id=*/ 3);
+        let mut table = start_table(&broker, /*persist_lsn=*/ Some(3)).await;
+        assert_eq!(
+            receive_until_commit(&mut table.event_rx, /*lsn=*/ 4).await,
+            vec![4]
+        );
+        shutdown(table).await;
+    }
+
+    #[tokio::test]
+    async fn test_kafka_sink_failure_stops_event_loop() {
+        let broker = InMemoryKafkaBroker::new();
+        let table = start_table(&broker, /*persist_lsn=*/ None).await;
+
+        // Table handler is gone, so consumed messages cannot be applied.
+        drop(table.event_rx);
+        produce_users(&broker, 0..1);
+        let result = tokio::time::timeout(Duration::from_secs(5), table.handle)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(result.is_err());
+    }
+}
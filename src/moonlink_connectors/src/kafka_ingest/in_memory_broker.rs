@@ -0,0 +1,304 @@
+/// This module contains an in-process stand-in for kafka broker, which keeps partitioned topics and consumer group offsets in memory.
+/// It's used to exercise kafka source without a kafka cluster.
+use crate::kafka_ingest::kafka_consumer::{KafkaConsumer, KafkaMessage};
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+
+#[derive(Default)]
+struct BrokerState {
+    /// Maps from topic name to its partitions, each of which keeps messages indexed by offset.
+    /// Topics are single-partition unless partition count is set explicitly.
+    topics: HashMap<String, Vec<Vec<Option<Vec<u8>>>>>,
+    /// Maps from (group id, topic, partition) to committed offset.
+    committed_offsets: HashMap<(String, String, i32), u64>,
+}
+
+impl BrokerState {
+    fn get_or_create_topic(&mut self, topic: &str) -> &mut Vec<Vec<Option<Vec<u8>>>> {
+        self.topics
+            .entry(topic.to_string())
+            .or_insert_with(|| vec![vec![]])
+    }
+}
+
+#[derive(Clone)]
+pub struct InMemoryKafkaBroker {
+    state: Arc<Mutex<BrokerState>>,
+    /// Notified with total number of produced messages, used to wake up consumers.
+    produced_tx: Arc<watch::Sender<u64>>,
+}
+
+impl Default for InMemoryKafkaBroker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryKafkaBroker {
+    pub fn new() -> Self {
+        let (produced_tx, _) = watch::channel(0);
+        Self {
+            state: Arc::new(Mutex::new(BrokerState::default())),
+            produced_tx: Arc::new(produced_tx),
+        }
+    }
+
+    /// Append a message to the first partition of the given topic, and return its offset.
+    pub fn produce(&self, topic: &str, payload: Option<Vec<u8>>) -> u64 {
+        self.produce_to_partition(topic, /*partition=*/ 0, payload)
+    }
+
+    /// Append a message to the given partition, and return its offset.
+    pub fn produce_to_partition(
+        &self,
+        topic: &str,
+        partition: i32,
+        payload: Option<Vec<u8>>,
+    ) -> u64 {
+        let offset = {
+            let mut guard = self.state.lock().unwrap();
+            let partitions = guard.get_or_create_topic(topic);
+            let messages = &mut partitions[partition as usize];
+            messages.push(payload);
+            messages.len() as u64 - 1
+        };
+        self.produced_tx.send_modify(|count| *count += 1);
+        offset
+    }
+
+    /// Set number of partitions for the given topic, partitions could only be added.
+    pub fn set_partition_count(&self, topic: &str, partition_count: usize) {
+        let mut guard = self.state.lock().unwrap();
+        let partitions = guard.get_or_create_topic(topic);
+        assert!(partitions.len() <= partition_count);
+        partitions.resize_with(partition_count, Vec::new);
+    }
+
+    /// Get offset committed by the given consumer group for one partition.
+    pub fn committed_offset(&self, group_id: &str, topic: &str, partition: i32) -> Option<u64> {
+        let guard = self.state.lock().unwrap();
+        guard
+            .committed_offsets
+            .get(&(group_id.to_string(), topic.to_string(), partition))
+            .copied()
+    }
+
+    /// Create a consumer which belongs to the given consumer group.
+    pub fn create_consumer(&self, group_id: &str) -> InMemoryKafkaConsumer {
+        InMemoryKafkaConsumer {
+            broker: self.clone(),
+            group_id: group_id.to_string(),
+            positions: BTreeMap::new(),
+            produced_rx: self.produced_tx.subscribe(),
+        }
+    }
+}
+
+pub struct InMemoryKafkaConsumer {
+    broker: InMemoryKafkaBroker,
+    group_id: String,
+    /// Maps from assigned (topic, partition) to its next offset to consume.
+    positions: BTreeMap<(String, i32), u64>,
+    produced_rx: watch::Receiver<u64>,
+}
+
+impl InMemoryKafkaConsumer {
+    /// Take at most `max_messages` messages from assigned partitions without blocking.
+    fn take_available_messages(&mut self, max_messages: usize) -> Vec<KafkaMessage> {
+        let guard = self.broker.state.lock().unwrap();
+        let mut messages = vec![];
+        for ((topic, partition), position) in self.positions.iter_mut() {
+            let Some(partition_messages) = guard
+                .topics
+                .get(topic)
+                .and_then(|partitions| partitions.get(*partition as usize))
+            else {
+                continue;
+            };
+            while (*position as usize) < partition_messages.len() && messages.len() < max_messages {
+                messages.push(KafkaMessage {
+                    topic: topic.clone(),
+                    partition: *partition,
+                    offset: *position,
+                    payload: partition_messages[*position as usize].clone(),
+                });
+                *position += 1;
+            }
+        }
+        messages
+    }
+}
+
+#[async_trait]
+impl KafkaConsumer for InMemoryKafkaConsumer {
+    async fn committed_offsets(
+        &mut self,
+        topic: &str,
+        partition_count: usize,
+    ) -> Result<BTreeMap<i32, u64>> {
+        Ok((0..partition_count as i32)
+            .filter_map(|partition| {
+                self.broker
+                    .committed_offset(&self.group_id, topic, partition)
+                    .map(|offset| (partition, offset))
+            })
+            .collect())
+    }
+
+    async fn partition_count(&mut self, topic: &str) -> Result<usize> {
+        let guard = self.broker.state.lock().unwrap();
+        Ok(guard
+            .topics
+            .get(topic)
+            .map_or(1, |partitions| partitions.len()))
+    }
+
+    async fn assign(&mut self, topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<()> {
+        for (partition, offset) in offsets.iter() {
+            self.positions
+                .insert((topic.to_string(), *partition), *offset);
+        }
+        Ok(())
+    }
+
+    async fn unassign(&mut self, topic: &str) -> Result<()> {
+        self.positions
+            .retain(|(cur_topic, _), _| cur_topic != topic);
+        Ok(())
+    }
+
+    async fn poll(&mut self, max_messages: usize) -> Result<Vec<KafkaMessage>> {
+        loop {
+            // Mark current produce count as seen before checking, so no message gets missed in between.
+            self.produced_rx.borrow_and_update();
+            let messages = self.take_available_messages(max_messages);
+            if !messages.is_empty() {
+                return Ok(messages);
+            }
+            // Broker is owned by consumer itself, so the channel never gets closed.
+            self.produced_rx.changed().await.unwrap();
+        }
+    }
+
+    async fn commit_offsets(&mut self, topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<()> {
+        let mut guard = self.broker.state.lock().unwrap();
+        for (partition, offset) in offsets.iter() {
+            guard.committed_offsets.insert(
+                (self.group_id.clone(), topic.to_string(), *partition),
+                *offset,
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_in_memory_broker_consume_and_commit() {
+        let broker = InMemoryKafkaBroker::new();
+        assert_eq!(broker.produce("topic", Some(b"a".to_vec())), 0);
+        assert_eq!(broker.produce("topic", None), 1);
+        assert_eq!(broker.produce("topic", Some(b"c".to_vec())), 2);
+
+        let mut consumer = broker.create_consumer("group");
+        assert!(consumer
+            .committed_offsets("topic", /*partition_count=*/ 1)
+            .await
+            .unwrap()
+            .is_empty());
+        consumer
+            .assign("topic", &BTreeMap::from([(0, 1)]))
+            .await
+            .unwrap();
+        let messages = consumer.poll(/*max_messages=*/ 10).await.unwrap();
+        assert_eq!(
+            messages,
+            vec![
+                KafkaMessage {
+                    topic: "topic".to_string(),
+                    partition: 0,
+                    offset: 1,
+                    payload: None,
+                },
+                KafkaMessage {
+                    topic: "topic".to_string(),
+                    partition: 0,
+                    offset: 2,
+                    payload: Some(b"c".to_vec()),
+                },
+            ]
+        );
+
+        // Consumer group offset is visible to other consumers within the same group.
+        consumer
+            .commit_offsets("topic", &BTreeMap::from([(0, 3)]))
+            .await
+            .unwrap();
+        let mut other_consumer = broker.create_consumer("group");
+        assert_eq!(
+            other_consumer
+                .committed_offsets("topic", /*partition_count=*/ 1)
+                .await
+                .unwrap(),
+            BTreeMap::from([(0, 3)])
+        );
+        assert!(broker
+            .committed_offset("other_group", "topic", /*partition=*/ 0)
+            .is_none());
+
+        // Poll waits for new messages.
+        let producer = broker.clone();
+        tokio::spawn(async move {
+            producer.produce("topic", Some(b"d".to_vec()));
+        });
+        let messages = consumer.poll(/*max_messages=*/ 10).await.unwrap();
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].offset, 3);
+    }
+
+    #[tokio::test]
+    async fn test_in_memory_broker_multi_partition_topic() {
+        let broker = InMemoryKafkaBroker::new();
+        broker.set_partition_count("topic", /*partition_count=*/ 2);
+        assert_eq!(
+            broker.produce_to_partition("topic", /*partition=*/ 1, Some(b"a".to_vec())),
+            0
+        );
+        assert_eq!(
+            broker.produce_to_partition("topic", /*partition=*/ 0, Some(b"b".to_vec())),
+            0
+        );
+
+        let mut consumer = broker.create_consumer("group");
+        assert_eq!(consumer.partition_count("topic").await.unwrap(), 2);
+        consumer
+            .assign("topic", &BTreeMap::from([(0, 0), (1, 0)]))
+            .await
+            .unwrap();
+        let messages = consumer.poll(/*max_messages=*/ 10).await.unwrap();
+        let partitions: Vec<_> = messages
+            .iter()
+            .map(|message| (message.partition, message.offset))
+            .collect();
+        assert_eq!(partitions, vec![(0, 0), (1, 0)]);
+
+        // Partitions are committed independently.
+        consumer
+            .commit_offsets("topic", &BTreeMap::from([(1, 1)]))
+            .await
+            .unwrap();
+        assert_eq!(
+            consumer
+                .committed_offsets("topic", /*partition_count=*/ 2)
+                .await
+                .unwrap(),
+            BTreeMap::from([(1, 1)])
+        );
+    }
+}
@@ -0,0 +1,44 @@
+/// This module defines consumer abstraction for kafka source, so the same event loop runs against a real kafka cluster and the in-process broker.
+use crate::Result;
+use async_trait::async_trait;
+use std::collections::BTreeMap;
+
+/// A single record consumed from kafka.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KafkaMessage {
+    pub topic: String,
+    pub partition: i32,
+    pub offset: u64,
+    /// Message value, tombstones don't carry payload.
+    pub payload: Option<Vec<u8>>,
+}
+
+/// Kafka consumer which belongs to one consumer group.
+///
+/// Offsets are only ordered within one partition, so all offsets are tracked per partition, keyed by partition id.
+#[async_trait]
+pub trait KafkaConsumer: Send {
+    /// Get offsets committed by the consumer group for the given topic, which are the next offsets to consume.
+    /// Partitions without committed offset are not included.
+    async fn committed_offsets(
+        &mut self,
+        topic: &str,
+        partition_count: usize,
+    ) -> Result<BTreeMap<i32, u64>>;
+
+    /// Get number of partitions for the given topic.
+    async fn partition_count(&mut self, topic: &str) -> Result<usize>;
+
+    /// Start consuming the given partitions of the topic, each from the given offset.
+    async fn assign(&mut self, topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<()>;
+
+    /// Stop consuming all partitions of the given topic.
+    async fn unassign(&mut self, topic: &str) -> Result<()>;
+
+    /// Wait until messages are available for assigned topics, and return at most `max_messages` of them.
+    /// Used inside of `tokio::select!`, so implementation should be cancel safe.
+    async fn poll(&mut self, max_messages: usize) -> Result<Vec<KafkaMessage>>;
+
+    /// Commit next offsets to consume for the given partitions of the topic to the consumer group.
+    async fn commit_offsets(&mut self, topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<()>;
+}
@@ -0,0 +1,569 @@
+/// This module contains per-topic states for kafka source, which decodes kafka messages and maps offsets to commit LSNs.
+use crate::kafka_ingest::kafka_consumer::KafkaMessage;
+use crate::kafka_ingest::KafkaValueFormat;
+use crate::rest_ingest::avro_converter::{
+    convert_avro_to_arrow_schema, AvroToMoonlinkRowConverter,
+};
+use crate::rest_ingest::avro_schema_registry::AvroSchemaRegistry;
+use crate::rest_ingest::event_request::RowEventOperation;
+use crate::rest_ingest::json_converter::JsonToMoonlinkRowConverter;
+use crate::rest_ingest::rest_event::RestEvent;
+use crate::rest_ingest::rest_source::RestSourceError;
+use crate::rest_ingest::SrcTableId;
+use crate::Result;
+use apache_avro::schema::Schema as AvroSchema;
+use arrow_schema::Schema;
+use moonlink::row::MoonlinkRow;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::Arc;
+use std::time::SystemTime;
+use tokio::sync::watch;
+use tracing::{debug, error, warn};
+
+/// Get next offset to consume for a single-partition topic, when all messages up to the given LSN have been persisted.
+/// Kafka offsets start from 0, while LSN 0 indicates nothing committed, so offset `n` is committed at LSN `n + 1`.
+pub(crate) fn lsn_to_next_offset(lsn: u64) -> u64 {
+    lsn
+}
+
+/// How to handle kafka messages which cannot be decoded into table rows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum KafkaDecodeErrorPolicy {
+    /// Stop consuming the topic at the first undecodable message, until a new avro schema is set or the table gets re-added.
+    #[default]
+    Stall,
+    /// Skip undecodable messages, which are counted per topic.
+    Skip,
+}
+
+struct KafkaTopicState {
+    src_table_id: SrcTableId,
+    json_converter: JsonToMoonlinkRowConverter,
+    schema: Arc<Schema>,
+    avro_schema_registry: Option<AvroSchemaRegistry>,
+    /// Maps from partition to its next offset to consume, messages before it have already been ingested.
+    next_offsets: BTreeMap<i32, u64>,
+    /// Commit LSN is the base plus next offsets summed over all partitions, which increases with every consumed message.
+    /// The base is only non-zero when a multi-partition topic resumes from consumer group offsets behind its persisted LSN.
+    lsn_base: u64,
+    /// Sum of next offsets over all partitions.
+    total_next_offset: u64,
+    /// Iceberg flush LSN, offsets are committed to consumer group only after persistence.
+    flush_lsn_rx: watch::Receiver<u64>,
+    /// Maps from commit LSN to next offsets of all partitions at the commit, for commits not yet committed to consumer group.
+    uncommitted_offsets: BTreeMap<u64, BTreeMap<i32, u64>>,
+    /// Offsets last committed to consumer group.
+    committed_offsets: BTreeMap<i32, u64>,
+    /// Consumption stops at the first message which cannot be decoded, so no message is silently lost.
+    stalled: bool,
+    /// Number of undecodable messages skipped, only for [`KafkaDecodeErrorPolicy::Skip`].
+    skipped_message_count: u64,
+}
+
+impl KafkaTopicState {
+    fn current_lsn(&self) -> u64 {
+        self.lsn_base + self.total_next_offset
+    }
+
+    /// Advance next offset of the given partition past the given message offset.
+    fn advance(&mut self, partition: i32, offset: u64) {
+        let next_offset = self.next_offsets.entry(partition).or_default();
+        self.total_next_offset += offset + 1 - *next_offset;
+        *next_offset = offset + 1;
+    }
+}
+
+pub struct KafkaSource {
+    value_format: KafkaValueFormat,
+    decode_error_policy: KafkaDecodeErrorPolicy,
+    /// Maps from topic name to its states.
+    topics: HashMap<String, KafkaTopicState>,
+    /// Topics stalled since last take, which should stop consumption.
+    newly_stalled_topics: Vec<String>,
+}
+
+impl KafkaSource {
+    pub fn new(
+        value_format: KafkaValueFormat,
+        decode_error_policy: KafkaDecodeErrorPolicy,
+    ) -> Self {
+        Self {
+            value_format,
+            decode_error_policy,
+            topics: HashMap::new(),
+            newly_stalled_topics: vec![],
+        }
+    }
+
+    /// Add a table which ingests from the given topic.
+    ///
+    /// # Arguments
+    ///
+    /// * start_offsets: offsets to start consumption for all partitions, which have been either persisted to iceberg or committed to consumer group.
+    /// * lsn_base: added to summed offsets to get commit LSN, so LSNs keep increasing after persisted LSN.
+    pub fn add_table(
+        &mut self,
+        topic: String,
+        src_table_id: SrcTableId,
+        schema: Arc<Schema>,
+        flush_lsn_rx: watch::Receiver<u64>,
+        start_offsets: BTreeMap<i32, u64>,
+        lsn_base: u64,
+    ) -> Result<()> {
+        debug!(%topic, src_table_id, ?start_offsets, lsn_base, "adding kafka topic");
+        if self.topics.contains_key(&topic) {
+            return Err(RestSourceError::DuplicateTable(topic).into());
+        }
+        self.topics.insert(
+            topic,
+            KafkaTopicState {
+                src_table_id,
+                json_converter: JsonToMoonlinkRowConverter::new(schema.clone()),
+                schema,
+                avro_schema_registry: None,
+                total_next_offset: start_offsets.values().sum(),
+                next_offsets: start_offsets.clone(),
+                lsn_base,
+                flush_lsn_rx,
+                uncommitted_offsets: BTreeMap::new(),
+                committed_offsets: start_offsets,
+                stalled: false,
+                skipped_message_count: 0,
+            },
+        );
+        Ok(())
+    }
+
+    pub fn remove_table(&mut self, topic: &str) -> Result<()> {
+        if self.topics.remove(topic).is_none() {
+            return Err(RestSourceError::NonExistentTable(topic.to_string()).into());
+        }
+        Ok(())
+    }
+
+    pub fn has_tables(&self) -> bool {
+        !self.topics.is_empty()
+    }
+
+    pub fn get_src_table_id(&self, topic: &str) -> Option<SrcTableId> {
+        self.topics.get(topic).map(|state| state.src_table_id)
+    }
+
+    /// Get number of undecodable messages skipped for the given topic.
+    pub fn get_skipped_message_count(&self, topic: &str) -> Option<u64> {
+        self.topics
+            .get(topic)
+            .map(|state| state.skipped_message_count)
+    }
+
+    /// Register avro schema for the given topic, which should match table schema.
+    /// Return offsets of all partitions to resume consumption from, if the topic has been stalled by undecodable messages.
+    ///
+    /// # Arguments
+    ///
+    /// * schema_id: schema id in confluent wire format, only assigned for schema registry versions.
+    pub fn set_avro_schema(
+        &mut self,
+        topic: &str,
+        schema_id: Option<u32>,
+        avro_schema: AvroSchema,
+    ) -> Result<Option<BTreeMap<i32, u64>>> {
+        let topic_state = self
+            .topics
+            .get_mut(topic)
+            .ok_or_else(|| RestSourceError::UnknownTable(topic.to_string()))?;
+        let avro_derived_arrow_schema =
+            convert_avro_to_arrow_schema(&avro_schema).map_err(RestSourceError::from)?;
+        if topic_state.schema.as_ref() == &avro_derived_arrow_schema {
+            if topic_state.avro_schema_registry.is_none() {
+                topic_state.avro_schema_registry =
+                    Some(AvroSchemaRegistry::new(avro_schema.clone()));
+            }
+        } else if topic_state.avro_schema_registry.is_none() {
+            return Err(RestSourceError::IncompatibleAvroSchema(format!(
+                "Avro schema doesn't match schema for topic {topic}"
+            ))
+            .into());
+        }
+
+        // Other versions are only used as writer schemas, which are resolved against the reader schema.
+        if let Some(schema_id) = schema_id {
+            topic_state
+                .avro_schema_registry
+                .as_mut()
+                .unwrap()
+                .register_writer_schema(schema_id, avro_schema)?;
+        }
+
+        // Messages which failed to decode could be decodable with the new schema.
+        if topic_state.stalled {
+            topic_state.stalled = false;
+            return Ok(Some(topic_state.next_offsets.clone()));
+        }
+        Ok(None)
+    }
+
+    fn decode_payload(
+        &self,
+        topic: &str,
+        topic_state: &KafkaTopicState,
+        payload: &[u8],
+    ) -> std::result::Result<MoonlinkRow, RestSourceError> {
+        if self.value_format == KafkaValueFormat::Json {
+            let value: serde_json::Value = serde_json::from_slice(payload)
+                .map_err(|e| RestSourceError::JsonConversion(e.into()))?;
+            return Ok(topic_state.json_converter.convert(&value)?);
+        }
+
+        let avro_schema_registry = topic_state.avro_schema_registry.as_ref().ok_or_else(|| {
+            RestSourceError::InvalidOperation(format!(
+                "Topic {topic} does not have an Avro schema configured"
+            ))
+        })?;
+        let avro_value = match self.value_format {
+            KafkaValueFormat::ConfluentAvro => {
+                avro_schema_registry.decode_confluent_datum(payload)?
+            }
+            _ => avro_schema_registry.decode_datum(payload)?,
+        };
+        Ok(AvroToMoonlinkRowConverter::convert(&avro_value)?)
+    }
+
+    /// Convert consumed messages into rest events, messages are ingested as appends.
+    /// Consecutive messages of the same topic are committed together, at the LSN of the last one.
+    /// Undecodable messages are handled by decode error policy; a stalled topic ignores its later messages until a new avro schema is set or the table gets re-added.
+    pub fn process_messages(&mut self, messages: Vec<KafkaMessage>) -> Vec<RestEvent> {
+        let mut rest_events = vec![];
+        // Topic and LSN of the last row event which hasn't been committed.
+        let mut pending_commit: Option<(String, u64)> = None;
+        for message in messages.into_iter() {
+            let Some(topic_state) = self.topics.get(&message.topic) else {
+                debug!(topic = %message.topic, "skip message for unknown topic");
+                continue;
+            };
+            // Messages could be re-delivered after reassignment.
+            let next_offset = topic_state
+                .next_offsets
+                .get(&message.partition)
+                .copied()
+                .unwrap_or(0);
+            if topic_state.stalled || message.offset < next_offset {
+                continue;
+            }
+            // Tombstones delete keys in compacted topics, which don't apply to append-only ingestion.
+            let Some(payload) = message.payload.as_deref() else {
+                debug!(topic = %message.topic, partition = message.partition, offset = message.offset, "skip tombstone kafka message");
+                self.topics
+                    .get_mut(&message.topic)
+                    .unwrap()
+                    .advance(message.partition, message.offset);
+                continue;
+            };
+            let row = match self.decode_payload(&message.topic, topic_state, payload) {
+                Ok(row) => row,
+                Err(e) => {
+                    let topic_state = self.topics.get_mut(&message.topic).unwrap();
+                    match self.decode_error_policy {
+                        KafkaDecodeErrorPolicy::Stall => {
+                            error!(topic = %message.topic, partition = message.partition, offset = message.offset, error = ?e, "stop consuming kafka topic at message which cannot be decoded");
+                            topic_state.stalled = true;
+                            self.newly_stalled_topics.push(message.topic);
+                        }
+                        KafkaDecodeErrorPolicy::Skip => {
+                            topic_state.skipped_message_count += 1;
+                            topic_state.advance(message.partition, message.offset);
+                            warn!(topic = %message.topic, partition = message.partition, offset = message.offset, skipped_message_count = topic_state.skipped_message_count, error = ?e, "skip kafka message which cannot be decoded");
+                        }
+                    }
+                    continue;
+                }
+            };
+
+            if let Some((topic, lsn)) = pending_commit.take() {
+                if topic == message.topic {
+                    pending_commit = Some((topic, lsn));
+                } else {
+                    rest_events.push(self.commit_topic(&topic, lsn));
+                }
+            }
+
+            let topic_state = self.topics.get_mut(&message.topic).unwrap();
+            topic_state.advance(message.partition, message.offset);
+            let src_table_id = topic_state.src_table_id;
+
+            let lsn = topic_state.current_lsn();
+            rest_events.push(RestEvent::RowEvent {
+                src_table_id,
+                operation: RowEventOperation::Insert,
+                row,
+                lsn,
+                timestamp: SystemTime::now(),
+            });
+            pending_commit = Some((message.topic, lsn));
+        }
+
+        if let Some((topic, lsn)) = pending_commit {
+            rest_events.push(self.commit_topic(&topic, lsn));
+        }
+        rest_events
+    }
+
+    /// Record offsets of all partitions at the given commit LSN, and return the commit event.
+    fn commit_topic(&mut self, topic: &str, lsn: u64) -> RestEvent {
+        let topic_state = self.topics.get_mut(topic).unwrap();
+        topic_state
+            .uncommitted_offsets
+            .insert(lsn, topic_state.next_offsets.clone());
+        RestEvent::Commit {
+            lsn,
+            timestamp: SystemTime::now(),
+        }
+    }
+
+    /// Take topics stalled by undecodable messages since last call, which should stop consumption.
+    pub fn take_stalled_topics(&mut self) -> Vec<String> {
+        std::mem::take(&mut self.newly_stalled_topics)
+    }
+
+    /// Get offsets to commit to consumer group, for topics which have persisted new messages to iceberg since last commit.
+    /// Offsets come from the latest commit at or before flush LSN.
+    pub fn get_offsets_to_commit(&mut self) -> Vec<(String, BTreeMap<i32, u64>)> {
+        let mut offsets_to_commit = vec![];
+        for (topic, topic_state) in self.topics.iter_mut() {
+            let flush_lsn = *topic_state.flush_lsn_rx.borrow();
+            let unpersisted_offsets = topic_state.uncommitted_offsets.split_off(&(flush_lsn + 1));
+            let persisted_offsets =
+                std::mem::replace(&mut topic_state.uncommitted_offsets, unpersisted_offsets);
+            let Some((_, offsets)) = persisted_offsets.into_iter().next_back() else {
+                continue;
+            };
+            if offsets != topic_state.committed_offsets {
+                topic_state.committed_offsets = offsets.clone();
+                offsets_to_commit.push((topic.clone(), offsets));
+            }
+        }
+        offsets_to_commit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow_schema::{DataType, Field};
+    use moonlink::row::RowValue;
+
+    fn create_schema() -> Arc<Schema> {
+        Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+        ]))
+    }
+
+    fn create_message(topic: &str, offset: u64, payload: Option<&str>) -> KafkaMessage {
+        create_partition_message(topic, /*partition=*/ 0, offset, payload)
+    }
+
+    fn create_partition_message(
+        topic: &str,
+        partition: i32,
+        offset: u64,
+        payload: Option<&str>,
+    ) -> KafkaMessage {
+        KafkaMessage {
+            topic: topic.to_string(),
+            partition,
+            offset,
+            payload: payload.map(|payload| payload.as_bytes().to_vec()),
+        }
+    }
+
+    fn get_event_lsns(rest_events: &[RestEvent]) -> Vec<(bool, u64)> {
+        rest_events
+            .iter()
+            .map(|event| {
+                (
+                    matches!(event, RestEvent::Commit { .. }),
+                    event.lsn().unwrap(),
+                )
+            })
+            .collect()
+    }
+
+    /// Create a kafka source with one topic "users", whose partitions all start from offset 0.
+    fn create_source(
+        decode_error_policy: KafkaDecodeErrorPolicy,
+        partition_count: i32,
+        flush_lsn_rx: watch::Receiver<u64>,
+    ) -> KafkaSource {
+        let mut source = KafkaSource::new(KafkaValueFormat::Json, decode_error_policy);
+        source
+            .add_table(
+                "users".to_string(),
+                /*src_table_id=*/ 1,
+                create_schema(),
+                flush_lsn_rx,
+                (0..partition_count)
+                    .map(|partition| (partition, 0))
+                    .collect(),
+                /*lsn_base=*/ 0,
+            )
+            .unwrap();
+        source
+    }
+
+    #[test]
+    fn test_process_json_messages() {
+        let (_flush_lsn_tx, flush_lsn_rx) = watch::channel(0);
+        let mut source = create_source(
+            KafkaDecodeErrorPolicy::Stall,
+            /*partition_count=*/ 1,
+            flush_lsn_rx.clone(),
+        );
+        source
+            .add_table(
+                "orders".to_string(),
+                /*src_table_id=*/ 2,
+                create_schema(),
+                flush_lsn_rx,
+                BTreeMap::from([(0, 0)]),
+                /*lsn_base=*/ 0,
+            )
+            .unwrap();
+
+        let rest_events = source.process_messages(vec![
+            create_message("users", 0, Some(r#"{"id": 1, "name": "Alice"}"#)),
+            create_message("users", 1, Some(r#"{"id": 2, "name": "Bob"}"#)),
+            create_message("orders", 0, Some(r#"{"id": 3, "name": "Carol"}"#)),
+            // Topic stalls at undecodable message, and later messages are not ingested.
+            create_message("orders", 1, Some(r#"{"id": "invalid"}"#)),
+            create_message("orders", 2, Some(r#"{"id": 4, "name": "Dave"}"#)),
+            create_message("unknown", 0, Some(r#"{"id": 5, "name": "Eve"}"#)),
+        ]);
+        assert_eq!(
+            get_event_lsns(&rest_events),
+            vec![(false, 1), (false, 2), (true, 2), (false, 1), (true, 1)]
+        );
+        let RestEvent::RowEvent {
+            src_table_id, row, ..
+        } = &rest_events[0]
+        else {
+            panic!("expected row event, got {:?}", rest_events[0]);
+        };
+        assert_eq!(*src_table_id, 1);
+        assert_eq!(
+            row.values,
+            vec![RowValue::Int32(1), RowValue::ByteArray(b"Alice".to_vec())]
+        );
+
+        assert_eq!(source.take_stalled_topics(), vec!["orders".to_string()]);
+        assert!(source.take_stalled_topics().is_empty());
+
+        // Re-delivered messages and tombstones are skipped.
+        let rest_events = source.process_messages(vec![
+            create_message("users", 1, Some(r#"{"id": 2, "name": "Bob"}"#)),
+            create_message("users", 2, None),
+            create_message("users", 3, Some(r#"{"id": 6, "name": "Frank"}"#)),
+            create_message("orders", 3, Some(r#"{"id": 7, "name": "Grace"}"#)),
+        ]);
+        assert_eq!(get_event_lsns(&rest_events), vec![(false, 4), (true, 4)]);
+    }
+
+    #[test]
+    fn test_skip_undecodable_messages() {
+        let (_flush_lsn_tx, flush_lsn_rx) = watch::channel(0);
+        let mut source = create_source(
+            KafkaDecodeErrorPolicy::Skip,
+            /*partition_count=*/ 1,
+            flush_lsn_rx,
+        );
+
+        let rest_events = source.process_messages(vec![
+            create_message("users", 0, Some(r#"{"id": 1, "name": "Alice"}"#)),
+            create_message("users", 1, Some(r#"{"id": "invalid"}"#)),
+            create_message("users", 2, Some("not json")),
+            create_message("users", 3, Some(r#"{"id": 2, "name": "Bob"}"#)),
+        ]);
+        assert_eq!(
+            get_event_lsns(&rest_events),
+            vec![(false, 1), (false, 4), (true, 4)]
+        );
+        assert_eq!(source.get_skipped_message_count("users"), Some(2));
+        assert!(source.take_stalled_topics().is_empty());
+    }
+
+    #[test]
+    fn test_process_multi_partition_messages() {
+        let (flush_lsn_tx, flush_lsn_rx) = watch::channel(0);
+        let mut source = create_source(
+            KafkaDecodeErrorPolicy::Stall,
+            /*partition_count=*/ 2,
+            flush_lsn_rx,
+        );
+
+        // LSN increases with every consumed message, across partitions.
+        let rest_events = source.process_messages(vec![
+            create_partition_message("users", 1, 0, Some(r#"{"id": 1, "name": "Alice"}"#)),
+            create_partition_message("users", 0, 0, Some(r#"{"id": 2, "name": "Bob"}"#)),
+            create_partition_message("users", 1, 1, Some(r#"{"id": 3, "name": "Carol"}"#)),
+        ]);
+        assert_eq!(
+            get_event_lsns(&rest_events),
+            vec![(false, 1), (false, 2), (false, 3), (true, 3)]
+        );
+        let rest_events = source.process_messages(vec![
+            // Re-delivered message is skipped per partition.
+            create_partition_message("users", 1, 1, Some(r#"{"id": 3, "name": "Carol"}"#)),
+            create_partition_message("users", 0, 1, Some(r#"{"id": 4, "name": "Dave"}"#)),
+        ]);
+        assert_eq!(get_event_lsns(&rest_events), vec![(false, 4), (true, 4)]);
+
+        // Each commit maps to offsets of all partitions.
+        flush_lsn_tx.send(3).unwrap();
+        assert_eq!(
+            source.get_offsets_to_commit(),
+            vec![("users".to_string(), BTreeMap::from([(0, 1), (1, 2)]))]
+        );
+        flush_lsn_tx.send(4).unwrap();
+        assert_eq!(
+            source.get_offsets_to_commit(),
+            vec![("users".to_string(), BTreeMap::from([(0, 2), (1, 2)]))]
+        );
+        assert!(source.get_offsets_to_commit().is_empty());
+    }
+
+    #[test]
+    fn test_get_offsets_to_commit() {
+        let (flush_lsn_tx, flush_lsn_rx) = watch::channel(0);
+        let mut source = KafkaSource::new(KafkaValueFormat::Json, KafkaDecodeErrorPolicy::Stall);
+        source
+            .add_table(
+                "users".to_string(),
+                /*src_table_id=*/ 1,
+                create_schema(),
+                flush_lsn_rx,
+                BTreeMap::from([(0, 3)]),
+                /*lsn_base=*/ 0,
+            )
+            .unwrap();
+
+        // Nothing persisted beyond start offset.
+        flush_lsn_tx.send(3).unwrap();
+        assert!(source.get_offsets_to_commit().is_empty());
+
+        // Message at offset 4 is persisted with LSN 5, consumption resumes from offset 5.
+        let rest_events = source.process_messages(vec![
+            create_message("users", 3, Some(r#"{"id": 1, "name": "Alice"}"#)),
+            create_message("users", 4, Some(r#"{"id": 2, "name": "Bob"}"#)),
+        ]);
+        assert_eq!(
+            get_event_lsns(&rest_events),
+            vec![(false, 4), (false, 5), (true, 5)]
+        );
+        flush_lsn_tx.send(5).unwrap();
+        assert_eq!(
+            source.get_offsets_to_commit(),
+            vec![("users".to_string(), BTreeMap::from([(0, 5)]))]
+        );
+        assert!(source.get_offsets_to_commit().is_empty());
+    }
+}
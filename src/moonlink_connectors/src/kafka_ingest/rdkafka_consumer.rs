@@ -0,0 +1,168 @@
+/// This module contains kafka consumer implementation backed by librdkafka.
+use crate::kafka_ingest::kafka_consumer::{KafkaConsumer, KafkaMessage};
+use crate::kafka_ingest::KafkaConnectionConfig;
+use crate::{Error, Result};
+use async_trait::async_trait;
+use futures::FutureExt;
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{CommitMode, Consumer, StreamConsumer};
+use rdkafka::error::KafkaError;
+use rdkafka::{Message, Offset, TopicPartitionList};
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Timeout to fetch committed offsets from group coordinator.
+const COMMITTED_OFFSET_TIMEOUT: Duration = Duration::from_secs(10);
+/// Timeout to fetch topic metadata from brokers.
+const METADATA_TIMEOUT: Duration = Duration::from_secs(10);
+
+fn to_error(context: &str, err: KafkaError) -> Error {
+    Error::kafka(format!("{context}: {err}"), Some(Arc::new(err.into())))
+}
+
+fn to_join_error(context: &str, err: tokio::task::JoinError) -> Error {
+    Error::kafka(format!("{context}: {err}"), Some(Arc::new(err.into())))
+}
+
+fn topic_partition_list(topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<TopicPartitionList> {
+    let mut tpl = TopicPartitionList::new();
+    for (partition, offset) in offsets.iter() {
+        tpl.add_partition_offset(topic, *partition, Offset::Offset(*offset as i64))
+            .map_err(|e| to_error("failed to set partition offset", e))?;
+    }
+    Ok(tpl)
+}
+
+pub struct RdKafkaConsumer {
+    /// Shared with blocking tasks, which run synchronous librdkafka requests.
+    consumer: Arc<StreamConsumer>,
+}
+
+impl RdKafkaConsumer {
+    pub fn new(config: &KafkaConnectionConfig) -> Result<Self> {
+        // Offsets are committed manually after iceberg persistence, rather than after consumption.
+        let consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &config.bootstrap_servers)
+            .set("group.id", &config.group_id)
+            .set("enable.auto.commit", "false")
+            .set("enable.auto.offset.store", "false")
+            .create()
+            .map_err(|e| to_error("failed to create kafka consumer", e))?;
+        Ok(Self {
+            consumer: Arc::new(consumer),
+        })
+    }
+}
+
+#[async_trait]
+impl KafkaConsumer for RdKafkaConsumer {
+    async fn committed_offsets(
+        &mut self,
+        topic: &str,
+        partition_count: usize,
+    ) -> Result<BTreeMap<i32, u64>> {
+        let mut tpl = TopicPartitionList::new();
+        for partition in 0..partition_count as i32 {
+            tpl.add_partition(topic, partition);
+        }
+        // Fetching committed offsets blocks until group coordinator responds, so it shouldn't block the async runtime.
+        let consumer = self.consumer.clone();
+        let committed = tokio::task::spawn_blocking(move || {
+            consumer.committed_offsets(tpl, COMMITTED_OFFSET_TIMEOUT)
+        })
+        .await
+        .map_err(|e| to_join_error("failed to join committed offset fetch", e))?
+        .map_err(|e| to_error("failed to fetch committed offsets", e))?;
+        let offsets = committed
+            .elements_for_topic(topic)
+            .iter()
+            .filter_map(|elem| match elem.offset() {
+                Offset::Offset(offset) if offset >= 0 => Some((elem.partition(), offset as u64)),
+                _ => None,
+            })
+            .collect();
+        Ok(offsets)
+    }
+
+    async fn partition_count(&mut self, topic: &str) -> Result<usize> {
+        // Fetching metadata blocks until brokers respond, so it shouldn't block the async runtime.
+        let consumer = self.consumer.clone();
+        let owned_topic = topic.to_string();
+        let metadata = tokio::task::spawn_blocking(move || {
+            consumer.fetch_metadata(Some(&owned_topic), METADATA_TIMEOUT)
+        })
+        .await
+        .map_err(|e| to_join_error("failed to join metadata fetch", e))?
+        .map_err(|e| to_error("failed to fetch topic metadata", e))?;
+        let topic_metadata = metadata
+            .topics()
+            .iter()
+            .find(|cur_topic| cur_topic.name() == topic)
+            .ok_or_else(|| Error::kafka(format!("topic {topic} not found in metadata"), None))?;
+        if let Some(err) = topic_metadata.error() {
+            return Err(Error::kafka(
+                format!("failed to fetch metadata for topic {topic}: {err:?}"),
+                None,
+            ));
+        }
+        Ok(topic_metadata.partitions().len())
+    }
+
+    async fn assign(&mut self, topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<()> {
+        let tpl = topic_partition_list(topic, offsets)?;
+        self.consumer
+            .incremental_assign(&tpl)
+            .map_err(|e| to_error("failed to assign topic", e))
+    }
+
+    async fn unassign(&mut self, topic: &str) -> Result<()> {
+        let assignment = self
+            .consumer
+            .assignment()
+            .map_err(|e| to_error("failed to get assignment", e))?;
+        let mut tpl = TopicPartitionList::new();
+        for elem in assignment.elements_for_topic(topic) {
+            tpl.add_partition(topic, elem.partition());
+        }
+        if tpl.count() == 0 {
+            return Ok(());
+        }
+        self.consumer
+            .incremental_unassign(&tpl)
+            .map_err(|e| to_error("failed to unassign topic", e))
+    }
+
+    async fn poll(&mut self, max_messages: usize) -> Result<Vec<KafkaMessage>> {
+        let to_kafka_message = |message: &rdkafka::message::BorrowedMessage<'_>| KafkaMessage {
+            topic: message.topic().to_string(),
+            partition: message.partition(),
+            offset: message.offset() as u64,
+            payload: message.payload().map(|payload| payload.to_vec()),
+        };
+
+        // Block for the first message, and drain whatever is already buffered afterwards.
+        let first_message = self
+            .consumer
+            .recv()
+            .await
+            .map_err(|e| to_error("failed to consume message", e))?;
+        let mut messages = vec![to_kafka_message(&first_message)];
+        drop(first_message);
+        while messages.len() < max_messages {
+            match self.consumer.recv().now_or_never() {
+                Some(Ok(message)) => messages.push(to_kafka_message(&message)),
+                // Stop draining on error, messages already consumed still need to be returned.
+                Some(Err(_)) | None => break,
+            }
+        }
+        Ok(messages)
+    }
+
+    async fn commit_offsets(&mut self, topic: &str, offsets: &BTreeMap<i32, u64>) -> Result<()> {
+        let tpl = topic_partition_list(topic, offsets)?;
+        self.consumer
+            .commit(&tpl, CommitMode::Async)
+            .map_err(|e| to_error("failed to commit offsets", e))
+    }
+}
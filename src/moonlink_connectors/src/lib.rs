@@ -1,10 +1,12 @@
 pub mod error;
+pub mod kafka_ingest;
 pub mod pg_replicate;
 mod replication_connection;
 mod replication_manager;
 pub mod rest_ingest;
 
 pub use error::*;
+pub use kafka_ingest::KAFKA_URI_PREFIX;
 pub use pg_replicate::postgres_source::PostgresSourceError;
 pub use replication_connection::{ReplicationConnection, SourceType};
 pub use replication_manager::ReplicationManager;
@@ -6,11 +6,15 @@ pub mod initial_copy;
 pub mod initial_copy_writer;
 pub mod moonlink_sink;
 pub mod postgres_source;
+pub mod schema_diff;
 pub mod table;
+pub mod table_filter;
 pub mod table_init;
 pub mod util;
 
-use crate::pg_replicate::clients::postgres::{build_tls_connector, ReplicationClient};
+use crate::pg_replicate::clients::postgres::{
+    build_tls_connector, ReplicationClient, ReplicationClientError,
+};
 use crate::pg_replicate::conversions::cdc_event::{CdcEvent, CdcEventConversionError};
 use crate::pg_replicate::initial_copy::copy_table_stream;
 use crate::pg_replicate::initial_copy::{InitialCopyConfig, InitialCopyReaderConfig};
@@ -19,12 +23,16 @@ use crate::pg_replicate::postgres_source::{
     CdcStreamConfig, CdcStreamError, PostgresSource, PostgresSourceError,
 };
 use crate::pg_replicate::table::{SrcTableId, TableName, TableSchema};
+use crate::pg_replicate::table_filter::{
+    can_push_down, project_table_schema, publication_column_list, publication_row_filter,
+    validate_source_filter, TableFilter,
+};
 use crate::pg_replicate::table_init::{build_table_components, TableComponents};
 use crate::Result;
 use futures::StreamExt;
 use moonlink::{
-    CommitState, MooncakeTableId, MoonlinkTableConfig, ObjectStorageCache, ReadStateFilepathRemap,
-    ReplicationState, TableEvent, WalManager,
+    CommitState, MemoryAccountant, MooncakeTableId, MoonlinkTableConfig, ObjectStorageCache,
+    ReadStateFilepathRemap, ReplicationState, SourceFilterConfig, TableEvent, WalManager,
 };
 use native_tls::{Certificate, TlsConnector};
 use pg_escape::{quote_identifier, quote_literal};
@@ -63,7 +71,10 @@ fn is_transport_like(sqlstate: Option<&SqlState>) -> bool {
 pub enum PostgresReplicationCommand {
     AddTable {
         src_table_id: SrcTableId,
+        /// Schema of rows in cdc events.
         schema: TableSchema,
+        /// Filters applied on rows in cdc events.
+        table_filter: TableFilter,
         event_sender: mpsc::Sender<TableEvent>,
         commit_state: Arc<CommitState>,
         flush_lsn_rx: watch::Receiver<u64>,
@@ -85,10 +96,12 @@ pub struct PostgresConnection {
     pub cmd_rx: Option<mpsc::Receiver<PostgresReplicationCommand>>,
     pub replication_state: Arc<ReplicationState>,
     pub retry_handles: Vec<JoinHandle<Result<()>>>,
+    /// Shared by all tables, CDC stream consumption pauses when memory usage exceeds hard limit.
+    pub memory_accountant: MemoryAccountant,
 }
 
 impl PostgresConnection {
-    pub async fn new(uri: String) -> Result<Self> {
+    pub async fn new(uri: String, memory_accountant: MemoryAccountant) -> Result<Self> {
         debug!(%uri, "initializing postgres connection");
 
         let tls = build_tls_connector().map_err(PostgresSourceError::from)?;
@@ -155,6 +168,7 @@ impl PostgresConnection {
             cmd_rx: Some(cmd_rx),
             replication_state: ReplicationState::new(),
             retry_handles: Vec::new(),
+            memory_accountant,
         })
     }
 
@@ -240,10 +254,13 @@ impl PostgresConnection {
 
     #[must_use]
     /// Perform initial copy of existing table data
+    /// Source filters are pushed down into the publication if `push_down_filter` is true, while the initial copy always filters on moonlink side.
     /// Returns true if initial copy was performed, false otherwise.
     pub async fn perform_initial_copy(
         &self,
         schema: &TableSchema,
+        source_filter_config: &SourceFilterConfig,
+        push_down_filter: bool,
         event_sender: mpsc::Sender<TableEvent>,
         is_recovery: bool,
         commit_lsn_tx: Arc<CommitState>,
@@ -256,6 +273,15 @@ impl PostgresConnection {
         // Check if there are existing rows
         let row_count = copy_source.get_row_count(&schema.table_name).await?;
 
+        let (column_list, row_filter) = if push_down_filter {
+            (
+                publication_column_list(source_filter_config, schema),
+                publication_row_filter(source_filter_config),
+            )
+        } else {
+            (None, None)
+        };
+
         // Only perform initial copy for new tables, not during recovery.
         // Early return if there are no rows to copy.
         if !is_recovery && row_count > 0 {
@@ -267,7 +293,11 @@ impl PostgresConnection {
             // Add table to publication first to begin accumulating any cdc events.
             // We can check where our initial copy started from and discard any rows we have already seen.
             copy_source
-                .add_table_to_publication(&schema.table_name)
+                .add_table_to_publication(
+                    &schema.table_name,
+                    column_list.as_deref(),
+                    row_filter.as_deref(),
+                )
                 .await?;
 
             let ic_config = InitialCopyConfig {
@@ -277,13 +307,18 @@ impl PostgresConnection {
                 },
                 writer: Default::default(),
             };
-            let progress =
-                copy_table_stream(schema.clone(), &event_sender, table_base_path, ic_config)
-                    .await
-                    .expect(&format!(
-                        "failed to copy table for src_table_id: {}",
-                        src_table_id
-                    ));
+            let progress = copy_table_stream(
+                schema.clone(),
+                TableFilter::new(source_filter_config.clone(), schema),
+                &event_sender,
+                table_base_path,
+                ic_config,
+            )
+            .await
+            .expect(&format!(
+                "failed to copy table for src_table_id: {}",
+                src_table_id
+            ));
 
             if let Err(e) = event_sender
                 .send(TableEvent::FinishInitialCopy {
@@ -302,7 +337,11 @@ impl PostgresConnection {
         } else {
             // If there are no rows to copy, we still need to add the table to publication.
             copy_source
-                .add_table_to_publication(&schema.table_name)
+                .add_table_to_publication(
+                    &schema.table_name,
+                    column_list.as_deref(),
+                    row_filter.as_deref(),
+                )
                 .await?;
             Ok(false)
         }
@@ -394,6 +433,7 @@ impl PostgresConnection {
         &self,
         src_table_id: SrcTableId,
         schema: TableSchema,
+        table_filter: TableFilter,
         event_sender: mpsc::Sender<TableEvent>,
         commit_state: Arc<CommitState>,
         flush_lsn_rx: watch::Receiver<u64>,
@@ -403,6 +443,7 @@ impl PostgresConnection {
         let cmd = PostgresReplicationCommand::AddTable {
             src_table_id,
             schema,
+            table_filter,
             event_sender,
             commit_state,
             flush_lsn_rx,
@@ -489,19 +530,70 @@ impl PostgresConnection {
         object_storage_cache: ObjectStorageCache,
     ) -> Result<(SrcTableId, crate::pg_replicate::table_init::TableResources)> {
         debug!(table_name, "adding table");
-        // TODO: We should not naively alter the replica identity of a table. We should only do this if we are sure that the table does not already have a FULL replica identity. [https://github.com/Mooncake-Labs/moonlink/issues/104]
-        self.alter_table_replica_identity(table_name).await?;
-        let table_schema = self
+        let mut table_schema = self
             .source
             .fetch_table_schema(None, Some(table_name), None)
             .await?;
+        // Respect the existing replica identity, and only fall back to FULL if the table cannot be identified by key and it's explicitly opted in.
+        if table_schema.requires_replica_identity_full() {
+            if !moonlink_table_config
+                .mooncake_table_config
+                .replica_identity_full
+            {
+                return Err(PostgresSourceError::from(
+                    ReplicationClientError::MissingReplicaIdentityKey(table_schema.table_name),
+                )
+                .into());
+            }
+            self.alter_table_replica_identity(table_name).await?;
+            table_schema = self
+                .source
+                .fetch_table_schema(None, Some(table_name), None)
+                .await?;
+        }
+
+        let source_filter_config = moonlink_table_config
+            .mooncake_table_config
+            .source_filter_config
+            .clone();
+        if let Err(reason) = validate_source_filter(&source_filter_config, &table_schema) {
+            return Err(
+                PostgresSourceError::from(ReplicationClientError::InvalidTableFilter(
+                    table_schema.table_name,
+                    reason,
+                ))
+                .into(),
+            );
+        }
+        // Push down filters into the publication when supported, otherwise filter cdc events on moonlink side.
+        let push_down_filter = !source_filter_config.is_empty()
+            && can_push_down(
+                &source_filter_config,
+                &table_schema,
+                self.source.get_server_version_num().await?,
+            );
+        let (cdc_table_schema, cdc_table_filter) = if push_down_filter {
+            (
+                project_table_schema(&source_filter_config, &table_schema),
+                TableFilter::pushed_down(),
+            )
+        } else {
+            (
+                table_schema.clone(),
+                TableFilter::new(source_filter_config.clone(), &table_schema),
+            )
+        };
 
         let (arrow_schema, identity) =
-            crate::pg_replicate::util::postgres_schema_to_moonlink_schema(&table_schema);
+            crate::pg_replicate::util::postgres_schema_to_moonlink_schema(&project_table_schema(
+                &source_filter_config,
+                &table_schema,
+            ));
         moonlink_table_config.mooncake_table_config.row_identity = identity;
         let table_components = TableComponents {
             read_state_filepath_remap,
             object_storage_cache,
+            memory_accountant: self.memory_accountant.clone(),
             moonlink_table_config: moonlink_table_config.clone(),
         };
 
@@ -526,7 +618,8 @@ impl PostgresConnection {
         let ready_rx = self
             .add_table_to_replication(
                 table_schema.src_table_id,
-                table_schema.clone(),
+                cdc_table_schema,
+                cdc_table_filter,
                 table_resources.event_sender.clone(),
                 commit_lsn_tx,
                 table_resources
@@ -549,6 +642,8 @@ impl PostgresConnection {
         let initial_copy_performed = self
             .perform_initial_copy(
                 &table_schema,
+                &source_filter_config,
+                push_down_filter,
                 table_resources.event_sender.clone(),
                 is_recovery,
                 commit_lsn_tx_for_copy,
@@ -635,9 +730,10 @@ impl PostgresConnection {
         let uri = self.uri.clone();
         let cfg = self.source.get_cdc_stream_config().unwrap();
         let source = self.source.clone();
+        let over_hard_limit_rx = self.memory_accountant.subscribe_hard_limit();
 
         tokio::spawn(async move {
-            run_event_loop(cfg, sink, receiver, source)
+            run_event_loop(cfg, sink, receiver, source, over_hard_limit_rx)
                 .await
                 .map_err(|err| {
                     error!("Postgres replication eventloop failed: {:?}", err);
@@ -679,7 +775,9 @@ pub async fn run_event_loop(
     mut sink: Sink,
     mut cmd_rx: mpsc::Receiver<PostgresReplicationCommand>,
     postgres_source: Arc<PostgresSource>,
+    mut over_hard_limit_rx: watch::Receiver<bool>,
 ) -> Result<()> {
+    let publication = cfg.publication.clone();
     // Persist across reconnects
     let mut saved_schemas: Vec<TableSchema> = Vec::new();
     let mut flush_lsn_rxs: HashMap<SrcTableId, watch::Receiver<u64>> = HashMap::new();
@@ -760,8 +858,8 @@ pub async fn run_event_loop(
                     }
                 },
                 Some(cmd) = cmd_rx.recv() => match cmd {
-                    PostgresReplicationCommand::AddTable { src_table_id, schema, event_sender, commit_state, flush_lsn_rx, wal_flush_lsn_rx, ready_tx } => {
-                        sink.add_table(src_table_id, event_sender, commit_state, &schema);
+                    PostgresReplicationCommand::AddTable { src_table_id, schema, table_filter, event_sender, commit_state, flush_lsn_rx, wal_flush_lsn_rx, ready_tx } => {
+                        sink.add_table(src_table_id, event_sender, commit_state, &schema, table_filter);
                         flush_lsn_rxs.insert(src_table_id, flush_lsn_rx);
                         wal_flush_lsn_rxs.insert(src_table_id, wal_flush_lsn_rx);
                         stream.as_mut().add_table_schema(schema);
@@ -781,7 +879,10 @@ pub async fn run_event_loop(
                         break 'outer;
                     }
                 },
-                (n, last_end_lsn) = stream.as_mut().next_batch_msgs(&mut batch, MAX_EVENTS_PER_WAKE) => {
+                // Pause consuming CDC stream when memory usage exceeds hard limit, status updates keep the connection alive meanwhile.
+                // An open non-streaming transaction keeps being consumed until its commit, since its writes are only flushed at commit;
+                // large transactions are streamed by postgres, whose in-progress writes get flushed on memory pressure.
+                (n, last_end_lsn) = stream.as_mut().next_batch_msgs(&mut batch, MAX_EVENTS_PER_WAKE), if !*over_hard_limit_rx.borrow() || sink.is_in_transaction() => {
                     if n == 0 {
                         // If we produced no events but did observe frames (skipped), just continue.
                         if last_end_lsn.is_some() {
@@ -802,7 +903,7 @@ pub async fn run_event_loop(
                                 continue;
                             }
                             Err(CdcStreamError::CdcEventConversion(CdcEventConversionError::MessageNotSupported)) => {
-                                // TODO: Add support for Truncate and Origin messages and remove this.
+                                // TODO: Add support for Origin messages and remove this.
                                 warn!("message not supported");
                                 continue;
                             }
@@ -816,8 +917,13 @@ pub async fn run_event_loop(
                             }
                             Ok(event) => {
                                 if let Some(SchemaChangeRequest(src_table_id)) = sink.process_cdc_event(event).await.unwrap() {
-                                    let table_schema = postgres_source.fetch_table_schema(Some(src_table_id), None, None).await?;
-                                    sink.alter_table(src_table_id, &table_schema).await;
+                                    // Pushed down column lists decide columns in cdc events, which are only available since postgres 15.
+                                    let publication = sink.is_filter_pushed_down(src_table_id).then_some(publication.as_str());
+                                    let table_schema = postgres_source.fetch_table_schema(Some(src_table_id), None, publication).await?;
+                                    // Tables with incompatible schema changes stop replicating rows and get marked for resync, other tables keep replicating.
+                                    if let Err(e) = sink.alter_table(src_table_id, &table_schema).await {
+                                        error!(src_table_id, table_name = %table_schema.table_name, error = %e, "stop replicating table which requires resync");
+                                    }
                                     stream.as_mut().update_table_schema(table_schema);
                                 }
                             }
@@ -828,6 +934,9 @@ pub async fn run_event_loop(
                         last_seen_end_lsn = Some(end);
                     }
                 },
+                Ok(()) = over_hard_limit_rx.changed() => {
+                    debug!(paused = *over_hard_limit_rx.borrow(), "cdc stream consumption pause state changed");
+                },
                 _ = &mut connection_pin => {
                     error!("replication connection closed");
                     // Snapshot schemas before reconnecting
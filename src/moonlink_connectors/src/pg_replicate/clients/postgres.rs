@@ -1,7 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use crate::pg_replicate::conversions::text::TextFormatConverter;
-use crate::pg_replicate::table::{ColumnSchema, LookupKey, SrcTableId, TableName, TableSchema};
+use crate::pg_replicate::table::{
+    ColumnSchema, LookupKey, ReplicaIdentity, SrcTableId, TableName, TableSchema,
+};
 use futures::future::err;
 use native_tls::TlsConnector;
 use pg_escape::{quote_identifier, quote_literal};
@@ -51,6 +53,9 @@ pub enum ReplicationClientError {
     #[error("type modifier column is not a valid u32")]
     TypeModifierColumnNotI32,
 
+    #[error("attnum column is not a valid i16")]
+    AttnumColumnNotI16,
+
     #[error("column {0}'s type with oid {1} in relation {2} is not supported")]
     UnsupportedType(String, u32, String),
 
@@ -60,6 +65,15 @@ pub enum ReplicationClientError {
     #[error("table with oid {0} doesn't exist")]
     MissingTableId(SrcTableId),
 
+    #[error("table {0} has neither primary key nor replica identity index, set its replica identity or opt in replica_identity_full")]
+    MissingReplicaIdentityKey(TableName),
+
+    #[error("invalid column or row filter for table {0}: {1}")]
+    InvalidTableFilter(TableName, String),
+
+    #[error("invalid server version {0}")]
+    InvalidServerVersion(String),
+
     #[error("not a valid PgLsn")]
     InvalidPgLsn,
 
@@ -137,14 +151,29 @@ impl ReplicationClient {
         Ok(())
     }
 
+    /// Add table to publication, optionally with a column list and a row filter expression, which requires postgres 15 or above.
     pub async fn add_table_to_publication(
         &mut self,
         table_name: &TableName,
+        column_list: Option<&[String]>,
+        row_filter: Option<&str>,
     ) -> Result<(), ReplicationClientError> {
-        let query = format!(
-            "ALTER PUBLICATION moonlink_pub ADD TABLE {};",
+        let mut query = format!(
+            "ALTER PUBLICATION moonlink_pub ADD TABLE {}",
             table_name.as_quoted_identifier()
         );
+        if let Some(column_list) = column_list {
+            let columns = column_list
+                .iter()
+                .map(|column| quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", ");
+            query.push_str(&format!(" ({columns})"));
+        }
+        if let Some(row_filter) = row_filter {
+            query.push_str(&format!(" WHERE ({row_filter})"));
+        }
+        query.push(';');
         self.postgres_client.simple_query(&query).await?;
         Ok(())
     }
@@ -299,7 +328,8 @@ impl ReplicationClient {
 
         let column_info_query = format!(
             "{}
-            select a.attname,
+            select a.attnum,
+                a.attname,
                 a.atttypid,
                 a.atttypmod,
                 a.attnotnull,
@@ -328,6 +358,15 @@ impl ReplicationClient {
             .await?
         {
             if let SimpleQueryMessage::Row(row) = message {
+                let attnum = row
+                    .try_get("attnum")?
+                    .ok_or(ReplicationClientError::MissingColumn(
+                        "attnum".to_string(),
+                        "pg_attribute".to_string(),
+                    ))?
+                    .parse()
+                    .map_err(|_| ReplicationClientError::AttnumColumnNotI16)?;
+
                 let name = row
                     .try_get("attname")?
                     .ok_or(ReplicationClientError::MissingColumn(
@@ -397,6 +436,7 @@ impl ReplicationClient {
                         == "f";
 
                 column_schemas.push(ColumnSchema {
+                    attnum,
                     name,
                     typ,
                     modifier,
@@ -541,10 +581,22 @@ impl ReplicationClient {
         &self,
         table_id: SrcTableId,
         published_column_names: HashSet<String>,
+        replica_identity: ReplicaIdentity,
     ) -> Result<Option<LookupKey>, ReplicationClientError> {
         let index_rows = self.fetch_index_rows(table_id).await?;
 
         for index_row in index_rows {
+            // Old tuple only carries columns of the replica identity, so the lookup key has to be the same index.
+            let index_usable = match replica_identity {
+                ReplicaIdentity::Default => index_row.get("indisprimary") == Some("t"),
+                ReplicaIdentity::Index => index_row.get("indisreplident") == Some("t"),
+                ReplicaIdentity::Full => true,
+                ReplicaIdentity::Nothing => false,
+            };
+            if !index_usable {
+                continue;
+            }
+
             let index_name = match index_row.get("index_name") {
                 Some(name) => name.to_string(),
                 None => continue,
@@ -595,6 +647,7 @@ impl ReplicationClient {
                 i.indkey,
                 i.indisunique,
                 i.indisprimary,
+                i.indisreplident,
                 i.indpred IS NOT NULL AS is_partial,
                 COALESCE(con.condeferrable, false) AS is_deferrable
             FROM pg_index i
@@ -656,10 +709,14 @@ impl ReplicationClient {
         &self,
         src_table_id: SrcTableId,
         column_schemas: &Vec<ColumnSchema>,
+        replica_identity: ReplicaIdentity,
     ) -> Result<LookupKey, ReplicationClientError> {
         let column_names: HashSet<String> =
             column_schemas.iter().map(|cs| cs.name.clone()).collect();
-        if let Some(unique_index_key) = self.fetch_lookup_key(src_table_id, column_names).await? {
+        if let Some(unique_index_key) = self
+            .fetch_lookup_key(src_table_id, column_names, replica_identity)
+            .await?
+        {
             return Ok(unique_index_key);
         }
 
@@ -676,20 +733,74 @@ impl ReplicationClient {
         let column_schemas = self
             .get_column_schemas(src_table_id, &table_name, publication)
             .await?;
-        let lookup_key = self.get_lookup_key(src_table_id, &column_schemas).await?;
+        let replica_identity = self.get_replica_identity(src_table_id).await?;
+        let lookup_key = self
+            .get_lookup_key(src_table_id, &column_schemas, replica_identity)
+            .await?;
 
         let table_schema = TableSchema {
             table_name,
             src_table_id,
             column_schemas,
             lookup_key,
+            replica_identity,
         };
         Ok(table_schema)
     }
 
+    /// Returns the server version in `server_version_num` format, e.g. 150004 for 15.4.
+    pub async fn get_server_version_num(&self) -> Result<i32, ReplicationClientError> {
+        for message in self
+            .postgres_client
+            .simple_query("show server_version_num")
+            .await?
+        {
+            if let SimpleQueryMessage::Row(row) = message {
+                let server_version_num = row.try_get("server_version_num")?.ok_or(
+                    ReplicationClientError::MissingColumn(
+                        "server_version_num".to_string(),
+                        "pg_settings".to_string(),
+                    ),
+                )?;
+                return server_version_num.parse().map_err(|_| {
+                    ReplicationClientError::InvalidServerVersion(server_version_num.to_string())
+                });
+            }
+        }
+
+        Err(ReplicationClientError::MissingColumn(
+            "server_version_num".to_string(),
+            "pg_settings".to_string(),
+        ))
+    }
+
+    /// Returns the replica identity of a table.
+    pub async fn get_replica_identity(
+        &self,
+        src_table_id: SrcTableId,
+    ) -> Result<ReplicaIdentity, ReplicationClientError> {
+        let query = format!("select relreplident from pg_class where oid = {src_table_id}");
+
+        for message in self.postgres_client.simple_query(&query).await? {
+            if let SimpleQueryMessage::Row(row) = message {
+                let replica_identity =
+                    row.try_get("relreplident")?
+                        .ok_or(ReplicationClientError::MissingColumn(
+                            "relreplident".to_string(),
+                            "pg_class".to_string(),
+                        ))?;
+                return ReplicaIdentity::from_relreplident(replica_identity).ok_or(
+                    ReplicationClientError::ReplicaIdentityNotSupported(
+                        replica_identity.to_string(),
+                    ),
+                );
+            }
+        }
+
+        Err(ReplicationClientError::MissingTableId(src_table_id))
+    }
+
     /// Returns the src table id (called relation id in Postgres) of a table
-    /// Also checks whether the replica identity is default or full and
-    /// returns an error if not.
     pub async fn get_src_table_id(
         &self,
         table: &TableName,
@@ -698,8 +809,7 @@ impl ReplicationClient {
         let quoted_name = quote_literal(&table.name);
 
         let table_info_query = format!(
-            "select c.oid,
-                c.relreplident
+            "select c.oid
             from pg_class c
             join pg_namespace n
                 on (c.relnamespace = n.oid)
@@ -711,19 +821,6 @@ impl ReplicationClient {
 
         for message in self.postgres_client.simple_query(&table_info_query).await? {
             if let SimpleQueryMessage::Row(row) = message {
-                let replica_identity =
-                    row.try_get("relreplident")?
-                        .ok_or(ReplicationClientError::MissingColumn(
-                            "relreplident".to_string(),
-                            "pg_class".to_string(),
-                        ))?;
-
-                if !(replica_identity == "f") {
-                    return Err(ReplicationClientError::ReplicaIdentityNotSupported(
-                        replica_identity.to_string(),
-                    ));
-                }
-
                 let oid: u32 = row
                     .try_get("oid")?
                     .ok_or(ReplicationClientError::MissingColumn(
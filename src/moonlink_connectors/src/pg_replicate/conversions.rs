@@ -34,6 +34,8 @@ pub enum Cell {
     /// Represents a PostgreSQL composite type (custom type with multiple fields)
     /// Each Cell in the Vec represents one field of the composite type
     Composite(Vec<Cell>),
+    /// Represents an unchanged TOAST value in an update, which postgres doesn't send over replication.
+    UnchangedToast,
 }
 
 #[derive(Debug, Clone)]
@@ -4,7 +4,7 @@ use std::{collections::HashMap, str::Utf8Error};
 use postgres_replication::protocol::{
     BeginBody, CommitBody, DeleteBody, InsertBody, LogicalReplicationMessage, PrimaryKeepAliveBody,
     RelationBody, ReplicationMessage, StreamAbortBody, StreamCommitBody, StreamStartBody,
-    StreamStopBody, TupleData, TypeBody, UpdateBody,
+    StreamStopBody, TruncateBody, TupleData, TypeBody, UpdateBody,
 };
 use thiserror::Error;
 
@@ -46,6 +46,21 @@ pub enum CdcEventConversionError {
 pub struct CdcEventConverter;
 
 impl CdcEventConverter {
+    fn try_from_tuple_data(
+        column_schema: &ColumnSchema,
+        tuple_data: &TupleData,
+    ) -> Result<Cell, CdcEventConversionError> {
+        let cell = match tuple_data {
+            TupleData::Null => Cell::Null,
+            TupleData::UnchangedToast => Cell::UnchangedToast,
+            TupleData::Text(bytes) => {
+                let str = str::from_utf8(&bytes[..])?;
+                TextFormatConverter::try_from_str(&column_schema.typ, str)?
+            }
+        };
+        Ok(cell)
+    }
+
     fn try_from_tuple_data_slice(
         column_schemas: &[ColumnSchema],
         tuple_data: &[TupleData],
@@ -53,15 +68,26 @@ impl CdcEventConverter {
         let mut values = Vec::with_capacity(column_schemas.len());
 
         for (i, column_schema) in column_schemas.iter().enumerate() {
-            let cell = match &tuple_data[i] {
-                TupleData::Null => Cell::Null,
-                TupleData::UnchangedToast => TextFormatConverter::default_value(&column_schema.typ),
-                TupleData::Text(bytes) => {
-                    let str = str::from_utf8(&bytes[..])?;
-                    TextFormatConverter::try_from_str(&column_schema.typ, str)?
-                }
+            values.push(Self::try_from_tuple_data(column_schema, &tuple_data[i])?);
+        }
+
+        Ok(TableRow { values })
+    }
+
+    /// Convert one tuple of an update, whose unchanged TOAST columns are taken from the other tuple.
+    fn try_from_update_tuple_data_slice(
+        column_schemas: &[ColumnSchema],
+        tuple_data: &[TupleData],
+        other_tuple_data: &[TupleData],
+    ) -> Result<TableRow, CdcEventConversionError> {
+        let mut values = Vec::with_capacity(column_schemas.len());
+
+        for (i, column_schema) in column_schemas.iter().enumerate() {
+            let tuple_data = match &tuple_data[i] {
+                TupleData::UnchangedToast => &other_tuple_data[i],
+                tuple_data => tuple_data,
             };
-            values.push(cell);
+            values.push(Self::try_from_tuple_data(column_schema, tuple_data)?);
         }
 
         Ok(TableRow { values })
@@ -78,18 +104,35 @@ impl CdcEventConverter {
         Ok(CdcEvent::Insert((src_table_id, row, insert_body.xid())))
     }
 
-    // TODO: handle when identity columns are changed
+    /// Old tuple is only sent with REPLICA IDENTITY FULL, or when identity columns are changed, in which case it only contains identity columns.
+    /// If old tuple is absent, identity columns are unchanged and the new tuple identifies the old row.
     fn try_from_update_body(
         src_table_id: SrcTableId,
         column_schemas: &[ColumnSchema],
         update_body: UpdateBody,
     ) -> Result<CdcEvent, CdcEventConversionError> {
+        let new_tuple_data = update_body.new_tuple().tuple_data();
         let old_row = update_body
             .old_tuple()
-            .map(|tuple| Self::try_from_tuple_data_slice(column_schemas, tuple.tuple_data()))
+            .or(update_body.key_tuple())
+            .map(|tuple| {
+                Self::try_from_update_tuple_data_slice(
+                    column_schemas,
+                    tuple.tuple_data(),
+                    new_tuple_data,
+                )
+            })
             .transpose()?;
-        let new_row =
-            Self::try_from_tuple_data_slice(column_schemas, update_body.new_tuple().tuple_data())?;
+        // Only full old tuple carries unchanged TOAST values; key tuple sends other columns as null, so they're kept
+        // unchanged for the table to fill in from the current row.
+        let new_row = match update_body.old_tuple() {
+            Some(old_tuple) => Self::try_from_update_tuple_data_slice(
+                column_schemas,
+                new_tuple_data,
+                old_tuple.tuple_data(),
+            )?,
+            None => Self::try_from_tuple_data_slice(column_schemas, new_tuple_data)?,
+        };
 
         Ok(CdcEvent::Update((
             src_table_id,
@@ -104,6 +147,7 @@ impl CdcEventConverter {
         column_schemas: &[ColumnSchema],
         delete_body: DeleteBody,
     ) -> Result<CdcEvent, CdcEventConversionError> {
+        // Key tuple only carries identity columns, with other columns sent as null.
         let tuple = delete_body
             .key_tuple()
             .or(delete_body.old_tuple())
@@ -114,6 +158,24 @@ impl CdcEventConverter {
         Ok(CdcEvent::Delete((src_table_id, row, delete_body.xid())))
     }
 
+    fn try_from_truncate_body(
+        table_schemas: &HashMap<SrcTableId, TableSchema>,
+        truncate_body: TruncateBody,
+    ) -> Result<CdcEvent, CdcEventConversionError> {
+        // A cascaded truncate could reach tables not replicated by moonlink, skip them.
+        let src_table_ids = truncate_body
+            .rel_ids()
+            .iter()
+            .copied()
+            .filter(|table_id| table_schemas.contains_key(table_id))
+            .collect::<Vec<_>>();
+        Ok(CdcEvent::Truncate((
+            src_table_ids,
+            TruncateOptions::from_bits(truncate_body.options()),
+            truncate_body.xid(),
+        )))
+    }
+
     pub fn try_from(
         value: ReplicationMessage<LogicalReplicationMessage>,
         table_schemas: &HashMap<SrcTableId, TableSchema>,
@@ -165,8 +227,8 @@ impl CdcEventConverter {
                         delete_body,
                     )?)
                 }
-                LogicalReplicationMessage::Truncate(_) => {
-                    Err(CdcEventConversionError::MessageNotSupported)
+                LogicalReplicationMessage::Truncate(truncate_body) => {
+                    Ok(Self::try_from_truncate_body(table_schemas, truncate_body)?)
                 }
                 LogicalReplicationMessage::StreamStart(stream_start_body) => {
                     Ok(CdcEvent::StreamStart(stream_start_body))
@@ -197,6 +259,7 @@ pub enum CdcEvent {
     Insert((SrcTableId, TableRow, Option<u32>)),
     Update((SrcTableId, Option<TableRow>, TableRow, Option<u32>)),
     Delete((SrcTableId, TableRow, Option<u32>)),
+    Truncate((Vec<SrcTableId>, TruncateOptions, Option<u32>)),
     Relation(RelationBody),
     Type(TypeBody),
     PrimaryKeepAlive(PrimaryKeepAliveBody),
@@ -205,3 +268,24 @@ pub enum CdcEvent {
     StreamCommit(StreamCommitBody),
     StreamAbort(StreamAbortBody),
 }
+
+/// Options carried by a logical replication truncate message.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TruncateOptions {
+    /// `TRUNCATE ... CASCADE`
+    pub cascade: bool,
+    /// `TRUNCATE ... RESTART IDENTITY`
+    pub restart_identity: bool,
+}
+
+impl TruncateOptions {
+    const CASCADE: i8 = 1;
+    const RESTART_IDENTITY: i8 = 2;
+
+    pub fn from_bits(options: i8) -> Self {
+        Self {
+            cascade: options & Self::CASCADE != 0,
+            restart_identity: options & Self::RESTART_IDENTITY != 0,
+        }
+    }
+}
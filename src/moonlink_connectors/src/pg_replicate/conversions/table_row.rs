@@ -9,7 +9,7 @@ use crate::pg_replicate::conversions::text::TextFormatConverter;
 
 use super::{text::FromTextError, Cell};
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct TableRow {
     pub values: Vec<Cell>,
 }
@@ -135,12 +135,14 @@ mod tests {
     fn parse_basic_row() {
         let schemas = vec![
             ColumnSchema {
+                attnum: 1,
                 name: "id".into(),
                 typ: Type::INT4,
                 modifier: 0,
                 nullable: false,
             },
             ColumnSchema {
+                attnum: 2,
                 name: "text".into(),
                 typ: Type::TEXT,
                 modifier: 0,
@@ -158,12 +160,14 @@ mod tests {
     fn parse_escaped_and_null_values() {
         let schemas = vec![
             ColumnSchema {
+                attnum: 1,
                 name: "a".into(),
                 typ: Type::TEXT,
                 modifier: 0,
                 nullable: true,
             },
             ColumnSchema {
+                attnum: 2,
                 name: "b".into(),
                 typ: Type::TEXT,
                 modifier: 0,
@@ -180,6 +184,7 @@ mod tests {
     #[test]
     fn unterminated_row_error() {
         let schemas = vec![ColumnSchema {
+            attnum: 1,
             name: "a".into(),
             typ: Type::INT4,
             modifier: 0,
@@ -195,6 +200,7 @@ mod tests {
     #[test]
     fn num_cols_mismatch_error() {
         let schemas = vec![ColumnSchema {
+            attnum: 1,
             name: "a".into(),
             typ: Type::INT4,
             modifier: 0,
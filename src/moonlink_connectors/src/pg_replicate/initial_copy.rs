@@ -6,6 +6,7 @@ use crate::pg_replicate::initial_copy_writer::{
 use crate::pg_replicate::postgres_source::PostgresSource;
 use crate::pg_replicate::postgres_source::PostgresSourceError;
 use crate::pg_replicate::table::{ColumnSchema, LookupKey, SrcTableId, TableName, TableSchema};
+use crate::pg_replicate::table_filter::TableFilter;
 use crate::pg_replicate::util::postgres_schema_to_moonlink_schema;
 use crate::{Error, Result};
 use futures::StreamExt;
@@ -54,14 +55,17 @@ struct CtidShard {
 }
 
 /// Reads rows using parallel readers and sends them to the provided `event_sender`.
+/// Rows are filtered and projected by `table_filter`, which is resolved against `table_schema`.
 pub async fn copy_table_stream(
     table_schema: TableSchema,
+    table_filter: TableFilter,
     event_sender: &Sender<TableEvent>,
     table_base_path: &str,
     config: InitialCopyConfig,
 ) -> Result<CopyProgress> {
-    // Convert PostgreSQL schema to Arrow schema
-    let (arrow_schema, _identity_prop) = postgres_schema_to_moonlink_schema(&table_schema);
+    // Convert mirrored PostgreSQL schema to Arrow schema
+    let (arrow_schema, _identity_prop) =
+        postgres_schema_to_moonlink_schema(&table_filter.project_schema(&table_schema));
     let arrow_schema = Arc::new(arrow_schema);
 
     // Prepare writer config
@@ -111,6 +115,7 @@ pub async fn copy_table_stream(
             config.reader.uri.clone(),
             snapshot_id,
             table_schema.clone(),
+            table_filter,
             shards,
             batch_tx.clone(),
             writer_cfg.max_rows_per_batch,
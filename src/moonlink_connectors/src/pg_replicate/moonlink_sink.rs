@@ -1,20 +1,21 @@
 use crate::pg_replicate::util::PostgresTableRow;
 use crate::pg_replicate::{
-    conversions::{cdc_event::CdcEvent, table_row::TableRow},
+    conversions::{cdc_event::CdcEvent, table_row::TableRow, Cell},
+    schema_diff::{self, SchemaDiffError},
     table::{SrcTableId, TableSchema},
+    table_filter::TableFilter,
 };
 use moonlink::TableEvent;
 use moonlink::{CommitState, ReplicationState};
 use more_asserts as ma;
-use postgres_replication::protocol::Column as ReplicationColumn;
-use std::collections::HashMap;
 use std::collections::VecDeque;
+use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
 use std::sync::Arc;
 use tokio::sync::mpsc::{error::TrySendError, Sender};
 use tokio::sync::{mpsc, watch};
 use tokio_postgres::types::PgLsn;
-use tracing::{debug, warn};
+use tracing::{debug, error, warn};
 
 #[derive(Default)]
 struct TransactionState {
@@ -27,19 +28,21 @@ struct TransactionState {
     last_touched_table: Option<SrcTableId>,
 }
 
-#[derive(Eq, PartialEq)]
-struct ColumnInfo {
-    name: String,
-    typ: u32,
-    modifier: i32,
-}
 pub struct Sink {
     event_senders: HashMap<SrcTableId, Sender<TableEvent>>,
     commit_lsn_txs: HashMap<SrcTableId, Arc<CommitState>>,
     streaming_transactions_state: HashMap<u32, TransactionState>,
     transaction_state: TransactionState,
+    /// Whether a non-streaming transaction has begun but not committed yet.
+    in_transaction: bool,
     replication_state: Arc<ReplicationState>,
-    relation_cache: HashMap<SrcTableId, Vec<ColumnInfo>>,
+    /// Latest known schema for each table, used to detect schema changes from relation messages.
+    relation_cache: HashMap<SrcTableId, TableSchema>,
+    /// Column and row filters for each table, applied on rows before sending to moonlink.
+    table_filters: HashMap<SrcTableId, TableFilter>,
+    /// Tables which stop replicating rows due to incompatible schema changes, and need resync from source.
+    /// Commits still reach them, so transactions in flight at the schema change complete and their commit LSN keeps consistent.
+    tables_requiring_resync: HashSet<SrcTableId>,
     /// Cached sender for the last table used on the hot path.
     /// Avoids a HashMap lookup when consecutive rows target the same table.
     cached_event_sender: Option<(SrcTableId, Sender<TableEvent>)>,
@@ -77,14 +80,22 @@ impl Sink {
                 touched_tables: Vec::new(),
                 last_touched_table: None,
             },
+            in_transaction: false,
             replication_state,
             relation_cache: HashMap::new(),
+            table_filters: HashMap::new(),
+            tables_requiring_resync: HashSet::new(),
             cached_event_sender: None,
             streaming_last_key: None,
             max_keepalive_lsn_seen: 0,
         }
     }
 
+    /// Return whether a non-streaming transaction is open, whose writes can only be flushed after its commit is processed.
+    pub fn is_in_transaction(&self) -> bool {
+        self.in_transaction
+    }
+
     /// Reset the per-connection keepalive floor. Should be called after establishing a new CDC stream.
     pub fn reset_keepalive_floor(&mut self) {
         self.max_keepalive_lsn_seen = 0;
@@ -100,23 +111,34 @@ impl Sink {
         event_sender: Sender<TableEvent>,
         commit_lsn_tx: Arc<CommitState>,
         table_schema: &TableSchema,
+        table_filter: TableFilter,
     ) {
         self.event_senders.insert(src_table_id, event_sender);
         self.commit_lsn_txs.insert(src_table_id, commit_lsn_tx);
-        let columns = table_schema
-            .column_schemas
-            .iter()
-            .map(|c| ColumnInfo {
-                name: c.name.clone(),
-                typ: c.typ.oid(),
-                modifier: c.modifier,
-            })
-            .collect();
-        self.relation_cache.insert(src_table_id, columns);
+        self.relation_cache
+            .insert(src_table_id, table_schema.clone());
+        self.table_filters.insert(src_table_id, table_filter);
     }
     pub fn drop_table(&mut self, src_table_id: SrcTableId) {
         self.event_senders.remove(&src_table_id).unwrap();
         self.commit_lsn_txs.remove(&src_table_id).unwrap();
+        self.tables_requiring_resync.remove(&src_table_id);
+        self.table_filters.remove(&src_table_id);
+        self.clear_cached_event_sender(src_table_id);
+    }
+
+    /// Stop replicating rows of the table, which cannot follow the source schema.
+    fn mark_table_requiring_resync(&mut self, src_table_id: SrcTableId) {
+        self.clear_cached_event_sender(src_table_id);
+        self.tables_requiring_resync.insert(src_table_id);
+    }
+
+    /// Whether the table stops replicating rows and needs resync from source.
+    pub fn requires_resync(&self, src_table_id: SrcTableId) -> bool {
+        self.tables_requiring_resync.contains(&src_table_id)
+    }
+
+    fn clear_cached_event_sender(&mut self, src_table_id: SrcTableId) {
         if let Some((cached_id, _)) = &self.cached_event_sender {
             if *cached_id == src_table_id {
                 self.cached_event_sender = None;
@@ -124,31 +146,65 @@ impl Sink {
         }
     }
 
-    pub async fn alter_table(&mut self, src_table_id: SrcTableId, table_schema: &TableSchema) {
-        let new_columns: Vec<ColumnInfo> = table_schema
-            .column_schemas
-            .iter()
-            .map(|c| ColumnInfo {
-                name: c.name.clone(),
-                typ: c.typ.oid(),
-                modifier: c.modifier,
-            })
-            .collect();
-        let old_columns = self.relation_cache.get(&src_table_id).unwrap();
-        let columns_to_drop = old_columns
-            .iter()
-            .filter(|c| !new_columns.contains(c))
-            .map(|c| c.name.clone())
-            .collect();
-        if let Some(event_sender) = self.event_senders.get_mut(&src_table_id) {
-            event_sender
-                .send(TableEvent::AlterTable { columns_to_drop })
-                .await
-                .unwrap();
+    /// Whether filters of the table have been pushed down into the publication.
+    pub fn is_filter_pushed_down(&self, src_table_id: SrcTableId) -> bool {
+        self.table_filters
+            .get(&src_table_id)
+            .is_some_and(|table_filter| table_filter.is_pushed_down())
+    }
+
+    /// Apply source table schema change to the mooncake table.
+    /// If the change cannot be applied without losing existing data, the table stops replicating rows and is marked as requiring resync.
+    pub async fn alter_table(
+        &mut self,
+        src_table_id: SrcTableId,
+        table_schema: &TableSchema,
+    ) -> Result<(), SchemaDiffError> {
+        let old_table_schema = self.relation_cache.get(&src_table_id).unwrap();
+        let old_table_filter = self.table_filters.get(&src_table_id).unwrap();
+        let table_filter = old_table_filter.with_source_schema(table_schema);
+        // Mooncake table only contains mirrored columns.
+        let schema_diff = schema_diff::diff_table_schema(
+            &old_table_filter.project_schema(old_table_schema),
+            &table_filter.project_schema(table_schema),
+        );
+        // Cache the latest schema anyway, so the same change is not detected again from later relation messages.
+        self.relation_cache
+            .insert(src_table_id, table_schema.clone());
+        self.table_filters.insert(src_table_id, table_filter);
+        let schema_diff = match schema_diff {
+            Ok(schema_diff) => schema_diff,
+            Err(e) => {
+                self.mark_table_requiring_resync(src_table_id);
+                return Err(e);
+            }
+        };
+        debug!(src_table_id, ?schema_diff, "altering table");
+        // Tables requiring resync don't follow later schema changes either.
+        if schema_diff.is_empty() || self.tables_requiring_resync.contains(&src_table_id) {
+            return Ok(());
         }
-        self.relation_cache.insert(src_table_id, new_columns);
+        if let Some(event_sender) = self.event_senders.get(&src_table_id) {
+            if let Err(e) = Self::send_table_event(
+                event_sender,
+                TableEvent::AlterTable {
+                    columns_to_drop: schema_diff.columns_to_drop,
+                    columns_to_add: schema_diff.columns_to_add,
+                    columns_to_rename: schema_diff.columns_to_rename,
+                    columns_to_promote: schema_diff.columns_to_promote,
+                    columns_to_relax_nullability: schema_diff.columns_to_relax_nullability,
+                    validation_result_tx: None,
+                },
+            )
+            .await
+            {
+                warn!(error = ?e, "failed to send alter table event");
+            }
+        }
+        Ok(())
     }
     /// Get final lsn for the current transaction.
+    /// Tables requiring resync get no row events, so they're not touched by transactions afterwards.
     fn get_final_lsn(&mut self, table_id: SrcTableId, xact_id: Option<u32>) -> u64 {
         match xact_id {
             Some(xid) => {
@@ -159,7 +215,9 @@ impl Sink {
                 }
                 let state = self.streaming_transactions_state.entry(xid).or_default();
                 if state.last_touched_table != Some(table_id) {
-                    if !state.touched_tables.contains(&table_id) {
+                    if !state.touched_tables.contains(&table_id)
+                        && !self.tables_requiring_resync.contains(&table_id)
+                    {
                         state.touched_tables.push(table_id);
                     }
                     state.last_touched_table = Some(table_id);
@@ -170,7 +228,9 @@ impl Sink {
             }
             None => {
                 if self.transaction_state.last_touched_table != Some(table_id) {
-                    if !self.transaction_state.touched_tables.contains(&table_id) {
+                    if !self.transaction_state.touched_tables.contains(&table_id)
+                        && !self.tables_requiring_resync.contains(&table_id)
+                    {
                         self.transaction_state.touched_tables.push(table_id);
                     }
                     self.transaction_state.last_touched_table = Some(table_id);
@@ -180,7 +240,29 @@ impl Sink {
         }
     }
 
+    /// Filter and project the row with table filters, return `None` if the row is not mirrored.
+    fn filter_row(&self, table_id: SrcTableId, table_row: TableRow) -> Option<TableRow> {
+        match self.table_filters.get(&table_id) {
+            Some(table_filter) => table_filter
+                .matches(&table_row)
+                .then(|| table_filter.project_row(table_row)),
+            None => Some(table_row),
+        }
+    }
+
+    /// Project the row to mirrored columns.
+    fn project_row(&self, table_id: SrcTableId, table_row: TableRow) -> TableRow {
+        match self.table_filters.get(&table_id) {
+            Some(table_filter) => table_filter.project_row(table_row),
+            None => table_row,
+        }
+    }
+
+    /// Get event sender to apply row events on the table, tables requiring resync don't get row events.
     fn get_event_sender_for(&mut self, table_id: SrcTableId) -> Option<&Sender<TableEvent>> {
+        if self.tables_requiring_resync.contains(&table_id) {
+            return None;
+        }
         if let Some((cached_id, _)) = &self.cached_event_sender {
             if *cached_id == table_id {
                 return self.cached_event_sender.as_ref().map(|(_, s)| s);
@@ -205,6 +287,7 @@ impl Sink {
                 ma::assert_ge!(begin_body.final_lsn(), self.max_keepalive_lsn_seen);
                 self.transaction_state.final_lsn = begin_body.final_lsn();
                 self.transaction_state.last_touched_table = None;
+                self.in_transaction = true;
                 self.streaming_last_key = None;
             }
             CdcEvent::StreamStart(stream_start_body) => {
@@ -235,6 +318,7 @@ impl Sink {
                 }
                 self.transaction_state.touched_tables.clear();
                 self.transaction_state.last_touched_table = None;
+                self.in_transaction = false;
                 self.streaming_last_key = None;
                 let pg_lsn = PgLsn::from(commit_body.end_lsn());
                 self.replication_state.mark(pg_lsn.into());
@@ -275,6 +359,9 @@ impl Sink {
                 self.replication_state.mark(pg_lsn.into());
             }
             CdcEvent::Insert((table_id, table_row, xact_id)) => {
+                let Some(table_row) = self.filter_row(table_id, table_row) else {
+                    return Ok(None);
+                };
                 let final_lsn = self.get_final_lsn(table_id, xact_id);
                 if let Some(event_sender) = self.get_event_sender_for(table_id) {
                     if let Err(e) = Self::send_table_event(
@@ -293,38 +380,98 @@ impl Sink {
                 }
             }
             CdcEvent::Update((table_id, old_table_row, new_table_row, xact_id)) => {
+                // Row filter columns are part of replica identity, so they're unchanged if there's no old tuple.
+                let (old_matches, new_matches) = match self.table_filters.get(&table_id) {
+                    Some(table_filter) => {
+                        let new_matches = table_filter.matches(&new_table_row);
+                        let old_matches = old_table_row
+                            .as_ref()
+                            .map_or(new_matches, |row| table_filter.matches(row));
+                        (old_matches, new_matches)
+                    }
+                    None => (true, true),
+                };
+                if !old_matches && !new_matches {
+                    return Ok(None);
+                }
+                let new_table_row = self.project_row(table_id, new_table_row);
+                let old_table_row = old_table_row.map(|row| self.project_row(table_id, row));
+                let unchanged_columns = new_table_row
+                    .values
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, cell)| matches!(cell, Cell::UnchangedToast))
+                    .map(|(idx, _)| idx)
+                    .collect::<Vec<_>>();
+                // Row moving into the filter hasn't been mirrored, so unchanged toasted values cannot be filled from the current row.
+                if !old_matches && !unchanged_columns.is_empty() {
+                    error!(
+                        table_id,
+                        "row moving into the filter has unchanged toasted values, table stops replicating rows and needs resync"
+                    );
+                    self.mark_table_requiring_resync(table_id);
+                    return Ok(None);
+                }
+                // Without old tuple, identity columns are unchanged so the new row identifies the old one.
+                let old_table_row = old_table_row.unwrap_or_else(|| new_table_row.clone());
                 let final_lsn = self.get_final_lsn(table_id, xact_id);
                 if let Some(event_sender) = self.get_event_sender_for(table_id) {
-                    if let Err(e) = Self::send_table_event(
-                        event_sender,
-                        TableEvent::Delete {
-                            row: PostgresTableRow(old_table_row.unwrap()).into(),
-                            lsn: final_lsn,
-                            xact_id,
-                            delete_if_exists: false,
-                            is_recovery: false,
-                        },
-                    )
-                    .await
-                    {
-                        warn!(error = ?e, "failed to send delete event");
-                    }
-                    if let Err(e) = Self::send_table_event(
-                        event_sender,
-                        TableEvent::Append {
-                            row: PostgresTableRow(new_table_row).into(),
-                            lsn: final_lsn,
-                            xact_id,
-                            is_recovery: false,
-                        },
-                    )
-                    .await
-                    {
-                        warn!(error = ?e, "failed to send append event");
+                    if old_matches && new_matches && !unchanged_columns.is_empty() {
+                        if let Err(e) = Self::send_table_event(
+                            event_sender,
+                            TableEvent::Update {
+                                old_row: PostgresTableRow(old_table_row).into(),
+                                new_row: PostgresTableRow(new_table_row).into(),
+                                unchanged_columns,
+                                lsn: final_lsn,
+                                xact_id,
+                                is_recovery: false,
+                            },
+                        )
+                        .await
+                        {
+                            warn!(error = ?e, "failed to send update event");
+                        }
+                    } else {
+                        // Row moving out of the filter is deleted, and row moving into the filter is appended.
+                        if old_matches {
+                            if let Err(e) = Self::send_table_event(
+                                event_sender,
+                                TableEvent::Delete {
+                                    row: PostgresTableRow(old_table_row).into(),
+                                    lsn: final_lsn,
+                                    xact_id,
+                                    delete_if_exists: false,
+                                    is_recovery: false,
+                                },
+                            )
+                            .await
+                            {
+                                warn!(error = ?e, "failed to send delete event");
+                            }
+                        }
+                        if new_matches {
+                            if let Err(e) = Self::send_table_event(
+                                event_sender,
+                                TableEvent::Append {
+                                    row: PostgresTableRow(new_table_row).into(),
+                                    lsn: final_lsn,
+                                    xact_id,
+                                    is_recovery: false,
+                                },
+                            )
+                            .await
+                            {
+                                warn!(error = ?e, "failed to send append event");
+                            }
+                        }
                     }
                 }
             }
             CdcEvent::Delete((table_id, table_row, xact_id)) => {
+                let Some(table_row) = self.filter_row(table_id, table_row) else {
+                    return Ok(None);
+                };
                 let final_lsn = self.get_final_lsn(table_id, xact_id);
                 if let Some(event_sender) = self.get_event_sender_for(table_id) {
                     if let Err(e) = Self::send_table_event(
@@ -343,6 +490,28 @@ impl Sink {
                     }
                 }
             }
+            CdcEvent::Truncate((table_ids, options, xact_id)) => {
+                // Postgres has already expanded `CASCADE` into the truncated relations, and there's no identity sequence to restart in moonlink.
+                debug!(?table_ids, ?options, xact_id, "truncate");
+                // Truncated tables are marked as touched, so all truncations are applied at the transaction commit.
+                for table_id in table_ids {
+                    let final_lsn = self.get_final_lsn(table_id, xact_id);
+                    if let Some(event_sender) = self.get_event_sender_for(table_id) {
+                        if let Err(e) = Self::send_table_event(
+                            event_sender,
+                            TableEvent::Truncate {
+                                lsn: final_lsn,
+                                xact_id,
+                                is_recovery: false,
+                            },
+                        )
+                        .await
+                        {
+                            warn!(error = ?e, "failed to send truncate event");
+                        }
+                    }
+                }
+            }
             CdcEvent::Relation(relation_body) => {
                 debug!(
                     relation_id = relation_body.rel_id(),
@@ -350,9 +519,11 @@ impl Sink {
                     "Relation"
                 );
                 let src_table_id = relation_body.rel_id();
-                let cache_entry = self.relation_cache.get_mut(&src_table_id);
-                if let Some(cache_entry) = cache_entry {
-                    if cache_entry.len() != relation_body.columns().len() {
+                if let Some(cached_schema) = self.relation_cache.get(&src_table_id) {
+                    if schema_diff::is_relation_changed(
+                        &cached_schema.column_schemas,
+                        relation_body.columns(),
+                    ) {
                         return Ok(Some(SchemaChangeRequest(src_table_id)));
                     }
                 }
@@ -409,8 +580,11 @@ impl Sink {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::pg_replicate::conversions::cdc_event::TruncateOptions;
     use crate::pg_replicate::conversions::table_row::TableRow;
-    use crate::pg_replicate::table::{ColumnSchema, LookupKey, TableName};
+    use crate::pg_replicate::table::{ColumnSchema, LookupKey, ReplicaIdentity, TableName};
+    use moonlink::row::RowValue;
+    use moonlink::{ColumnFilter, RowFilter, RowFilterOperator, SourceFilterConfig};
     use tokio::sync::{mpsc, watch};
     use tokio_postgres::types::Type;
 
@@ -422,12 +596,14 @@ mod tests {
             },
             src_table_id,
             column_schemas: vec![ColumnSchema {
+                attnum: 1,
                 name: "id".into(),
                 typ: Type::INT4,
                 modifier: 0,
                 nullable: false,
             }],
             lookup_key: LookupKey::FullRow,
+            replica_identity: ReplicaIdentity::Full,
         }
     }
 
@@ -441,7 +617,7 @@ mod tests {
         let (tx, mut rx) = mpsc::channel::<TableEvent>(64);
         let commit_state = CommitState::new();
         let schema = make_table_schema(table_id);
-        sink.add_table(table_id, tx, commit_state, &schema);
+        sink.add_table(table_id, tx, commit_state, &schema, TableFilter::default());
 
         // Many inserts for the same (xid, table) pair
         let xid = Some(42u32);
@@ -479,6 +655,112 @@ mod tests {
         assert_eq!(append_count, rows);
     }
 
+    #[tokio::test]
+    async fn table_filter_skips_and_projects_rows() {
+        let replication_state = ReplicationState::new();
+        let mut sink = Sink::new(replication_state);
+
+        let table_id: SrcTableId = 1;
+        let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
+        let mut schema = make_table_schema(table_id);
+        schema.column_schemas.push(ColumnSchema {
+            attnum: 2,
+            name: "name".into(),
+            typ: Type::TEXT,
+            modifier: 0,
+            nullable: true,
+        });
+        let source_filter_config = SourceFilterConfig {
+            column_filter: Some(ColumnFilter::Exclude(vec!["name".into()])),
+            row_filters: vec![RowFilter {
+                column: "id".into(),
+                operator: RowFilterOperator::In,
+                values: vec!["1".into()],
+            }],
+        };
+        let table_filter = TableFilter::new(source_filter_config, &schema);
+        sink.add_table(table_id, tx, CommitState::new(), &schema, table_filter);
+
+        let make_row = |id: i32| TableRow {
+            values: vec![Cell::I32(id), Cell::String("name".into())],
+        };
+        let events = vec![
+            // Skipped since it doesn't match row filter.
+            CdcEvent::Insert((table_id, make_row(2), None)),
+            CdcEvent::Insert((table_id, make_row(1), None)),
+            // Row moves out of the filter.
+            CdcEvent::Update((table_id, Some(make_row(1)), make_row(2), None)),
+            // Row moves into the filter.
+            CdcEvent::Update((table_id, Some(make_row(2)), make_row(1), None)),
+            CdcEvent::Delete((table_id, make_row(2), None)),
+            CdcEvent::Delete((table_id, make_row(1), None)),
+        ];
+        for event in events {
+            sink.process_cdc_event(event).await.unwrap();
+        }
+
+        let expected_row = vec![RowValue::Int32(1)];
+        match rx.recv().await.expect("event") {
+            TableEvent::Append { row, .. } => assert_eq!(row.values, expected_row),
+            ev => panic!("unexpected event: {ev:?}"),
+        }
+        match rx.recv().await.expect("event") {
+            TableEvent::Delete { row, .. } => assert_eq!(row.values, expected_row),
+            ev => panic!("unexpected event: {ev:?}"),
+        }
+        match rx.recv().await.expect("event") {
+            TableEvent::Append { row, .. } => assert_eq!(row.values, expected_row),
+            ev => panic!("unexpected event: {ev:?}"),
+        }
+        match rx.recv().await.expect("event") {
+            TableEvent::Delete { row, .. } => assert_eq!(row.values, expected_row),
+            ev => panic!("unexpected event: {ev:?}"),
+        }
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn row_moving_into_filter_with_unchanged_toast_requires_resync() {
+        let replication_state = ReplicationState::new();
+        let mut sink = Sink::new(replication_state);
+
+        let table_id: SrcTableId = 2;
+        let (tx, mut rx) = mpsc::channel::<TableEvent>(8);
+        let mut schema = make_table_schema(table_id);
+        schema.column_schemas.push(ColumnSchema {
+            attnum: 2,
+            name: "name".into(),
+            typ: Type::TEXT,
+            modifier: 0,
+            nullable: true,
+        });
+        let source_filter_config = SourceFilterConfig {
+            column_filter: None,
+            row_filters: vec![RowFilter {
+                column: "id".into(),
+                operator: RowFilterOperator::In,
+                values: vec!["1".into()],
+            }],
+        };
+        let table_filter = TableFilter::new(source_filter_config, &schema);
+        sink.add_table(table_id, tx, CommitState::new(), &schema, table_filter);
+
+        // Unchanged toasted value of the row moving into the filter is unknown to moonlink.
+        let event = CdcEvent::Update((
+            table_id,
+            Some(TableRow {
+                values: vec![Cell::I32(2), Cell::Null],
+            }),
+            TableRow {
+                values: vec![Cell::I32(1), Cell::UnchangedToast],
+            },
+            None,
+        ));
+        sink.process_cdc_event(event).await.unwrap();
+        assert!(rx.try_recv().is_err());
+        assert!(sink.requires_resync(table_id));
+    }
+
     #[tokio::test]
     async fn hot_path_non_streaming_vec_dedupe_across_tables() {
         let replication_state = ReplicationState::new();
@@ -491,8 +773,20 @@ mod tests {
         let (tx_b, mut rx_b) = mpsc::channel::<TableEvent>(8);
         let commit_state_a = CommitState::new();
         let commit_state_b = CommitState::new();
-        sink.add_table(a, tx_a, commit_state_a.clone(), &make_table_schema(a));
-        sink.add_table(b, tx_b, commit_state_b.clone(), &make_table_schema(b));
+        sink.add_table(
+            a,
+            tx_a,
+            commit_state_a.clone(),
+            &make_table_schema(a),
+            TableFilter::default(),
+        );
+        sink.add_table(
+            b,
+            tx_b,
+            commit_state_b.clone(),
+            &make_table_schema(b),
+            TableFilter::default(),
+        );
 
         // Many inserts into A then into B within the same non-streaming transaction
         for _ in 0..5 {
@@ -534,6 +828,7 @@ mod tests {
             tx,
             commit_state.clone(),
             &make_table_schema(table_id),
+            TableFilter::default(),
         );
 
         // Populate sender cache
@@ -545,6 +840,82 @@ mod tests {
         assert!(sink.cached_event_sender.is_none());
     }
 
+    #[tokio::test]
+    async fn incompatible_schema_change_requires_resync() {
+        let replication_state = ReplicationState::new();
+        let commit_state = CommitState::new();
+        let mut sink = Sink::new(replication_state);
+
+        let table_id: SrcTableId = 22;
+        let (tx, mut rx) = mpsc::channel::<TableEvent>(4);
+        let mut table_schema = make_table_schema(table_id);
+        sink.add_table(
+            table_id,
+            tx,
+            commit_state.clone(),
+            &table_schema,
+            TableFilter::default(),
+        );
+        let _ = sink.get_event_sender_for(table_id);
+
+        // Rows before the schema change in the same transaction are applied.
+        let _ = sink
+            .process_cdc_event(CdcEvent::Insert((
+                table_id,
+                TableRow {
+                    values: vec![Cell::I32(1)],
+                },
+                None,
+            )))
+            .await
+            .unwrap();
+        assert!(matches!(rx.try_recv(), Ok(TableEvent::Append { .. })));
+
+        // Changing column type from integer to text cannot keep existing values.
+        table_schema.column_schemas[0].typ = Type::TEXT;
+        let res = sink.alter_table(table_id, &table_schema).await;
+        assert!(matches!(
+            res,
+            Err(SchemaDiffError::IncompatibleTypeChange { .. })
+        ));
+        assert!(sink.requires_resync(table_id));
+        assert!(sink.cached_event_sender.is_none());
+
+        // Later rows for the table are not applied, while the ongoing transaction still gets committed to the table.
+        let _ = sink
+            .process_cdc_event(CdcEvent::Insert((
+                table_id,
+                TableRow {
+                    values: vec![Cell::String("a".to_string())],
+                },
+                None,
+            )))
+            .await
+            .unwrap();
+        assert!(rx.try_recv().is_err());
+        assert_eq!(sink.transaction_state.touched_tables, vec![table_id]);
+
+        // Transactions afterwards don't touch the table.
+        sink.transaction_state.touched_tables.clear();
+        sink.transaction_state.last_touched_table = None;
+        let _ = sink
+            .process_cdc_event(CdcEvent::Insert((
+                table_id,
+                TableRow {
+                    values: vec![Cell::String("b".to_string())],
+                },
+                None,
+            )))
+            .await
+            .unwrap();
+        assert!(rx.try_recv().is_err());
+        assert!(sink.transaction_state.touched_tables.is_empty());
+
+        // Table could still be dropped for resync.
+        sink.drop_table(table_id);
+        assert!(!sink.requires_resync(table_id));
+    }
+
     #[tokio::test]
     async fn interleaved_streams_do_not_use_stale_cache() {
         let replication_state = ReplicationState::new();
@@ -559,6 +930,7 @@ mod tests {
             tx,
             commit_state.clone(),
             &make_table_schema(table_id),
+            TableFilter::default(),
         );
 
         let xid1 = Some(100u32);
@@ -627,8 +999,20 @@ mod tests {
         let (tx_b, mut rx_b) = mpsc::channel::<TableEvent>(8);
         let commit_state_a = CommitState::new();
         let commit_state_b = CommitState::new();
-        sink.add_table(a, tx_a, commit_state_a.clone(), &make_table_schema(a));
-        sink.add_table(b, tx_b, commit_state_b.clone(), &make_table_schema(b));
+        sink.add_table(
+            a,
+            tx_a,
+            commit_state_a.clone(),
+            &make_table_schema(a),
+            TableFilter::default(),
+        );
+        sink.add_table(
+            b,
+            tx_b,
+            commit_state_b.clone(),
+            &make_table_schema(b),
+            TableFilter::default(),
+        );
 
         let xid = Some(777u32);
         // A then B under same xid
@@ -669,6 +1053,7 @@ mod tests {
             tx,
             commit_state.clone(),
             &make_table_schema(table_id),
+            TableFilter::default(),
         );
 
         let xid1 = Some(1u32);
@@ -715,6 +1100,7 @@ mod tests {
             tx,
             commit_state.clone(),
             &make_table_schema(table_id),
+            TableFilter::default(),
         );
 
         // First transaction: several inserts (non-streaming)
@@ -759,6 +1145,67 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn truncate_multiple_tables_commits_atomically() {
+        let replication_state = ReplicationState::new();
+        let mut sink = Sink::new(replication_state);
+
+        let a: SrcTableId = 71;
+        let b: SrcTableId = 72;
+        let (tx_a, mut rx_a) = mpsc::channel::<TableEvent>(8);
+        let (tx_b, mut rx_b) = mpsc::channel::<TableEvent>(8);
+        sink.add_table(
+            a,
+            tx_a,
+            CommitState::new(),
+            &make_table_schema(a),
+            TableFilter::default(),
+        );
+        sink.add_table(
+            b,
+            tx_b,
+            CommitState::new(),
+            &make_table_schema(b),
+            TableFilter::default(),
+        );
+
+        // `TRUNCATE a, b RESTART IDENTITY CASCADE` within a non-streaming transaction.
+        sink.transaction_state.final_lsn = 100;
+        let _ = sink
+            .process_cdc_event(CdcEvent::Truncate((
+                vec![a, b],
+                TruncateOptions::from_bits(3),
+                None,
+            )))
+            .await
+            .unwrap();
+        assert_eq!(sink.transaction_state.touched_tables, vec![a, b]);
+
+        for rx in [&mut rx_a, &mut rx_b] {
+            match rx.recv().await.unwrap() {
+                TableEvent::Truncate {
+                    lsn,
+                    xact_id,
+                    is_recovery,
+                } => {
+                    assert_eq!(lsn, 100);
+                    assert_eq!(xact_id, None);
+                    assert!(!is_recovery);
+                }
+                ev => panic!("unexpected event: {ev:?}"),
+            }
+        }
+    }
+
+    #[test]
+    fn truncate_options_from_bits() {
+        assert_eq!(TruncateOptions::from_bits(0), TruncateOptions::default());
+        let options = TruncateOptions::from_bits(1);
+        assert!(options.cascade && !options.restart_identity);
+        let options = TruncateOptions::from_bits(2);
+        assert!(!options.cascade && options.restart_identity);
+    }
+
     #[tokio::test]
     async fn test_send_table_event_ok() {
         let (tx, mut rx) = mpsc::channel(1);
@@ -25,6 +25,7 @@ use crate::pg_replicate::{
         table_row::{TableRow, TableRowConversionError, TableRowConverter},
     },
     table::{ColumnSchema, SrcTableId, TableName, TableSchema},
+    table_filter::TableFilter,
 };
 
 #[derive(Debug, Error)]
@@ -145,13 +146,20 @@ impl PostgresSource {
     pub async fn add_table_to_publication(
         &mut self,
         table_name: &TableName,
+        column_list: Option<&[String]>,
+        row_filter: Option<&str>,
     ) -> Result<(), PostgresSourceError> {
         self.replication_client
-            .add_table_to_publication(table_name)
+            .add_table_to_publication(table_name, column_list, row_filter)
             .await?;
         Ok(())
     }
 
+    pub async fn get_server_version_num(&self) -> Result<i32, PostgresSourceError> {
+        let server_version_num = self.replication_client.get_server_version_num().await?;
+        Ok(server_version_num)
+    }
+
     pub async fn get_row_count(
         &mut self,
         table_name: &TableName,
@@ -369,12 +377,14 @@ impl PostgresSource {
 
     /// Spawn a single sharded COPY reader that imports a snapshot, reads using the predicate,
     /// converts to Arrow batches, and pushes to the shared BatchSender.
+    /// Only rows matching the table filter are pushed, projected to mirrored columns.
     /// NOTE: This function opens its own connection; errors are returned as PostgresSourceError.
     pub async fn spawn_sharded_copy_reader(
         &self,
         uri: String,
         snapshot_id: String,
         table_schema: TableSchema,
+        table_filter: TableFilter,
         predicate_sql: String,
         batch_tx: BatchSender,
         max_rows_per_batch: usize,
@@ -411,13 +421,18 @@ impl PostgresSource {
             futures::pin_mut!(stream);
 
             // Build batches and push to writers
-            let (arrow_schema, _id) =
-                crate::pg_replicate::util::postgres_schema_to_moonlink_schema(&table_schema);
+            let (arrow_schema, _id) = crate::pg_replicate::util::postgres_schema_to_moonlink_schema(
+                &table_filter.project_schema(&table_schema),
+            );
             let arrow_schema = std::sync::Arc::new(arrow_schema);
             let mut builder = ArrowBatchBuilder::new(arrow_schema, max_rows_per_batch);
             let mut rows: u64 = 0;
             while let Some(row_res) = stream.next().await {
                 let row = row_res.map_err(|e| crate::Error::from(e))?;
+                if !table_filter.matches(&row) {
+                    continue;
+                }
+                let row = table_filter.project_row(row);
                 if let Some(batch) = builder.append_table_row(row)? {
                     batch_tx.send(batch).await?;
                 }
@@ -443,6 +458,7 @@ impl PostgresSource {
         uri: String,
         snapshot_id: String,
         table_schema: TableSchema,
+        table_filter: TableFilter,
         predicates: Vec<String>,
         batch_tx: crate::pg_replicate::initial_copy_writer::BatchSender,
         max_rows_per_batch: usize,
@@ -455,6 +471,7 @@ impl PostgresSource {
                     uri.clone(),
                     snapshot_id.clone(),
                     table_schema.clone(),
+                    table_filter.clone(),
                     pred,
                     batch_tx.clone(),
                     max_rows_per_batch,
@@ -0,0 +1,348 @@
+use crate::pg_replicate::table::{ColumnSchema, TableSchema};
+use crate::pg_replicate::util::postgres_schema_to_moonlink_schema;
+use arrow_schema::{DataType, FieldRef};
+use moonlink::is_safe_type_promotion;
+use moonlink::row::IdentityProp;
+use postgres_replication::protocol::Column as ReplicationColumn;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+
+/// Column level changes between two versions of a table schema.
+#[derive(Debug, Default, PartialEq)]
+pub struct SchemaDiff {
+    pub columns_to_drop: Vec<String>,
+    /// Field ids for new columns are assigned by moonlink.
+    pub columns_to_add: Vec<FieldRef>,
+    /// Columns to rename, in the format of (old name, new name).
+    pub columns_to_rename: Vec<(String, String)>,
+    /// Columns to promote to a wider type, keyed by the name before renaming.
+    pub columns_to_promote: Vec<(String, DataType)>,
+    /// Columns to relax to nullable, keyed by the name before renaming.
+    pub columns_to_relax_nullability: Vec<String>,
+}
+
+/// Schema changes which mooncake table cannot follow, the table needs resync from source.
+#[derive(Debug, Error, PartialEq)]
+pub enum SchemaDiffError {
+    #[error(
+        "incompatible type change for column {column} from {from:?} to {to:?}, table needs resync"
+    )]
+    IncompatibleTypeChange {
+        column: String,
+        from: DataType,
+        to: DataType,
+    },
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.columns_to_drop.is_empty()
+            && self.columns_to_add.is_empty()
+            && self.columns_to_rename.is_empty()
+            && self.columns_to_promote.is_empty()
+            && self.columns_to_relax_nullability.is_empty()
+    }
+}
+
+/// Whether the relation message differs from the cached column schemas, in column names, type oids or type modifiers.
+/// Nullability is not carried in relation messages, which is compared when the table schema gets re-fetched.
+pub fn is_relation_changed(cached_columns: &[ColumnSchema], columns: &[ReplicationColumn]) -> bool {
+    cached_columns.len() != columns.len()
+        || cached_columns
+            .iter()
+            .zip(columns.iter())
+            .any(|(cached, column)| {
+                column.name().ok() != Some(cached.name.as_str())
+                    || column.type_id() as u32 != cached.typ.oid()
+                    || column.type_modifier() != cached.modifier
+            })
+}
+
+/// Whether lookup keys for existing rows still match after promoting the given column, so index lookup and deletion keep working.
+fn is_lookup_key_preserved(
+    identity: &IdentityProp,
+    column_idx: usize,
+    from: &DataType,
+    to: &DataType,
+) -> bool {
+    // Row values are hashed into lookup keys, which keep the same only if the promotion doesn't change row value representation.
+    let same_row_value = matches!(
+        (from, to),
+        (DataType::Int16, DataType::Int32) | (DataType::Decimal128(..), DataType::Decimal128(..))
+    );
+    match identity {
+        IdentityProp::SinglePrimitiveKey(key) if *key == column_idx => {
+            same_row_value
+                || matches!(
+                    (from, to),
+                    (DataType::Int16 | DataType::Int32, DataType::Int64)
+                )
+        }
+        IdentityProp::SinglePrimitiveKey(_) | IdentityProp::None => true,
+        IdentityProp::Keys(keys) => !keys.contains(&column_idx) || same_row_value,
+        IdentityProp::FullRow => same_row_value,
+    }
+}
+
+/// Pair up old and new columns which refer to the same postgres attribute, by attribute number.
+/// Unpaired old columns are dropped, and unpaired new columns are added.
+fn pair_columns(old: &TableSchema, new: &TableSchema) -> Vec<(Option<usize>, Option<usize>)> {
+    let new_indices: HashMap<i16, usize> = new
+        .column_schemas
+        .iter()
+        .enumerate()
+        .map(|(idx, column)| (column.attnum, idx))
+        .collect();
+
+    let mut pairs = vec![];
+    let mut paired_new_indices = HashSet::new();
+    for (old_idx, column) in old.column_schemas.iter().enumerate() {
+        match new_indices.get(&column.attnum) {
+            Some(new_idx) => {
+                paired_new_indices.insert(*new_idx);
+                pairs.push((Some(old_idx), Some(*new_idx)));
+            }
+            None => pairs.push((Some(old_idx), None)),
+        }
+    }
+    pairs.extend(
+        (0..new.column_schemas.len())
+            .filter(|new_idx| !paired_new_indices.contains(new_idx))
+            .map(|new_idx| (None, Some(new_idx))),
+    );
+    pairs
+}
+
+/// Compute column changes from `old` table schema to `new` one.
+/// Existing column values are never discarded, so incompatible type changes are reported as error instead of recreating the column.
+pub fn diff_table_schema(
+    old: &TableSchema,
+    new: &TableSchema,
+) -> Result<SchemaDiff, SchemaDiffError> {
+    let (old_arrow_schema, old_identity) = postgres_schema_to_moonlink_schema(old);
+    let (new_arrow_schema, _) = postgres_schema_to_moonlink_schema(new);
+
+    let mut schema_diff = SchemaDiff::default();
+    for pair in pair_columns(old, new) {
+        match pair {
+            (Some(old_idx), None) => {
+                schema_diff
+                    .columns_to_drop
+                    .push(old.column_schemas[old_idx].name.clone());
+            }
+            (None, Some(new_idx)) => {
+                schema_diff
+                    .columns_to_add
+                    .push(new_arrow_schema.fields()[new_idx].clone());
+            }
+            (Some(old_idx), Some(new_idx)) => {
+                let old_name = &old.column_schemas[old_idx].name;
+                let old_field = &old_arrow_schema.fields()[old_idx];
+                let new_field = &new_arrow_schema.fields()[new_idx];
+                if old_field.data_type() != new_field.data_type() {
+                    let can_promote =
+                        is_safe_type_promotion(old_field.data_type(), new_field.data_type())
+                            && is_lookup_key_preserved(
+                                &old_identity,
+                                old_idx,
+                                old_field.data_type(),
+                                new_field.data_type(),
+                            );
+                    if !can_promote {
+                        return Err(SchemaDiffError::IncompatibleTypeChange {
+                            column: old_name.clone(),
+                            from: old_field.data_type().clone(),
+                            to: new_field.data_type().clone(),
+                        });
+                    }
+                    schema_diff
+                        .columns_to_promote
+                        .push((old_name.clone(), new_field.data_type().clone()));
+                }
+                if !old_field.is_nullable() && new_field.is_nullable() {
+                    schema_diff
+                        .columns_to_relax_nullability
+                        .push(old_name.clone());
+                }
+                if old_name != new_field.name() {
+                    schema_diff
+                        .columns_to_rename
+                        .push((old_name.clone(), new_field.name().clone()));
+                }
+            }
+            (None, None) => unreachable!(),
+        }
+    }
+    Ok(schema_diff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_replicate::table::{LookupKey, ReplicaIdentity, TableName};
+    use tokio_postgres::types::Type;
+
+    fn column(attnum: i16, name: &str, typ: Type, nullable: bool) -> ColumnSchema {
+        ColumnSchema {
+            attnum,
+            name: name.to_string(),
+            typ,
+            modifier: -1,
+            nullable,
+        }
+    }
+
+    fn table_schema(column_schemas: Vec<ColumnSchema>) -> TableSchema {
+        TableSchema {
+            table_name: TableName {
+                schema: "public".to_string(),
+                name: "t".to_string(),
+            },
+            src_table_id: 1,
+            column_schemas,
+            lookup_key: LookupKey::Key {
+                name: "t_pkey".to_string(),
+                columns: vec!["id".to_string()],
+            },
+            replica_identity: ReplicaIdentity::Default,
+        }
+    }
+
+    fn added_column_names(schema_diff: &SchemaDiff) -> Vec<String> {
+        schema_diff
+            .columns_to_add
+            .iter()
+            .map(|f| f.name().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_add_and_drop_columns() {
+        let old = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "a", Type::TEXT, true),
+            column(3, "b", Type::TEXT, true),
+        ]);
+        let new = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(3, "b", Type::TEXT, true),
+            column(4, "c", Type::INT8, true),
+        ]);
+        let schema_diff = diff_table_schema(&old, &new).unwrap();
+        assert_eq!(schema_diff.columns_to_drop, vec!["a".to_string()]);
+        assert_eq!(added_column_names(&schema_diff), vec!["c".to_string()]);
+        assert!(schema_diff.columns_to_rename.is_empty());
+        assert!(schema_diff.columns_to_promote.is_empty());
+    }
+
+    #[test]
+    fn test_diff_drop_and_add_column_at_same_position() {
+        let old = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "a", Type::TEXT, true),
+        ]);
+        // New column takes the place of the dropped one, but is a different postgres attribute.
+        let new = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(3, "c", Type::TEXT, true),
+        ]);
+        let schema_diff = diff_table_schema(&old, &new).unwrap();
+        assert_eq!(schema_diff.columns_to_drop, vec!["a".to_string()]);
+        assert_eq!(added_column_names(&schema_diff), vec!["c".to_string()]);
+        assert!(schema_diff.columns_to_rename.is_empty());
+    }
+
+    #[test]
+    fn test_diff_rename_and_promote_columns() {
+        let old = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "name", Type::TEXT, true),
+            column(3, "score", Type::FLOAT4, false),
+            column(4, "age", Type::INT2, false),
+        ]);
+        let new = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "full_name", Type::TEXT, true),
+            column(3, "score", Type::FLOAT8, true),
+            column(4, "years", Type::INT8, false),
+        ]);
+        let schema_diff = diff_table_schema(&old, &new).unwrap();
+        assert!(schema_diff.columns_to_drop.is_empty());
+        assert!(schema_diff.columns_to_add.is_empty());
+        assert_eq!(
+            schema_diff.columns_to_rename,
+            vec![
+                ("name".to_string(), "full_name".to_string()),
+                ("age".to_string(), "years".to_string()),
+            ]
+        );
+        assert_eq!(
+            schema_diff.columns_to_promote,
+            vec![
+                ("score".to_string(), DataType::Float64),
+                ("age".to_string(), DataType::Int64),
+            ]
+        );
+        assert_eq!(
+            schema_diff.columns_to_relax_nullability,
+            vec!["score".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_diff_incompatible_type_change() {
+        let old = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "v", Type::INT8, true),
+        ]);
+        let new = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "v", Type::TEXT, true),
+        ]);
+        assert_eq!(
+            diff_table_schema(&old, &new),
+            Err(SchemaDiffError::IncompatibleTypeChange {
+                column: "v".to_string(),
+                from: DataType::Int64,
+                to: DataType::Utf8,
+            })
+        );
+    }
+
+    #[test]
+    fn test_diff_incompatible_key_type_change() {
+        let old = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "v", Type::TEXT, true),
+        ]);
+        let new = table_schema(vec![
+            column(1, "id", Type::TEXT, false),
+            column(2, "v", Type::TEXT, true),
+        ]);
+        assert!(matches!(
+            diff_table_schema(&old, &new),
+            Err(SchemaDiffError::IncompatibleTypeChange { column, .. }) if column == "id"
+        ));
+    }
+
+    #[test]
+    fn test_diff_promote_single_primitive_key() {
+        let old = table_schema(vec![column(1, "id", Type::INT4, false)]);
+        let new = table_schema(vec![column(1, "id", Type::INT8, false)]);
+        let schema_diff = diff_table_schema(&old, &new).unwrap();
+        assert_eq!(
+            schema_diff.columns_to_promote,
+            vec![("id".to_string(), DataType::Int64)]
+        );
+    }
+
+    #[test]
+    fn test_diff_unchanged_schema() {
+        let old = table_schema(vec![
+            column(1, "id", Type::INT4, false),
+            column(2, "name", Type::VARCHAR, true),
+        ]);
+        let schema_diff = diff_table_schema(&old, &old.clone()).unwrap();
+        assert!(schema_diff.is_empty());
+    }
+}
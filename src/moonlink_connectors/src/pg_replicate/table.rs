@@ -45,6 +45,8 @@ type TypeModifier = i32;
 
 #[derive(Debug, Clone)]
 pub struct ColumnSchema {
+    /// Postgres attribute number, which identifies the column across renames and never gets reused after drop.
+    pub attnum: i16,
     pub name: String,
     pub typ: Type,
     pub modifier: TypeModifier,
@@ -57,6 +59,33 @@ pub enum LookupKey {
     FullRow,
 }
 
+/// Replica identity of a source table, which decides the old tuple sent for updates and deletes.
+/// See [https://www.postgresql.org/docs/current/sql-altertable.html#SQL-ALTERTABLE-REPLICA-IDENTITY]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplicaIdentity {
+    /// Old tuple only contains primary key columns.
+    Default,
+    /// Old tuple only contains columns of the replica identity index.
+    Index,
+    /// Old tuple contains all columns.
+    Full,
+    /// No old tuple is sent.
+    Nothing,
+}
+
+impl ReplicaIdentity {
+    /// Parse from `relreplident` column in `pg_class`.
+    pub fn from_relreplident(relreplident: &str) -> Option<Self> {
+        match relreplident {
+            "d" => Some(ReplicaIdentity::Default),
+            "i" => Some(ReplicaIdentity::Index),
+            "f" => Some(ReplicaIdentity::Full),
+            "n" => Some(ReplicaIdentity::Nothing),
+            _ => None,
+        }
+    }
+}
+
 pub type SrcTableId = u32;
 
 #[derive(Debug, Clone)]
@@ -65,6 +94,13 @@ pub struct TableSchema {
     pub src_table_id: SrcTableId,
     pub column_schemas: Vec<ColumnSchema>,
     pub lookup_key: LookupKey,
+    pub replica_identity: ReplicaIdentity,
 }
 
-impl TableSchema {}
+impl TableSchema {
+    /// Whether updates and deletes cannot be identified with the current replica identity, so the table needs REPLICA IDENTITY FULL to be replicated.
+    pub fn requires_replica_identity_full(&self) -> bool {
+        self.replica_identity != ReplicaIdentity::Full
+            && matches!(self.lookup_key, LookupKey::FullRow)
+    }
+}
@@ -0,0 +1,467 @@
+use crate::pg_replicate::conversions::text::TextFormatConverter;
+use crate::pg_replicate::conversions::{table_row::TableRow, Cell};
+use crate::pg_replicate::table::{LookupKey, ReplicaIdentity, TableSchema};
+use moonlink::row::RowValue;
+use moonlink::{ColumnFilter, RowFilterOperator, SourceFilterConfig};
+use pg_escape::{quote_identifier, quote_literal};
+use tracing::warn;
+
+/// Minimum server version (in `server_version_num` format) which supports column lists and row filters in publications.
+pub const PUBLICATION_FILTER_MIN_SERVER_VERSION: i32 = 150000;
+
+/// Row filter resolved against the source schema.
+#[derive(Clone, Debug)]
+struct RowCondition {
+    /// Index of the filter column in source schema, unassigned if the column or filter values are no longer valid.
+    column_idx: Option<usize>,
+    operator: RowFilterOperator,
+    values: Vec<RowValue>,
+}
+
+impl RowCondition {
+    fn matches(&self, row: &TableRow) -> bool {
+        // Fail closed on unresolved filters, so no unexpected rows get mirrored.
+        let Some(column_idx) = self.column_idx else {
+            return false;
+        };
+        let value = match &row.values[column_idx] {
+            Cell::Null | Cell::UnchangedToast => return false,
+            cell => RowValue::from(cell.clone()),
+        };
+        let contained = self.values.contains(&value);
+        match self.operator {
+            RowFilterOperator::In => contained,
+            RowFilterOperator::NotIn => !contained,
+        }
+    }
+}
+
+/// Column and row filters applied on moonlink side, for rows read from the source table.
+/// If the filters have been pushed down into the publication, rows are already filtered and projected by postgres.
+#[derive(Clone, Debug, Default)]
+pub struct TableFilter {
+    config: SourceFilterConfig,
+    pushed_down: bool,
+    /// Indices of mirrored columns in source schema, unassigned if all columns are mirrored.
+    projection: Option<Vec<usize>>,
+    conditions: Vec<RowCondition>,
+}
+
+impl TableFilter {
+    /// Resolve filters against the given source schema.
+    pub fn new(config: SourceFilterConfig, source_schema: &TableSchema) -> Self {
+        let projection = config.column_filter.as_ref().map(|column_filter| {
+            source_schema
+                .column_schemas
+                .iter()
+                .enumerate()
+                .filter(|(_, column)| column_filter.contains(&column.name))
+                .map(|(idx, _)| idx)
+                .collect()
+        });
+        let conditions = config
+            .row_filters
+            .iter()
+            .map(|row_filter| {
+                let column_idx = source_schema
+                    .column_schemas
+                    .iter()
+                    .position(|column| column.name == row_filter.column);
+                let values = column_idx.and_then(|idx| {
+                    parse_filter_values(&source_schema.column_schemas[idx].typ, &row_filter.values)
+                });
+                if values.is_none() {
+                    warn!(
+                        table_name = %source_schema.table_name,
+                        column = %row_filter.column,
+                        "row filter cannot be resolved, no rows will be mirrored"
+                    );
+                }
+                RowCondition {
+                    column_idx: values.as_ref().and(column_idx),
+                    operator: row_filter.operator,
+                    values: values.unwrap_or_default(),
+                }
+            })
+            .collect();
+        Self {
+            config,
+            pushed_down: false,
+            projection,
+            conditions,
+        }
+    }
+
+    /// Filters which have been pushed down into the publication, so rows are mirrored as is.
+    pub fn pushed_down() -> Self {
+        Self {
+            pushed_down: true,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_pushed_down(&self) -> bool {
+        self.pushed_down
+    }
+
+    /// Resolve the same filters against a new source schema, after source schema change.
+    pub fn with_source_schema(&self, source_schema: &TableSchema) -> Self {
+        if self.pushed_down {
+            return self.clone();
+        }
+        Self::new(self.config.clone(), source_schema)
+    }
+
+    /// Return whether the row should be mirrored, which must be in the source schema.
+    pub fn matches(&self, row: &TableRow) -> bool {
+        self.conditions
+            .iter()
+            .all(|condition| condition.matches(row))
+    }
+
+    /// Project the row in source schema to mirrored columns.
+    pub fn project_row(&self, row: TableRow) -> TableRow {
+        let Some(projection) = &self.projection else {
+            return row;
+        };
+        let mut values = row.values.into_iter().map(Some).collect::<Vec<_>>();
+        TableRow {
+            values: projection
+                .iter()
+                .map(|idx| values[*idx].take().unwrap())
+                .collect(),
+        }
+    }
+
+    /// Project the source schema to mirrored columns.
+    pub fn project_schema(&self, source_schema: &TableSchema) -> TableSchema {
+        project_table_schema(&self.config, source_schema)
+    }
+}
+
+/// Parse filter values into row values of the given column type, return `None` if any fails.
+fn parse_filter_values(
+    typ: &tokio_postgres::types::Type,
+    values: &[String],
+) -> Option<Vec<RowValue>> {
+    values
+        .iter()
+        .map(|value| {
+            TextFormatConverter::try_from_str(typ, value)
+                .ok()
+                .map(RowValue::from)
+        })
+        .collect()
+}
+
+/// Project the source schema to columns mirrored by the given filters.
+pub fn project_table_schema(
+    config: &SourceFilterConfig,
+    source_schema: &TableSchema,
+) -> TableSchema {
+    let mut table_schema = source_schema.clone();
+    if let Some(column_filter) = &config.column_filter {
+        table_schema
+            .column_schemas
+            .retain(|column| column_filter.contains(&column.name));
+    }
+    table_schema
+}
+
+/// Validate filters against the source schema, return the reason if invalid.
+pub fn validate_source_filter(
+    config: &SourceFilterConfig,
+    source_schema: &TableSchema,
+) -> Result<(), String> {
+    let has_column = |name: &str| {
+        source_schema
+            .column_schemas
+            .iter()
+            .any(|column| column.name == name)
+    };
+    if let Some(column_filter) = &config.column_filter {
+        let (ColumnFilter::Include(columns) | ColumnFilter::Exclude(columns)) = column_filter;
+        if let Some(column) = columns.iter().find(|column| !has_column(column.as_str())) {
+            return Err(format!("filtered column {column} doesn't exist"));
+        }
+    }
+    let table_schema = project_table_schema(config, source_schema);
+    if table_schema.column_schemas.is_empty() {
+        return Err("no column is mirrored".to_string());
+    }
+    let key_columns: &[String] = match &source_schema.lookup_key {
+        LookupKey::Key { columns, .. } => columns.as_slice(),
+        LookupKey::FullRow => &[],
+    };
+    if let Some(column) = key_columns.iter().find(|column| {
+        !table_schema
+            .column_schemas
+            .iter()
+            .any(|c| c.name == **column)
+    }) {
+        return Err(format!("key column {column} must be mirrored"));
+    }
+    for row_filter in config.row_filters.iter() {
+        let Some(column) = source_schema
+            .column_schemas
+            .iter()
+            .find(|column| column.name == row_filter.column)
+        else {
+            return Err(format!(
+                "row filter column {} doesn't exist",
+                row_filter.column
+            ));
+        };
+        if parse_filter_values(&column.typ, &row_filter.values).is_none() {
+            return Err(format!(
+                "row filter values {:?} are invalid for column {}",
+                row_filter.values, row_filter.column
+            ));
+        }
+        // Same as postgres publications, so row filter columns are always available in old tuples of updates and deletes.
+        if source_schema.replica_identity != ReplicaIdentity::Full
+            && !key_columns.contains(&row_filter.column)
+        {
+            return Err(format!(
+                "row filter column {} must be part of the replica identity",
+                row_filter.column
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether filters could be pushed down into the publication for the source table.
+/// Publication column list must cover all replica identity columns, so column filters cannot be pushed down with full replica identity.
+/// Publication column list is fixed, so excluded columns are only filtered on moonlink side, otherwise columns added to the source later are never published.
+pub fn can_push_down(
+    config: &SourceFilterConfig,
+    source_schema: &TableSchema,
+    server_version_num: i32,
+) -> bool {
+    let column_filter_pushable = match &config.column_filter {
+        None => true,
+        Some(ColumnFilter::Include(_)) => source_schema.replica_identity != ReplicaIdentity::Full,
+        Some(ColumnFilter::Exclude(_)) => false,
+    };
+    server_version_num >= PUBLICATION_FILTER_MIN_SERVER_VERSION && column_filter_pushable
+}
+
+/// Column list of the publication, unassigned if all columns are published.
+pub fn publication_column_list(
+    config: &SourceFilterConfig,
+    source_schema: &TableSchema,
+) -> Option<Vec<String>> {
+    config.column_filter.as_ref()?;
+    Some(
+        project_table_schema(config, source_schema)
+            .column_schemas
+            .into_iter()
+            .map(|column| column.name)
+            .collect(),
+    )
+}
+
+/// Row filter expression of the publication, unassigned if all rows are published.
+pub fn publication_row_filter(config: &SourceFilterConfig) -> Option<String> {
+    if config.row_filters.is_empty() {
+        return None;
+    }
+    let expressions = config
+        .row_filters
+        .iter()
+        .map(|row_filter| {
+            let operator = match row_filter.operator {
+                RowFilterOperator::In => "IN",
+                RowFilterOperator::NotIn => "NOT IN",
+            };
+            let values = row_filter
+                .values
+                .iter()
+                .map(|value| quote_literal(value).into_owned())
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!(
+                "{} {operator} ({values})",
+                quote_identifier(&row_filter.column)
+            )
+        })
+        .collect::<Vec<_>>();
+    Some(expressions.join(" AND "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pg_replicate::table::{ColumnSchema, TableName};
+    use moonlink::RowFilter;
+    use tokio_postgres::types::Type;
+
+    fn make_table_schema(replica_identity: ReplicaIdentity) -> TableSchema {
+        let make_column = |attnum: i16, name: &str, typ: Type| ColumnSchema {
+            attnum,
+            name: name.to_string(),
+            typ,
+            modifier: -1,
+            nullable: true,
+        };
+        TableSchema {
+            table_name: TableName {
+                schema: "public".to_string(),
+                name: "t".to_string(),
+            },
+            src_table_id: 1,
+            column_schemas: vec![
+                make_column(1, "id", Type::INT4),
+                make_column(2, "tenant_id", Type::INT8),
+                make_column(3, "name", Type::TEXT),
+                make_column(4, "secret", Type::TEXT),
+            ],
+            lookup_key: LookupKey::Key {
+                name: "t_pkey".to_string(),
+                columns: vec!["id".to_string(), "tenant_id".to_string()],
+            },
+            replica_identity,
+        }
+    }
+
+    fn make_config(operator: RowFilterOperator) -> SourceFilterConfig {
+        SourceFilterConfig {
+            column_filter: Some(ColumnFilter::Exclude(vec!["secret".to_string()])),
+            row_filters: vec![RowFilter {
+                column: "tenant_id".to_string(),
+                operator,
+                values: vec!["1".to_string(), "2".to_string()],
+            }],
+        }
+    }
+
+    fn make_row(tenant_id: Option<i64>) -> TableRow {
+        TableRow {
+            values: vec![
+                Cell::I32(10),
+                tenant_id.map(Cell::I64).unwrap_or(Cell::Null),
+                Cell::String("name".to_string()),
+                Cell::String("secret".to_string()),
+            ],
+        }
+    }
+
+    #[test]
+    fn test_row_filter() {
+        let table_schema = make_table_schema(ReplicaIdentity::Default);
+        let table_filter = TableFilter::new(make_config(RowFilterOperator::In), &table_schema);
+        assert!(table_filter.matches(&make_row(Some(1))));
+        assert!(!table_filter.matches(&make_row(Some(3))));
+        assert!(!table_filter.matches(&make_row(None)));
+
+        let table_filter = TableFilter::new(make_config(RowFilterOperator::NotIn), &table_schema);
+        assert!(!table_filter.matches(&make_row(Some(2))));
+        assert!(table_filter.matches(&make_row(Some(3))));
+        assert!(!table_filter.matches(&make_row(None)));
+    }
+
+    #[test]
+    fn test_unresolved_row_filter() {
+        let mut table_schema = make_table_schema(ReplicaIdentity::Default);
+        let table_filter = TableFilter::new(make_config(RowFilterOperator::NotIn), &table_schema);
+        // Filter column gets dropped from source table.
+        table_schema.column_schemas.remove(1);
+        let table_filter = table_filter.with_source_schema(&table_schema);
+        let mut row = make_row(Some(3));
+        row.values.remove(1);
+        assert!(!table_filter.matches(&row));
+    }
+
+    #[test]
+    fn test_column_filter() {
+        let table_schema = make_table_schema(ReplicaIdentity::Default);
+        let table_filter = TableFilter::new(make_config(RowFilterOperator::In), &table_schema);
+        let projected_schema = table_filter.project_schema(&table_schema);
+        assert_eq!(
+            projected_schema
+                .column_schemas
+                .iter()
+                .map(|column| column.name.as_str())
+                .collect::<Vec<_>>(),
+            vec!["id", "tenant_id", "name"]
+        );
+        let projected_row = table_filter.project_row(make_row(Some(1)));
+        assert_eq!(projected_row.values.len(), 3);
+        assert!(matches!(projected_row.values[2], Cell::String(ref s) if s == "name"));
+
+        // Pushed down filters keep rows as is.
+        let table_filter = TableFilter::pushed_down();
+        assert!(table_filter.matches(&make_row(None)));
+        assert_eq!(table_filter.project_row(make_row(None)).values.len(), 4);
+    }
+
+    #[test]
+    fn test_validate_source_filter() {
+        let table_schema = make_table_schema(ReplicaIdentity::Default);
+        assert!(validate_source_filter(&make_config(RowFilterOperator::In), &table_schema).is_ok());
+
+        // Key columns must be mirrored.
+        let config = SourceFilterConfig {
+            column_filter: Some(ColumnFilter::Include(vec!["name".to_string()])),
+            row_filters: vec![],
+        };
+        assert!(validate_source_filter(&config, &table_schema).is_err());
+
+        // Filtered columns must exist.
+        let config = SourceFilterConfig {
+            column_filter: Some(ColumnFilter::Exclude(vec!["unknown".to_string()])),
+            row_filters: vec![],
+        };
+        assert!(validate_source_filter(&config, &table_schema).is_err());
+
+        // Row filter values must be valid for the column type.
+        let mut config = make_config(RowFilterOperator::In);
+        config.row_filters[0].values.push("abc".to_string());
+        assert!(validate_source_filter(&config, &table_schema).is_err());
+
+        // Row filter column must be part of replica identity.
+        let mut config = make_config(RowFilterOperator::In);
+        config.row_filters[0].column = "name".to_string();
+        assert!(validate_source_filter(&config, &table_schema).is_err());
+        let table_schema = make_table_schema(ReplicaIdentity::Full);
+        assert!(validate_source_filter(&config, &table_schema).is_ok());
+    }
+
+    #[test]
+    fn test_publication_filter() {
+        let mut config = make_config(RowFilterOperator::NotIn);
+        let table_schema = make_table_schema(ReplicaIdentity::Default);
+        // Excluded columns are not pushed down.
+        assert!(!can_push_down(&config, &table_schema, 150004));
+        config.column_filter = Some(ColumnFilter::Include(vec![
+            "id".to_string(),
+            "tenant_id".to_string(),
+            "name".to_string(),
+        ]));
+        assert!(can_push_down(&config, &table_schema, 150004));
+        assert!(!can_push_down(&config, &table_schema, 140010));
+        assert!(!can_push_down(
+            &config,
+            &make_table_schema(ReplicaIdentity::Full),
+            150004
+        ));
+        config.column_filter = None;
+        assert!(can_push_down(
+            &config,
+            &make_table_schema(ReplicaIdentity::Full),
+            150004
+        ));
+
+        assert_eq!(
+            publication_column_list(&config, &table_schema).unwrap(),
+            vec!["id", "tenant_id", "name"]
+        );
+        assert_eq!(
+            publication_row_filter(&config).unwrap(),
+            "tenant_id NOT IN ('1', '2')"
+        );
+        assert!(publication_column_list(&SourceFilterConfig::default(), &table_schema).is_none());
+        assert!(publication_row_filter(&SourceFilterConfig::default()).is_none());
+    }
+}
@@ -7,9 +7,10 @@ use moonlink::PersistentWalMetadata;
 use moonlink::ReadStateFilepathRemap;
 use moonlink::{
     row::IdentityProp, AccessorConfig, BaseFileSystemAccess, EventSyncReceiver, EventSyncSender,
-    FileSystemAccessor, IcebergTableConfig, MooncakeTable, MooncakeTableConfig, MoonlinkSecretType,
-    MoonlinkTableConfig, MoonlinkTableSecret, ObjectStorageCache, ReadStateManager, StorageConfig,
-    TableEvent, TableEventManager, TableHandler, TableStatusReader, WalConfig, WalManager,
+    FileSystemAccessor, IcebergTableConfig, MemoryAccountant, MooncakeTable, MooncakeTableConfig,
+    MoonlinkSecretType, MoonlinkTableConfig, MoonlinkTableSecret, ObjectStorageCache,
+    ReadStateManager, StorageConfig, TableEncryption, TableEvent, TableEventManager, TableHandler,
+    TableStatusReader, WalChangeFeed, WalConfig, WalManager,
 };
 use moonlink::{CommitState, ReplicationState};
 
@@ -34,6 +35,8 @@ pub struct TableComponents {
     pub read_state_filepath_remap: ReadStateFilepathRemap,
     /// Shared object storage cache for all mooncake tables.
     pub object_storage_cache: ObjectStorageCache,
+    /// Shared memory accountant for all mooncake tables.
+    pub memory_accountant: MemoryAccountant,
     /// Mooncake table configuration.
     pub moonlink_table_config: MoonlinkTableConfig,
 }
@@ -44,12 +47,14 @@ pub struct TableResources {
     pub read_state_manager: ReadStateManager,
     pub table_event_manager: TableEventManager,
     pub table_status_reader: TableStatusReader,
+    pub change_feed: WalChangeFeed,
     pub commit_state: Option<Arc<CommitState>>,
     pub flush_lsn_rx: Option<watch::Receiver<u64>>,
     pub wal_flush_lsn_rx: Option<watch::Receiver<u64>>,
     pub wal_file_accessor: Arc<dyn BaseFileSystemAccess>,
     pub wal_persistence_metadata: Option<PersistentWalMetadata>,
     pub last_persistence_snapshot_lsn: Option<u64>,
+    pub table_encryption: Option<Arc<TableEncryption>>,
 }
 
 /// Util function to delete and re-create the given directory.
@@ -102,9 +107,47 @@ pub async fn build_table_components(
         .moonlink_table_config
         .wal_table_config
         .clone();
-    let wal_file_accessor = Arc::new(FileSystemAccessor::new(
+    let mut wal_file_accessor: Arc<dyn BaseFileSystemAccess> = Arc::new(FileSystemAccessor::new(
         wal_config.get_accessor_config().clone(),
     ));
+    let deltalake_table_config = table_components
+        .moonlink_table_config
+        .deltalake_table_config
+        .clone();
+    let table_accessor_config = match &deltalake_table_config {
+        Some(deltalake_table_config) => deltalake_table_config.data_accessor_config.clone(),
+        None => table_components
+            .moonlink_table_config
+            .iceberg_table_config
+            .data_accessor_config
+            .clone(),
+    };
+    let table_filesystem_accessor: Arc<dyn BaseFileSystemAccess> =
+        Arc::new(FileSystemAccessor::new(table_accessor_config));
+    let mut mooncake_table_config = table_components
+        .moonlink_table_config
+        .mooncake_table_config
+        .clone();
+
+    // Resolve table data key; table files are encrypted by their writers, while WAL is encrypted by its accessor.
+    let mut table_encryption = None;
+    if let Some(encryption_config) = &mooncake_table_config.encryption_config {
+        if deltalake_table_config.is_some() {
+            return Err(moonlink::Error::invalid_table_config(
+                "Encryption is not supported for delta lake tables".to_string(),
+            )
+            .into());
+        }
+        let cur_table_encryption = TableEncryption::load_or_create(
+            encryption_config,
+            table_filesystem_accessor.clone(),
+            &mooncake_table_id,
+        )
+        .await?;
+        wal_file_accessor = cur_table_encryption.wrap_wal_filesystem_accessor(wal_file_accessor);
+        mooncake_table_config.table_encryption = Some(cur_table_encryption.clone());
+        table_encryption = Some(cur_table_encryption);
+    }
 
     let wal_persistence_metadata = {
         if is_recovery {
@@ -126,33 +169,40 @@ pub async fn build_table_components(
             wal_config.clone(),
         )
     } else {
-        WalManager::new(&wal_config)
+        WalManager::new_with_file_system_accessor(&wal_config, wal_file_accessor.clone())
     };
 
-    let table = MooncakeTable::new(
-        arrow_schema,
-        mooncake_table_id,
-        get_next_table_id(),
-        write_cache_path,
-        table_components
-            .moonlink_table_config
-            .iceberg_table_config
-            .clone(),
-        table_components
-            .moonlink_table_config
-            .mooncake_table_config
-            .clone(),
-        wal_manager,
-        Arc::new(table_components.object_storage_cache),
-        Arc::new(FileSystemAccessor::new(
+    let object_storage_cache = Arc::new(table_components.object_storage_cache);
+    let table = if let Some(deltalake_table_config) = deltalake_table_config {
+        MooncakeTable::new_with_deltalake_table(
+            arrow_schema,
+            mooncake_table_id,
+            get_next_table_id(),
+            write_cache_path,
+            deltalake_table_config,
+            mooncake_table_config,
+            wal_manager,
+            object_storage_cache,
+            table_filesystem_accessor,
+        )
+        .await?
+    } else {
+        MooncakeTable::new(
+            arrow_schema,
+            mooncake_table_id,
+            get_next_table_id(),
+            write_cache_path,
             table_components
                 .moonlink_table_config
                 .iceberg_table_config
-                .data_accessor_config
                 .clone(),
-        )),
-    )
-    .await?;
+            mooncake_table_config,
+            wal_manager,
+            object_storage_cache,
+            table_filesystem_accessor,
+        )
+        .await?
+    };
 
     let last_persistence_snapshot_lsn = table.get_persistence_snapshot_lsn();
 
@@ -184,10 +234,16 @@ pub async fn build_table_components(
         replication_state.subscribe(),
         /*event_replay_tx=*/ None,
         /*table_event_replay_tx=*/ None,
+        table_components.memory_accountant,
     )
     .await;
     let flush_lsn_rx = event_sync_receiver.flush_lsn_rx.clone();
     let wal_flush_lsn_rx = event_sync_receiver.wal_flush_lsn_rx.clone();
+    let change_feed = WalChangeFeed::new(
+        wal_file_accessor.clone(),
+        wal_config,
+        wal_flush_lsn_rx.clone(),
+    );
     let table_event_manager =
         TableEventManager::new(table_handler.get_event_sender(), event_sync_receiver);
     let event_sender = table_handler.get_event_sender();
@@ -197,12 +253,14 @@ pub async fn build_table_components(
         read_state_manager,
         table_status_reader,
         table_event_manager,
+        change_feed,
         commit_state: Some(commit_state),
         flush_lsn_rx: Some(flush_lsn_rx),
         wal_flush_lsn_rx: Some(wal_flush_lsn_rx),
         wal_file_accessor,
         wal_persistence_metadata,
         last_persistence_snapshot_lsn,
+        table_encryption,
     };
     Ok(table_resource)
 }
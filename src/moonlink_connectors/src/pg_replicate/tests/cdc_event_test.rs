@@ -8,6 +8,7 @@ use super::test_utils::{
 use crate::pg_replicate::conversions::cdc_event::CdcEvent;
 use crate::pg_replicate::conversions::Cell;
 use crate::pg_replicate::postgres_source::{CdcStreamConfig, PostgresSource};
+use crate::pg_replicate::table::{LookupKey, ReplicaIdentity};
 use futures::StreamExt;
 use serial_test::serial;
 use std::collections::VecDeque;
@@ -426,3 +427,228 @@ async fn test_null() {
     assert!(matches!(row3.values.get(1), Some(Cell::Null)));
     assert!(matches!(row3.values.get(2), Some(Cell::String(s)) if s == "null"));
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[serial]
+async fn test_default_replica_identity() {
+    let client = setup_connection().await;
+
+    let table_name = format!("test_cdc_default_identity");
+    let publication = format!("test_cdc_default_identity_pub");
+    let slot_name = format!("test_cdc_default_identity_slot");
+
+    let mut resources = TestResources::new(client);
+    resources.add_table(table_name.clone());
+    resources.add_publication(publication.clone());
+    resources.add_slot(slot_name.clone());
+
+    // Keep the default replica identity, so old tuples only carry primary key columns.
+    resources
+        .client()
+        .simple_query(&format!(
+            "CREATE TABLE {table_name} (
+                id INTEGER PRIMARY KEY,
+                t1 TEXT
+            );"
+        ))
+        .await
+        .unwrap();
+    create_publication_for_table(resources.client(), &publication, &table_name).await;
+
+    let (replication_client, confirmed_flush_lsn) =
+        create_replication_client_and_slot(&slot_name).await;
+
+    let cdc_config = CdcStreamConfig {
+        publication: publication.clone(),
+        slot_name: slot_name.clone(),
+        confirmed_flush_lsn: confirmed_flush_lsn,
+    };
+
+    let cdc_stream = PostgresSource::create_cdc_stream(replication_client, cdc_config.clone())
+        .await
+        .unwrap();
+
+    // Lookup key comes from primary key, which is the replica identity.
+    let table_schema = fetch_table_schema(&publication, &table_name).await;
+    assert_eq!(table_schema.replica_identity, ReplicaIdentity::Default);
+    assert!(
+        matches!(&table_schema.lookup_key, LookupKey::Key { columns, .. } if columns == &vec!["id".to_string()])
+    );
+    assert!(!table_schema.requires_replica_identity_full());
+    let mut pinned_stream = Box::pin(cdc_stream);
+    pinned_stream.as_mut().add_table_schema(table_schema);
+
+    let sql_tx = spawn_sql_executor(database_url());
+    resources.set_sql_tx(sql_tx.clone());
+    sql_tx
+        .send(format!(
+            "INSERT INTO {table_name} VALUES (1, 'a'), (2, 'b');"
+        ))
+        .unwrap();
+    sql_tx
+        .send(format!("UPDATE {table_name} SET t1 = 'c' WHERE id = 1;"))
+        .unwrap();
+    sql_tx
+        .send(format!("UPDATE {table_name} SET id = 3 WHERE id = 2;"))
+        .unwrap();
+    sql_tx
+        .send(format!("DELETE FROM {table_name} WHERE id = 3;"))
+        .unwrap();
+
+    let mut events = Vec::new();
+    let timeout = Duration::from_secs(EVENT_COLLECTION_SECS);
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < timeout {
+        match tokio::time::timeout(
+            Duration::from_millis(STREAM_NEXT_TIMEOUT_MS),
+            pinned_stream.next(),
+        )
+        .await
+        {
+            Ok(Some(Ok(ev))) => events.push(ev),
+            Ok(Some(Err(e))) => panic!("Error in CDC stream: {:?}", e),
+            Ok(None) => break,
+            Err(_) => continue,
+        }
+    }
+
+    let updates = events
+        .iter()
+        .filter_map(|e| match e {
+            CdcEvent::Update((_, old_row, new_row, _)) => Some((old_row, new_row)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(updates.len(), 2);
+
+    // Primary key unchanged, no old tuple is sent.
+    let (old_row, new_row) = &updates[0];
+    assert!(old_row.is_none());
+    assert!(matches!(new_row.values.get(0), Some(Cell::I32(1))));
+    assert!(matches!(new_row.values.get(1), Some(Cell::String(s)) if s == "c"));
+
+    // Primary key changed, old tuple only carries key columns.
+    let (old_row, new_row) = &updates[1];
+    let old_row = old_row.as_ref().expect("missing old tuple for key update");
+    assert!(matches!(old_row.values.get(0), Some(Cell::I32(2))));
+    assert!(matches!(old_row.values.get(1), Some(Cell::Null)));
+    assert!(matches!(new_row.values.get(0), Some(Cell::I32(3))));
+
+    // Delete carries key-only tuple.
+    let deleted_row = events
+        .iter()
+        .find_map(|e| match e {
+            CdcEvent::Delete((_, row, _)) => Some(row),
+            _ => None,
+        })
+        .expect("missing delete for id=3");
+    assert!(matches!(deleted_row.values.get(0), Some(Cell::I32(3))));
+    assert!(matches!(deleted_row.values.get(1), Some(Cell::Null)));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+#[serial]
+async fn test_key_update_with_unchanged_toast() {
+    let client = setup_connection().await;
+
+    let table_name = format!("test_cdc_key_update_toast");
+    let publication = format!("test_cdc_key_update_toast_pub");
+    let slot_name = format!("test_cdc_key_update_toast_slot");
+
+    let mut resources = TestResources::new(client);
+    resources.add_table(table_name.clone());
+    resources.add_publication(publication.clone());
+    resources.add_slot(slot_name.clone());
+
+    // Store text out of line uncompressed, so an update which doesn't touch it sends it as unchanged TOAST.
+    resources
+        .client()
+        .simple_query(&format!(
+            "CREATE TABLE {table_name} (
+                id INTEGER PRIMARY KEY,
+                t1 TEXT
+            );
+            ALTER TABLE {table_name} ALTER COLUMN t1 SET STORAGE EXTERNAL;"
+        ))
+        .await
+        .unwrap();
+    create_publication_for_table(resources.client(), &publication, &table_name).await;
+
+    let (replication_client, confirmed_flush_lsn) =
+        create_replication_client_and_slot(&slot_name).await;
+
+    let cdc_config = CdcStreamConfig {
+        publication: publication.clone(),
+        slot_name: slot_name.clone(),
+        confirmed_flush_lsn: confirmed_flush_lsn,
+    };
+
+    let cdc_stream = PostgresSource::create_cdc_stream(replication_client, cdc_config.clone())
+        .await
+        .unwrap();
+
+    let table_schema = fetch_table_schema(&publication, &table_name).await;
+    let mut pinned_stream = Box::pin(cdc_stream);
+    pinned_stream.as_mut().add_table_schema(table_schema);
+
+    let toast_value = "x".repeat(10_000);
+    let sql_tx = spawn_sql_executor(database_url());
+    resources.set_sql_tx(sql_tx.clone());
+    sql_tx
+        .send(format!(
+            "INSERT INTO {table_name} VALUES (1, '{toast_value}');"
+        ))
+        .unwrap();
+    // Default replica identity sends a key tuple for key change.
+    sql_tx
+        .send(format!("UPDATE {table_name} SET id = 2 WHERE id = 1;"))
+        .unwrap();
+    // Full replica identity sends a full old tuple for key change.
+    sql_tx
+        .send(format!(
+            "ALTER TABLE {table_name} REPLICA IDENTITY FULL; UPDATE {table_name} SET id = 3 WHERE id = 2;"
+        ))
+        .unwrap();
+
+    let mut events = Vec::new();
+    let timeout = Duration::from_secs(EVENT_COLLECTION_SECS);
+    let start_time = std::time::Instant::now();
+    while start_time.elapsed() < timeout {
+        match tokio::time::timeout(
+            Duration::from_millis(STREAM_NEXT_TIMEOUT_MS),
+            pinned_stream.next(),
+        )
+        .await
+        {
+            Ok(Some(Ok(ev))) => events.push(ev),
+            Ok(Some(Err(e))) => panic!("Error in CDC stream: {:?}", e),
+            Ok(None) => break,
+            Err(_) => continue,
+        }
+    }
+
+    let updates = events
+        .iter()
+        .filter_map(|e| match e {
+            CdcEvent::Update((_, old_row, new_row, _)) => Some((old_row, new_row)),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(updates.len(), 2);
+
+    // Key tuple sends non-key columns as null, which shouldn't overwrite the unchanged TOAST column.
+    let (old_row, new_row) = &updates[0];
+    let old_row = old_row.as_ref().expect("missing key tuple for key update");
+    assert!(matches!(old_row.values.get(0), Some(Cell::I32(1))));
+    assert!(matches!(old_row.values.get(1), Some(Cell::Null)));
+    assert!(matches!(new_row.values.get(0), Some(Cell::I32(2))));
+    assert!(matches!(new_row.values.get(1), Some(Cell::UnchangedToast)));
+
+    // Full old tuple carries the unchanged TOAST value.
+    let (old_row, new_row) = &updates[1];
+    let old_row = old_row.as_ref().expect("missing old tuple for key update");
+    assert!(matches!(old_row.values.get(0), Some(Cell::I32(2))));
+    assert!(matches!(old_row.values.get(1), Some(Cell::String(s)) if s == &toast_value));
+    assert!(matches!(new_row.values.get(0), Some(Cell::I32(3))));
+    assert!(matches!(new_row.values.get(1), Some(Cell::String(s)) if s == &toast_value));
+}
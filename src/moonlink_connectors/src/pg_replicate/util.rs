@@ -361,7 +361,8 @@ impl From<Cell> for RowValue {
                     }
                 }
             }
-            Cell::Null => RowValue::Null,
+            // Unchanged TOAST values are filled by moonlink table from the current row.
+            Cell::Null | Cell::UnchangedToast => RowValue::Null,
         }
     }
 }
@@ -376,7 +377,9 @@ impl From<PostgresTableRow> for MoonlinkRow {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::pg_replicate::table::{ColumnSchema, LookupKey, TableName, TableSchema};
+    use crate::pg_replicate::table::{
+        ColumnSchema, LookupKey, ReplicaIdentity, TableName, TableSchema,
+    };
     use arrow::array::{Date32Array, StringArray, TimestampMicrosecondArray};
     use arrow::datatypes::DataType;
     use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc};
@@ -394,132 +397,154 @@ mod tests {
             src_table_id: 1,
             column_schemas: vec![
                 ColumnSchema {
+                    attnum: 1,
                     name: "bool_field".to_string(),
                     typ: Type::BOOL,
                     modifier: 0,
                     nullable: false,
                 },
                 ColumnSchema {
+                    attnum: 2,
                     name: "int2_field".to_string(),
                     typ: Type::INT2,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 3,
                     name: "int4_field".to_string(),
                     typ: Type::INT4,
                     modifier: 0,
                     nullable: false,
                 },
                 ColumnSchema {
+                    attnum: 4,
                     name: "int8_field".to_string(),
                     typ: Type::INT8,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 5,
                     name: "float4_field".to_string(),
                     typ: Type::FLOAT4,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 6,
                     name: "float8_field".to_string(),
                     typ: Type::FLOAT8,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 7,
                     name: "numeric_field".to_string(),
                     typ: Type::NUMERIC,
                     modifier: ((12 << 16) | 5) + 4, // NUMERIC(12,5)
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 8,
                     name: "varchar_field".to_string(),
                     typ: Type::VARCHAR,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 9,
                     name: "text_field".to_string(),
                     typ: Type::TEXT,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 10,
                     name: "bpchar_field".to_string(),
                     typ: Type::BPCHAR,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 11,
                     name: "char_field".to_string(),
                     typ: Type::CHAR,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 12,
                     name: "name_field".to_string(),
                     typ: Type::NAME,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 13,
                     name: "date_field".to_string(),
                     typ: Type::DATE,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 14,
                     name: "timestamp_field".to_string(),
                     typ: Type::TIMESTAMP,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 15,
                     name: "timestamptz_field".to_string(),
                     typ: Type::TIMESTAMPTZ,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 16,
                     name: "time_field".to_string(),
                     typ: Type::TIME,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 17,
                     name: "timetz_field".to_string(),
                     typ: Type::TIMETZ,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 18,
                     name: "uuid_field".to_string(),
                     typ: Type::UUID,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 19,
                     name: "json_field".to_string(),
                     typ: Type::JSON,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 20,
                     name: "jsonb_field".to_string(),
                     typ: Type::JSONB,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 21,
                     name: "bytea_field".to_string(),
                     typ: Type::BYTEA,
                     modifier: 0,
                     nullable: true,
                 },
                 ColumnSchema {
+                    attnum: 22,
                     name: "oid_field".to_string(),
                     typ: Type::OID,
                     modifier: 0,
@@ -527,6 +552,7 @@ mod tests {
                 },
                 // Array type.
                 ColumnSchema {
+                    attnum: 23,
                     name: "bool_array_field".to_string(),
                     typ: Type::BOOL_ARRAY,
                     modifier: 0,
@@ -536,6 +562,7 @@ mod tests {
                 // Column type: point
                 // Arrow type: Struct {x: Int32, y: Int32}
                 ColumnSchema {
+                    attnum: 24,
                     name: "point_field".to_string(),
                     typ: Type::new(
                         "point".to_string(),
@@ -553,6 +580,7 @@ mod tests {
                 // Column type: point[]
                 // Arrow type: List<Struct {x: Int32, y: Int32}>
                 ColumnSchema {
+                    attnum: 25,
                     name: "point_array_field".to_string(),
                     typ: Type::new(
                         "point_array".to_string(),
@@ -579,6 +607,7 @@ mod tests {
                 //   top_left:  Struct { x: Int32, y: Int32 },
                 // }
                 ColumnSchema {
+                    attnum: 26,
                     name: "rectangle_field".to_string(),
                     typ: Type::new(
                         "rectangle".to_string(),
@@ -605,6 +634,7 @@ mod tests {
                 name: "uuid_field".to_string(),
                 columns: vec!["uuid_field".to_string()],
             },
+            replica_identity: ReplicaIdentity::Full,
         };
 
         let (arrow_schema, identity) = postgres_schema_to_moonlink_schema(&table_schema);
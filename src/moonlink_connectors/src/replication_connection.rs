@@ -1,16 +1,20 @@
+use crate::kafka_ingest::{KafkaConnection, KAFKA_URI_PREFIX};
 use crate::pg_replicate::table_init::{build_table_components, TableComponents};
 use crate::pg_replicate::{table::SrcTableId, PostgresConnection};
 use crate::rest_ingest::event_request::EventRequest;
 use crate::rest_ingest::RestApiConnection;
 use crate::{Error, Result};
 use moonlink::{
-    MooncakeTableId, MoonlinkTableConfig, ObjectStorageCache, ReadStateFilepathRemap,
-    ReadStateManager, TableEventManager, TableStatusReader,
+    MemoryAccountant, MooncakeTableId, MoonlinkTableConfig, ObjectStorageCache,
+    ReadStateFilepathRemap, ReadStateManager, ReplicationState, TableEncryption, TableEventManager,
+    TableStatusReader, WalChangeFeed,
 };
 
-use arrow_schema::Schema as ArrowSchema;
+use arrow_schema::{FieldRef, Schema as ArrowSchema};
+use moonlink::row::IdentityProp;
 use std::collections::HashMap;
 use std::hash::Hash;
+use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::{debug, warn};
@@ -19,6 +23,7 @@ use tracing::{debug, warn};
 pub enum SourceType {
     Postgres(PostgresConnection),
     RestApi(RestApiConnection),
+    Kafka(KafkaConnection),
 }
 
 impl SourceType {
@@ -26,6 +31,7 @@ impl SourceType {
         match self {
             SourceType::Postgres(conn) => conn.spawn_replication_task().await,
             SourceType::RestApi(conn) => conn.spawn_rest_task().await,
+            SourceType::Kafka(conn) => conn.spawn_kafka_task().await,
         }
     }
 
@@ -33,6 +39,7 @@ impl SourceType {
         match self {
             SourceType::Postgres(conn) => conn.shutdown_replication().await,
             SourceType::RestApi(conn) => conn.shutdown_replication().await,
+            SourceType::Kafka(conn) => conn.shutdown_replication().await,
         }
     }
 
@@ -40,6 +47,7 @@ impl SourceType {
         match self {
             SourceType::Postgres(conn) => conn.drop_table(src_table_id, table_name).await,
             SourceType::RestApi(conn) => conn.drop_table(src_table_id, table_name).await,
+            SourceType::Kafka(conn) => conn.drop_table(src_table_id, table_name).await,
         }
     }
 
@@ -48,7 +56,7 @@ impl SourceType {
     async fn finalize(&mut self, postgres_drop_all: bool) -> Result<()> {
         match self {
             SourceType::Postgres(conn) => conn.shutdown(postgres_drop_all).await,
-            SourceType::RestApi(_) => Ok(()),
+            SourceType::RestApi(_) | SourceType::Kafka(_) => Ok(()),
         }
     }
 }
@@ -58,6 +66,8 @@ struct TableState {
     reader: ReadStateManager,
     event_manager: TableEventManager,
     status_reader: TableStatusReader,
+    change_feed: WalChangeFeed,
+    table_encryption: Option<Arc<TableEncryption>>,
 }
 
 /// Id which uniquely identifies a table, including source information (src uri, src table id) and destination information (mooncake table id).
@@ -67,7 +77,7 @@ struct UniqueTableId {
     src_table_id: u32,
 }
 
-/// Manages replication for table(s) within a database from various sources (PostgreSQL CDC, REST API, etc.).
+/// Manages replication for table(s) within a database from various sources (PostgreSQL CDC, REST API, Kafka, etc.).
 pub struct ReplicationConnection {
     table_base_path: String,
     // Source-specific connections
@@ -80,6 +90,8 @@ pub struct ReplicationConnection {
     replication_started: bool,
     /// Object storage cache.
     object_storage_cache: ObjectStorageCache,
+    /// Memory accountant shared by all tables.
+    memory_accountant: MemoryAccountant,
 }
 
 impl ReplicationConnection {
@@ -87,17 +99,21 @@ impl ReplicationConnection {
         uri: String,
         table_base_path: String,
         object_storage_cache: ObjectStorageCache,
+        memory_accountant: MemoryAccountant,
     ) -> Result<Self> {
         let source = if uri.starts_with("rest://") {
             SourceType::RestApi(RestApiConnection::new().await?)
+        } else if uri.starts_with(KAFKA_URI_PREFIX) {
+            SourceType::Kafka(KafkaConnection::new(&uri, memory_accountant.clone()).await?)
         } else {
-            SourceType::Postgres(PostgresConnection::new(uri).await?)
+            SourceType::Postgres(PostgresConnection::new(uri, memory_accountant.clone()).await?)
         };
 
         Ok(Self {
             source,
             table_base_path,
             object_storage_cache,
+            memory_accountant,
             table_states: HashMap::new(),
             replication_started: false,
             handle: None,
@@ -115,6 +131,9 @@ impl ReplicationConnection {
             SourceType::Postgres(_) => {
                 panic!("rest request sender not available for postgres source")
             }
+            SourceType::Kafka(_) => {
+                panic!("rest request sender not available for kafka source")
+            }
             SourceType::RestApi(conn) => conn.get_rest_request_sender(),
         }
     }
@@ -147,6 +166,34 @@ impl ReplicationConnection {
             .status_reader
     }
 
+    pub fn get_table_change_feed(
+        &self,
+        mooncake_table_id: &MooncakeTableId,
+        src_table_id: SrcTableId,
+    ) -> &WalChangeFeed {
+        let unique_table_id = UniqueTableId {
+            mooncake_table_id: mooncake_table_id.clone(),
+            src_table_id,
+        };
+        &self.table_states.get(&unique_table_id).unwrap().change_feed
+    }
+
+    pub fn get_table_encryption(
+        &self,
+        mooncake_table_id: &MooncakeTableId,
+        src_table_id: SrcTableId,
+    ) -> Option<Arc<TableEncryption>> {
+        let unique_table_id = UniqueTableId {
+            mooncake_table_id: mooncake_table_id.clone(),
+            src_table_id,
+        };
+        self.table_states
+            .get(&unique_table_id)
+            .unwrap()
+            .table_encryption
+            .clone()
+    }
+
     pub fn get_table_status_readers(&self) -> HashMap<MooncakeTableId, &TableStatusReader> {
         self.table_states
             .iter()
@@ -223,6 +270,8 @@ impl ReplicationConnection {
                     reader: table_resources.read_state_manager,
                     event_manager: table_resources.table_event_manager,
                     status_reader: table_resources.table_status_reader,
+                    change_feed: table_resources.change_feed,
+                    table_encryption: table_resources.table_encryption,
                 };
 
                 // TODO(hjiang): Add assertion or error propagation.
@@ -237,13 +286,17 @@ impl ReplicationConnection {
             SourceType::RestApi(_) => {
                 panic!("Cannot add replication table to REST API connection")
             }
+            SourceType::Kafka(_) => {
+                panic!("Cannot add replication table to Kafka connection")
+            }
         }
     }
 
-    /// Add a table for REST API ingestion with Arrow schema
+    /// Add a table for REST API or Kafka ingestion with Arrow schema
     ///
     /// # Arguments
     ///
+    /// * src_table_name: source table name for REST API, or topic name for Kafka.
     /// * persist_lsn: only assigned at recovery, used to indicate and update replication LSN.
     #[allow(clippy::too_many_arguments)]
     pub async fn add_table_api(
@@ -255,88 +308,117 @@ impl ReplicationConnection {
         read_state_filepath_remap: ReadStateFilepathRemap,
         persist_lsn: Option<u64>,
     ) -> Result<SrcTableId> {
-        match &mut self.source {
+        let (src_table_id, replication_state) = match &self.source {
             SourceType::RestApi(conn) => {
                 debug!(src_table_name, "adding REST API table");
+                (conn.next_src_table_id(), conn.get_replication_state())
+            }
+            // LSNs are derived from per-topic offsets, so each topic has its own replication state.
+            SourceType::Kafka(conn) => {
+                debug!(src_table_name, "adding Kafka table");
+                (conn.next_src_table_id(), ReplicationState::new())
+            }
+            SourceType::Postgres(_) => {
+                panic!("Cannot add API table to PostgreSQL connection")
+            }
+        };
+        let table_components = TableComponents {
+            read_state_filepath_remap,
+            object_storage_cache: self.object_storage_cache.clone(),
+            memory_accountant: self.memory_accountant.clone(),
+            moonlink_table_config,
+        };
 
-                let src_table_id = conn.next_src_table_id();
-                let table_components = TableComponents {
-                    read_state_filepath_remap,
-                    object_storage_cache: self.object_storage_cache.clone(),
-                    moonlink_table_config,
-                };
-
-                // Create MooncakeTable resources using the table init function.
-                let replication_state = conn.get_replication_state();
-                let mut table_resources = build_table_components(
-                    mooncake_table_id.to_string(),
-                    arrow_schema.clone(),
+        // Create MooncakeTable resources using the table init function.
+        let mut table_resources = build_table_components(
+            mooncake_table_id.to_string(),
+            arrow_schema.clone(),
+            src_table_name.to_string(),
+            src_table_id,
+            &self.table_base_path,
+            &replication_state,
+            table_components,
+            /*is_recovery=*/ persist_lsn.is_some(),
+        )
+        .await?;
+
+        // Add table to source and connect to sink.
+        let schema = std::sync::Arc::new(arrow_schema);
+        let event_sender = table_resources.event_sender.clone();
+        let commit_lsn_tx = table_resources
+            .commit_state
+            .take()
+            .expect("commit_lsn_tx not set");
+        let flush_lsn_rx = table_resources
+            .flush_lsn_rx
+            .take()
+            .expect("flush_lsn_rx not set");
+        let wal_flush_lsn_rx = table_resources
+            .wal_flush_lsn_rx
+            .take()
+            .expect("wal_flush_lsn_rx not set");
+        match &self.source {
+            SourceType::RestApi(conn) => {
+                conn.add_table(
                     src_table_name.to_string(),
                     src_table_id,
-                    &self.table_base_path,
-                    &replication_state,
-                    table_components,
-                    /*is_recovery=*/ persist_lsn.is_some(),
+                    schema,
+                    event_sender,
+                    commit_lsn_tx,
+                    flush_lsn_rx,
+                    wal_flush_lsn_rx,
+                    persist_lsn,
                 )
-                .await?;
-
-                // Add table to RestSource and connect to RestSink
+                .await?
+            }
+            SourceType::Kafka(conn) => {
                 conn.add_table(
                     src_table_name.to_string(),
                     src_table_id,
-                    std::sync::Arc::new(arrow_schema),
-                    table_resources.event_sender.clone(),
-                    table_resources
-                        .commit_state
-                        .take()
-                        .expect("commit_lsn_tx not set"),
-                    table_resources
-                        .flush_lsn_rx
-                        .take()
-                        .expect("flush_lsn_rx not set"),
-                    table_resources
-                        .wal_flush_lsn_rx
-                        .take()
-                        .expect("wal_flush_lsn_rx not set"),
+                    schema,
+                    event_sender,
+                    commit_lsn_tx,
+                    flush_lsn_rx,
+                    wal_flush_lsn_rx,
+                    replication_state.clone(),
                     persist_lsn,
                 )
-                .await?;
-
-                // Store table state
-                let table_state = TableState {
-                    src_table_name: src_table_name.to_string(),
-                    reader: table_resources.read_state_manager,
-                    event_manager: table_resources.table_event_manager,
-                    status_reader: table_resources.table_status_reader,
-                };
-
-                let unique_table_id = UniqueTableId {
-                    mooncake_table_id: mooncake_table_id.clone(),
-                    src_table_id,
-                };
-                // TODO(hjiang): Add assertion or error propagation.
-                self.table_states.insert(unique_table_id, table_state);
-                debug!(
-                    src_table_id,
-                    src_table_name, "REST API table added successfully"
-                );
-                Ok(src_table_id)
-            }
-            SourceType::Postgres(_) => {
-                panic!("Cannot add API table to PostgreSQL connection")
+                .await?
             }
+            SourceType::Postgres(_) => unreachable!(),
         }
+
+        // Store table state
+        let table_state = TableState {
+            src_table_name: src_table_name.to_string(),
+            reader: table_resources.read_state_manager,
+            event_manager: table_resources.table_event_manager,
+            status_reader: table_resources.table_status_reader,
+            change_feed: table_resources.change_feed,
+            table_encryption: table_resources.table_encryption,
+        };
+
+        let unique_table_id = UniqueTableId {
+            mooncake_table_id: mooncake_table_id.clone(),
+            src_table_id,
+        };
+        // TODO(hjiang): Add assertion or error propagation.
+        self.table_states.insert(unique_table_id, table_state);
+        debug!(src_table_id, src_table_name, "API table added successfully");
+        Ok(src_table_id)
     }
 
-    /// Set Avro schema for an existing REST table
+    /// Set Avro schema for an existing REST or Kafka table
     ///
     /// # Arguments
     ///
     /// * src_table_name: Source table name to set Avro schema for
+    /// * schema_id: Schema id in confluent wire format, if the schema comes from a schema registry
     /// * avro_schema: Avro schema for parsing data
     pub async fn set_avro_schema(
         &mut self,
         src_table_name: String,
+        schema_id: Option<u32>,
         avro_schema: apache_avro::schema::Schema,
     ) -> Result<()> {
         match &mut self.source {
@@ -344,16 +426,108 @@ impl ReplicationConnection {
                 debug!(src_table_name, "setting Avro schema for REST table");
 
                 // Set Avro schema on the REST connection
-                conn.set_avro_schema(src_table_name, avro_schema).await?;
+                conn.set_avro_schema(src_table_name, schema_id, avro_schema)
+                    .await?;
 
                 Ok(())
             }
+            SourceType::Kafka(conn) => {
+                debug!(src_table_name, "setting Avro schema for Kafka topic");
+                conn.set_avro_schema(src_table_name, schema_id, avro_schema)
+                    .await?;
+                Ok(())
+            }
             SourceType::Postgres(_) => {
                 panic!("Cannot set Avro schema on PostgreSQL connection")
             }
         }
     }
 
+    /// Alter schema for an existing REST table.
+    ///
+    /// # Arguments
+    ///
+    /// * src_table_name: source table name used for REST ingestion, which must match the table if assigned
+    /// * columns_to_add: nullable columns to append at the end of the table
+    /// * columns_to_rename: columns to rename, in the format of (old name, new name)
+    pub async fn alter_table(
+        &self,
+        mooncake_table_id: &MooncakeTableId,
+        src_table_id: SrcTableId,
+        src_table_name: Option<&str>,
+        columns_to_add: Vec<FieldRef>,
+        columns_to_drop: Vec<String>,
+        columns_to_rename: Vec<(String, String)>,
+    ) -> Result<()> {
+        let SourceType::RestApi(conn) = &self.source else {
+            return Err(Error::rest_invalid_schema_change(format!(
+                "table {mooncake_table_id} is not created via REST API"
+            )));
+        };
+        let unique_table_id = UniqueTableId {
+            mooncake_table_id: mooncake_table_id.clone(),
+            src_table_id,
+        };
+        let table_state = self
+            .table_states
+            .get(&unique_table_id)
+            .ok_or(Error::table_not_found(mooncake_table_id.to_string()))?;
+        if let Some(src_table_name) = src_table_name {
+            if src_table_name != table_state.src_table_name {
+                return Err(Error::rest_invalid_schema_change(format!(
+                    "source table {src_table_name} doesn't match table {mooncake_table_id}, whose source table is {}",
+                    table_state.src_table_name
+                )));
+            }
+        }
+
+        // Columns referenced by table config cannot be dropped, while key indices of other columns are remapped by the table.
+        let table_config = table_state.status_reader.get_current_table_config().await;
+        let table_schema = table_state.status_reader.get_current_table_schema().await?;
+        let key_columns = match table_config.row_identity {
+            IdentityProp::SinglePrimitiveKey(_) | IdentityProp::Keys(_) => table_config
+                .row_identity
+                .get_key_indices(table_schema.fields().len())
+                .into_iter()
+                .map(|idx| table_schema.field(idx).name().clone())
+                .collect::<Vec<_>>(),
+            IdentityProp::FullRow | IdentityProp::None => vec![],
+        };
+        for column in key_columns.iter() {
+            if columns_to_drop.contains(column) {
+                return Err(Error::rest_invalid_schema_change(format!(
+                    "cannot drop row identity column {column}"
+                )));
+            }
+        }
+        let config_columns = table_config
+            .partition_spec
+            .iter()
+            .map(|field| &field.source_column)
+            .chain(
+                table_config
+                    .sort_order
+                    .iter()
+                    .map(|field| &field.source_column),
+            );
+        for column in config_columns {
+            if columns_to_drop.contains(column) {
+                return Err(Error::rest_invalid_schema_change(format!(
+                    "cannot drop partition or sort column {column}"
+                )));
+            }
+        }
+
+        debug!(src_table_id, "altering REST table");
+        conn.alter_table(
+            table_state.src_table_name.clone(),
+            columns_to_add,
+            columns_to_drop,
+            columns_to_rename,
+        )
+        .await
+    }
+
     /// Remove the given table from connection.
     pub async fn drop_table(
         &mut self,
@@ -416,7 +590,7 @@ impl ReplicationConnection {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use moonlink::{ObjectStorageCache, ObjectStorageCacheConfig};
+    use moonlink::{MemoryAccountant, ObjectStorageCache, ObjectStorageCacheConfig};
 
     #[tokio::test]
     async fn test_rest_api_connection_creation() {
@@ -432,6 +606,7 @@ mod tests {
             crate::replication_manager::REST_API_URI.to_string(),
             temp_dir.path().join("tables").to_string_lossy().to_string(),
             object_storage_cache,
+            MemoryAccountant::default(),
         )
         .await
         .unwrap();
@@ -1,13 +1,17 @@
 use crate::pg_replicate::table::SrcTableId;
 use crate::rest_ingest::event_request::EventRequest;
 use crate::ReplicationConnection;
+use crate::KAFKA_URI_PREFIX;
 use crate::{Error, Result};
+use arrow_schema::FieldRef;
 use moonlink::{
-    MooncakeTableId, MoonlinkTableConfig, ObjectStorageCache, ReadStateManager, TableEventManager,
+    MemoryAccountant, MooncakeTableId, MoonlinkTableConfig, ObjectStorageCache, ReadStateManager,
+    TableEventManager,
 };
-use moonlink::{ReadStateFilepathRemap, TableStatusReader};
+use moonlink::{ReadStateFilepathRemap, TableEncryption, TableStatusReader, WalChangeFeed};
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
+use std::sync::Arc;
 use tokio::task::JoinHandle;
 use tracing::debug;
 
@@ -28,21 +32,32 @@ pub struct ReplicationManager {
     table_base_path: String,
     /// Object storage cache.
     object_storage_cache: ObjectStorageCache,
+    /// Memory accountant shared by all tables.
+    memory_accountant: MemoryAccountant,
     /// Background shutdown handles.
     shutdown_handles: Vec<JoinHandle<Result<()>>>,
 }
 
 impl ReplicationManager {
-    pub fn new(table_base_path: String, object_storage_cache: ObjectStorageCache) -> Self {
+    pub fn new(
+        table_base_path: String,
+        object_storage_cache: ObjectStorageCache,
+        memory_accountant: MemoryAccountant,
+    ) -> Self {
         Self {
             connections: HashMap::new(),
             table_info: HashMap::new(),
             table_base_path,
             object_storage_cache,
+            memory_accountant,
             shutdown_handles: Vec::new(),
         }
     }
 
+    pub fn get_memory_accountant(&self) -> &MemoryAccountant {
+        &self.memory_accountant
+    }
+
     pub async fn get_or_create_connection(
         &mut self,
         src_uri: &str,
@@ -58,6 +73,7 @@ impl ReplicationManager {
                     src_uri.to_string(),
                     base_path.to_str().unwrap().to_string(),
                     self.object_storage_cache.clone(),
+                    self.memory_accountant.clone(),
                 )
                 .await?;
                 entry.insert(replication_connection)
@@ -176,15 +192,70 @@ impl ReplicationManager {
         Ok(())
     }
 
+    /// Add a table which ingests from a Kafka topic.
+    ///
+    /// If the Kafka connection for this `uri` doesn't exist, a new one will be created and started.
+    ///
+    /// # Arguments
+    ///
+    /// * src_uri: Kafka URI, which starts with `kafka://`
+    /// * topic: Kafka topic to consume
+    /// * arrow_schema: Arrow schema for the table
+    /// * flush_lsn: only assigned when recovery, which indicates the iceberg persistence LSN; otherwise it's a fresh table.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn add_kafka_table(
+        &mut self,
+        src_uri: &str,
+        mooncake_table_id: MooncakeTableId,
+        topic: &str,
+        arrow_schema: arrow_schema::Schema,
+        moonlink_table_config: MoonlinkTableConfig,
+        read_state_filepath_remap: ReadStateFilepathRemap,
+        flush_lsn: Option<u64>,
+    ) -> Result<()> {
+        debug!(%src_uri, topic, "adding Kafka table through manager");
+
+        // Error handling: don't allow duplicate mooncake table id be registered.
+        if self.table_info.contains_key(&mooncake_table_id) {
+            return Err(Error::repl_duplicate_table(mooncake_table_id.to_string()));
+        }
+
+        let replication_connection = self.get_or_create_connection(src_uri).await?;
+        if !replication_connection.replication_started() {
+            replication_connection.start_replication().await?;
+        }
+
+        let src_table_id = replication_connection
+            .add_table_api(
+                topic,
+                &mooncake_table_id,
+                arrow_schema,
+                moonlink_table_config,
+                read_state_filepath_remap,
+                flush_lsn,
+            )
+            .await?;
+        assert!(self
+            .table_info
+            .insert(mooncake_table_id, (src_uri.to_string(), src_table_id))
+            .is_none());
+
+        debug!(src_table_id, "Kafka table added through manager");
+
+        Ok(())
+    }
+
     /// Set Avro schema for an existing REST table
     ///
     /// # Arguments
     ///
     /// * src_table_name: Source table name to set Avro schema for
+    /// * schema_id: Schema id in confluent wire format, if the schema comes from a schema registry
     /// * avro_schema: Avro schema for parsing data
     pub async fn set_avro_schema(
         &mut self,
         src_table_name: String,
+        schema_id: Option<u32>,
         avro_schema: apache_avro::schema::Schema,
     ) -> Result<()> {
         debug!(src_table_name, "setting Avro schema for REST table");
@@ -199,7 +270,7 @@ impl ReplicationManager {
 
         // Set Avro schema on the REST connection
         rest_connection
-            .set_avro_schema(src_table_name.clone(), avro_schema)
+            .set_avro_schema(src_table_name.clone(), schema_id, avro_schema)
             .await?;
 
         debug!(src_table_name, "Avro schema set for REST table");
@@ -207,6 +278,39 @@ impl ReplicationManager {
         Ok(())
     }
 
+    /// Set Avro schema for an existing Kafka topic
+    ///
+    /// # Arguments
+    ///
+    /// * src_uri: Kafka URI the topic is consumed from
+    /// * topic: Kafka topic to set Avro schema for
+    /// * schema_id: Schema id in confluent wire format, if the schema comes from a schema registry
+    /// * avro_schema: Avro schema for parsing message values
+    pub async fn set_kafka_avro_schema(
+        &mut self,
+        src_uri: &str,
+        topic: String,
+        schema_id: Option<u32>,
+        avro_schema: apache_avro::schema::Schema,
+    ) -> Result<()> {
+        debug!(%src_uri, topic, "setting Avro schema for Kafka topic");
+
+        if !src_uri.starts_with(KAFKA_URI_PREFIX) {
+            return Err(Error::kafka(
+                format!("'{src_uri}' is not a Kafka URI."),
+                None,
+            ));
+        }
+        let kafka_connection = self.connections.get_mut(src_uri).ok_or_else(|| {
+            Error::kafka(format!("Kafka connection '{src_uri}' not found."), None)
+        })?;
+        kafka_connection
+            .set_avro_schema(topic, schema_id, avro_schema)
+            .await?;
+
+        Ok(())
+    }
+
     /// Initialize event API connection for data ingestion.
     /// Returns the event request sender channel for the API to use.
     pub async fn initialize_event_api_for_once(
@@ -231,6 +335,7 @@ impl ReplicationManager {
             REST_API_URI.to_string(),
             base_path.to_str().unwrap().to_string(),
             self.object_storage_cache.clone(),
+            self.memory_accountant.clone(),
         )
         .await?;
 
@@ -256,6 +361,28 @@ impl ReplicationManager {
         Ok(())
     }
 
+    /// Alter schema for the given table, which is only supported for tables created via REST API.
+    pub async fn alter_table(
+        &self,
+        mooncake_table_id: &MooncakeTableId,
+        src_table_name: Option<&str>,
+        columns_to_add: Vec<FieldRef>,
+        columns_to_drop: Vec<String>,
+        columns_to_rename: Vec<(String, String)>,
+    ) -> Result<()> {
+        let (src_table_id, connection) = self.get_replication_connection(mooncake_table_id)?;
+        connection
+            .alter_table(
+                mooncake_table_id,
+                src_table_id,
+                src_table_name,
+                columns_to_add,
+                columns_to_drop,
+                columns_to_rename,
+            )
+            .await
+    }
+
     /// Drop table specified by the given table id.
     /// If the table is not tracked, logs a message and returns successfully.
     /// Return whether the table is tracked by moonlink.
@@ -300,6 +427,22 @@ impl ReplicationManager {
         Ok(connection.get_table_status_reader(mooncake_table_id, src_table_id))
     }
 
+    pub fn get_table_change_feed(
+        &self,
+        mooncake_table_id: &MooncakeTableId,
+    ) -> Result<&WalChangeFeed> {
+        let (src_table_id, connection) = self.get_replication_connection(mooncake_table_id)?;
+        Ok(connection.get_table_change_feed(mooncake_table_id, src_table_id))
+    }
+
+    pub fn get_table_encryption(
+        &self,
+        mooncake_table_id: &MooncakeTableId,
+    ) -> Result<Option<Arc<TableEncryption>>> {
+        let (src_table_id, connection) = self.get_replication_connection(mooncake_table_id)?;
+        Ok(connection.get_table_encryption(mooncake_table_id, src_table_id))
+    }
+
     /// Return mapping from mooncake table id to its table status readers.
     pub fn get_table_status_readers(&self) -> HashMap<MooncakeTableId, &TableStatusReader> {
         let mut table_state_readers = HashMap::with_capacity(self.connections.len());
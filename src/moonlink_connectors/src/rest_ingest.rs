@@ -1,4 +1,5 @@
 pub mod avro_converter;
+pub(crate) mod avro_schema_registry;
 pub mod datetime_utils;
 pub mod decimal_utils;
 pub mod event_request;
@@ -6,15 +7,17 @@ pub mod json_converter;
 pub mod moonlink_rest_sink;
 pub mod rest_event;
 pub mod rest_source;
+pub mod schema_inference;
 pub mod schema_util;
 
 use crate::rest_ingest::event_request::EventRequest;
 use crate::rest_ingest::moonlink_rest_sink::RestSink;
 use crate::rest_ingest::moonlink_rest_sink::TableStatus;
+use crate::rest_ingest::rest_event::RestEvent;
 use crate::rest_ingest::rest_source::RestSource;
 use crate::Result;
 use apache_avro::schema::Schema as AvroSchema;
-use arrow_schema::Schema;
+use arrow_schema::{FieldRef, Schema};
 use futures::StreamExt;
 use moonlink::CommitState;
 use moonlink::ReplicationState;
@@ -23,7 +26,7 @@ use more_asserts as ma;
 use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
 use tokio::pin;
-use tokio::sync::{mpsc, watch, RwLock};
+use tokio::sync::{mpsc, oneshot, watch, RwLock};
 use tracing::{debug, error, warn};
 
 pub type SrcTableId = u32;
@@ -44,7 +47,21 @@ pub enum RestCommand {
     },
     SetAvroSchema {
         src_table_name: String,
+        /// Schema id in confluent wire format, if the schema comes from a schema registry.
+        schema_id: Option<u32>,
         avro_schema: AvroSchema,
+        /// Used to notify whether the avro schema is valid and has been applied.
+        result_tx: oneshot::Sender<Result<()>>,
+    },
+    AlterTable {
+        src_table_name: String,
+        /// Nullable columns to append at the end of the table.
+        columns_to_add: Vec<FieldRef>,
+        columns_to_drop: Vec<String>,
+        /// Columns to rename, in the format of (old name, new name).
+        columns_to_rename: Vec<(String, String)>,
+        /// Used to notify whether the schema change is valid and has been applied.
+        result_tx: oneshot::Sender<Result<()>>,
     },
     DropTable {
         src_table_name: String,
@@ -129,15 +146,19 @@ impl RestApiConnection {
         Ok(())
     }
 
-    /// Set Avro schema for an existing table
+    /// Set Avro schema for an existing table, and block wait until the schema gets applied to REST source.
     pub async fn set_avro_schema(
         &self,
         src_table_name: String,
+        schema_id: Option<u32>,
         avro_schema: AvroSchema,
     ) -> Result<()> {
+        let (result_tx, result_rx) = oneshot::channel();
         let command = RestCommand::SetAvroSchema {
             src_table_name,
+            schema_id,
             avro_schema,
+            result_tx,
         };
 
         self.cmd_tx.send(command).await.map_err(|e| {
@@ -147,7 +168,44 @@ impl RestApiConnection {
             )
         })?;
 
-        Ok(())
+        result_rx.await.map_err(|e| {
+            crate::Error::rest_api(
+                format!("Failed to receive set Avro schema result: {e}"),
+                Some(Arc::new(e.into())),
+            )
+        })?
+    }
+
+    /// Alter schema for an existing table, and block wait until the table accepts the schema change and it gets applied to REST source.
+    pub async fn alter_table(
+        &self,
+        src_table_name: String,
+        columns_to_add: Vec<FieldRef>,
+        columns_to_drop: Vec<String>,
+        columns_to_rename: Vec<(String, String)>,
+    ) -> Result<()> {
+        let (result_tx, result_rx) = oneshot::channel();
+        let command = RestCommand::AlterTable {
+            src_table_name,
+            columns_to_add,
+            columns_to_drop,
+            columns_to_rename,
+            result_tx,
+        };
+
+        self.cmd_tx.send(command).await.map_err(|e| {
+            crate::Error::rest_api(
+                format!("Failed to send alter table command: {e}"),
+                Some(Arc::new(e.into())),
+            )
+        })?;
+
+        result_rx.await.map_err(|e| {
+            crate::Error::rest_api(
+                format!("Failed to receive alter table result: {e}"),
+                Some(Arc::new(e.into())),
+            )
+        })?
     }
 
     /// Drop a table from the REST source and sink (sends command to event loop)
@@ -189,10 +247,13 @@ impl RestApiConnection {
 
     /// Spawn REST API event loop task (following PostgreSQL's spawn_replication_task pattern)
     pub async fn spawn_rest_task(&mut self) -> tokio::task::JoinHandle<Result<()>> {
-        let sink = RestSink::new(self.replication_state.clone());
+        let sink = RestSink::new();
+        let replication_state = self.replication_state.clone();
         let (cmd_rx, rest_request_rx) = self.start_replication();
 
-        tokio::spawn(async move { run_rest_event_loop(sink, cmd_rx, rest_request_rx).await })
+        tokio::spawn(async move {
+            run_rest_event_loop(sink, replication_state, cmd_rx, rest_request_rx).await
+        })
     }
 }
 
@@ -200,6 +261,7 @@ impl RestApiConnection {
 #[tracing::instrument(name = "rest_event_loop", skip_all)]
 pub async fn run_rest_event_loop(
     mut sink: RestSink,
+    replication_state: Arc<ReplicationState>,
     mut cmd_rx: mpsc::Receiver<RestCommand>,
     rest_request_rx: mpsc::Receiver<EventRequest>,
 ) -> Result<()> {
@@ -223,6 +285,7 @@ pub async fn run_rest_event_loop(
                         _flush_lsn_rx: flush_lsn_rx,
                         event_sender,
                         commit_lsn_tx,
+                        replication_state: replication_state.clone(),
                     };
                     if let Err(e) = sink.add_table(src_table_id, table_status, persist_lsn) {
                         error!("Add table {src_table_name} with id {src_table_id} to sink failed: {e}");
@@ -236,13 +299,48 @@ pub async fn run_rest_event_loop(
                         continue;
                     }
                 }
-                RestCommand::SetAvroSchema { src_table_name, avro_schema } => {
+                RestCommand::SetAvroSchema { src_table_name, schema_id, avro_schema, result_tx } => {
                     debug!("Setting Avro schema for table '{}'", src_table_name);
+
+                    // Same as alter table, schema is validated first, and only set after the table accepts appended columns, so both are altered or neither is.
                     let mut source = rest_source.write().await;
-                    if let Err(e) = source.set_avro_schema(src_table_name.clone(), avro_schema) {
-                        error!("Set avro schema failed for {src_table_name} failed: {e}");
-                        continue;
+                    let result = match source.get_avro_schema_columns_to_add(&src_table_name, schema_id, &avro_schema) {
+                        // Alter table before any rows decoded with the new schema get ingested.
+                        Ok(columns_to_add) if !columns_to_add.is_empty() => {
+                            let src_table_id = source.get_src_table_id(&src_table_name).unwrap();
+                            sink.alter_table(src_table_id, /*columns_to_drop=*/ vec![], columns_to_add, /*columns_to_rename=*/ vec![], /*columns_to_promote=*/ vec![]).await
+                        }
+                        Ok(_) => Ok(()),
+                        Err(e) => Err(e),
+                    };
+                    let result = result.and_then(|()| source.set_avro_schema(src_table_name.clone(), schema_id, avro_schema).map(|_| ()));
+                    if let Err(e) = &result {
+                        error!("Set avro schema for {src_table_name} failed: {e}");
+                    }
+                    // Requester could be gone, so no guarantee send success.
+                    let _ = result_tx.send(result);
+                }
+                RestCommand::AlterTable { src_table_name, columns_to_add, columns_to_drop, columns_to_rename, result_tx } => {
+                    debug!("Altering schema for table '{}'", src_table_name);
+
+                    // Swap table schema under write lock, so each request decodes all its rows with either the old schema or the new one.
+                    // Source schema is validated first, and only swapped after the table accepts the change, so both are altered or neither is.
+                    let mut source = rest_source.write().await;
+                    let mut result = source
+                        .get_altered_schema(&src_table_name, &columns_to_add, &columns_to_drop, &columns_to_rename)
+                        .map(|_| ());
+                    if result.is_ok() {
+                        let src_table_id = source.get_src_table_id(&src_table_name).unwrap();
+                        result = sink.alter_table(src_table_id, columns_to_drop.clone(), columns_to_add.clone(), columns_to_rename.clone(), /*columns_to_promote=*/ vec![]).await;
+                    }
+                    if result.is_ok() {
+                        result = source.alter_table(&src_table_name, &columns_to_add, &columns_to_drop, &columns_to_rename);
+                    }
+                    if let Err(e) = &result {
+                        error!("Alter table {src_table_name} failed: {e}");
                     }
+                    // Requester could be gone, so no guarantee send success.
+                    let _ = result_tx.send(result);
                 }
                 RestCommand::DropTable { src_table_name, src_table_id } => {
                     debug!("Dropping REST table '{}' with src_table_id {}", src_table_name, src_table_id);
@@ -270,28 +368,51 @@ pub async fn run_rest_event_loop(
                 match result {
                     Ok(rest_events) => {
                         let mut lsn = 0;
+                        let mut schema_change_error = None;
                         for rest_event in rest_events {
                             if let Some(rest_lsn) = rest_event.lsn() {
                                 ma::assert_gt!(rest_lsn, lsn);
                                 lsn = rest_lsn;
                             }
 
+                            // Schema inferred from payloads only takes effect at source after the table accepts it.
+                            let inferred_schema_change = match &rest_event {
+                                RestEvent::AlterTable { src_table_id, columns_to_add, columns_to_promote, .. } => {
+                                    Some((*src_table_id, columns_to_add.clone(), columns_to_promote.clone()))
+                                }
+                                _ => None,
+                            };
+
                             // Process rest events.
-                            let rest_event_proc_result = sink.process_rest_event(rest_event).await;
+                            let mut rest_event_proc_result = sink.process_rest_event(rest_event).await;
+                            if let (Ok(()), Some((src_table_id, columns_to_add, columns_to_promote))) = (&rest_event_proc_result, &inferred_schema_change) {
+                                let mut source = rest_source.write().await;
+                                rest_event_proc_result = source.apply_inferred_schema_change(*src_table_id, columns_to_add, columns_to_promote);
+                            }
                             if let Err(e) = &rest_event_proc_result {
                                 warn!(error = ?e, "failed to process REST event");
+                                // Rows of the request are decoded with the rejected schema, so none of them gets ingested.
+                                if inferred_schema_change.is_some() {
+                                    schema_change_error = Some(e.to_string());
+                                    break;
+                                }
                                 continue;
                             }
                         }
 
-                        // Send back event response if applicable.
+                        // Send back event response if applicable, dropping the response sender fails the request.
                         if let Some(tx) = request_tx {
-                            // Client connection could be cut down during request handling, so no guarantee send success.
-                            let _ = tx.send(lsn).await;
+                            match schema_change_error {
+                                Some(error) => tx.send_error(error).await,
+                                None => tx.send_lsn(lsn).await,
+                            }
                         }
                     }
                     Err(e) => {
                         warn!(error = ?e, "failed to process REST request");
+                        if let Some(tx) = request_tx {
+                            tx.send_error(e.to_string()).await;
+                        }
                     }
                 }
             },
@@ -0,0 +1,248 @@
+/// This module contains per-table avro schema registry, which resolves records written with different schema versions against the table schema.
+use crate::rest_ingest::rest_source::RestSourceError;
+use apache_avro::from_avro_datum;
+use apache_avro::schema::Schema as AvroSchema;
+use apache_avro::schema_compatibility::SchemaCompatibility;
+use apache_avro::types::Value as AvroValue;
+use arrow_schema::{FieldRef, Schema as ArrowSchema};
+use std::collections::HashMap;
+
+/// Magic byte for confluent wire format.
+const CONFLUENT_MAGIC_BYTE: u8 = 0;
+/// Size of confluent wire format header, including magic byte and 4-byte big-endian schema id.
+const CONFLUENT_HEADER_SIZE: usize = 5;
+
+type Result<T> = std::result::Result<T, RestSourceError>;
+
+/// Split confluent framed payload into schema id and avro datum.
+pub(crate) fn parse_confluent_framing(bytes: &[u8]) -> Result<(u32, &[u8])> {
+    if bytes.len() < CONFLUENT_HEADER_SIZE {
+        return Err(RestSourceError::InvalidConfluentFraming(format!(
+            "payload size {} is smaller than header size {CONFLUENT_HEADER_SIZE}",
+            bytes.len()
+        )));
+    }
+    if bytes[0] != CONFLUENT_MAGIC_BYTE {
+        return Err(RestSourceError::InvalidConfluentFraming(format!(
+            "unknown magic byte {}",
+            bytes[0]
+        )));
+    }
+    let schema_id = u32::from_be_bytes(bytes[1..CONFLUENT_HEADER_SIZE].try_into().unwrap());
+    Ok((schema_id, &bytes[CONFLUENT_HEADER_SIZE..]))
+}
+
+/// Get fields appended by the new schema, if the new schema only adds columns at the end.
+/// Appended avro fields must have default values, so records written with older schemas could be resolved.
+pub(crate) fn get_appended_fields(
+    old_arrow_schema: &ArrowSchema,
+    new_arrow_schema: &ArrowSchema,
+    new_avro_schema: &AvroSchema,
+) -> Option<Vec<FieldRef>> {
+    let old_fields = old_arrow_schema.fields();
+    let new_fields = new_arrow_schema.fields();
+    if new_fields.len() <= old_fields.len() {
+        return None;
+    }
+    let prefix_matches = old_fields.iter().zip(new_fields.iter()).all(|(old, new)| {
+        old.name() == new.name()
+            && old.data_type() == new.data_type()
+            && old.is_nullable() == new.is_nullable()
+    });
+    if !prefix_matches {
+        return None;
+    }
+    let AvroSchema::Record(record_schema) = new_avro_schema else {
+        return None;
+    };
+    if record_schema.fields[old_fields.len()..]
+        .iter()
+        .any(|field| field.default.is_none())
+    {
+        return None;
+    }
+    Some(new_fields[old_fields.len()..].to_vec())
+}
+
+/// Check whether records written with the writer schema could be read with the reader schema.
+pub(crate) fn check_writer_schema(
+    schema_id: u32,
+    writer_schema: &AvroSchema,
+    reader_schema: &AvroSchema,
+) -> Result<()> {
+    SchemaCompatibility::can_read(writer_schema, reader_schema).map_err(|e| {
+        RestSourceError::IncompatibleAvroSchema(format!(
+            "writer schema {schema_id} cannot be read with table schema: {e}"
+        ))
+    })?;
+    Ok(())
+}
+
+/// Avro schemas for one table.
+pub(crate) struct AvroSchemaRegistry {
+    /// Reader schema, which always matches the table schema.
+    reader_schema: AvroSchema,
+    /// Writer schemas keyed by schema id.
+    writer_schemas: HashMap<u32, AvroSchema>,
+}
+
+impl AvroSchemaRegistry {
+    pub(crate) fn new(reader_schema: AvroSchema) -> Self {
+        Self {
+            reader_schema,
+            writer_schemas: HashMap::new(),
+        }
+    }
+
+    /// Update reader schema, only called after the table schema gets altered.
+    pub(crate) fn set_reader_schema(&mut self, reader_schema: AvroSchema) {
+        self.reader_schema = reader_schema;
+    }
+
+    /// Get reader schema, which matches the table schema.
+    pub(crate) fn get_reader_schema(&self) -> &AvroSchema {
+        &self.reader_schema
+    }
+
+    /// Register a writer schema, which should be readable with the current reader schema.
+    pub(crate) fn register_writer_schema(
+        &mut self,
+        schema_id: u32,
+        writer_schema: AvroSchema,
+    ) -> Result<()> {
+        check_writer_schema(schema_id, &writer_schema, &self.reader_schema)?;
+        self.writer_schemas.insert(schema_id, writer_schema);
+        Ok(())
+    }
+
+    /// Decode a single avro datum, which is written with the reader schema.
+    pub(crate) fn decode_datum(&self, bytes: &[u8]) -> Result<AvroValue> {
+        let mut cursor = std::io::Cursor::new(bytes);
+        from_avro_datum(&self.reader_schema, &mut cursor, None)
+            .map_err(|e| RestSourceError::AvroError(Box::new(e)))
+    }
+
+    /// Decode a confluent framed avro datum, and resolve it against the reader schema.
+    pub(crate) fn decode_confluent_datum(&self, bytes: &[u8]) -> Result<AvroValue> {
+        let (schema_id, datum) = parse_confluent_framing(bytes)?;
+        let writer_schema = self
+            .writer_schemas
+            .get(&schema_id)
+            .ok_or(RestSourceError::UnknownSchemaId(schema_id))?;
+        let mut cursor = std::io::Cursor::new(datum);
+        from_avro_datum(writer_schema, &mut cursor, Some(&self.reader_schema))
+            .map_err(|e| RestSourceError::AvroError(Box::new(e)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rest_ingest::avro_converter::convert_avro_to_arrow_schema;
+    use apache_avro::to_avro_datum;
+
+    fn create_avro_schema(with_email: bool) -> AvroSchema {
+        let email_field = if with_email {
+            r#", {"name": "email", "type": ["null", "string"], "default": null}"#
+        } else {
+            ""
+        };
+        AvroSchema::parse_str(&format!(
+            r#"{{
+                "type": "record",
+                "name": "User",
+                "fields": [
+                    {{"name": "id", "type": "int"}},
+                    {{"name": "name", "type": "string"}}{email_field}
+                ]
+            }}"#
+        ))
+        .unwrap()
+    }
+
+    fn frame(schema_id: u32, datum: Vec<u8>) -> Vec<u8> {
+        let mut bytes = vec![CONFLUENT_MAGIC_BYTE];
+        bytes.extend_from_slice(&schema_id.to_be_bytes());
+        bytes.extend(datum);
+        bytes
+    }
+
+    #[test]
+    fn test_parse_confluent_framing() {
+        let (schema_id, datum) = parse_confluent_framing(&[0, 0, 0, 1, 2, 42]).unwrap();
+        assert_eq!(schema_id, 258);
+        assert_eq!(datum, &[42]);
+        assert!(parse_confluent_framing(&[0, 0, 0]).is_err());
+        assert!(parse_confluent_framing(&[1, 0, 0, 0, 1]).is_err());
+    }
+
+    #[test]
+    fn test_get_appended_fields() {
+        let old_avro_schema = create_avro_schema(/*with_email=*/ false);
+        let new_avro_schema = create_avro_schema(/*with_email=*/ true);
+        let old_arrow_schema = convert_avro_to_arrow_schema(&old_avro_schema).unwrap();
+        let new_arrow_schema = convert_avro_to_arrow_schema(&new_avro_schema).unwrap();
+
+        let appended_fields =
+            get_appended_fields(&old_arrow_schema, &new_arrow_schema, &new_avro_schema).unwrap();
+        assert_eq!(appended_fields.len(), 1);
+        assert_eq!(appended_fields[0].name(), "email");
+
+        // Removing columns is not an addition.
+        assert!(
+            get_appended_fields(&new_arrow_schema, &old_arrow_schema, &old_avro_schema).is_none()
+        );
+    }
+
+    #[test]
+    fn test_resolve_records_with_writer_schemas() {
+        let old_avro_schema = create_avro_schema(/*with_email=*/ false);
+        let new_avro_schema = create_avro_schema(/*with_email=*/ true);
+        let mut registry = AvroSchemaRegistry::new(new_avro_schema.clone());
+        registry
+            .register_writer_schema(/*schema_id=*/ 1, old_avro_schema.clone())
+            .unwrap();
+        registry
+            .register_writer_schema(/*schema_id=*/ 2, new_avro_schema.clone())
+            .unwrap();
+
+        // Record written with older schema gets default value for the new column.
+        let record = AvroValue::Record(vec![
+            ("id".to_string(), AvroValue::Int(1)),
+            ("name".to_string(), AvroValue::String("Alice".to_string())),
+        ]);
+        let datum = to_avro_datum(&old_avro_schema, record).unwrap();
+        let value = registry.decode_confluent_datum(&frame(1, datum)).unwrap();
+        let AvroValue::Record(fields) = value else {
+            panic!("expected record, got {value:?}");
+        };
+        assert_eq!(fields.len(), 3);
+        assert_eq!(
+            fields[2],
+            (
+                "email".to_string(),
+                AvroValue::Union(0, Box::new(AvroValue::Null))
+            )
+        );
+
+        // Unknown schema id.
+        let datum = to_avro_datum(
+            &old_avro_schema,
+            AvroValue::Record(vec![
+                ("id".to_string(), AvroValue::Int(2)),
+                ("name".to_string(), AvroValue::String("Bob".to_string())),
+            ]),
+        )
+        .unwrap();
+        assert!(registry.decode_confluent_datum(&frame(3, datum)).is_err());
+
+        // Writer schema which cannot be read by reader schema.
+        let incompatible_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "User", "fields": [{"name": "id", "type": "string"}]}"#,
+        )
+        .unwrap();
+        assert!(registry
+            .register_writer_schema(/*schema_id=*/ 3, incompatible_schema)
+            .is_err());
+    }
+}
@@ -2,6 +2,10 @@ use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime, TimeZone, Utc};
 use chrono_tz;
 use moonlink::row::RowValue;
 
+/// Arrow field metadata key for the timezone to interpret timestamps without offset at ingestion.
+/// Timezone-aware timestamps are always stored in UTC, since iceberg only accepts UTC timestamptz.
+pub const INPUT_TIMEZONE_METADATA_KEY: &str = "moonlink.input_timezone";
+
 /// Parse a date string in YYYY-MM-DD format to Date32 (days since epoch)
 pub fn parse_date(date_str: &str) -> Result<RowValue, String> {
     const ARROW_EPOCH: NaiveDate = match NaiveDate::from_ymd_opt(1970, 1, 1) {
@@ -18,7 +18,10 @@ pub enum RowEventOperation {
 pub enum IngestRequestPayload {
     Json(serde_json::Value),
     Protobuf(Vec<u8>),
+    /// Avro datum written with the table's reader schema.
     Avro(Vec<u8>),
+    /// Avro datum in confluent wire format, with magic byte and schema id as header.
+    ConfluentAvro(Vec<u8>),
 }
 
 #[derive(Debug, Clone)]
@@ -32,6 +35,26 @@ pub struct RowEventRequest {
     pub tx: Option<mpsc::Sender<u64>>,
 }
 
+/// ======================
+/// Batch row event request
+/// ======================
+///
+#[derive(Debug, Clone)]
+pub struct BatchRowOperation {
+    pub operation: RowEventOperation,
+    pub payload: IngestRequestPayload,
+}
+
+#[derive(Debug, Clone)]
+pub struct BatchRowEventRequest {
+    pub src_table_name: String,
+    /// Row operations, which are applied in order and committed atomically.
+    pub rows: Vec<BatchRowOperation>,
+    pub timestamp: SystemTime,
+    /// An optional channel for commit LSN, or error message if the batch is rejected, used to synchronize request completion.
+    pub tx: Option<mpsc::Sender<std::result::Result<u64, String>>>,
+}
+
 /// ======================
 /// File event request
 /// ======================
@@ -87,6 +110,40 @@ pub struct FlushRequest {
     pub tx: mpsc::Sender<u64>,
 }
 
+/// ======================
+/// Request completion
+/// ======================
+///
+#[derive(Debug, Clone)]
+pub enum RequestTx {
+    /// Commit LSN is sent on success, and the channel is dropped on failure.
+    Lsn(mpsc::Sender<u64>),
+    /// Commit LSN is sent on success, and error message on failure.
+    LsnOrError(mpsc::Sender<std::result::Result<u64, String>>),
+}
+
+impl RequestTx {
+    /// Notify request completion with commit LSN.
+    pub async fn send_lsn(&self, lsn: u64) {
+        // Client connection could be cut down during request handling, so no guarantee send success.
+        match self {
+            RequestTx::Lsn(tx) => {
+                let _ = tx.send(lsn).await;
+            }
+            RequestTx::LsnOrError(tx) => {
+                let _ = tx.send(Ok(lsn)).await;
+            }
+        }
+    }
+
+    /// Notify request failure, which is only sent if the channel accepts error message.
+    pub async fn send_error(&self, message: String) {
+        if let RequestTx::LsnOrError(tx) = self {
+            let _ = tx.send(Err(message)).await;
+        }
+    }
+}
+
 /// ======================
 /// Event request
 /// ======================
@@ -94,6 +151,7 @@ pub struct FlushRequest {
 #[derive(Debug, Clone)]
 pub enum EventRequest {
     RowRequest(RowEventRequest),
+    BatchRowRequest(BatchRowEventRequest),
     FileRequest(FileEventRequest),
     SnapshotRequest(SnapshotRequest),
     FlushRequest(FlushRequest),
@@ -101,12 +159,13 @@ pub enum EventRequest {
 
 impl EventRequest {
     /// Get event compleion receiver.
-    pub fn get_request_tx(&self) -> Option<mpsc::Sender<u64>> {
+    pub fn get_request_tx(&self) -> Option<RequestTx> {
         match &self {
-            EventRequest::RowRequest(req) => req.tx.clone(),
-            EventRequest::FileRequest(req) => req.tx.clone(),
-            EventRequest::SnapshotRequest(req) => Some(req.tx.clone()),
-            EventRequest::FlushRequest(req) => Some(req.tx.clone()),
+            EventRequest::RowRequest(req) => req.tx.clone().map(RequestTx::Lsn),
+            EventRequest::BatchRowRequest(req) => req.tx.clone().map(RequestTx::LsnOrError),
+            EventRequest::FileRequest(req) => req.tx.clone().map(RequestTx::Lsn),
+            EventRequest::SnapshotRequest(req) => Some(RequestTx::Lsn(req.tx.clone())),
+            EventRequest::FlushRequest(req) => Some(RequestTx::Lsn(req.tx.clone())),
         }
     }
 }
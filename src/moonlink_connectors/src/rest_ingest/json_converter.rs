@@ -1,6 +1,11 @@
-use crate::rest_ingest::datetime_utils::{parse_date, parse_time, parse_timestamp_with_timezone};
+use crate::rest_ingest::datetime_utils::{
+    parse_date, parse_time, parse_timestamp_with_timezone, INPUT_TIMEZONE_METADATA_KEY,
+};
 use crate::rest_ingest::decimal_utils::convert_decimal_to_row_value;
+use arrow_schema::extension::{ExtensionType, Json as ArrowJson, EXTENSION_TYPE_NAME_KEY};
 use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine;
 use moonlink::row::{MoonlinkRow, RowValue};
 use serde_json::Value;
 use std::sync::Arc;
@@ -44,7 +49,10 @@ impl JsonToMoonlinkRowConverter {
         Ok(MoonlinkRow::new(values))
     }
 
-    fn convert_value(field: &Field, value: &Value) -> Result<RowValue, JsonToMoonlinkRowError> {
+    pub(crate) fn convert_value(
+        field: &Field,
+        value: &Value,
+    ) -> Result<RowValue, JsonToMoonlinkRowError> {
         match field.data_type() {
             DataType::Boolean => {
                 if let Some(b) = value.as_bool() {
@@ -55,6 +63,18 @@ impl JsonToMoonlinkRowConverter {
                     Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
                 }
             }
+            DataType::Int16 => {
+                if let Some(i) = value.as_i64() {
+                    // Int16 values are carried as Int32 in moonlink rows.
+                    i16::try_from(i)
+                        .map(|i| RowValue::Int32(i as i32))
+                        .map_err(|_| JsonToMoonlinkRowError::InvalidValue(field.name().clone()))
+                } else if value.is_null() && field.is_nullable() {
+                    Ok(RowValue::Null)
+                } else {
+                    Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
+                }
+            }
             DataType::Int32 => {
                 if let Some(i) = value.as_i64() {
                     Ok(RowValue::Int32(i as i32))
@@ -113,7 +133,13 @@ impl JsonToMoonlinkRowConverter {
             }
             DataType::Timestamp(TimeUnit::Microsecond, tz) => {
                 if let Some(s) = value.as_str() {
-                    parse_timestamp_with_timezone(s, tz.as_deref())
+                    // Input timezone takes precedence over the stored one, which is always UTC for tables created via REST API.
+                    let input_tz = field
+                        .metadata()
+                        .get(INPUT_TIMEZONE_METADATA_KEY)
+                        .map(String::as_str)
+                        .or(tz.as_deref());
+                    parse_timestamp_with_timezone(s, input_tz)
                         .map_err(|_| JsonToMoonlinkRowError::InvalidValue(field.name().clone()))
                 } else if value.is_null() && field.is_nullable() {
                     Ok(RowValue::Null)
@@ -121,6 +147,14 @@ impl JsonToMoonlinkRowConverter {
                     Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
                 }
             }
+            // JSON columns take any JSON value, which is stored as its serialized text.
+            DataType::Utf8 if Self::is_json_field(field) => {
+                if value.is_null() && field.is_nullable() {
+                    Ok(RowValue::Null)
+                } else {
+                    Ok(RowValue::ByteArray(value.to_string().into_bytes()))
+                }
+            }
             DataType::Utf8 => {
                 if let Some(s) = value.as_str() {
                     Ok(RowValue::ByteArray(s.as_bytes().to_vec()))
@@ -130,6 +164,41 @@ impl JsonToMoonlinkRowConverter {
                     Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
                 }
             }
+            // Binary values are base64 encoded strings.
+            DataType::Binary => {
+                if let Some(s) = value.as_str() {
+                    BASE64_STANDARD
+                        .decode(s)
+                        .map(RowValue::ByteArray)
+                        .map_err(|e| {
+                            JsonToMoonlinkRowError::InvalidValueWithCause(
+                                field.name().clone(),
+                                Box::new(e),
+                            )
+                        })
+                } else if value.is_null() && field.is_nullable() {
+                    Ok(RowValue::Null)
+                } else {
+                    Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
+                }
+            }
+            // UUID values are canonical hyphenated strings.
+            DataType::FixedSizeBinary(16) => {
+                if let Some(s) = value.as_str() {
+                    uuid::Uuid::parse_str(s)
+                        .map(|uuid| RowValue::FixedLenByteArray(*uuid.as_bytes()))
+                        .map_err(|e| {
+                            JsonToMoonlinkRowError::InvalidValueWithCause(
+                                field.name().clone(),
+                                Box::new(e),
+                            )
+                        })
+                } else if value.is_null() && field.is_nullable() {
+                    Ok(RowValue::Null)
+                } else {
+                    Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
+                }
+            }
             DataType::Decimal128(precision, scale) => {
                 if let Some(s) = value.as_str() {
                     convert_decimal_to_row_value(s, *precision, *scale).map_err(|e| {
@@ -177,6 +246,54 @@ impl JsonToMoonlinkRowConverter {
                         converted_elements.push(converted_element);
                     }
                     Ok(RowValue::Array(converted_elements))
+                } else if value.is_null() && field.is_nullable() {
+                    Ok(RowValue::Null)
+                } else {
+                    Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
+                }
+            }
+            // Map values are JSON objects, keys are parsed from object keys based on key type.
+            DataType::Map(entries_field, _) => {
+                if let Some(obj) = value.as_object() {
+                    let DataType::Struct(entry_fields) = entries_field.data_type() else {
+                        return Err(JsonToMoonlinkRowError::UnsupportedDataType(
+                            entries_field.data_type().to_string(),
+                            field.name().clone(),
+                        ));
+                    };
+                    let (key_field, value_field) = (&entry_fields[0], &entry_fields[1]);
+                    let mut entries = Vec::with_capacity(obj.len());
+                    for (key, val) in obj.iter() {
+                        let key = match key_field.data_type() {
+                            DataType::Utf8 => RowValue::ByteArray(key.as_bytes().to_vec()),
+                            _ => {
+                                // Non-string keys are JSON literals, or plain strings for temporal and uuid keys.
+                                let key_value = serde_json::from_str::<Value>(key)
+                                    .unwrap_or_else(|_| Value::String(key.clone()));
+                                Self::convert_value(key_field, &key_value).map_err(|_| {
+                                    JsonToMoonlinkRowError::InvalidValue(format!(
+                                        "{}.{}",
+                                        field.name(),
+                                        key_field.name()
+                                    ))
+                                })?
+                            }
+                        };
+                        let val = Self::convert_value(value_field, val).map_err(|e| match e {
+                            JsonToMoonlinkRowError::TypeMismatch(existing_path) => {
+                                JsonToMoonlinkRowError::TypeMismatch(format!(
+                                    "{}.{}",
+                                    field.name(),
+                                    existing_path
+                                ))
+                            }
+                            other => other,
+                        })?;
+                        entries.push(RowValue::Struct(vec![key, val]));
+                    }
+                    Ok(RowValue::Array(entries))
+                } else if value.is_null() && field.is_nullable() {
+                    Ok(RowValue::Null)
                 } else {
                     Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone()))
                 }
@@ -226,6 +343,14 @@ impl JsonToMoonlinkRowConverter {
             _ => Err(JsonToMoonlinkRowError::TypeMismatch(field.name().clone())),
         }
     }
+
+    /// Return whether the given field is annotated with arrow JSON extension type.
+    fn is_json_field(field: &Field) -> bool {
+        field
+            .metadata()
+            .get(EXTENSION_TYPE_NAME_KEY)
+            .is_some_and(|name| name == ArrowJson::NAME)
+    }
 }
 
 #[cfg(test)]
@@ -1431,4 +1556,85 @@ mod tests {
             _ => panic!("unexpected error"),
         }
     }
+
+    #[test]
+    fn test_schema_builder_extended_types() {
+        let fields = vec![
+            FieldSchema::new_primitive("small", "int16", true),
+            FieldSchema::new_primitive("ts", "timestamptz(America/New_York)", true),
+            FieldSchema::new_primitive("t", "time", true),
+            FieldSchema::new_primitive("id", "uuid", false),
+            FieldSchema::new_primitive("payload", "bytes", true),
+            FieldSchema::new_primitive("doc", "json", true),
+            FieldSchema::new_primitive("counts", "map<string, int64>", true),
+            FieldSchema::new_primitive("by_day", "map<int32, string>", true),
+        ];
+        let schema = Arc::new(build_arrow_schema_impl(&fields).unwrap());
+        let converter = JsonToMoonlinkRowConverter::new(schema);
+        let input = json!({
+            "small": -3,
+            "ts": "2024-01-01T00:00:00",
+            "t": "12:00:00",
+            "id": "67e55044-10b1-426f-9247-bb680e5fe0c8",
+            "payload": "aGVsbG8=",
+            "doc": {"a": [1, 2], "b": null},
+            "counts": {"x": 1},
+            "by_day": {"7": "sunday"},
+        });
+        let row = converter.convert(&input).unwrap();
+        assert_eq!(row.values[0], RowValue::Int32(-3));
+        // Timestamps without offset are interpreted in column timezone.
+        let expected_ts = Utc
+            .with_ymd_and_hms(2024, 1, 1, 5, 0, 0)
+            .unwrap()
+            .timestamp_micros();
+        assert_eq!(row.values[1], RowValue::Int64(expected_ts));
+        assert_eq!(row.values[2], RowValue::Int64(12 * 3600 * 1_000_000));
+        assert_eq!(
+            row.values[3],
+            RowValue::FixedLenByteArray(
+                *uuid::Uuid::parse_str("67e55044-10b1-426f-9247-bb680e5fe0c8")
+                    .unwrap()
+                    .as_bytes()
+            )
+        );
+        assert_eq!(row.values[4], RowValue::ByteArray(b"hello".to_vec()));
+        assert_eq!(
+            row.values[5],
+            RowValue::ByteArray(br#"{"a":[1,2],"b":null}"#.to_vec())
+        );
+        assert_eq!(
+            row.values[6],
+            RowValue::Array(vec![RowValue::Struct(vec![
+                RowValue::ByteArray(b"x".to_vec()),
+                RowValue::Int64(1),
+            ])])
+        );
+        assert_eq!(
+            row.values[7],
+            RowValue::Array(vec![RowValue::Struct(vec![
+                RowValue::Int32(7),
+                RowValue::ByteArray(b"sunday".to_vec()),
+            ])])
+        );
+
+        // Invalid values.
+        for (field, value) in [
+            ("small", json!(40000)),
+            ("id", json!("not-a-uuid")),
+            ("payload", json!("%%%")),
+            ("by_day", json!({"monday": "x"})),
+        ] {
+            let mut invalid_input = input.clone();
+            invalid_input[field] = value;
+            let err = converter.convert(&invalid_input).unwrap_err();
+            match err {
+                JsonToMoonlinkRowError::InvalidValue(f)
+                | JsonToMoonlinkRowError::InvalidValueWithCause(f, _) => {
+                    assert!(f.starts_with(field), "unexpected field {f}")
+                }
+                _ => panic!("unexpected error: {err:?}"),
+            }
+        }
+    }
 }
@@ -2,7 +2,8 @@ use crate::rest_ingest::event_request::RowEventOperation;
 use crate::rest_ingest::rest_event::RestEvent;
 use crate::rest_ingest::rest_source::SrcTableId;
 use crate::{Error, Result};
-use moonlink::{CommitState, ReplicationState};
+use arrow_schema::{DataType, FieldRef};
+use moonlink::{AlterTableValidationSender, CommitState, ReplicationState};
 use moonlink::{StorageConfig, TableEvent};
 use std::collections::HashMap;
 use std::sync::Arc;
@@ -16,21 +17,27 @@ pub struct TableStatus {
     pub(crate) _flush_lsn_rx: watch::Receiver<u64>,
     pub(crate) event_sender: mpsc::Sender<TableEvent>,
     pub(crate) commit_lsn_tx: Arc<CommitState>,
+    /// Replication state, shared by all tables of a REST connection, while each kafka topic has its own since offsets of different topics are unrelated.
+    pub(crate) replication_state: Arc<ReplicationState>,
 }
 
 /// REST-specific sink for handling REST API table events
 pub struct RestSink {
     table_status: HashMap<SrcTableId, TableStatus>,
     tables_in_progress: Option<SrcTableId>,
-    replication_state: Arc<ReplicationState>,
+}
+
+impl Default for RestSink {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl RestSink {
-    pub fn new(replication_state: Arc<ReplicationState>) -> Self {
+    pub fn new() -> Self {
         Self {
             table_status: HashMap::new(),
             tables_in_progress: None,
-            replication_state,
         }
     }
 
@@ -45,9 +52,10 @@ impl RestSink {
         table_status: TableStatus,
         persist_lsn: Option<u64>,
     ) -> Result<()> {
-        // Update per-table commit LSN.
+        // Update per-table commit LSN and replication LSN.
         if let Some(persist_lsn) = persist_lsn {
             table_status.commit_lsn_tx.mark(persist_lsn);
+            table_status.replication_state.mark(persist_lsn);
         }
 
         if self
@@ -58,11 +66,6 @@ impl RestSink {
             return Err(Error::rest_duplicate_table(src_table_id));
         }
 
-        // Update per-database replication LSN.
-        if let Some(persist_lsn) = persist_lsn {
-            self.replication_state.mark(persist_lsn);
-        }
-
         Ok(())
     }
 
@@ -74,21 +77,62 @@ impl RestSink {
         Ok(())
     }
 
+    /// Alter schema for the given table, used when a compatible schema version gets registered, the table schema is altered by users, or evolves with ingested payloads.
+    /// Return after the table validates the request against its schema, so the source only takes the new schema if the table accepts it.
+    pub async fn alter_table(
+        &self,
+        src_table_id: SrcTableId,
+        columns_to_drop: Vec<String>,
+        columns_to_add: Vec<FieldRef>,
+        columns_to_rename: Vec<(String, String)>,
+        columns_to_promote: Vec<(String, DataType)>,
+    ) -> Result<()> {
+        debug!(
+            src_table_id,
+            ?columns_to_drop,
+            ?columns_to_add,
+            ?columns_to_rename,
+            ?columns_to_promote,
+            "altering REST table"
+        );
+        let (validation_result_tx, validation_result_rx) = AlterTableValidationSender::new();
+        self.send_table_event(
+            src_table_id,
+            TableEvent::AlterTable {
+                columns_to_drop,
+                columns_to_add,
+                columns_to_rename,
+                columns_to_promote,
+                columns_to_relax_nullability: vec![],
+                validation_result_tx: Some(validation_result_tx),
+            },
+        )
+        .await?;
+        let validation_result = validation_result_rx.await.map_err(|_| {
+            crate::Error::rest_api(
+                format!("Table {src_table_id} dropped alter table request before validation"),
+                None,
+            )
+        })?;
+        validation_result?;
+        Ok(())
+    }
+
     /// Update commit LSN and replication LSN for the given table.
     ///
     /// Difference on commit LSN and replication LSN:
     /// - Commit LSN is used per-table
-    /// - Replication LSN is used per-database
+    /// - Replication LSN is used per-database for REST API, and per-topic for kafka
     fn mark_commit(&self, src_table_id: SrcTableId, lsn: u64) -> Result<()> {
         if let Some(table_status) = self.table_status.get(&src_table_id) {
             table_status.commit_lsn_tx.mark(lsn);
+            table_status.replication_state.mark(lsn);
         } else {
             return Err(crate::Error::rest_api(
                 format!("No table status found for src_table_id: {src_table_id}"),
                 None,
             ));
         }
-        self.replication_state.mark(lsn);
         Ok(())
     }
 
@@ -161,6 +205,30 @@ impl RestSink {
                 self.process_flush_event(src_table_id).await?;
                 Ok(())
             }
+            // ==================
+            // Schema events
+            // ==================
+            //
+            RestEvent::AlterTable {
+                src_table_id,
+                columns_to_add,
+                columns_to_promote,
+                lsn,
+            } => {
+                // Alter table requires the table at a consistent view, which is not guaranteed for tables without any commits.
+                self.process_commit_event(lsn, src_table_id, std::time::SystemTime::now())
+                    .await?;
+                self.alter_table(
+                    src_table_id,
+                    /*columns_to_drop=*/ vec![],
+                    columns_to_add,
+                    /*columns_to_rename=*/ vec![],
+                    columns_to_promote,
+                )
+                .await?;
+                self.mark_commit(src_table_id, lsn)?;
+                Ok(())
+            }
         }
     }
 
@@ -330,7 +398,7 @@ mod tests {
     async fn test_rest_sink_basic_operations() {
         let replication_state = ReplicationState::new();
         let _replication_state_rx = replication_state.subscribe();
-        let mut sink = RestSink::new(replication_state.clone());
+        let mut sink = RestSink::new();
 
         // Create channels for testing
         let (event_tx, mut event_rx) = mpsc::channel::<TableEvent>(10);
@@ -342,6 +410,7 @@ mod tests {
             _flush_lsn_rx,
             event_sender: event_tx,
             commit_lsn_tx: commit_state,
+            replication_state: replication_state.clone(),
         };
 
         // Add table to sink
@@ -426,7 +495,7 @@ mod tests {
     async fn test_rest_sink_process_rest_event() {
         let replication_state = ReplicationState::new();
         let _replication_state_rx = replication_state.subscribe();
-        let mut sink = RestSink::new(replication_state.clone());
+        let mut sink = RestSink::new();
 
         // Create channels for testing
         let (event_tx, mut event_rx) = mpsc::channel::<TableEvent>(10);
@@ -438,6 +507,7 @@ mod tests {
             _flush_lsn_rx,
             event_sender: event_tx,
             commit_lsn_tx: commit_state,
+            replication_state: replication_state.clone(),
         };
 
         let src_table_id = 1;
@@ -503,7 +573,7 @@ mod tests {
     async fn test_rest_sink_operations() {
         let replication_state = ReplicationState::new();
         let _replication_state_rx = replication_state.subscribe();
-        let mut sink = RestSink::new(replication_state.clone());
+        let mut sink = RestSink::new();
 
         // Create channels for testing
         let (event_tx_1, mut event_rx_1) = mpsc::channel::<TableEvent>(10);
@@ -515,6 +585,7 @@ mod tests {
             _flush_lsn_rx: _flush_lsn_rx_1,
             event_sender: event_tx_1,
             commit_lsn_tx: commit_state,
+            replication_state: replication_state.clone(),
         };
 
         let (event_tx_2, mut event_rx_2) = mpsc::channel::<TableEvent>(10);
@@ -526,6 +597,7 @@ mod tests {
             _flush_lsn_rx: _flush_lsn_rx_2,
             event_sender: event_tx_2,
             commit_lsn_tx: commit_state,
+            replication_state: replication_state.clone(),
         };
 
         // Add two tables
@@ -1,5 +1,6 @@
 use crate::rest_ingest::SrcTableId;
 use crate::{rest_ingest::event_request::RowEventOperation, Result};
+use arrow_schema::{DataType, FieldRef};
 use moonlink::row::MoonlinkRow;
 use moonlink::StorageConfig;
 
@@ -51,6 +52,17 @@ pub enum RestEvent {
         /// Source table id.
         src_table_id: SrcTableId,
     },
+    /// Schema change inferred from ingested payloads, which applies before rows decoded with the new schema.
+    AlterTable {
+        /// Source table id.
+        src_table_id: SrcTableId,
+        /// Nullable columns to append at the end of the table.
+        columns_to_add: Vec<FieldRef>,
+        /// Columns to promote to a wider type.
+        columns_to_promote: Vec<(String, DataType)>,
+        /// LSN to commit at before altering, so the table stays at a consistent view.
+        lsn: u64,
+    },
 }
 
 impl RestEvent {
@@ -63,6 +75,7 @@ impl RestEvent {
             RestEvent::FileUploadEvent { lsn, .. } => Some(*lsn),
             RestEvent::Snapshot { .. } => None,
             RestEvent::Flush { .. } => None,
+            RestEvent::AlterTable { lsn, .. } => Some(*lsn),
         }
     }
 }
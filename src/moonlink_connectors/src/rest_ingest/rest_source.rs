@@ -1,14 +1,20 @@
-use crate::rest_ingest::avro_converter::{AvroToMoonlinkRowConverter, AvroToMoonlinkRowError};
+use crate::rest_ingest::avro_converter::{
+    convert_avro_to_arrow_schema, AvroToArrowSchemaError, AvroToMoonlinkRowConverter,
+    AvroToMoonlinkRowError,
+};
+use crate::rest_ingest::avro_schema_registry::{
+    check_writer_schema, get_appended_fields, AvroSchemaRegistry,
+};
 use crate::rest_ingest::event_request::{
-    EventRequest, FileEventOperation, FileEventRequest, FlushRequest, IngestRequestPayload,
-    RowEventOperation, RowEventRequest, SnapshotRequest,
+    BatchRowEventRequest, EventRequest, FileEventOperation, FileEventRequest, FlushRequest,
+    IngestRequestPayload, RequestTx, RowEventOperation, RowEventRequest, SnapshotRequest,
 };
 use crate::rest_ingest::json_converter::{JsonToMoonlinkRowConverter, JsonToMoonlinkRowError};
 use crate::rest_ingest::rest_event::RestEvent;
+use crate::rest_ingest::schema_inference::{self, InferredSchemaChange, OVERFLOW_COLUMN_NAME};
 use crate::Result;
-use apache_avro::from_avro_datum;
 use apache_avro::schema::Schema as AvroSchema;
-use arrow_schema::Schema;
+use arrow_schema::{DataType, FieldRef, Schema};
 use async_stream::stream;
 use bytes::Bytes;
 use futures::stream::{FuturesUnordered, Stream, StreamExt};
@@ -36,6 +42,14 @@ pub enum RestSourceError {
     AvroConversion(#[from] AvroToMoonlinkRowError),
     #[error("avro parsing error: {0}")]
     AvroError(#[from] Box<apache_avro::Error>),
+    #[error("avro schema conversion error: {0}")]
+    AvroSchemaConversion(#[from] AvroToArrowSchemaError),
+    #[error("incompatible avro schema: {0}")]
+    IncompatibleAvroSchema(String),
+    #[error("unknown avro schema id: {0}")]
+    UnknownSchemaId(u32),
+    #[error("invalid confluent wire format: {0}")]
+    InvalidConfluentFraming(String),
     #[error("unknown table: {0}")]
     UnknownTable(String),
     #[error("invalid operation for table: {0}")]
@@ -44,6 +58,8 @@ pub enum RestSourceError {
     DuplicateTable(String),
     #[error("non-existent table to remove: {0}")]
     NonExistentTable(String),
+    #[error("invalid schema change: {0}")]
+    InvalidSchemaChange(String),
     #[error("protobuf conversion error: {0}")]
     ProtobufDecoding(#[from] prost::DecodeError),
     #[error("moonlink row conversion error: {0}")]
@@ -51,7 +67,7 @@ pub enum RestSourceError {
 }
 
 pub struct RestSource {
-    table_schemas: HashMap<String, (Arc<Schema>, Option<AvroSchema>)>,
+    table_schemas: HashMap<String, (Arc<Schema>, Option<AvroSchemaRegistry>)>,
     src_table_name_to_src_id: HashMap<String, SrcTableId>,
     lsn_generator: Arc<AtomicU64>,
 }
@@ -62,7 +78,7 @@ impl Default for RestSource {
     }
 }
 
-type RestSourceStreamResult = (Option<mpsc::Sender<u64>>, Result<Vec<RestEvent>>);
+type RestSourceStreamResult = (Option<RequestTx>, Result<Vec<RestEvent>>);
 
 impl RestSource {
     pub fn new() -> Self {
@@ -111,40 +127,229 @@ impl RestSource {
         Ok(())
     }
 
-    /// Set Avro schema for an existing table
+    /// Register an Avro schema for an existing table, and return columns to add if the schema appends new ones to the table.
+    /// Schema is validated first, so the table is left unchanged if the schema is rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * schema_id: schema id in confluent wire format, only assigned for schema registry versions.
     pub fn set_avro_schema(
         &mut self,
         src_table_name: String,
+        schema_id: Option<u32>,
         avro_schema: AvroSchema,
-    ) -> Result<()> {
-        // Find the existing table
-        debug!("setting Avro schema for table {}", src_table_name);
-        if let Some((arrow_schema, _)) = self.table_schemas.get(&src_table_name) {
-            let arrow_schema = arrow_schema.clone();
-            // TODO: schema evolution
-            // for now, we just assert they are compatible
-            // Assert that the Avro schema is compatible with the existing Arrow schema
-            let avro_derived_arrow_schema =
-                crate::rest_ingest::avro_converter::convert_avro_to_arrow_schema(&avro_schema)
-                    .expect("Failed to convert Avro schema to Arrow schema");
-
-            assert_eq!(
-                arrow_schema.as_ref(), &avro_derived_arrow_schema,
-                "Arrow schema and Avro-derived schema must be exactly equal. Schema evolution not yet supported."
-            );
+    ) -> Result<Vec<FieldRef>> {
+        debug!(
+            "setting Avro schema {:?} for table {}",
+            schema_id, src_table_name
+        );
+        let columns_to_add =
+            self.get_avro_schema_columns_to_add(&src_table_name, schema_id, &avro_schema)?;
+        let (arrow_schema, avro_schema_registry) =
+            self.table_schemas.get_mut(&src_table_name).unwrap();
 
+        // Compatible additions become the new reader schema, and alter table schema accordingly.
+        if !columns_to_add.is_empty() {
             debug!(
-                "Avro schema is compatible with existing Arrow schema for table {}",
+                "Avro schema appends {} columns to table {}",
+                columns_to_add.len(),
                 src_table_name
             );
+            *arrow_schema = Arc::new(
+                convert_avro_to_arrow_schema(&avro_schema).map_err(RestSourceError::from)?,
+            );
+            if let Some(registry) = avro_schema_registry.as_mut() {
+                registry.set_reader_schema(avro_schema.clone());
+            }
+        }
+        let avro_schema_registry = avro_schema_registry
+            .get_or_insert_with(|| AvroSchemaRegistry::new(avro_schema.clone()));
 
-            // Update the table schema with the Avro schema
-            self.table_schemas
-                .insert(src_table_name, (arrow_schema, Some(avro_schema)));
-            Ok(())
+        // Other versions are only used as writer schemas, which are resolved against the reader schema.
+        if let Some(schema_id) = schema_id {
+            avro_schema_registry.register_writer_schema(schema_id, avro_schema)?;
+        }
+        Ok(columns_to_add)
+    }
+
+    /// Validate Avro schema for an existing table and return columns it appends to the table, without altering the table.
+    pub fn get_avro_schema_columns_to_add(
+        &self,
+        src_table_name: &str,
+        schema_id: Option<u32>,
+        avro_schema: &AvroSchema,
+    ) -> Result<Vec<FieldRef>> {
+        let (arrow_schema, avro_schema_registry) = self
+            .table_schemas
+            .get(src_table_name)
+            .ok_or_else(|| RestSourceError::UnknownTable(src_table_name.to_string()))?;
+        if schema_inference::is_schema_inferred(arrow_schema) {
+            return Err(RestSourceError::InvalidOperation(format!(
+                "table {src_table_name} infers schema from JSON payloads"
+            ))
+            .into());
+        }
+        let avro_derived_arrow_schema =
+            convert_avro_to_arrow_schema(avro_schema).map_err(RestSourceError::from)?;
+
+        // Get the reader schema after the schema gets set, which writer schema is checked against.
+        let mut columns_to_add = vec![];
+        let reader_schema = if arrow_schema.as_ref() == &avro_derived_arrow_schema {
+            avro_schema_registry
+                .as_ref()
+                .map_or(avro_schema, |registry| registry.get_reader_schema())
+        } else if let Some(appended_fields) =
+            get_appended_fields(arrow_schema, &avro_derived_arrow_schema, avro_schema)
+        {
+            columns_to_add = appended_fields;
+            avro_schema
+        } else if let Some(registry) = avro_schema_registry {
+            registry.get_reader_schema()
         } else {
-            Err(RestSourceError::UnknownTable(src_table_name).into())
+            return Err(RestSourceError::IncompatibleAvroSchema(format!(
+                "Avro schema doesn't match schema for table {src_table_name}"
+            ))
+            .into());
+        };
+        if let Some(schema_id) = schema_id {
+            check_writer_schema(schema_id, avro_schema, reader_schema)?;
         }
+        Ok(columns_to_add)
+    }
+
+    /// Alter schema for an existing table, which is only allowed for tables without Avro schema.
+    pub fn alter_table(
+        &mut self,
+        src_table_name: &str,
+        columns_to_add: &[FieldRef],
+        columns_to_drop: &[String],
+        columns_to_rename: &[(String, String)],
+    ) -> Result<()> {
+        debug!(
+            src_table_name,
+            ?columns_to_add,
+            ?columns_to_drop,
+            ?columns_to_rename,
+            "altering table schema"
+        );
+        let new_schema = self.get_altered_schema(
+            src_table_name,
+            columns_to_add,
+            columns_to_drop,
+            columns_to_rename,
+        )?;
+        self.table_schemas.get_mut(src_table_name).unwrap().0 = new_schema;
+        Ok(())
+    }
+
+    /// Validate schema change for an existing table and return the new schema, without altering the table.
+    /// The new schema follows mooncake table column order: remaining columns with renames applied, then added columns at the end.
+    pub fn get_altered_schema(
+        &self,
+        src_table_name: &str,
+        columns_to_add: &[FieldRef],
+        columns_to_drop: &[String],
+        columns_to_rename: &[(String, String)],
+    ) -> Result<Arc<Schema>> {
+        let (arrow_schema, avro_schema_registry) = self
+            .table_schemas
+            .get(src_table_name)
+            .ok_or_else(|| RestSourceError::UnknownTable(src_table_name.to_string()))?;
+        if avro_schema_registry.is_some() {
+            return Err(RestSourceError::InvalidOperation(format!(
+                "table {src_table_name} has Avro schema, which evolves with schema versions"
+            ))
+            .into());
+        }
+
+        let invalid_change =
+            |msg: String| -> crate::Error { RestSourceError::InvalidSchemaChange(msg).into() };
+        if columns_to_add.is_empty() && columns_to_drop.is_empty() && columns_to_rename.is_empty() {
+            return Err(invalid_change("no columns to alter".to_string()));
+        }
+        if schema_inference::is_schema_inferred(arrow_schema)
+            && (columns_to_drop
+                .iter()
+                .any(|name| name == OVERFLOW_COLUMN_NAME)
+                || columns_to_rename
+                    .iter()
+                    .any(|(old_name, _)| old_name == OVERFLOW_COLUMN_NAME))
+        {
+            return Err(invalid_change(format!(
+                "overflow column {OVERFLOW_COLUMN_NAME} cannot be dropped or renamed"
+            )));
+        }
+        let mut column_names = arrow_schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>();
+        for column in columns_to_drop.iter() {
+            let Some(idx) = column_names.iter().position(|name| name == column) else {
+                return Err(invalid_change(format!(
+                    "column {column} to drop doesn't exist"
+                )));
+            };
+            column_names.remove(idx);
+        }
+        if column_names.is_empty() {
+            return Err(invalid_change("cannot drop all columns".to_string()));
+        }
+        for (old_name, new_name) in columns_to_rename.iter() {
+            let Some(idx) = column_names.iter().position(|name| name == old_name) else {
+                return Err(invalid_change(format!(
+                    "column {old_name} to rename doesn't exist"
+                )));
+            };
+            if column_names.contains(new_name) {
+                return Err(invalid_change(format!(
+                    "cannot rename column {old_name} to existing column {new_name}"
+                )));
+            }
+            column_names[idx] = new_name.clone();
+        }
+        for field in columns_to_add.iter() {
+            if column_names.contains(field.name()) {
+                return Err(invalid_change(format!(
+                    "column {} to add already exists",
+                    field.name()
+                )));
+            }
+            // Existing rows don't have values for new columns.
+            if !field.is_nullable() {
+                return Err(invalid_change(format!(
+                    "column {} to add must be nullable",
+                    field.name()
+                )));
+            }
+            column_names.push(field.name().clone());
+        }
+
+        let mut new_fields = vec![];
+        for field in arrow_schema.fields().iter() {
+            if columns_to_drop.contains(field.name()) {
+                continue;
+            }
+            match columns_to_rename
+                .iter()
+                .find(|(old_name, _)| old_name == field.name())
+            {
+                Some((_, new_name)) => {
+                    new_fields.push(Arc::new(field.as_ref().clone().with_name(new_name)))
+                }
+                None => new_fields.push(field.clone()),
+            }
+        }
+        new_fields.extend(columns_to_add.iter().cloned());
+        Ok(Arc::new(Schema::new_with_metadata(
+            new_fields,
+            arrow_schema.metadata().clone(),
+        )))
+    }
+
+    /// Get source table id for the given table name.
+    pub fn get_src_table_id(&self, src_table_name: &str) -> Option<SrcTableId> {
+        self.src_table_name_to_src_id.get(src_table_name).copied()
     }
 
     pub fn remove_table(&mut self, src_table_name: &str) -> Result<()> {
@@ -227,25 +432,27 @@ impl RestSource {
                                     }
 
                                     // Light operations: process inline
+                                    // Lock is released before yielding, since the event loop updates source when applying the events.
+                                    // Schema inferred from row requests is applied to the source by the event loop, after the table accepts it.
                                     EventRequest::RowRequest(row_request) => {
-                                        let source = source.read().await;
-                                        let result = source.process_row_request_sync(row_request);
+                                        let result = source.read().await.process_row_request_sync(row_request);
+                                        yield (request_tx, result);
+                                    }
+                                    EventRequest::BatchRowRequest(batch_row_request) => {
+                                        let result = source.read().await.process_batch_row_request_sync(batch_row_request);
                                         yield (request_tx, result);
                                     }
                                     EventRequest::FileRequest(file_request) => {
                                         // FileEventOperation::Upload
-                                        let source = source.read().await;
-                                        let result = source.process_file_upload_sync(file_request);
+                                        let result = source.read().await.process_file_upload_sync(file_request);
                                         yield (request_tx, result);
                                     }
                                     EventRequest::SnapshotRequest(snapshot_request) => {
-                                        let source = source.read().await;
-                                        let result = source.process_snapshot_request(&snapshot_request);
+                                        let result = source.read().await.process_snapshot_request(&snapshot_request);
                                         yield (request_tx, result);
                                     }
                                     EventRequest::FlushRequest(flush_request) => {
-                                        let source = source.read().await;
-                                        let result = source.process_flush_request(&flush_request);
+                                        let result = source.read().await.process_flush_request(&flush_request);
                                         yield (request_tx, result);
                                     }
                                 }
@@ -331,24 +538,32 @@ impl RestSource {
         Ok(rest_events)
     }
 
-    /// Synchronous row processing
-    fn process_row_request_sync(&self, request: RowEventRequest) -> Result<Vec<RestEvent>> {
+    /// Decode the given payload into moonlink row, based on the table schema.
+    ///
+    /// # Arguments
+    ///
+    /// * inferred_schema: schema evolved from payloads for schema-inferred tables, which is not applied to the source yet.
+    fn decode_row_payload(
+        &self,
+        src_table_name: &str,
+        payload: &IngestRequestPayload,
+        inferred_schema: Option<&Arc<Schema>>,
+    ) -> Result<MoonlinkRow> {
         let schema = self
             .table_schemas
-            .get(&request.src_table_name)
-            .ok_or_else(|| RestSourceError::UnknownTable(request.src_table_name.clone()))?;
-
-        let src_table_id = self
-            .src_table_name_to_src_id
-            .get(&request.src_table_name)
-            .ok_or_else(|| RestSourceError::UnknownTable(request.src_table_name.clone()))?;
+            .get(src_table_name)
+            .ok_or_else(|| RestSourceError::UnknownTable(src_table_name.to_string()))?;
 
         // Decode based on payload type
-        let row = match &request.payload {
+        let row = match payload {
             IngestRequestPayload::Json(value) => {
-                let (arrow_schema, _) = schema;
-                let converter = JsonToMoonlinkRowConverter::new(arrow_schema.clone());
-                converter.convert(value)?
+                let arrow_schema = inferred_schema.unwrap_or(&schema.0);
+                if schema_inference::is_schema_inferred(arrow_schema) {
+                    schema_inference::convert_row(arrow_schema, value)?
+                } else {
+                    let converter = JsonToMoonlinkRowConverter::new(arrow_schema.clone());
+                    converter.convert(value)?
+                }
             }
             IngestRequestPayload::Protobuf(bytes) => {
                 let p: moonlink_proto::moonlink::MoonlinkRow =
@@ -356,46 +571,215 @@ impl RestSource {
                         .map_err(RestSourceError::ProtobufDecoding)?;
                 moonlink::row::proto_to_moonlink_row(p)?
             }
-            IngestRequestPayload::Avro(bytes) => {
-                // Get the Avro schema for this table
-                let (_, avro_schema_opt) = schema;
-                let avro_schema = avro_schema_opt.as_ref().ok_or_else(|| {
+            IngestRequestPayload::Avro(bytes) | IngestRequestPayload::ConfluentAvro(bytes) => {
+                // Get the Avro schema registry for this table
+                let (_, avro_schema_registry) = schema;
+                let avro_schema_registry = avro_schema_registry.as_ref().ok_or_else(|| {
                     RestSourceError::InvalidOperation(format!(
-                        "Table {} does not have an Avro schema configured",
-                        request.src_table_name
+                        "Table {src_table_name} does not have an Avro schema configured"
                     ))
                 })?;
 
-                let avro_value = {
-                    // Decode a single datum - assumes single record per message
-                    // On the kafka side we are already stripping any magic bytes and schema headers
-                    let mut cursor = std::io::Cursor::new(bytes.as_slice());
-                    from_avro_datum(avro_schema, &mut cursor, None)
-                        .map_err(|e| RestSourceError::AvroError(Box::new(e)))?
+                // Decode a single datum - assumes single record per message
+                let avro_value = match payload {
+                    IngestRequestPayload::ConfluentAvro(_) => {
+                        avro_schema_registry.decode_confluent_datum(bytes)?
+                    }
+                    _ => avro_schema_registry.decode_datum(bytes)?,
                 };
 
                 AvroToMoonlinkRowConverter::convert(&avro_value)
                     .map_err(RestSourceError::AvroConversion)?
             }
         };
+        Ok(row)
+    }
+
+    /// Evolve schema for schema-inferred tables with the given payloads, return [`None`] if no change is needed.
+    /// The source schema is left unchanged, which is only updated via [`Self::apply_inferred_schema_change`] after the table accepts the change.
+    fn evolve_inferred_schema(
+        &self,
+        src_table_name: &str,
+        payloads: &[&IngestRequestPayload],
+    ) -> Result<Option<InferredSchemaChange>> {
+        let (arrow_schema, _) = self
+            .table_schemas
+            .get(src_table_name)
+            .ok_or_else(|| RestSourceError::UnknownTable(src_table_name.to_string()))?;
+        if !schema_inference::is_schema_inferred(arrow_schema) {
+            return Ok(None);
+        }
+
+        let mut rows = Vec::with_capacity(payloads.len());
+        for payload in payloads.iter() {
+            match payload {
+                IngestRequestPayload::Json(value) if value.is_object() => rows.push(value),
+                _ => {
+                    return Err(RestSourceError::InvalidOperation(format!(
+                        "Table {src_table_name} infers schema from JSON objects, other payloads are not supported"
+                    ))
+                    .into());
+                }
+            }
+        }
+        let schema_change = schema_inference::evolve_schema(arrow_schema, &rows);
+        if let Some(schema_change) = &schema_change {
+            debug!(
+                src_table_name,
+                columns_to_add = ?schema_change.columns_to_add,
+                columns_to_promote = ?schema_change.columns_to_promote,
+                "evolving inferred table schema"
+            );
+        }
+        Ok(schema_change)
+    }
+
+    /// Apply schema change inferred from payloads to the source, which should be called after the table has been altered.
+    pub fn apply_inferred_schema_change(
+        &mut self,
+        src_table_id: SrcTableId,
+        columns_to_add: &[FieldRef],
+        columns_to_promote: &[(String, DataType)],
+    ) -> Result<()> {
+        let src_table_name = self
+            .src_table_name_to_src_id
+            .iter()
+            .find(|(_, id)| **id == src_table_id)
+            .map(|(name, _)| name.clone())
+            .ok_or_else(|| RestSourceError::UnknownTable(src_table_id.to_string()))?;
+        let (arrow_schema, _) = self.table_schemas.get_mut(&src_table_name).unwrap();
+        let mut new_fields = arrow_schema
+            .fields()
+            .iter()
+            .map(|field| {
+                match columns_to_promote
+                    .iter()
+                    .find(|(name, _)| name == field.name())
+                {
+                    Some((_, new_type)) => {
+                        Arc::new(field.as_ref().clone().with_data_type(new_type.clone()))
+                    }
+                    None => field.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        new_fields.extend(columns_to_add.iter().cloned());
+        *arrow_schema = Arc::new(Schema::new_with_metadata(
+            new_fields,
+            arrow_schema.metadata().clone(),
+        ));
+        Ok(())
+    }
+
+    /// Get alter table event for the given inferred schema change, which is applied before ingesting rows decoded with the new schema.
+    fn get_inferred_alter_table_event(
+        &self,
+        src_table_id: SrcTableId,
+        schema_change: &InferredSchemaChange,
+    ) -> RestEvent {
+        RestEvent::AlterTable {
+            src_table_id,
+            columns_to_add: schema_change.columns_to_add.clone(),
+            columns_to_promote: schema_change.columns_to_promote.clone(),
+            lsn: self.lsn_generator.fetch_add(1, Ordering::SeqCst),
+        }
+    }
+
+    /// Synchronous row processing
+    fn process_row_request_sync(&self, request: RowEventRequest) -> Result<Vec<RestEvent>> {
+        let src_table_id = *self
+            .src_table_name_to_src_id
+            .get(&request.src_table_name)
+            .ok_or_else(|| RestSourceError::UnknownTable(request.src_table_name.clone()))?;
+        let schema_change =
+            self.evolve_inferred_schema(&request.src_table_name, &[&request.payload])?;
+        let row = self.decode_row_payload(
+            &request.src_table_name,
+            &request.payload,
+            schema_change.as_ref().map(|change| &change.new_schema),
+        )?;
+        let alter_table_event = schema_change
+            .as_ref()
+            .map(|change| self.get_inferred_alter_table_event(src_table_id, change));
 
         let row_lsn = self.lsn_generator.fetch_add(1, Ordering::SeqCst);
         let commit_lsn = self.lsn_generator.fetch_add(1, Ordering::SeqCst);
 
-        // Generate both a row event and a commit event
-        let events = vec![
-            RestEvent::RowEvent {
-                src_table_id: *src_table_id,
-                operation: request.operation,
+        // Generate both a row event and a commit event, after schema change if any.
+        let mut events = alter_table_event.into_iter().collect::<Vec<_>>();
+        events.push(RestEvent::RowEvent {
+            src_table_id,
+            operation: request.operation,
+            row,
+            lsn: row_lsn,
+            timestamp: request.timestamp,
+        });
+        events.push(RestEvent::Commit {
+            lsn: commit_lsn,
+            timestamp: request.timestamp,
+        });
+
+        Ok(events)
+    }
+
+    /// Synchronous batch row processing.
+    /// All rows are decoded before LSN assignment, so no events are generated if any of them fails conversion; rows are emitted in one transaction with a single commit.
+    fn process_batch_row_request_sync(
+        &self,
+        request: BatchRowEventRequest,
+    ) -> Result<Vec<RestEvent>> {
+        let src_table_id = *self
+            .src_table_name_to_src_id
+            .get(&request.src_table_name)
+            .ok_or_else(|| RestSourceError::UnknownTable(request.src_table_name.clone()))?;
+        if request.rows.is_empty() {
+            return Err(RestSourceError::InvalidOperation(format!(
+                "Empty batch for table {}",
+                request.src_table_name
+            ))
+            .into());
+        }
+
+        let payloads = request
+            .rows
+            .iter()
+            .map(|cur_row| &cur_row.payload)
+            .collect::<Vec<_>>();
+        let schema_change = self.evolve_inferred_schema(&request.src_table_name, &payloads)?;
+
+        let mut decoded_rows = Vec::with_capacity(request.rows.len());
+        for cur_row in request.rows.into_iter() {
+            let row = self.decode_row_payload(
+                &request.src_table_name,
+                &cur_row.payload,
+                schema_change.as_ref().map(|change| &change.new_schema),
+            )?;
+            decoded_rows.push((cur_row.operation, row));
+        }
+        let alter_table_event = schema_change
+            .as_ref()
+            .map(|change| self.get_inferred_alter_table_event(src_table_id, change));
+
+        // Allocate contiguous LSNs for all rows and the commit.
+        let row_count = decoded_rows.len() as u64;
+        let start_lsn = self
+            .lsn_generator
+            .fetch_add(row_count + 1, Ordering::SeqCst);
+        let mut events = Vec::with_capacity(decoded_rows.len() + 2);
+        events.extend(alter_table_event);
+        for (row_lsn, (operation, row)) in (start_lsn..).zip(decoded_rows.into_iter()) {
+            events.push(RestEvent::RowEvent {
+                src_table_id,
+                operation,
                 row,
                 lsn: row_lsn,
                 timestamp: request.timestamp,
-            },
-            RestEvent::Commit {
-                lsn: commit_lsn,
-                timestamp: request.timestamp,
-            },
-        ];
+            });
+        }
+        events.push(RestEvent::Commit {
+            lsn: start_lsn + row_count,
+            timestamp: request.timestamp,
+        });
 
         Ok(events)
     }
@@ -447,11 +831,32 @@ impl RestSource {
     }
 }
 
+/// Get reader schema out of Avro schemas set for a table in order, which is the first one or the latest one appending columns to it.
+/// Used at recovery, since table schema could already include columns appended by later schemas.
+pub fn get_avro_reader_schema(avro_schemas: &[AvroSchema]) -> Result<Option<AvroSchema>> {
+    let mut reader_schema: Option<(&AvroSchema, Schema)> = None;
+    for avro_schema in avro_schemas.iter() {
+        let avro_derived_arrow_schema =
+            convert_avro_to_arrow_schema(avro_schema).map_err(RestSourceError::from)?;
+        let is_reader_schema = match &reader_schema {
+            Some((_, reader_arrow_schema)) => {
+                get_appended_fields(reader_arrow_schema, &avro_derived_arrow_schema, avro_schema)
+                    .is_some()
+            }
+            None => true,
+        };
+        if is_reader_schema {
+            reader_schema = Some((avro_schema, avro_derived_arrow_schema));
+        }
+    }
+    Ok(reader_schema.map(|(avro_schema, _)| avro_schema.clone()))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::{
-        rest_ingest::event_request::{FileEventRequest, IngestRequestPayload},
+        rest_ingest::event_request::{BatchRowOperation, FileEventRequest, IngestRequestPayload},
         Error,
     };
     use arrow::record_batch::RecordBatch;
@@ -629,6 +1034,255 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_process_batch_row_request_success() {
+        let mut source = RestSource::new();
+        let schema = make_test_schema();
+        source
+            .add_table(
+                "test_table".to_string(),
+                /*src_table_id=*/ 1,
+                schema,
+                /*persist_lsn=*/ Some(0),
+            )
+            .unwrap();
+
+        let request = BatchRowEventRequest {
+            src_table_name: "test_table".to_string(),
+            rows: vec![
+                BatchRowOperation {
+                    operation: RowEventOperation::Insert,
+                    payload: IngestRequestPayload::Json(json!({"id": 1, "name": "first"})),
+                },
+                BatchRowOperation {
+                    operation: RowEventOperation::Upsert,
+                    payload: IngestRequestPayload::Json(json!({"id": 2, "name": "second"})),
+                },
+                BatchRowOperation {
+                    operation: RowEventOperation::Delete,
+                    payload: IngestRequestPayload::Json(json!({"id": 1, "name": "first"})),
+                },
+            ],
+            timestamp: SystemTime::now(),
+            tx: None,
+        };
+
+        // All rows share one commit, which comes last.
+        let events = source.process_batch_row_request_sync(request).unwrap();
+        assert_eq!(events.len(), 4);
+        let expected_operations = [
+            RowEventOperation::Insert,
+            RowEventOperation::Upsert,
+            RowEventOperation::Delete,
+        ];
+        for (idx, expected_operation) in expected_operations.iter().enumerate() {
+            match &events[idx] {
+                RestEvent::RowEvent {
+                    src_table_id,
+                    operation,
+                    lsn,
+                    ..
+                } => {
+                    assert_eq!(*src_table_id, 1);
+                    assert_eq!(operation, expected_operation);
+                    assert_eq!(*lsn, idx as u64 + 1);
+                }
+                other => panic!("expected RowEvent, got {other:?}"),
+            }
+        }
+        match &events[3] {
+            RestEvent::Commit { lsn, .. } => {
+                assert_eq!(*lsn, 4);
+            }
+            other => panic!("expected Commit event, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_row_request_with_inferred_schema() {
+        let mut source = RestSource::new();
+        source
+            .add_table(
+                "test_table".to_string(),
+                /*src_table_id=*/ 1,
+                Arc::new(schema_inference::get_initial_inferred_schema()),
+                /*persist_lsn=*/ Some(0),
+            )
+            .unwrap();
+        let make_request = |rows: Vec<serde_json::Value>| BatchRowEventRequest {
+            src_table_name: "test_table".to_string(),
+            rows: rows
+                .into_iter()
+                .map(|row| BatchRowOperation {
+                    operation: RowEventOperation::Insert,
+                    payload: IngestRequestPayload::Json(row),
+                })
+                .collect(),
+            timestamp: SystemTime::now(),
+            tx: None,
+        };
+
+        let get_column_count = |source: &RestSource| {
+            source
+                .table_schemas
+                .get("test_table")
+                .unwrap()
+                .0
+                .fields()
+                .len()
+        };
+
+        // New fields add columns before rows get ingested, while source schema is only updated after the table is altered.
+        let events = source
+            .process_batch_row_request_sync(make_request(vec![
+                json!({"id": 1}),
+                json!({"id": 2, "name": "second"}),
+            ]))
+            .unwrap();
+        assert_eq!(events.len(), 4);
+        match &events[0] {
+            RestEvent::AlterTable {
+                src_table_id,
+                columns_to_add,
+                columns_to_promote,
+                lsn,
+            } => {
+                assert_eq!(*src_table_id, 1);
+                assert_eq!(columns_to_add.len(), 2);
+                assert_eq!(columns_to_add[0].data_type(), &DataType::Int64);
+                assert!(columns_to_promote.is_empty());
+                assert_eq!(*lsn, 1);
+                assert_eq!(get_column_count(&source), 1);
+                source
+                    .apply_inferred_schema_change(*src_table_id, columns_to_add, columns_to_promote)
+                    .unwrap();
+                assert_eq!(get_column_count(&source), 3);
+            }
+            other => panic!("expected AlterTable event, got {other:?}"),
+        }
+        match &events[2] {
+            RestEvent::RowEvent { row, lsn, .. } => {
+                assert_eq!(*lsn, 3);
+                assert_eq!(
+                    row.values,
+                    vec![
+                        RowValue::Null,
+                        RowValue::Int64(2),
+                        RowValue::ByteArray("second".as_bytes().to_vec()),
+                    ]
+                );
+            }
+            other => panic!("expected RowEvent, got {other:?}"),
+        }
+
+        // Values which cannot be stored in existing columns go to the overflow column, without schema change.
+        let events = source
+            .process_batch_row_request_sync(make_request(vec![json!({"id": 1.5, "name": 1})]))
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        match &events[0] {
+            RestEvent::RowEvent { row, .. } => {
+                let RowValue::ByteArray(overflow) = &row.values[0] else {
+                    panic!("overflow column should be set");
+                };
+                assert_eq!(
+                    serde_json::from_slice::<serde_json::Value>(overflow).unwrap(),
+                    json!({"id": 1.5, "name": 1})
+                );
+                assert_eq!(&row.values[1..], &[RowValue::Null, RowValue::Null]);
+            }
+            other => panic!("expected RowEvent, got {other:?}"),
+        }
+
+        // Schema change not accepted by the table leaves source schema unchanged, so it's inferred again by later requests.
+        for _ in 0..2 {
+            let events = source
+                .process_batch_row_request_sync(make_request(vec![json!({"score": 1.5})]))
+                .unwrap();
+            assert_eq!(events.len(), 3);
+            assert!(matches!(&events[0], RestEvent::AlterTable { .. }));
+            assert_eq!(get_column_count(&source), 3);
+        }
+
+        // Known fields don't change schema; non-object payloads are rejected, and Avro schema cannot be set.
+        let events = source
+            .process_batch_row_request_sync(make_request(vec![json!({"id": 3})]))
+            .unwrap();
+        assert_eq!(events.len(), 2);
+        assert!(source
+            .process_batch_row_request_sync(make_request(vec![json!({"email": "a"}), json!(1)]))
+            .is_err());
+        assert_eq!(get_column_count(&source), 3);
+        let avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "row", "fields": [{"name": "id", "type": "long"}]}"#,
+        )
+        .unwrap();
+        assert!(source
+            .set_avro_schema(
+                "test_table".to_string(),
+                /*schema_id=*/ None,
+                avro_schema
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_process_batch_row_request_with_invalid_row() {
+        let mut source = RestSource::new();
+        let schema = make_test_schema();
+        source
+            .add_table(
+                "test_table".to_string(),
+                /*src_table_id=*/ 1,
+                schema,
+                /*persist_lsn=*/ Some(0),
+            )
+            .unwrap();
+
+        // The second row misses a required column, so the whole batch is rejected.
+        let request = BatchRowEventRequest {
+            src_table_name: "test_table".to_string(),
+            rows: vec![
+                BatchRowOperation {
+                    operation: RowEventOperation::Insert,
+                    payload: IngestRequestPayload::Json(json!({"id": 1, "name": "first"})),
+                },
+                BatchRowOperation {
+                    operation: RowEventOperation::Insert,
+                    payload: IngestRequestPayload::Json(json!({"id": 2})),
+                },
+            ],
+            timestamp: SystemTime::now(),
+            tx: None,
+        };
+        assert!(source.process_batch_row_request_sync(request).is_err());
+
+        // Empty batch is rejected as well.
+        let request = BatchRowEventRequest {
+            src_table_name: "test_table".to_string(),
+            rows: vec![],
+            timestamp: SystemTime::now(),
+            tx: None,
+        };
+        assert!(source.process_batch_row_request_sync(request).is_err());
+
+        // No LSN is consumed by rejected batches.
+        let request = RowEventRequest {
+            src_table_name: "test_table".to_string(),
+            operation: RowEventOperation::Insert,
+            payload: IngestRequestPayload::Json(json!({"id": 3, "name": "third"})),
+            timestamp: SystemTime::now(),
+            tx: None,
+        };
+        let events = source.process_row_request_sync(request).unwrap();
+        match &events[0] {
+            RestEvent::RowEvent { lsn, .. } => {
+                assert_eq!(*lsn, 1);
+            }
+            other => panic!("expected RowEvent, got {other:?}"),
+        }
+    }
+
     #[tokio::test]
     async fn test_process_file_insertion_request_success() {
         let tempdir = TempDir::new().unwrap();
@@ -799,6 +1453,251 @@ mod tests {
         assert!(res.is_err());
     }
 
+    #[test]
+    fn test_alter_table() {
+        let mut source = RestSource::new();
+        source
+            .add_table(
+                "test_table".to_string(),
+                /*src_table_id=*/ 1,
+                make_test_schema(),
+                /*persist_lsn=*/ Some(0),
+            )
+            .unwrap();
+
+        // Invalid schema changes leave table schema unchanged.
+        let email_column = Arc::new(Field::new("email", DataType::Utf8, true));
+        let invalid_changes: Vec<(Vec<FieldRef>, Vec<String>, Vec<(String, String)>)> = vec![
+            (vec![], vec![], vec![]),
+            (vec![], vec!["unknown".to_string()], vec![]),
+            (vec![], vec!["id".to_string(), "name".to_string()], vec![]),
+            (vec![], vec![], vec![("name".to_string(), "id".to_string())]),
+            (
+                vec![],
+                vec!["name".to_string()],
+                vec![("name".to_string(), "full_name".to_string())],
+            ),
+            (
+                vec![Arc::new(Field::new("email", DataType::Utf8, false))],
+                vec![],
+                vec![],
+            ),
+            (
+                vec![email_column.clone(), email_column.clone()],
+                vec![],
+                vec![],
+            ),
+        ];
+        for (columns_to_add, columns_to_drop, columns_to_rename) in invalid_changes.into_iter() {
+            let res = source.alter_table(
+                "test_table",
+                &columns_to_add,
+                &columns_to_drop,
+                &columns_to_rename,
+            );
+            assert!(matches!(res, Err(Error::RestInvalidSchemaChange(_))));
+        }
+        assert!(source
+            .alter_table("unknown_table", &[email_column.clone()], &[], &[])
+            .is_err());
+        assert_eq!(
+            source.table_schemas.get("test_table").unwrap().0,
+            make_test_schema()
+        );
+
+        // Valid schema change, rows are decoded with the new schema.
+        source
+            .alter_table(
+                "test_table",
+                &[email_column],
+                /*columns_to_drop=*/ &["id".to_string()],
+                /*columns_to_rename=*/ &[("name".to_string(), "full_name".to_string())],
+            )
+            .unwrap();
+        let schema = source.table_schemas.get("test_table").unwrap().0.clone();
+        let column_names = schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(column_names, vec!["full_name", "email"]);
+
+        let request = RowEventRequest {
+            src_table_name: "test_table".to_string(),
+            operation: RowEventOperation::Insert,
+            payload: IngestRequestPayload::Json(json!({
+                "full_name": "test",
+                "email": "test@example.com"
+            })),
+            timestamp: SystemTime::now(),
+            tx: None,
+        };
+        let events = source.process_row_request_sync(request).unwrap();
+        match &events[0] {
+            RestEvent::RowEvent { row, .. } => {
+                assert_eq!(
+                    row.values,
+                    vec![
+                        RowValue::ByteArray(b"test".to_vec()),
+                        RowValue::ByteArray(b"test@example.com".to_vec()),
+                    ]
+                );
+            }
+            _ => panic!("Expected RowEvent"),
+        }
+    }
+
+    #[test]
+    fn test_avro_schema_evolution_with_confluent_framing() {
+        let old_avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "User", "fields": [
+                {"name": "id", "type": "int"},
+                {"name": "name", "type": "string"}
+            ]}"#,
+        )
+        .unwrap();
+        let new_avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "User", "fields": [
+                {"name": "id", "type": "int"},
+                {"name": "name", "type": "string"},
+                {"name": "email", "type": ["null", "string"], "default": null}
+            ]}"#,
+        )
+        .unwrap();
+
+        let mut source = RestSource::new();
+        let schema = Arc::new(convert_avro_to_arrow_schema(&old_avro_schema).unwrap());
+        source
+            .add_table(
+                "test_table".to_string(),
+                /*src_table_id=*/ 1,
+                schema,
+                /*persist_lsn=*/ Some(0),
+            )
+            .unwrap();
+
+        // Register the initial schema version, which matches the table schema.
+        let columns_to_add = source
+            .set_avro_schema(
+                "test_table".to_string(),
+                /*schema_id=*/ Some(1),
+                old_avro_schema.clone(),
+            )
+            .unwrap();
+        assert!(columns_to_add.is_empty());
+
+        // Register a new schema version which appends a column.
+        let columns_to_add = source
+            .set_avro_schema(
+                "test_table".to_string(),
+                /*schema_id=*/ Some(2),
+                new_avro_schema,
+            )
+            .unwrap();
+        assert_eq!(columns_to_add.len(), 1);
+        assert_eq!(columns_to_add[0].name(), "email");
+
+        // Records written with the old schema are resolved against the new one.
+        let record = apache_avro::types::Value::Record(vec![
+            ("id".to_string(), apache_avro::types::Value::Int(1)),
+            (
+                "name".to_string(),
+                apache_avro::types::Value::String("Alice".to_string()),
+            ),
+        ]);
+        let mut payload = vec![0, 0, 0, 0, 1];
+        payload.extend(apache_avro::to_avro_datum(&old_avro_schema, record).unwrap());
+        let row = source
+            .decode_row_payload(
+                "test_table",
+                &IngestRequestPayload::ConfluentAvro(payload),
+                /*inferred_schema=*/ None,
+            )
+            .unwrap();
+        assert_eq!(
+            row.values,
+            vec![
+                RowValue::Int32(1),
+                RowValue::ByteArray(b"Alice".to_vec()),
+                RowValue::Null,
+            ]
+        );
+
+        // Schemas which are neither the same as nor additions to the table schema, and cannot be resolved, are rejected.
+        let incompatible_avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "User", "fields": [{"name": "id", "type": "string"}]}"#,
+        )
+        .unwrap();
+        assert!(source
+            .set_avro_schema(
+                "test_table".to_string(),
+                /*schema_id=*/ Some(3),
+                incompatible_avro_schema,
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_avro_schema_validation_and_reader_schema() {
+        let old_avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "User", "fields": [
+                {"name": "id", "type": "int"}
+            ]}"#,
+        )
+        .unwrap();
+        let new_avro_schema = AvroSchema::parse_str(
+            r#"{"type": "record", "name": "User", "fields": [
+                {"name": "id", "type": "int"},
+                {"name": "email", "type": ["null", "string"], "default": null}
+            ]}"#,
+        )
+        .unwrap();
+
+        let mut source = RestSource::new();
+        let schema = Arc::new(convert_avro_to_arrow_schema(&old_avro_schema).unwrap());
+        source
+            .add_table(
+                "test_table".to_string(),
+                /*src_table_id=*/ 1,
+                schema,
+                /*persist_lsn=*/ Some(0),
+            )
+            .unwrap();
+
+        // Validation doesn't set the schema, so validating twice gets the same columns to add.
+        for _ in 0..2 {
+            let columns_to_add = source
+                .get_avro_schema_columns_to_add("test_table", Some(2), &new_avro_schema)
+                .unwrap();
+            assert_eq!(columns_to_add.len(), 1);
+            assert_eq!(columns_to_add[0].name(), "email");
+        }
+        let payload = vec![0, 0, 0, 0, 2, 2];
+        assert!(source
+            .decode_row_payload(
+                "test_table",
+                &IngestRequestPayload::ConfluentAvro(payload),
+                /*inferred_schema=*/ None,
+            )
+            .is_err());
+
+        // Reader schema is the first schema or the latest one appending columns, no matter which order older versions are set.
+        assert!(get_avro_reader_schema(&[]).unwrap().is_none());
+        assert_eq!(
+            get_avro_reader_schema(&[
+                old_avro_schema.clone(),
+                new_avro_schema.clone(),
+                old_avro_schema.clone()
+            ])
+            .unwrap(),
+            Some(new_avro_schema.clone())
+        );
+        assert_eq!(
+            get_avro_reader_schema(&[new_avro_schema.clone(), old_avro_schema]).unwrap(),
+            Some(new_avro_schema)
+        );
+    }
+
     #[test]
     fn test_drop_non_existent_table() {
         let mut source = RestSource::new();
@@ -808,7 +1707,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_process_request_unknown_table() {
-        let source = RestSource::new();
+        let mut source = RestSource::new();
 
         let request = RowEventRequest {
             src_table_name: "unknown_table".to_string(),
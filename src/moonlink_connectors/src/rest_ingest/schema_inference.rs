@@ -0,0 +1,319 @@
+/// This module infers and evolves schema for tables ingesting schema-less JSON payloads.
+///
+/// A schema-inferred table starts with only the overflow column, which is also how such tables are identified, including after recovery.
+/// Top-level fields in payloads become nullable columns when first seen with non-null values, integers are inferred as Int64, and columns
+/// seen with both integers and floats in the same payloads are added as Float64.
+/// Existing integer columns cannot be widened to Float64, since it's not a valid iceberg type promotion; values which cannot be stored in
+/// their columns are kept in the overflow column as a JSON object.
+use crate::rest_ingest::json_converter::{JsonToMoonlinkRowConverter, JsonToMoonlinkRowError};
+use arrow_schema::extension::Json as ArrowJson;
+use arrow_schema::{DataType, Field, FieldRef, Schema};
+use moonlink::row::{MoonlinkRow, RowValue};
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Reserved column for fields which cannot be reconciled with the table schema.
+pub const OVERFLOW_COLUMN_NAME: &str = "_moonlink_overflow";
+
+/// Schema change derived from incoming payloads.
+#[derive(Debug)]
+pub(crate) struct InferredSchemaChange {
+    /// Table schema after the change.
+    pub(crate) new_schema: Arc<Schema>,
+    /// Nullable columns to append at the end of the table.
+    pub(crate) columns_to_add: Vec<FieldRef>,
+    /// Existing columns to promote to a wider type.
+    pub(crate) columns_to_promote: Vec<(String, DataType)>,
+}
+
+fn get_overflow_field() -> Field {
+    Field::new(
+        OVERFLOW_COLUMN_NAME,
+        DataType::Utf8,
+        /*nullable=*/ true,
+    )
+    .with_extension_type(ArrowJson::default())
+}
+
+/// Get the schema which a schema-inferred table is created with.
+pub fn get_initial_inferred_schema() -> Schema {
+    Schema::new(vec![get_overflow_field()])
+}
+
+/// Return whether the given table schema is inferred from payloads.
+pub fn is_schema_inferred(schema: &Schema) -> bool {
+    schema
+        .field_with_name(OVERFLOW_COLUMN_NAME)
+        .is_ok_and(|field| field.data_type() == &DataType::Utf8)
+}
+
+/// Infer column type for the given JSON value, return [`None`] for null values, or numbers which fit neither 64-bit integer nor float.
+/// Integers are always inferred as Int64, so later payloads with larger values don't need a type promotion.
+fn infer_data_type(value: &Value) -> Option<DataType> {
+    match value {
+        Value::Null => None,
+        Value::Bool(_) => Some(DataType::Boolean),
+        Value::Number(number) => {
+            if number.is_i64() {
+                Some(DataType::Int64)
+            } else if number.is_f64() {
+                Some(DataType::Float64)
+            } else {
+                None
+            }
+        }
+        Value::String(_) => Some(DataType::Utf8),
+        // Nested values are stored as JSON text.
+        Value::Array(_) | Value::Object(_) => Some(DataType::Utf8),
+    }
+}
+
+/// Get the type to widen the given column to for the value type, return [`None`] if the column type is kept.
+/// Integer columns are widened to Float64 only when they're added by the same payloads, since existing values cannot be promoted.
+fn get_widened_type(
+    column_type: &DataType,
+    value_type: &DataType,
+    is_new_column: bool,
+) -> Option<DataType> {
+    if moonlink::is_safe_type_promotion(value_type, column_type) {
+        return None;
+    }
+    if moonlink::is_safe_type_promotion(column_type, value_type) {
+        return Some(value_type.clone());
+    }
+    if is_new_column && column_type.is_integer() && value_type == &DataType::Float64 {
+        return Some(DataType::Float64);
+    }
+    None
+}
+
+fn make_inferred_field(name: &str, value: &Value, data_type: DataType) -> Field {
+    let field = Field::new(name, data_type, /*nullable=*/ true);
+    if value.is_array() || value.is_object() {
+        return field.with_extension_type(ArrowJson::default());
+    }
+    field
+}
+
+/// Evolve the given table schema with top-level fields of the given JSON objects, return [`None`] if no change is needed.
+/// Fields whose values are incompatible with existing columns leave the schema unchanged, which go to the overflow column at conversion.
+pub(crate) fn evolve_schema(schema: &Schema, rows: &[&Value]) -> Option<InferredSchemaChange> {
+    let mut fields = schema
+        .fields()
+        .iter()
+        .map(|field| field.as_ref().clone())
+        .collect::<Vec<_>>();
+    let existing_column_count = fields.len();
+    let mut column_indices = fields
+        .iter()
+        .enumerate()
+        .map(|(idx, field)| (field.name().clone(), idx))
+        .collect::<HashMap<_, _>>();
+    let mut promoted_columns = HashMap::new();
+
+    for row in rows.iter() {
+        let Some(object) = row.as_object() else {
+            continue;
+        };
+        for (name, value) in object.iter() {
+            if name == OVERFLOW_COLUMN_NAME {
+                continue;
+            }
+            let Some(value_type) = infer_data_type(value) else {
+                continue;
+            };
+            let Some(&idx) = column_indices.get(name) else {
+                column_indices.insert(name.clone(), fields.len());
+                fields.push(make_inferred_field(name, value, value_type));
+                continue;
+            };
+            let is_new_column = idx >= existing_column_count;
+            let Some(widened_type) =
+                get_widened_type(fields[idx].data_type(), &value_type, is_new_column)
+            else {
+                continue;
+            };
+            fields[idx] = fields[idx].clone().with_data_type(widened_type.clone());
+            // Columns added by the same payloads are added with the widened type directly.
+            if !is_new_column {
+                promoted_columns.insert(name.clone(), widened_type);
+            }
+        }
+    }
+
+    if fields.len() == existing_column_count && promoted_columns.is_empty() {
+        return None;
+    }
+    let columns_to_add = fields[existing_column_count..]
+        .iter()
+        .map(|field| Arc::new(field.clone()))
+        .collect::<Vec<_>>();
+    let mut columns_to_promote = promoted_columns.into_iter().collect::<Vec<_>>();
+    columns_to_promote.sort_by(|(left, _), (right, _)| left.cmp(right));
+    Some(InferredSchemaChange {
+        new_schema: Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone())),
+        columns_to_add,
+        columns_to_promote,
+    })
+}
+
+/// Convert the given JSON object into moonlink row with the inferred schema.
+/// Missing fields are filled with nulls, and fields which cannot be converted are kept in the overflow column.
+pub(crate) fn convert_row(
+    schema: &Schema,
+    row: &Value,
+) -> Result<MoonlinkRow, JsonToMoonlinkRowError> {
+    let object = row
+        .as_object()
+        .ok_or_else(|| JsonToMoonlinkRowError::TypeMismatch("row".to_string()))?;
+    let mut overflow = Map::new();
+    let mut overflow_idx = None;
+    let mut values = Vec::with_capacity(schema.fields().len());
+    for (idx, field) in schema.fields().iter().enumerate() {
+        if field.name() == OVERFLOW_COLUMN_NAME {
+            overflow_idx = Some(idx);
+            values.push(RowValue::Null);
+            continue;
+        }
+        let row_value = match object.get(field.name()) {
+            Some(value) => match JsonToMoonlinkRowConverter::convert_value(field, value) {
+                Ok(row_value) => row_value,
+                Err(_) => {
+                    overflow.insert(field.name().clone(), value.clone());
+                    RowValue::Null
+                }
+            },
+            None => RowValue::Null,
+        };
+        values.push(row_value);
+    }
+
+    // Collect fields without columns, including the ones named after overflow column.
+    for (name, value) in object.iter() {
+        if value.is_null() {
+            continue;
+        }
+        if name == OVERFLOW_COLUMN_NAME || schema.field_with_name(name).is_err() {
+            overflow.insert(name.clone(), value.clone());
+        }
+    }
+    let overflow_idx = overflow_idx
+        .ok_or_else(|| JsonToMoonlinkRowError::MissingField(OVERFLOW_COLUMN_NAME.to_string()))?;
+    if !overflow.is_empty() {
+        values[overflow_idx] =
+            RowValue::ByteArray(Value::Object(overflow).to_string().into_bytes());
+    }
+    Ok(MoonlinkRow::new(values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_evolve_schema_with_new_and_promoted_columns() {
+        let schema = get_initial_inferred_schema();
+        assert!(is_schema_inferred(&schema));
+        assert!(evolve_schema(&schema, &[&json!({})]).is_none());
+
+        // New fields are added as nullable columns, and null values don't decide column type.
+        let change = evolve_schema(
+            &schema,
+            &[
+                &json!({"id": 1, "name": "Alice", "tags": ["a"], "score": null}),
+                &json!({"id": 2, "active": true}),
+            ],
+        )
+        .unwrap();
+        assert!(change.columns_to_promote.is_empty());
+        let new_schema = change.new_schema;
+        assert_eq!(new_schema.fields().len(), 5);
+        assert_eq!(change.columns_to_add.len(), 4);
+        assert_eq!(
+            new_schema.field_with_name("id").unwrap().data_type(),
+            &DataType::Int64
+        );
+        assert!(new_schema
+            .field_with_name("tags")
+            .unwrap()
+            .try_extension_type::<ArrowJson>()
+            .is_ok());
+        assert!(new_schema.fields().iter().all(|field| field.is_nullable()));
+
+        // Existing integer columns are not widened to float, and incompatible values leave columns untouched.
+        let change =
+            evolve_schema(&new_schema, &[&json!({"id": 1.5, "name": 3, "score": 1.5})]).unwrap();
+        assert!(change.columns_to_promote.is_empty());
+        assert_eq!(change.columns_to_add.len(), 1);
+        assert_eq!(change.columns_to_add[0].data_type(), &DataType::Float64);
+        assert_eq!(
+            change.new_schema.field_with_name("id").unwrap().data_type(),
+            &DataType::Int64
+        );
+        assert_eq!(
+            change
+                .new_schema
+                .field_with_name("name")
+                .unwrap()
+                .data_type(),
+            &DataType::Utf8
+        );
+
+        // Columns added with both integers and floats by the same payloads are added as Float64.
+        let change =
+            evolve_schema(&schema, &[&json!({"value": 1}), &json!({"value": 1.5})]).unwrap();
+        assert!(change.columns_to_promote.is_empty());
+        assert_eq!(change.columns_to_add[0].data_type(), &DataType::Float64);
+        let change =
+            evolve_schema(&schema, &[&json!({"value": 1.5}), &json!({"value": 1})]).unwrap();
+        assert_eq!(change.columns_to_add[0].data_type(), &DataType::Float64);
+
+        // Integer columns inferred by older versions are widened to Int64.
+        let legacy_schema = Schema::new(vec![
+            get_overflow_field(),
+            Field::new("id", DataType::Int32, /*nullable=*/ true),
+        ]);
+        let change = evolve_schema(&legacy_schema, &[&json!({"id": 5_000_000_000_i64})]).unwrap();
+        assert_eq!(
+            change.columns_to_promote,
+            vec![("id".to_string(), DataType::Int64)]
+        );
+    }
+
+    #[test]
+    fn test_convert_row_with_overflow() {
+        let schema = evolve_schema(
+            &get_initial_inferred_schema(),
+            &[&json!({"id": 1, "name": "Alice"})],
+        )
+        .unwrap()
+        .new_schema;
+
+        // Missing fields are nulls, and overflow column is null when all fields fit.
+        let row = convert_row(&schema, &json!({"id": 1})).unwrap();
+        assert_eq!(
+            row.values,
+            vec![RowValue::Null, RowValue::Int64(1), RowValue::Null]
+        );
+
+        let row = convert_row(
+            &schema,
+            &json!({"id": 2, "name": {"first": "Bob"}, "extra": null, "_moonlink_overflow": 1}),
+        )
+        .unwrap();
+        assert_eq!(row.values[1], RowValue::Int64(2));
+        assert_eq!(row.values[2], RowValue::Null);
+        let RowValue::ByteArray(overflow) = &row.values[0] else {
+            panic!("overflow column should be set");
+        };
+        assert_eq!(
+            serde_json::from_slice::<Value>(overflow).unwrap(),
+            json!({"name": {"first": "Bob"}, "_moonlink_overflow": 1})
+        );
+
+        // Only JSON objects are accepted.
+        assert!(convert_row(&schema, &json!([1, 2])).is_err());
+    }
+}
@@ -1,6 +1,10 @@
-use arrow_schema::{DataType, Field, Schema};
+use arrow_schema::extension::{Json as ArrowJson, Uuid as ArrowUuid};
+use arrow_schema::{DataType, Field, Fields, Schema, TimeUnit};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::rest_ingest::datetime_utils::INPUT_TIMEZONE_METADATA_KEY;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct FieldSchema {
@@ -13,12 +17,27 @@ pub struct FieldSchema {
     pub item: Option<Box<FieldSchema>>, // for list/array
 }
 
+impl FieldSchema {
+    /// Create a field schema for primitive or map types, which don't have nested field schemas.
+    pub(crate) fn new_primitive(name: &str, data_type: &str, nullable: bool) -> Self {
+        Self {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            nullable,
+            fields: None,
+            item: None,
+        }
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum SchemaBuildError {
     #[error("invalid decimal type: {0}")]
     InvalidDecimal(String),
     #[error("invalid schema: {0}")]
     InvalidSchema(String),
+    #[error("invalid timezone: {0}")]
+    InvalidTimezone(String),
     #[error("unsupported data type: {0}")]
     UnsupportedType(String),
 }
@@ -57,6 +76,54 @@ fn parse_decimal(data_type_str: &str) -> Result<DecimalType, SchemaBuildError> {
     }
 }
 
+/// Parse a timezone-aware timestamp type string in the form of
+///   - timestamptz, which uses UTC timezone
+///   - timestamptz(zone), where zone is an IANA timezone name
+///
+/// Timestamps are stored in UTC, timezone is only used to interpret timestamps without offset at ingestion.
+/// Return [`None`] for UTC timezone.
+fn parse_timestamptz(
+    data_type_str: &str,
+    original_str: &str,
+) -> Result<Option<String>, SchemaBuildError> {
+    if data_type_str == "timestamptz" {
+        return Ok(None);
+    }
+    // Timezone names are case sensitive, so parse from the original type string.
+    let zone = original_str["timestamptz(".len()..original_str.len() - 1].trim();
+    zone.parse::<chrono_tz::Tz>()
+        .map_err(|_| SchemaBuildError::InvalidTimezone(original_str.to_string()))?;
+    if zone == "UTC" {
+        return Ok(None);
+    }
+    Ok(Some(zone.to_string()))
+}
+
+/// Parse a map type string in the form of map<key type, value type>, and return key and value type strings.
+fn parse_map(data_type_str: &str) -> Result<(String, String), SchemaBuildError> {
+    let inner = &data_type_str[4..data_type_str.len() - 1];
+    // Split at the top-level comma, since value types like decimal and map contain commas as well.
+    let mut depth = 0;
+    for (idx, ch) in inner.char_indices() {
+        match ch {
+            '<' | '(' => depth += 1,
+            '>' | ')' => depth -= 1,
+            ',' if depth == 0 => {
+                let key_type = inner[..idx].trim();
+                let value_type = inner[idx + 1..].trim();
+                if key_type.is_empty() || value_type.is_empty() {
+                    break;
+                }
+                return Ok((key_type.to_string(), value_type.to_string()));
+            }
+            _ => {}
+        }
+    }
+    Err(SchemaBuildError::InvalidSchema(format!(
+        "Invalid map type '{data_type_str}', expected map<key type, value type>"
+    )))
+}
+
 /// Build an Arrow `Field` from a `FieldSchema`.
 ///
 /// Returns an Arrow field and mutates the `field_id` as a side effect
@@ -120,6 +187,95 @@ fn build_field_from_schema(
             *field_id += 1;
             Ok(Field::new(&name, DataType::Date32, nullable).with_metadata(metadata))
         }
+        "timestamp" => {
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            *field_id += 1;
+            Ok(Field::new(
+                &name,
+                DataType::Timestamp(TimeUnit::Microsecond, None),
+                nullable,
+            )
+            .with_metadata(metadata))
+        }
+        // Timezone-aware timestamp type: timestamptz[(zone)]
+        dt if dt == "timestamptz" || (dt.starts_with("timestamptz(") && dt.ends_with(')')) => {
+            let zone = parse_timestamptz(&data_type_str, field_schema.data_type.trim())?;
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            if let Some(zone) = zone {
+                metadata.insert(INPUT_TIMEZONE_METADATA_KEY.to_string(), zone);
+            }
+            *field_id += 1;
+            Ok(Field::new(
+                &name,
+                DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into())),
+                nullable,
+            )
+            .with_metadata(metadata))
+        }
+        "time" => {
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            *field_id += 1;
+            Ok(
+                Field::new(&name, DataType::Time64(TimeUnit::Microsecond), nullable)
+                    .with_metadata(metadata),
+            )
+        }
+        "uuid" => {
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            *field_id += 1;
+            Ok(Field::new(&name, DataType::FixedSizeBinary(16), nullable)
+                .with_metadata(metadata)
+                .with_extension_type(ArrowUuid::default()))
+        }
+        "binary" | "bytes" => {
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            *field_id += 1;
+            Ok(Field::new(&name, DataType::Binary, nullable).with_metadata(metadata))
+        }
+        "json" => {
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            *field_id += 1;
+            Ok(Field::new(&name, DataType::Utf8, nullable)
+                .with_metadata(metadata)
+                .with_extension_type(ArrowJson::default()))
+        }
+        // Map type: map<key type, value type>, keys and values take primitive types, values could also be maps.
+        dt if dt.starts_with("map<") && dt.ends_with('>') => {
+            let (key_type, value_type) = parse_map(field_schema.data_type.trim())?;
+            // Map keys cannot be null.
+            let key_field = build_field_from_schema(
+                &FieldSchema::new_primitive("key", &key_type, /*nullable=*/ false),
+                /*override_name=*/ None,
+                field_id,
+            )?;
+            if matches!(key_field.data_type(), DataType::Map(_, _)) {
+                return Err(SchemaBuildError::UnsupportedType(format!(
+                    "Invalid key type for map '{}': {key_type}",
+                    field_schema.name
+                )));
+            }
+            let value_field = build_field_from_schema(
+                &FieldSchema::new_primitive("value", &value_type, /*nullable=*/ true),
+                /*override_name=*/ None,
+                field_id,
+            )?;
+            let entries_field = Field::new(
+                "key_value",
+                DataType::Struct(Fields::from(vec![key_field, value_field])),
+                /*nullable=*/ false,
+            );
+            let map_type = DataType::Map(Arc::new(entries_field), /*sorted=*/ false);
+            let mut metadata = HashMap::new();
+            metadata.insert("PARQUET:field_id".to_string(), field_id.to_string());
+            *field_id += 1;
+            Ok(Field::new(&name, map_type, nullable).with_metadata(metadata))
+        }
         // Decimal type: decimal(precision[, scale])
         dt if dt.starts_with("decimal(") && dt.ends_with(')') => {
             let DecimalType { precision, scale } = parse_decimal(&data_type_str)?;
@@ -159,9 +315,9 @@ fn build_field_from_schema(
                 ))
             })?;
 
-            if matches!(item_schema.data_type.as_str(), "list" | "array" | "struct") {
+            if item_schema.data_type.to_lowercase() == "struct" {
                 return Err(SchemaBuildError::UnsupportedType(format!(
-                    "Invalid 'item' for list '{}': list/array/struct is not supported as a list item",
+                    "Invalid 'item' for list '{}': struct is not supported as a list item",
                     field_schema.name
                 )));
             }
@@ -269,7 +425,7 @@ mod tests {
     }
 
     #[test]
-    fn test_build_nested_lists_rejected() {
+    fn test_build_nested_lists() {
         let fields = vec![FieldSchema {
             name: "matrix".into(),
             data_type: "array".into(),
@@ -289,6 +445,36 @@ mod tests {
                 })),
             })),
         }];
+        let schema = build_arrow_schema_impl(&fields).unwrap();
+        let matrix_field = schema.field(0);
+        let DataType::List(row_field) = matrix_field.data_type() else {
+            panic!("unexpected data type: {:?}", matrix_field.data_type());
+        };
+        let DataType::List(cell_field) = row_field.data_type() else {
+            panic!("unexpected data type: {:?}", row_field.data_type());
+        };
+        assert_eq!(cell_field.data_type(), &DataType::Int32);
+        // Children get field ids ahead of their parents.
+        assert_eq!(cell_field.metadata()["PARQUET:field_id"], "0");
+        assert_eq!(row_field.metadata()["PARQUET:field_id"], "1");
+        assert_eq!(matrix_field.metadata()["PARQUET:field_id"], "2");
+    }
+
+    #[test]
+    fn test_build_list_of_structs_rejected() {
+        let fields = vec![FieldSchema {
+            name: "items".into(),
+            data_type: "list".into(),
+            nullable: true,
+            fields: None,
+            item: Some(Box::new(FieldSchema {
+                name: "item".into(),
+                data_type: "Struct".into(),
+                nullable: true,
+                fields: Some(vec![FieldSchema::new_primitive("id", "int32", false)]),
+                item: None,
+            })),
+        }];
         let err = build_arrow_schema_impl(&fields).unwrap_err();
         match err {
             SchemaBuildError::UnsupportedType(_) => {}
@@ -296,6 +482,106 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_build_temporal_and_binary_types() {
+        let fields = vec![
+            FieldSchema::new_primitive("ts", "timestamp", true),
+            FieldSchema::new_primitive("ts_utc", "timestamptz", true),
+            FieldSchema::new_primitive("ts_la", "timestamptz(America/Los_Angeles)", true),
+            FieldSchema::new_primitive("t", "time", true),
+            FieldSchema::new_primitive("id", "uuid", false),
+            FieldSchema::new_primitive("payload", "bytes", true),
+            FieldSchema::new_primitive("blob", "binary", true),
+            FieldSchema::new_primitive("doc", "json", true),
+        ];
+        let schema = build_arrow_schema_impl(&fields).unwrap();
+        assert_eq!(
+            schema.field(0).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, None)
+        );
+        assert_eq!(
+            schema.field(1).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert!(!schema
+            .field(1)
+            .metadata()
+            .contains_key(INPUT_TIMEZONE_METADATA_KEY));
+        assert_eq!(
+            schema.field(2).data_type(),
+            &DataType::Timestamp(TimeUnit::Microsecond, Some("UTC".into()))
+        );
+        assert_eq!(
+            schema.field(2).metadata()[INPUT_TIMEZONE_METADATA_KEY],
+            "America/Los_Angeles"
+        );
+        assert_eq!(
+            schema.field(3).data_type(),
+            &DataType::Time64(TimeUnit::Microsecond)
+        );
+        assert_eq!(schema.field(4).data_type(), &DataType::FixedSizeBinary(16));
+        assert!(schema.field(4).try_extension_type::<ArrowUuid>().is_ok());
+        assert_eq!(schema.field(5).data_type(), &DataType::Binary);
+        assert_eq!(schema.field(6).data_type(), &DataType::Binary);
+        assert_eq!(schema.field(7).data_type(), &DataType::Utf8);
+        assert!(schema.field(7).try_extension_type::<ArrowJson>().is_ok());
+        // Extension metadata keeps field ids.
+        assert_eq!(schema.field(7).metadata()["PARQUET:field_id"], "7");
+
+        // Unknown timezone.
+        let fields = vec![FieldSchema::new_primitive(
+            "ts",
+            "timestamptz(Mars/Olympus_Mons)",
+            true,
+        )];
+        let err = build_arrow_schema_impl(&fields).unwrap_err();
+        match err {
+            SchemaBuildError::InvalidTimezone(_) => {}
+            _ => panic!("unexpected error: {err:?}"),
+        }
+    }
+
+    #[test]
+    fn test_build_map_schema() {
+        let fields = vec![
+            FieldSchema::new_primitive("attrs", "map<string, decimal(10,2)>", true),
+            FieldSchema::new_primitive("nested", "map<int32, map<string, timestamptz(UTC)>>", true),
+        ];
+        let schema = build_arrow_schema_impl(&fields).unwrap();
+        let DataType::Map(entries_field, /*sorted=*/ false) = schema.field(0).data_type() else {
+            panic!("unexpected data type: {:?}", schema.field(0).data_type());
+        };
+        let DataType::Struct(entries) = entries_field.data_type() else {
+            panic!("unexpected entries type: {:?}", entries_field.data_type());
+        };
+        assert_eq!(entries[0].name(), "key");
+        assert_eq!(entries[0].data_type(), &DataType::Utf8);
+        assert!(!entries[0].is_nullable());
+        assert_eq!(entries[1].name(), "value");
+        assert_eq!(entries[1].data_type(), &DataType::Decimal128(10, 2));
+        assert!(entries[1].is_nullable());
+        assert_eq!(entries[0].metadata()["PARQUET:field_id"], "0");
+        assert_eq!(entries[1].metadata()["PARQUET:field_id"], "1");
+        assert_eq!(schema.field(0).metadata()["PARQUET:field_id"], "2");
+        assert!(matches!(
+            schema.field(1).data_type(),
+            DataType::Map(_, /*sorted=*/ false)
+        ));
+
+        // Invalid map definitions.
+        for data_type in [
+            "map<string>",
+            "map<, int32>",
+            "map<map<int32, int32>, int32>",
+        ] {
+            let fields = vec![FieldSchema::new_primitive("bad_map", data_type, true)];
+            assert!(
+                build_arrow_schema_impl(&fields).is_err(),
+                "{data_type} should be rejected"
+            );
+        }
+    }
+
     #[test]
     fn test_invalid_struct_missing_fields() {
         let fields = vec![FieldSchema {
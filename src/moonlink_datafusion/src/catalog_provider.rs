@@ -2,19 +2,30 @@ use crate::connection_pool::Pool;
 use crate::error::Result;
 use crate::schema_provider::MooncakeSchemaProvider;
 use datafusion::catalog::{CatalogProvider, SchemaProvider};
+use moonlink::KeyProviderConfig;
 use std::any::Any;
 use std::sync::Arc;
 
 #[derive(Debug)]
 pub struct MooncakeCatalogProvider {
     uri: String,
+    /// Key provider to unwrap data keys of encrypted tables with, encrypted tables are not readable if unassigned.
+    key_provider_config: Option<KeyProviderConfig>,
 }
 
 impl MooncakeCatalogProvider {
     pub async fn try_new(uri: String) -> Result<Self> {
         let _ = Pool::get_stream(&uri).await?;
 
-        Ok(Self { uri })
+        Ok(Self {
+            uri,
+            key_provider_config: None,
+        })
+    }
+
+    pub fn with_key_provider(mut self, key_provider_config: KeyProviderConfig) -> Self {
+        self.key_provider_config = Some(key_provider_config);
+        self
     }
 }
 
@@ -32,6 +43,7 @@ impl CatalogProvider for MooncakeCatalogProvider {
         Some(Arc::new(MooncakeSchemaProvider::new(
             self.uri.clone(),
             database_id,
+            self.key_provider_config.clone(),
         )))
     }
 }
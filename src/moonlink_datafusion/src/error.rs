@@ -1,5 +1,6 @@
 use arrow::error::ArrowError;
 use bincode::error::DecodeError;
+use moonlink::Error as MoonlinkError;
 use moonlink_error::{io_error_utils, ErrorStatus, ErrorStruct};
 use moonlink_rpc::Error as MoonlinkRPCError;
 use std::{panic::Location, sync::Arc};
@@ -12,6 +13,8 @@ pub enum Error {
     #[error("{0}")]
     Bincode(ErrorStruct),
     #[error("{0}")]
+    Encryption(ErrorStruct),
+    #[error("{0}")]
     Io(ErrorStruct),
     #[error("{0}")]
     Rpc(ErrorStruct),
@@ -45,6 +48,19 @@ impl From<DecodeError> for Error {
     }
 }
 
+impl From<MoonlinkError> for Error {
+    #[track_caller]
+    fn from(source: MoonlinkError) -> Self {
+        let status = source.get_status();
+        Error::Encryption(ErrorStruct {
+            message: "Encryption error".to_string(),
+            status,
+            source: Some(Arc::new(source.into())),
+            location: Some(Location::caller().to_string()),
+        })
+    }
+}
+
 impl From<std::io::Error> for Error {
     #[track_caller]
     fn from(source: std::io::Error) -> Self {
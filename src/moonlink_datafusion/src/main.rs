@@ -3,6 +3,7 @@ use datafusion::prelude::SessionContext;
 use datafusion_cli::exec::exec_from_repl;
 use datafusion_cli::print_format::PrintFormat;
 use datafusion_cli::print_options::{MaxRows, PrintOptions};
+use moonlink::KeyProviderConfig;
 use moonlink_datafusion::MooncakeCatalogProvider;
 use std::error::Error;
 use std::sync::Arc;
@@ -10,13 +11,19 @@ use std::sync::Arc;
 #[derive(Parser)]
 struct Cli {
     uri: String,
+    /// Local directory holding master keys, required to read encrypted tables.
+    #[arg(long)]
+    key_directory: Option<String>,
 }
 
 #[tokio::main]
 pub async fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
     let ctx = SessionContext::new();
-    let catalog = MooncakeCatalogProvider::try_new(cli.uri).await?;
+    let mut catalog = MooncakeCatalogProvider::try_new(cli.uri).await?;
+    if let Some(directory) = cli.key_directory {
+        catalog = catalog.with_key_provider(KeyProviderConfig::LocalFile { directory });
+    }
     ctx.register_catalog("mooncake", Arc::new(catalog));
 
     // EXAMPLE:
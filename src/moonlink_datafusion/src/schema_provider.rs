@@ -2,6 +2,7 @@ use crate::table_provider::MooncakeTableProvider;
 use async_trait::async_trait;
 use datafusion::catalog::{SchemaProvider, TableProvider};
 use datafusion::common::DataFusionError;
+use moonlink::KeyProviderConfig;
 use std::any::Any;
 use std::sync::Arc;
 
@@ -9,11 +10,20 @@ use std::sync::Arc;
 pub(crate) struct MooncakeSchemaProvider {
     uri: String,
     schema: String,
+    key_provider_config: Option<KeyProviderConfig>,
 }
 
 impl MooncakeSchemaProvider {
-    pub(crate) fn new(uri: String, schema: String) -> Self {
-        Self { uri, schema }
+    pub(crate) fn new(
+        uri: String,
+        schema: String,
+        key_provider_config: Option<KeyProviderConfig>,
+    ) -> Self {
+        Self {
+            uri,
+            schema,
+            key_provider_config,
+        }
     }
 }
 
@@ -28,9 +38,14 @@ impl SchemaProvider for MooncakeSchemaProvider {
     }
 
     async fn table(&self, table: &str) -> Result<Option<Arc<dyn TableProvider>>, DataFusionError> {
-        let res =
-            MooncakeTableProvider::try_new(&self.uri, self.schema.clone(), table.to_string(), 0)
-                .await;
+        let res = MooncakeTableProvider::try_new(
+            &self.uri,
+            self.schema.clone(),
+            table.to_string(),
+            0,
+            self.key_provider_config.as_ref(),
+        )
+        .await;
         let Ok(table) = res else {
             return Ok(None);
         };
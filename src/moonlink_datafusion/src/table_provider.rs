@@ -7,6 +7,7 @@ use bincode::config;
 use datafusion::catalog::memory::DataSourceExec;
 use datafusion::catalog::{Session, TableProvider};
 use datafusion::common::{DFSchema, DataFusionError};
+use datafusion::config::{ConfigFileDecryptionProperties, TableParquetOptions};
 use datafusion::datasource::listing::PartitionedFile;
 use datafusion::datasource::physical_plan::parquet::{
     DefaultParquetFileReaderFactory, ParquetAccessPlan,
@@ -19,10 +20,11 @@ use datafusion::logical_expr::utils::conjunction;
 use datafusion::logical_expr::{Expr, TableProviderFilterPushDown, TableType};
 use datafusion::physical_plan::metrics::ExecutionPlanMetricsSet;
 use datafusion::physical_plan::ExecutionPlan;
+use moonlink::{DataEncryptionKey, KeyProviderConfig, TableEncryption};
 use moonlink_rpc::{get_table_schema, scan_table_begin, scan_table_end};
 use moonlink_table_metadata::{DeletionVector, MooncakeTableMetadata, PositionDelete};
 use object_store::ObjectStore;
-use parquet::arrow::arrow_reader::{RowSelection, RowSelector};
+use parquet::arrow::arrow_reader::{ArrowReaderOptions, RowSelection, RowSelector};
 use parquet::arrow::async_reader::AsyncFileReader;
 use parquet::arrow::ParquetRecordBatchStreamBuilder;
 use roaring::RoaringTreemap;
@@ -38,7 +40,16 @@ pub struct MooncakeTableProvider {
 }
 
 impl MooncakeTableProvider {
-    pub async fn try_new(uri: &str, schema: String, table: String, lsn: u64) -> Result<Self> {
+    /// # Arguments
+    ///
+    /// * key_provider_config: key provider to unwrap data key with, required if the table is encrypted.
+    pub async fn try_new(
+        uri: &str,
+        schema: String,
+        table: String,
+        lsn: u64,
+        key_provider_config: Option<&KeyProviderConfig>,
+    ) -> Result<Self> {
         let mut pooled_stream = Pool::get_stream(uri).await?;
         let table_schema = get_table_schema(
             &mut pooled_stream.stream_mut(),
@@ -48,7 +59,10 @@ impl MooncakeTableProvider {
         .await?;
 
         let table_schema = StreamReader::try_new(table_schema.as_slice(), None)?.schema();
-        let scan = Arc::new(MooncakeTableScan::try_new(pooled_stream, schema, table, lsn).await?);
+        let scan = Arc::new(
+            MooncakeTableScan::try_new(pooled_stream, schema, table, lsn, key_provider_config)
+                .await?,
+        );
 
         Ok(Self {
             schema: table_schema,
@@ -82,7 +96,32 @@ impl TableProvider for MooncakeTableProvider {
         let predicate = conjunction(filters.to_vec())
             .map(|predicate| state.create_physical_expr(predicate, &schema))
             .transpose()?;
-        let mut source = ParquetSource::default();
+        let MooncakeTableMetadata {
+            data_files,
+            puffin_files,
+            deletion_vectors,
+            position_deletes,
+            data_key_envelope: _,
+        } = &self.scan.metadata;
+
+        // Data files of encrypted tables are decrypted with the data key unwrapped at scan creation.
+        let data_key = self.scan.data_key.as_ref();
+        let mut parquet_options = TableParquetOptions::default();
+        let mut reader_options = ArrowReaderOptions::new();
+        if let Some(data_key) = data_key {
+            parquet_options.crypto.file_decryption = Some(ConfigFileDecryptionProperties {
+                footer_key_as_hex: data_key
+                    .get_parquet_key()
+                    .iter()
+                    .map(|byte| format!("{byte:02x}"))
+                    .collect(),
+                ..Default::default()
+            });
+            reader_options = reader_options
+                .with_file_decryption_properties(data_key.get_file_decryption_properties());
+        }
+
+        let mut source = ParquetSource::new(parquet_options);
         if let Some(predicate) = predicate {
             source = source.with_predicate(predicate);
         }
@@ -97,12 +136,6 @@ impl TableProvider for MooncakeTableProvider {
             .with_projection(projection.cloned())
             .with_limit(limit);
 
-        let MooncakeTableMetadata {
-            data_files,
-            puffin_files,
-            deletion_vectors,
-            position_deletes,
-        } = &self.scan.metadata;
         let mut deletion_vector_number = 0;
         let mut position_delete_number = 0;
         for (data_file_number, data_file) in data_files.iter().enumerate() {
@@ -118,11 +151,17 @@ impl TableProvider for MooncakeTableProvider {
                     deletion_vector_number += 1;
                     let mut puffin_file =
                         File::open(&puffin_files[puffin_file_number as usize]).await?;
+                    puffin_file.seek(SeekFrom::Start(offset as u64)).await?;
+                    let mut blob = vec![0u8; size as usize];
+                    puffin_file.read_exact(&mut blob).await?;
+                    // Deletion vector blobs of encrypted tables are encrypted as a whole.
+                    if let Some(data_key) = data_key {
+                        blob = data_key
+                            .decrypt(&blob)
+                            .map_err(|e| DataFusionError::External(Box::new(e)))?;
+                    }
                     // | 4-byte length | 4-byte magic | buffer | 4-byte CRC-32 |
-                    puffin_file.seek(SeekFrom::Start(offset as u64 + 8)).await?;
-                    let mut buffer = vec![0u8; size as usize - 12];
-                    puffin_file.read_exact(&mut buffer).await?;
-                    deleted_rows = RoaringTreemap::deserialize_from(buffer.as_slice())?;
+                    deleted_rows = RoaringTreemap::deserialize_from(&blob[8..blob.len() - 4])?;
                 }
             }
             while position_delete_number < position_deletes.len() {
@@ -139,7 +178,9 @@ impl TableProvider for MooncakeTableProvider {
 
             let file = File::open(data_file).await?;
             let size = file.metadata().await?.len();
-            let stream_builder = ParquetRecordBatchStreamBuilder::new(file).await?;
+            let stream_builder =
+                ParquetRecordBatchStreamBuilder::new_with_options(file, reader_options.clone())
+                    .await?;
             let mut access_plan =
                 ParquetAccessPlan::new_all(stream_builder.metadata().num_row_groups());
             let mut data_file_row_number = 0;
@@ -214,6 +255,8 @@ struct MooncakeTableScan {
     schema: String,
     table: String,
     metadata: MooncakeTableMetadata,
+    /// Data key unwrapped from read state, if the table is encrypted.
+    data_key: Option<DataEncryptionKey>,
 }
 
 impl MooncakeTableScan {
@@ -222,6 +265,7 @@ impl MooncakeTableScan {
         schema: String,
         table: String,
         lsn: u64,
+        key_provider_config: Option<&KeyProviderConfig>,
     ) -> Result<Self> {
         let metadata = scan_table_begin(
             &mut pooled_stream.stream_mut(),
@@ -232,12 +276,28 @@ impl MooncakeTableScan {
         .await?;
         let metadata: MooncakeTableMetadata =
             bincode::decode_from_slice(&metadata, config::standard())?.0;
-        Ok(Self {
+        // Construct scan ahead of data key unwrap, so read state is released on failure.
+        let mut scan = Self {
             pooled_stream: Some(pooled_stream),
             schema,
             table,
             metadata,
-        })
+            data_key: None,
+        };
+        if let Some(data_key_envelope) = &scan.metadata.data_key_envelope {
+            let Some(key_provider_config) = key_provider_config else {
+                return Err(moonlink::Error::encryption_error(format!(
+                    "Table {}.{} is encrypted, but no key provider is configured",
+                    scan.schema, scan.table
+                ))
+                .into());
+            };
+            scan.data_key = Some(
+                TableEncryption::unwrap_data_key_envelope(key_provider_config, data_key_envelope)
+                    .await?,
+            );
+        }
+        Ok(scan)
     }
 }
 
@@ -10,6 +10,8 @@ use moonlink::MoonlinkTableConfig;
 pub const MOONLINK_SCHEMA: &str = "mooncake";
 /// Metadata table name for moonlink.
 pub const MOONLINK_METADATA_TABLE: &str = "tables";
+/// Avro schema table name for moonlink.
+pub const MOONLINK_AVRO_SCHEMA_TABLE: &str = "avro_schemas";
 
 /// Metadata entry for each table.
 #[derive(Clone, Debug)]
@@ -26,6 +28,15 @@ pub struct TableMetadataEntry {
     pub moonlink_table_config: MoonlinkTableConfig,
 }
 
+/// Avro schema set for a table.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AvroSchemaEntry {
+    /// Schema id in confluent wire format, if the schema comes from a schema registry.
+    pub schema_id: Option<u32>,
+    /// JSON serialized Avro schema.
+    pub avro_schema: String,
+}
+
 #[async_trait]
 pub trait MetadataStoreTrait: Send + Sync {
     /// Return whether metadata table exists.
@@ -61,8 +72,23 @@ pub trait MetadataStoreTrait: Send + Sync {
         moonlink_table_config: MoonlinkTableConfig,
     ) -> Result<()>;
 
-    /// Delete table config for the given table.
+    /// Delete table config for the given table, along with its Avro schemas.
     /// Precondition: the requested table id has been record in the metadata storage.
     #[allow(async_fn_in_trait)]
     async fn delete_table_metadata(&self, database: &str, table: &str) -> Result<()>;
+
+    /// Store an Avro schema set for the given mooncake table, so schemas could be set again in the same order at recovery.
+    /// Avro schema table will be created if it doesn't exist.
+    #[allow(async_fn_in_trait)]
+    async fn store_avro_schema(
+        &self,
+        database: &str,
+        table: &str,
+        schema_id: Option<u32>,
+        avro_schema: &str,
+    ) -> Result<()>;
+
+    /// Get Avro schemas set for the given mooncake table, in the order they're set.
+    #[allow(async_fn_in_trait)]
+    async fn get_avro_schemas(&self, database: &str, table: &str) -> Result<Vec<AvroSchemaEntry>>;
 }
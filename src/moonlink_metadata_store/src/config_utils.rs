@@ -1,8 +1,10 @@
 use crate::error::Result;
 use moonlink::row::IdentityProp;
 use moonlink::{
-    DataCompactionConfig, DiskSliceWriterConfig, FileIndexMergeConfig, IcebergPersistenceConfig,
-    IcebergTableConfig, MooncakeTableConfig, MoonlinkTableConfig, WalConfig,
+    DataCompactionConfig, DeltalakeTableConfig, DiskSliceWriterConfig, EncryptionConfig,
+    FileIndexMergeConfig, IcebergPersistenceConfig, IcebergTableConfig, MooncakeTableConfig,
+    MoonlinkTableConfig, ParquetWriterConfig, PartitionFieldConfig, SnapshotRetentionConfig,
+    SortFieldConfig, SourceFilterConfig, WalConfig,
 };
 /// This module contains util functions related to moonlink config.
 use serde::{Deserialize, Serialize};
@@ -46,6 +48,34 @@ struct MooncakeTableConfigForPersistence {
     /// Identity of a single row.
     #[serde(default = "MoonlinkTableConfigForPersistence::default_row_identity")]
     row_identity: IdentityProp,
+
+    /// Partition spec, which cannot be changed after table creation.
+    #[serde(default)]
+    partition_spec: Vec<PartitionFieldConfig>,
+
+    /// Sort order, which cannot be changed after table creation.
+    #[serde(default)]
+    sort_order: Vec<SortFieldConfig>,
+
+    /// Retention policy for iceberg snapshots.
+    #[serde(default)]
+    snapshot_retention_config: SnapshotRetentionConfig,
+
+    /// Parquet writer options for data files.
+    #[serde(default)]
+    parquet_writer_config: ParquetWriterConfig,
+
+    /// Client-side encryption config, which cannot be changed after table creation.
+    #[serde(default)]
+    encryption_config: Option<EncryptionConfig>,
+
+    /// Whether to fall back to REPLICA IDENTITY FULL for source tables without replica identity key.
+    #[serde(default)]
+    replica_identity_full: bool,
+
+    /// Column and row filters on the source table.
+    #[serde(default)]
+    source_filter_config: SourceFilterConfig,
 }
 
 impl MooncakeTableConfigForPersistence {
@@ -69,6 +99,9 @@ struct MoonlinkTableConfigForPersistence {
     mooncake_table_config: MooncakeTableConfigForPersistence,
     /// Iceberg table configuration.
     iceberg_table_config: IcebergTableConfig,
+    /// Delta table configuration, only assigned for tables persisted in delta format.
+    #[serde(default)]
+    deltalake_table_config: Option<DeltalakeTableConfig>,
     /// WAL configuration.
     wal_config: WalConfig,
 }
@@ -112,6 +145,14 @@ impl MoonlinkTableConfigForPersistence {
             data_compaction_config: self.mooncake_table_config.data_compaction_config.clone(),
             file_index_config: self.mooncake_table_config.file_index_config.clone(),
             temp_files_directory: MooncakeTableConfig::DEFAULT_TEMP_FILE_DIRECTORY.to_string(),
+            partition_spec: self.mooncake_table_config.partition_spec.clone(),
+            sort_order: self.mooncake_table_config.sort_order.clone(),
+            snapshot_retention_config: self.mooncake_table_config.snapshot_retention_config.clone(),
+            parquet_writer_config: self.mooncake_table_config.parquet_writer_config.clone(),
+            encryption_config: self.mooncake_table_config.encryption_config.clone(),
+            table_encryption: None,
+            replica_identity_full: self.mooncake_table_config.replica_identity_full,
+            source_filter_config: self.mooncake_table_config.source_filter_config.clone(),
         }
     }
 }
@@ -126,10 +167,12 @@ pub(crate) fn parse_moonlink_table_config(
 ) -> Result<serde_json::Value> {
     // Serialize mooncake table config.
     let iceberg_table_config = moonlink_table_config.iceberg_table_config;
+    let deltalake_table_config = moonlink_table_config.deltalake_table_config;
     let wal_config = moonlink_table_config.wal_table_config;
     let mooncake_config = moonlink_table_config.mooncake_table_config;
     let persisted = MoonlinkTableConfigForPersistence {
         iceberg_table_config,
+        deltalake_table_config,
         wal_config,
         mooncake_table_config: MooncakeTableConfigForPersistence {
             mem_slice_size: mooncake_config.mem_slice_size,
@@ -143,6 +186,13 @@ pub(crate) fn parse_moonlink_table_config(
             persistence_config: mooncake_config.persistence_config.clone(),
             append_only: mooncake_config.append_only,
             row_identity: mooncake_config.row_identity,
+            partition_spec: mooncake_config.partition_spec,
+            sort_order: mooncake_config.sort_order,
+            snapshot_retention_config: mooncake_config.snapshot_retention_config,
+            parquet_writer_config: mooncake_config.parquet_writer_config,
+            encryption_config: mooncake_config.encryption_config,
+            replica_identity_full: mooncake_config.replica_identity_full,
+            source_filter_config: mooncake_config.source_filter_config,
         },
     };
     let config_json = serde_json::to_value(&persisted)?;
@@ -159,6 +209,7 @@ pub(crate) fn deserialize_moonlink_table_config(
 
     let moonlink_table_config = MoonlinkTableConfig {
         iceberg_table_config: parsed.iceberg_table_config,
+        deltalake_table_config: parsed.deltalake_table_config,
         wal_table_config: parsed.wal_config,
         mooncake_table_config,
     };
@@ -176,6 +227,7 @@ mod tests {
     fn test_moonlink_table_config_serde() {
         let old_moonlink_table_config = MoonlinkTableConfig {
             iceberg_table_config: IcebergTableConfig::default(),
+            deltalake_table_config: None,
             mooncake_table_config: MooncakeTableConfig::default(),
             wal_table_config: WalConfig::default(),
         };
@@ -193,6 +245,38 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_moonlink_table_config_with_delta_serde() {
+        let iceberg_table_config = IcebergTableConfig::default();
+        let old_moonlink_table_config = MoonlinkTableConfig {
+            deltalake_table_config: Some(DeltalakeTableConfig {
+                table_name: iceberg_table_config.table_name.clone(),
+                location: "/tmp/moonlink_iceberg/default/table".to_string(),
+                data_accessor_config: iceberg_table_config.data_accessor_config.clone(),
+            }),
+            iceberg_table_config,
+            mooncake_table_config: MooncakeTableConfig::default(),
+            wal_table_config: WalConfig::default(),
+        };
+        let mut serialized_persisted_config =
+            parse_moonlink_table_config(old_moonlink_table_config.clone()).unwrap();
+        let new_moonlink_table_config =
+            deserialize_moonlink_table_config(serialized_persisted_config.clone()).unwrap();
+        assert_eq!(
+            new_moonlink_table_config.deltalake_table_config,
+            old_moonlink_table_config.deltalake_table_config
+        );
+
+        // Configs persisted before delta format support are iceberg tables.
+        serialized_persisted_config
+            .as_object_mut()
+            .unwrap()
+            .remove("deltalake_table_config");
+        let new_moonlink_table_config =
+            deserialize_moonlink_table_config(serialized_persisted_config).unwrap();
+        assert!(new_moonlink_table_config.deltalake_table_config.is_none());
+    }
+
     // Testing scenario: serialized json config only contains partial fields, check whether json deserialization succeeds, and populates default value correctly.
     #[test]
     fn test_mooncake_persisted_config_serde() {
@@ -226,6 +310,8 @@ mod tests {
                 data_file_final_size: 123456,
                 data_file_deletion_percentage:
                     DataCompactionConfig::default_data_file_deletion_percentage(),
+                recluster_overlap_threshold:
+                    DataCompactionConfig::default_recluster_overlap_threshold(),
             },
             // Index merge config.
             file_index_config: FileIndexMergeConfig {
@@ -240,6 +326,20 @@ mod tests {
             append_only: true,
             // Row identity.
             row_identity: IdentityProp::None,
+            // Partition spec.
+            partition_spec: vec![],
+            // Sort order.
+            sort_order: vec![],
+            // Snapshot retention config.
+            snapshot_retention_config: SnapshotRetentionConfig::default(),
+            // Parquet writer config.
+            parquet_writer_config: ParquetWriterConfig::default(),
+            // Encryption config.
+            encryption_config: None,
+            // Replica identity full fallback.
+            replica_identity_full: false,
+            // Source filter config.
+            source_filter_config: SourceFilterConfig::default(),
         };
         assert_eq!(actual_persisted_config, expected_persisted_config);
     }
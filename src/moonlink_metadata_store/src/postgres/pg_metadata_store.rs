@@ -1,6 +1,7 @@
 use crate::base_metadata_store::MetadataStoreTrait;
-use crate::base_metadata_store::TableMetadataEntry;
+use crate::base_metadata_store::MOONLINK_AVRO_SCHEMA_TABLE;
 use crate::base_metadata_store::MOONLINK_METADATA_TABLE;
+use crate::base_metadata_store::{AvroSchemaEntry, TableMetadataEntry};
 use crate::config_utils;
 use crate::error::{Error, Result};
 use crate::postgres::pg_client_wrapper::PgClientWrapper;
@@ -13,6 +14,8 @@ use postgres_types::Json as PgJson;
 
 /// SQL statements for moonlink metadata table database.
 const CREATE_TABLE_SCHEMA_SQL: &str = include_str!("sql/create_tables.sql");
+/// SQL statements for Avro schema table.
+const CREATE_AVRO_SCHEMA_TABLE_SQL: &str = include_str!("sql/create_avro_schemas_table.sql");
 
 pub struct PgMetadataStore {
     /// Database connection string.
@@ -120,6 +123,8 @@ impl MetadataStoreTrait for PgMetadataStore {
 
     async fn delete_table_metadata(&self, database: &str, table: &str) -> Result<()> {
         let pg_client = PgClientWrapper::new(&self.uri).await?;
+        let avro_schema_table_exists =
+            utils::table_exists(&pg_client.postgres_client, MOONLINK_AVRO_SCHEMA_TABLE).await?;
 
         // Start a transaction to insert rows into metadata table and secret table.
         pg_client.postgres_client.execute("BEGIN", &[]).await?;
@@ -139,11 +144,84 @@ impl MetadataStoreTrait for PgMetadataStore {
             )));
         }
 
+        // Delete Avro schemas, which only exist for tables with Avro schema set.
+        if avro_schema_table_exists {
+            pg_client
+                .postgres_client
+                .execute(
+                    r#"DELETE FROM avro_schemas WHERE "database" = $1 AND "table" = $2"#,
+                    &[&database, &table],
+                )
+                .await?;
+        }
+
         // Commit the transaction.
         pg_client.postgres_client.execute("COMMIT", &[]).await?;
 
         Ok(())
     }
+
+    async fn store_avro_schema(
+        &self,
+        database: &str,
+        table: &str,
+        schema_id: Option<u32>,
+        avro_schema: &str,
+    ) -> Result<()> {
+        let pg_client = PgClientWrapper::new(&self.uri).await?;
+
+        // Create Avro schema table if not exist.
+        utils::create_table_if_non_existent(
+            &pg_client.postgres_client,
+            MOONLINK_AVRO_SCHEMA_TABLE,
+            CREATE_AVRO_SCHEMA_TABLE_SQL,
+        )
+        .await?;
+
+        pg_client
+            .postgres_client
+            .execute(
+                r#"INSERT INTO avro_schemas ("database", "table", schema_id, avro_schema)
+                VALUES ($1, $2, $3, $4)"#,
+                &[&database, &table, &schema_id.map(i64::from), &avro_schema],
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    async fn get_avro_schemas(&self, database: &str, table: &str) -> Result<Vec<AvroSchemaEntry>> {
+        let pg_client = PgClientWrapper::new(&self.uri).await?;
+        if !utils::table_exists(&pg_client.postgres_client, MOONLINK_AVRO_SCHEMA_TABLE).await? {
+            return Ok(vec![]);
+        }
+
+        let rows = pg_client
+            .postgres_client
+            .query(
+                r#"
+                SELECT schema_id, avro_schema
+                FROM avro_schemas
+                WHERE "database" = $1 AND "table" = $2
+                ORDER BY id
+                "#,
+                &[&database, &table],
+            )
+            .await?;
+
+        let avro_schemas = rows
+            .into_iter()
+            .map(|row| {
+                let schema_id: Option<i64> = row.get("schema_id");
+                AvroSchemaEntry {
+                    // Schema id is always stored from u32.
+                    schema_id: schema_id.map(|id| id as u32),
+                    avro_schema: row.get("avro_schema"),
+                }
+            })
+            .collect();
+        Ok(avro_schemas)
+    }
 }
 
 impl PgMetadataStore {
@@ -0,0 +1,8 @@
+-- SQL statement(s) to store Avro schemas set for moonlink managed column store tables, in the order they're set.
+CREATE TABLE avro_schemas (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,  -- order the schema is set
+    "database" TEXT NOT NULL,              -- column store database name
+    "table" TEXT NOT NULL,                 -- column store table name
+    schema_id INTEGER,                     -- schema id in confluent wire format, if any
+    avro_schema TEXT NOT NULL              -- JSON serialized Avro schema
+);
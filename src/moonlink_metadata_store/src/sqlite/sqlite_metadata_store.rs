@@ -1,8 +1,10 @@
 use async_trait::async_trait;
 use sqlx::Row;
 
-use crate::base_metadata_store::TableMetadataEntry;
-use crate::base_metadata_store::{MetadataStoreTrait, MOONLINK_METADATA_TABLE, MOONLINK_SCHEMA};
+use crate::base_metadata_store::{AvroSchemaEntry, TableMetadataEntry};
+use crate::base_metadata_store::{
+    MetadataStoreTrait, MOONLINK_AVRO_SCHEMA_TABLE, MOONLINK_METADATA_TABLE, MOONLINK_SCHEMA,
+};
 use crate::config_utils;
 use crate::error::Error;
 use crate::error::Result;
@@ -15,6 +17,8 @@ use moonlink_error::{ErrorStatus, ErrorStruct};
 const METADATA_DATABASE_FILENAME: &str = "moonlink_metadata_store.sqlite";
 /// SQL statements for moonlink metadata table database.
 const CREATE_TABLE_SCHEMA_SQL: &str = include_str!("sql/create_tables.sql");
+/// SQL statements for Avro schema table.
+const CREATE_AVRO_SCHEMA_TABLE_SQL: &str = include_str!("sql/create_avro_schemas_table.sql");
 
 pub struct SqliteMetadataStore {
     /// Database uri.
@@ -119,6 +123,12 @@ impl MetadataStoreTrait for SqliteMetadataStore {
 
     async fn delete_table_metadata(&self, database: &str, table: &str) -> Result<()> {
         let sqlite_conn = SqliteConnWrapper::new(&self.database_uri).await?;
+        let avro_schema_table_exists = utils::table_exists(
+            &sqlite_conn.pool,
+            MOONLINK_SCHEMA,
+            MOONLINK_AVRO_SCHEMA_TABLE,
+        )
+        .await?;
         let mut tx = sqlite_conn.pool.begin().await?;
 
         // Delete from metadata table.
@@ -136,10 +146,91 @@ impl MetadataStoreTrait for SqliteMetadataStore {
             )));
         }
 
+        // Delete Avro schemas, which only exist for tables with Avro schema set.
+        if avro_schema_table_exists {
+            sqlx::query(r#"DELETE FROM avro_schemas WHERE "database" = ? AND "table" = ?"#)
+                .bind(database)
+                .bind(table)
+                .execute(&mut *tx)
+                .await?;
+        }
+
         tx.commit().await?;
 
         Ok(())
     }
+
+    async fn store_avro_schema(
+        &self,
+        database: &str,
+        table: &str,
+        schema_id: Option<u32>,
+        avro_schema: &str,
+    ) -> Result<()> {
+        // Create Avro schema table if it doesn't exist.
+        let sqlite_conn = SqliteConnWrapper::new(&self.database_uri).await?;
+        utils::create_table_if_non_existent(
+            &sqlite_conn.pool,
+            MOONLINK_SCHEMA,
+            MOONLINK_AVRO_SCHEMA_TABLE,
+            CREATE_AVRO_SCHEMA_TABLE_SQL,
+        )
+        .await?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO avro_schemas ("database", "table", schema_id, avro_schema)
+            VALUES (?, ?, ?, ?);
+            "#,
+        )
+        .bind(database)
+        .bind(table)
+        .bind(schema_id.map(i64::from))
+        .bind(avro_schema)
+        .execute(&sqlite_conn.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_avro_schemas(&self, database: &str, table: &str) -> Result<Vec<AvroSchemaEntry>> {
+        let sqlite_conn = SqliteConnWrapper::new(&self.database_uri).await?;
+        if !utils::table_exists(
+            &sqlite_conn.pool,
+            MOONLINK_SCHEMA,
+            MOONLINK_AVRO_SCHEMA_TABLE,
+        )
+        .await?
+        {
+            return Ok(vec![]);
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT schema_id, avro_schema
+            FROM avro_schemas
+            WHERE "database" = ? AND "table" = ?
+            ORDER BY id
+            "#,
+        )
+        .bind(database)
+        .bind(table)
+        .fetch_all(&sqlite_conn.pool)
+        .await?;
+
+        let avro_schemas = rows
+            .into_iter()
+            .map(|row| {
+                let schema_id: Option<i64> = row.get("schema_id");
+                AvroSchemaEntry {
+                    // Schema id is always stored from u32.
+                    schema_id: schema_id.map(|id| id as u32),
+                    avro_schema: row.get("avro_schema"),
+                }
+            })
+            .collect();
+        Ok(avro_schemas)
+    }
 }
 
 impl SqliteMetadataStore {
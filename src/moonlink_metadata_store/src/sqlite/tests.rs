@@ -1,4 +1,4 @@
-use crate::base_metadata_store::MetadataStoreTrait;
+use crate::base_metadata_store::{AvroSchemaEntry, MetadataStoreTrait};
 use crate::sqlite::sqlite_metadata_store::SqliteMetadataStore;
 use moonlink::{
     AccessorConfig, IcebergCatalogConfig, IcebergTableConfig, MoonlinkTableConfig, StorageConfig,
@@ -84,6 +84,7 @@ pub(crate) fn get_moonlink_table_config() -> MoonlinkTableConfig {
                 accessor_config: get_accessor_config(),
             },
         },
+        deltalake_table_config: None,
         wal_table_config: WalConfig::new(wal_accessor, &format!("{DATABASE}.{TABLE}")),
         ..Default::default()
     }
@@ -227,6 +228,35 @@ async fn test_table_metadata_store_and_load_gcs() {
     assert_eq!(entries[0].moonlink_table_config, moonlink_table_config);
 }
 
+#[cfg(feature = "storage-azure")]
+#[tokio::test]
+async fn test_table_metadata_store_and_load_azure() {
+    use crate::test_utils::get_azure_moonlink_table_config;
+    let tmp_dir = tempdir().unwrap();
+    let sqlite_path = get_sqlite_database_filepath(&tmp_dir);
+
+    let metadata_store = SqliteMetadataStore::new(sqlite_path.clone()).await.unwrap();
+    let moonlink_table_config = get_azure_moonlink_table_config(DATABASE, TABLE);
+
+    metadata_store
+        .store_table_metadata(
+            DATABASE,
+            TABLE,
+            SRC_TABLE_NAME,
+            SRC_TABLE_URI,
+            moonlink_table_config.clone(),
+        )
+        .await
+        .unwrap();
+
+    let entries = metadata_store
+        .get_all_table_metadata_entries()
+        .await
+        .unwrap();
+    assert_eq!(entries.len(), 1);
+    assert_eq!(entries[0].moonlink_table_config, moonlink_table_config);
+}
+
 /// Test scenario: store for duplicate table ids.
 #[tokio::test]
 async fn test_table_metadata_store_for_duplicate_tables() {
@@ -312,3 +342,65 @@ async fn test_delete_table_metadata_store() {
     let res = metadata_store.delete_table_metadata(DATABASE, TABLE).await;
     assert!(res.is_err());
 }
+
+/// Test scenario: store Avro schemas, load them in order, and delete them along with table metadata.
+#[tokio::test]
+async fn test_avro_schema_store_and_load() {
+    let tmp_dir = tempdir().unwrap();
+    let sqlite_path = get_sqlite_database_filepath(&tmp_dir);
+
+    let metadata_store = SqliteMetadataStore::new(sqlite_path.clone()).await.unwrap();
+    metadata_store
+        .store_table_metadata(
+            DATABASE,
+            TABLE,
+            SRC_TABLE_NAME,
+            SRC_TABLE_URI,
+            get_moonlink_table_config(),
+        )
+        .await
+        .unwrap();
+
+    // Load before any Avro schema is set.
+    let avro_schemas = metadata_store
+        .get_avro_schemas(DATABASE, TABLE)
+        .await
+        .unwrap();
+    assert!(avro_schemas.is_empty());
+
+    // Store Avro schemas and check load order.
+    let expected_avro_schemas = vec![
+        AvroSchemaEntry {
+            schema_id: None,
+            avro_schema: r#"{"type":"record","name":"t","fields":[{"name":"id","type":"int"}]}"#
+                .to_string(),
+        },
+        AvroSchemaEntry {
+            schema_id: Some(u32::MAX),
+            avro_schema: r#"{"type":"record","name":"t","fields":[{"name":"id","type":"int"},{"name":"name","type":["null","string"],"default":null}]}"#
+                .to_string(),
+        },
+    ];
+    for entry in expected_avro_schemas.iter() {
+        metadata_store
+            .store_avro_schema(DATABASE, TABLE, entry.schema_id, &entry.avro_schema)
+            .await
+            .unwrap();
+    }
+    let avro_schemas = metadata_store
+        .get_avro_schemas(DATABASE, TABLE)
+        .await
+        .unwrap();
+    assert_eq!(avro_schemas, expected_avro_schemas);
+
+    // Avro schemas are deleted along with table metadata.
+    metadata_store
+        .delete_table_metadata(DATABASE, TABLE)
+        .await
+        .unwrap();
+    let avro_schemas = metadata_store
+        .get_avro_schemas(DATABASE, TABLE)
+        .await
+        .unwrap();
+    assert!(avro_schemas.is_empty());
+}
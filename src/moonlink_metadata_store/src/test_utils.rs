@@ -1,4 +1,8 @@
-#[cfg(any(feature = "storage-s3", feature = "storage-gcs"))]
+#[cfg(any(
+    feature = "storage-s3",
+    feature = "storage-gcs",
+    feature = "storage-azure"
+))]
 use moonlink::{
     AccessorConfig, IcebergCatalogConfig, IcebergTableConfig, MoonlinkTableConfig, StorageConfig,
     WalConfig,
@@ -30,6 +34,7 @@ pub fn get_s3_moonlink_table_config(database: &str, table: &str) -> MoonlinkTabl
                 accessor_config: AccessorConfig::new_with_storage_config(iceberg_storage.clone()),
             },
         },
+        deltalake_table_config: None,
         wal_table_config: WalConfig::new(wal_accessor, &format!("{database}.{table}")),
         ..Default::default()
     }
@@ -67,6 +72,39 @@ pub fn get_gcs_moonlink_table_config(database: &str, table: &str) -> MoonlinkTab
                 accessor_config: AccessorConfig::new_with_storage_config(iceberg_storage.clone()),
             },
         },
+        deltalake_table_config: None,
+        wal_table_config: WalConfig::new(wal_accessor, &format!("{database}.{table}")),
+        ..Default::default()
+    }
+}
+
+#[cfg(feature = "storage-azure")]
+pub fn get_azure_moonlink_table_config(database: &str, table: &str) -> MoonlinkTableConfig {
+    let iceberg_storage = StorageConfig::Azblob {
+        account_name: "moonlinkaccount".to_string(),
+        container: "moonlink-iceberg".to_string(),
+        account_key: Some("account-key".to_string()),
+        sas_token: None,
+        endpoint: None,
+    };
+    let wal_storage = StorageConfig::Azblob {
+        account_name: "moonlinkaccount".to_string(),
+        container: "moonlink-wal".to_string(),
+        account_key: None,
+        sas_token: Some("sas-token-wal".to_string()),
+        endpoint: None,
+    };
+    let wal_accessor = AccessorConfig::new_with_storage_config(wal_storage);
+    MoonlinkTableConfig {
+        iceberg_table_config: IcebergTableConfig {
+            namespace: vec!["namespace".to_string()],
+            table_name: "table".to_string(),
+            data_accessor_config: AccessorConfig::new_with_storage_config(iceberg_storage.clone()),
+            metadata_accessor_config: IcebergCatalogConfig::File {
+                accessor_config: AccessorConfig::new_with_storage_config(iceberg_storage.clone()),
+            },
+        },
+        deltalake_table_config: None,
         wal_table_config: WalConfig::new(wal_accessor, &format!("{database}.{table}")),
         ..Default::default()
     }
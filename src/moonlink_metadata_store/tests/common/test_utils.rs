@@ -23,6 +23,7 @@ pub(crate) fn get_moonlink_table_config() -> MoonlinkTableConfig {
                 accessor_config: iceberg_accessor_config,
             },
         },
+        deltalake_table_config: None,
         wal_table_config: WalConfig::new(wal_accessor_config, "dst-database.dst-schema.dst-table"),
         ..Default::default()
     }
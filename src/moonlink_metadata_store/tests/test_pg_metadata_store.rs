@@ -136,6 +136,36 @@ mod tests {
         assert_eq!(entries[0].moonlink_table_config, moonlink_table_config);
     }
 
+    #[cfg(all(feature = "storage-azure", feature = "test-utils"))]
+    #[tokio::test]
+    #[serial]
+    async fn test_table_metadata_store_and_load_azure() {
+        let table_uri = get_table_uri();
+        let _test_environment = TestEnvironment::new(&table_uri).await;
+        let metadata_store = PgMetadataStore::new(table_uri.clone()).unwrap();
+
+        let moonlink_table_config =
+            moonlink_metadata_store::test_utils::get_azure_moonlink_table_config(DATABASE, TABLE);
+
+        metadata_store
+            .store_table_metadata(
+                DATABASE,
+                TABLE,
+                SRC_TABLE_NAME,
+                &table_uri,
+                moonlink_table_config.clone(),
+            )
+            .await
+            .unwrap();
+
+        let entries = metadata_store
+            .get_all_table_metadata_entries()
+            .await
+            .unwrap();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].moonlink_table_config, moonlink_table_config);
+    }
+
     /// Test scenario: load from non-existent schema.
     #[tokio::test]
     #[serial]
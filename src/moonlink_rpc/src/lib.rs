@@ -26,6 +26,7 @@ macro_rules! rpcs {
 }
 
 rpcs! {
+    alter_table(database: String, table: String, columns_to_add: Vec<u8>, columns_to_drop: Vec<String>, columns_to_rename: Vec<(String, String)>) -> ();
     create_snapshot(database: String, table: String, lsn: u64) -> ();
     create_table(database: String, table: String, src: String, src_uri: String, table_config: String) -> ();
     drop_table(database: String, table: String) -> ();
@@ -34,6 +35,7 @@ rpcs! {
     list_tables() -> Vec<Table>;
     load_files(database: String, table: String, files: Vec<String>) -> ();
     optimize_table(database: String, table: String, mode: String) -> ();
+    rotate_master_key(database: String, table: String, master_key_id: String) -> ();
     scan_table_begin(database: String, table: String, lsn: u64) -> Vec<u8>;
     scan_table_end(database: String, table: String) -> ();
 }
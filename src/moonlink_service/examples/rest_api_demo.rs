@@ -25,9 +25,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             rest_api_port: Some(3030),
             tcp_port: None,
             otel_ingestion_api_port: None,
+            otel_grpc_ingestion_api_port: None,
             data_server_uri: None,
             log_directory: None,
             otel_export_target: None,
+            memory_soft_limit_bytes: None,
+            memory_hard_limit_bytes: None,
         })
         .await
         {
@@ -22,6 +22,9 @@ pub enum Error {
     #[error("{0}")]
     Rpc(ErrorStruct),
 
+    #[error("{0}")]
+    Grpc(ErrorStruct),
+
     #[error("{0}")]
     TaskJoin(ErrorStruct),
 
@@ -36,6 +39,9 @@ pub enum Error {
 
     #[error("{0}")]
     OtelInvalidOption(ErrorStruct),
+
+    #[error("{0}")]
+    Parquet(ErrorStruct),
 }
 
 pub type Result<T> = result::Result<T, Error>;
@@ -83,6 +89,16 @@ impl From<ArrowError> for Error {
     }
 }
 
+impl From<parquet::errors::ParquetError> for Error {
+    #[track_caller]
+    fn from(source: parquet::errors::ParquetError) -> Self {
+        Error::Parquet(
+            ErrorStruct::new("Parquet error".to_string(), ErrorStatus::Permanent)
+                .with_source(source),
+        )
+    }
+}
+
 impl From<moonlink_backend::Error> for Error {
     #[track_caller]
     fn from(source: moonlink_backend::Error) -> Self {
@@ -118,3 +134,13 @@ impl From<tokio::task::JoinError> for Error {
         )
     }
 }
+
+impl From<tonic::transport::Error> for Error {
+    #[track_caller]
+    fn from(source: tonic::transport::Error) -> Self {
+        Error::Grpc(
+            ErrorStruct::new("gRPC transport error".to_string(), ErrorStatus::Permanent)
+                .with_source(source),
+        )
+    }
+}
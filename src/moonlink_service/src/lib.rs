@@ -3,11 +3,12 @@ mod logging;
 mod otel;
 pub(crate) mod rest_api;
 mod rpc_server;
+mod scan_encoder;
 
 pub use error::Error;
 pub use error::Result;
 
-use moonlink_backend::MoonlinkBackend;
+use moonlink_backend::{MemoryBudgetConfig, MoonlinkBackend};
 use moonlink_metadata_store::SqliteMetadataStore;
 use otel::service::initialize_opentelemetry_meter_provider;
 use std::net::SocketAddr;
@@ -36,14 +37,20 @@ pub struct ServiceConfig {
     pub data_server_uri: Option<String>,
     /// Used for REST API as ingestion source.
     pub rest_api_port: Option<u16>,
-    /// Used for otel data ingestion.
+    /// Used for otel data ingestion over OTLP/HTTP.
     pub otel_ingestion_api_port: Option<u16>,
+    /// Used for otel data ingestion over OTLP/gRPC.
+    pub otel_grpc_ingestion_api_port: Option<u16>,
     /// Used for moonlink standalone deployment.
     pub tcp_port: Option<u16>,
     /// Log persistence directory.
     pub log_directory: Option<String>,
     /// Otel export target: "stdout", "otel", or None (default).
     pub otel_export_target: Option<String>,
+    /// Total memory usage in bytes for all tables, above which the largest tables are forced to flush; unlimited if unspecified.
+    pub memory_soft_limit_bytes: Option<u64>,
+    /// Total memory usage in bytes for all tables, above which ingestion is paused or rejected; unlimited if unspecified.
+    pub memory_hard_limit_bytes: Option<u64>,
 }
 
 impl ServiceConfig {
@@ -100,10 +107,19 @@ pub async fn start_with_config(config: ServiceConfig) -> Result<()> {
     let sqlite_metadata_accessor = SqliteMetadataStore::new_with_directory(&config.base_path)
         .await
         .unwrap();
-    let mut backend = MoonlinkBackend::new(
+    let memory_budget_config = MemoryBudgetConfig {
+        soft_limit_bytes: config
+            .memory_soft_limit_bytes
+            .unwrap_or(MemoryBudgetConfig::DEFAULT_SOFT_LIMIT_BYTES),
+        hard_limit_bytes: config
+            .memory_hard_limit_bytes
+            .unwrap_or(MemoryBudgetConfig::DEFAULT_HARD_LIMIT_BYTES),
+    };
+    let mut backend = MoonlinkBackend::new_with_memory_budget(
         config.base_path.clone(),
         config.data_server_uri.clone(),
         Box::new(sqlite_metadata_accessor),
+        memory_budget_config,
     )
     .await?;
 
@@ -136,32 +152,32 @@ pub async fn start_with_config(config: ServiceConfig) -> Result<()> {
         (None, None)
     };
 
-    // Optionally start otel HTTP endpoint.
-    let (otel_api_handle, otel_api_shutdown_signal) =
-        if let Some(otel_port) = config.otel_ingestion_api_port {
-            if let Some(rest_port) = config.rest_api_port {
-                let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
-                let backend_clone = backend.clone();
-                let handle = tokio::spawn(async move {
-                    if let Err(e) = otel::service::start_otel_service(
-                        otel_port,
-                        rest_port,
-                        backend_clone,
-                        shutdown_rx,
-                    )
-                    .await
-                    {
-                        error!("OTEL service failed: {}", e);
-                    }
-                    info!("Starting OTLP/HTTP metrics starts at port {otel_port}");
-                });
-                (Some(handle), Some(shutdown_tx))
-            } else {
-                (None, None)
-            }
-        } else {
-            (None, None)
-        };
+    // Optionally start otel HTTP and gRPC endpoints.
+    let otel_enabled =
+        config.otel_ingestion_api_port.is_some() || config.otel_grpc_ingestion_api_port.is_some();
+    let (otel_api_handle, otel_api_shutdown_signal) = match config.rest_api_port {
+        Some(rest_port) if otel_enabled => {
+            let otel_http_port = config.otel_ingestion_api_port;
+            let otel_grpc_port = config.otel_grpc_ingestion_api_port;
+            let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+            let backend_clone = backend.clone();
+            let handle = tokio::spawn(async move {
+                if let Err(e) = otel::service::start_otel_service(
+                    otel_http_port,
+                    otel_grpc_port,
+                    rest_port,
+                    backend_clone,
+                    shutdown_rx,
+                )
+                .await
+                {
+                    error!("OTEL service failed: {}", e);
+                }
+            });
+            (Some(handle), Some(shutdown_tx))
+        }
+        _ => (None, None),
+    };
 
     // Optionally start TCP server.
     let tcp_api_handle = if let Some(port) = config.tcp_port {
@@ -200,7 +216,7 @@ pub async fn start_with_config(config: ServiceConfig) -> Result<()> {
 
     if let Some(handle) = otel_api_handle {
         otel_api_shutdown_signal
-            .expect("OTEL HTTP API shutdown sender supposed to be valid")
+            .expect("OTEL API shutdown sender supposed to be valid")
             .send(())
             .unwrap();
         handle.await?;
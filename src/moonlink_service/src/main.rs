@@ -35,6 +35,9 @@ struct Cli {
     /// Disable standalone deployment.
     #[arg(long)]
     no_otel_api: bool,
+    /// Port for OTLP/gRPC receiver (optional, disabled if unspecified, conventionally 4317).
+    #[arg(long)]
+    otel_grpc_ingestion_port: Option<u16>,
 
     /// IP/port for data server.
     /// For example: http://34.19.1.175:8080.
@@ -48,6 +51,13 @@ struct Cli {
     /// Otel collector endpoint: "stdout", "otel", or None (default).
     #[arg(long)]
     otel_export_target: Option<String>,
+
+    /// Total memory usage in bytes for all tables, above which the largest tables are forced to flush (optional, unlimited by default).
+    #[arg(long)]
+    memory_soft_limit_bytes: Option<u64>,
+    /// Total memory usage in bytes for all tables, above which ingestion is paused or rejected (optional, unlimited by default).
+    #[arg(long)]
+    memory_hard_limit_bytes: Option<u64>,
 }
 
 #[tokio::main]
@@ -74,8 +84,11 @@ pub async fn main() -> Result<()> {
         } else {
             Some(cli.otel_ingestion_port.unwrap_or(DEFAULT_OTEL_PORT))
         },
+        otel_grpc_ingestion_api_port: cli.otel_grpc_ingestion_port,
         log_directory: None,
         otel_export_target: cli.otel_export_target,
+        memory_soft_limit_bytes: cli.memory_soft_limit_bytes,
+        memory_hard_limit_bytes: cli.memory_hard_limit_bytes,
     };
 
     start_with_config(config).await
@@ -1,7 +1,10 @@
+mod grpc_service;
+mod logs_handler;
 pub(crate) mod metric_type;
 mod metrics_handler;
 pub(crate) mod otel_schema;
 pub(crate) mod otel_state;
+mod otel_table_manager;
 pub(crate) mod otel_to_moonlink_pb;
 pub(crate) mod service;
 #[cfg(feature = "otel-integration")]
@@ -9,3 +12,4 @@ pub(crate) mod service;
 mod test;
 #[cfg(test)]
 mod test_utils;
+mod traces_handler;
@@ -0,0 +1,111 @@
+use crate::otel::otel_state::OtelState;
+/// OTLP/gRPC receiver, which shares the same handlers with OTLP/HTTP endpoint.
+use opentelemetry_proto::tonic::collector::logs::v1::logs_service_server::{
+    LogsService, LogsServiceServer,
+};
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_server::{
+    MetricsService, MetricsServiceServer,
+};
+use opentelemetry_proto::tonic::collector::metrics::v1::{
+    ExportMetricsServiceRequest, ExportMetricsServiceResponse,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::trace_service_server::{
+    TraceService, TraceServiceServer,
+};
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
+use tonic::codec::CompressionEncoding;
+use tonic::{Request, Response, Status};
+use tracing::error;
+
+#[derive(Clone)]
+pub(crate) struct OtelGrpcService {
+    state: OtelState,
+}
+
+/// Convert handler result into gRPC response.
+fn to_grpc_response<T>(result: crate::Result<T>) -> Result<Response<T>, Status> {
+    match result {
+        Ok(resp) => Ok(Response::new(resp)),
+        Err(err) => {
+            // Failed otel request won't be processed usually, so to detect errors we log on server side.
+            error!("Failed to process otel ingestion request: {:?}", err);
+            Err(Status::internal(format!(
+                "failed to process otel request: {err}"
+            )))
+        }
+    }
+}
+
+impl OtelGrpcService {
+    pub(crate) fn new(state: OtelState) -> Self {
+        Self { state }
+    }
+
+    pub(crate) fn metrics_server(&self) -> MetricsServiceServer<Self> {
+        MetricsServiceServer::new(self.clone())
+            .accept_compressed(CompressionEncoding::Gzip)
+            .send_compressed(CompressionEncoding::Gzip)
+    }
+
+    pub(crate) fn logs_server(&self) -> LogsServiceServer<Self> {
+        LogsServiceServer::new(self.clone())
+            .accept_compressed(CompressionEncoding::Gzip)
+            .send_compressed(CompressionEncoding::Gzip)
+    }
+
+    pub(crate) fn traces_server(&self) -> TraceServiceServer<Self> {
+        TraceServiceServer::new(self.clone())
+            .accept_compressed(CompressionEncoding::Gzip)
+            .send_compressed(CompressionEncoding::Gzip)
+    }
+}
+
+#[tonic::async_trait]
+impl MetricsService for OtelGrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportMetricsServiceRequest>,
+    ) -> Result<Response<ExportMetricsServiceResponse>, Status> {
+        to_grpc_response(
+            self.state
+                .metrics_handler
+                .handle_request(request.into_inner())
+                .await,
+        )
+    }
+}
+
+#[tonic::async_trait]
+impl LogsService for OtelGrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportLogsServiceRequest>,
+    ) -> Result<Response<ExportLogsServiceResponse>, Status> {
+        to_grpc_response(
+            self.state
+                .logs_handler
+                .handle_request(request.into_inner())
+                .await,
+        )
+    }
+}
+
+#[tonic::async_trait]
+impl TraceService for OtelGrpcService {
+    async fn export(
+        &self,
+        request: Request<ExportTraceServiceRequest>,
+    ) -> Result<Response<ExportTraceServiceResponse>, Status> {
+        to_grpc_response(
+            self.state
+                .traces_handler
+                .handle_request(request.into_inner())
+                .await,
+        )
+    }
+}
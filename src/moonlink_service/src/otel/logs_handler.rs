@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+/// Handler to process logs ingestion.
+use crate::error::Result;
+use crate::otel::otel_schema::otlp_logs_schema;
+use crate::otel::otel_table_manager::{get_service_name, OtelTableManager};
+use crate::otel::otel_to_moonlink_pb;
+
+use opentelemetry_proto::tonic::collector::logs::v1::{
+    ExportLogsServiceRequest, ExportLogsServiceResponse,
+};
+
+/// Database which manages all otel logs tables, each service has its own table.
+const DATABASE: &str = "otel_logs";
+
+#[derive(Clone)]
+pub(crate) struct LogsHandler {
+    /// Manages all logs tables.
+    table_manager: Arc<OtelTableManager>,
+}
+
+impl LogsHandler {
+    pub(crate) async fn new(
+        rest_port: u16,
+        moonlink_backend: Arc<moonlink_backend::MoonlinkBackend>,
+    ) -> Result<Self> {
+        let table_manager = OtelTableManager::new(DATABASE, rest_port, moonlink_backend).await?;
+        Ok(Self {
+            table_manager: Arc::new(table_manager),
+        })
+    }
+
+    /// Handle request for the incoming logs request, log records are routed to tables by their resource service name.
+    pub(crate) async fn handle_request(
+        &self,
+        request: ExportLogsServiceRequest,
+    ) -> Result<ExportLogsServiceResponse> {
+        for rl in request.resource_logs.iter() {
+            let moonlink_row_pbs = otel_to_moonlink_pb::resource_logs_to_moonlink_rows(rl);
+            if moonlink_row_pbs.is_empty() {
+                continue;
+            }
+            let mooncake_table_id = get_service_name(rl.resource.as_ref());
+            self.table_manager
+                .create_table_for_once(mooncake_table_id, otlp_logs_schema)
+                .await?;
+            for cur_row_pb in moonlink_row_pbs.into_iter() {
+                self.table_manager
+                    .insert_row(mooncake_table_id, cur_row_pb)
+                    .await;
+            }
+        }
+        Ok(ExportLogsServiceResponse::default())
+    }
+}
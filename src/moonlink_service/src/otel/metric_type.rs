@@ -1,15 +1,19 @@
 #[derive(Clone, PartialEq, Eq)]
 pub(crate) enum MetricsType {
     Histogram,
+    ExponentialHistogram,
     Gauge,
     Sum,
+    Summary,
 }
 impl std::fmt::Display for MetricsType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let s = match self {
             MetricsType::Histogram => "histogram",
+            MetricsType::ExponentialHistogram => "exponential_histogram",
             MetricsType::Gauge => "gauge",
             MetricsType::Sum => "sum",
+            MetricsType::Summary => "summary",
         };
         write!(f, "{s}")
     }
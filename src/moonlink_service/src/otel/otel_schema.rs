@@ -121,53 +121,66 @@ fn exemplar_struct(ids: &mut i32) -> DataType {
     ]))
 }
 
-fn common_metric_fields(ids: &mut i32) -> Vec<Field> {
+/// Resource and instrumentation scope fields, shared by metrics, logs and traces.
+fn resource_scope_fields(ids: &mut i32) -> Vec<Field> {
     vec![
-        // 0 kind
-        field_with_id("kind", DataType::Utf8, /*nullable=*/ false, ids),
-        // 1 resource_attributes
+        // resource_attributes
         attributes_field("resource_attributes", ids),
-        // 2 resource_entity_refs
+        // resource_entity_refs
         entity_refs_field("resource_entity_refs", ids),
-        // 3 resource_dropped_attributes_count
+        // resource_dropped_attributes_count
         field_with_id(
             "resource_dropped_attributes_count",
             DataType::Int64,
             /*nullable=*/ true,
             ids,
         ),
-        // 4 resource_schema_url
+        // resource_schema_url
         field_with_id(
             "resource_schema_url",
             DataType::Utf8,
             /*nullable=*/ true,
             ids,
         ),
-        // 5 scope_name
+        // scope_name
         field_with_id("scope_name", DataType::Utf8, /*nullable=*/ true, ids),
-        // 6 scope_version
+        // scope_version
         field_with_id(
             "scope_version",
             DataType::Utf8,
             /*nullable=*/ true,
             ids,
         ),
-        // 7 scope_attributes
+        // scope_attributes
         attributes_field("scope_attributes", ids),
-        // 8 scope_dropped_attributes_count
+        // scope_dropped_attributes_count
         field_with_id(
             "scope_dropped_attributes_count",
             DataType::Int64,
             /*nullable=*/ true,
             ids,
         ),
-        // 9 scope_schema_url
+        // scope_schema_url
         field_with_id(
             "scope_schema_url",
             DataType::Utf8,
             /*nullable=*/ true,
             ids,
         ),
+    ]
+}
+
+fn common_metric_fields(ids: &mut i32) -> Vec<Field> {
+    // 0 kind
+    let mut fields = vec![field_with_id(
+        "kind",
+        DataType::Utf8,
+        /*nullable=*/ false,
+        ids,
+    )];
+    // 1..=9 resource and scope
+    fields.extend(resource_scope_fields(ids));
+    fields.extend(vec![
         // 10 metric_name
         field_with_id("metric_name", DataType::Utf8, /*nullable=*/ false, ids),
         // 11 metric_description
@@ -202,7 +215,8 @@ fn common_metric_fields(ids: &mut i32) -> Vec<Field> {
             /*nullable=*/ true,
             ids,
         ),
-    ]
+    ]);
+    fields
 }
 
 fn number_point_fields(ids: &mut i32) -> Vec<Field> {
@@ -263,7 +277,116 @@ fn histogram_point_fields(ids: &mut i32) -> Vec<Field> {
     ]
 }
 
-/// Unified Arrow schema for Gauge / Sum / Histogram rows (one row per datapoint).
+/// Bucket offset and counts for one side (positive or negative) of exponential histogram.
+fn exp_histogram_buckets_fields(prefix: &str, ids: &mut i32) -> Vec<Field> {
+    vec![
+        field_with_id(
+            &format!("{prefix}_offset"),
+            DataType::Int32,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            &format!("{prefix}_bucket_counts"),
+            list_of_with_id("item", DataType::Int64, /*nullable=*/ true, ids),
+            /*nullable=*/ true,
+            ids,
+        ),
+    ]
+}
+
+fn exp_histogram_point_fields(ids: &mut i32) -> Vec<Field> {
+    let mut fields = vec![
+        field_with_id(
+            "exp_hist_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "exp_hist_sum",
+            DataType::Float64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "exp_hist_min",
+            DataType::Float64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "exp_hist_max",
+            DataType::Float64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id("scale", DataType::Int32, /*nullable=*/ true, ids),
+        field_with_id("zero_count", DataType::Int64, /*nullable=*/ true, ids),
+        field_with_id(
+            "zero_threshold",
+            DataType::Float64,
+            /*nullable=*/ true,
+            ids,
+        ),
+    ];
+    fields.extend(exp_histogram_buckets_fields("positive", ids));
+    fields.extend(exp_histogram_buckets_fields("negative", ids));
+    fields.extend(vec![
+        field_with_id(
+            "exp_hist_temporality",
+            DataType::Int32,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "exp_hist_exemplars",
+            list_of_with_id("item", exemplar_struct(ids), /*nullable=*/ true, ids),
+            /*nullable=*/ true,
+            ids,
+        ),
+    ]);
+    fields
+}
+
+/// Quantile value struct: { quantile: Float64, value: Float64 }
+fn value_at_quantile_struct(ids: &mut i32) -> DataType {
+    DataType::Struct(Fields::from(vec![
+        field_with_id("quantile", DataType::Float64, /*nullable=*/ false, ids),
+        field_with_id("value", DataType::Float64, /*nullable=*/ false, ids),
+    ]))
+}
+
+fn summary_point_fields(ids: &mut i32) -> Vec<Field> {
+    vec![
+        field_with_id(
+            "summary_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "summary_sum",
+            DataType::Float64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "quantile_values",
+            list_of_with_id(
+                "item",
+                value_at_quantile_struct(ids),
+                /*nullable=*/ true,
+                ids,
+            ),
+            /*nullable=*/ true,
+            ids,
+        ),
+    ]
+}
+
+/// Unified Arrow schema for metrics rows (one row per datapoint).
+/// All metric types share common and number point fields, and histogram-like types append their own fields.
 #[allow(unused)]
 pub(crate) fn otlp_metrics_gsh_schema(metric_type: &MetricsType) -> Schema {
     let mut ids = 0;
@@ -271,17 +394,266 @@ pub(crate) fn otlp_metrics_gsh_schema(metric_type: &MetricsType) -> Schema {
     fields.extend(common_metric_fields(&mut ids));
 
     fields.extend(number_point_fields(&mut ids));
-    if *metric_type == MetricsType::Histogram {
-        fields.extend(histogram_point_fields(&mut ids));
+    match metric_type {
+        MetricsType::Histogram => fields.extend(histogram_point_fields(&mut ids)),
+        MetricsType::ExponentialHistogram => fields.extend(exp_histogram_point_fields(&mut ids)),
+        MetricsType::Summary => fields.extend(summary_point_fields(&mut ids)),
+        MetricsType::Gauge | MetricsType::Sum => {}
     }
 
     Schema::new(fields)
 }
 
+/// Span event struct: { time_unix_nano, name, attributes, dropped_attributes_count }
+fn span_event_struct(ids: &mut i32) -> DataType {
+    DataType::Struct(Fields::from(vec![
+        field_with_id(
+            "time_unix_nano",
+            DataType::Int64,
+            /*nullable=*/ false,
+            ids,
+        ),
+        field_with_id("name", DataType::Utf8, /*nullable=*/ true, ids),
+        attributes_field("attributes", ids),
+        field_with_id(
+            "dropped_attributes_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            ids,
+        ),
+    ]))
+}
+
+/// Span link struct: { trace_id, span_id, trace_state, attributes, dropped_attributes_count, flags }
+fn span_link_struct(ids: &mut i32) -> DataType {
+    DataType::Struct(Fields::from(vec![
+        field_with_id(
+            "trace_id",
+            DataType::FixedSizeBinary(16),
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id(
+            "span_id",
+            DataType::FixedSizeBinary(8),
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id("trace_state", DataType::Utf8, /*nullable=*/ true, ids),
+        attributes_field("attributes", ids),
+        field_with_id(
+            "dropped_attributes_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            ids,
+        ),
+        field_with_id("flags", DataType::Int64, /*nullable=*/ true, ids),
+    ]))
+}
+
+/// Arrow schema for log records (one row per log record).
+pub(crate) fn otlp_logs_schema() -> Schema {
+    let mut ids = 0;
+    // 0..=8 resource and scope
+    let mut fields = resource_scope_fields(&mut ids);
+    fields.extend(vec![
+        // 9 time_unix_nano
+        field_with_id(
+            "time_unix_nano",
+            DataType::Int64,
+            /*nullable=*/ false,
+            &mut ids,
+        ),
+        // 10 observed_time_unix_nano
+        field_with_id(
+            "observed_time_unix_nano",
+            DataType::Int64,
+            /*nullable=*/ false,
+            &mut ids,
+        ),
+        // 11 severity_number
+        field_with_id(
+            "severity_number",
+            DataType::Int32,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 12 severity_text
+        field_with_id(
+            "severity_text",
+            DataType::Utf8,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 13 body
+        field_with_id(
+            "body",
+            any_value_struct(&mut ids),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 14 attributes
+        attributes_field("attributes", &mut ids),
+        // 15 dropped_attributes_count
+        field_with_id(
+            "dropped_attributes_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 16 flags
+        field_with_id("flags", DataType::Int64, /*nullable=*/ true, &mut ids),
+        // 17 trace_id
+        field_with_id(
+            "trace_id",
+            DataType::FixedSizeBinary(16),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 18 span_id
+        field_with_id(
+            "span_id",
+            DataType::FixedSizeBinary(8),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 19 event_name
+        field_with_id(
+            "event_name",
+            DataType::Utf8,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+    ]);
+    Schema::new(fields)
+}
+
+/// Arrow schema for spans (one row per span), events and links are kept as nested lists.
+pub(crate) fn otlp_traces_schema() -> Schema {
+    let mut ids = 0;
+    // 0..=8 resource and scope
+    let mut fields = resource_scope_fields(&mut ids);
+    fields.extend(vec![
+        // 9 trace_id
+        field_with_id(
+            "trace_id",
+            DataType::FixedSizeBinary(16),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 10 span_id
+        field_with_id(
+            "span_id",
+            DataType::FixedSizeBinary(8),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 11 trace_state
+        field_with_id(
+            "trace_state",
+            DataType::Utf8,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 12 parent_span_id
+        field_with_id(
+            "parent_span_id",
+            DataType::FixedSizeBinary(8),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 13 flags
+        field_with_id("flags", DataType::Int64, /*nullable=*/ true, &mut ids),
+        // 14 name
+        field_with_id("name", DataType::Utf8, /*nullable=*/ false, &mut ids),
+        // 15 kind
+        field_with_id("kind", DataType::Int32, /*nullable=*/ true, &mut ids),
+        // 16 start_time_unix_nano
+        field_with_id(
+            "start_time_unix_nano",
+            DataType::Int64,
+            /*nullable=*/ false,
+            &mut ids,
+        ),
+        // 17 end_time_unix_nano
+        field_with_id(
+            "end_time_unix_nano",
+            DataType::Int64,
+            /*nullable=*/ false,
+            &mut ids,
+        ),
+        // 18 attributes
+        attributes_field("attributes", &mut ids),
+        // 19 dropped_attributes_count
+        field_with_id(
+            "dropped_attributes_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 20 events
+        field_with_id(
+            "events",
+            list_of_with_id(
+                "item",
+                span_event_struct(&mut ids),
+                /*nullable=*/ true,
+                &mut ids,
+            ),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 21 dropped_events_count
+        field_with_id(
+            "dropped_events_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 22 links
+        field_with_id(
+            "links",
+            list_of_with_id(
+                "item",
+                span_link_struct(&mut ids),
+                /*nullable=*/ true,
+                &mut ids,
+            ),
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 23 dropped_links_count
+        field_with_id(
+            "dropped_links_count",
+            DataType::Int64,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 24 status_code
+        field_with_id(
+            "status_code",
+            DataType::Int32,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+        // 25 status_message
+        field_with_id(
+            "status_message",
+            DataType::Utf8,
+            /*nullable=*/ true,
+            &mut ids,
+        ),
+    ]);
+    Schema::new(fields)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::otel::otel_to_moonlink_pb::export_metrics_to_moonlink_rows;
+    use crate::otel::otel_to_moonlink_pb::{
+        export_metrics_to_moonlink_rows, resource_logs_to_moonlink_rows,
+        resource_spans_to_moonlink_rows,
+    };
     use crate::otel::test_utils::*;
     use moonlink::row::proto_to_moonlink_row;
     use moonlink::{
@@ -290,17 +662,17 @@ mod tests {
         StorageConfig, WalConfig, WalManager,
     };
     use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, EntityRef, KeyValue};
+    use opentelemetry_proto::tonic::logs::v1::LogRecord;
     use opentelemetry_proto::tonic::metrics::v1::{
-        metric, AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric,
-        NumberDataPoint, Sum,
+        exponential_histogram_data_point, metric, summary_data_point, AggregationTemporality,
+        ExponentialHistogram, ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint,
+        Metric, NumberDataPoint, Sum, Summary, SummaryDataPoint,
     };
+    use opentelemetry_proto::tonic::trace::v1::{span, Span};
     use tempfile::{tempdir, TempDir};
 
     /// Util function to create a mooncake table with otel schema.
-    async fn create_mooncake_otel_table(
-        table_temp_dir: &TempDir,
-        metric_type: &MetricsType,
-    ) -> MooncakeTable {
+    async fn create_mooncake_otel_table(table_temp_dir: &TempDir, schema: Schema) -> MooncakeTable {
         let table_path = table_temp_dir.path().to_str().unwrap().to_string();
         let iceberg_table_config = IcebergTableConfig::default();
         let table_config = MooncakeTableConfig::new(table_path.clone());
@@ -326,7 +698,7 @@ mod tests {
         let table_filesystem_accessor = Arc::new(FileSystemAccessor::new(accessor_config));
 
         let table = MooncakeTable::new(
-            otlp_metrics_gsh_schema(metric_type),
+            schema,
             /*name=*/ "table".to_string(),
             /*table_id=*/ 0,
             /*base_path=*/ std::path::PathBuf::from(table_path.clone()),
@@ -344,7 +716,11 @@ mod tests {
     #[tokio::test]
     async fn test_gauge_table_creation_ingestion() {
         let table_temp_dir = tempdir().unwrap();
-        let mut table = create_mooncake_otel_table(&table_temp_dir, &MetricsType::Gauge).await;
+        let mut table = create_mooncake_otel_table(
+            &table_temp_dir,
+            otlp_metrics_gsh_schema(&MetricsType::Gauge),
+        )
+        .await;
 
         let dp = NumberDataPoint {
             attributes: vec![kv_str("dp_k", "dp_v")],
@@ -390,7 +766,9 @@ mod tests {
     #[tokio::test]
     async fn test_sum_table_creation_ingestion() {
         let table_temp_dir = tempdir().unwrap();
-        let mut table = create_mooncake_otel_table(&table_temp_dir, &MetricsType::Sum).await;
+        let mut table =
+            create_mooncake_otel_table(&table_temp_dir, otlp_metrics_gsh_schema(&MetricsType::Sum))
+                .await;
 
         let arr_any = any_array(vec![
             AnyValue {
@@ -452,7 +830,11 @@ mod tests {
     #[tokio::test]
     async fn test_histogram_table_creation_ingestion() {
         let table_temp_dir = tempdir().unwrap();
-        let mut table = create_mooncake_otel_table(&table_temp_dir, &MetricsType::Histogram).await;
+        let mut table = create_mooncake_otel_table(
+            &table_temp_dir,
+            otlp_metrics_gsh_schema(&MetricsType::Histogram),
+        )
+        .await;
 
         let dp1 = HistogramDataPoint {
             attributes: vec![kv_str("h", "a")],
@@ -497,4 +879,184 @@ mod tests {
             table.append(cur_row).unwrap();
         }
     }
+
+    #[tokio::test]
+    async fn test_exponential_histogram_table_creation_ingestion() {
+        let table_temp_dir = tempdir().unwrap();
+        let mut table = create_mooncake_otel_table(
+            &table_temp_dir,
+            otlp_metrics_gsh_schema(&MetricsType::ExponentialHistogram),
+        )
+        .await;
+
+        let dp1 = ExponentialHistogramDataPoint {
+            attributes: vec![kv_str("h", "a")],
+            start_time_unix_nano: 1,
+            time_unix_nano: 2,
+            count: 3,
+            sum: Some(4.5),
+            scale: 2,
+            zero_count: 0,
+            positive: Some(exponential_histogram_data_point::Buckets {
+                offset: 1,
+                bucket_counts: vec![1, 2],
+            }),
+            negative: Some(exponential_histogram_data_point::Buckets {
+                offset: 0,
+                bucket_counts: vec![],
+            }),
+            ..Default::default()
+        };
+        // 2nd point without sum and buckets
+        let dp2 = ExponentialHistogramDataPoint {
+            sum: None,
+            positive: None,
+            negative: None,
+            ..dp1.clone()
+        };
+
+        let metric = Metric {
+            name: "latency_exp_hist".into(),
+            description: "".into(),
+            unit: "ms".into(),
+            metadata: vec![],
+            data: Some(metric::Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![dp1, dp2],
+                aggregation_temporality: AggregationTemporality::Cumulative as i32,
+            })),
+        };
+        let req = make_req_with_metrics(
+            vec![metric],
+            /*resource_attrs=*/ vec![],
+            /*resource_entity_refs=*/ vec![],
+            /*scope_name=*/ "scope",
+            /*scope_attrs=*/ vec![],
+        );
+        let row_pbs = export_metrics_to_moonlink_rows(&req);
+        for cur_row_pb in row_pbs.into_iter() {
+            let cur_row = proto_to_moonlink_row(cur_row_pb).unwrap();
+            table.append(cur_row).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_table_creation_ingestion() {
+        let table_temp_dir = tempdir().unwrap();
+        let mut table = create_mooncake_otel_table(
+            &table_temp_dir,
+            otlp_metrics_gsh_schema(&MetricsType::Summary),
+        )
+        .await;
+
+        let dp = SummaryDataPoint {
+            attributes: vec![kv_str("s", "a")],
+            start_time_unix_nano: 1,
+            time_unix_nano: 2,
+            count: 2,
+            sum: 3.0,
+            quantile_values: vec![summary_data_point::ValueAtQuantile {
+                quantile: 0.5,
+                value: 1.0,
+            }],
+            flags: 0,
+        };
+        let metric = Metric {
+            name: "latency_summary".into(),
+            description: "".into(),
+            unit: "ms".into(),
+            metadata: vec![],
+            data: Some(metric::Data::Summary(Summary {
+                data_points: vec![dp],
+            })),
+        };
+        let req = make_req_with_metrics(
+            vec![metric],
+            /*resource_attrs=*/ vec![],
+            /*resource_entity_refs=*/ vec![],
+            /*scope_name=*/ "scope",
+            /*scope_attrs=*/ vec![],
+        );
+        let row_pbs = export_metrics_to_moonlink_rows(&req);
+        for cur_row_pb in row_pbs.into_iter() {
+            let cur_row = proto_to_moonlink_row(cur_row_pb).unwrap();
+            table.append(cur_row).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_logs_table_creation_ingestion() {
+        let table_temp_dir = tempdir().unwrap();
+        let mut table = create_mooncake_otel_table(&table_temp_dir, otlp_logs_schema()).await;
+
+        let log_record = LogRecord {
+            time_unix_nano: 10,
+            observed_time_unix_nano: 20,
+            severity_text: "INFO".into(),
+            body: Some(AnyValue {
+                value: Some(any_value::Value::StringValue("hello".into())),
+            }),
+            attributes: vec![kv_str("k", "v")],
+            trace_id: vec![1; 16],
+            span_id: vec![2; 8],
+            ..Default::default()
+        };
+        // Log record without body and trace context.
+        let empty_log_record = LogRecord::default();
+        let req = make_req_with_logs(
+            vec![log_record, empty_log_record],
+            vec![kv_str("service.name", "svc")],
+            /*scope_name=*/ "scope",
+            /*scope_attrs=*/ vec![],
+        );
+        let row_pbs = resource_logs_to_moonlink_rows(&req.resource_logs[0]);
+        for cur_row_pb in row_pbs.into_iter() {
+            let cur_row = proto_to_moonlink_row(cur_row_pb).unwrap();
+            table.append(cur_row).unwrap();
+        }
+    }
+
+    #[tokio::test]
+    async fn test_traces_table_creation_ingestion() {
+        let table_temp_dir = tempdir().unwrap();
+        let mut table = create_mooncake_otel_table(&table_temp_dir, otlp_traces_schema()).await;
+
+        let root_span = Span {
+            trace_id: vec![1; 16],
+            span_id: vec![2; 8],
+            name: "root".into(),
+            start_time_unix_nano: 1,
+            end_time_unix_nano: 5,
+            events: vec![span::Event {
+                time_unix_nano: 2,
+                name: "event".into(),
+                attributes: vec![kv_i64("n", 1)],
+                dropped_attributes_count: 0,
+            }],
+            ..Default::default()
+        };
+        let child_span = Span {
+            span_id: vec![3; 8],
+            parent_span_id: vec![2; 8],
+            name: "child".into(),
+            events: vec![],
+            links: vec![span::Link {
+                trace_id: vec![4; 16],
+                span_id: vec![5; 8],
+                attributes: vec![kv_bool("sampled", true)],
+                ..Default::default()
+            }],
+            ..root_span.clone()
+        };
+        let req = make_req_with_spans(
+            vec![root_span, child_span],
+            vec![kv_str("service.name", "svc")],
+            /*scope_name=*/ "scope",
+            /*scope_attrs=*/ vec![],
+        );
+        let row_pbs = resource_spans_to_moonlink_rows(&req.resource_spans[0]);
+        for cur_row_pb in row_pbs.into_iter() {
+            let cur_row = proto_to_moonlink_row(cur_row_pb).unwrap();
+            table.append(cur_row).unwrap();
+        }
+    }
 }
@@ -1,4 +1,6 @@
+use crate::otel::logs_handler::LogsHandler;
 use crate::otel::metrics_handler::MetricsHandler;
+use crate::otel::traces_handler::TracesHandler;
 use crate::Result;
 use std::sync::Arc;
 
@@ -8,6 +10,10 @@ use std::sync::Arc;
 pub struct OtelState {
     /// Metrics handler.
     pub(crate) metrics_handler: Arc<MetricsHandler>,
+    /// Logs handler.
+    pub(crate) logs_handler: Arc<LogsHandler>,
+    /// Traces handler.
+    pub(crate) traces_handler: Arc<TracesHandler>,
 }
 
 impl OtelState {
@@ -17,6 +23,13 @@ impl OtelState {
     ) -> Result<Self> {
         let metrics_handler =
             Arc::new(MetricsHandler::new(rest_port, moonlink_backend.clone()).await?);
-        Ok(Self { metrics_handler })
+        let logs_handler = Arc::new(LogsHandler::new(rest_port, moonlink_backend.clone()).await?);
+        let traces_handler =
+            Arc::new(TracesHandler::new(rest_port, moonlink_backend.clone()).await?);
+        Ok(Self {
+            metrics_handler,
+            logs_handler,
+            traces_handler,
+        })
     }
 }
@@ -0,0 +1,163 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+/// Manages auto-created mooncake tables under one database for otel signals, and ingests rows into them via REST API.
+use crate::error::{Error, Result};
+use crate::rest_api::ListTablesResponse;
+use arrow_schema::Schema;
+use moonlink_backend::REST_API_URI;
+use moonlink_proto::moonlink as moonlink_pb;
+use serde_json::json;
+use tokio::sync::Mutex;
+use tracing::error;
+
+pub(crate) struct OtelTableManager {
+    /// Database which all managed tables belong to.
+    database: &'static str,
+    /// IP/port for REST API.
+    rest_addr: String,
+    /// HTTP request client, used to access REST API.
+    rest_client: reqwest::Client,
+    /// All table names.
+    tables: Mutex<HashSet<String>>,
+    /// Moonlink backend.
+    moonlink_backend: Arc<moonlink_backend::MoonlinkBackend>,
+}
+
+impl OtelTableManager {
+    pub(crate) async fn new(
+        database: &'static str,
+        rest_port: u16,
+        moonlink_backend: Arc<moonlink_backend::MoonlinkBackend>,
+    ) -> Result<Self> {
+        let rest_addr = format!("http://0.0.0.0:{rest_port}");
+        let rest_client = reqwest::Client::new();
+        let response = rest_client
+            .get(format!("{rest_addr}/tables"))
+            .header("content-type", "application/json")
+            .send()
+            .await?;
+        // TODO(hjiang): Error propagation.
+        if !response.status().is_success() {
+            return Err(Error::http_error(response.status()));
+        }
+
+        // List all existing tables under the database.
+        let response: ListTablesResponse = response.json().await?;
+        let tables = response
+            .tables
+            .into_iter()
+            .filter(|cur_table_status| cur_table_status.database == database)
+            .map(|cur_table_status| cur_table_status.table)
+            .collect::<HashSet<_>>();
+        Ok(Self {
+            database,
+            rest_addr,
+            rest_client,
+            tables: Mutex::new(tables),
+            moonlink_backend,
+        })
+    }
+
+    /// Create a mooncake table for once, if it hasn't been created.
+    /// Table schema is only built when the table doesn't exist.
+    pub(crate) async fn create_table_for_once<F>(
+        &self,
+        mooncake_table_id: &str,
+        table_schema: F,
+    ) -> Result<()>
+    where
+        F: FnOnce() -> Schema,
+    {
+        let crafted_src_table_name = format!("{}.{mooncake_table_id}", self.database);
+        // Fake REST ingestion.
+        let serialized_table_config = json!({
+            "mooncake": {
+                "append_only": true,
+                "row_identity": "None"
+            }
+        })
+        .to_string();
+
+        // Table creation for duplicate table name leads to error, so intentionally place table creation under critical section.
+        // Performance is not a big concern here, since it only happens when new table data are received.
+        {
+            let mut guard = self.tables.lock().await;
+            if guard.contains(mooncake_table_id) {
+                return Ok(());
+            }
+
+            self.moonlink_backend
+                .create_table(
+                    self.database.to_string(),
+                    mooncake_table_id.to_string(),
+                    crafted_src_table_name,
+                    REST_API_URI.to_string(),
+                    serialized_table_config,
+                    Some(table_schema()),
+                )
+                .await?;
+            assert!(guard.insert(mooncake_table_id.to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Insert one single row via REST API, which handles LSN internally.
+    /// Here we use asynchronous ingestion as best-effort attempt without flush or snapshot semantics.
+    ///
+    /// For any errors encountered during ingestion, simply log and proceed.
+    pub(crate) async fn insert_row(
+        &self,
+        mooncake_table_id: &str,
+        row_pb: moonlink_pb::MoonlinkRow,
+    ) {
+        let mut buf = Vec::new();
+        // Serialization doesn't expect failure.
+        prost::Message::encode(&row_pb, &mut buf).unwrap();
+        let insert_payload = json!({
+            "operation": "insert",
+            "request_mode": "async",
+            "data": buf
+        });
+        let crafted_src_table_name = format!("{}.{mooncake_table_id}", self.database);
+        let response = self
+            .rest_client
+            .post(format!(
+                "{}/ingestpb/{}",
+                self.rest_addr, crafted_src_table_name
+            ))
+            .header("content-type", "application/json")
+            .json(&insert_payload)
+            .send()
+            .await;
+        if response.is_err() {
+            error!("Failed to ingest otel data: {:?}", response.unwrap_err());
+        }
+    }
+}
+
+/// Get string value from otel anyvalue.
+pub(crate) fn anyvalue_as_str(
+    v: &opentelemetry_proto::tonic::common::v1::AnyValue,
+) -> Option<&str> {
+    match &v.value {
+        Some(opentelemetry_proto::tonic::common::v1::any_value::Value::StringValue(s)) => {
+            Some(s.as_str())
+        }
+        _ => None,
+    }
+}
+
+/// Get service name from otel resource, which falls back to "unknown_service" as otel SDKs do.
+pub(crate) fn get_service_name(
+    resource: Option<&opentelemetry_proto::tonic::resource::v1::Resource>,
+) -> &str {
+    resource
+        .into_iter()
+        .flat_map(|resource| resource.attributes.iter())
+        .filter(|attr| attr.key == "service.name")
+        .filter_map(|attr| attr.value.as_ref().and_then(anyvalue_as_str))
+        .next_back()
+        .unwrap_or("unknown_service")
+}
@@ -1,15 +1,26 @@
 use std::collections::HashMap;
 
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
-use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, EntityRef, KeyValue};
+use opentelemetry_proto::tonic::common::v1::{
+    any_value, AnyValue, EntityRef, InstrumentationScope, KeyValue,
+};
+use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs};
 use opentelemetry_proto::tonic::metrics::v1::{
-    exemplar, number_data_point, Exemplar, Gauge, Histogram, HistogramDataPoint, Metric,
-    NumberDataPoint, Sum,
+    exemplar, exponential_histogram_data_point, number_data_point, Exemplar, ExponentialHistogram,
+    ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint, Metric, NumberDataPoint,
+    Sum, Summary, SummaryDataPoint,
 };
+use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::{span, ResourceSpans, Span};
 
 use moonlink_pb::{Array, RowValue};
 use moonlink_proto::moonlink as moonlink_pb;
 
+/// Number of bytes for a valid otel trace id.
+const TRACE_ID_LEN: usize = 16;
+/// Number of bytes for a valid otel span id.
+const SPAN_ID_LEN: usize = 8;
+
 pub fn export_metrics_to_moonlink_rows(
     req: &ExportMetricsServiceRequest,
 ) -> Vec<moonlink_pb::MoonlinkRow> {
@@ -97,8 +108,42 @@ pub fn export_metrics_to_moonlink_rows(
                             ));
                         }
                     }
-                    _ => {
-                        // Extend as needed for other metric types.
+                    Some(
+                        opentelemetry_proto::tonic::metrics::v1::metric::Data::ExponentialHistogram(
+                            ExponentialHistogram {
+                                data_points,
+                                aggregation_temporality,
+                            },
+                        ),
+                    ) => {
+                        let temp = *aggregation_temporality;
+                        for dp in data_points {
+                            rows.push(exp_hist_point_row(
+                                metric,
+                                resource_attrs,
+                                resource_entity_refs,
+                                &scope_name,
+                                scope_attrs,
+                                dp,
+                                temp,
+                            ));
+                        }
+                    }
+                    Some(opentelemetry_proto::tonic::metrics::v1::metric::Data::Summary(
+                        Summary { data_points },
+                    )) => {
+                        for dp in data_points {
+                            rows.push(summary_point_row(
+                                metric,
+                                resource_attrs,
+                                resource_entity_refs,
+                                &scope_name,
+                                scope_attrs,
+                                dp,
+                            ));
+                        }
+                    }
+                    None => {
                         continue;
                     }
                 }
@@ -109,6 +154,40 @@ pub fn export_metrics_to_moonlink_rows(
     rows
 }
 
+/// Convert log records under one resource to moonlink rows, one row per log record.
+pub fn resource_logs_to_moonlink_rows(rl: &ResourceLogs) -> Vec<moonlink_pb::MoonlinkRow> {
+    let mut rows = Vec::new();
+    for sl in &rl.scope_logs {
+        for log_record in &sl.log_records {
+            rows.push(log_record_row(
+                rl.resource.as_ref(),
+                &rl.schema_url,
+                sl.scope.as_ref(),
+                &sl.schema_url,
+                log_record,
+            ));
+        }
+    }
+    rows
+}
+
+/// Convert spans under one resource to moonlink rows, one row per span.
+pub fn resource_spans_to_moonlink_rows(rs: &ResourceSpans) -> Vec<moonlink_pb::MoonlinkRow> {
+    let mut rows = Vec::new();
+    for ss in &rs.scope_spans {
+        for span in &ss.spans {
+            rows.push(span_row(
+                rs.resource.as_ref(),
+                &rs.schema_url,
+                ss.scope.as_ref(),
+                &ss.schema_url,
+                span,
+            ));
+        }
+    }
+    rows
+}
+
 /// Util function to make moonlink struct type.
 fn make_struct(fields: Vec<RowValue>) -> RowValue {
     RowValue {
@@ -216,6 +295,196 @@ fn entityrefs_to_rowvalue_array(entity_refs: &[EntityRef], attrs: &[KeyValue]) -
     RowValue::array(Array { values: out })
 }
 
+/// Util function to convert string to moonlink value, empty string is treated as unset.
+fn non_empty_string(s: &str) -> RowValue {
+    if s.is_empty() {
+        RowValue::null()
+    } else {
+        RowValue::bytes(s.to_string())
+    }
+}
+
+/// Util function to convert trace id or span id to moonlink value.
+/// Ids are stored as fixed size binary, so invalid ones (including unset ones) are stored as null.
+fn fixed_size_id(id: &[u8], len: usize) -> RowValue {
+    if id.len() == len {
+        RowValue::bytes(id.to_vec())
+    } else {
+        RowValue::null()
+    }
+}
+
+/// Convert resource and instrumentation scope into moonlink values, layout matches columns 0..=8 for logs and traces.
+fn resource_scope_values(
+    resource: Option<&Resource>,
+    resource_schema_url: &str,
+    scope: Option<&InstrumentationScope>,
+    scope_schema_url: &str,
+) -> Vec<RowValue> {
+    let resource_attrs = resource.map(|r| r.attributes.as_slice()).unwrap_or(&[]);
+    let resource_entity_refs = resource.map(|r| r.entity_refs.as_slice()).unwrap_or(&[]);
+    vec![
+        // 0 resource_attributes
+        kvs_to_rowvalue_array_anyvalue(resource_attrs),
+        // 1 resource_entity_refs
+        entityrefs_to_rowvalue_array(resource_entity_refs, resource_attrs),
+        // 2 resource_dropped_attributes_count
+        RowValue::int64(resource.map(|r| r.dropped_attributes_count).unwrap_or(0) as i64),
+        // 3 resource_schema_url
+        non_empty_string(resource_schema_url),
+        // 4 scope_name
+        non_empty_string(scope.map(|s| s.name.as_str()).unwrap_or_default()),
+        // 5 scope_version
+        non_empty_string(scope.map(|s| s.version.as_str()).unwrap_or_default()),
+        // 6 scope_attributes
+        kvs_to_rowvalue_array_anyvalue(scope.map(|s| s.attributes.as_slice()).unwrap_or(&[])),
+        // 7 scope_dropped_attributes_count
+        RowValue::int64(scope.map(|s| s.dropped_attributes_count).unwrap_or(0) as i64),
+        // 8 scope_schema_url
+        non_empty_string(scope_schema_url),
+    ]
+}
+
+/// Build a [`MoonlinkRow`] representing a single OpenTelemetry [`LogRecord`].
+fn log_record_row(
+    resource: Option<&Resource>,
+    resource_schema_url: &str,
+    scope: Option<&InstrumentationScope>,
+    scope_schema_url: &str,
+    log_record: &LogRecord,
+) -> moonlink_pb::MoonlinkRow {
+    let mut values = Vec::with_capacity(20);
+    // 0..=8 resource and scope
+    values.extend(resource_scope_values(
+        resource,
+        resource_schema_url,
+        scope,
+        scope_schema_url,
+    ));
+    // 9 time_unix_nano
+    values.push(RowValue::int64(log_record.time_unix_nano as i64));
+    // 10 observed_time_unix_nano
+    values.push(RowValue::int64(log_record.observed_time_unix_nano as i64));
+    // 11 severity_number
+    values.push(RowValue::int32(log_record.severity_number));
+    // 12 severity_text
+    values.push(non_empty_string(&log_record.severity_text));
+    // 13 body
+    values.push(anyvalue_to_struct(log_record.body.as_ref()));
+    // 14 attributes
+    values.push(kvs_to_rowvalue_array_anyvalue(&log_record.attributes));
+    // 15 dropped_attributes_count
+    values.push(RowValue::int64(log_record.dropped_attributes_count as i64));
+    // 16 flags
+    values.push(RowValue::int64(log_record.flags as i64));
+    // 17 trace_id
+    values.push(fixed_size_id(&log_record.trace_id, TRACE_ID_LEN));
+    // 18 span_id
+    values.push(fixed_size_id(&log_record.span_id, SPAN_ID_LEN));
+    // 19 event_name
+    values.push(non_empty_string(&log_record.event_name));
+
+    moonlink_pb::MoonlinkRow { values }
+}
+
+// events => List<Struct{ time_unix_nano, name, attributes, dropped_attributes_count }>
+fn span_events_to_rowvalue_array(events: &[span::Event]) -> RowValue {
+    let values = events
+        .iter()
+        .map(|e| {
+            make_struct(vec![
+                RowValue::int64(e.time_unix_nano as i64),
+                non_empty_string(&e.name),
+                kvs_to_rowvalue_array_anyvalue(&e.attributes),
+                RowValue::int64(e.dropped_attributes_count as i64),
+            ])
+        })
+        .collect();
+    RowValue::array(Array { values })
+}
+
+// links => List<Struct{ trace_id, span_id, trace_state, attributes, dropped_attributes_count, flags }>
+fn span_links_to_rowvalue_array(links: &[span::Link]) -> RowValue {
+    let values = links
+        .iter()
+        .map(|l| {
+            make_struct(vec![
+                fixed_size_id(&l.trace_id, TRACE_ID_LEN),
+                fixed_size_id(&l.span_id, SPAN_ID_LEN),
+                non_empty_string(&l.trace_state),
+                kvs_to_rowvalue_array_anyvalue(&l.attributes),
+                RowValue::int64(l.dropped_attributes_count as i64),
+                RowValue::int64(l.flags as i64),
+            ])
+        })
+        .collect();
+    RowValue::array(Array { values })
+}
+
+/// Build a [`MoonlinkRow`] representing a single OpenTelemetry [`Span`].
+fn span_row(
+    resource: Option<&Resource>,
+    resource_schema_url: &str,
+    scope: Option<&InstrumentationScope>,
+    scope_schema_url: &str,
+    span: &Span,
+) -> moonlink_pb::MoonlinkRow {
+    let mut values = Vec::with_capacity(26);
+    // 0..=8 resource and scope
+    values.extend(resource_scope_values(
+        resource,
+        resource_schema_url,
+        scope,
+        scope_schema_url,
+    ));
+    // 9 trace_id
+    values.push(fixed_size_id(&span.trace_id, TRACE_ID_LEN));
+    // 10 span_id
+    values.push(fixed_size_id(&span.span_id, SPAN_ID_LEN));
+    // 11 trace_state
+    values.push(non_empty_string(&span.trace_state));
+    // 12 parent_span_id (null for root spans)
+    values.push(fixed_size_id(&span.parent_span_id, SPAN_ID_LEN));
+    // 13 flags
+    values.push(RowValue::int64(span.flags as i64));
+    // 14 name
+    values.push(RowValue::bytes(span.name.clone()));
+    // 15 kind
+    values.push(RowValue::int32(span.kind));
+    // 16 start_time_unix_nano
+    values.push(RowValue::int64(span.start_time_unix_nano as i64));
+    // 17 end_time_unix_nano
+    values.push(RowValue::int64(span.end_time_unix_nano as i64));
+    // 18 attributes
+    values.push(kvs_to_rowvalue_array_anyvalue(&span.attributes));
+    // 19 dropped_attributes_count
+    values.push(RowValue::int64(span.dropped_attributes_count as i64));
+    // 20 events
+    values.push(span_events_to_rowvalue_array(&span.events));
+    // 21 dropped_events_count
+    values.push(RowValue::int64(span.dropped_events_count as i64));
+    // 22 links
+    values.push(span_links_to_rowvalue_array(&span.links));
+    // 23 dropped_links_count
+    values.push(RowValue::int64(span.dropped_links_count as i64));
+    // 24 status_code
+    values.push(
+        span.status
+            .as_ref()
+            .map(|s| RowValue::int32(s.code))
+            .unwrap_or_else(RowValue::null),
+    );
+    // 25 status_message
+    values.push(non_empty_string(
+        span.status
+            .as_ref()
+            .map(|s| s.message.as_str())
+            .unwrap_or_default(),
+    ));
+
+    moonlink_pb::MoonlinkRow { values }
+}
+
 // Split number into (int_col, double_col)
 fn number_pair(dp: &NumberDataPoint) -> (RowValue, RowValue) {
     match dp.value.as_ref() {
@@ -267,6 +536,83 @@ fn exemplars_to_rowvalue_array(exemplars: &[Exemplar]) -> RowValue {
     RowValue::array(Array { values: out })
 }
 
+/// Build moonlink values for columns shared by all metric types (0..=16).
+#[allow(clippy::too_many_arguments)]
+fn common_metric_values(
+    kind: &[u8],
+    metric: &Metric,
+    resource_attrs: &[KeyValue],
+    resource_entity_refs: &[EntityRef],
+    scope_name: &str,
+    scope_attrs: &[KeyValue],
+    start_time_unix_nano: u64,
+    time_unix_nano: u64,
+    point_attrs: &[KeyValue],
+) -> Vec<RowValue> {
+    vec![
+        // 0 kind
+        RowValue::bytes(kind.to_vec()),
+        // 1 resource_attributes
+        kvs_to_rowvalue_array_anyvalue(resource_attrs),
+        // 2 resource_entity_refs
+        entityrefs_to_rowvalue_array(resource_entity_refs, resource_attrs),
+        // 3 resource_dropped_attributes_count
+        RowValue::null(),
+        // 4 resource_schema_url
+        RowValue::null(),
+        // 5 scope_name
+        RowValue::bytes(scope_name.to_string()),
+        // 6 scope_version
+        RowValue::null(),
+        // 7 scope_attributes
+        kvs_to_rowvalue_array_anyvalue(scope_attrs),
+        // 8 scope_dropped_attributes_count
+        RowValue::null(),
+        // 9 scope_schema_url
+        RowValue::null(),
+        // 10 metric_name
+        RowValue::bytes(metric.name.clone()),
+        // 11 metric_description
+        RowValue::null(),
+        // 12 metric_unit
+        RowValue::bytes(metric.unit.clone()),
+        // 13 start_time_unix_nano
+        RowValue::int64(start_time_unix_nano as i64),
+        // 14 time_unix_nano
+        RowValue::int64(time_unix_nano as i64),
+        // 15 point_attributes
+        kvs_to_rowvalue_array_anyvalue(point_attrs),
+        // 16 point_dropped_attributes_count
+        RowValue::null(),
+    ]
+}
+
+/// Build moonlink values for number point columns (17..=21), which are all null for histogram-like metrics.
+fn null_number_point_values() -> Vec<RowValue> {
+    vec![
+        // 17 number_int
+        RowValue::null(),
+        // 18 number_double
+        RowValue::null(),
+        // 19 temporality
+        RowValue::null(),
+        // 20 is_monotonic
+        RowValue::null(),
+        // 21 exemplars
+        RowValue::null(),
+    ]
+}
+
+/// Util function to convert bucket counts to moonlink value.
+fn bucket_counts_to_rowvalue_array(bucket_counts: &[u64]) -> RowValue {
+    RowValue::array(Array {
+        values: bucket_counts
+            .iter()
+            .map(|v| RowValue::int64(*v as i64))
+            .collect(),
+    })
+}
+
 /// Build a [`MoonlinkRow`] representing a single numeric metric data point (Gauge or Sum) from an OpenTelemetry [`NumberDataPoint`].
 #[allow(clippy::too_many_arguments)]
 #[allow(clippy::vec_init_then_push)]
@@ -285,43 +631,18 @@ fn number_point_row(
     let (num_i, num_d) = number_pair(dp);
     let mut values = Vec::with_capacity(21);
 
-    // 0 kind
-    values.push(RowValue::bytes(kind.to_vec()));
-    // 1 resource_attributes
-    values.push(kvs_to_rowvalue_array_anyvalue(resource_attrs));
-    // 2 resource_entity_refs
-    values.push(entityrefs_to_rowvalue_array(
-        resource_entity_refs,
+    // 0..=16 common metric columns
+    values.extend(common_metric_values(
+        kind,
+        metric,
         resource_attrs,
+        resource_entity_refs,
+        scope_name,
+        scope_attrs,
+        dp.start_time_unix_nano,
+        dp.time_unix_nano,
+        &dp.attributes,
     ));
-    // 3 resource_dropped_attributes_count
-    values.push(RowValue::null());
-    // 4 resource_schema_url
-    values.push(RowValue::null());
-    // 5 scope_name
-    values.push(RowValue::bytes(scope_name.to_string()));
-    // 6 scope_version
-    values.push(RowValue::null());
-    // 7 scope_attributes
-    values.push(kvs_to_rowvalue_array_anyvalue(scope_attrs));
-    // 8 scope_dropped_attributes_count
-    values.push(RowValue::null());
-    // 9 scope_schema_url
-    values.push(RowValue::null());
-    // 10 metric_name
-    values.push(RowValue::bytes(metric.name.clone()));
-    // 11 metric_description
-    values.push(RowValue::null());
-    // 12 metric_unit
-    values.push(RowValue::bytes(metric.unit.clone()));
-    // 13 start_time_unix_nano
-    values.push(RowValue::int64(dp.start_time_unix_nano as i64));
-    // 14 time_unix_nano
-    values.push(RowValue::int64(dp.time_unix_nano as i64));
-    // 15 point_attributes
-    values.push(kvs_to_rowvalue_array_anyvalue(&dp.attributes));
-    // 16 point_dropped_attributes_count
-    values.push(RowValue::null());
     // 17 number_int
     values.push(num_i);
     // 18 number_double
@@ -349,43 +670,18 @@ fn hist_point_row(
 ) -> moonlink_pb::MoonlinkRow {
     let mut values = Vec::with_capacity(29);
 
-    // 0 kind
-    values.push(RowValue::bytes(b"histogram".to_vec()));
-    // 1 resource_attributes TODO
-    values.push(kvs_to_rowvalue_array_anyvalue(resource_attrs));
-    // 2 resource_entity_refs
-    values.push(entityrefs_to_rowvalue_array(
-        resource_entity_refs,
+    // 0..=16 common metric columns
+    values.extend(common_metric_values(
+        b"histogram",
+        metric,
         resource_attrs,
+        resource_entity_refs,
+        scope_name,
+        scope_attrs,
+        dp.start_time_unix_nano,
+        dp.time_unix_nano,
+        &dp.attributes,
     ));
-    // 3 resource_dropped_attributes_count
-    values.push(RowValue::null());
-    // 4 resource_schema_url
-    values.push(RowValue::null());
-    // 5 scope_name
-    values.push(RowValue::bytes(scope_name.to_string()));
-    // 6 scope_version
-    values.push(RowValue::null());
-    // 7 scope_attributes
-    values.push(kvs_to_rowvalue_array_anyvalue(scope_attrs));
-    // 8 scope_dropped_attributes_count
-    values.push(RowValue::null());
-    // 9 scope_schema_url
-    values.push(RowValue::null());
-    // 10 metric_name
-    values.push(RowValue::bytes(metric.name.clone()));
-    // 11 metric_description
-    values.push(RowValue::null());
-    // 12 metric_unit
-    values.push(RowValue::bytes(metric.unit.clone()));
-    // 13 start_time_unix_nano
-    values.push(RowValue::int64(dp.start_time_unix_nano as i64));
-    // 14 time_unix_nano
-    values.push(RowValue::int64(dp.time_unix_nano as i64));
-    // 15 point_attributes
-    values.push(kvs_to_rowvalue_array_anyvalue(&dp.attributes));
-    // 16 point_dropped_attributes_count
-    values.push(RowValue::null());
     // 17 number_int        (hist => null)
     values.push(RowValue::null());
     // 18 number_double     (hist => null)
@@ -413,15 +709,126 @@ fn hist_point_row(
             .collect(),
     }));
     // 27 bucket_counts (array<int64>)
+    values.push(bucket_counts_to_rowvalue_array(&dp.bucket_counts));
+    // 28 hist_temporality
+    values.push(RowValue::int32(hist_temporality));
+
+    moonlink_pb::MoonlinkRow { values }
+}
+
+// Split exponential histogram buckets into (offset_col, bucket_counts_col)
+fn exp_hist_buckets_pair(
+    buckets: Option<&exponential_histogram_data_point::Buckets>,
+) -> (RowValue, RowValue) {
+    match buckets {
+        Some(b) => (
+            RowValue::int32(b.offset),
+            bucket_counts_to_rowvalue_array(&b.bucket_counts),
+        ),
+        None => (RowValue::null(), RowValue::null()),
+    }
+}
+
+/// Build a [`MoonlinkRow`] representing a single exponential histogram metric data point from an OpenTelemetry [`ExponentialHistogramDataPoint`].
+#[allow(clippy::vec_init_then_push)]
+fn exp_hist_point_row(
+    metric: &Metric,
+    resource_attrs: &[KeyValue],
+    resource_entity_refs: &[EntityRef],
+    scope_name: &str,
+    scope_attrs: &[KeyValue],
+    dp: &ExponentialHistogramDataPoint,
+    exp_hist_temporality: i32,
+) -> moonlink_pb::MoonlinkRow {
+    let mut values = Vec::with_capacity(35);
+
+    // 0..=16 common metric columns
+    values.extend(common_metric_values(
+        b"exponential_histogram",
+        metric,
+        resource_attrs,
+        resource_entity_refs,
+        scope_name,
+        scope_attrs,
+        dp.start_time_unix_nano,
+        dp.time_unix_nano,
+        &dp.attributes,
+    ));
+    // 17..=21 number point columns (exp hist => null)
+    values.extend(null_number_point_values());
+    // 22 exp_hist_count
+    values.push(RowValue::int64(dp.count as i64));
+    // 23 exp_hist_sum
+    values.push(dp.sum.map(RowValue::float64).unwrap_or_else(RowValue::null));
+    // 24 exp_hist_min
+    values.push(dp.min.map(RowValue::float64).unwrap_or_else(RowValue::null));
+    // 25 exp_hist_max
+    values.push(dp.max.map(RowValue::float64).unwrap_or_else(RowValue::null));
+    // 26 scale
+    values.push(RowValue::int32(dp.scale));
+    // 27 zero_count
+    values.push(RowValue::int64(dp.zero_count as i64));
+    // 28 zero_threshold
+    values.push(RowValue::float64(dp.zero_threshold));
+    // 29 positive_offset, 30 positive_bucket_counts
+    let (positive_offset, positive_bucket_counts) = exp_hist_buckets_pair(dp.positive.as_ref());
+    values.push(positive_offset);
+    values.push(positive_bucket_counts);
+    // 31 negative_offset, 32 negative_bucket_counts
+    let (negative_offset, negative_bucket_counts) = exp_hist_buckets_pair(dp.negative.as_ref());
+    values.push(negative_offset);
+    values.push(negative_bucket_counts);
+    // 33 exp_hist_temporality
+    values.push(RowValue::int32(exp_hist_temporality));
+    // 34 exp_hist_exemplars
+    values.push(exemplars_to_rowvalue_array(&dp.exemplars));
+
+    moonlink_pb::MoonlinkRow { values }
+}
+
+/// Build a [`MoonlinkRow`] representing a single summary metric data point from an OpenTelemetry [`SummaryDataPoint`].
+#[allow(clippy::vec_init_then_push)]
+fn summary_point_row(
+    metric: &Metric,
+    resource_attrs: &[KeyValue],
+    resource_entity_refs: &[EntityRef],
+    scope_name: &str,
+    scope_attrs: &[KeyValue],
+    dp: &SummaryDataPoint,
+) -> moonlink_pb::MoonlinkRow {
+    let mut values = Vec::with_capacity(25);
+
+    // 0..=16 common metric columns
+    values.extend(common_metric_values(
+        b"summary",
+        metric,
+        resource_attrs,
+        resource_entity_refs,
+        scope_name,
+        scope_attrs,
+        dp.start_time_unix_nano,
+        dp.time_unix_nano,
+        &dp.attributes,
+    ));
+    // 17..=21 number point columns (summary => null)
+    values.extend(null_number_point_values());
+    // 22 summary_count
+    values.push(RowValue::int64(dp.count as i64));
+    // 23 summary_sum
+    values.push(RowValue::float64(dp.sum));
+    // 24 quantile_values (array<struct{quantile, value}>)
     values.push(RowValue::array(Array {
         values: dp
-            .bucket_counts
+            .quantile_values
             .iter()
-            .map(|v| RowValue::int64(*v as i64))
+            .map(|q| {
+                make_struct(vec![
+                    RowValue::float64(q.quantile),
+                    RowValue::float64(q.value),
+                ])
+            })
             .collect(),
     }));
-    // 28 hist_temporality
-    values.push(RowValue::int32(hist_temporality));
 
     moonlink_pb::MoonlinkRow { values }
 }
@@ -432,10 +839,13 @@ mod tests {
     use crate::otel::test_utils::*;
     use moonlink_pb::{row_value, Array, RowValue, Struct as MlStruct};
     use opentelemetry_proto::tonic::common::v1::{any_value, AnyValue, EntityRef, KeyValue};
+    use opentelemetry_proto::tonic::logs::v1::{LogRecord, SeverityNumber};
     use opentelemetry_proto::tonic::metrics::v1::{
-        metric, AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric,
-        NumberDataPoint, Sum,
+        exponential_histogram_data_point, metric, summary_data_point, AggregationTemporality,
+        ExponentialHistogram, ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint,
+        Metric, NumberDataPoint, Sum, Summary, SummaryDataPoint,
     };
+    use opentelemetry_proto::tonic::trace::v1::{span, status, Span, Status};
 
     fn as_bytes(rv: &RowValue) -> Option<Vec<u8>> {
         match rv.kind.as_ref()? {
@@ -786,4 +1196,259 @@ mod tests {
             assert!(is_null(&r[23])); // sum is null
         }
     }
+
+    #[test]
+    fn test_exponential_histogram_point() {
+        let dp = ExponentialHistogramDataPoint {
+            attributes: vec![kv_str("h", "a")],
+            start_time_unix_nano: 1,
+            time_unix_nano: 2,
+            count: 7,
+            sum: Some(12.5),
+            scale: 3,
+            zero_count: 1,
+            zero_threshold: 0.001,
+            positive: Some(exponential_histogram_data_point::Buckets {
+                offset: -2,
+                bucket_counts: vec![1, 2, 3],
+            }),
+            negative: None,
+            min: Some(-0.5),
+            max: Some(8.0),
+            ..Default::default()
+        };
+        let metric = Metric {
+            name: "latency_exp_hist".into(),
+            description: "".into(),
+            unit: "ms".into(),
+            metadata: vec![],
+            data: Some(metric::Data::ExponentialHistogram(ExponentialHistogram {
+                data_points: vec![dp],
+                aggregation_temporality: AggregationTemporality::Delta as i32,
+            })),
+        };
+        let req = make_req_with_metrics(
+            vec![metric],
+            /*resource_attrs=*/ vec![],
+            /*resource_entity_refs=*/ vec![],
+            /*scope_name=*/ "scope",
+            /*scope_attrs=*/ vec![],
+        );
+
+        let rows = export_metrics_to_moonlink_rows(&req);
+        assert_eq!(rows.len(), 1);
+        let r = &rows[0].values;
+        assert_eq!(
+            r.len(),
+            35,
+            "exponential histogram row should have 35 columns (0..=34)"
+        );
+        assert_eq!(as_bytes(&r[0]).unwrap(), b"exponential_histogram".to_vec());
+        assert_eq!(as_bytes(&r[10]).unwrap(), b"latency_exp_hist".to_vec());
+        assert_eq!(as_i64(&r[13]).unwrap(), 1);
+        assert_eq!(as_i64(&r[14]).unwrap(), 2);
+        // number point columns are null
+        assert!(r[17..=21].iter().all(is_null));
+
+        assert_eq!(as_i64(&r[22]).unwrap(), 7); // count
+        assert!((as_f64(&r[23]).unwrap() - 12.5).abs() < 1e-9); // sum
+        assert!((as_f64(&r[24]).unwrap() + 0.5).abs() < 1e-9); // min
+        assert!((as_f64(&r[25]).unwrap() - 8.0).abs() < 1e-9); // max
+        assert_eq!(as_i32(&r[26]).unwrap(), 3); // scale
+        assert_eq!(as_i64(&r[27]).unwrap(), 1); // zero_count
+        assert!((as_f64(&r[28]).unwrap() - 0.001).abs() < 1e-9); // zero_threshold
+
+        // positive buckets
+        assert_eq!(as_i32(&r[29]).unwrap(), -2);
+        let positive_counts = as_array(&r[30]).unwrap();
+        assert_eq!(
+            positive_counts
+                .values
+                .iter()
+                .map(|v| as_i64(v).unwrap())
+                .collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        // negative buckets are absent
+        assert!(is_null(&r[31]));
+        assert!(is_null(&r[32]));
+
+        assert_eq!(
+            as_i32(&r[33]).unwrap(),
+            AggregationTemporality::Delta as i32
+        );
+        assert!(as_array(&r[34]).unwrap().values.is_empty()); // exemplars
+    }
+
+    #[test]
+    fn test_summary_point() {
+        let dp = SummaryDataPoint {
+            attributes: vec![kv_str("s", "a")],
+            start_time_unix_nano: 1,
+            time_unix_nano: 2,
+            count: 4,
+            sum: 10.0,
+            quantile_values: vec![
+                summary_data_point::ValueAtQuantile {
+                    quantile: 0.5,
+                    value: 2.0,
+                },
+                summary_data_point::ValueAtQuantile {
+                    quantile: 0.99,
+                    value: 4.0,
+                },
+            ],
+            flags: 0,
+        };
+        let metric = Metric {
+            name: "latency_summary".into(),
+            description: "".into(),
+            unit: "ms".into(),
+            metadata: vec![],
+            data: Some(metric::Data::Summary(Summary {
+                data_points: vec![dp],
+            })),
+        };
+        let req = make_req_with_metrics(
+            vec![metric],
+            /*resource_attrs=*/ vec![],
+            /*resource_entity_refs=*/ vec![],
+            /*scope_name=*/ "scope",
+            /*scope_attrs=*/ vec![],
+        );
+
+        let rows = export_metrics_to_moonlink_rows(&req);
+        assert_eq!(rows.len(), 1);
+        let r = &rows[0].values;
+        assert_eq!(r.len(), 25, "summary row should have 25 columns (0..=24)");
+        assert_eq!(as_bytes(&r[0]).unwrap(), b"summary".to_vec());
+        assert_eq!(as_bytes(&r[10]).unwrap(), b"latency_summary".to_vec());
+        assert!(r[17..=21].iter().all(is_null));
+
+        assert_eq!(as_i64(&r[22]).unwrap(), 4); // count
+        assert!((as_f64(&r[23]).unwrap() - 10.0).abs() < 1e-9); // sum
+        let quantiles = as_array(&r[24]).unwrap();
+        assert_eq!(quantiles.values.len(), 2);
+        let q1 = as_struct(&quantiles.values[1]).unwrap();
+        assert!((as_f64(&q1.fields[0]).unwrap() - 0.99).abs() < 1e-9);
+        assert!((as_f64(&q1.fields[1]).unwrap() - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_log_record_row() {
+        let log_record = LogRecord {
+            time_unix_nano: 10,
+            observed_time_unix_nano: 20,
+            severity_number: SeverityNumber::Warn as i32,
+            severity_text: "WARN".into(),
+            body: Some(AnyValue {
+                value: Some(any_value::Value::StringValue("disk almost full".into())),
+            }),
+            attributes: vec![kv_i64("disk_usage", 95)],
+            trace_id: vec![1; 16],
+            // Invalid span id is stored as null.
+            span_id: vec![2; 3],
+            ..Default::default()
+        };
+        let req = make_req_with_logs(
+            vec![log_record],
+            vec![kv_str("service.name", "svc")],
+            "logger",
+            /*scope_attrs=*/ vec![],
+        );
+
+        let rows = resource_logs_to_moonlink_rows(&req.resource_logs[0]);
+        assert_eq!(rows.len(), 1);
+        let r = &rows[0].values;
+        assert_eq!(r.len(), 20, "log row should have 20 columns");
+
+        // resource and scope
+        let res_attrs = as_array(&r[0]).unwrap();
+        assert_eq!(res_attrs.values.len(), 1);
+        assert_eq!(as_bytes(&r[4]).unwrap(), b"logger".to_vec());
+        assert!(is_null(&r[5])); // scope_version
+
+        // log record
+        assert_eq!(as_i64(&r[9]).unwrap(), 10);
+        assert_eq!(as_i64(&r[10]).unwrap(), 20);
+        assert_eq!(as_i32(&r[11]).unwrap(), SeverityNumber::Warn as i32);
+        assert_eq!(as_bytes(&r[12]).unwrap(), b"WARN".to_vec());
+        assert!(any_is_string_bytes(&r[13], b"disk almost full"));
+        let attrs = as_array(&r[14]).unwrap();
+        assert_eq!(attrs.values.len(), 1);
+        assert_eq!(as_bytes(&r[17]).unwrap(), vec![1; 16]);
+        assert!(is_null(&r[18]));
+        assert!(is_null(&r[19])); // event_name
+    }
+
+    #[test]
+    fn test_span_row_with_events_and_links() {
+        let span = Span {
+            trace_id: vec![1; 16],
+            span_id: vec![2; 8],
+            name: "GET /tables".into(),
+            kind: span::SpanKind::Server as i32,
+            start_time_unix_nano: 100,
+            end_time_unix_nano: 200,
+            attributes: vec![kv_str("http.method", "GET")],
+            events: vec![span::Event {
+                time_unix_nano: 150,
+                name: "cache_miss".into(),
+                attributes: vec![kv_bool("retry", true)],
+                dropped_attributes_count: 0,
+            }],
+            links: vec![span::Link {
+                trace_id: vec![3; 16],
+                span_id: vec![4; 8],
+                ..Default::default()
+            }],
+            status: Some(Status {
+                message: "timeout".into(),
+                code: status::StatusCode::Error as i32,
+            }),
+            ..Default::default()
+        };
+        let req = make_req_with_spans(
+            vec![span],
+            vec![kv_str("service.name", "svc")],
+            "tracer",
+            /*scope_attrs=*/ vec![],
+        );
+
+        let rows = resource_spans_to_moonlink_rows(&req.resource_spans[0]);
+        assert_eq!(rows.len(), 1);
+        let r = &rows[0].values;
+        assert_eq!(r.len(), 26, "span row should have 26 columns");
+
+        assert_eq!(as_bytes(&r[4]).unwrap(), b"tracer".to_vec());
+        assert_eq!(as_bytes(&r[9]).unwrap(), vec![1; 16]);
+        assert_eq!(as_bytes(&r[10]).unwrap(), vec![2; 8]);
+        assert!(is_null(&r[12])); // root span has no parent
+        assert_eq!(as_bytes(&r[14]).unwrap(), b"GET /tables".to_vec());
+        assert_eq!(as_i32(&r[15]).unwrap(), span::SpanKind::Server as i32);
+        assert_eq!(as_i64(&r[16]).unwrap(), 100);
+        assert_eq!(as_i64(&r[17]).unwrap(), 200);
+
+        // events
+        let events = as_array(&r[20]).unwrap();
+        assert_eq!(events.values.len(), 1);
+        let event = as_struct(&events.values[0]).unwrap();
+        assert_eq!(as_i64(&event.fields[0]).unwrap(), 150);
+        assert_eq!(as_bytes(&event.fields[1]).unwrap(), b"cache_miss".to_vec());
+        let event_attrs = as_array(&event.fields[2]).unwrap();
+        let ea0 = as_struct(&event_attrs.values[0]).unwrap();
+        assert!(any_get_bool(&ea0.fields[1]).unwrap());
+
+        // links
+        let links = as_array(&r[22]).unwrap();
+        assert_eq!(links.values.len(), 1);
+        let link = as_struct(&links.values[0]).unwrap();
+        assert_eq!(as_bytes(&link.fields[0]).unwrap(), vec![3; 16]);
+        assert_eq!(as_bytes(&link.fields[1]).unwrap(), vec![4; 8]);
+        assert!(is_null(&link.fields[2])); // trace_state
+
+        // status
+        assert_eq!(as_i32(&r[24]).unwrap(), status::StatusCode::Error as i32);
+        assert_eq!(as_bytes(&r[25]).unwrap(), b"timeout".to_vec());
+    }
 }
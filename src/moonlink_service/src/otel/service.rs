@@ -1,3 +1,4 @@
+use crate::otel::grpc_service::OtelGrpcService;
 use crate::otel::otel_state::OtelState;
 use crate::{Error, Result};
 use axum::error_handling::HandleErrorLayer;
@@ -13,14 +14,18 @@ use axum::{
 use moonlink_error::{ErrorStatus, ErrorStruct};
 use opentelemetry::global;
 use opentelemetry_otlp::{Protocol, WithExportConfig};
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
 use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
 use prost::Message;
+use std::net::SocketAddr;
 use std::sync::Arc;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, watch};
 use tower::timeout::TimeoutLayer;
 use tower::{BoxError, ServiceBuilder};
 use tower_http::cors::{Any, CorsLayer};
+use tower_http::decompression::RequestDecompressionLayer;
 use tracing::{error, info};
 
 /// Default timeout for otel API calls.
@@ -49,7 +54,11 @@ pub fn create_otel_router(state: OtelState) -> Router {
 
     Router::new()
         .route("/v1/metrics", post(handle_metrics))
+        .route("/v1/logs", post(handle_logs))
+        .route("/v1/traces", post(handle_traces))
         .with_state(state)
+        // OTLP exporters could send gzip-compressed payload with `Content-Encoding: gzip`.
+        .layer(RequestDecompressionLayer::new())
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
@@ -59,24 +68,60 @@ pub fn create_otel_router(state: OtelState) -> Router {
         .layer(timeout_layer)
 }
 
+/// Wait until shutdown is requested.
+async fn wait_for_shutdown(mut shutdown_rx: watch::Receiver<bool>) {
+    // Sender dropped also means shutdown.
+    let _ = shutdown_rx.wait_for(|shutdown| *shutdown).await;
+}
+
+/// Start OTLP/HTTP and (optionally) OTLP/gRPC servers, which share the same otel state.
 pub async fn start_otel_service(
-    otel_port: u16,
+    otel_http_port: Option<u16>,
+    otel_grpc_port: Option<u16>,
     rest_port: u16,
     moonlink_backend: Arc<moonlink_backend::MoonlinkBackend>,
     shutdown_signal: oneshot::Receiver<()>,
 ) -> Result<()> {
     let otel_state = OtelState::new(rest_port, moonlink_backend).await?;
-    let app = create_otel_router(otel_state);
-    let otel_addr = format!("0.0.0.0:{otel_port}");
-    info!("Starting otel API server on {}", otel_addr);
 
-    let listener = tokio::net::TcpListener::bind(&otel_addr).await?;
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async {
-            shutdown_signal.await.ok();
-        })
-        .await?;
+    // Fan out shutdown signal to all servers.
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    tokio::spawn(async move {
+        shutdown_signal.await.ok();
+        let _ = shutdown_tx.send(true);
+    });
+
+    let http_server = async {
+        let Some(otel_port) = otel_http_port else {
+            return Ok(());
+        };
+        let app = create_otel_router(otel_state.clone());
+        let otel_addr = format!("0.0.0.0:{otel_port}");
+        info!("Starting OTLP/HTTP server on {}", otel_addr);
+        let listener = tokio::net::TcpListener::bind(&otel_addr).await?;
+        axum::serve(listener, app)
+            .with_graceful_shutdown(wait_for_shutdown(shutdown_rx.clone()))
+            .await?;
+        Ok::<(), Error>(())
+    };
+
+    let grpc_server = async {
+        let Some(otel_grpc_port) = otel_grpc_port else {
+            return Ok(());
+        };
+        let grpc_service = OtelGrpcService::new(otel_state.clone());
+        let otel_grpc_addr = SocketAddr::from(([0, 0, 0, 0], otel_grpc_port));
+        info!("Starting OTLP/gRPC server on {}", otel_grpc_addr);
+        tonic::transport::Server::builder()
+            .add_service(grpc_service.metrics_server())
+            .add_service(grpc_service.logs_server())
+            .add_service(grpc_service.traces_server())
+            .serve_with_shutdown(otel_grpc_addr, wait_for_shutdown(shutdown_rx.clone()))
+            .await?;
+        Ok::<(), Error>(())
+    };
 
+    tokio::try_join!(http_server, grpc_server)?;
     Ok(())
 }
 
@@ -116,38 +161,69 @@ pub(crate) fn initialize_opentelemetry_meter_provider(target: String) -> Result<
     Ok(())
 }
 
+type OtelResponse = (StatusCode, [(header::HeaderName, &'static str); 1], Vec<u8>);
+
+/// Encode the handler result into otel HTTP response.
+fn to_otel_response<T: Message>(result: Result<T>) -> OtelResponse {
+    match result {
+        Ok(resp) => {
+            let bytes = resp.encode_to_vec();
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/x-protobuf")],
+                bytes,
+            )
+        }
+        // TODO(hjiang): Better error propagation.
+        Err(err) => {
+            // Different from general user-facing requests, failed otel request won't be processed usually, so to detect errors we log on server side.
+            error!("Failed to process otel ingestion request: {:?}", err);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                [(header::CONTENT_TYPE, "text/plain")],
+                format!("protobuf decode failed: {err}").into_bytes(),
+            )
+        }
+    }
+}
+
+fn decode_error_response(err: prost::DecodeError) -> OtelResponse {
+    (
+        StatusCode::BAD_REQUEST,
+        [(header::CONTENT_TYPE, "text/plain")],
+        format!("protobuf decode failed: {err}").into_bytes(),
+    )
+}
+
 async fn handle_metrics(
     State(state): State<OtelState>,
     _headers: HeaderMap,
     body: Bytes,
-) -> (StatusCode, [(header::HeaderName, &'static str); 1], Vec<u8>) {
+) -> OtelResponse {
     match ExportMetricsServiceRequest::decode(body) {
-        Ok(req) => {
-            match state.metrics_handler.handle_request(req).await {
-                Ok(resp) => {
-                    let bytes = resp.encode_to_vec();
-                    (
-                        StatusCode::OK,
-                        [(header::CONTENT_TYPE, "application/x-protobuf")],
-                        bytes,
-                    )
-                }
-                // TODO(hjiang): Better error propagation.
-                Err(err) => {
-                    // Different from general user-facing requests, failed otel request won't be processed usually, so to detect errors we log on server side.
-                    error!("Failed to process otel ingestion request: {:?}", err);
-                    (
-                        StatusCode::INTERNAL_SERVER_ERROR,
-                        [(header::CONTENT_TYPE, "text/plain")],
-                        format!("protobuf decode failed: {err}").into_bytes(),
-                    )
-                }
-            }
-        }
-        Err(e) => (
-            StatusCode::BAD_REQUEST,
-            [(header::CONTENT_TYPE, "text/plain")],
-            format!("protobuf decode failed: {e}").into_bytes(),
-        ),
+        Ok(req) => to_otel_response(state.metrics_handler.handle_request(req).await),
+        Err(e) => decode_error_response(e),
+    }
+}
+
+async fn handle_logs(
+    State(state): State<OtelState>,
+    _headers: HeaderMap,
+    body: Bytes,
+) -> OtelResponse {
+    match ExportLogsServiceRequest::decode(body) {
+        Ok(req) => to_otel_response(state.logs_handler.handle_request(req).await),
+        Err(e) => decode_error_response(e),
+    }
+}
+
+async fn handle_traces(
+    State(state): State<OtelState>,
+    _headers: HeaderMap,
+    body: Bytes,
+) -> OtelResponse {
+    match ExportTraceServiceRequest::decode(body) {
+        Ok(req) => to_otel_response(state.traces_handler.handle_request(req).await),
+        Err(e) => decode_error_response(e),
     }
 }
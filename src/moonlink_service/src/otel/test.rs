@@ -1,8 +1,20 @@
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use opentelemetry::{global, KeyValue};
 use opentelemetry_otlp::{Protocol, WithExportConfig};
+use opentelemetry_proto::tonic::collector::metrics::v1::metrics_service_client::MetricsServiceClient;
+use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::metrics::v1::{
+    metric, number_data_point, Gauge, Metric, NumberDataPoint,
+};
 use opentelemetry_sdk::metrics::{PeriodicReader, SdkMeterProvider};
+use prost::Message;
+use serial_test::serial;
+use std::io::Write;
+use tonic::codec::CompressionEncoding;
 use tracing_subscriber::EnvFilter;
 
+use crate::otel::test_utils::{kv_str, make_req_with_metrics};
 use crate::start_with_config;
 use crate::test_guard::TestGuard;
 use crate::test_utils::*;
@@ -10,8 +22,40 @@ use tracing::error;
 
 /// Default HTTP opentelemetry endpoint.
 const DEFAULT_HTTP_OTEL_ENDPOINT: &str = "http://127.0.0.1:3435/v1/metrics";
+/// Default gRPC opentelemetry endpoint.
+const DEFAULT_GRPC_OTEL_ENDPOINT: &str =
+    const_format::formatcp!("http://127.0.0.1:{}", OTEL_GRPC_API_PORT);
+
+/// Test util function to create a metrics export request routed to the given mooncake table.
+fn create_gauge_export_request(mooncake_table_id: &str) -> ExportMetricsServiceRequest {
+    let dp = NumberDataPoint {
+        attributes: vec![kv_str("moonlink.mooncake_table_id", mooncake_table_id)],
+        start_time_unix_nano: 1,
+        time_unix_nano: 2,
+        value: Some(number_data_point::Value::AsInt(10)),
+        exemplars: vec![],
+        flags: 0,
+    };
+    let metric = Metric {
+        name: "test_gauge".into(),
+        description: "".into(),
+        unit: "unit".into(),
+        metadata: vec![],
+        data: Some(metric::Data::Gauge(Gauge {
+            data_points: vec![dp],
+        })),
+    };
+    make_req_with_metrics(
+        vec![metric],
+        vec![kv_str("service.name", "test_service")],
+        /*resource_entity_refs=*/ vec![],
+        /*scope_name=*/ "scope",
+        /*scope_attrs=*/ vec![],
+    )
+}
 
 #[tokio::test(flavor = "multi_thread")]
+#[serial]
 async fn test_opentelemetry_export() {
     let _guard = TestGuard::new(&get_moonlink_backend_dir());
     let config = get_service_config();
@@ -61,3 +105,54 @@ async fn test_opentelemetry_export() {
         error!("Failed to shutdown provider: {:?}", e);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_otlp_grpc_export_with_gzip() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    let client = loop {
+        match MetricsServiceClient::connect(DEFAULT_GRPC_OTEL_ENDPOINT).await {
+            Ok(client) => break client,
+            Err(_) => tokio::time::sleep(std::time::Duration::from_millis(100)).await,
+        }
+    };
+    let mut client = client
+        .send_compressed(CompressionEncoding::Gzip)
+        .accept_compressed(CompressionEncoding::Gzip);
+    client
+        .export(create_gauge_export_request("grpc_id"))
+        .await
+        .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[serial]
+async fn test_otlp_http_export_with_gzip() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    let payload = create_gauge_export_request("http_id").encode_to_vec();
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&payload).unwrap();
+    let compressed_payload = encoder.finish().unwrap();
+
+    let response = reqwest::Client::new()
+        .post(DEFAULT_HTTP_OTEL_ENDPOINT)
+        .header("content-type", "application/x-protobuf")
+        .header("content-encoding", "gzip")
+        .body(compressed_payload)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::OK);
+}
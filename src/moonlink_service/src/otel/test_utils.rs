@@ -1,10 +1,14 @@
 /// Test util function to otel requests.
+use opentelemetry_proto::tonic::collector::logs::v1::ExportLogsServiceRequest;
 use opentelemetry_proto::tonic::collector::metrics::v1::ExportMetricsServiceRequest;
+use opentelemetry_proto::tonic::collector::trace::v1::ExportTraceServiceRequest;
 use opentelemetry_proto::tonic::common::v1::{
     any_value, AnyValue, EntityRef, InstrumentationScope, KeyValue,
 };
+use opentelemetry_proto::tonic::logs::v1::{LogRecord, ResourceLogs, ScopeLogs};
 use opentelemetry_proto::tonic::metrics::v1::{Metric, ResourceMetrics, ScopeMetrics};
 use opentelemetry_proto::tonic::resource::v1::Resource;
+use opentelemetry_proto::tonic::trace::v1::{ResourceSpans, ScopeSpans, Span};
 
 pub(crate) fn kv_str(key: &str, val: &str) -> KeyValue {
     KeyValue {
@@ -74,3 +78,59 @@ pub(crate) fn make_req_with_metrics(
         }],
     }
 }
+
+pub(crate) fn make_req_with_logs(
+    log_records: Vec<LogRecord>,
+    resource_attrs: Vec<KeyValue>,
+    scope_name: &str,
+    scope_attrs: Vec<KeyValue>,
+) -> ExportLogsServiceRequest {
+    ExportLogsServiceRequest {
+        resource_logs: vec![ResourceLogs {
+            resource: Some(Resource {
+                attributes: resource_attrs,
+                dropped_attributes_count: 0,
+                entity_refs: vec![],
+            }),
+            scope_logs: vec![ScopeLogs {
+                scope: Some(InstrumentationScope {
+                    name: scope_name.to_string(),
+                    version: "".into(),
+                    attributes: scope_attrs,
+                    dropped_attributes_count: 0,
+                }),
+                log_records,
+                schema_url: "".into(),
+            }],
+            schema_url: "".into(),
+        }],
+    }
+}
+
+pub(crate) fn make_req_with_spans(
+    spans: Vec<Span>,
+    resource_attrs: Vec<KeyValue>,
+    scope_name: &str,
+    scope_attrs: Vec<KeyValue>,
+) -> ExportTraceServiceRequest {
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(Resource {
+                attributes: resource_attrs,
+                dropped_attributes_count: 0,
+                entity_refs: vec![],
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: scope_name.to_string(),
+                    version: "".into(),
+                    attributes: scope_attrs,
+                    dropped_attributes_count: 0,
+                }),
+                spans,
+                schema_url: "".into(),
+            }],
+            schema_url: "".into(),
+        }],
+    }
+}
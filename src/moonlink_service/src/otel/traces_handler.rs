@@ -0,0 +1,55 @@
+use std::sync::Arc;
+
+/// Handler to process traces ingestion.
+use crate::error::Result;
+use crate::otel::otel_schema::otlp_traces_schema;
+use crate::otel::otel_table_manager::{get_service_name, OtelTableManager};
+use crate::otel::otel_to_moonlink_pb;
+
+use opentelemetry_proto::tonic::collector::trace::v1::{
+    ExportTraceServiceRequest, ExportTraceServiceResponse,
+};
+
+/// Database which manages all otel traces tables, each service has its own table.
+const DATABASE: &str = "otel_traces";
+
+#[derive(Clone)]
+pub(crate) struct TracesHandler {
+    /// Manages all traces tables.
+    table_manager: Arc<OtelTableManager>,
+}
+
+impl TracesHandler {
+    pub(crate) async fn new(
+        rest_port: u16,
+        moonlink_backend: Arc<moonlink_backend::MoonlinkBackend>,
+    ) -> Result<Self> {
+        let table_manager = OtelTableManager::new(DATABASE, rest_port, moonlink_backend).await?;
+        Ok(Self {
+            table_manager: Arc::new(table_manager),
+        })
+    }
+
+    /// Handle request for the incoming traces request, spans are routed to tables by their resource service name.
+    pub(crate) async fn handle_request(
+        &self,
+        request: ExportTraceServiceRequest,
+    ) -> Result<ExportTraceServiceResponse> {
+        for rs in request.resource_spans.iter() {
+            let moonlink_row_pbs = otel_to_moonlink_pb::resource_spans_to_moonlink_rows(rs);
+            if moonlink_row_pbs.is_empty() {
+                continue;
+            }
+            let mooncake_table_id = get_service_name(rs.resource.as_ref());
+            self.table_manager
+                .create_table_for_once(mooncake_table_id, otlp_traces_schema)
+                .await?;
+            for cur_row_pb in moonlink_row_pbs.into_iter() {
+                self.table_manager
+                    .insert_row(mooncake_table_id, cur_row_pb)
+                    .await;
+            }
+        }
+        Ok(ExportTraceServiceResponse::default())
+    }
+}
@@ -1,25 +1,40 @@
+use crate::scan_encoder::{
+    encode_table_scan, ScanOutputFormat, ARROW_STREAM_CONTENT_TYPE, NDJSON_CONTENT_TYPE,
+    PARQUET_CONTENT_TYPE,
+};
 use apache_avro::Schema as AvroSchema;
 use arrow_ipc::writer::StreamWriter;
 use axum::{
+    body::Body,
     error_handling::HandleErrorLayer,
-    extract::{Path, State},
-    http::{Method, StatusCode},
+    extract::{Path, Query, State},
+    http::{header, HeaderMap, Method, StatusCode},
+    response::sse::{Event, KeepAlive, Sse},
     response::{Json, Response},
-    routing::{delete, get, post},
+    routing::{delete, get, patch, post},
     BoxError, Router,
 };
+use futures::stream::{self, Stream, StreamExt};
+use moonlink::row::IdentityProp;
 use moonlink::StorageConfig;
+use moonlink_backend::change_feed::CommittedChanges;
+use moonlink_backend::table_scan::{ScanOptions, ScanPredicate};
 use moonlink_backend::{table_config::TableConfig, table_status::TableStatus};
 use moonlink_backend::{
-    EventRequest, FileEventOperation, FileEventRequest, FlushRequest, IngestRequestPayload,
-    RowEventOperation, RowEventRequest, SnapshotRequest, REST_API_URI,
+    BatchRowEventRequest, BatchRowOperation, EventRequest, FileEventOperation, FileEventRequest,
+    FlushRequest, IngestRequestPayload, RowEventOperation, RowEventRequest, SnapshotRequest,
+    REST_API_URI,
 };
 use moonlink_connectors::rest_ingest::avro_converter::convert_avro_to_arrow_schema;
+use moonlink_connectors::rest_ingest::schema_inference::{
+    get_initial_inferred_schema, is_schema_inferred,
+};
 use moonlink_connectors::rest_ingest::schema_util::{build_arrow_schema, FieldSchema};
 use moonlink_error::ErrorStatus;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::io::Cursor;
+use std::str::FromStr;
 use std::sync::Arc;
 use std::time::SystemTime;
 use tokio::sync::RwLock;
@@ -37,8 +52,8 @@ const DEFAULT_REST_TIMEOUT: std::time::Duration = std::time::Duration::from_secs
 pub struct ApiState {
     /// Reference to the backend for table operations
     pub backend: Arc<moonlink_backend::MoonlinkBackend>,
-    /// Maps from source table name to schema id.
-    pub kafka_schema_id_cache: Arc<RwLock<HashMap<String, u64>>>,
+    /// Maps from source table name to registered schema ids.
+    pub kafka_schema_id_cache: Arc<RwLock<HashMap<String, HashSet<u64>>>>,
 }
 
 impl ApiState {
@@ -101,6 +116,11 @@ pub struct CreateTableRequest {
     #[serde(rename = "avro_schema")]
     pub avro_schema: Option<serde_json::Value>,
 
+    /// Whether to infer table schema from ingested JSON payloads, which requires neither schema nor avro schema provided.
+    #[serde(rename = "infer_schema")]
+    #[serde(default)]
+    pub infer_schema: bool,
+
     #[serde(rename = "table_config")]
     pub table_config: TableConfig,
 }
@@ -138,6 +158,25 @@ pub struct SetAvroSchemaRequest {
     pub schema_id: u64,
 }
 
+/// Wire format for kafka ingestion payload.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum KafkaWireFormat {
+    /// Avro datum written with the table's schema, with no header.
+    #[default]
+    Raw,
+    /// Confluent wire format, which prefixes avro datum with magic byte and 4-byte schema id.
+    Confluent,
+}
+
+/// Query parameters for kafka ingestion.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KafkaIngestParams {
+    #[serde(rename = "wire_format")]
+    #[serde(default)]
+    pub wire_format: KafkaWireFormat,
+}
+
 /// Response structure for kafka schema creation.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SetAvroSchemaResponse {
@@ -207,6 +246,103 @@ pub struct DropTableRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct DropTableResponse {}
 
+/// ====================
+/// Alter table schema
+/// ====================
+///
+/// Column to rename.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RenameColumn {
+    #[serde(rename = "from")]
+    pub from: String,
+
+    #[serde(rename = "to")]
+    pub to: String,
+}
+
+/// Request structure for table schema alteration.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AlterTableSchemaRequest {
+    #[serde(rename = "database")]
+    pub database: String,
+
+    #[serde(rename = "table")]
+    pub table: String,
+
+    /// Columns to append at the end of the table, which must be nullable.
+    #[serde(rename = "add_columns")]
+    #[serde(default)]
+    pub add_columns: Vec<FieldSchema>,
+
+    #[serde(rename = "drop_columns")]
+    #[serde(default)]
+    pub drop_columns: Vec<String>,
+
+    #[serde(rename = "rename_columns")]
+    #[serde(default)]
+    pub rename_columns: Vec<RenameColumn>,
+}
+
+/// Response structure for table schema alteration.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AlterTableSchemaResponse {}
+
+/// ====================
+/// Scan table
+/// ====================
+///
+/// Query parameters for table scan.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ScanTableQuery {
+    #[serde(rename = "database")]
+    pub database: String,
+
+    #[serde(rename = "table")]
+    pub table: String,
+
+    /// LSN to read at, if unassigned, the latest committed data is read.
+    #[serde(rename = "lsn", default)]
+    pub lsn: Option<u64>,
+
+    /// Comma-separated columns to return, all columns are returned if unassigned.
+    #[serde(rename = "columns", default)]
+    pub columns: Option<String>,
+
+    /// Comma-separated predicates in the form of `<column><op><value>`, which are combined with AND.
+    /// Supported operators are `=`, `!=`, `<`, `<=`, `>` and `>=`.
+    #[serde(rename = "filter", default)]
+    pub filter: Option<String>,
+}
+
+/// ====================
+/// Table changes
+/// ====================
+///
+/// Query parameters for table change subscription.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TableChangesQuery {
+    #[serde(rename = "database")]
+    pub database: String,
+
+    #[serde(rename = "table")]
+    pub table: String,
+
+    /// Changes committed at or after the LSN are delivered, if unassigned, all changes retained in WAL are delivered.
+    /// `Last-Event-ID` header takes precedence, which resumes after the last delivered transaction.
+    #[serde(rename = "from_lsn", default)]
+    pub from_lsn: Option<u64>,
+}
+
+/// Payload for one change event, event type is the change operation.
+#[derive(Debug, Serialize)]
+struct TableChangeEventData<'a> {
+    #[serde(rename = "lsn")]
+    lsn: u64,
+
+    #[serde(rename = "row", skip_serializing_if = "Option::is_none")]
+    row: Option<&'a serde_json::Map<String, serde_json::Value>>,
+}
+
 /// ====================
 /// List table
 /// ====================
@@ -239,6 +375,27 @@ pub struct OptimizeTableRequest {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OptimizeTableResponse {}
 
+/// ====================
+/// Rotate master key
+/// ====================
+///
+/// Request structure for master key rotation of an encrypted table.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RotateMasterKeyRequest {
+    #[serde(rename = "database")]
+    pub database: String,
+
+    #[serde(rename = "table")]
+    pub table: String,
+
+    #[serde(rename = "master_key_id")]
+    pub master_key_id: String,
+}
+
+/// Response structure for master key rotation.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RotateMasterKeyResponse {}
+
 /// ====================
 /// Create Snapshot
 /// ====================
@@ -306,6 +463,41 @@ pub struct IngestResponse {
     pub lsn: Option<u64>,
 }
 
+/// One row operation within a batch ingestion request.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestBatchOperation {
+    #[serde(rename = "operation")]
+    pub operation: String,
+
+    #[serde(rename = "data")]
+    pub data: serde_json::Value,
+}
+
+/// Request structure for batch data ingestion, all operations are committed atomically.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestBatchRequest {
+    #[serde(rename = "operations")]
+    pub operations: Vec<IngestBatchOperation>,
+    /// Whether to enable synchronous mode.
+    #[serde(rename = "request_mode")]
+    #[serde(default)]
+    pub request_mode: RequestMode,
+}
+
+/// Response structure for batch data ingestion
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IngestBatchResponse {
+    #[serde(rename = "table")]
+    pub table: String,
+
+    #[serde(rename = "row_count")]
+    pub row_count: usize,
+
+    /// Commit LSN for the batch, assigned for synchronous mode.
+    #[serde(rename = "lsn")]
+    pub lsn: Option<u64>,
+}
+
 /// ====================
 /// File upload
 /// ====================
@@ -378,6 +570,8 @@ fn get_backend_error_status_code(error: &moonlink_backend::Error) -> StatusCode
         moonlink_backend::Error::InvalidArgumentError(_)
         | moonlink_backend::Error::ParseIntError(_)
         | moonlink_backend::Error::Json(_) => StatusCode::BAD_REQUEST,
+        moonlink_backend::Error::WalTruncated(_) => StatusCode::GONE,
+        moonlink_backend::Error::MemoryLimitExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
 
         _ => match error.get_status() {
             ErrorStatus::Temporary => StatusCode::SERVICE_UNAVAILABLE,
@@ -412,20 +606,25 @@ pub fn create_router(state: ApiState) -> Router {
             post(create_table_from_postgres),
         )
         .route("/tables/{table}", delete(drop_table))
+        .route("/tables/{table}/schema", patch(alter_table_schema))
+        .route("/tables/{table}/scan", get(scan_table))
+        .route("/tables/{table}/changes", get(subscribe_table_changes))
         .route("/schema/{database}/{table}", get(fetch_schema))
         .route("/ingest/{table}", post(ingest_data_json))
+        .route("/ingest/{table}/batch", post(ingest_data_batch))
         .route("/ingestpb/{table}", post(ingest_data_protobuf))
         .route("/kafka/{table}/schema", post(set_avro_schema))
         .route("/kafka/{table}/ingest", post(ingest_data_kafka))
         .route("/upload/{table}", post(upload_files))
         .route("/tables/{table}/optimize", post(optimize_table))
+        .route("/tables/{table}/rotate_key", post(rotate_master_key))
         .route("/tables/{table}/snapshot", post(create_snapshot))
         .route("/tables/{table}/flush", post(flush_table))
         .with_state(state)
         .layer(
             CorsLayer::new()
                 .allow_origin(Any)
-                .allow_methods([Method::GET, Method::POST, Method::DELETE])
+                .allow_methods([Method::GET, Method::POST, Method::PATCH, Method::DELETE])
                 .allow_headers(Any),
         )
         .layer(timeout_layer)
@@ -447,7 +646,7 @@ async fn health_check() -> Json<HealthResponse> {
 async fn create_table(
     Path(src_table_name): Path<String>,
     State(state): State<ApiState>,
-    Json(payload): Json<CreateTableRequest>,
+    Json(mut payload): Json<CreateTableRequest>,
 ) -> Result<Json<CreateTableResponse>, (StatusCode, Json<ErrorResponse>)> {
     debug!(
         "Received table creation request for '{}': {:?}",
@@ -455,7 +654,38 @@ async fn create_table(
     );
 
     let mut parsed_avro_schema: Option<AvroSchema> = None;
-    let arrow_schema = if let Some(ref schema) = payload.schema {
+    let arrow_schema = if payload.infer_schema {
+        if payload.schema.is_some() || payload.avro_schema.is_some() {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    message: format!(
+                        "Schema or avro schema shouldn't be provided on schema-inferred table {src_table_name} creation",
+                    ),
+                }),
+            ));
+        }
+        // Inferred columns are added over time, so rows cannot be identified by them.
+        let mooncake_config = &mut payload.table_config.mooncake_config;
+        if mooncake_config.append_only == Some(false)
+            || mooncake_config
+                .row_identity
+                .as_ref()
+                .is_some_and(|identity| *identity != IdentityProp::None)
+        {
+            return Err((
+                StatusCode::BAD_REQUEST,
+                Json(ErrorResponse {
+                    message: format!(
+                        "Schema-inferred table {src_table_name} must be append-only without row identity",
+                    ),
+                }),
+            ));
+        }
+        mooncake_config.append_only = Some(true);
+        mooncake_config.row_identity = Some(IdentityProp::None);
+        get_initial_inferred_schema()
+    } else if let Some(ref schema) = payload.schema {
         match build_arrow_schema(schema) {
             Ok(s) => s,
             Err(e) => {
@@ -518,6 +748,18 @@ async fn create_table(
         }
     };
 
+    // Overflow column is reserved, which identifies schema-inferred tables.
+    if !payload.infer_schema && is_schema_inferred(&arrow_schema) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: format!(
+                    "Schema on table {src_table_name} creation contains reserved overflow column",
+                ),
+            }),
+        ));
+    }
+
     // Serialization not expect to fail.
     let serialized_table_config = match serde_json::to_string(&payload.table_config) {
         Ok(cfg) => cfg,
@@ -552,7 +794,13 @@ async fn create_table(
             if let Some(avro_schema) = parsed_avro_schema {
                 state
                     .backend
-                    .set_avro_schema(src_table_name.clone(), avro_schema)
+                    .set_avro_schema(
+                        payload.database.clone(),
+                        payload.table.clone(),
+                        src_table_name.clone(),
+                        /*schema_id=*/ None,
+                        avro_schema,
+                    )
                     .await
                     .map_err(|e| {
                         (
@@ -564,11 +812,6 @@ async fn create_table(
                             }),
                         )
                     })?;
-                state
-                    .kafka_schema_id_cache
-                    .write()
-                    .await
-                    .insert(src_table_name.clone(), 0 /*placeholder*/);
             }
             Ok(Json(CreateTableResponse {
                 database: payload.database.clone(),
@@ -680,6 +923,59 @@ async fn drop_table(
     Ok(Json(DropTableResponse {}))
 }
 
+/// Table schema alteration endpoint
+async fn alter_table_schema(
+    Path(src_table_name): Path<String>,
+    State(state): State<ApiState>,
+    Json(payload): Json<AlterTableSchemaRequest>,
+) -> Result<Json<AlterTableSchemaResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!(
+        "Received table schema alteration request for '{}': {:?}",
+        src_table_name, payload
+    );
+
+    let columns_to_add = build_arrow_schema(&payload.add_columns).map_err(|e| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: format!(
+                    "Invalid columns to add on table {} schema alteration {:?}: {}",
+                    src_table_name, payload.add_columns, e
+                ),
+            }),
+        )
+    })?;
+    let columns_to_rename = payload
+        .rename_columns
+        .into_iter()
+        .map(|column| (column.from, column.to))
+        .collect::<Vec<_>>();
+
+    state
+        .backend
+        .alter_table(
+            payload.database.clone(),
+            payload.table.clone(),
+            Some(src_table_name.clone()),
+            columns_to_add.fields().iter().cloned().collect(),
+            payload.drop_columns,
+            columns_to_rename,
+        )
+        .await
+        .map_err(|e| {
+            (
+                get_backend_error_status_code(&e),
+                Json(ErrorResponse {
+                    message: format!(
+                        "Failed to alter table schema {} with ID {}.{}: {}",
+                        src_table_name, payload.database, payload.table, e
+                    ),
+                }),
+            )
+        })?;
+    Ok(Json(AlterTableSchemaResponse {}))
+}
+
 /// Table list endpoint
 async fn list_tables(
     State(state): State<ApiState>,
@@ -791,6 +1087,215 @@ async fn optimize_table(
     }
 }
 
+async fn rotate_master_key(
+    Path(src_table_name): Path<String>,
+    State(state): State<ApiState>,
+    Json(payload): Json<RotateMasterKeyRequest>,
+) -> Result<Json<RotateMasterKeyResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!(
+        "Received master key rotation request for '{}': {:?}",
+        src_table_name, payload
+    );
+    match state
+        .backend
+        .rotate_master_key(
+            payload.database.clone(),
+            payload.table.clone(),
+            payload.master_key_id.clone(),
+        )
+        .await
+    {
+        Ok(_) => Ok(Json(RotateMasterKeyResponse {})),
+        Err(e) => {
+            let status_code = get_backend_error_status_code(&e);
+            Err((
+                status_code,
+                Json(ErrorResponse {
+                    message: format!(
+                        "Failed to rotate master key for table {} with ID {}.{}: {}",
+                        src_table_name, payload.database, payload.table, e
+                    ),
+                }),
+            ))
+        }
+    }
+}
+
+/// Table scan endpoint, which streams rows visible at the requested LSN in the format negotiated by `Accept` header.
+async fn scan_table(
+    Path(src_table_name): Path<String>,
+    State(state): State<ApiState>,
+    Query(query): Query<ScanTableQuery>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<ErrorResponse>)> {
+    debug!(
+        "Received table scan request for '{}': {:?}",
+        src_table_name, query
+    );
+
+    let accept = headers
+        .get(header::ACCEPT)
+        .and_then(|value| value.to_str().ok());
+    let format = ScanOutputFormat::from_accept_header(accept).ok_or_else(|| {
+        (
+            StatusCode::NOT_ACCEPTABLE,
+            Json(ErrorResponse {
+                message: format!(
+                    "Unsupported media type {accept:?} for table scan, supported ones are {ARROW_STREAM_CONTENT_TYPE}, {NDJSON_CONTENT_TYPE} and {PARQUET_CONTENT_TYPE}"
+                ),
+            }),
+        )
+    })?;
+
+    let bad_request = |message: String| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: format!("Invalid table scan request for {src_table_name}: {message}"),
+            }),
+        )
+    };
+    let projection = query.columns.as_ref().map(|columns| {
+        columns
+            .split(',')
+            .map(|column| column.trim().to_string())
+            .collect::<Vec<_>>()
+    });
+    let predicates = match &query.filter {
+        Some(filter) => filter
+            .split(',')
+            .map(ScanPredicate::from_str)
+            .collect::<moonlink_backend::Result<Vec<_>>>()
+            .map_err(|e| bad_request(e.to_string()))?,
+        None => vec![],
+    };
+
+    let table_scan = state
+        .backend
+        .scan_table_rows(
+            query.database.clone(),
+            query.table.clone(),
+            query.lsn,
+            ScanOptions {
+                projection,
+                predicates,
+            },
+        )
+        .await
+        .map_err(|e| {
+            (
+                get_backend_error_status_code(&e),
+                Json(ErrorResponse {
+                    message: format!(
+                        "Failed to scan table {} with ID {}.{}: {}",
+                        src_table_name, query.database, query.table, e
+                    ),
+                }),
+            )
+        })?;
+    let body_stream = encode_table_scan(table_scan, format).map_err(|e| {
+        (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(ErrorResponse {
+                message: format!("Failed to encode table scan for {src_table_name}: {e}"),
+            }),
+        )
+    })?;
+
+    // Response body is streamed, so the status code only reflects failures before the first data file is read.
+    Ok(Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, format.content_type())
+        .body(Body::from_stream(body_stream))
+        .unwrap())
+}
+
+/// Convert changes of one committed transaction into server-sent events.
+/// Event id is only assigned to the last event of the transaction, so `Last-Event-ID` always points to a fully delivered transaction.
+fn get_table_change_events(
+    committed_changes: &CommittedChanges,
+) -> Vec<Result<Event, axum::Error>> {
+    let last_change_idx = committed_changes.changes.len().saturating_sub(1);
+    committed_changes
+        .changes
+        .iter()
+        .enumerate()
+        .map(|(idx, change)| {
+            let event = Event::default()
+                .event(change.operation.as_str())
+                .json_data(TableChangeEventData {
+                    lsn: committed_changes.commit_lsn,
+                    row: change.row.as_ref(),
+                })?;
+            if idx == last_change_idx {
+                return Ok(event.id(committed_changes.commit_lsn.to_string()));
+            }
+            Ok(event)
+        })
+        .collect()
+}
+
+/// Table change subscription endpoint, which streams committed inserts and deletes as server-sent events.
+async fn subscribe_table_changes(
+    Path(src_table_name): Path<String>,
+    State(state): State<ApiState>,
+    Query(query): Query<TableChangesQuery>,
+    headers: HeaderMap,
+) -> Result<Sse<impl Stream<Item = Result<Event, axum::Error>>>, (StatusCode, Json<ErrorResponse>)>
+{
+    debug!(
+        "Received table change subscription for '{}': {:?}",
+        src_table_name, query
+    );
+
+    let start_lsn = match headers.get("last-event-id") {
+        Some(last_event_id) => {
+            let last_lsn = last_event_id
+                .to_str()
+                .ok()
+                .and_then(|last_event_id| last_event_id.trim().parse::<u64>().ok())
+                .ok_or_else(|| {
+                    (
+                        StatusCode::BAD_REQUEST,
+                        Json(ErrorResponse {
+                            message: format!(
+                                "Invalid Last-Event-ID {last_event_id:?} for table {src_table_name}"
+                            ),
+                        }),
+                    )
+                })?;
+            last_lsn + 1
+        }
+        None => query.from_lsn.unwrap_or(0),
+    };
+
+    let changes = state
+        .backend
+        .subscribe_table_changes(query.database.clone(), query.table.clone(), start_lsn)
+        .await
+        .map_err(|e| {
+            (
+                get_backend_error_status_code(&e),
+                Json(ErrorResponse {
+                    message: format!(
+                        "Failed to subscribe to changes of table {} with ID {}.{}: {}",
+                        src_table_name, query.database, query.table, e
+                    ),
+                }),
+            )
+        })?;
+
+    // Failures after the response starts are reported as an `error` event, which terminates the stream.
+    let events = changes.flat_map(|committed_changes| {
+        let events = match committed_changes {
+            Ok(committed_changes) => get_table_change_events(&committed_changes),
+            Err(e) => vec![Ok(Event::default().event("error").data(e.to_string()))],
+        };
+        stream::iter(events)
+    });
+    Ok(Sse::new(events).keep_alive(KeepAlive::default()))
+}
+
 /// Fetch schema for the requested table.
 async fn fetch_schema(
     Path((database, table)): Path<(String, String)>,
@@ -953,7 +1458,7 @@ async fn set_avro_schema(
         .read()
         .await
         .get(&src_table_name)
-        .is_some_and(|id| *id == payload.schema_id)
+        .is_some_and(|ids| ids.contains(&payload.schema_id))
     {
         return Ok(Json(SetAvroSchemaResponse {
             database: payload.database,
@@ -961,6 +1466,19 @@ async fn set_avro_schema(
             schema_id: payload.schema_id,
         }));
     }
+    // Confluent schema ids are encoded as 4 bytes in wire format.
+    let schema_id = u32::try_from(payload.schema_id).map_err(|_| {
+        (
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: format!(
+                    "Schema id {} for table {src_table_name} exceeds 4 bytes",
+                    payload.schema_id
+                ),
+            }),
+        )
+    })?;
+
     // Parse the Avro schema
     let schema_json_string = match serde_json::to_string(&payload.kafka_schema) {
         Ok(s) => s,
@@ -992,7 +1510,13 @@ async fn set_avro_schema(
     // Set Avro schema on the existing table
     match state
         .backend
-        .set_avro_schema(src_table_name.clone(), avro_schema)
+        .set_avro_schema(
+            payload.database.clone(),
+            payload.table.clone(),
+            src_table_name.clone(),
+            Some(schema_id),
+            avro_schema,
+        )
         .await
     {
         Ok(()) => {
@@ -1000,7 +1524,9 @@ async fn set_avro_schema(
                 .kafka_schema_id_cache
                 .write()
                 .await
-                .insert(src_table_name.clone(), payload.schema_id);
+                .entry(src_table_name.clone())
+                .or_default()
+                .insert(payload.schema_id);
             Ok(Json(SetAvroSchemaResponse {
                 database: payload.database,
                 table: payload.table,
@@ -1057,6 +1583,25 @@ async fn ingest_data_json(
     .await
 }
 
+/// Parse row operation for data ingestion.
+fn parse_row_event_operation(
+    operation: &str,
+) -> Result<RowEventOperation, (StatusCode, Json<ErrorResponse>)> {
+    match operation.to_lowercase().as_str() {
+        "insert" => Ok(RowEventOperation::Insert),
+        "upsert" => Ok(RowEventOperation::Upsert),
+        "delete" => Ok(RowEventOperation::Delete),
+        _ => Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: format!(
+                    "Invalid operation '{operation}' for data ingestion. Must be 'insert', 'upsert', or 'delete'"
+                ),
+            }),
+        )),
+    }
+}
+
 /// Data ingestion endpoint
 async fn ingest_data_impl(
     src_table_name: String,
@@ -1069,22 +1614,7 @@ async fn ingest_data_impl(
     );
 
     // Parse operation.
-    let operation = match payload.operation.to_lowercase().as_str() {
-        "insert" => RowEventOperation::Insert,
-        "upsert" => RowEventOperation::Upsert,
-        "delete" => RowEventOperation::Delete,
-        _ => {
-            return Err((
-                StatusCode::BAD_REQUEST,
-                Json(ErrorResponse {
-                    message: format!(
-                        "Invalid operation '{}' for data ingestion. Must be 'insert', 'upsert', or 'delete'",
-                        payload.operation
-                    ),
-                }),
-            ));
-        }
-    };
+    let operation = parse_row_event_operation(&payload.operation)?;
 
     // Create REST request
     let (tx, mut rx) = mpsc::channel(1);
@@ -1107,7 +1637,7 @@ async fn ingest_data_impl(
         .await
         .map_err(|e| {
             (
-                StatusCode::INTERNAL_SERVER_ERROR,
+                get_backend_error_status_code(&e),
                 Json(ErrorResponse {
                     message: format!(
                         "Failed to process data ingestion request for table {src_table_name} because {e}"
@@ -1128,9 +1658,102 @@ async fn ingest_data_impl(
     }))
 }
 
+/// Batch data ingestion endpoint, which commits all operations under a single LSN.
+async fn ingest_data_batch(
+    Path(src_table_name): Path<String>,
+    State(state): State<ApiState>,
+    Json(request): Json<IngestBatchRequest>,
+) -> Result<Json<IngestBatchResponse>, (StatusCode, Json<ErrorResponse>)> {
+    debug!(
+        "Received batch ingestion request for table '{}' with {} operations",
+        src_table_name,
+        request.operations.len()
+    );
+
+    if request.operations.is_empty() {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(ErrorResponse {
+                message: format!("Empty batch for data ingestion to table {src_table_name}"),
+            }),
+        ));
+    }
+    let mut rows = Vec::with_capacity(request.operations.len());
+    for cur_operation in request.operations.into_iter() {
+        rows.push(BatchRowOperation {
+            operation: parse_row_event_operation(&cur_operation.operation)?,
+            payload: IngestRequestPayload::Json(cur_operation.data),
+        });
+    }
+    let row_count = rows.len();
+
+    // Create REST request
+    let (tx, mut rx) = mpsc::channel(1);
+    let batch_row_event_request = BatchRowEventRequest {
+        src_table_name: src_table_name.clone(),
+        rows,
+        timestamp: SystemTime::now(),
+        tx: if request.request_mode == RequestMode::Sync {
+            Some(tx)
+        } else {
+            None
+        },
+    };
+    let rest_event_request = EventRequest::BatchRowRequest(batch_row_event_request);
+
+    state
+        .backend
+        .send_event_request(rest_event_request)
+        .await
+        .map_err(|e| {
+            (
+                get_backend_error_status_code(&e),
+                Json(ErrorResponse {
+                    message: format!(
+                        "Failed to process batch ingestion request for table {src_table_name} because {e}"
+                    ),
+                }),
+            )
+        })?;
+
+    let lsn: Option<u64> = if request.request_mode == RequestMode::Sync {
+        match rx.recv().await {
+            Some(Ok(commit_lsn)) => Some(commit_lsn),
+            Some(Err(error)) => {
+                return Err((
+                    StatusCode::BAD_REQUEST,
+                    Json(ErrorResponse {
+                        message: format!(
+                            "Batch ingestion request for table {src_table_name} is rejected because {error}"
+                        ),
+                    }),
+                ));
+            }
+            None => {
+                return Err((
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    Json(ErrorResponse {
+                        message: format!(
+                            "Batch ingestion request for table {src_table_name} is dropped without completion"
+                        ),
+                    }),
+                ));
+            }
+        }
+    } else {
+        None
+    };
+    Ok(Json(IngestBatchResponse {
+        table: src_table_name,
+        row_count,
+        lsn,
+    }))
+}
+
 async fn ingest_data_kafka(
     Path(src_table_name): Path<String>,
     State(state): State<ApiState>,
+    Query(params): Query<KafkaIngestParams>,
     body: axum::body::Bytes,
 ) -> Result<Json<IngestResponse>, (StatusCode, Json<ErrorResponse>)> {
     debug!(
@@ -1146,7 +1769,10 @@ async fn ingest_data_kafka(
         state,
         IngestRequestInternal {
             operation: "insert".to_string(),
-            data: IngestRequestPayload::Avro(body.to_vec()),
+            data: match params.wire_format {
+                KafkaWireFormat::Raw => IngestRequestPayload::Avro(body.to_vec()),
+                KafkaWireFormat::Confluent => IngestRequestPayload::ConfluentAvro(body.to_vec()),
+            },
             request_mode: RequestMode::Sync,
         },
     )
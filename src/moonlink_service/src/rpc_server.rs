@@ -1,9 +1,11 @@
 use crate::{error::Error, Result};
+use arrow_ipc::reader::StreamReader;
 use arrow_ipc::writer::StreamWriter;
 use moonlink_backend::MoonlinkBackend;
 use moonlink_error::{ErrorStatus, ErrorStruct};
 use moonlink_rpc::{read, write, Request, RpcResult, Table};
 use std::collections::HashMap;
+use std::io::Cursor;
 use std::io::ErrorKind::{BrokenPipe, ConnectionReset, UnexpectedEof};
 use std::net::SocketAddr;
 use std::sync::Arc;
@@ -102,6 +104,33 @@ where
     loop {
         let request = read(&mut stream).await?;
         match request {
+            Request::AlterTable {
+                database,
+                table,
+                columns_to_add,
+                columns_to_drop,
+                columns_to_rename,
+            } => {
+                // Columns to add are serialized as arrow IPC schema.
+                let result: anyhow::Result<()> = async {
+                    let reader = StreamReader::try_new(Cursor::new(columns_to_add), None)?;
+                    let columns_to_add = reader.schema().fields().iter().cloned().collect();
+                    backend
+                        .alter_table(
+                            database,
+                            table,
+                            /*src_table_name=*/ None,
+                            columns_to_add,
+                            columns_to_drop,
+                            columns_to_rename,
+                        )
+                        .await?;
+                    Ok(())
+                }
+                .await;
+                let res: RpcResult<()> = result.map_err(into_error_struct);
+                write(&mut stream, &res).await?;
+            }
             Request::CreateSnapshot {
                 database,
                 table,
@@ -189,6 +218,17 @@ where
                     .map_err(into_error_struct);
                 write(&mut stream, &res).await?;
             }
+            Request::RotateMasterKey {
+                database,
+                table,
+                master_key_id,
+            } => {
+                let res: RpcResult<()> = backend
+                    .rotate_master_key(database, table, master_key_id)
+                    .await
+                    .map_err(into_error_struct);
+                write(&mut stream, &res).await?;
+            }
             Request::ScanTableBegin {
                 database,
                 table,
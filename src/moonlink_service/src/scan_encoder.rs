@@ -0,0 +1,179 @@
+/// This module encodes table scan results into HTTP response body, in the format negotiated by `Accept` header.
+use crate::error::Result;
+
+use arrow_array::RecordBatch;
+use arrow_ipc::writer::StreamWriter;
+use arrow_json::LineDelimitedWriter;
+use arrow_schema::SchemaRef;
+use futures::stream::{self, BoxStream, StreamExt};
+use moonlink_backend::table_scan::TableScan;
+use parquet::arrow::ArrowWriter;
+
+/// Content type for arrow IPC streaming format.
+pub(crate) const ARROW_STREAM_CONTENT_TYPE: &str = "application/vnd.apache.arrow.stream";
+/// Content type for newline-delimited JSON.
+pub(crate) const NDJSON_CONTENT_TYPE: &str = "application/x-ndjson";
+/// Content type for parquet file.
+pub(crate) const PARQUET_CONTENT_TYPE: &str = "application/vnd.apache.parquet";
+
+/// Output format for table scan.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ScanOutputFormat {
+    ArrowIpc,
+    NdJson,
+    Parquet,
+}
+
+impl ScanOutputFormat {
+    /// Pick the first supported media type in `Accept` header, arrow IPC is used if the header is missing or takes wildcard.
+    /// Return `None` if none of the requested media types is supported.
+    pub(crate) fn from_accept_header(accept: Option<&str>) -> Option<Self> {
+        let Some(accept) = accept else {
+            return Some(ScanOutputFormat::ArrowIpc);
+        };
+        for media_type in accept.split(',') {
+            // Parameters (i.e. quality values) are ignored, media types are taken in the given order.
+            let media_type = media_type.split(';').next().unwrap_or_default().trim();
+            match media_type.to_ascii_lowercase().as_str() {
+                ARROW_STREAM_CONTENT_TYPE | "*/*" | "application/*" => {
+                    return Some(ScanOutputFormat::ArrowIpc)
+                }
+                NDJSON_CONTENT_TYPE | "application/jsonl" => return Some(ScanOutputFormat::NdJson),
+                PARQUET_CONTENT_TYPE | "application/x-parquet" => {
+                    return Some(ScanOutputFormat::Parquet)
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub(crate) fn content_type(&self) -> &'static str {
+        match self {
+            ScanOutputFormat::ArrowIpc => ARROW_STREAM_CONTENT_TYPE,
+            ScanOutputFormat::NdJson => NDJSON_CONTENT_TYPE,
+            ScanOutputFormat::Parquet => PARQUET_CONTENT_TYPE,
+        }
+    }
+}
+
+/// Writer which encodes record batches into an in-memory buffer, which is drained after each write.
+trait RecordBatchEncoder: Send + 'static {
+    fn write(&mut self, record_batch: &RecordBatch) -> Result<()>;
+    fn finish(&mut self) -> Result<()>;
+    fn take_buffer(&mut self) -> Vec<u8>;
+}
+
+impl RecordBatchEncoder for StreamWriter<Vec<u8>> {
+    fn write(&mut self, record_batch: &RecordBatch) -> Result<()> {
+        Ok(StreamWriter::write(self, record_batch)?)
+    }
+    fn finish(&mut self) -> Result<()> {
+        Ok(StreamWriter::finish(self)?)
+    }
+    fn take_buffer(&mut self) -> Vec<u8> {
+        std::mem::take(self.get_mut())
+    }
+}
+
+impl RecordBatchEncoder for LineDelimitedWriter<Vec<u8>> {
+    fn write(&mut self, record_batch: &RecordBatch) -> Result<()> {
+        Ok(LineDelimitedWriter::write(self, record_batch)?)
+    }
+    fn finish(&mut self) -> Result<()> {
+        Ok(LineDelimitedWriter::finish(self)?)
+    }
+    fn take_buffer(&mut self) -> Vec<u8> {
+        std::mem::take(self.get_mut())
+    }
+}
+
+/// Parquet writer keeps track of written bytes by itself, so it's safe to drain the underlying buffer.
+impl RecordBatchEncoder for ArrowWriter<Vec<u8>> {
+    fn write(&mut self, record_batch: &RecordBatch) -> Result<()> {
+        Ok(ArrowWriter::write(self, record_batch)?)
+    }
+    fn finish(&mut self) -> Result<()> {
+        ArrowWriter::finish(self)?;
+        Ok(())
+    }
+    fn take_buffer(&mut self) -> Vec<u8> {
+        std::mem::take(self.inner_mut())
+    }
+}
+
+fn create_encoder(
+    format: ScanOutputFormat,
+    schema: &SchemaRef,
+) -> Result<Box<dyn RecordBatchEncoder>> {
+    let encoder: Box<dyn RecordBatchEncoder> = match format {
+        ScanOutputFormat::ArrowIpc => Box::new(StreamWriter::try_new(Vec::new(), schema)?),
+        ScanOutputFormat::NdJson => Box::new(LineDelimitedWriter::new(Vec::new())),
+        ScanOutputFormat::Parquet => Box::new(ArrowWriter::try_new(
+            Vec::new(),
+            schema.clone(),
+            /*props=*/ None,
+        )?),
+    };
+    Ok(encoder)
+}
+
+/// Encode table scan results into a stream of response body chunks.
+/// Encoder failure is reported at creation, so response status could reflect it; errors afterwards terminate the stream.
+pub(crate) fn encode_table_scan(
+    table_scan: TableScan,
+    format: ScanOutputFormat,
+) -> Result<BoxStream<'static, Result<Vec<u8>>>> {
+    let encoder = create_encoder(format, &table_scan.schema)?;
+    let stream = stream::unfold(
+        (Some(encoder), table_scan.stream),
+        |(encoder, mut record_batches)| async move {
+            let mut encoder = encoder?;
+            let chunk = match record_batches.next().await {
+                Some(Ok(record_batch)) => {
+                    encoder.write(&record_batch).map(|_| encoder.take_buffer())
+                }
+                Some(Err(e)) => Err(e.into()),
+                None => {
+                    let chunk = encoder.finish().map(|_| encoder.take_buffer());
+                    return Some((chunk, (None, record_batches)));
+                }
+            };
+            // Stop encoding at the first failure.
+            let encoder = if chunk.is_ok() { Some(encoder) } else { None };
+            Some((chunk, (encoder, record_batches)))
+        },
+    )
+    // Encoders buffer rows internally, so chunks could be empty.
+    .filter(|chunk| std::future::ready(!matches!(chunk, Ok(bytes) if bytes.is_empty())))
+    .boxed();
+    Ok(stream)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_output_format_negotiation() {
+        assert_eq!(
+            ScanOutputFormat::from_accept_header(None),
+            Some(ScanOutputFormat::ArrowIpc)
+        );
+        assert_eq!(
+            ScanOutputFormat::from_accept_header(Some("*/*")),
+            Some(ScanOutputFormat::ArrowIpc)
+        );
+        assert_eq!(
+            ScanOutputFormat::from_accept_header(Some("application/x-ndjson")),
+            Some(ScanOutputFormat::NdJson)
+        );
+        assert_eq!(
+            ScanOutputFormat::from_accept_header(Some(
+                "text/html, application/vnd.apache.parquet;q=0.9, */*;q=0.1"
+            )),
+            Some(ScanOutputFormat::Parquet)
+        );
+        assert_eq!(ScanOutputFormat::from_accept_header(Some("text/csv")), None);
+    }
+}
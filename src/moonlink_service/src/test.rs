@@ -180,6 +180,89 @@ async fn test_create_snapshot() {
     assert_data_and_puffin(TABLE, lsn).await;
 }
 
+/// Test table creation with timezone-aware timestamp column, which is persisted into iceberg.
+#[tokio::test]
+#[serial]
+async fn test_create_table_with_timestamptz() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    // Create test table with non-UTC input timezone.
+    let client = reqwest::Client::new();
+    let crafted_src_table_name = format!("{DATABASE}.{TABLE}");
+    let payload = json!({
+        "database": DATABASE,
+        "table": TABLE,
+        "schema": [
+            {"name": "id", "data_type": "int32", "nullable": false},
+            {"name": "ts", "data_type": "timestamptz(America/Los_Angeles)", "nullable": true}
+        ],
+        "table_config": {
+            "mooncake": {
+                "append_only": true
+            }
+        }
+    });
+    let response = client
+        .post(format!("{REST_ADDR}/tables/{crafted_src_table_name}"))
+        .header("content-type", "application/json")
+        .json(&payload)
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        response.status().is_success(),
+        "Response status is {response:?}"
+    );
+
+    // Timestamp without offset is interpreted in the input timezone.
+    let insert_payload = json!({
+        "operation": "insert",
+        "request_mode": "sync",
+        "data": {"id": 1, "ts": "2024-01-01T00:00:00"}
+    });
+    let response = execute_test_ingest(&client, &crafted_src_table_name, &insert_payload).await;
+    let lsn = response.lsn.unwrap();
+
+    // Iceberg only accepts UTC timestamptz.
+    flush_table(&client, DATABASE, TABLE, lsn).await;
+    create_snapshot(&client, DATABASE, TABLE, lsn).await;
+
+    let mut moonlink_stream = TcpStream::connect(MOONLINK_ADDR).await.unwrap();
+    let bytes = scan_table_begin(
+        &mut moonlink_stream,
+        DATABASE.to_string(),
+        TABLE.to_string(),
+        lsn,
+    )
+    .await
+    .unwrap();
+    let (data_file_paths, _, _, _) = decode_serialized_read_state_for_testing(bytes);
+    assert_eq!(data_file_paths.len(), 1);
+    let record_batches = read_all_batches(&data_file_paths[0]).await;
+    assert_eq!(record_batches.len(), 1);
+    let ts_column = record_batches[0]
+        .column(1)
+        .as_any()
+        .downcast_ref::<arrow_array::TimestampMicrosecondArray>()
+        .unwrap();
+    assert!(ts_column.timezone().is_some());
+    // 2024-01-01T08:00:00Z
+    assert_eq!(ts_column.value(0), 1_704_096_000_000_000);
+
+    scan_table_end(
+        &mut moonlink_stream,
+        DATABASE.to_string(),
+        TABLE.to_string(),
+    )
+    .await
+    .unwrap();
+}
+
 /// Test basic table creation, insertion and query.
 #[tokio::test]
 #[serial]
@@ -517,6 +600,402 @@ async fn test_drop_serialize_uses_renamed_fields() {
     assert!(out.contains("\"table\":\"users\""));
 }
 
+/// Testing scenario: alter schema for a REST table, and ingest rows with the new schema.
+#[tokio::test]
+#[serial]
+async fn test_alter_table_schema() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    // Create test table.
+    let client = reqwest::Client::new();
+    create_table(&client, DATABASE, TABLE, /*nested=*/ false).await;
+    let crafted_src_table_name = format!("{DATABASE}.{TABLE}");
+
+    // Invalid schema change is rejected.
+    let payload = json!({
+        "database": DATABASE,
+        "table": TABLE,
+        "drop_columns": ["non_existent_column"]
+    });
+    let response = client
+        .patch(format!(
+            "{REST_ADDR}/tables/{crafted_src_table_name}/schema"
+        ))
+        .header("content-type", "application/json")
+        .json(&payload)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    // Source table in request path must match the table.
+    let payload = json!({
+        "database": DATABASE,
+        "table": TABLE,
+        "add_columns": [
+            {"name": "nickname", "data_type": "string", "nullable": true}
+        ]
+    });
+    let response = client
+        .patch(format!("{REST_ADDR}/tables/non_existent_table/schema"))
+        .header("content-type", "application/json")
+        .json(&payload)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+
+    // Add, drop and rename columns.
+    let payload = json!({
+        "database": DATABASE,
+        "table": TABLE,
+        "add_columns": [
+            {"name": "nickname", "data_type": "string", "nullable": true}
+        ],
+        "drop_columns": ["age"],
+        "rename_columns": [
+            {"from": "email", "to": "contact"}
+        ]
+    });
+    let response = client
+        .patch(format!(
+            "{REST_ADDR}/tables/{crafted_src_table_name}/schema"
+        ))
+        .header("content-type", "application/json")
+        .json(&payload)
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        response.status().is_success(),
+        "Response status is {response:?}"
+    );
+
+    // Ingest with the new schema.
+    let insert_payload = json!({
+        "operation": "insert",
+        "request_mode": "sync",
+        "data": {
+            "id": 1,
+            "name": "Alice Johnson",
+            "contact": "alice@example.com",
+            "nickname": "Alice"
+        }
+    });
+    let response = execute_test_ingest(&client, &crafted_src_table_name, &insert_payload).await;
+    let lsn = response.lsn.unwrap();
+
+    // Scan table and check data file schema.
+    let mut moonlink_stream = TcpStream::connect(MOONLINK_ADDR).await.unwrap();
+    let bytes = scan_table_begin(
+        &mut moonlink_stream,
+        DATABASE.to_string(),
+        TABLE.to_string(),
+        lsn,
+    )
+    .await
+    .unwrap();
+    let (data_file_paths, _, _, _) = decode_serialized_read_state_for_testing(bytes);
+    assert_eq!(data_file_paths.len(), 1);
+    let record_batches = read_all_batches(&data_file_paths[0]).await;
+    let column_names = record_batches[0]
+        .schema()
+        .fields()
+        .iter()
+        .map(|field| field.name().clone())
+        .collect::<Vec<_>>();
+    assert_eq!(column_names, vec!["id", "name", "contact", "nickname"]);
+    scan_table_end(
+        &mut moonlink_stream,
+        DATABASE.to_string(),
+        TABLE.to_string(),
+    )
+    .await
+    .unwrap();
+}
+
+/// Error case: avro schema incompatible with the table schema is rejected, and stays rejected on retry.
+#[tokio::test]
+#[serial]
+async fn test_set_incompatible_avro_schema() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    // Create test table.
+    let client = reqwest::Client::new();
+    create_table(&client, DATABASE, TABLE, /*nested=*/ false).await;
+    let crafted_src_table_name = format!("{DATABASE}.{TABLE}");
+
+    let payload = json!({
+        "database": DATABASE,
+        "table": TABLE,
+        "kafka_schema": {
+            "type": "record",
+            "name": "unrelated",
+            "fields": [
+                {"name": "unrelated_column", "type": "string"}
+            ]
+        },
+        "schema_id": 1
+    });
+    // Failed schema id isn't cached, so retry fails as well.
+    for _ in 0..2 {
+        let response = client
+            .post(format!("{REST_ADDR}/kafka/{crafted_src_table_name}/schema"))
+            .header("content-type", "application/json")
+            .json(&payload)
+            .send()
+            .await
+            .unwrap();
+        assert!(
+            !response.status().is_success(),
+            "Response status is {response:?}"
+        );
+    }
+}
+
+/// Testing scenario: ingest rows, and scan them back via REST API in all supported formats.
+#[tokio::test]
+#[serial]
+async fn test_scan_table() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    // Create test table and ingest rows.
+    let client = reqwest::Client::new();
+    create_table(&client, DATABASE, TABLE, /*nested=*/ false).await;
+    let crafted_src_table_name = format!("{DATABASE}.{TABLE}");
+    let mut lsn = 0;
+    for (id, name, age) in [(1, "Alice", 30), (2, "Bob", 40), (3, "Carol", 50)] {
+        let insert_payload = json!({
+            "operation": "insert",
+            "request_mode": "sync",
+            "data": {
+                "id": id,
+                "name": name,
+                "email": format!("{}@example.com", name.to_lowercase()),
+                "age": age
+            }
+        });
+        let response = execute_test_ingest(&client, &crafted_src_table_name, &insert_payload).await;
+        lsn = response.lsn.unwrap();
+    }
+    let scan_url = format!(
+        "{REST_ADDR}/tables/{crafted_src_table_name}/scan?database={DATABASE}&table={TABLE}&lsn={lsn}"
+    );
+
+    // Scan with projection and predicates as newline-delimited JSON.
+    let response = client
+        .get(format!("{scan_url}&columns=name,id&filter=age>=40,id!=3"))
+        .header("accept", "application/x-ndjson")
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        response.status().is_success(),
+        "Response status is {response:?}"
+    );
+    assert_eq!(response.headers()["content-type"], "application/x-ndjson");
+    let body = response.text().await.unwrap();
+    let rows = body
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(rows, vec![json!({"name": "Bob", "id": 2})]);
+
+    // Scan all rows as arrow IPC stream by default.
+    let response = client.get(&scan_url).send().await.unwrap();
+    assert!(
+        response.status().is_success(),
+        "Response status is {response:?}"
+    );
+    let body = response.bytes().await.unwrap();
+    let reader = arrow_ipc::reader::StreamReader::try_new(body.as_ref(), None).unwrap();
+    let record_batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    let num_rows = record_batches
+        .iter()
+        .map(|batch| batch.num_rows())
+        .sum::<usize>();
+    assert_eq!(num_rows, 3);
+    assert_eq!(record_batches[0].num_columns(), 4);
+
+    // Scan as parquet file.
+    let response = client
+        .get(format!("{scan_url}&filter=name=Carol"))
+        .header("accept", "application/vnd.apache.parquet")
+        .send()
+        .await
+        .unwrap();
+    assert!(
+        response.status().is_success(),
+        "Response status is {response:?}"
+    );
+    let body = response.bytes().await.unwrap();
+    let reader =
+        parquet::arrow::arrow_reader::ParquetRecordBatchReader::try_new(body, 1024).unwrap();
+    let record_batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+    assert_eq!(record_batches.len(), 1);
+    assert_eq!(record_batches[0].num_rows(), 1);
+
+    // Invalid predicate and unsupported media type.
+    let response = client
+        .get(format!("{scan_url}&filter=age>=abc"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let response = client
+        .get(format!("{scan_url}&columns=non_existent_column"))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+    let response = client
+        .get(&scan_url)
+        .header("accept", "text/csv")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::NOT_ACCEPTABLE);
+}
+
+/// Read the given number of server-sent events from response, return event type, event id and data for each of them.
+async fn read_server_sent_events(
+    response: &mut reqwest::Response,
+    count: usize,
+) -> Vec<(String, Option<String>, serde_json::Value)> {
+    let mut buffer = String::new();
+    let mut events = vec![];
+    while events.len() < count {
+        let chunk = tokio::time::timeout(std::time::Duration::from_secs(30), response.chunk())
+            .await
+            .expect("timeout waiting for server-sent events")
+            .unwrap()
+            .expect("server-sent event stream ended unexpectedly");
+        buffer.push_str(std::str::from_utf8(&chunk).unwrap());
+        while let Some(end) = buffer.find("\n\n") {
+            let raw_event = buffer[..end].to_string();
+            buffer.drain(..end + 2);
+            let (mut event_type, mut event_id, mut data) = (None, None, None);
+            for line in raw_event.lines() {
+                if let Some(value) = line.strip_prefix("event:") {
+                    event_type = Some(value.trim().to_string());
+                } else if let Some(value) = line.strip_prefix("id:") {
+                    event_id = Some(value.trim().to_string());
+                } else if let Some(value) = line.strip_prefix("data:") {
+                    data = Some(serde_json::from_str(value.trim()).unwrap());
+                }
+            }
+            // Keep-alive comments don't carry event type.
+            if let (Some(event_type), Some(data)) = (event_type, data) {
+                events.push((event_type, event_id, data));
+            }
+        }
+    }
+    events
+}
+
+/// Testing scenario: ingest and delete rows, and subscribe to committed changes via server-sent events.
+#[tokio::test]
+#[serial]
+async fn test_subscribe_table_changes() {
+    let _guard = TestGuard::new(&get_moonlink_backend_dir());
+    let config = get_service_config();
+    tokio::spawn(async move {
+        start_with_config(config).await.unwrap();
+    });
+    wait_for_server_ready().await;
+
+    // Create test table, ingest two rows and delete the first one.
+    let client = reqwest::Client::new();
+    create_table(&client, DATABASE, TABLE, /*nested=*/ false).await;
+    let crafted_src_table_name = format!("{DATABASE}.{TABLE}");
+    let mut commit_lsns = vec![];
+    for (operation, id, name) in [
+        ("insert", 1, "Alice"),
+        ("insert", 2, "Bob"),
+        ("delete", 1, "Alice"),
+    ] {
+        let payload = json!({
+            "operation": operation,
+            "request_mode": "sync",
+            "data": {
+                "id": id,
+                "name": name,
+                "email": format!("{}@example.com", name.to_lowercase()),
+                "age": 30
+            }
+        });
+        let response = execute_test_ingest(&client, &crafted_src_table_name, &payload).await;
+        commit_lsns.push(response.lsn.unwrap());
+    }
+    let changes_url = format!(
+        "{REST_ADDR}/tables/{crafted_src_table_name}/changes?database={DATABASE}&table={TABLE}"
+    );
+
+    // Subscribe from the beginning.
+    let mut response = client.get(&changes_url).send().await.unwrap();
+    assert!(
+        response.status().is_success(),
+        "Response status is {response:?}"
+    );
+    assert_eq!(response.headers()["content-type"], "text/event-stream");
+    let events = read_server_sent_events(&mut response, /*count=*/ 3).await;
+    let event_types = events
+        .iter()
+        .map(|(event_type, _, _)| event_type.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(event_types, vec!["insert", "insert", "delete"]);
+    for ((_, event_id, data), commit_lsn) in events.iter().zip(commit_lsns.iter()) {
+        assert_eq!(event_id.as_deref(), Some(commit_lsn.to_string().as_str()));
+        assert_eq!(data["lsn"], json!(commit_lsn));
+    }
+    assert_eq!(events[1].2["row"]["name"], json!("Bob"));
+    assert_eq!(events[2].2["row"]["id"], json!(1));
+
+    // Resume after the first transaction.
+    let mut response = client
+        .get(&changes_url)
+        .header("last-event-id", commit_lsns[0].to_string())
+        .send()
+        .await
+        .unwrap();
+    let events = read_server_sent_events(&mut response, /*count=*/ 1).await;
+    assert_eq!(events[0].0, "insert");
+    assert_eq!(events[0].2["lsn"], json!(commit_lsns[1]));
+
+    // Subscribe from a later LSN.
+    let mut response = client
+        .get(format!("{changes_url}&from_lsn={}", commit_lsns[2]))
+        .send()
+        .await
+        .unwrap();
+    let events = read_server_sent_events(&mut response, /*count=*/ 1).await;
+    assert_eq!(events[0].0, "delete");
+
+    // Invalid resume position.
+    let response = client
+        .get(&changes_url)
+        .header("last-event-id", "abc")
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), reqwest::StatusCode::BAD_REQUEST);
+}
+
 /// Testing scenario: create multiple tables, and check list table result.
 #[tokio::test]
 #[serial]
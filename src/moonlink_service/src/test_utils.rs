@@ -43,6 +43,8 @@ pub(crate) fn get_nginx_addr() -> String {
 pub(crate) const REST_API_PORT: u16 = 3030;
 /// OTEL API port.
 pub(crate) const OTEL_API_PORT: u16 = 3435;
+/// OTEL gRPC API port.
+pub(crate) const OTEL_GRPC_API_PORT: u16 = 4317;
 /// TCP port.
 pub(crate) const TCP_PORT: u16 = 3031;
 /// Local nginx server IP/port address.
@@ -65,9 +67,12 @@ pub(crate) fn get_service_config() -> ServiceConfig {
         data_server_uri: Some(nginx_addr),
         rest_api_port: Some(REST_API_PORT),
         otel_ingestion_api_port: Some(OTEL_API_PORT),
+        otel_grpc_ingestion_api_port: Some(OTEL_GRPC_API_PORT),
         tcp_port: Some(TCP_PORT),
         log_directory: None,
         otel_export_target: None,
+        memory_soft_limit_bytes: None,
+        memory_hard_limit_bytes: None,
     }
 }
 
@@ -1,2 +1,2 @@
 pub mod table_metadata;
-pub use table_metadata::{DeletionVector, MooncakeTableMetadata, PositionDelete};
+pub use table_metadata::{DataKeyEnvelope, DeletionVector, MooncakeTableMetadata, PositionDelete};
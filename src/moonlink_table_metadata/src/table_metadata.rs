@@ -19,12 +19,21 @@ pub struct PositionDelete {
     pub data_file_row_number: u32,
 }
 
+/// Data key of an encrypted table, wrapped by the master key; readers unwrap it with their own key provider.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataKeyEnvelope {
+    pub master_key_id: String,
+    pub wrapped_key: Vec<u8>,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct MooncakeTableMetadata {
     pub data_files: Vec<String>,
     pub puffin_files: Vec<String>,
     pub deletion_vectors: Vec<DeletionVector>,
     pub position_deletes: Vec<PositionDelete>,
+    /// Wrapped data key to decrypt data files and deletion vectors with, if the table is encrypted.
+    pub data_key_envelope: Option<DataKeyEnvelope>,
 }
 
 impl Encode for MooncakeTableMetadata {
@@ -89,6 +98,19 @@ impl Encode for MooncakeTableMetadata {
             writer.write(puffin_file.as_bytes())?;
         }
 
+        // Write wrapped data key at the end, master key id and wrapped key are both zero length for unencrypted tables.
+        let (master_key_id, wrapped_key) = match &self.data_key_envelope {
+            Some(envelope) => (
+                envelope.master_key_id.as_bytes(),
+                envelope.wrapped_key.as_slice(),
+            ),
+            None => (&[][..], &[][..]),
+        };
+        write_usize(writer, master_key_id.len())?;
+        writer.write(master_key_id)?;
+        write_usize(writer, wrapped_key.len())?;
+        writer.write(wrapped_key)?;
+
         Ok(())
     }
 }
@@ -155,11 +177,27 @@ impl<Context> Decode<Context> for MooncakeTableMetadata {
             puffin_files.push(puffin_file);
         }
 
+        let master_key_id_len = read_usize(&mut reader)?;
+        let mut master_key_id = vec![0u8; master_key_id_len];
+        reader.read(&mut master_key_id)?;
+        let wrapped_key_len = read_usize(&mut reader)?;
+        let mut wrapped_key = vec![0u8; wrapped_key_len];
+        reader.read(&mut wrapped_key)?;
+        let data_key_envelope = if wrapped_key.is_empty() {
+            None
+        } else {
+            Some(DataKeyEnvelope {
+                master_key_id: String::from_utf8(master_key_id).unwrap(),
+                wrapped_key,
+            })
+        };
+
         Ok(Self {
             data_files,
             puffin_files,
             deletion_vectors,
             position_deletes,
+            data_key_envelope,
         })
     }
 }
@@ -228,6 +266,26 @@ mod tests {
                 data_file_number: 2,
                 data_file_row_number: 2,
             }],
+            data_key_envelope: None,
+        };
+        let data = bincode::encode_to_vec(table_metadata.clone(), BINCODE_CONFIG).unwrap();
+
+        let decoded_metadata: (MooncakeTableMetadata, usize) =
+            bincode::decode_from_slice(&data, config::standard()).unwrap();
+        assert_eq!(table_metadata, decoded_metadata.0);
+    }
+
+    #[test]
+    fn test_table_metadata_serde_with_data_key_envelope() {
+        let table_metadata = MooncakeTableMetadata {
+            data_files: vec!["/tmp/iceberg_test/data/1.parquet".to_string()],
+            puffin_files: vec![],
+            deletion_vectors: vec![],
+            position_deletes: vec![],
+            data_key_envelope: Some(DataKeyEnvelope {
+                master_key_id: "key-1".to_string(),
+                wrapped_key: vec![7u8; 60],
+            }),
         };
         let data = bincode::encode_to_vec(table_metadata.clone(), BINCODE_CONFIG).unwrap();
 